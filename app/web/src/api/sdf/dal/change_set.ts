@@ -6,6 +6,7 @@ export enum ChangeSetStatus {
   Abandoned = "Abandoned",
   Applied = "Applied",
   Failed = "Failed",
+  NeedsApproval = "NeedsApproval",
 }
 
 export interface ChangeSet extends StandardModelNoVisibility {
@@ -13,6 +14,7 @@ export interface ChangeSet extends StandardModelNoVisibility {
   name: string;
   note?: string;
   status: ChangeSetStatus;
+  approval_requested_by?: string;
 }
 
 export type ChangeStatus = "added" | "deleted" | "modified" | "unmodified";
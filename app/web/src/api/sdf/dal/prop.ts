@@ -1,6 +1,7 @@
 export enum PropKind {
   Array = "array",
   Boolean = "boolean",
+  Float = "float",
   Integer = "integer",
   Object = "object",
   String = "string",
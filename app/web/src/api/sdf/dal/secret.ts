@@ -3,7 +3,8 @@ import { StandardModel } from "@/api/sdf/dal/standard_model";
 export interface Secret extends StandardModel {
   name: string;
   objectType: SecretObjectType;
-  kind: SecretKind;
+  // Either a built in kind or the name of a SecretDefinition
+  kind: SecretKind | string;
 }
 
 export enum SecretObjectType {
@@ -19,6 +20,7 @@ export enum SecretKind {
 
 export enum SecretVersion {
   V1 = "v1",
+  V2 = "v2",
 }
 
 export enum SecretAlgorithm {
@@ -30,3 +32,9 @@ export interface SecretKindFields {
   displayName: string;
   fields: { keyName: string; displayName: string; password: boolean }[];
 }
+
+export interface SecretDefinition extends StandardModel {
+  name: string;
+  description?: string;
+  jsonSchema: Record<string, unknown>;
+}
@@ -216,6 +216,14 @@ export function useChangeSetsStore() {
               }
             },
           },
+          {
+            eventType: "ChangeSetRebased",
+            callback: (cs) => {
+              // a rebase rewrites the change set underneath whoever is looking at it, so treat it
+              // like a write to get anything watching to refresh
+              this.changeSetsWrittenAtById[cs] = new Date();
+            },
+          },
           {
             eventType: "ChangeSetWritten",
             callback: (cs) => {
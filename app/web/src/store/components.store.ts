@@ -557,6 +557,32 @@ export const useComponentsStore = (forceChangeSetId?: ChangeSetId) => {
             },
           });
         },
+        async IMPORT_COMPONENT(
+          schemaId: string,
+          identifier: string,
+          position: Vector2d,
+          name?: string,
+        ) {
+          if (changeSetsStore.creatingChangeSet)
+            throw new Error("race, wait until the change set is created");
+          if (changeSetId === nilId()) changeSetsStore.creatingChangeSet = true;
+
+          return new ApiRequest<{
+            componentId: ComponentId;
+            nodeId: ComponentNodeId;
+          }>({
+            method: "post",
+            url: "diagram/import_component",
+            params: {
+              schemaId,
+              identifier,
+              name,
+              x: position.x.toString(),
+              y: position.y.toString(),
+              ...visibilityParams,
+            },
+          });
+        },
         async CREATE_COMPONENT_CONNECTION(
           from: { nodeId: ComponentNodeId; socketId: SocketId },
           to: { nodeId: ComponentNodeId; socketId: SocketId },
@@ -22,6 +22,7 @@ export type FixStatus =
 export enum ActionKind {
   Create = "create",
   Delete = "delete",
+  Import = "import",
   Other = "other",
   Refresh = "refresh",
 }
@@ -88,6 +89,23 @@ export type FixBatch = {
   finishedAt?: string;
 };
 
+export type ActionPlan = {
+  status: "ok" | "warning" | "error";
+  proposedChanges?: unknown;
+  message?: string;
+  logs: string[];
+};
+
+export type FixPlan = {
+  attributeValueId: AttributeValueId;
+  componentId: ComponentId;
+  componentName: string;
+  actionPrototypeId: ActionPrototypeId;
+  actionKind: ActionKind;
+  // empty when the action has no plan handler
+  plan?: ActionPlan | null;
+};
+
 export interface ConfirmationStats {
   failure: number;
   success: number;
@@ -111,6 +129,7 @@ export const useFixesStore = () => {
         fixBatches: [] as Array<FixBatch>,
         runningFixBatch: undefined as FixBatchId | undefined,
         populatingFixes: false,
+        fixPlans: [] as Array<FixPlan>,
         recommendationsSelection: {} as Record<
           string,
           { recommendation: Recommendation; selected: boolean }
@@ -283,6 +302,25 @@ export const useFixesStore = () => {
             },
           });
         },
+        async PLAN_FIXES_FROM_RECOMMENDATIONS(
+          recommendations: Array<Recommendation>,
+        ) {
+          return new ApiRequest<{ plans: Array<FixPlan> }>({
+            method: "post",
+            params: {
+              list: recommendations.map((r) => ({
+                attributeValueId: r.confirmationAttributeValueId,
+                componentId: r.componentId,
+                actionPrototypeId: r.actionPrototypeId,
+              })),
+              visibility_change_set_pk: nilId(),
+            },
+            url: "/fix/plan",
+            onSuccess: (response) => {
+              this.fixPlans = response.plans;
+            },
+          });
+        },
         async EXECUTE_FIXES_FROM_RECOMMENDATIONS(
           recommendations: Array<Recommendation>,
         ) {
@@ -298,6 +336,7 @@ export const useFixesStore = () => {
             },
             url: "/fix/run",
             onSuccess: (response) => {
+              this.fixPlans = [];
               this.LOAD_CONFIRMATIONS();
               this.LOAD_FIX_BATCHES();
             },
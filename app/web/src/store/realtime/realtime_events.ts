@@ -18,6 +18,13 @@ import {
 export type WsEventPayloadMap = {
   ChangeSetCreated: string;
   ChangeSetApplied: string;
+  ChangeSetApprovalRequested: string;
+  ChangeSetApprovalDecided: {
+    changeSetPk: string;
+    userPk: string;
+    decision: "Approved" | "Rejected";
+  };
+  ChangeSetRebased: string;
   ChangeSetWritten: string;
   ChangeSetCancelled: string;
 
@@ -35,6 +42,10 @@ export type WsEventPayloadMap = {
   ResourceRefreshed: {
     componentId: string;
   };
+  ComponentDriftUpdated: {
+    componentId: string;
+    drifted: boolean;
+  };
   // UpdatedDependentValue: {
   //   componentId: string;
   // }
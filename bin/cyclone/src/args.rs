@@ -1,7 +1,7 @@
 use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
 use clap::{ArgAction, Parser};
-use cyclone_server::{Config, ConfigError, IncomingStream};
+use cyclone_server::{Config, ConfigError, ExecutionLimits, IncomingStream};
 
 const NAME: &str = "cyclone";
 
@@ -100,6 +100,18 @@ pub(crate) struct Args {
     #[arg(long, group = "request_limiting")]
     pub(crate) limit_requests: Option<u32>,
 
+    /// Wall-clock seconds a function may run for, unless its request asks otherwise
+    #[arg(long)]
+    pub(crate) timeout_secs: Option<u64>,
+
+    /// Bytes of JavaScript heap a function may use, unless its request asks otherwise
+    #[arg(long)]
+    pub(crate) memory_limit_bytes: Option<u64>,
+
+    /// CPU seconds a function may consume, unless its request asks otherwise
+    #[arg(long)]
+    pub(crate) cpu_limit_secs: Option<u64>,
+
     /// Cyclone decryption key file location [example: /run/cyclone/cyclone.key]
     #[arg(long)]
     pub(crate) decryption_key: PathBuf,
@@ -144,6 +156,12 @@ impl TryFrom<Args> for Config {
             builder.limit_requests(limit_requests);
         }
 
+        builder.execution_limits(ExecutionLimits {
+            timeout_secs: args.timeout_secs,
+            memory_bytes: args.memory_limit_bytes,
+            cpu_secs: args.cpu_limit_secs,
+        });
+
         builder.build().map_err(Into::into)
     }
 }
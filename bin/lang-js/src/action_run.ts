@@ -26,6 +26,7 @@ export interface ActionRunResultSuccess extends ResultSuccess {
   payload: unknown;
   health: "ok" | "warning" | "error";
   message?: string;
+  domain?: Record<string, unknown>;
 }
 export type ActionRunResultFailure = ResultFailure;
 
@@ -122,6 +123,21 @@ async function execute(
       };
     }
 
+    if (
+      !_.isUndefined(actionRunResult["domain"]) &&
+      !_.isPlainObject(actionRunResult["domain"])
+    ) {
+      return {
+        protocol: "result",
+        status: "failure",
+        executionId,
+        error: {
+          kind: "ActionFieldWrongType",
+          message: "The domain field type must be an object when present",
+        },
+      };
+    }
+
     const result: ActionRunResultSuccess = {
       protocol: "result",
       status: "success",
@@ -130,6 +146,7 @@ async function execute(
       payload: actionRunResult.payload,
       health: actionRunResult.status as "ok" | "warning" | "error",
       message: actionRunResult.message as string | undefined,
+      domain: actionRunResult.domain as Record<string, unknown> | undefined,
     };
     return result;
   } catch (err) {
@@ -106,15 +106,32 @@ export class SocketDefinitionBuilder implements ISocketDefinitionBuilder {
 }
 
 export type ValidationKind =
+    | "arrayItemsAreUnique"
+    | "arrayLengthIsBetween"
     | "customValidation"
+    | "floatIsBetweenTwoFloats"
+    | "floatIsNotEmpty"
     | "integerIsBetweenTwoIntegers"
     | "integerIsNotEmpty"
     | "stringEquals"
     | "stringHasPrefix"
     | "stringInStringArray"
+    | "stringIsArn"
     | "stringIsHexColor"
+    | "stringIsHostname"
     | "stringIsNotEmpty"
-    | "stringIsValidIpAddr";
+    | "stringIsValidCidr"
+    | "stringIsValidIpAddr"
+    | "stringIsValidUrl"
+    | "stringLengthIsBetween"
+    | "stringMatchesRegex";
+
+const BOUNDED_VALIDATION_KINDS: ValidationKind[] = [
+    "arrayLengthIsBetween",
+    "floatIsBetweenTwoFloats",
+    "integerIsBetweenTwoIntegers",
+    "stringLengthIsBetween",
+];
 
 export interface Validation {
     kind: ValidationKind;
@@ -123,6 +140,7 @@ export interface Validation {
     upperBound?: number;
     expected?: string[];
     displayExpected?: boolean;
+    pattern?: string;
 }
 
 export interface IValidationBuilder {
@@ -138,6 +156,8 @@ export interface IValidationBuilder {
 
     setDisplayExpected(display: boolean): this;
 
+    setPattern(pattern: string): this;
+
     build(): Validation;
 }
 
@@ -192,7 +212,7 @@ export class ValidationBuilder implements IValidationBuilder {
     }
 
     setLowerBound(value: number): this {
-        if (this.validation.kind !== "integerIsBetweenTwoIntegers") {
+        if (!BOUNDED_VALIDATION_KINDS.includes(this.validation.kind)) {
             return this;
         }
         this.validation.lowerBound = value;
@@ -204,8 +224,16 @@ export class ValidationBuilder implements IValidationBuilder {
         return this;
     }
 
+    setPattern(pattern: string): this {
+        if (this.validation.kind !== "stringMatchesRegex") {
+            return this;
+        }
+        this.validation.pattern = pattern;
+        return this;
+    }
+
     setUpperBound(value: number): this {
-        if (this.validation.kind !== "integerIsBetweenTwoIntegers") {
+        if (!BOUNDED_VALIDATION_KINDS.includes(this.validation.kind)) {
             return this;
         }
         this.validation.upperBound = value;
@@ -355,6 +383,7 @@ export class SiPropValueFromDefinitionBuilder
 export type PropDefinitionKind =
     | "array"
     | "boolean"
+    | "float"
     | "integer"
     | "map"
     | "object"
@@ -24,6 +24,7 @@ export enum FuncBackendResponseType {
   Action = "Action",
   Array = "Array",
   Boolean = "Boolean",
+  Float = "Float",
   Identity = "Identity",
   Integer = "Integer",
   Map = "Map",
@@ -83,6 +84,11 @@ const isInteger = (value: unknown): TypeCheckResult =>
     ? { valid: true }
     : { valid: false, message: `Return type must be an integer.` };
 
+const isFloat = (value: unknown): TypeCheckResult =>
+  _.isFinite(value)
+    ? { valid: true }
+    : { valid: false, message: `Return type must be a number.` };
+
 // This check is not 100% valid because javascript does not distinguish
 // between objects, arrays, functions and null in typeof checks. This
 // could return true if the function returns another function.
@@ -158,6 +164,7 @@ const typeChecks: {
 } = {
   [FuncBackendResponseType.Array]: isArray,
   [FuncBackendResponseType.Boolean]: isBoolean,
+  [FuncBackendResponseType.Float]: isFloat,
   [FuncBackendResponseType.Integer]: isInteger,
   [FuncBackendResponseType.Object]: isObject,
   [FuncBackendResponseType.String]: isString,
@@ -170,6 +177,7 @@ const typeChecks: {
 const nullables: { [key in FuncBackendResponseType]?: boolean } = {
   [FuncBackendResponseType.Array]: true,
   [FuncBackendResponseType.Boolean]: true,
+  [FuncBackendResponseType.Float]: true,
   [FuncBackendResponseType.Integer]: true,
   [FuncBackendResponseType.Json]: true,
   [FuncBackendResponseType.Map]: true,
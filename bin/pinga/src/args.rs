@@ -57,12 +57,28 @@ pub(crate) struct Args {
     #[arg(long)]
     pub(crate) concurrency: Option<u32>,
 
+    /// The number of fixes in a batch that can run at once [default: 8]
+    #[arg(long)]
+    pub(crate) fix_concurrency: Option<u32>,
+
     /// Instance ID [example: 01GWEAANW5BVFK5KDRVS6DEY0F"]
     ///
     /// And instance ID is used when tracking the execution of jobs in a way that can be traced
     /// back to an instance of a Pinga service.
     #[arg(long)]
     pub(crate) instance_id: Option<String>,
+
+    /// Prints the jobs on the dead-letter stream as JSON lines and exits
+    #[arg(long, conflicts_with = "replay_dead_letters")]
+    pub(crate) list_dead_letters: bool,
+
+    /// Puts the jobs on the dead-letter stream back on the job queue and exits
+    #[arg(long)]
+    pub(crate) replay_dead_letters: bool,
+
+    /// Only list or replay dead-lettered jobs of this kind [example: FixesJob]
+    #[arg(long)]
+    pub(crate) dead_letter_kind: Option<String>,
 }
 
 impl TryFrom<Args> for Config {
@@ -94,6 +110,9 @@ impl TryFrom<Args> for Config {
             if let Some(concurrency) = args.concurrency {
                 config_map.set("concurrency_limit", i64::from(concurrency));
             }
+            if let Some(fix_concurrency) = args.fix_concurrency {
+                config_map.set("fix_concurrency_limit", i64::from(fix_concurrency));
+            }
             if let Some(instance_id) = args.instance_id {
                 config_map.set("instance_id", instance_id);
             }
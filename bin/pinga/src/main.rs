@@ -1,5 +1,5 @@
 use color_eyre::Result;
-use pinga_server::{Config, Server};
+use pinga_server::{Config, JobQueue, Server};
 use telemetry_application::{
     prelude::*, start_tracing_level_signal_handler_task, ApplicationTelemetryClient,
     TelemetryClient, TelemetryConfig,
@@ -47,8 +47,27 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
         telemetry.disable_opentelemetry().await?;
     }
 
+    let list_dead_letters = args.list_dead_letters;
+    let replay_dead_letters = args.replay_dead_letters;
+    let dead_letter_kind = args.dead_letter_kind.clone();
+
     let config = Config::try_from(args)?;
 
+    if list_dead_letters || replay_dead_letters {
+        let job_queue = JobQueue::connect(config.nats(), config.job_queue().clone()).await?;
+        if replay_dead_letters {
+            let replayed = job_queue
+                .replay_dead_letters(dead_letter_kind.as_deref())
+                .await?;
+            info!(replayed, "replayed dead-lettered jobs");
+        } else {
+            for dead_letter in job_queue.dead_letters(dead_letter_kind.as_deref()).await? {
+                println!("{}", serde_json::to_string(&dead_letter)?);
+            }
+        }
+        return Ok(());
+    }
+
     start_tracing_level_signal_handler_task(&telemetry)?;
 
     Server::from_config(config).await?.run().await?;
@@ -116,6 +116,11 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
 
     let posthog_client = Server::start_posthog(config.posthog()).await?;
 
+    let resource_scheduler_config = config.resource_scheduler().clone();
+    let audit_export_config = config.audit_export().clone();
+    let audit_export_job_processor = job_processor.clone();
+    let webhook_dispatcher_job_processor = job_processor.clone();
+
     match config.incoming_stream() {
         IncomingStream::HTTPSocket(_) => {
             let (server, initial_shutdown_broadcast_rx) = Server::http(
@@ -131,6 +136,8 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
                 module_index_url,
             )?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let fourth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
 
             Server::start_resource_refresh_scheduler(
                 pg_pool.clone(),
@@ -138,10 +145,32 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
                 resource_job_processor,
                 veritech.clone(),
                 encryption_key,
+                resource_scheduler_config,
                 initial_shutdown_broadcast_rx,
             )
             .await;
 
+            Server::start_audit_exporter(
+                pg_pool.clone(),
+                nats.clone(),
+                audit_export_job_processor,
+                veritech.clone(),
+                encryption_key,
+                audit_export_config,
+                third_shutdown_broadcast_rx,
+            )
+            .await;
+
+            Server::start_webhook_dispatcher(
+                pg_pool.clone(),
+                nats.clone(),
+                webhook_dispatcher_job_processor,
+                veritech.clone(),
+                encryption_key,
+                fourth_shutdown_broadcast_rx,
+            )
+            .await?;
+
             Server::start_status_updater(
                 pg_pool,
                 nats,
@@ -169,6 +198,8 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
             )
             .await?;
             let second_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let third_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
+            let fourth_shutdown_broadcast_rx = initial_shutdown_broadcast_rx.resubscribe();
 
             Server::start_resource_refresh_scheduler(
                 pg_pool.clone(),
@@ -176,10 +207,32 @@ async fn run(args: args::Args, mut telemetry: ApplicationTelemetryClient) -> Res
                 resource_job_processor,
                 veritech.clone(),
                 encryption_key,
+                resource_scheduler_config,
                 initial_shutdown_broadcast_rx,
             )
             .await;
 
+            Server::start_audit_exporter(
+                pg_pool.clone(),
+                nats.clone(),
+                audit_export_job_processor,
+                veritech.clone(),
+                encryption_key,
+                audit_export_config,
+                third_shutdown_broadcast_rx,
+            )
+            .await;
+
+            Server::start_webhook_dispatcher(
+                pg_pool.clone(),
+                nats.clone(),
+                webhook_dispatcher_job_processor,
+                veritech.clone(),
+                encryption_key,
+                fourth_shutdown_broadcast_rx,
+            )
+            .await?;
+
             Server::start_status_updater(
                 pg_pool,
                 nats,
@@ -3,7 +3,7 @@ use si_data_nats::{NatsClient, Subscription};
 use std::time::Duration;
 use telemetry::prelude::*;
 
-use crate::{Graph, Id, Request, Response};
+use crate::{Graph, Id, Request, Response, ValueCreationStatus};
 
 #[remain::sorted]
 #[derive(Debug)]
@@ -82,6 +82,23 @@ impl PubClient {
         Ok(())
     }
 
+    /// Asks council for the status of this job's outstanding work, which arrives as a
+    /// [`Response::Status`].
+    pub async fn request_status(&self) -> Result<()> {
+        let message = serde_json::to_vec(&Request::Status {
+            change_set_id: self.change_set_id,
+        })?;
+        self.nats
+            .publish_with_reply_or_headers(
+                &self.pub_channel,
+                Some(&self.reply_channel),
+                None,
+                message,
+            )
+            .await?;
+        Ok(())
+    }
+
     pub async fn bye(self) -> Result<()> {
         let message = serde_json::to_vec(&Request::Bye {
             change_set_id: self.change_set_id,
@@ -135,6 +152,9 @@ impl Client {
     }
 
     // None means subscription has been unsubscribed or that the connection has been closed
+    //
+    // If council stays quiet for a while we ask it for our status, in case it restarted and lost
+    // some of our requests, so callers must be ready to receive a `Response::Status`.
     pub async fn fetch_response(&mut self) -> Result<Option<Response>> {
         // TODO: timeout so we don't get stuck here forever if council goes away
         // TODO: handle message.data() empty with Status header as 503: https://github.com/nats-io/nats.go/pull/576
@@ -146,6 +166,7 @@ impl Client {
                 Ok(msg) => break msg?,
                 Err(_) => {
                     warn!(change_set_id = ?self.change_set_id, pub_channel = ?self.pub_channel, reply_channel = ?self.reply_channel, "Council client waiting for response for 60 seconds");
+                    self.request_status().await?;
                 }
             }
         };
@@ -172,10 +193,32 @@ impl Client {
             )
             .await?;
 
-        match self.fetch_response().await? {
-            Some(Response::OkToCreate) => Ok(State::Continue),
-            Some(Response::Shutdown) => Ok(State::Shutdown),
-            resp => unreachable!("{:?}", resp),
+        loop {
+            match self.fetch_response().await? {
+                Some(Response::OkToCreate) => return Ok(State::Continue),
+                // Council let us go ahead, but its response went missing
+                Some(Response::Status {
+                    value_creation: ValueCreationStatus::Creating,
+                    ..
+                }) => return Ok(State::Continue),
+                Some(Response::Shutdown) => return Ok(State::Shutdown),
+                // Council no longer knows we're waiting, so ask again
+                Some(Response::Status {
+                    value_creation: ValueCreationStatus::NotQueued,
+                    ..
+                }) => {
+                    self.nats
+                        .publish_with_reply_or_headers(
+                            &self.pub_channel,
+                            Some(&self.reply_channel),
+                            None,
+                            serde_json::to_vec(&Request::CreateValues)?,
+                        )
+                        .await?;
+                }
+                Some(Response::Status { .. }) => {}
+                resp => unreachable!("{:?}", resp),
+            }
         }
     }
 
@@ -193,6 +236,10 @@ impl Client {
         self.clone_into_pub().processed_value(node_id).await
     }
 
+    pub async fn request_status(&self) -> Result<()> {
+        self.clone_into_pub().request_status().await
+    }
+
     pub async fn bye(&self) -> Result<()> {
         self.clone_into_pub().bye().await
     }
@@ -64,6 +64,11 @@ pub enum Request {
         change_set_id: Id,
         node_id: Id,
     },
+    /// Asks for the [`Response::Status`] of the requesting job's outstanding work, so a job can
+    /// find out what council still knows about after either of them reconnects.
+    Status {
+        change_set_id: Id,
+    },
     ValueCreationDone,
     ValueDependencyGraph {
         change_set_id: Id,
@@ -79,9 +84,43 @@ pub enum Request {
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "kind")]
 pub enum Response {
-    BeenProcessed { node_id: Id },
-    Failed { node_id: Id },
+    BeenProcessed {
+        node_id: Id,
+    },
+    Failed {
+        node_id: Id,
+    },
     OkToCreate,
-    OkToProcess { node_ids: Vec<Id> },
+    OkToProcess {
+        node_ids: Vec<Id>,
+    },
     Shutdown,
+    Status {
+        value_creation: ValueCreationStatus,
+        /// Every node the job is processing or waiting to process. Nodes the job registered but
+        /// which are missing here are unknown to council and need to be registered again.
+        nodes: HashMap<Id, NodeStatus>,
+    },
+}
+
+/// Where a job stands in the queue of jobs waiting to create values.
+#[remain::sorted]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueCreationStatus {
+    /// The job has been told it may create values and has not yet said it's done.
+    Creating,
+    /// The job is not waiting to create values, nor creating them.
+    NotQueued,
+    /// The job is waiting for its turn to create values.
+    Queued,
+}
+
+/// Where one of a job's nodes stands in the dependency graph.
+#[remain::sorted]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeStatus {
+    /// The job has been told it may process the node.
+    Processing,
+    /// The job wants to process the node once its dependencies have been processed.
+    Waiting,
 }
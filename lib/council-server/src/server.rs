@@ -8,20 +8,23 @@ use tokio::{signal, sync::watch};
 
 pub mod config;
 mod graph;
+mod store;
 pub use config::Config;
 
 use graph::{ChangeSetGraph, ValueCreationQueue};
+use store::GraphStore;
 
 #[derive(Debug, Clone)]
 pub struct Server {
     nats: NatsClient,
+    graph_store: GraphStore,
 }
 
 impl Server {
     pub async fn new_with_config(config: config::Config) -> Result<Self> {
-        Ok(Self {
-            nats: NatsClient::new(config.nats()).await?,
-        })
+        let nats = NatsClient::new(config.nats()).await?;
+        let graph_store = GraphStore::new(&nats, config.graph_store()).await?;
+        Ok(Self { nats, graph_store })
     }
 
     pub async fn run(
@@ -68,10 +71,23 @@ impl Server {
             }
         });
 
-        let mut value_create_queue = ValueCreationQueue::default();
-        let mut complete_graph = ChangeSetGraph::default();
+        // Pick up where a previous instance left off, so jobs waiting on it don't hang forever
+        let (mut complete_graph, mut value_create_queue) = self.graph_store.load().await?;
         loop {
-            if let Some(reply_channel) = value_create_queue.fetch_next() {
+            let next_value_creator = value_create_queue.fetch_next();
+            let available_nodes = complete_graph.fetch_all_available();
+
+            // Persist before telling jobs to go ahead, so a restart can't forget what they were
+            // told
+            if let Err(err) = self
+                .graph_store
+                .persist(&mut complete_graph, &mut value_create_queue)
+                .await
+            {
+                error!("Unable to persist council graph state: {err}");
+            }
+
+            if let Some(reply_channel) = next_value_creator {
                 info!(%reply_channel, "OK to create AttributeValues");
                 self.nats
                     .publish(
@@ -82,7 +98,7 @@ impl Server {
                     .unwrap();
             }
 
-            for (reply_channel, node_id) in complete_graph.fetch_all_available() {
+            for (reply_channel, node_id) in available_nodes {
                 info!(%reply_channel, %node_id, "Ok to process AttributeValue");
                 self.nats
                     .publish(
@@ -205,6 +221,17 @@ impl Server {
                     .await
                     .unwrap();
                 }
+                Request::Status { change_set_id } => {
+                    job_requested_status(
+                        &self.nats,
+                        &complete_graph,
+                        &value_create_queue,
+                        reply_channel,
+                        change_set_id,
+                    )
+                    .await
+                    .unwrap();
+                }
             };
         }
 
@@ -246,6 +273,8 @@ pub enum Error {
     Io(#[from] std::io::Error),
     #[error(transparent)]
     Nats(#[from] si_data_nats::Error),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
     #[error("Job reported finishing processing, but we expected a different job to be processing")]
     ShouldNotBeProcessingByJob,
     #[error("Unexpected JobId")]
@@ -339,6 +368,25 @@ pub async fn job_failed_processing_a_value(
     Ok(())
 }
 
+#[instrument(level = "info", skip(nats, complete_graph, value_create_queue))]
+pub async fn job_requested_status(
+    nats: &NatsClient,
+    complete_graph: &ChangeSetGraph,
+    value_create_queue: &ValueCreationQueue,
+    reply_channel: String,
+    change_set_id: Id,
+) -> Result<(), Error> {
+    let response = Response::Status {
+        value_creation: value_create_queue.status(&reply_channel),
+        nodes: complete_graph.node_statuses(change_set_id, &reply_channel),
+    };
+    debug!(%reply_channel, %change_set_id, ?response, "Job requested status");
+    nats.publish(reply_channel, serde_json::to_vec(&response)?)
+        .await?;
+
+    Ok(())
+}
+
 #[instrument(level = "info")]
 pub async fn job_is_going_away(
     complete_graph: &mut ChangeSetGraph,
@@ -18,6 +18,9 @@ pub type Result<T, E = ConfigError> = std::result::Result<T, E>;
 pub struct Config {
     #[builder(default = "NatsConfig::default()")]
     nats: NatsConfig,
+
+    #[builder(default)]
+    graph_store: GraphStoreConfig,
 }
 
 impl StandardConfig for Config {
@@ -27,6 +30,27 @@ impl StandardConfig for Config {
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct ConfigFile {
     nats: NatsConfig,
+    #[serde(default)]
+    graph_store: GraphStoreConfig,
+}
+
+/// Configures the key-value bucket council persists its graph state to.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GraphStoreConfig {
+    /// The name of the bucket.
+    pub bucket: String,
+    /// Whether the bucket is stored in memory rather than on disk.
+    pub in_memory: bool,
+}
+
+impl Default for GraphStoreConfig {
+    fn default() -> Self {
+        Self {
+            bucket: "COUNCIL_GRAPH".to_string(),
+            in_memory: false,
+        }
+    }
 }
 
 impl StandardConfigFile for ConfigFile {
@@ -39,6 +63,7 @@ impl TryFrom<ConfigFile> for Config {
     fn try_from(value: ConfigFile) -> Result<Self> {
         let mut config = Config::builder();
         config.nats(value.nats);
+        config.graph_store(value.graph_store);
         config.build().map_err(Into::into)
     }
 }
@@ -50,6 +75,11 @@ impl Config {
         &self.nats
     }
 
+    /// Gets a reference to the config's graph store.
+    pub fn graph_store(&self) -> &GraphStoreConfig {
+        &self.graph_store
+    }
+
     /// Gets a reference to the config's subject prefix.
     pub fn subject_prefix(&self) -> Option<&str> {
         self.nats.subject_prefix.as_deref()
@@ -1,19 +1,27 @@
-use crate::{server::Error, Graph, Id};
+use crate::{server::Error, Graph, Id, NodeStatus, ValueCreationStatus};
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 
 mod node_metadata;
 
-use node_metadata::NodeMetadata;
+pub use node_metadata::NodeMetadata;
 
-#[derive(Default, Debug)]
+/// The nodes of a single change set's dependency graph.
+pub type ChangeSetGraphData = HashMap<Id, NodeMetadata>;
+
+#[derive(Default, Debug, Serialize, Deserialize)]
 pub struct ValueCreationQueue {
     processing: Option<String>,
     queue: VecDeque<String>,
+    /// Whether the queue changed since it was last persisted.
+    #[serde(skip)]
+    dirty: bool,
 }
 
 impl ValueCreationQueue {
     pub fn push(&mut self, reply_channel: String) {
         self.queue.push_back(reply_channel);
+        self.dirty = true;
     }
 
     pub fn is_busy(&self) -> bool {
@@ -26,6 +34,7 @@ impl ValueCreationQueue {
         }
         let next_channel = self.queue.pop_front();
         self.processing = next_channel.clone();
+        self.dirty |= next_channel.is_some();
 
         next_channel
     }
@@ -36,6 +45,7 @@ impl ValueCreationQueue {
         }
 
         self.processing = None;
+        self.dirty = true;
 
         Ok(())
     }
@@ -43,12 +53,35 @@ impl ValueCreationQueue {
     pub fn remove(&mut self, reply_channel: &str) {
         self.processing = self.processing.take().filter(|el| *el != reply_channel);
         self.queue.retain(|el| reply_channel != el);
+        self.dirty = true;
+    }
+
+    pub fn status(&self, reply_channel: &str) -> ValueCreationStatus {
+        if self.processing.as_deref() == Some(reply_channel) {
+            ValueCreationStatus::Creating
+        } else if self.queue.iter().any(|el| el == reply_channel) {
+            ValueCreationStatus::Queued
+        } else {
+            ValueCreationStatus::NotQueued
+        }
+    }
+
+    /// Returns whether the queue changed since this was last called.
+    pub fn take_dirty(&mut self) -> bool {
+        std::mem::take(&mut self.dirty)
+    }
+
+    /// Flags the queue as changed again, for when persisting it failed.
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
     }
 }
 
 #[derive(Default, Debug)]
 pub struct ChangeSetGraph {
-    dependency_data: HashMap<Id, HashMap<Id, NodeMetadata>>,
+    dependency_data: HashMap<Id, ChangeSetGraphData>,
+    /// The change sets whose graphs changed since they were last persisted.
+    dirty_change_set_ids: HashSet<Id>,
 }
 
 impl ChangeSetGraph {
@@ -58,9 +91,10 @@ impl ChangeSetGraph {
 
     pub fn fetch_all_available(&mut self) -> Vec<(String, Id)> {
         let mut result = Vec::new();
-        for graph in self.dependency_data.values_mut() {
+        for (change_set_id, graph) in self.dependency_data.iter_mut() {
             for (id, metadata) in graph.iter_mut() {
                 if let Some(reply_channel) = metadata.next_to_process() {
+                    self.dirty_change_set_ids.insert(*change_set_id);
                     result.push((reply_channel, *id));
                 }
             }
@@ -68,12 +102,52 @@ impl ChangeSetGraph {
         result
     }
 
+    /// Gets the graph for a change set, if it has one.
+    pub fn change_set(&self, change_set_id: Id) -> Option<&ChangeSetGraphData> {
+        self.dependency_data.get(&change_set_id)
+    }
+
+    /// Restores the graph for a change set, as previously retrieved with
+    /// [`change_set`](Self::change_set()).
+    pub fn restore_change_set(&mut self, change_set_id: Id, graph: ChangeSetGraphData) {
+        if !graph.is_empty() {
+            self.dependency_data.insert(change_set_id, graph);
+        }
+    }
+
+    /// Returns the change sets whose graphs changed since this was last called.
+    pub fn take_dirty_change_set_ids(&mut self) -> HashSet<Id> {
+        std::mem::take(&mut self.dirty_change_set_ids)
+    }
+
+    /// Flags a change set's graph as changed again, for when persisting it failed.
+    pub fn mark_change_set_dirty(&mut self, change_set_id: Id) {
+        self.dirty_change_set_ids.insert(change_set_id);
+    }
+
+    /// Gets the status of every node in a change set's graph which the job is processing or
+    /// waiting to process.
+    pub fn node_statuses(&self, change_set_id: Id, reply_channel: &str) -> HashMap<Id, NodeStatus> {
+        let mut statuses = HashMap::new();
+        if let Some(graph) = self.dependency_data.get(&change_set_id) {
+            for (id, metadata) in graph {
+                if metadata.processing_reply_channel().map(String::as_str) == Some(reply_channel) {
+                    statuses.insert(*id, NodeStatus::Processing);
+                } else if metadata.is_wanted_by(reply_channel) {
+                    statuses.insert(*id, NodeStatus::Waiting);
+                }
+            }
+        }
+        statuses
+    }
+
     pub fn merge_dependency_graph(
         &mut self,
         reply_channel: String,
         new_dependency_data: Graph,
         change_set_id: Id,
     ) -> Result<(), Error> {
+        self.dirty_change_set_ids.insert(change_set_id);
         let change_set_graph_data = self.dependency_data.entry(change_set_id).or_default();
 
         for (attribute_value_id, dependencies) in new_dependency_data {
@@ -113,6 +187,7 @@ impl ChangeSetGraph {
         change_set_id: Id,
         node_id: Id,
     ) -> Result<HashSet<String>, Error> {
+        self.dirty_change_set_ids.insert(change_set_id);
         let change_set_graph_data = self.dependency_data.get_mut(&change_set_id).unwrap();
 
         let (ok_to_remove_node, wanted_by_reply_channels) =
@@ -139,6 +214,7 @@ impl ChangeSetGraph {
 
     pub fn remove_channel(&mut self, change_set_id: Id, reply_channel: &str) {
         if let Some(graph) = self.dependency_data.get_mut(&change_set_id) {
+            self.dirty_change_set_ids.insert(change_set_id);
             let mut to_remove = Vec::new();
             for (id, metadata) in graph.iter_mut() {
                 metadata.remove_channel(reply_channel);
@@ -164,6 +240,7 @@ impl ChangeSetGraph {
         node_id: Id,
     ) -> Result<Vec<(String, Id)>, Error> {
         let mut failure_notifications = Vec::new();
+        self.dirty_change_set_ids.insert(change_set_id);
         let change_set_graph_data = self.dependency_data.get_mut(&change_set_id).unwrap();
 
         let mut node_ids_to_fail = VecDeque::new();
@@ -1,11 +1,14 @@
 use std::{
     collections::{vec_deque::Iter, HashSet, VecDeque},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::{server::Error, Id};
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "PersistedNodeMetadata", into = "PersistedNodeMetadata")]
 pub struct NodeMetadata {
     // This should really be an ordered set, to remove duplicates, but we'll deal with
     // that later.
@@ -28,6 +31,51 @@ impl Default for NodeMetadata {
     }
 }
 
+/// The form [`NodeMetadata`] is persisted in. An [`Instant`] only means something within the
+/// process that created it, so only whether the processing job is working from stale information
+/// is kept.
+#[derive(Serialize, Deserialize)]
+struct PersistedNodeMetadata {
+    wanted_by_reply_channels: VecDeque<String>,
+    processing_reply_channel: Option<String>,
+    depends_on_node_ids: HashSet<Id>,
+    processing_is_stale: bool,
+}
+
+impl From<NodeMetadata> for PersistedNodeMetadata {
+    fn from(metadata: NodeMetadata) -> Self {
+        Self {
+            processing_is_stale: metadata.is_processing_stale(),
+            wanted_by_reply_channels: metadata.wanted_by_reply_channels,
+            processing_reply_channel: metadata.processing_reply_channel,
+            depends_on_node_ids: metadata.depends_on_node_ids,
+        }
+    }
+}
+
+impl From<PersistedNodeMetadata> for NodeMetadata {
+    fn from(persisted: PersistedNodeMetadata) -> Self {
+        let last_updated_at = Instant::now();
+        let processing_started_at = persisted.processing_reply_channel.as_ref().map(|_| {
+            if persisted.processing_is_stale {
+                last_updated_at
+                    .checked_sub(Duration::from_nanos(1))
+                    .unwrap_or(last_updated_at)
+            } else {
+                last_updated_at
+            }
+        });
+
+        Self {
+            wanted_by_reply_channels: persisted.wanted_by_reply_channels,
+            processing_reply_channel: persisted.processing_reply_channel,
+            depends_on_node_ids: persisted.depends_on_node_ids,
+            processing_started_at,
+            last_updated_at,
+        }
+    }
+}
+
 impl NodeMetadata {
     pub fn add_wanted_by_reply_channel(&mut self, reply_channel: &str) {
         self.wanted_by_reply_channels
@@ -47,6 +95,12 @@ impl NodeMetadata {
         self.wanted_by_reply_channels.is_empty() && self.processing_reply_channel.is_none()
     }
 
+    pub fn is_wanted_by(&self, reply_channel: &str) -> bool {
+        self.wanted_by_reply_channels
+            .iter()
+            .any(|el| el == reply_channel)
+    }
+
     pub fn is_processing_stale(&self) -> bool {
         if let Some(processing_started_at) = self.processing_started_at {
             // If we've been updated more recently than when we last set the reply channel
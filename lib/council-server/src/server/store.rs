@@ -0,0 +1,271 @@
+//! Persists council's graph state to a [JetStream](https://docs.nats.io/nats-concepts/jetstream)
+//! key-value bucket so that it can be recovered when council restarts.
+//!
+//! Each change set's graph is kept under its own key, next to a single key for the
+//! [`ValueCreationQueue`]. Only the parts of the state which changed since they were last
+//! persisted are written.
+
+use si_data_nats::{
+    jetstream::{KeyValue, KeyValueConfig, StorageType},
+    NatsClient,
+};
+use telemetry::prelude::*;
+
+use super::{
+    config::GraphStoreConfig,
+    graph::{ChangeSetGraph, ChangeSetGraphData, ValueCreationQueue},
+    Result,
+};
+use crate::Id;
+
+const VALUE_CREATION_QUEUE_KEY: &str = "value-creation-queue";
+const CHANGE_SET_KEY_PREFIX: &str = "change-set.";
+
+#[derive(Debug, Clone)]
+pub struct GraphStore {
+    kv: KeyValue,
+}
+
+impl GraphStore {
+    pub async fn new(nats: &NatsClient, config: &GraphStoreConfig) -> Result<Self> {
+        // Bucket names are shared by the whole NATS server, so they're prefixed like subjects are
+        let bucket = match nats.metadata().subject_prefix() {
+            Some(prefix) => format!("{}_{}", prefix.replace('.', "_"), config.bucket),
+            None => config.bucket.clone(),
+        };
+        let kv = nats
+            .jetstream()
+            .create_key_value(KeyValueConfig {
+                bucket,
+                history: 1,
+                storage: if config.in_memory {
+                    StorageType::Memory
+                } else {
+                    StorageType::File
+                },
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(Self { kv })
+    }
+
+    /// Loads the graph state persisted by a previous council instance.
+    #[instrument(level = "info", skip_all)]
+    pub async fn load(&self) -> Result<(ChangeSetGraph, ValueCreationQueue)> {
+        let mut complete_graph = ChangeSetGraph::default();
+        let mut value_create_queue = ValueCreationQueue::default();
+
+        for key in self.kv.keys().await? {
+            let value = match self.kv.get(&key).await? {
+                Some(value) => value,
+                None => continue,
+            };
+
+            if key == VALUE_CREATION_QUEUE_KEY {
+                value_create_queue = serde_json::from_slice(&value)?;
+            } else if let Some(change_set_id) = key
+                .strip_prefix(CHANGE_SET_KEY_PREFIX)
+                .and_then(|id| Id::from_string(id).ok())
+            {
+                let graph: ChangeSetGraphData = serde_json::from_slice(&value)?;
+                complete_graph.restore_change_set(change_set_id, graph);
+            } else {
+                warn!(%key, "Ignoring unknown key in council graph store");
+            }
+        }
+
+        info!(
+            ?complete_graph,
+            ?value_create_queue,
+            "Recovered council graph state"
+        );
+        Ok((complete_graph, value_create_queue))
+    }
+
+    /// Writes the parts of the graph state which changed since they were last persisted.
+    ///
+    /// Anything which fails to be written stays marked as changed, so that it's written again the
+    /// next time around. The first error is returned once everything else has been attempted.
+    pub async fn persist(
+        &self,
+        complete_graph: &mut ChangeSetGraph,
+        value_create_queue: &mut ValueCreationQueue,
+    ) -> Result<()> {
+        let mut first_error = None;
+
+        if value_create_queue.take_dirty() {
+            if let Err(err) = self.persist_value_creation_queue(value_create_queue).await {
+                value_create_queue.mark_dirty();
+                first_error.get_or_insert(err);
+            }
+        }
+
+        for change_set_id in complete_graph.take_dirty_change_set_ids() {
+            if let Err(err) = self.persist_change_set(complete_graph, change_set_id).await {
+                complete_graph.mark_change_set_dirty(change_set_id);
+                first_error.get_or_insert(err);
+            }
+        }
+
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    async fn persist_value_creation_queue(
+        &self,
+        value_create_queue: &ValueCreationQueue,
+    ) -> Result<()> {
+        self.kv
+            .put(
+                VALUE_CREATION_QUEUE_KEY,
+                serde_json::to_vec(value_create_queue)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn persist_change_set(
+        &self,
+        complete_graph: &ChangeSetGraph,
+        change_set_id: Id,
+    ) -> Result<()> {
+        let key = format!("{CHANGE_SET_KEY_PREFIX}{change_set_id}");
+        match complete_graph
+            .change_set(change_set_id)
+            .filter(|graph| !graph.is_empty())
+        {
+            Some(graph) => {
+                self.kv.put(key, serde_json::to_vec(graph)?).await?;
+            }
+            None => self.kv.delete(key).await?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use si_data_nats::NatsConfig;
+    use ulid::Ulid;
+
+    use super::*;
+    use crate::ValueCreationStatus;
+
+    const ENV_VAR_NATS_URL: &str = "SI_TEST_NATS_URL";
+
+    /// Connects to NATS with a subject prefix of its own, and so buckets of its own.
+    #[allow(clippy::disallowed_methods)] // Environment variables are used exclusively in test and
+                                         // all are prefixed with `SI_TEST_`
+    async fn nats() -> NatsClient {
+        NatsClient::new(&NatsConfig {
+            url: std::env::var(ENV_VAR_NATS_URL).unwrap_or_else(|_| "localhost".to_string()),
+            subject_prefix: Some(format!("council_test_{}", Ulid::new())),
+        })
+        .await
+        .expect("failed to connect to nats")
+    }
+
+    fn config() -> GraphStoreConfig {
+        GraphStoreConfig {
+            in_memory: true,
+            ..Default::default()
+        }
+    }
+
+    fn dirty_state(change_set_id: Id) -> (ChangeSetGraph, ValueCreationQueue) {
+        let mut complete_graph = ChangeSetGraph::default();
+        complete_graph
+            .merge_dependency_graph(
+                "job".to_string(),
+                HashMap::from([(Id::default(), vec![Id::default()])]),
+                change_set_id,
+            )
+            .expect("failed to merge dependency graph");
+        let mut value_create_queue = ValueCreationQueue::default();
+        value_create_queue.push("creator".to_string());
+        (complete_graph, value_create_queue)
+    }
+
+    #[tokio::test]
+    async fn persisted_state_is_restored() {
+        let nats = nats().await;
+        let store = GraphStore::new(&nats, &config())
+            .await
+            .expect("failed to create graph store");
+        let change_set_id = Id::default();
+        let (mut complete_graph, mut value_create_queue) = dirty_state(change_set_id);
+
+        store
+            .persist(&mut complete_graph, &mut value_create_queue)
+            .await
+            .expect("failed to persist");
+        assert!(!value_create_queue.take_dirty());
+        assert!(complete_graph.take_dirty_change_set_ids().is_empty());
+
+        let (restored_graph, restored_queue) = store.load().await.expect("failed to load");
+        let nodes = restored_graph
+            .change_set(change_set_id)
+            .expect("change set graph was not restored");
+        assert_eq!(
+            complete_graph
+                .change_set(change_set_id)
+                .expect("change set graph is gone")
+                .keys()
+                .collect::<HashSet<_>>(),
+            nodes.keys().collect(),
+        );
+        assert_eq!(
+            ValueCreationStatus::Queued,
+            restored_queue.status("creator")
+        );
+
+        // Change sets whose graphs emptied out are removed from the store
+        complete_graph.remove_channel(change_set_id, "job");
+        store
+            .persist(&mut complete_graph, &mut value_create_queue)
+            .await
+            .expect("failed to persist");
+        let (restored_graph, _) = store.load().await.expect("failed to load");
+        assert!(restored_graph.change_set(change_set_id).is_none());
+    }
+
+    #[tokio::test]
+    async fn failed_writes_stay_dirty() {
+        let nats = nats().await;
+        // Nothing fits in a bucket with values this small, so every write fails
+        let kv = nats
+            .jetstream()
+            .create_key_value(KeyValueConfig {
+                bucket: format!(
+                    "{}_TINY",
+                    nats.metadata()
+                        .subject_prefix()
+                        .expect("test connection has a subject prefix")
+                ),
+                max_value_size: 1,
+                storage: StorageType::Memory,
+                ..Default::default()
+            })
+            .await
+            .expect("failed to create bucket");
+        let store = GraphStore { kv };
+        let change_set_id = Id::default();
+        let (mut complete_graph, mut value_create_queue) = dirty_state(change_set_id);
+
+        store
+            .persist(&mut complete_graph, &mut value_create_queue)
+            .await
+            .expect_err("writes should fail");
+
+        assert!(value_create_queue.take_dirty());
+        assert_eq!(
+            HashSet::from([change_set_id]),
+            complete_graph.take_dirty_change_set_ids()
+        );
+    }
+}
@@ -452,8 +452,8 @@ mod tests {
     use base64::{engine::general_purpose, Engine};
     use buck2_resources::Buck2Resources;
     use cyclone_core::{
-        ComponentKind, ComponentView, FunctionResult, ProgressMessage, ResolverFunctionComponent,
-        ValidationRequest,
+        ComponentKind, ComponentView, ExecutionLimits, FunctionResult, FunctionResultFailureError,
+        ProgressMessage, ResolverFunctionComponent, ValidationRequest,
     };
     use cyclone_server::{Config, ConfigBuilder, DecryptionKey, Server, UdsIncomingStream};
     use futures::StreamExt;
@@ -788,6 +788,7 @@ mod tests {
                     return v;
                 }"#,
             ),
+            limits: None,
         };
 
         // Start the protocol
@@ -878,6 +879,7 @@ mod tests {
                     return v;
                 }"#,
             ),
+            limits: None,
         };
 
         // Start the protocol
@@ -951,6 +953,7 @@ mod tests {
                     }
                 }"#,
             ),
+            limits: None,
         };
         let mut progress = client
             .execute_validation(req)
@@ -1048,6 +1051,7 @@ mod tests {
                     return { status: 'ok' };
                 }"#,
             ),
+            limits: None,
         };
 
         // Start the protocol
@@ -1123,6 +1127,7 @@ mod tests {
                     return { status: 'ok' };
                 }"#,
             ),
+            limits: None,
         };
 
         // Start the protocol
@@ -1179,6 +1184,154 @@ mod tests {
         }
     }
 
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test)]
+    async fn uds_execute_action_run_cancel() {
+        let (_, key) = gen_keys();
+        let tmp_socket = rand_uds();
+        let mut builder = Config::builder();
+        let mut client =
+            uds_client_for_running_server(builder.enable_action_run(true), &tmp_socket, key).await;
+
+        let req = ActionRunRequest {
+            execution_id: "1234".to_string(),
+            handler: "workit".to_string(),
+            args: Default::default(),
+            code_base64: base64_encode(
+                r#"function workit() {
+                    while (true) {}
+                }"#,
+            ),
+            limits: None,
+        };
+
+        // Start the protocol
+        let mut progress = client
+            .execute_action_run(req)
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        progress.cancel().await.expect("failed to cancel execution");
+
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(unexpected) => panic!("output stream should be done: {unexpected:?}"),
+            };
+        }
+        // Get the result
+        let result = progress.finish().await.expect("failed to return result");
+        match result {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => {
+                assert_eq!(
+                    FunctionResultFailureError::KIND_CANCELLED,
+                    failure.error.kind
+                );
+            }
+        }
+    }
+
+    async fn execute_action_run_to_failure<C, Strm>(
+        mut client: C,
+        code: &str,
+        limits: ExecutionLimits,
+    ) -> FunctionResultFailureError
+    where
+        C: CycloneClient<Strm>,
+        Strm: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static,
+    {
+        let req = ActionRunRequest {
+            execution_id: "1234".to_string(),
+            handler: "workit".to_string(),
+            args: Default::default(),
+            code_base64: base64_encode(code),
+            limits: Some(limits),
+        };
+
+        // Start the protocol
+        let mut progress = client
+            .execute_action_run(req)
+            .await
+            .expect("failed to establish websocket stream")
+            .start()
+            .await
+            .expect("failed to start protocol");
+
+        loop {
+            match progress.next().await {
+                None => break,
+                Some(Ok(ProgressMessage::Heartbeat)) => continue,
+                Some(Ok(ProgressMessage::OutputStream(_))) => continue,
+                Some(Err(err)) => panic!("failed to receive progress: err={err:?}"),
+            };
+        }
+        // Get the result
+        match progress.finish().await.expect("failed to return result") {
+            FunctionResult::Success(success) => {
+                panic!("result should be failure; success={success:?}")
+            }
+            FunctionResult::Failure(failure) => failure.error,
+        }
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test)]
+    async fn uds_execute_action_run_timeout() {
+        let (_, key) = gen_keys();
+        let tmp_socket = rand_uds();
+        let mut builder = Config::builder();
+        let client =
+            uds_client_for_running_server(builder.enable_action_run(true), &tmp_socket, key).await;
+
+        let error = execute_action_run_to_failure(
+            client,
+            r#"function workit() {
+                while (true) {}
+            }"#,
+            ExecutionLimits {
+                timeout_secs: Some(1),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(FunctionResultFailureError::KIND_TIMEOUT, error.kind);
+    }
+
+    #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
+    #[test(tokio::test)]
+    async fn uds_execute_action_run_oom() {
+        let (_, key) = gen_keys();
+        let tmp_socket = rand_uds();
+        let mut builder = Config::builder();
+        let client =
+            uds_client_for_running_server(builder.enable_action_run(true), &tmp_socket, key).await;
+
+        let error = execute_action_run_to_failure(
+            client,
+            r#"function workit() {
+                const hoard = [];
+                while (true) {
+                    hoard.push(new Array(1024 * 1024).fill("leak"));
+                }
+            }"#,
+            ExecutionLimits {
+                memory_bytes: Some(64 * 1024 * 1024),
+                // Don't let a limit which doesn't take hang the test
+                timeout_secs: Some(60),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(FunctionResultFailureError::KIND_OOM, error.kind);
+    }
+
     #[allow(clippy::disallowed_methods)] // `$RUST_LOG` is checked for in macro
     #[test(tokio::test)]
     async fn http_execute_reconciliation() {
@@ -1198,6 +1351,7 @@ mod tests {
                     return { updates: { "myid": true }, actions: ["run"] };
                 }"#,
             ),
+            limits: None,
         };
 
         // Start the protocol
@@ -1274,6 +1428,7 @@ mod tests {
                     return { updates: { "myid": true }, actions: ["run"] };
                 }"#,
             ),
+            limits: None,
         };
 
         // Start the protocol
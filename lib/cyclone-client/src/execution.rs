@@ -4,7 +4,7 @@ use std::{
     task::{Context, Poll},
 };
 
-use cyclone_core::{FunctionResult, Message, ProgressMessage};
+use cyclone_core::{ControlMessage, FunctionResult, Message, ProgressMessage};
 use futures::{Future, SinkExt, Stream, StreamExt};
 use hyper::client::connect::Connection;
 use serde::{de::DeserializeOwned, Serialize};
@@ -119,6 +119,21 @@ where
     pub async fn finish(self) -> Result<FunctionResult<Success>, ExecutionError<Success>> {
         ExecutionClosing::try_from(self)?.finish().await
     }
+
+    /// Asks cyclone to stop the execution.
+    ///
+    /// The execution still completes as usual afterwards: the stream should be consumed and
+    /// [`finish`](Self::finish) called, which returns a cancelled failure unless the function
+    /// produced its result first.
+    pub async fn cancel(&mut self) -> Result<(), ExecutionError<Success>> {
+        let msg = ControlMessage::Cancel
+            .serialize_to_string()
+            .map_err(ExecutionError::JSONSerialize)?;
+        self.stream
+            .send(WebSocketMessage::Text(msg))
+            .await
+            .map_err(ExecutionError::WSSendIO)
+    }
 }
 
 impl<T, Success> Stream for ExecutionStarted<T, Success>
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ExecutionLimits;
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionRunRequest {
@@ -7,6 +9,8 @@ pub struct ActionRunRequest {
     pub handler: String,
     pub code_base64: String,
     pub args: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ExecutionLimits>,
 }
 
 #[remain::sorted]
@@ -27,4 +31,7 @@ pub struct ActionRunResultSuccess {
     pub message: Option<String>,
     // Collects the error if the function throws
     pub error: Option<String>,
+    // The proposed domain of the component, returned by import actions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<serde_json::Value>,
 }
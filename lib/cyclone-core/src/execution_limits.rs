@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Limits on the resources a single function execution may use.
+///
+/// Every limit is optional: an unset limit is left to whatever cyclone is configured with, and
+/// if that is unset too, the execution is not limited.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionLimits {
+    /// Wall-clock time, in seconds, the function may run for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Memory, in bytes, the function's JavaScript heap may grow to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+    /// CPU time, in seconds, the function's process may consume.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_secs: Option<u64>,
+}
+
+impl ExecutionLimits {
+    /// Returns these limits with any unset limit taken from `defaults`.
+    #[must_use]
+    pub fn or(self, defaults: Self) -> Self {
+        Self {
+            timeout_secs: self.timeout_secs.or(defaults.timeout_secs),
+            memory_bytes: self.memory_bytes.or(defaults.memory_bytes),
+            cpu_secs: self.cpu_secs.or(defaults.cpu_secs),
+        }
+    }
+
+    /// The wall-clock timeout as a [`Duration`], if one is set.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout_secs.map(Duration::from_secs)
+    }
+}
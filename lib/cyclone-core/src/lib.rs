@@ -15,6 +15,7 @@ mod action_run;
 mod canonical_command;
 mod component_view;
 mod encryption_key;
+mod execution_limits;
 mod liveness;
 pub mod process;
 mod progress;
@@ -29,10 +30,11 @@ pub use action_run::{ActionRunRequest, ActionRunResultSuccess, ResourceStatus};
 pub use canonical_command::{CanonicalCommand, CanonicalCommandError};
 pub use component_view::{ComponentKind, ComponentView};
 pub use encryption_key::{EncryptionKey, EncryptionKeyError};
+pub use execution_limits::ExecutionLimits;
 pub use liveness::{LivenessStatus, LivenessStatusParseError};
 pub use progress::{
-    FunctionResult, FunctionResultFailure, FunctionResultFailureError, Message, OutputStream,
-    ProgressMessage,
+    ControlMessage, FunctionResult, FunctionResultFailure, FunctionResultFailureError, Message,
+    OutputStream, ProgressMessage,
 };
 pub use readiness::{ReadinessStatus, ReadinessStatusParseError};
 pub use reconciliation::{ReconciliationRequest, ReconciliationResultSuccess};
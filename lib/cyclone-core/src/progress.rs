@@ -51,6 +51,25 @@ pub enum ProgressMessage {
     OutputStream(OutputStream),
 }
 
+/// A message sent by a client to control a function while it is executing.
+#[remain::sorted]
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ControlMessage {
+    /// Stops the execution, which then completes with a [`FunctionResultFailure`] of kind
+    /// [`FunctionResultFailureError::KIND_CANCELLED`].
+    Cancel,
+}
+
+impl ControlMessage {
+    pub fn deserialize_from_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    pub fn serialize_to_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
+
 #[remain::sorted]
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Message<R> {
@@ -110,6 +129,15 @@ pub struct FunctionResultFailureError {
     pub message: String,
 }
 
+impl FunctionResultFailureError {
+    /// The kind of failure reported when a function's execution is cancelled by a client.
+    pub const KIND_CANCELLED: &'static str = "cancelled";
+    /// The kind of failure reported when a function runs for longer than its limits allow.
+    pub const KIND_TIMEOUT: &'static str = "timeout";
+    /// The kind of failure reported when a function uses more memory than its limits allow.
+    pub const KIND_OOM: &'static str = "oom";
+}
+
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Fail {
     pub message: String,
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::ExecutionLimits;
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ReconciliationRequest {
@@ -8,6 +10,8 @@ pub struct ReconciliationRequest {
     pub handler: String,
     pub code_base64: String,
     pub args: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ExecutionLimits>,
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ExecutionLimits;
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ValidationRequest {
@@ -7,6 +9,8 @@ pub struct ValidationRequest {
     pub handler: String,
     pub value: serde_json::Value,
     pub code_base64: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limits: Option<ExecutionLimits>,
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -4,6 +4,7 @@ use std::{
     time::Duration,
 };
 
+use cyclone_core::ExecutionLimits;
 use derive_builder::Builder;
 use si_settings::{CanonicalFile, CanonicalFileError};
 use thiserror::Error;
@@ -54,6 +55,9 @@ pub struct Config {
 
     #[builder(setter(into), default)]
     limit_requests: Option<u32>,
+
+    #[builder(default)]
+    execution_limits: ExecutionLimits,
 }
 
 impl Config {
@@ -122,6 +126,12 @@ impl Config {
     pub fn limit_requests(&self) -> Option<u32> {
         self.limit_requests
     }
+
+    /// Gets the config's default execution limits, which requests may override.
+    #[must_use]
+    pub fn execution_limits(&self) -> ExecutionLimits {
+        self.execution_limits
+    }
 }
 
 impl ConfigBuilder {
@@ -1,6 +1,7 @@
 use std::{
     fmt, io,
     marker::{PhantomData, Unpin},
+    os::unix::process::ExitStatusExt,
     path::PathBuf,
     process::Stdio,
     sync::Arc,
@@ -10,11 +11,12 @@ use std::{
 use axum::extract::ws::WebSocket;
 use bytes_lines_codec::BytesLinesCodec;
 use cyclone_core::{
-    process::{self, ShutdownError},
-    FunctionResult, FunctionResultFailure, FunctionResultFailureError, Message, OutputStream,
-    SensitiveString,
+    process::{self, ShutdownError, Signal},
+    ControlMessage, ExecutionLimits, FunctionResult, FunctionResultFailure,
+    FunctionResultFailureError, Message, OutputStream, SensitiveString,
 };
 use futures::{SinkExt, StreamExt, TryStreamExt};
+use nix::sys::resource::{self, Resource};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
 use telemetry::prelude::*;
@@ -27,22 +29,25 @@ use tokio_serde::{formats::SymmetricalJson, Deserializer, Framed, SymmetricallyF
 use tokio_util::codec::{Decoder, FramedRead, FramedWrite};
 
 use crate::{
-    request::{DecryptRequest, ListSecrets},
+    request::{DecryptRequest, LimitRequest, ListSecrets},
     DecryptionKey, DecryptionKeyError, WebSocketMessage,
 };
 
 const TX_TIMEOUT_SECS: Duration = Duration::from_secs(5);
+const CHILD_EXIT_TIMEOUT_SECS: Duration = Duration::from_secs(5);
 
 pub fn new<Request, LangServerSuccess, Success>(
     lang_server_path: impl Into<PathBuf>,
     lang_server_debugging: bool,
     key: Arc<DecryptionKey>,
+    limits: ExecutionLimits,
     command: String,
 ) -> Execution<Request, LangServerSuccess, Success> {
     Execution {
         lang_server_path: lang_server_path.into(),
         lang_server_debugging,
         key,
+        limits,
         command,
         request_marker: PhantomData,
         lang_server_success_marker: PhantomData,
@@ -90,6 +95,7 @@ pub struct Execution<Request, LangServerSuccess, Success> {
     lang_server_path: PathBuf,
     lang_server_debugging: bool,
     key: Arc<DecryptionKey>,
+    limits: ExecutionLimits,
     command: String,
     request_marker: PhantomData<Request>,
     lang_server_success_marker: PhantomData<LangServerSuccess>,
@@ -98,7 +104,13 @@ pub struct Execution<Request, LangServerSuccess, Success> {
 
 impl<Request, LangServerSuccess, Success> Execution<Request, LangServerSuccess, Success>
 where
-    Request: DecryptRequest + ListSecrets + Serialize + DeserializeOwned + Unpin + core::fmt::Debug,
+    Request: DecryptRequest
+        + ListSecrets
+        + LimitRequest
+        + Serialize
+        + DeserializeOwned
+        + Unpin
+        + core::fmt::Debug,
     LangServerSuccess: DeserializeOwned,
     Success: Serialize,
 {
@@ -111,15 +123,31 @@ where
         // Now that the server said to start, I am going to read my message!
         let request = Self::read_request(ws).await?;
         let credentials: Vec<SensitiveString> = request.list_secrets(&self.key)?;
+        let execution_id = request.execution_id().to_owned();
+        let limits = request
+            .limits()
+            .map_or(self.limits, |limits| limits.or(self.limits));
         let mut command = Command::new(&self.lang_server_path);
         command
             .arg(&self.command)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            // Don't leave the child running if the execution is abandoned part way through
+            .kill_on_drop(true);
         if self.lang_server_debugging {
             command.env("DEBUG", "*").env("DEBUG_DEPTH", "5");
         }
+        if let Some(memory_bytes) = limits.memory_bytes {
+            command.env("NODE_OPTIONS", node_options_with_heap_limit(memory_bytes));
+        }
+        if let Some(cpu_secs) = limits.cpu_secs {
+            // SAFETY: the closure runs in the forked child just before it execs and only calls
+            // `setrlimit(2)`, which is async-signal-safe.
+            unsafe {
+                command.pre_exec(move || set_cpu_limit(cpu_secs));
+            }
+        }
         debug!(cmd = ?command, "spawning child process");
         let mut child = command
             .spawn()
@@ -150,6 +178,8 @@ where
             stdout,
             stderr,
             credentials,
+            execution_id,
+            limits,
             success_marker: self.success_marker,
         })
     }
@@ -200,6 +230,26 @@ where
     }
 }
 
+/// Caps the CPU time of the child process. A process which exceeds its CPU time is sent `SIGXCPU`,
+/// followed by `SIGKILL` a second later if it is still running.
+fn set_cpu_limit(cpu_secs: u64) -> io::Result<()> {
+    resource::setrlimit(Resource::RLIMIT_CPU, cpu_secs, cpu_secs.saturating_add(1))?;
+    Ok(())
+}
+
+/// Returns the `NODE_OPTIONS` which cap V8's heap at `memory_bytes`.
+///
+/// An address space rlimit doesn't work for lang-js: V8 reserves far more virtual memory than it
+/// ever touches, so any useful limit stops it from starting at all. Capping the heap instead
+/// bounds what a function can allocate, and V8 aborts the process once the heap is exhausted. The
+/// limit is rounded down to whole MiB, which is what V8 takes.
+fn node_options_with_heap_limit(memory_bytes: u64) -> String {
+    format!(
+        "--max-old-space-size={}",
+        (memory_bytes / (1024 * 1024)).max(1)
+    )
+}
+
 type SiFramedRead = FramedRead<ChildStdout, BytesLinesCodec>;
 type SiFramed<S> = Framed<SiFramedRead, S, S, SymmetricalJson<S>>;
 type SiMessage<S> = LangServerMessage<S>;
@@ -212,6 +262,8 @@ pub struct ExecutionStarted<LangServerSuccess, Success> {
     stdout: SiFramed<SiMessage<LangServerSuccess>>,
     stderr: FramedRead<ChildStderr, BytesLinesCodec>,
     credentials: Vec<SensitiveString>,
+    execution_id: String,
+    limits: ExecutionLimits,
     success_marker: PhantomData<Success>,
 }
 
@@ -254,42 +306,153 @@ where
     pub async fn process(self, ws: &mut WebSocket) -> Result<ExecutionClosing<Success>> {
         tokio::spawn(handle_stderr(self.stderr, self.credentials.clone()));
 
-        let mut stream = self
-            .stdout
-            .map(|ls_result| match ls_result {
-                Ok(ls_msg) => match ls_msg {
-                    LangServerMessage::Output(mut output) => {
-                        Self::filter_output(&mut output, &self.credentials)?;
-                        Ok(Message::OutputStream(output.into()))
-                    }
-                    LangServerMessage::Result(mut result) => {
-                        Self::filter_result(&mut result, &self.credentials)?;
-                        Ok(Message::Result(result.into()))
-                    }
-                },
-                Err(err) => Err(ExecutionError::ChildRecvIO(err)),
-            })
-            .map(|msg_result: Result<_>| match msg_result {
-                Ok(msg) => match msg
-                    .serialize_to_string()
-                    .map_err(ExecutionError::JSONSerialize)
-                {
-                    Ok(json_str) => Ok(WebSocketMessage::Text(json_str)),
-                    Err(err) => Err(err),
-                },
-                Err(err) => Err(err),
-            });
+        let mut child = self.child;
+        let mut result_sent = false;
 
-        while let Some(msg) = stream.try_next().await? {
-            ws.send(msg).await.map_err(ExecutionError::WSSendIO)?;
+        let mut stream = self.stdout.map(|ls_result| match ls_result {
+            Ok(ls_msg) => match ls_msg {
+                LangServerMessage::Output(mut output) => {
+                    Self::filter_output(&mut output, &self.credentials)?;
+                    Ok(Message::OutputStream(output.into()))
+                }
+                LangServerMessage::Result(mut result) => {
+                    Self::filter_result(&mut result, &self.credentials)?;
+                    Ok(Message::Result(result.into()))
+                }
+            },
+            Err(err) => Err(ExecutionError::ChildRecvIO(err)),
+        });
+
+        // Forward the child's messages until its output ends or the client cancels the execution,
+        // returning whether it was cancelled
+        let forward = async {
+            loop {
+                tokio::select! {
+                    msg = stream.try_next() => match msg? {
+                        Some(msg) => {
+                            result_sent |= matches!(msg, Message::Result(_));
+                            Self::ws_send_message(ws, msg).await?;
+                        }
+                        None => return Ok::<_, ExecutionError>(false),
+                    },
+                    control = Self::ws_read_control(ws) => match control? {
+                        ControlMessage::Cancel => return Ok(true),
+                    },
+                }
+            }
+        };
+        let forwarded = match self.limits.timeout() {
+            Some(timeout) => time::timeout(timeout, forward).await.ok(),
+            None => Some(forward.await),
+        };
+
+        let failure = match forwarded {
+            Some(forwarded) => {
+                let cancelled = forwarded?;
+                if cancelled {
+                    info!(execution_id = %self.execution_id, "function execution cancelled");
+                    process::child_shutdown(&mut child, Some(Signal::SIGKILL), None).await?;
+                }
+                if result_sent {
+                    None
+                } else if cancelled {
+                    Some((
+                        FunctionResultFailureError::KIND_CANCELLED,
+                        "function execution was cancelled".to_owned(),
+                    ))
+                } else {
+                    Self::limit_failure(&mut child, &self.limits).await
+                }
+            }
+            None => {
+                warn!(execution_id = %self.execution_id, "function execution timed out");
+                process::child_shutdown(&mut child, Some(Signal::SIGKILL), None).await?;
+                Some((
+                    FunctionResultFailureError::KIND_TIMEOUT,
+                    format!(
+                        "function execution exceeded its timeout of {}s",
+                        self.limits.timeout_secs.unwrap_or_default()
+                    ),
+                ))
+            }
+        };
+        if let Some((kind, message)) = failure {
+            let msg = Message::Result(FunctionResult::Failure(FunctionResultFailure {
+                execution_id: self.execution_id,
+                error: FunctionResultFailureError {
+                    kind: kind.to_owned(),
+                    message,
+                },
+                timestamp: crate::timestamp(),
+            }));
+            Self::ws_send_message(ws, msg).await?;
         }
 
         Ok(ExecutionClosing {
-            child: self.child,
+            child,
             success_marker: PhantomData,
         })
     }
 
+    async fn ws_read_control(ws: &mut WebSocket) -> Result<ControlMessage> {
+        loop {
+            match ws.next().await {
+                Some(Ok(WebSocketMessage::Text(json_str))) => {
+                    return ControlMessage::deserialize_from_str(&json_str)
+                        .map_err(ExecutionError::JSONDeserialize);
+                }
+                Some(Ok(WebSocketMessage::Ping(_) | WebSocketMessage::Pong(_))) => continue,
+                Some(Ok(unexpected)) => {
+                    return Err(ExecutionError::UnexpectedMessageType(unexpected))
+                }
+                Some(Err(err)) => return Err(ExecutionError::WSRecvIO(err)),
+                None => return Err(ExecutionError::WSRecvClosed),
+            }
+        }
+    }
+
+    async fn ws_send_message(ws: &mut WebSocket, msg: Message<Success>) -> Result<()> {
+        let msg = msg
+            .serialize_to_string()
+            .map_err(ExecutionError::JSONSerialize)?;
+        ws.send(WebSocketMessage::Text(msg))
+            .await
+            .map_err(ExecutionError::WSSendIO)
+    }
+
+    /// Determines whether a child which exited without producing a result was killed for
+    /// exceeding one of its resource limits.
+    async fn limit_failure(
+        child: &mut Child,
+        limits: &ExecutionLimits,
+    ) -> Option<(&'static str, String)> {
+        if limits.memory_bytes.is_none() && limits.cpu_secs.is_none() {
+            return None;
+        }
+        let status = time::timeout(CHILD_EXIT_TIMEOUT_SECS, child.wait())
+            .await
+            .ok()?
+            .ok()?;
+        let signal = Signal::try_from(status.signal()?).ok()?;
+
+        match (signal, limits.cpu_secs, limits.memory_bytes) {
+            (Signal::SIGXCPU | Signal::SIGKILL, Some(cpu_secs), _) => Some((
+                FunctionResultFailureError::KIND_TIMEOUT,
+                format!("function execution exceeded its CPU time limit of {cpu_secs}s"),
+            )),
+            // V8 aborts the process when its heap is exhausted
+            (
+                Signal::SIGABRT | Signal::SIGSEGV | Signal::SIGTRAP | Signal::SIGKILL,
+                _,
+                Some(memory_bytes),
+            ) => Some((
+                FunctionResultFailureError::KIND_OOM,
+                format!("function execution exceeded its memory limit of {memory_bytes} bytes"),
+            )),
+            _ => None,
+        }
+    }
+
     fn filter_output(output: &mut LangServerOutput, credentials: &[SensitiveString]) -> Result<()> {
         // Note: This brings a possibility of random substrings being matched out of context,
         // exposing that we have a secret by censoring it But trying to infer word boundary might
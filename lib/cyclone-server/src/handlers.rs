@@ -13,8 +13,8 @@ use axum::{
     response::IntoResponse,
 };
 use cyclone_core::{
-    ActionRunRequest, ActionRunResultSuccess, LivenessStatus, Message, ReadinessStatus,
-    ReconciliationRequest, ReconciliationResultSuccess, ResolverFunctionRequest,
+    ActionRunRequest, ActionRunResultSuccess, ExecutionLimits, LivenessStatus, Message,
+    ReadinessStatus, ReconciliationRequest, ReconciliationResultSuccess, ResolverFunctionRequest,
     ResolverFunctionResultSuccess, SchemaVariantDefinitionRequest,
     SchemaVariantDefinitionResultSuccess, ValidationRequest, ValidationResultSuccess,
 };
@@ -25,7 +25,7 @@ use telemetry::prelude::*;
 use super::extract::LimitRequestGuard;
 use crate::{
     execution::{self, Execution},
-    request::{DecryptRequest, ListSecrets},
+    request::{DecryptRequest, LimitRequest, ListSecrets},
     result::{
         LangServerActionRunResultSuccess, LangServerReconciliationResultSuccess,
         LangServerResolverFunctionResultSuccess, LangServerValidationResultSuccess,
@@ -86,6 +86,7 @@ pub async fn ws_execute_resolver(
     State(lang_server_path): State<LangServerPath>,
     State(key): State<DecryptionKey>,
     State(telemetry_level): State<TelemetryLevel>,
+    State(execution_limits): State<ExecutionLimits>,
     limit_request_guard: LimitRequestGuard,
 ) -> impl IntoResponse {
     let lang_server_path = lang_server_path.as_path().to_path_buf();
@@ -98,6 +99,7 @@ pub async fn ws_execute_resolver(
             lang_server_path,
             telemetry_level.is_debug_or_lower(),
             key.into(),
+            execution_limits,
             limit_request_guard,
             "resolverfunction".to_owned(),
             request,
@@ -113,6 +115,7 @@ pub async fn ws_execute_validation(
     State(lang_server_path): State<LangServerPath>,
     State(key): State<DecryptionKey>,
     State(telemetry_level): State<TelemetryLevel>,
+    State(execution_limits): State<ExecutionLimits>,
     limit_request_guard: LimitRequestGuard,
 ) -> impl IntoResponse {
     let lang_server_path = lang_server_path.as_path().to_path_buf();
@@ -125,6 +128,7 @@ pub async fn ws_execute_validation(
             lang_server_path,
             telemetry_level.is_debug_or_lower(),
             key.into(),
+            execution_limits,
             limit_request_guard,
             "validation".to_owned(),
             request,
@@ -140,6 +144,7 @@ pub async fn ws_execute_action_run(
     State(lang_server_path): State<LangServerPath>,
     State(key): State<DecryptionKey>,
     State(telemetry_level): State<TelemetryLevel>,
+    State(execution_limits): State<ExecutionLimits>,
     limit_request_guard: LimitRequestGuard,
 ) -> impl IntoResponse {
     let lang_server_path = lang_server_path.as_path().to_path_buf();
@@ -152,6 +157,7 @@ pub async fn ws_execute_action_run(
             lang_server_path,
             telemetry_level.is_debug_or_lower(),
             key.into(),
+            execution_limits,
             limit_request_guard,
             "actionRun".to_owned(),
             request,
@@ -167,6 +173,7 @@ pub async fn ws_execute_reconciliation(
     State(lang_server_path): State<LangServerPath>,
     State(key): State<DecryptionKey>,
     State(telemetry_level): State<TelemetryLevel>,
+    State(execution_limits): State<ExecutionLimits>,
     limit_request_guard: LimitRequestGuard,
 ) -> impl IntoResponse {
     let lang_server_path = lang_server_path.as_path().to_path_buf();
@@ -179,6 +186,7 @@ pub async fn ws_execute_reconciliation(
             lang_server_path,
             telemetry_level.is_debug_or_lower(),
             key.into(),
+            execution_limits,
             limit_request_guard,
             "reconciliation".to_owned(),
             request,
@@ -194,6 +202,7 @@ pub async fn ws_execute_schema_variant_definition(
     State(lang_server_path): State<LangServerPath>,
     State(key): State<DecryptionKey>,
     State(telemetry_level): State<TelemetryLevel>,
+    State(execution_limits): State<ExecutionLimits>,
     limit_request_guard: LimitRequestGuard,
 ) -> impl IntoResponse {
     let lang_server_path = lang_server_path.as_path().to_path_buf();
@@ -206,6 +215,7 @@ pub async fn ws_execute_schema_variant_definition(
             lang_server_path,
             telemetry_level.is_debug_or_lower(),
             key.into(),
+            execution_limits,
             limit_request_guard,
             "schemaVariantDefinition".to_owned(),
             request,
@@ -221,19 +231,31 @@ async fn handle_socket<Request, LangServerSuccess, Success>(
     lang_server_path: PathBuf,
     lang_server_debugging: bool,
     key: Arc<crate::DecryptionKey>,
+    execution_limits: ExecutionLimits,
     _limit_request_guard: LimitRequestGuard,
     sub_command: String,
     _request_marker: PhantomData<Request>,
     _lang_server_success_marker: PhantomData<LangServerSuccess>,
     success_marker: PhantomData<Success>,
 ) where
-    Request: DecryptRequest + ListSecrets + Serialize + DeserializeOwned + Unpin + fmt::Debug,
+    Request: DecryptRequest
+        + ListSecrets
+        + LimitRequest
+        + Serialize
+        + DeserializeOwned
+        + Unpin
+        + fmt::Debug,
     Success: Serialize + Unpin + fmt::Debug,
     LangServerSuccess: Serialize + DeserializeOwned + Unpin + fmt::Debug + Into<Success>,
 {
     let proto = {
-        let execution: Execution<Request, LangServerSuccess, Success> =
-            execution::new(lang_server_path, lang_server_debugging, key, sub_command);
+        let execution: Execution<Request, LangServerSuccess, Success> = execution::new(
+            lang_server_path,
+            lang_server_debugging,
+            key,
+            execution_limits,
+            sub_command,
+        );
         match execution.start(&mut socket).await {
             Ok(started) => started,
             Err(err) => {
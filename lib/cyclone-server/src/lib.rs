@@ -15,6 +15,7 @@ mod watch;
 
 pub use axum::extract::ws::Message as WebSocketMessage;
 pub use config::{Config, ConfigBuilder, ConfigError, IncomingStream};
+pub use cyclone_core::ExecutionLimits;
 pub use decryption_key::{DecryptionKey, DecryptionKeyError};
 pub use server::{Server, ShutdownSource};
 pub use timestamp::timestamp;
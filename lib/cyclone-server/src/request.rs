@@ -1,6 +1,6 @@
 use cyclone_core::{
-    ActionRunRequest, ComponentKind, ComponentView, ReconciliationRequest, ResolverFunctionRequest,
-    SchemaVariantDefinitionRequest, SensitiveString, ValidationRequest,
+    ActionRunRequest, ComponentView, ExecutionLimits, ReconciliationRequest,
+    ResolverFunctionRequest, SchemaVariantDefinitionRequest, SensitiveString, ValidationRequest,
 };
 use serde_json::Value;
 
@@ -15,15 +15,21 @@ pub trait DecryptRequest {
     fn decrypt_request(self, key: &DecryptionKey) -> Result<serde_json::Value, DecryptionKeyError>;
 }
 
+pub trait LimitRequest {
+    fn execution_id(&self) -> &str;
+
+    /// The limits requested for this execution, which override the ones cyclone is configured
+    /// with.
+    fn limits(&self) -> Option<ExecutionLimits>;
+}
+
+/// Secrets may be referenced by a component of any kind, so they are found by their
+/// `cycloneEncryptedDataMarker` alone.
 impl ListSecrets for ComponentView {
     fn list_secrets(
         &self,
         key: &DecryptionKey,
     ) -> Result<Vec<SensitiveString>, DecryptionKeyError> {
-        if self.kind != ComponentKind::Credential {
-            return Ok(vec![]);
-        }
-
         let mut credentials: Vec<SensitiveString> = vec![];
 
         // We need to first parse the tree for the secrets and then list them
@@ -81,9 +87,6 @@ impl ListSecrets for ComponentView {
 impl DecryptRequest for ComponentView {
     fn decrypt_request(self, key: &DecryptionKey) -> Result<Value, DecryptionKeyError> {
         let mut value = serde_json::to_value(&self)?;
-        if self.kind != ComponentKind::Credential {
-            return Ok(value);
-        }
 
         let mut work_queue = vec!["".to_owned()]; // JSON pointers
         while let Some(pointer) = work_queue.pop() {
@@ -178,23 +181,49 @@ impl DecryptRequest for ResolverFunctionRequest {
     }
 }
 
+/// Action funcs are handed the component they act on, which may carry secret references. Import
+/// actions are handed the component along with the identifier of the resource to import, in which
+/// case the component is the first argument.
+fn action_run_component(args: &Value) -> Option<(&'static str, ComponentView)> {
+    let (pointer, component) = match args {
+        Value::Array(args) => ("/args/0", args.first()?.clone()),
+        args => ("/args", args.clone()),
+    };
+    serde_json::from_value::<ComponentView>(component)
+        .ok()
+        .map(|component| (pointer, component))
+}
+
 impl ListSecrets for ActionRunRequest {
     fn list_secrets(
         &self,
-        _key: &DecryptionKey,
+        key: &DecryptionKey,
     ) -> Result<Vec<SensitiveString>, DecryptionKeyError> {
-        // TODO(fnichol): we'll need to populate/consume secrets here shortly
-        Ok(vec![])
+        match action_run_component(&self.args) {
+            Some((_, component)) => component.list_secrets(key),
+            None => Ok(vec![]),
+        }
     }
 }
 
 impl DecryptRequest for ActionRunRequest {
-    fn decrypt_request(
-        self,
-        _key: &DecryptionKey,
-    ) -> Result<serde_json::Value, DecryptionKeyError> {
-        let value = serde_json::to_value(&self)?;
-        // TODO(fnichol): we'll need to process the request with decrypted secrets
+    fn decrypt_request(self, key: &DecryptionKey) -> Result<serde_json::Value, DecryptionKeyError> {
+        let mut value = serde_json::to_value(&self)?;
+
+        let (pointer, component) = match action_run_component(&self.args) {
+            Some(found) => found,
+            None => return Ok(value),
+        };
+
+        match value.pointer_mut(pointer) {
+            Some(v) => *v = component.decrypt_request(key)?,
+            None => {
+                return Err(DecryptionKeyError::JSONPointerNotFound(
+                    value,
+                    pointer.to_owned(),
+                ));
+            }
+        }
         Ok(value)
     }
 }
@@ -262,9 +291,60 @@ impl DecryptRequest for SchemaVariantDefinitionRequest {
     }
 }
 
+impl LimitRequest for ResolverFunctionRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+
+    fn limits(&self) -> Option<ExecutionLimits> {
+        self.limits
+    }
+}
+
+impl LimitRequest for ActionRunRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+
+    fn limits(&self) -> Option<ExecutionLimits> {
+        self.limits
+    }
+}
+
+impl LimitRequest for ReconciliationRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+
+    fn limits(&self) -> Option<ExecutionLimits> {
+        self.limits
+    }
+}
+
+impl LimitRequest for ValidationRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+
+    fn limits(&self) -> Option<ExecutionLimits> {
+        self.limits
+    }
+}
+
+impl LimitRequest for SchemaVariantDefinitionRequest {
+    fn execution_id(&self) -> &str {
+        &self.execution_id
+    }
+
+    fn limits(&self) -> Option<ExecutionLimits> {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use base64::{engine::general_purpose, Engine};
+    use cyclone_core::ComponentKind;
     use sodiumoxide::crypto::box_::{PublicKey, SecretKey};
 
     use super::*;
@@ -356,4 +436,149 @@ mod tests {
         });
         assert_eq!(json, decrypted_json);
     }
+
+    #[test]
+    fn decrypt_standard_component() {
+        let (pkey, skey) = gen_keypair();
+        let decryption_key = DecryptionKey::from(skey);
+
+        let secret_json = serde_json::json!({
+            "my-super-secret": "Varginha's UFO",
+        });
+        let secret = serde_json::to_string(&secret_json).expect("Unable to serialize secret");
+        let encoded = encrypt_and_encode(secret.as_bytes(), &pkey);
+
+        let component = ComponentView {
+            kind: ComponentKind::Standard,
+            properties: serde_json::json!({
+                "domain": {
+                    "token": {
+                        "name": "ufo",
+                        "secret_kind": "dockerHub",
+                        "object_type": "credential",
+                        "message": { "cycloneEncryptedDataMarker": true, "encryptedSecret": encoded },
+                    },
+                },
+            }),
+        };
+
+        let secrets = component
+            .list_secrets(&decryption_key)
+            .expect("Unable to list secrets");
+        assert_eq!(secrets[0].as_str(), "Varginha's UFO");
+
+        let json = component
+            .decrypt_request(&decryption_key)
+            .expect("Unable to decrypt component view");
+        assert_eq!(json["kind"], "standard");
+        assert_eq!(
+            json.pointer("/properties/domain/token/message"),
+            Some(&secret_json)
+        );
+    }
+
+    #[test]
+    fn decrypt_action_run_args() {
+        let (pkey, skey) = gen_keypair();
+        let decryption_key = DecryptionKey::from(skey);
+
+        let secret_json = serde_json::json!({
+            "my-super-secret": "Varginha's UFO",
+        });
+        let secret = serde_json::to_string(&secret_json).expect("Unable to serialize secret");
+        let encoded = encrypt_and_encode(secret.as_bytes(), &pkey);
+
+        let request = ActionRunRequest {
+            execution_id: "1234".to_owned(),
+            handler: "create".to_owned(),
+            code_base64: "".to_owned(),
+            args: serde_json::json!({
+                "kind": "credential",
+                "properties": {
+                    "domain": {
+                        "secret": {
+                            "name": "ufo",
+                            "secret_kind": "dockerHub",
+                            "object_type": "credential",
+                            "message": {
+                                "cycloneEncryptedDataMarker": true,
+                                "encryptedSecret": encoded,
+                            },
+                        },
+                    },
+                },
+            }),
+            limits: None,
+        };
+
+        let secrets = request
+            .list_secrets(&decryption_key)
+            .expect("Unable to list secrets");
+        assert_eq!(secrets[0].as_str(), "Varginha's UFO");
+
+        let json = request
+            .decrypt_request(&decryption_key)
+            .expect("Unable to decrypt action run request");
+        assert_eq!(
+            json.pointer("/args/properties/domain/secret/message"),
+            Some(&secret_json)
+        );
+    }
+
+    #[test]
+    fn decrypt_import_action_run_args() {
+        let (pkey, skey) = gen_keypair();
+        let decryption_key = DecryptionKey::from(skey);
+
+        let secret_json = serde_json::json!({
+            "my-super-secret": "Varginha's UFO",
+        });
+        let secret = serde_json::to_string(&secret_json).expect("Unable to serialize secret");
+        let encoded = encrypt_and_encode(secret.as_bytes(), &pkey);
+
+        let request = ActionRunRequest {
+            execution_id: "1234".to_owned(),
+            handler: "discover".to_owned(),
+            code_base64: "".to_owned(),
+            args: serde_json::json!([
+                {
+                    "kind": "credential",
+                    "properties": {
+                        "domain": {
+                            "secret": {
+                                "name": "ufo",
+                                "secret_kind": "dockerHub",
+                                "object_type": "credential",
+                                "message": {
+                                    "cycloneEncryptedDataMarker": true,
+                                    "encryptedSecret": encoded,
+                                },
+                            },
+                        },
+                    },
+                },
+                "arn:aws:ec2:us-east-2:123456789012:instance/i-1234",
+            ]),
+            limits: None,
+        };
+
+        let secrets = request
+            .list_secrets(&decryption_key)
+            .expect("Unable to list secrets");
+        assert_eq!(secrets[0].as_str(), "Varginha's UFO");
+
+        let json = request
+            .decrypt_request(&decryption_key)
+            .expect("Unable to decrypt action run request");
+        assert_eq!(
+            json.pointer("/args/0/properties/domain/secret/message"),
+            Some(&secret_json)
+        );
+        assert_eq!(
+            json.pointer("/args/1"),
+            Some(&serde_json::json!(
+                "arn:aws:ec2:us-east-2:123456789012:instance/i-1234"
+            ))
+        );
+    }
 }
@@ -6,6 +6,7 @@ use std::{
 };
 
 use axum::extract::FromRef;
+use cyclone_core::ExecutionLimits;
 use tokio::sync::mpsc;
 
 #[derive(Clone, FromRef)]
@@ -13,6 +14,7 @@ pub struct AppState {
     lang_server_path: LangServerPath,
     decryption_key: DecryptionKey,
     telemetry_level: TelemetryLevel,
+    execution_limits: ExecutionLimits,
 }
 
 impl AppState {
@@ -20,11 +22,13 @@ impl AppState {
         lang_server_path: impl Into<PathBuf>,
         decryption_key: crate::DecryptionKey,
         telemetry_level: Box<dyn telemetry::TelemetryLevel>,
+        execution_limits: ExecutionLimits,
     ) -> Self {
         Self {
             lang_server_path: LangServerPath(Arc::new(lang_server_path.into())),
             decryption_key: DecryptionKey(Arc::new(decryption_key)),
             telemetry_level: TelemetryLevel(Arc::new(telemetry_level)),
+            execution_limits,
         }
     }
 }
@@ -409,6 +409,10 @@ pub async fn jwt_private_signing_key() -> Result<RS256KeyPair> {
 pub async fn council_server(nats_config: NatsConfig) -> Result<council_server::Server> {
     let config = council_server::server::Config::builder()
         .nats(nats_config)
+        .graph_store(council_server::server::config::GraphStoreConfig {
+            in_memory: true,
+            ..Default::default()
+        })
         .build()?;
     let server = council_server::Server::new_with_config(config).await?;
     Ok(server)
@@ -435,7 +439,12 @@ pub fn pinga_server(services_context: &ServicesContext) -> Result<pinga_server::
         services_context.veritech().clone(),
         services_context.job_processor(),
     )
-    .wrap_err("failed to create Pinga server")?;
+    .wrap_err("failed to create Pinga server")?
+    // Every test has its own subject prefix and so its own streams, which needn't outlive it
+    .with_job_queue_config(pinga_server::JobQueueConfig {
+        in_memory: true,
+        ..Default::default()
+    });
 
     Ok(server)
 }
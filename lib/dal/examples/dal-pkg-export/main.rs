@@ -46,6 +46,7 @@ async fn main() -> Result<()> {
             Some(description),
             created_by,
             variant_ids,
+            vec![],
         )
         .await?,
     )
@@ -8,12 +8,20 @@ use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use si_pkg::ActionFuncSpecKind;
 use telemetry::prelude::*;
+use veritech_client::{FunctionResult, FunctionResultFailure, ResourceStatus};
 
 use crate::{
-    component::view::ComponentViewError, func::backend::js_action::ActionRunResult,
-    impl_standard_model, pk, standard_model, standard_model_accessor, Component, ComponentId,
-    ComponentView, DalContext, FuncBinding, FuncBindingError, FuncBindingReturnValueError, FuncId,
-    HistoryEventError, SchemaVariantId, StandardModel, StandardModelError, Tenancy, Timestamp,
+    component::view::ComponentViewError,
+    func::backend::{
+        js_action::{ActionRunResult, FuncBackendJsAction, FuncBackendJsActionArgs},
+        ExtractPayload, FuncBackendError, FuncDispatch, FuncDispatchContext,
+    },
+    func::execution::FuncExecutionPk,
+    impl_standard_model, pk,
+    pkg::{self, PkgError},
+    standard_model, standard_model_accessor, Component, ComponentId, ComponentView, DalContext,
+    Func, FuncBinding, FuncBindingError, FuncBindingReturnValueError, FuncId, HistoryEventError,
+    Node, SchemaVariantId, StandardModel, StandardModelError, Tenancy, Timestamp,
     TransactionsError, Visibility, WsEvent, WsEventError,
 };
 
@@ -27,6 +35,8 @@ const FIND_FOR_CONTEXT_AND_FUNC: &str =
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ActionPrototypeError {
+    #[error("resource {0} has already been imported as components: {1:?}")]
+    AlreadyImported(String, Vec<ComponentId>),
     #[error("component error: {0}")]
     Component(String),
     #[error("component not found: {0}")]
@@ -34,6 +44,8 @@ pub enum ActionPrototypeError {
     #[error(transparent)]
     ComponentView(#[from] ComponentViewError),
     #[error(transparent)]
+    FuncBackend(#[from] FuncBackendError),
+    #[error(transparent)]
     FuncBinding(#[from] FuncBindingError),
     #[error(transparent)]
     FuncBindingReturnValue(#[from] FuncBindingReturnValueError),
@@ -41,12 +53,28 @@ pub enum ActionPrototypeError {
     FuncNotFound(FuncId, ActionPrototypeId),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
+    #[error("import function failed for {0}: {}", .1.error.message)]
+    ImportFunctionFailed(String, FunctionResultFailure),
+    #[error("cannot import a resource on head")]
+    ImportOnHead,
+    #[error("resource {0} could not be imported: {1}")]
+    ImportResourceError(String, String),
+    #[error("resource {0} not found")]
+    ImportResourceNotFound(String),
+    #[error("action prototype {0} is a {1} action, not an import action")]
+    InvalidImportKind(ActionPrototypeId, ActionKind),
+    #[error("action Func {0} has no code or handler")]
+    MissingCode(FuncId),
+    #[error("action Func {0} has a plan handler but no code")]
+    MissingPlanCode(FuncId),
     #[error("nats txn error: {0}")]
     Nats(#[from] NatsError),
     #[error("not found with kind {0} for context {1:?}")]
     NotFoundByKindAndContext(ActionKind, ActionPrototypeContext),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("pkg error: {0}")]
+    Pkg(#[from] Box<PkgError>),
     #[error("schema not found")]
     SchemaNotFound,
     #[error("schema variant not found")]
@@ -78,6 +106,10 @@ pub enum ActionKind {
     Create,
     /// The [`action`](ActionPrototype) deletes an existing "resource".
     Delete,
+    /// The [`action`](ActionPrototype) discovers an existing "resource" that was not created by
+    /// SI, given its identifier, and proposes a [`Component`] for it. A "resource" which doesn't
+    /// exist is reported by returning no payload.
+    Import,
     /// The [`action`](ActionPrototype) is "internal only" or has multiple effects.
     Other,
     /// The [`action`](ActionPrototype) that refreshes an existing "resource".
@@ -91,6 +123,7 @@ impl From<ActionFuncSpecKind> for ActionKind {
             ActionFuncSpecKind::Refresh => ActionKind::Refresh,
             ActionFuncSpecKind::Other => ActionKind::Other,
             ActionFuncSpecKind::Delete => ActionKind::Delete,
+            ActionFuncSpecKind::Import => ActionKind::Import,
         }
     }
 }
@@ -102,10 +135,25 @@ impl From<&ActionKind> for ActionFuncSpecKind {
             ActionKind::Refresh => ActionFuncSpecKind::Refresh,
             ActionKind::Other => ActionFuncSpecKind::Other,
             ActionKind::Delete => ActionFuncSpecKind::Delete,
+            ActionKind::Import => ActionFuncSpecKind::Import,
         }
     }
 }
 
+/// What an [`ActionPrototype`] would do to a [`Component`], as described by the plan handler of
+/// its [`Func`].
+///
+/// Plan handlers return the same shape as the action itself, with the proposed change document
+/// as the payload, and must not have any side effects.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionPlan {
+    pub status: ResourceStatus,
+    pub proposed_changes: Option<serde_json::Value>,
+    pub message: Option<String>,
+    pub logs: Vec<String>,
+}
+
 // Hrm - is this a universal resolver context? -- Adam
 impl Default for ActionPrototypeContext {
     fn default() -> Self {
@@ -308,7 +356,10 @@ impl ActionPrototype {
         component_id: ComponentId,
         trigger_dependent_values_update: bool,
     ) -> ActionPrototypeResult<Option<ActionRunResult>> {
-        let component_view = ComponentView::new(ctx, component_id).await?;
+        let component_view = ComponentView::new(ctx, component_id)
+            .await?
+            .reencrypt_secrets(ctx)
+            .await?;
         let (_, return_value) = FuncBinding::create_and_execute(
             ctx,
             serde_json::to_value(component_view)?,
@@ -360,4 +411,174 @@ impl ActionPrototype {
             None => None,
         })
     }
+
+    /// Runs the prototype's [`Func`] as an [`ActionKind::Import`] to discover an existing
+    /// "resource" by its identifier (such as an ARN), creating a [`Component`] named `name` for it
+    /// in the current change set, along with its [`Node`].
+    ///
+    /// The func is handed the newly created component along with the identifier and returns the
+    /// "resource" as its payload, plus an optional proposed domain for the component:
+    ///
+    /// ```js
+    /// async function discover(component, identifier) {
+    ///   return { status: "ok", payload: { ... }, domain: { ... } };
+    /// }
+    /// ```
+    ///
+    /// If the func does not return an "ok" or "warning" status, an error is returned and the
+    /// caller is expected to discard the transaction.
+    pub async fn import(
+        &self,
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        identifier: impl AsRef<str>,
+    ) -> ActionPrototypeResult<(Component, Node)> {
+        if ctx.visibility().is_head() {
+            return Err(ActionPrototypeError::ImportOnHead);
+        }
+        if self.kind != ActionKind::Import {
+            return Err(ActionPrototypeError::InvalidImportKind(self.id, self.kind));
+        }
+        let identifier = identifier.as_ref();
+
+        let already_imported =
+            Component::list_imported_for_schema_variant(ctx, self.schema_variant_id(), identifier)
+                .await
+                .map_err(|e| ActionPrototypeError::Component(e.to_string()))?;
+        if !already_imported.is_empty() {
+            return Err(ActionPrototypeError::AlreadyImported(
+                identifier.to_owned(),
+                already_imported,
+            ));
+        }
+
+        let func = Func::get_by_id(ctx, &self.func_id())
+            .await?
+            .ok_or(ActionPrototypeError::FuncNotFound(self.func_id(), self.id))?;
+        let (code_base64, handler) = match (func.code_base64(), func.handler()) {
+            (Some(code_base64), Some(handler)) => (code_base64, handler),
+            _ => return Err(ActionPrototypeError::MissingCode(*func.id())),
+        };
+
+        let (mut component, node) = Component::new(ctx, name, self.schema_variant_id())
+            .await
+            .map_err(|e| ActionPrototypeError::Component(e.to_string()))?;
+
+        let component_view = ComponentView::new(ctx, *component.id())
+            .await?
+            .reencrypt_secrets(ctx)
+            .await?;
+        let args =
+            FuncBackendJsActionArgs::deserialize(serde_json::json!([component_view, identifier]))?;
+
+        let (context, mut rx) = FuncDispatchContext::new(ctx, FuncExecutionPk::generate());
+        let value = FuncBackendJsAction::new(context, code_base64, handler, args)
+            .dispatch()
+            .await?;
+
+        let mut output = Vec::new();
+        while let Some(output_stream) = rx.recv().await {
+            output.push(output_stream);
+        }
+        output.sort_by_key(|output_stream| output_stream.timestamp);
+
+        let success = match value {
+            FunctionResult::Success(success) => success,
+            FunctionResult::Failure(failure) => {
+                return Err(ActionPrototypeError::ImportFunctionFailed(
+                    identifier.to_owned(),
+                    failure,
+                ))
+            }
+        };
+        if success.status == ResourceStatus::Error {
+            return Err(ActionPrototypeError::ImportResourceError(
+                identifier.to_owned(),
+                success
+                    .message
+                    .or(success.error)
+                    .unwrap_or_else(|| "no message returned".to_owned()),
+            ));
+        }
+        // An import function reports a resource which doesn't exist by returning no payload
+        if success
+            .payload
+            .as_ref()
+            .map_or(true, serde_json::Value::is_null)
+        {
+            return Err(ActionPrototypeError::ImportResourceNotFound(
+                identifier.to_owned(),
+            ));
+        }
+
+        if let Some(domain) = &success.domain {
+            pkg::set_component_domain(ctx, &component, domain)
+                .await
+                .map_err(Box::new)?;
+        }
+
+        let mut run_result = success.extract()?;
+        run_result.logs = output.into_iter().map(|o| o.message).collect();
+        component
+            .set_imported_resource(ctx, run_result)
+            .await
+            .map_err(|e| ActionPrototypeError::Component(e.to_string()))?;
+        component
+            .set_import_identifier(ctx, Some(identifier.to_owned()))
+            .await
+            .map_err(|e| ActionPrototypeError::Component(e.to_string()))?;
+
+        Ok((component, node))
+    }
+
+    /// Runs the plan handler of the prototype's [`Func`] against the [`Component`], returning
+    /// [`None`] if the func does not have one.
+    ///
+    /// Nothing is recorded for the execution and the component's resource is left untouched.
+    pub async fn plan(
+        &self,
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ActionPrototypeResult<Option<ActionPlan>> {
+        let func = Func::get_by_id(ctx, &self.func_id())
+            .await?
+            .ok_or(ActionPrototypeError::FuncNotFound(self.func_id(), self.id))?;
+        let plan_handler = match func.plan_handler() {
+            Some(plan_handler) => plan_handler,
+            None => return Ok(None),
+        };
+        let code_base64 = func
+            .code_base64()
+            .ok_or(ActionPrototypeError::MissingPlanCode(*func.id()))?;
+
+        let component_view = ComponentView::new(ctx, component_id)
+            .await?
+            .reencrypt_secrets(ctx)
+            .await?;
+        let args = FuncBackendJsActionArgs::deserialize(serde_json::to_value(component_view)?)?;
+
+        let (context, mut rx) = FuncDispatchContext::new(ctx, FuncExecutionPk::generate());
+        let (value, _) = FuncBackendJsAction::new(context, code_base64, plan_handler, args)
+            .execute()
+            .await?;
+
+        let mut output = Vec::new();
+        while let Some(output_stream) = rx.recv().await {
+            output.push(output_stream);
+        }
+        output.sort_by_key(|output_stream| output_stream.timestamp);
+
+        Ok(match value {
+            Some(value) => {
+                let run_result: ActionRunResult = serde_json::from_value(value)?;
+                Some(ActionPlan {
+                    status: run_result.status,
+                    proposed_changes: run_result.payload,
+                    message: run_result.message,
+                    logs: output.into_iter().map(|o| o.message).collect(),
+                })
+            }
+            None => None,
+        })
+    }
 }
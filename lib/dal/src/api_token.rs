@@ -0,0 +1,173 @@
+//! This module contains [`ApiToken`], which lets programs call the headless API on behalf of a
+//! [`User`](crate::User) in one workspace.
+//!
+//! A token acts as the user who created it, with whatever
+//! [`WorkspaceRole`](crate::WorkspaceRole) they hold when the token is used. Only a hash of the
+//! token is stored; the token itself is returned once, by [`ApiToken::new()`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use sodiumoxide::crypto::hash::sha256;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::standard_model::{object_option_from_row_option, objects_from_rows};
+use crate::{
+    pk, DalContext, HistoryActor, HistoryEvent, HistoryEventError, StandardModelError, Tenancy,
+    Timestamp, TransactionsError, UserPk, WorkspacePk,
+};
+
+const API_TOKEN_AUTHENTICATE: &str = include_str!("queries/api_token/authenticate.sql");
+const API_TOKEN_LIST: &str = include_str!("queries/api_token/list.sql");
+const API_TOKEN_REVOKE: &str = include_str!("queries/api_token/revoke.sql");
+
+/// Every token starts with this, so that they are easy to spot, e.g. by secret scanners.
+const API_TOKEN_PREFIX: &str = "si_";
+/// How much of the token is kept in the clear to tell tokens apart.
+const API_TOKEN_VISIBLE_LEN: usize = 8;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ApiTokenError {
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("api token not found: {0}")]
+    NotFound(ApiTokenPk),
+    #[error("api tokens can only be created by a user")]
+    NoUserActor,
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type ApiTokenResult<T> = Result<T, ApiTokenError>;
+
+pk!(ApiTokenPk);
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ApiToken {
+    pub pk: ApiTokenPk,
+    pub name: String,
+    pub user_pk: UserPk,
+    #[serde(default, skip_serializing)]
+    token_hash: String,
+    pub token_prefix: String,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    #[serde(flatten)]
+    pub tenancy: Tenancy,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+impl ApiToken {
+    /// Create a token for the acting user in the workspace, returning it along with the token
+    /// itself, which can't be recovered later.
+    #[instrument(skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        name: impl AsRef<str> + std::fmt::Debug,
+    ) -> ApiTokenResult<(Self, String)> {
+        let user_pk = match ctx.history_actor() {
+            HistoryActor::User(user_pk) => *user_pk,
+            HistoryActor::SystemInit => return Err(ApiTokenError::NoUserActor),
+        };
+
+        let mut secret = [0; 32];
+        sodiumoxide::randombytes::randombytes_into(&mut secret);
+        let token = format!("{API_TOKEN_PREFIX}{}", hex::encode(secret));
+        let token_prefix = token[..API_TOKEN_PREFIX.len() + API_TOKEN_VISIBLE_LEN].to_owned();
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM api_token_create_v1($1, $2, $3, $4, $5)",
+                &[
+                    &name.as_ref(),
+                    &user_pk,
+                    &Self::hash(&token),
+                    &token_prefix,
+                    ctx.tenancy(),
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let api_token: Self = serde_json::from_value(json)?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "api_token.create",
+            "API token created",
+            &serde_json::json![{ "pk": &api_token.pk, "name": &api_token.name }],
+        )
+        .await?;
+
+        Ok((api_token, token))
+    }
+
+    /// Find the unrevoked token matching `token` in any workspace, recording that it was used.
+    /// The token's [`workspace`](Self::workspace_pk()) decides the tenancy to use from then on.
+    #[instrument(skip_all)]
+    pub async fn authenticate(ctx: &DalContext, token: &str) -> ApiTokenResult<Option<Self>> {
+        if !token.starts_with(API_TOKEN_PREFIX) {
+            return Ok(None);
+        }
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(API_TOKEN_AUTHENTICATE, &[&Self::hash(token)])
+            .await?;
+        Ok(object_option_from_row_option(row)?)
+    }
+
+    pub fn workspace_pk(&self) -> Option<WorkspacePk> {
+        self.tenancy.workspace_pk()
+    }
+
+    #[instrument(skip(ctx))]
+    pub async fn list(ctx: &DalContext) -> ApiTokenResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(API_TOKEN_LIST, &[ctx.tenancy()])
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+
+    /// Revoke the token so that it can no longer be used.
+    #[instrument(skip(ctx))]
+    pub async fn revoke(ctx: &DalContext, pk: ApiTokenPk) -> ApiTokenResult<Self> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(API_TOKEN_REVOKE, &[ctx.tenancy(), &pk])
+            .await?;
+        let api_token: Self =
+            object_option_from_row_option(row)?.ok_or(ApiTokenError::NotFound(pk))?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "api_token.revoke",
+            "API token revoked",
+            &serde_json::json![{ "pk": &api_token.pk, "name": &api_token.name }],
+        )
+        .await?;
+
+        Ok(api_token)
+    }
+
+    fn hash(token: &str) -> String {
+        hex::encode(sha256::hash(token.as_bytes()))
+    }
+}
@@ -41,6 +41,7 @@ use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use std::collections::HashMap;
+use std::str::FromStr;
 use telemetry::prelude::*;
 use thiserror::Error;
 
@@ -61,12 +62,14 @@ use crate::{
     impl_standard_model,
     job::definition::DependentValuesUpdate,
     pk,
+    property_editor::schema::WidgetKind,
     standard_model::{self, TypeHint},
     standard_model_accessor, standard_model_belongs_to, standard_model_has_many,
     AttributeContextError, AttributePrototypeArgumentError, Component, ComponentId, DalContext,
     Func, FuncBinding, FuncError, HistoryEventError, IndexMap, InternalProvider,
-    InternalProviderId, Prop, PropError, PropId, PropKind, StandardModel, StandardModelError,
-    Tenancy, Timestamp, TransactionsError, Visibility, WsEventError,
+    InternalProviderId, Prop, PropError, PropId, PropKind, Secret, SecretId, SecretKind,
+    StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError, Visibility,
+    WsEventError,
 };
 
 pub mod view;
@@ -146,6 +149,8 @@ pub enum AttributeValueError {
     InvalidObjectValueFields(Vec<String>),
     #[error("invalid prop value; expected {0} but got {1}")]
     InvalidPropValue(String, serde_json::Value),
+    #[error("invalid secret reference for prop {0}: {1} is not a secret id")]
+    InvalidSecretReference(PropId, String),
     #[error("json pointer missing for attribute view {0:?} {1:?}")]
     JsonPointerMissing(AttributeValueId, HashMap<AttributeValueId, String>),
     #[error("missing attribute value")]
@@ -202,6 +207,15 @@ pub enum AttributeValueError {
     SchemaVariantMissing,
     #[error("schema variant not found for component id: {0}")]
     SchemaVariantNotFoundForComponent(ComponentId),
+    #[error("prop {prop_id} only refers to {expected} secrets, but secret {secret_id} is a {found} secret")]
+    SecretKindMismatch {
+        prop_id: PropId,
+        secret_id: SecretId,
+        expected: SecretKind,
+        found: SecretKind,
+    },
+    #[error("secret not found for prop {0}: {1}")]
+    SecretNotFound(PropId, SecretId),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("standard model error: {0}")]
@@ -754,6 +768,10 @@ impl AttributeValue {
         // TODO(nick,paulo,zack,jacob): ensure we do not _have_ to do this in the future.
         let ctx = &ctx.clone_without_deleted_visibility();
 
+        if let Some(serde_json::Value::String(raw_secret_id)) = &value {
+            Self::check_secret_reference(ctx, context.prop_id(), raw_secret_id).await?;
+        }
+
         let row = ctx.txns()
             .await?
             .pg()
@@ -788,6 +806,45 @@ impl AttributeValue {
         Ok((value, new_attribute_value_id))
     }
 
+    /// Checks that a value written to a secret reference [`Prop`] is the id of a [`Secret`] in
+    /// the workspace, of the [`SecretKind`](Prop::secret_kind()) the prop refers to. Values of
+    /// any other prop are left alone.
+    async fn check_secret_reference(
+        ctx: &DalContext,
+        prop_id: PropId,
+        raw_secret_id: &str,
+    ) -> AttributeValueResult<()> {
+        if prop_id.is_none() {
+            return Ok(());
+        }
+        let prop = Prop::get_by_id(ctx, &prop_id)
+            .await?
+            .ok_or(AttributeValueError::PropNotFound(prop_id))?;
+        if *prop.widget_kind() != WidgetKind::SecretSelect {
+            return Ok(());
+        }
+
+        let secret_id = SecretId::from_str(raw_secret_id).map_err(|_| {
+            AttributeValueError::InvalidSecretReference(prop_id, raw_secret_id.to_owned())
+        })?;
+        // Secrets are only visible within their own workspace
+        let secret = Secret::get_by_id(ctx, &secret_id)
+            .await?
+            .ok_or(AttributeValueError::SecretNotFound(prop_id, secret_id))?;
+        if let Some(expected) = prop.secret_kind() {
+            if *secret.kind() != expected {
+                return Err(AttributeValueError::SecretKindMismatch {
+                    prop_id,
+                    secret_id,
+                    expected,
+                    found: secret.kind().clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Insert a new value under the parent [`AttributeValue`] in the given [`AttributeContext`]. This is mostly only
     /// useful for adding elements to a [`PropKind::Array`], or to a [`PropKind::Map`]. Updating existing values in an
     /// [`Array`](PropKind::Array), or [`Map`](PropKind::Map), and setting/updating all other [`PropKind`] should be
@@ -842,6 +899,11 @@ impl AttributeValue {
         key: Option<String>,
         create_child_proxies: bool,
     ) -> AttributeValueResult<AttributeValueId> {
+        if let Some(serde_json::Value::String(raw_secret_id)) = &value {
+            Self::check_secret_reference(ctx, item_attribute_context.prop_id(), raw_secret_id)
+                .await?;
+        }
+
         let row = ctx.txns().await?.pg().query_one(
             "SELECT new_attribute_value_id FROM attribute_value_insert_for_context_raw_v1($1, $2, $3, $4, $5, $6, $7)",
             &[
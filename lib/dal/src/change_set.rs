@@ -6,24 +6,45 @@ use strum::{Display, EnumString};
 use telemetry::prelude::*;
 use thiserror::Error;
 
+use crate::change_status::ChangeStatusError;
+use crate::component::ComponentViewError;
 use crate::label_list::LabelList;
 use crate::standard_model::object_option_from_row_option;
 use crate::ws_event::{WsEvent, WsEventError, WsPayload};
 use crate::{
     pk, HistoryEvent, HistoryEventError, LabelListError, StandardModelError, Tenancy, Timestamp,
-    TransactionsError, UserError, UserPk, Visibility,
+    TransactionsError, UserError, UserPk, Visibility, WorkspaceError,
 };
-use crate::{Component, ComponentError, DalContext, WsEventResult};
+use crate::{Component, ComponentError, DalContext, DependentValuesUpdate, WsEventResult};
+
+pub mod approval;
+pub mod conflict;
+pub mod diff;
+
+pub use approval::{ChangeSetApproval, ChangeSetApprovalDecision, ChangeSetApprovalPk};
+pub use conflict::{
+    ChangeSetConflict, ChangeSetConflictChoice, ChangeSetConflictKind, ChangeSetConflictObject,
+    ChangeSetConflictResolution,
+};
+pub use diff::ChangeSetDiff;
 
 const CHANGE_SET_OPEN_LIST: &str = include_str!("queries/change_set/open_list.sql");
 const CHANGE_SET_GET_BY_PK: &str = include_str!("queries/change_set/get_by_pk.sql");
+const CHANGE_SET_REBASE_AFFECTED_ATTRIBUTE_VALUES: &str =
+    include_str!("queries/change_set/rebase_affected_attribute_values.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ChangeSetError {
+    #[error(transparent)]
+    ChangeStatus(#[from] ChangeStatusError),
     #[error(transparent)]
     Component(#[from] ComponentError),
     #[error(transparent)]
+    ComponentView(#[from] ComponentViewError),
+    #[error("change set has {} unresolved conflict(s) with head", .0.len())]
+    Conflicts(Vec<ChangeSetConflict>),
+    #[error(transparent)]
     HistoryEvent(#[from] HistoryEventError),
     #[error("invalid user actor pk")]
     InvalidActor(UserPk),
@@ -31,29 +52,50 @@ pub enum ChangeSetError {
     LabelList(#[from] LabelListError),
     #[error(transparent)]
     Nats(#[from] NatsError),
+    #[error(
+        "change set needs {required} approval(s) before it can be applied, but has {approved}"
+    )]
+    NotApproved { required: i32, approved: i32 },
+    #[error("user {0} is not a reviewer of this change set")]
+    NotAReviewer(UserPk),
+    #[error("change set must be awaiting approval to be reviewed, but it is {0}")]
+    NotAwaitingApproval(ChangeSetStatus),
+    #[error("change set must be open, but it is {0}")]
+    NotOpen(ChangeSetStatus),
+    #[error("only users can review change sets")]
+    NoUserActor,
     #[error(transparent)]
     Pg(#[from] PgError),
+    #[error("user {0} can't review a change set they sent for review")]
+    SelfReview(UserPk),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
     StandardModel(#[from] StandardModelError),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error("unknown change status: {0}")]
+    UnknownChangeStatus(String),
+    #[error("unknown change set conflict kind: {0}")]
+    UnknownConflictKind(String),
     #[error(transparent)]
     User(#[from] UserError),
     #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error(transparent)]
     WsEvent(#[from] WsEventError),
 }
 
 pub type ChangeSetResult<T> = Result<T, ChangeSetError>;
 
 #[remain::sorted]
-#[derive(Deserialize, Serialize, Debug, Display, EnumString, PartialEq, Eq)]
+#[derive(Deserialize, Serialize, Debug, Display, EnumString, PartialEq, Eq, Clone, Copy)]
 pub enum ChangeSetStatus {
     Abandoned,
     Applied,
     Closed,
     Failed,
+    NeedsApproval,
     Open,
 }
 
@@ -65,6 +107,11 @@ pub struct ChangeSet {
     pub name: String,
     pub note: Option<String>,
     pub status: ChangeSetStatus,
+    /// The user who last sent the change set for review, if any.
+    #[serde(default)]
+    pub approval_requested_by: Option<UserPk>,
+    /// When the change set was last based on _head_, either by being opened or by being rebased.
+    pub based_at: DateTime<Utc>,
     #[serde(flatten)]
     pub tenancy: Tenancy,
     #[serde(flatten)]
@@ -109,12 +156,40 @@ impl ChangeSet {
         Utc::now().format("%Y-%m-%d-%H:%M").to_string()
     }
 
+    /// List every object that both this change set and _head_ have changed since the change set
+    /// was opened. Applying the change set as-is would overwrite _head_'s side of each of these.
+    #[instrument(skip(ctx))]
+    pub async fn conflicts(&self, ctx: &DalContext) -> ChangeSetResult<Vec<ChangeSetConflict>> {
+        ChangeSetConflict::list_for_change_set(ctx, self.pk).await
+    }
+
+    /// Apply the change set, refusing with [`ChangeSetError::Conflicts`] if it conflicts with
+    /// _head_.
     #[instrument(skip(ctx))]
     pub async fn apply_raw(
         &mut self,
         ctx: &mut DalContext,
         run_confirmations: bool,
     ) -> ChangeSetResult<()> {
+        self.apply_with_resolutions(ctx, run_confirmations, &[])
+            .await
+    }
+
+    /// Apply the change set after carrying out the provided
+    /// [`resolutions`](ChangeSetConflictResolution). Any conflict that is not covered by a
+    /// resolution causes the apply to be refused with [`ChangeSetError::Conflicts`], and a change
+    /// set without the approvals its workspace requires is refused with
+    /// [`ChangeSetError::NotApproved`].
+    #[instrument(skip(ctx))]
+    pub async fn apply_with_resolutions(
+        &mut self,
+        ctx: &mut DalContext,
+        run_confirmations: bool,
+        resolutions: &[ChangeSetConflictResolution],
+    ) -> ChangeSetResult<()> {
+        self.ensure_approved(ctx).await?;
+        self.resolve_conflicts(ctx, resolutions).await?;
+
         let actor = serde_json::to_value(ctx.history_actor())?;
         let row = ctx
             .txns()
@@ -152,6 +227,102 @@ impl ChangeSet {
         Ok(())
     }
 
+    /// Move this change set's base forward to the current _head_, keeping the change set's own
+    /// edits on top. Conflicts are handled the same way as in
+    /// [`Self::apply_with_resolutions()`]. Once rebased, a
+    /// [`DependentValuesUpdate`](crate::DependentValuesUpdate) is enqueued for every
+    /// [`AttributeValue`](crate::AttributeValue) that either side touched, so that derived values
+    /// in the change set reflect the new _head_.
+    #[instrument(skip(ctx))]
+    pub async fn rebase(
+        &mut self,
+        ctx: &DalContext,
+        resolutions: &[ChangeSetConflictResolution],
+    ) -> ChangeSetResult<()> {
+        if self.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::NotOpen(self.status));
+        }
+
+        self.resolve_conflicts(ctx, resolutions).await?;
+
+        let previously_based_at = self.based_at;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT timestamp_based_at FROM change_set_rebase_v1($1, $2)",
+                &[&self.pk, ctx.tenancy()],
+            )
+            .await?;
+        self.based_at = row.try_get("timestamp_based_at")?;
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.rebase",
+            "Change Set rebased",
+            &serde_json::json![{ "pk": &self.pk }],
+        )
+        .await?;
+
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                CHANGE_SET_REBASE_AFFECTED_ATTRIBUTE_VALUES,
+                &[&self.tenancy, &self.pk, &previously_based_at],
+            )
+            .await?;
+        let mut attribute_value_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            attribute_value_ids.push(row.try_get("attribute_value_id")?);
+        }
+        if !attribute_value_ids.is_empty() {
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                Visibility::new_change_set(self.pk, false),
+                attribute_value_ids,
+            ))
+            .await?;
+        }
+
+        WsEvent::change_set_rebased(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Carry out the provided [`resolutions`](ChangeSetConflictResolution) and refuse with
+    /// [`ChangeSetError::Conflicts`] if any conflict with _head_ is left uncovered.
+    async fn resolve_conflicts(
+        &self,
+        ctx: &DalContext,
+        resolutions: &[ChangeSetConflictResolution],
+    ) -> ChangeSetResult<()> {
+        for resolution in resolutions {
+            resolution.resolve(ctx, self.pk).await?;
+        }
+
+        let unresolved: Vec<ChangeSetConflict> = self
+            .conflicts(ctx)
+            .await?
+            .into_iter()
+            .filter(|conflict| {
+                !resolutions.iter().any(|resolution| {
+                    resolution.object == conflict.object
+                        && resolution.choice == ChangeSetConflictChoice::KeepChangeSet
+                })
+            })
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(ChangeSetError::Conflicts(unresolved));
+        }
+
+        Ok(())
+    }
+
     #[instrument(skip(ctx))]
     pub async fn apply(&mut self, ctx: &mut DalContext) -> ChangeSetResult<()> {
         self.apply_raw(ctx, true).await?;
@@ -201,6 +372,13 @@ impl WsEvent {
         WsEvent::new(ctx, WsPayload::ChangeSetApplied(change_set_pk)).await
     }
 
+    pub async fn change_set_rebased(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetRebased(change_set_pk)).await
+    }
+
     pub async fn change_set_canceled(
         ctx: &DalContext,
         change_set_pk: ChangeSetPk,
@@ -0,0 +1,314 @@
+//! This module contains the approval workflow for [`ChangeSets`](crate::ChangeSet).
+//!
+//! A [`Workspace`] decides how many approvals a change set needs before it can be applied (see
+//! [`Workspace::required_change_set_approvals()`]). When that is more than zero, the change set
+//! has to be [sent for review](ChangeSet::request_approval()), moving it to
+//! [`NeedsApproval`](ChangeSetStatus::NeedsApproval), and enough reviewers have to
+//! [approve](ChangeSet::approve()) it. A [rejection](ChangeSet::reject()) sends it back to
+//! [`Open`](ChangeSetStatus::Open). The user who asked for the review can't approve it themselves.
+//!
+//! Each approval records a [fingerprint](ChangeSet::fingerprint()) of the change set. Writing to
+//! the change set after it was approved changes the fingerprint, and approvals made against an
+//! earlier fingerprint no longer count towards applying it.
+
+use serde::{Deserialize, Serialize};
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+
+use crate::change_set::{ChangeSetError, ChangeSetResult, ChangeSetStatus};
+use crate::standard_model::objects_from_rows;
+use crate::ws_event::{WsEvent, WsPayload};
+use crate::{
+    pk, ChangeSet, ChangeSetPk, DalContext, HistoryActor, HistoryEvent, Tenancy, Timestamp, UserPk,
+    Workspace, WsEventResult,
+};
+
+const CHANGE_SET_LIST_APPROVALS: &str = include_str!("../queries/change_set/list_approvals.sql");
+const CHANGE_SET_LIST_REVIEWERS: &str = include_str!("../queries/change_set/list_reviewers.sql");
+
+pk!(ChangeSetApprovalPk);
+
+/// What a reviewer decided about a [`ChangeSet`].
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, AsRefStr,
+)]
+pub enum ChangeSetApprovalDecision {
+    Approved,
+    Rejected,
+}
+
+/// A reviewer's decision about a [`ChangeSet`]. Each reviewer has at most one, the latest.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ChangeSetApproval {
+    pub pk: ChangeSetApprovalPk,
+    pub change_set_pk: ChangeSetPk,
+    pub user_pk: UserPk,
+    pub decision: ChangeSetApprovalDecision,
+    pub comment: Option<String>,
+    /// The [fingerprint](ChangeSet::fingerprint()) of the change set when the decision was made.
+    #[serde(default)]
+    pub change_set_fingerprint: Option<String>,
+    #[serde(flatten)]
+    pub tenancy: Tenancy,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+impl ChangeSetApproval {
+    #[instrument(skip(ctx))]
+    pub async fn list_for_change_set(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> ChangeSetResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(CHANGE_SET_LIST_APPROVALS, &[ctx.tenancy(), &change_set_pk])
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+}
+
+impl ChangeSet {
+    /// Send the change set for review by the provided reviewers, moving it to
+    /// [`NeedsApproval`](ChangeSetStatus::NeedsApproval). Any decisions made in an earlier round
+    /// of review are cleared. With no reviewers, any user other than the requester may review.
+    #[instrument(skip(ctx))]
+    pub async fn request_approval(
+        &mut self,
+        ctx: &DalContext,
+        reviewers: Vec<UserPk>,
+    ) -> ChangeSetResult<()> {
+        if self.status != ChangeSetStatus::Open {
+            return Err(ChangeSetError::NotOpen(self.status));
+        }
+        let requested_by = Self::acting_user(ctx)?;
+        if reviewers.contains(&requested_by) {
+            return Err(ChangeSetError::SelfReview(requested_by));
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT timestamp_updated_at FROM change_set_request_approval_v1($1, $2, $3, $4)",
+                &[&self.pk, &requested_by, &reviewers, &self.tenancy],
+            )
+            .await?;
+        self.timestamp.updated_at = row.try_get("timestamp_updated_at")?;
+        self.status = ChangeSetStatus::NeedsApproval;
+        self.approval_requested_by = Some(requested_by);
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.request_approval",
+            "Change Set approval requested",
+            &serde_json::json![{ "pk": &self.pk, "reviewers": &reviewers }],
+        )
+        .await?;
+        WsEvent::change_set_approval_requested(ctx, self.pk)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Approve the change set as the acting user.
+    #[instrument(skip(ctx))]
+    pub async fn approve(
+        &mut self,
+        ctx: &DalContext,
+        comment: Option<String>,
+    ) -> ChangeSetResult<ChangeSetApproval> {
+        self.decide(ctx, ChangeSetApprovalDecision::Approved, comment)
+            .await
+    }
+
+    /// Reject the change set as the acting user, sending it back to
+    /// [`Open`](ChangeSetStatus::Open).
+    #[instrument(skip(ctx))]
+    pub async fn reject(
+        &mut self,
+        ctx: &DalContext,
+        comment: Option<String>,
+    ) -> ChangeSetResult<ChangeSetApproval> {
+        self.decide(ctx, ChangeSetApprovalDecision::Rejected, comment)
+            .await
+    }
+
+    async fn decide(
+        &mut self,
+        ctx: &DalContext,
+        decision: ChangeSetApprovalDecision,
+        comment: Option<String>,
+    ) -> ChangeSetResult<ChangeSetApproval> {
+        if self.status != ChangeSetStatus::NeedsApproval {
+            return Err(ChangeSetError::NotAwaitingApproval(self.status));
+        }
+        let user_pk = Self::acting_user(ctx)?;
+        if self.approval_requested_by == Some(user_pk) {
+            return Err(ChangeSetError::SelfReview(user_pk));
+        }
+        let reviewers = self.reviewers(ctx).await?;
+        if !reviewers.is_empty() && !reviewers.contains(&user_pk) {
+            return Err(ChangeSetError::NotAReviewer(user_pk));
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM change_set_decide_approval_v1($1, $2, $3, $4, $5)",
+                &[
+                    &self.pk,
+                    &user_pk,
+                    &decision.as_ref(),
+                    &comment,
+                    &self.tenancy,
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let approval: ChangeSetApproval = serde_json::from_value(json)?;
+
+        let (label, message) = match decision {
+            ChangeSetApprovalDecision::Approved => ("change_set.approve", "Change Set approved"),
+            ChangeSetApprovalDecision::Rejected => {
+                self.status = ChangeSetStatus::Open;
+                ("change_set.reject", "Change Set rejected")
+            }
+        };
+        let _history_event = HistoryEvent::new(
+            ctx,
+            label,
+            message,
+            &serde_json::json![{ "pk": &self.pk, "userPk": user_pk, "comment": &approval.comment }],
+        )
+        .await?;
+        WsEvent::change_set_approval_decided(ctx, self.pk, user_pk, decision)
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(approval)
+    }
+
+    /// List the users asked to review the change set. An empty list means any user other than the
+    /// requester may review it.
+    #[instrument(skip(ctx))]
+    pub async fn reviewers(&self, ctx: &DalContext) -> ChangeSetResult<Vec<UserPk>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(CHANGE_SET_LIST_REVIEWERS, &[&self.tenancy, &self.pk])
+            .await?;
+        let mut reviewers = Vec::with_capacity(rows.len());
+        for row in rows {
+            reviewers.push(row.try_get("user_pk")?);
+        }
+        Ok(reviewers)
+    }
+
+    /// List the decisions reviewers have made in the current round of review.
+    pub async fn approvals(&self, ctx: &DalContext) -> ChangeSetResult<Vec<ChangeSetApproval>> {
+        ChangeSetApproval::list_for_change_set(ctx, self.pk).await
+    }
+
+    /// A digest of every row the change set holds. It changes whenever anything is written to the
+    /// change set.
+    #[instrument(skip(ctx))]
+    pub async fn fingerprint(&self, ctx: &DalContext) -> ChangeSetResult<String> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT fingerprint FROM change_set_fingerprint_v1($1, $2)",
+                &[&self.pk, &self.tenancy],
+            )
+            .await?;
+        Ok(row.try_get("fingerprint")?)
+    }
+
+    /// Refuse with [`ChangeSetError::NotApproved`] unless the change set has as many approvals as
+    /// its [`Workspace`] requires. Approvals made before the latest write to the change set don't
+    /// count.
+    pub(crate) async fn ensure_approved(&self, ctx: &DalContext) -> ChangeSetResult<()> {
+        let required = match self.tenancy.workspace_pk() {
+            Some(workspace_pk) => Workspace::get_by_pk(ctx, &workspace_pk)
+                .await?
+                .map(|workspace| workspace.required_change_set_approvals())
+                .unwrap_or_default(),
+            None => 0,
+        };
+        if required <= 0 {
+            return Ok(());
+        }
+
+        let approved = if self.status == ChangeSetStatus::NeedsApproval {
+            let fingerprint = self.fingerprint(ctx).await?;
+            self.approvals(ctx)
+                .await?
+                .iter()
+                .filter(|approval| {
+                    approval.decision == ChangeSetApprovalDecision::Approved
+                        && approval.change_set_fingerprint.as_deref() == Some(fingerprint.as_str())
+                })
+                .count() as i32
+        } else {
+            0
+        };
+        if approved < required {
+            return Err(ChangeSetError::NotApproved { required, approved });
+        }
+
+        Ok(())
+    }
+
+    fn acting_user(ctx: &DalContext) -> ChangeSetResult<UserPk> {
+        match ctx.history_actor() {
+            HistoryActor::User(user_pk) => Ok(*user_pk),
+            HistoryActor::SystemInit => Err(ChangeSetError::NoUserActor),
+        }
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetApprovalDecidedPayload {
+    change_set_pk: ChangeSetPk,
+    user_pk: UserPk,
+    decision: ChangeSetApprovalDecision,
+}
+
+impl WsEvent {
+    pub async fn change_set_approval_requested(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(ctx, WsPayload::ChangeSetApprovalRequested(change_set_pk)).await
+    }
+
+    pub async fn change_set_approval_decided(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+        user_pk: UserPk,
+        decision: ChangeSetApprovalDecision,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::ChangeSetApprovalDecided(ChangeSetApprovalDecidedPayload {
+                change_set_pk,
+                user_pk,
+                decision,
+            }),
+        )
+        .await
+    }
+}
@@ -0,0 +1,206 @@
+//! This module contains [`ChangeSetConflict`], the result of a three-way comparison between a
+//! [`ChangeSet`](crate::ChangeSet), the _base_ it was opened against and the current _head_.
+//!
+//! We do not keep a copy of the _base_ around. Instead, every row that a change set touches is
+//! copied from _head_ on first write, so the copy's `created_at` is the moment the change set
+//! "forked" that object. If the _head_ row has been updated since then, both sides have moved and
+//! we compare their contents to see if there is anything to choose between.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgRow;
+use std::str::FromStr;
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+
+use crate::change_set::{ChangeSetError, ChangeSetResult};
+use crate::edge::EdgeId;
+use crate::{AttributeValueId, ChangeSetPk, ComponentId, DalContext, HistoryEvent, PropId};
+
+const CONFLICTS_ATTRIBUTE_VALUES: &str =
+    include_str!("../queries/change_set/conflicts_attribute_values.sql");
+const CONFLICTS_COMPONENTS: &str = include_str!("../queries/change_set/conflicts_components.sql");
+const CONFLICTS_EDGES: &str = include_str!("../queries/change_set/conflicts_edges.sql");
+
+/// How the [`ChangeSet`](crate::ChangeSet) and _head_ disagree about an object.
+#[remain::sorted]
+#[derive(
+    Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, AsRefStr,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSetConflictKind {
+    /// The change set deleted the object, but _head_ modified it after the change set forked it.
+    DeletedInChangeSetModifiedOnHead,
+    /// Both sides modified the object and ended up with different contents.
+    ModifiedInBoth,
+    /// The change set modified the object, but _head_ deleted it after the change set forked it.
+    ModifiedInChangeSetDeletedOnHead,
+}
+
+/// The object that a [`ChangeSetConflict`] is about.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ChangeSetConflictObject {
+    #[serde(rename_all = "camelCase")]
+    AttributeValue {
+        id: AttributeValueId,
+        component_id: ComponentId,
+        prop_id: PropId,
+    },
+    #[serde(rename_all = "camelCase")]
+    Component { id: ComponentId },
+    #[serde(rename_all = "camelCase")]
+    Edge { id: EdgeId },
+}
+
+impl ChangeSetConflictObject {
+    fn table_name(&self) -> &'static str {
+        match self {
+            Self::AttributeValue { .. } => "attribute_values",
+            Self::Component { .. } => "components",
+            Self::Edge { .. } => "edges",
+        }
+    }
+}
+
+/// A single object that was changed both in a [`ChangeSet`](crate::ChangeSet) and on _head_
+/// since the change set was opened. Generated by
+/// [`ChangeSet::conflicts()`](crate::ChangeSet::conflicts()).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetConflict {
+    pub object: ChangeSetConflictObject,
+    pub kind: ChangeSetConflictKind,
+    /// The contents of the object in the change set. For
+    /// [`AttributeValues`](crate::AttributeValue), this is the value itself.
+    pub change_set_value: Option<serde_json::Value>,
+    /// The contents of the object on _head_. For [`AttributeValues`](crate::AttributeValue), this
+    /// is the value itself.
+    pub head_value: Option<serde_json::Value>,
+    /// When _head_ last changed the object.
+    pub head_updated_at: DateTime<Utc>,
+}
+
+/// Which side wins when applying a conflicting object.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSetConflictChoice {
+    /// Apply the change set's version of the object, overwriting _head_.
+    KeepChangeSet,
+    /// Throw away the change set's version of the object, leaving _head_ untouched.
+    KeepHead,
+}
+
+/// A user's decision for one [`ChangeSetConflict`], passed to
+/// [`ChangeSet::apply_with_resolutions()`](crate::ChangeSet::apply_with_resolutions()).
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetConflictResolution {
+    pub object: ChangeSetConflictObject,
+    pub choice: ChangeSetConflictChoice,
+}
+
+impl ChangeSetConflict {
+    /// Find every [`AttributeValue`](crate::AttributeValue), [`Component`](crate::Component) and
+    /// [`Edge`](crate::Edge) in the [`ChangeSet`](crate::ChangeSet) that would silently overwrite
+    /// a newer change on _head_ if the change set were applied.
+    #[instrument(skip(ctx))]
+    pub async fn list_for_change_set(
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> ChangeSetResult<Vec<Self>> {
+        let txns = ctx.txns().await?;
+        let mut conflicts = Vec::new();
+
+        for row in txns
+            .pg()
+            .query(CONFLICTS_ATTRIBUTE_VALUES, &[ctx.tenancy(), &change_set_pk])
+            .await?
+        {
+            let object = ChangeSetConflictObject::AttributeValue {
+                id: row.try_get("object_id")?,
+                component_id: row.try_get("component_id")?,
+                prop_id: row.try_get("prop_id")?,
+            };
+            conflicts.push(Self::from_row(object, &row)?);
+        }
+
+        for row in txns
+            .pg()
+            .query(CONFLICTS_COMPONENTS, &[ctx.tenancy(), &change_set_pk])
+            .await?
+        {
+            let object = ChangeSetConflictObject::Component {
+                id: row.try_get("object_id")?,
+            };
+            conflicts.push(Self::from_row(object, &row)?);
+        }
+
+        for row in txns
+            .pg()
+            .query(CONFLICTS_EDGES, &[ctx.tenancy(), &change_set_pk])
+            .await?
+        {
+            let object = ChangeSetConflictObject::Edge {
+                id: row.try_get("object_id")?,
+            };
+            conflicts.push(Self::from_row(object, &row)?);
+        }
+
+        Ok(conflicts)
+    }
+
+    fn from_row(object: ChangeSetConflictObject, row: &PgRow) -> ChangeSetResult<Self> {
+        let kind: String = row.try_get("conflict_kind")?;
+        let kind = ChangeSetConflictKind::from_str(&kind)
+            .map_err(|_| ChangeSetError::UnknownConflictKind(kind))?;
+
+        Ok(Self {
+            object,
+            kind,
+            change_set_value: row.try_get("change_set_value")?,
+            head_value: row.try_get("head_value")?,
+            head_updated_at: row.try_get("head_updated_at")?,
+        })
+    }
+}
+
+impl ChangeSetConflictResolution {
+    /// Carry out the resolution for the given [`ChangeSet`](crate::ChangeSet). Choosing _head_
+    /// discards the change set's copy of the object; choosing the change set is a no-op, since
+    /// applying will overwrite _head_ anyway.
+    #[instrument(skip(ctx))]
+    pub async fn resolve(
+        &self,
+        ctx: &DalContext,
+        change_set_pk: ChangeSetPk,
+    ) -> ChangeSetResult<()> {
+        if self.choice == ChangeSetConflictChoice::KeepHead {
+            let id: &(dyn postgres_types::ToSql + Sync) = match &self.object {
+                ChangeSetConflictObject::AttributeValue { id, .. } => id,
+                ChangeSetConflictObject::Component { id } => id,
+                ChangeSetConflictObject::Edge { id } => id,
+            };
+            ctx.txns()
+                .await?
+                .pg()
+                .execute(
+                    "SELECT change_set_discard_object_v1($1, $2, $3, $4)",
+                    &[&self.object.table_name(), &change_set_pk, id, ctx.tenancy()],
+                )
+                .await?;
+        }
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "change_set.conflict_resolved",
+            "Change Set conflict resolved",
+            &serde_json::json![{ "pk": change_set_pk, "resolution": self }],
+        )
+        .await?;
+
+        Ok(())
+    }
+}
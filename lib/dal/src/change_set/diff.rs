@@ -0,0 +1,340 @@
+//! This module contains [`ChangeSetDiff`], which describes everything a
+//! [`ChangeSet`](crate::ChangeSet) would do to _head_ if it were applied.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_data_pg::PgRow;
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use telemetry::prelude::*;
+
+use crate::change_set::{ChangeSetError, ChangeSetResult};
+use crate::change_status::{ChangeStatus, ComponentChangeStatus, ComponentChangeStatusGroup};
+use crate::edge::EdgeId;
+use crate::{
+    ComponentId, ComponentView, ComponentViewProperties, DalContext, FuncId, SchemaVariantId,
+    SocketId,
+};
+
+const DIFF_EDGES: &str = include_str!("../queries/change_set/diff_edges.sql");
+const DIFF_FUNCS: &str = include_str!("../queries/change_set/diff_funcs.sql");
+const DIFF_SCHEMA_VARIANTS: &str = include_str!("../queries/change_set/diff_schema_variants.sql");
+
+/// A single value that differs between _head_ and the [`ChangeSet`](crate::ChangeSet), addressed
+/// by a [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) into the component's
+/// properties (e.g. `/root/domain/region`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttributeChange {
+    pub pointer: String,
+    pub change_status: ChangeStatus,
+    /// The value on _head_. Empty if the value was added.
+    pub head: Option<Value>,
+    /// The value in the change set. Empty if the value was deleted.
+    pub change_set: Option<Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentChangeSetDiff {
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub change_status: ChangeStatus,
+    /// The individual values that changed. Always empty for deleted components.
+    pub attribute_changes: Vec<AttributeChange>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeChangeSetDiff {
+    pub edge_id: EdgeId,
+    pub change_status: ChangeStatus,
+    pub head_component_id: ComponentId,
+    pub head_socket_id: SocketId,
+    pub tail_component_id: ComponentId,
+    pub tail_socket_id: SocketId,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FuncChangeSetDiff {
+    pub func_id: FuncId,
+    pub name: String,
+    pub change_status: ChangeStatus,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVariantChangeSetDiff {
+    pub schema_variant_id: SchemaVariantId,
+    pub name: String,
+    pub change_status: ChangeStatus,
+}
+
+/// Every [`Component`](crate::Component), [`Edge`](crate::Edge), [`Func`](crate::Func) and
+/// [`SchemaVariant`](crate::SchemaVariant) added, removed or modified in the current
+/// [`ChangeSet`](crate::ChangeSet), relative to _head_. Generated by [`Self::new()`].
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSetDiff {
+    pub components: Vec<ComponentChangeSetDiff>,
+    pub edges: Vec<EdgeChangeSetDiff>,
+    pub funcs: Vec<FuncChangeSetDiff>,
+    pub schema_variants: Vec<SchemaVariantChangeSetDiff>,
+}
+
+impl ChangeSetDiff {
+    /// Generate the diff for the [`ChangeSet`](crate::ChangeSet) in the provided
+    /// [`DalContext`]'s [`Visibility`](crate::Visibility). The diff is empty on _head_.
+    #[instrument(skip_all)]
+    pub async fn new(ctx: &DalContext) -> ChangeSetResult<Self> {
+        if ctx.visibility().is_head() {
+            return Ok(Self::default());
+        }
+
+        let mut components = Vec::new();
+        for group in ComponentChangeStatus::list_added(ctx).await? {
+            components.push(Self::component_diff(ctx, group).await?);
+        }
+        for group in ComponentChangeStatus::list_deleted(ctx).await? {
+            components.push(Self::component_diff(ctx, group).await?);
+        }
+        for group in ComponentChangeStatus::list_modified(ctx).await? {
+            let diff = Self::component_diff(ctx, group).await?;
+            // Components can have modified attribute values that end up with the same contents
+            // as head (e.g. after dependent values update), which is no change at all.
+            if !diff.attribute_changes.is_empty() {
+                components.push(diff);
+            }
+        }
+
+        let txns = ctx.txns().await?;
+        let change_set_pk = ctx.visibility().change_set_pk;
+
+        let mut edges = Vec::new();
+        for row in txns
+            .pg()
+            .query(DIFF_EDGES, &[ctx.tenancy(), &change_set_pk])
+            .await?
+        {
+            edges.push(EdgeChangeSetDiff {
+                edge_id: row.try_get("id")?,
+                change_status: change_status_from_row(&row)?,
+                head_component_id: row.try_get("head_component_id")?,
+                head_socket_id: row.try_get("head_socket_id")?,
+                tail_component_id: row.try_get("tail_component_id")?,
+                tail_socket_id: row.try_get("tail_socket_id")?,
+            });
+        }
+
+        let mut funcs = Vec::new();
+        for row in txns
+            .pg()
+            .query(DIFF_FUNCS, &[ctx.tenancy(), &change_set_pk])
+            .await?
+        {
+            funcs.push(FuncChangeSetDiff {
+                func_id: row.try_get("id")?,
+                name: row.try_get("name")?,
+                change_status: change_status_from_row(&row)?,
+            });
+        }
+
+        let mut schema_variants = Vec::new();
+        for row in txns
+            .pg()
+            .query(DIFF_SCHEMA_VARIANTS, &[ctx.tenancy(), &change_set_pk])
+            .await?
+        {
+            schema_variants.push(SchemaVariantChangeSetDiff {
+                schema_variant_id: row.try_get("id")?,
+                name: row.try_get("name")?,
+                change_status: change_status_from_row(&row)?,
+            });
+        }
+
+        Ok(Self {
+            components,
+            edges,
+            funcs,
+            schema_variants,
+        })
+    }
+
+    async fn component_diff(
+        ctx: &DalContext,
+        group: ComponentChangeStatusGroup,
+    ) -> ChangeSetResult<ComponentChangeSetDiff> {
+        let attribute_changes = match group.component_status {
+            ChangeStatus::Added => {
+                let current = Self::component_properties(ctx, group.component_id).await?;
+                diff_values(None, Some(&current))
+            }
+            ChangeStatus::Modified => {
+                let head_ctx = ctx.clone_with_head();
+                let head = Self::component_properties(&head_ctx, group.component_id).await?;
+                let current = Self::component_properties(ctx, group.component_id).await?;
+                diff_values(Some(&head), Some(&current))
+            }
+            ChangeStatus::Deleted | ChangeStatus::Unmodified => Vec::new(),
+        };
+
+        Ok(ComponentChangeSetDiff {
+            component_id: group.component_id,
+            component_name: group.component_name,
+            change_status: group.component_status,
+            attribute_changes,
+        })
+    }
+
+    /// The user-facing (i.e. "/root/si" and "/root/domain") properties for a
+    /// [`Component`](crate::Component), keyed under "root" so that pointers line up with prop
+    /// paths.
+    async fn component_properties(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> ChangeSetResult<Value> {
+        let view = ComponentView::new(ctx, component_id).await?;
+        if view.properties.is_null() {
+            return Ok(serde_json::json!({ "root": {} }));
+        }
+
+        let mut properties = ComponentViewProperties::try_from(view)?;
+        properties.drop_private();
+        Ok(serde_json::json!({ "root": properties.to_value()? }))
+    }
+}
+
+fn change_status_from_row(row: &PgRow) -> ChangeSetResult<ChangeStatus> {
+    let change_status: String = row.try_get("change_status")?;
+    ChangeStatus::from_str(&change_status)
+        .map_err(|_| ChangeSetError::UnknownChangeStatus(change_status))
+}
+
+/// Walk both values in lockstep and report every leaf (or whole subtree, when only one side has
+/// it) that differs.
+pub fn diff_values(head: Option<&Value>, change_set: Option<&Value>) -> Vec<AttributeChange> {
+    let mut changes = Vec::new();
+    diff_values_at(String::new(), head, change_set, &mut changes);
+    changes
+}
+
+fn diff_values_at(
+    pointer: String,
+    head: Option<&Value>,
+    change_set: Option<&Value>,
+    changes: &mut Vec<AttributeChange>,
+) {
+    match (head, change_set) {
+        (Some(Value::Object(head_map)), Some(Value::Object(change_set_map))) => {
+            let keys: BTreeSet<&String> = head_map.keys().chain(change_set_map.keys()).collect();
+            for key in keys {
+                diff_values_at(
+                    format!("{pointer}/{}", escape_pointer_token(key)),
+                    head_map.get(key),
+                    change_set_map.get(key),
+                    changes,
+                );
+            }
+        }
+        (Some(Value::Array(head_items)), Some(Value::Array(change_set_items))) => {
+            for index in 0..head_items.len().max(change_set_items.len()) {
+                diff_values_at(
+                    format!("{pointer}/{index}"),
+                    head_items.get(index),
+                    change_set_items.get(index),
+                    changes,
+                );
+            }
+        }
+        (None, None) => {}
+        (head, change_set) if head == change_set => {}
+        (head, change_set) => changes.push(AttributeChange {
+            pointer,
+            change_status: match (head, change_set) {
+                (None, _) => ChangeStatus::Added,
+                (_, None) => ChangeStatus::Deleted,
+                _ => ChangeStatus::Modified,
+            },
+            head: head.cloned(),
+            change_set: change_set.cloned(),
+        }),
+    }
+}
+
+/// Escape a single reference token per RFC 6901.
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diff_values_reports_leaves() {
+        let head = json!({
+            "root": {
+                "si": { "name": "vpc" },
+                "domain": { "cidr": "10.0.0.0/16", "tags": ["a"] },
+            },
+        });
+        let change_set = json!({
+            "root": {
+                "si": { "name": "vpc" },
+                "domain": { "cidr": "10.1.0.0/16", "tags": ["a", "b"], "region": "us-east-2" },
+            },
+        });
+
+        let changes = diff_values(Some(&head), Some(&change_set));
+
+        assert_eq!(
+            vec![
+                AttributeChange {
+                    pointer: "/root/domain/cidr".to_string(),
+                    change_status: ChangeStatus::Modified,
+                    head: Some(json!("10.0.0.0/16")),
+                    change_set: Some(json!("10.1.0.0/16")),
+                },
+                AttributeChange {
+                    pointer: "/root/domain/region".to_string(),
+                    change_status: ChangeStatus::Added,
+                    head: None,
+                    change_set: Some(json!("us-east-2")),
+                },
+                AttributeChange {
+                    pointer: "/root/domain/tags/1".to_string(),
+                    change_status: ChangeStatus::Added,
+                    head: None,
+                    change_set: Some(json!("b")),
+                },
+            ],
+            changes
+        );
+    }
+
+    #[test]
+    fn diff_values_reports_removed_subtrees_and_escapes_pointers() {
+        let head = json!({ "a/b": { "c": 1 }, "d~e": 2 });
+        let change_set = json!({ "d~e": 2 });
+
+        let changes = diff_values(Some(&head), Some(&change_set));
+
+        assert_eq!(
+            vec![AttributeChange {
+                pointer: "/a~1b".to_string(),
+                change_status: ChangeStatus::Deleted,
+                head: Some(json!({ "c": 1 })),
+                change_set: None,
+            }],
+            changes
+        );
+    }
+
+    #[test]
+    fn diff_values_identical_is_empty() {
+        let value = json!({ "root": { "domain": { "cidr": "10.0.0.0/16" } } });
+        assert!(diff_values(Some(&value), Some(&value)).is_empty());
+    }
+}
@@ -129,7 +129,7 @@ impl ComponentChangeStatus {
 #[serde(rename_all = "camelCase")]
 pub struct ComponentChangeStatusGroup {
     pub component_id: ComponentId,
-    component_name: String,
+    pub component_name: String,
     pub component_status: ChangeStatus,
 }
 
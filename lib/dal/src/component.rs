@@ -17,6 +17,7 @@ use crate::code_view::CodeViewError;
 use crate::func::binding::FuncBindingError;
 use crate::func::binding_return_value::{FuncBindingReturnValueError, FuncBindingReturnValueId};
 use crate::job::definition::DependentValuesUpdate;
+use crate::prop::PropPath;
 use crate::schema::variant::root_prop::SiPropChild;
 use crate::schema::variant::{SchemaVariantError, SchemaVariantId};
 use crate::schema::SchemaVariant;
@@ -32,10 +33,10 @@ use crate::{
     AttributePrototypeError, AttributePrototypeId, AttributeReadContext, ComponentType, DalContext,
     EdgeError, ExternalProvider, ExternalProviderError, ExternalProviderId, FixError, FixId, Func,
     FuncBackendKind, FuncError, HistoryActor, HistoryEventError, InternalProvider,
-    InternalProviderId, Node, NodeError, PropError, PropId, RootPropChild, Schema, SchemaError,
-    SchemaId, Socket, StandardModel, StandardModelError, Tenancy, Timestamp, TransactionsError,
-    UserPk, ValidationPrototypeError, ValidationResolverError, Visibility, WorkspaceError, WsEvent,
-    WsEventResult, WsPayload,
+    InternalProviderId, Node, NodeError, Prop, PropError, PropId, PropKind, RootPropChild, Schema,
+    SchemaError, SchemaId, Socket, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, UserPk, ValidationPrototypeError, ValidationResolverError, Visibility,
+    VisibilityError, WorkspaceError, WsEvent, WsEventResult, WsPayload,
 };
 use crate::{AttributeValueId, QualificationError};
 use crate::{Edge, FixResolverError, NodeKind};
@@ -49,7 +50,9 @@ pub mod status;
 pub mod validation;
 pub mod view;
 
-pub use view::{ComponentView, ComponentViewError, ComponentViewProperties};
+pub use view::{
+    ComponentView, ComponentViewError, ComponentViewProperties, ComponentViewSecretReference,
+};
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -155,6 +158,8 @@ pub enum ComponentError {
     PgPool(#[from] si_data_pg::PgPoolError),
     #[error("prop error: {0}")]
     Prop(#[from] PropError),
+    #[error("prop at path {0} can't be set directly, as its parent is not an object")]
+    PropNotSettableByPath(String),
     #[error("qualification error: {0}")]
     Qualification(#[from] QualificationError),
     #[error("qualification result for {0} on component {1} has no value")]
@@ -177,6 +182,8 @@ pub enum ComponentError {
     ValidationPrototype(#[from] ValidationPrototypeError),
     #[error("validation resolver error: {0}")]
     ValidationResolver(#[from] ValidationResolverError),
+    #[error("visibility error: {0}")]
+    Visibility(#[from] VisibilityError),
     #[error("workspace error: {0}")]
     Workspace(#[from] WorkspaceError),
     #[error("ws event error: {0}")]
@@ -189,6 +196,8 @@ const FIND_FOR_NODE: &str = include_str!("queries/component/find_for_node.sql");
 const FIND_SI_CHILD_PROP_ATTRIBUTE_VALUE: &str =
     include_str!("queries/component/find_si_child_attribute_value.sql");
 const LIST_FOR_SCHEMA_VARIANT: &str = include_str!("queries/component/list_for_schema_variant.sql");
+const LIST_IMPORTED_FOR_SCHEMA_VARIANT: &str =
+    include_str!("queries/component/list_imported_for_schema_variant.sql");
 const LIST_SOCKETS_FOR_SOCKET_EDGE_KIND: &str =
     include_str!("queries/component/list_sockets_for_socket_edge_kind.sql");
 const FIND_NAME: &str = include_str!("queries/component/find_name.sql");
@@ -245,6 +254,9 @@ pub struct Component {
     kind: ComponentKind,
     pub deletion_user_pk: Option<UserPk>,
     needs_destroy: bool,
+    /// The identifier of the existing "resource" the component was imported from, if any.
+    #[serde(default)]
+    import_identifier: Option<String>,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -387,6 +399,7 @@ impl Component {
 
     standard_model_accessor!(kind, Enum(ComponentKind), ComponentResult);
     standard_model_accessor!(needs_destroy, bool, ComponentResult);
+    standard_model_accessor!(import_identifier, Option<String>, ComponentResult);
 
     standard_model_belongs_to!(
         lookup_fn: schema,
@@ -537,6 +550,33 @@ impl Component {
         Ok(results)
     }
 
+    /// Lists the [`Components`](Self) of the [`SchemaVariant`] that were imported from the
+    /// "resource" with the given identifier, on _head_ or in any open change set of the workspace.
+    pub async fn list_imported_for_schema_variant(
+        ctx: &DalContext,
+        schema_variant_id: SchemaVariantId,
+        identifier: &str,
+    ) -> ComponentResult<Vec<ComponentId>> {
+        let mut component_ids = Vec::new();
+        for visibility in Visibility::list_open_in_workspace(ctx).await? {
+            let rows = ctx
+                .txns()
+                .await?
+                .pg()
+                .query(
+                    LIST_IMPORTED_FOR_SCHEMA_VARIANT,
+                    &[ctx.tenancy(), &visibility, &schema_variant_id, &identifier],
+                )
+                .await?;
+            for row in rows {
+                component_ids.push(row.try_get("component_id")?);
+            }
+        }
+        component_ids.sort();
+        component_ids.dedup();
+        Ok(component_ids)
+    }
+
     /// Sets the "/root/si/name" for [`self`](Self).
     #[instrument(skip_all)]
     pub async fn set_name<T: Serialize + std::fmt::Debug + std::clone::Clone>(
@@ -592,6 +632,85 @@ impl Component {
         Ok(())
     }
 
+    /// Sets the value of the [`Prop`](crate::Prop) found at the given path (e.g.
+    /// `["root", "domain", "region"]`) for [`self`](Self), returning the updated
+    /// [`AttributeValueId`]. Only props whose parent is an object can be set this way.
+    #[instrument(skip(self, ctx))]
+    pub async fn set_value_by_prop_path(
+        &self,
+        ctx: &DalContext,
+        path: &PropPath,
+        value: Option<Value>,
+    ) -> ComponentResult<AttributeValueId> {
+        let schema_variant_id = Self::schema_variant_id(ctx, self.id).await?;
+        let prop = Prop::find_prop_by_path(ctx, schema_variant_id, path).await?;
+        let parent_prop = prop
+            .parent_prop(ctx)
+            .await?
+            .filter(|parent_prop| *parent_prop.kind() == PropKind::Object)
+            .ok_or_else(|| ComponentError::PropNotSettableByPath(path.with_replaced_sep("/")))?;
+
+        let base_read_context = AttributeReadContext {
+            prop_id: None,
+            component_id: Some(self.id),
+            ..AttributeReadContext::default()
+        };
+        let read_context = AttributeReadContext {
+            prop_id: Some(*prop.id()),
+            ..base_read_context
+        };
+        let attribute_value = AttributeValue::find_for_context(ctx, read_context)
+            .await?
+            .ok_or(ComponentError::AttributeValueNotFoundForContext(
+                read_context,
+            ))?;
+        let parent_read_context = AttributeReadContext {
+            prop_id: Some(*parent_prop.id()),
+            ..base_read_context
+        };
+        let parent_attribute_value = AttributeValue::find_for_context(ctx, parent_read_context)
+            .await?
+            .ok_or(ComponentError::AttributeValueNotFoundForContext(
+                parent_read_context,
+            ))?;
+
+        let attribute_context = AttributeContextBuilder::from(base_read_context)
+            .set_prop_id(*prop.id())
+            .to_context()?;
+        let (_, attribute_value_id) = AttributeValue::update_for_context(
+            ctx,
+            *attribute_value.id(),
+            Some(*parent_attribute_value.id()),
+            attribute_context,
+            value,
+            None,
+        )
+        .await?;
+
+        Ok(attribute_value_id)
+    }
+
+    /// Re-runs the qualifications for [`self`](Self) by running a
+    /// [`DependentValuesUpdate`](crate::job::definition::DependentValuesUpdate) job for its
+    /// "/root/domain" [`AttributeValue`](crate::AttributeValue).
+    pub async fn run_qualifications(&self, ctx: &DalContext) -> ComponentResult<()> {
+        let domain_attribute_value = Self::root_prop_child_attribute_value_for_component(
+            ctx,
+            self.id,
+            RootPropChild::Domain,
+        )
+        .await?;
+
+        ctx.enqueue_job(DependentValuesUpdate::new(
+            ctx.access_builder(),
+            *ctx.visibility(),
+            vec![*domain_attribute_value.id()],
+        ))
+        .await?;
+
+        Ok(())
+    }
+
     #[instrument(skip_all)]
     pub async fn set_deleted_at(
         &self,
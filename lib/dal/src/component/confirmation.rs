@@ -155,6 +155,9 @@ impl Component {
                     ActionKind::Delete => {
                         delete_recommendations.push(recommendation_component_specific)
                     }
+                    // Import actions create components rather than acting on existing ones, so
+                    // there is nothing to recommend.
+                    ActionKind::Import => {}
                 }
             }
 
@@ -102,6 +102,31 @@ impl Component {
             return Err(ComponentError::CannotUpdateResourceTreeInChangeSet);
         }
 
+        self.write_resource(ctx, result, trigger_dependent_values_update)
+            .await
+    }
+
+    /// Sets "/root/resource" for a [`Component`] that was just imported in a change set from a
+    /// "resource" which already exists, so that applying the change set carries the resource over
+    /// to head.
+    ///
+    /// Unlike [`Self::set_resource`], this does not require head, since the "resource" is
+    /// discovered rather than created by running an action.
+    pub(crate) async fn set_imported_resource(
+        &self,
+        ctx: &DalContext,
+        result: ActionRunResult,
+    ) -> ComponentResult<()> {
+        self.write_resource(ctx, result, true).await?;
+        Ok(())
+    }
+
+    async fn write_resource(
+        &self,
+        ctx: &DalContext,
+        result: ActionRunResult,
+        trigger_dependent_values_update: bool,
+    ) -> ComponentResult<bool> {
         let resource_attribute_value = Component::root_prop_child_attribute_value_for_component(
             ctx,
             self.id,
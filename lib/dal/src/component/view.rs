@@ -1,13 +1,15 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::str::FromStr;
 use thiserror::Error;
 
 use crate::{
     component::ComponentKind, func::binding_return_value::FuncBindingReturnValueId,
-    AttributeReadContext, AttributeValue, AttributeValueError, Component, ComponentId, DalContext,
-    EncryptedSecret, FuncBindingReturnValue, InternalProvider, InternalProviderError, PropError,
-    PropId, SchemaVariantId, SecretError, SecretId, StandardModel, StandardModelError,
+    property_editor::schema::WidgetKind, AttributeReadContext, AttributeValue, AttributeValueError,
+    Component, ComponentId, DalContext, EncryptedSecret, FuncBindingReturnValue, InternalProvider,
+    InternalProviderError, PropError, PropId, PropKind, SchemaVariant, SchemaVariantError,
+    SchemaVariantId, SecretError, SecretId, StandardModel, StandardModelError,
 };
 
 pub mod properties;
@@ -42,6 +44,8 @@ pub enum ComponentViewError {
     #[error(transparent)]
     Prop(#[from] PropError),
     #[error(transparent)]
+    SchemaVariant(#[from] SchemaVariantError),
+    #[error(transparent)]
     Secret(#[from] SecretError),
     #[error("secret not found: {0}")]
     SecretNotFound(SecretId),
@@ -54,9 +58,14 @@ pub enum ComponentViewError {
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct ComponentView {
     pub kind: ComponentKind,
     pub properties: Value,
+    /// The values in [`properties`](Self::properties) that refer to a [`Secret`](crate::Secret)
+    /// through a secret reference [`Prop`](crate::Prop).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secret_references: Vec<ComponentViewSecretReference>,
 }
 
 impl Default for ComponentView {
@@ -64,10 +73,20 @@ impl Default for ComponentView {
         Self {
             kind: Default::default(),
             properties: serde_json::json!({}),
+            secret_references: Vec::new(),
         }
     }
 }
 
+/// A value within a [`ComponentView`] that holds the id of a [`Secret`](crate::Secret).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentViewSecretReference {
+    /// A JSON pointer to the value within the view's properties.
+    pub pointer: String,
+    pub secret_id: SecretId,
+}
+
 impl ComponentView {
     pub async fn new(
         ctx: &DalContext,
@@ -112,64 +131,134 @@ impl ComponentView {
             .value()
             .unwrap_or(&Value::Null);
 
+        let secret_references =
+            Self::find_secret_references(ctx, *schema_variant.id(), properties).await?;
+
         Ok(ComponentView {
             kind: *component.kind(),
             properties: properties.clone(),
+            secret_references,
         })
     }
 
-    pub async fn reencrypt_secrets(
+    /// Walks the paths of the schema variant's secret reference [`Props`](crate::Prop) through
+    /// the given properties, expanding every element of arrays and maps along the way.
+    async fn find_secret_references(
         ctx: &DalContext,
-        component: &mut veritech_client::ComponentView,
-    ) -> Result<(), ComponentViewError> {
-        if component.kind != veritech_client::ComponentKind::Credential {
-            return Ok(());
-        }
+        schema_variant_id: SchemaVariantId,
+        properties: &Value,
+    ) -> ComponentViewResult<Vec<ComponentViewSecretReference>> {
+        let props = SchemaVariant::all_props(ctx, schema_variant_id).await?;
+        let kinds_by_path: HashMap<Vec<String>, PropKind> = props
+            .iter()
+            .map(|prop| (prop.path().as_owned_parts(), *prop.kind()))
+            .collect();
 
-        // If it's a credential it's already unencrypted
-        if let Some(object) = component
-            .properties
-            .as_object_mut()
-            .and_then(|obj| obj.get_mut("root").and_then(|obj| obj.as_object_mut()))
+        let mut references = Vec::new();
+        for prop in props
+            .iter()
+            .filter(|prop| *prop.widget_kind() == WidgetKind::SecretSelect)
         {
-            // Note: we can't know which fields are WidgetKind::SecretSelect as we lose information by being so low on the stack
-            // So for now we will try to decrypt every integer root field, which kinda suck
-            //
-            // TODO: traverse tree and decrypt leafs
-            for (_key, value) in object {
-                if let Some(raw_id) = value.as_str() {
-                    let id = SecretId::from_str(raw_id)?;
-                    let decrypted_secret = EncryptedSecret::get_by_id(ctx, &id)
-                        .await?
-                        .ok_or(ComponentViewError::SecretNotFound(id))?
-                        .decrypt(ctx)
-                        .await?;
-                    let encoded = ctx
-                        .encryption_key()
-                        .encrypt_and_encode(serde_json::to_string(&decrypted_secret.message())?);
-
-                    *value = serde_json::to_value(&decrypted_secret)?;
-                    match value.pointer_mut("/message") {
-                        Some(v) => {
-                            *v = serde_json::json!({
-                                "cycloneEncryptedDataMarker": true,
-                                "encryptedSecret": encoded
-                            })
+            let parts = prop.path().as_owned_parts();
+
+            // The properties hold the children of the root prop, so the walk starts below it
+            let mut found = vec![(String::new(), properties)];
+            for depth in 1..parts.len() {
+                let parent_kind = kinds_by_path.get(&parts[..depth]).copied();
+                let mut next = Vec::new();
+                for (pointer, value) in found {
+                    match (parent_kind, value) {
+                        (Some(PropKind::Array), Value::Array(items)) => {
+                            for (index, item) in items.iter().enumerate() {
+                                next.push((format!("{pointer}/{index}"), item));
+                            }
                         }
-                        None => {
-                            return Err(ComponentViewError::JSONPointerNotFound(
-                                value.clone(),
-                                "/message".to_owned(),
-                            ));
+                        (Some(PropKind::Map), Value::Object(entries)) => {
+                            for (key, entry) in entries {
+                                next.push((format!("{pointer}/{}", escape_pointer(key)), entry));
+                            }
                         }
+                        (_, Value::Object(fields)) => {
+                            if let Some(field) = fields.get(&parts[depth]) {
+                                next.push((
+                                    format!("{pointer}/{}", escape_pointer(&parts[depth])),
+                                    field,
+                                ));
+                            }
+                        }
+                        _ => {}
                     }
                 }
+                found = next;
+            }
+
+            for (pointer, value) in found {
+                if let Some(raw_id) = value.as_str() {
+                    references.push(ComponentViewSecretReference {
+                        pointer,
+                        secret_id: SecretId::from_str(raw_id)?,
+                    });
+                }
+            }
+        }
+
+        Ok(references)
+    }
+
+    /// Builds the [`veritech_client::ComponentView`] for this view, replacing every secret
+    /// reference with its decrypted secret, whose message is encrypted for cyclone to decrypt
+    /// at execution time. Cyclone recognizes the encrypted message by its marker, whatever the
+    /// component's kind.
+    pub async fn reencrypt_secrets(
+        &self,
+        ctx: &DalContext,
+    ) -> ComponentViewResult<veritech_client::ComponentView> {
+        let mut component: veritech_client::ComponentView = self.clone().into();
+        for reference in &self.secret_references {
+            let decrypted_secret = EncryptedSecret::get_by_id(ctx, &reference.secret_id)
+                .await?
+                .ok_or(ComponentViewError::SecretNotFound(reference.secret_id))?
+                .decrypt(ctx)
+                .await?;
+            let encoded = ctx
+                .encryption_key()
+                .encrypt_and_encode(serde_json::to_string(&decrypted_secret.message())?);
+
+            let value = component
+                .properties
+                .pointer_mut(&reference.pointer)
+                .ok_or_else(|| {
+                    ComponentViewError::JSONPointerNotFound(
+                        self.properties.clone(),
+                        reference.pointer.clone(),
+                    )
+                })?;
+            *value = serde_json::to_value(&decrypted_secret)?;
+            match value.pointer_mut("/message") {
+                Some(v) => {
+                    *v = serde_json::json!({
+                        "cycloneEncryptedDataMarker": true,
+                        "encryptedSecret": encoded
+                    })
+                }
+                None => {
+                    return Err(ComponentViewError::JSONPointerNotFound(
+                        value.clone(),
+                        "/message".to_owned(),
+                    ));
+                }
             }
         }
-        Ok(())
+
+        Ok(component)
     }
 }
 
+/// Escapes a key for use as a JSON pointer reference token (RFC 6901).
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
 impl From<ComponentKind> for veritech_client::ComponentKind {
     fn from(view: ComponentKind) -> Self {
         match view {
@@ -14,9 +14,14 @@ use crate::{
         processor::{JobQueueProcessor, JobQueueProcessorError},
         producer::{BlockingJobError, BlockingJobResult, JobProducer},
     },
+    secret::backend::SecretBackends,
     HistoryActor, StandardModel, Tenancy, TenancyError, Visibility,
 };
 
+/// The number of [`Fixes`](crate::Fix) in a [`FixBatch`](crate::FixBatch) which may run at once
+/// unless configured otherwise.
+pub const DEFAULT_FIX_CONCURRENCY_LIMIT: usize = 8;
+
 /// A context type which contains handles to common core service dependencies.
 ///
 /// These services are typically used by most DAL objects, such as a database connection pool, a
@@ -37,6 +42,10 @@ pub struct ServicesContext {
     pkgs_path: Option<PathBuf>,
     /// The URL of the module index
     module_index_url: Option<String>,
+    /// The external stores that secret values can be resolved from.
+    secret_backends: Arc<SecretBackends>,
+    /// The number of fixes in a batch which may run at once.
+    fix_concurrency_limit: usize,
 }
 
 impl ServicesContext {
@@ -58,9 +67,25 @@ impl ServicesContext {
             encryption_key,
             pkgs_path,
             module_index_url,
+            secret_backends: Arc::new(SecretBackends::default()),
+            fix_concurrency_limit: DEFAULT_FIX_CONCURRENCY_LIMIT,
         }
     }
 
+    /// Sets the [`SecretBackends`] that secret values can be resolved from. Without this, only
+    /// secrets stored in the database can be decrypted.
+    pub fn with_secret_backends(mut self, secret_backends: SecretBackends) -> Self {
+        self.secret_backends = Arc::new(secret_backends);
+        self
+    }
+
+    /// Sets the number of [`Fixes`](crate::Fix) in a [`FixBatch`](crate::FixBatch) which may run
+    /// at once.
+    pub fn with_fix_concurrency_limit(mut self, fix_concurrency_limit: usize) -> Self {
+        self.fix_concurrency_limit = fix_concurrency_limit;
+        self
+    }
+
     /// Consumes and returns [`DalContextBuilder`].
     pub fn into_builder(self, blocking: bool) -> DalContextBuilder {
         DalContextBuilder {
@@ -93,6 +118,16 @@ impl ServicesContext {
         self.encryption_key.clone()
     }
 
+    /// Gets a reference to the secret backends.
+    pub fn secret_backends(&self) -> Arc<SecretBackends> {
+        self.secret_backends.clone()
+    }
+
+    /// Gets the number of fixes in a batch which may run at once.
+    pub fn fix_concurrency_limit(&self) -> usize {
+        self.fix_concurrency_limit
+    }
+
     /// Builds and returns a new [`Connections`].
     pub async fn connections(&self) -> PgPoolResult<Connections> {
         let pg_conn = self.pg_pool.get().await?;
@@ -433,6 +468,16 @@ impl DalContext {
         &self.services_context.encryption_key
     }
 
+    /// Gets a reference to the DAL context's secret backends.
+    pub fn secret_backends(&self) -> &SecretBackends {
+        &self.services_context.secret_backends
+    }
+
+    /// Gets the number of fixes in a batch which may run at once.
+    pub fn fix_concurrency_limit(&self) -> usize {
+        self.services_context.fix_concurrency_limit
+    }
+
     /// Gets a reference to the dal context's tenancy.
     pub fn tenancy(&self) -> &Tenancy {
         &self.tenancy
@@ -16,9 +16,10 @@ use crate::provider::internal::InternalProviderError;
 use crate::schema::variant::SchemaVariantError;
 use crate::socket::SocketError;
 use crate::{
-    AttributeContextBuilderError, AttributePrototypeArgumentError, AttributeValueError,
-    ChangeSetPk, ComponentError, ComponentId, DalContext, Edge, EdgeError, Node, NodeError, NodeId,
-    NodeKind, PropError, SchemaError, SocketId, StandardModel, StandardModelError,
+    AttributeContextBuilderError, AttributePrototypeArgumentError, AttributeReadContext,
+    AttributeValueError, ChangeSetPk, ComponentError, ComponentId, ComponentType, DalContext, Edge,
+    EdgeError, Node, NodeError, NodeId, NodeKind, PropError, SchemaError, SocketId, StandardModel,
+    StandardModelError, TransactionsError,
 };
 
 pub mod connection;
@@ -37,6 +38,8 @@ pub enum DiagramError {
     AttributeValue(#[from] AttributeValueError),
     #[error("attribute value not found")]
     AttributeValueNotFound,
+    #[error("attribute value not found for context: {0:?}")]
+    AttributeValueNotFoundForContext(AttributeReadContext),
     #[error("change status error: {0}")]
     ChangeStatus(#[from] ChangeStatusError),
     #[error("component error: {0}")]
@@ -59,6 +62,8 @@ pub enum DiagramError {
     InternalProvider(#[from] InternalProviderError),
     #[error("internal provider not found for socket id: {0}")]
     InternalProviderNotFoundForSocket(SocketId),
+    #[error("invalid component type ({0:?}) for frame")]
+    InvalidComponentTypeForFrame(ComponentType),
     #[error("node error: {0}")]
     Node(#[from] NodeError),
     #[error("node not found")]
@@ -89,6 +94,8 @@ pub enum DiagramError {
     SocketNotFound,
     #[error("standard model error: {0}")]
     StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
 }
 
 pub type DiagramResult<T> = Result<T, DiagramError>;
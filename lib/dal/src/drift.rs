@@ -0,0 +1,351 @@
+//! This module contains [`ComponentDrift`], which records where a [`Component's`](Component)
+//! refreshed resource disagrees with its domain, and [`DriftSummary`], which summarizes that
+//! drift across a workspace in the same shape as a
+//! [`QualificationSummary`](crate::qualification::QualificationSummary).
+//!
+//! A resource prop is compared with the domain prop it [refers to](Prop::refers_to_prop_id()). If
+//! the resource prop has a diff func, that func decides whether the two disagree; otherwise, the
+//! values must be equal.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use si_data_pg::PgError;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::standard_model::TypeHint;
+use crate::{
+    impl_standard_model, pk, standard_model, standard_model_accessor_ro, AttributeReadContext,
+    AttributeValue, AttributeValueError, AttributeView, Component, ComponentError, ComponentId,
+    DalContext, ExternalProviderId, Func, FuncBinding, FuncBindingError, FuncError, FuncId,
+    InternalProviderId, Prop, PropId, StandardModel, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, Visibility, WsEvent, WsEventError, WsEventResult, WsPayload,
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum DriftError {
+    #[error("attribute value error: {0}")]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("attribute value not found for prop {0} of component {1}")]
+    AttributeValueNotFound(PropId, ComponentId),
+    #[error("component error: {0}")]
+    Component(#[from] ComponentError),
+    #[error("func error: {0}")]
+    Func(#[from] FuncError),
+    #[error("func binding error: {0}")]
+    FuncBinding(#[from] FuncBindingError),
+    #[error("func not found: {0}")]
+    FuncNotFound(FuncId),
+    #[error("schema variant not found for component: {0}")]
+    NoSchemaVariant(ComponentId),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type DriftResult<T> = Result<T, DriftError>;
+
+pk!(ComponentDriftPk);
+pk!(ComponentDriftId);
+
+/// A domain prop whose value disagrees with the resource.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftedProp {
+    /// The path of the resource prop, separated by "/".
+    pub path: String,
+    pub domain: Value,
+    pub resource: Value,
+}
+
+/// The drift last detected for a [`Component`]. An empty list of
+/// [`drifted props`](Self::drifted_props()) means the resource matches the domain.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ComponentDrift {
+    pk: ComponentDriftPk,
+    id: ComponentDriftId,
+    component_id: ComponentId,
+    drifted_props: Vec<DriftedProp>,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: ComponentDrift,
+    pk: ComponentDriftPk,
+    id: ComponentDriftId,
+    table_name: "component_drifts",
+    history_event_label_base: "component_drift",
+    history_event_message_name: "Component Drift"
+}
+
+impl ComponentDrift {
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        drifted_props: Vec<DriftedProp>,
+    ) -> DriftResult<Self> {
+        let drifted_props = serde_json::to_value(drifted_props)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM component_drift_create_v1($1, $2, $3, $4)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &component_id,
+                    &drifted_props,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    standard_model_accessor_ro!(component_id, ComponentId);
+
+    pub fn drifted_props(&self) -> &[DriftedProp] {
+        &self.drifted_props
+    }
+
+    pub fn is_drifted(&self) -> bool {
+        !self.drifted_props.is_empty()
+    }
+
+    pub async fn set_drifted_props(
+        &mut self,
+        ctx: &DalContext,
+        drifted_props: Vec<DriftedProp>,
+    ) -> DriftResult<()> {
+        let updated_at = standard_model::update(
+            ctx,
+            "component_drifts",
+            "drifted_props",
+            self.id(),
+            &serde_json::to_value(&drifted_props)?,
+            TypeHint::JsonB,
+        )
+        .await?;
+        self.timestamp.updated_at = updated_at;
+        self.drifted_props = drifted_props;
+        Ok(())
+    }
+
+    pub async fn find_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> DriftResult<Option<Self>> {
+        Ok(Self::find_by_attr(ctx, "component_id", &component_id)
+            .await?
+            .pop())
+    }
+
+    /// Compares the [`Component's`](Component) resource with its domain and stores the result,
+    /// publishing a [`WsPayload::ComponentDriftUpdated`] event on commit if the drift changed.
+    #[instrument(skip(ctx))]
+    pub async fn update_for_component(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> DriftResult<Self> {
+        let drifted_props = Self::detect(ctx, component_id).await?;
+
+        let drift = match Self::find_for_component(ctx, component_id).await? {
+            Some(drift) if drift.drifted_props == drifted_props => return Ok(drift),
+            Some(mut drift) => {
+                drift.set_drifted_props(ctx, drifted_props).await?;
+                drift
+            }
+            None if drifted_props.is_empty() => {
+                // Nothing was ever recorded and nothing has drifted, so there's nothing to announce
+                return Self::new(ctx, component_id, drifted_props).await;
+            }
+            None => Self::new(ctx, component_id, drifted_props).await?,
+        };
+
+        WsEvent::component_drift_updated(ctx, component_id, drift.is_drifted())
+            .await?
+            .publish_on_commit(ctx)
+            .await?;
+
+        Ok(drift)
+    }
+
+    /// Lists the domain props of a [`Component`] which disagree with its resource. A component
+    /// without a resource has not drifted.
+    pub async fn detect(
+        ctx: &DalContext,
+        component_id: ComponentId,
+    ) -> DriftResult<Vec<DriftedProp>> {
+        let component = Component::get_by_id(ctx, &component_id)
+            .await?
+            .ok_or(ComponentError::NotFound(component_id))?;
+        if component.resource(ctx).await?.payload.is_none() {
+            return Ok(Vec::new());
+        }
+        let schema_variant = component
+            .schema_variant(ctx)
+            .await?
+            .ok_or(DriftError::NoSchemaVariant(component_id))?;
+
+        let mut drifted_props = Vec::new();
+        for prop in Prop::find_by_attr(ctx, "schema_variant_id", schema_variant.id()).await? {
+            let domain_prop_id = match prop.refers_to_prop_id() {
+                Some(prop_id) => *prop_id,
+                None => continue,
+            };
+
+            let resource = Self::prop_value(ctx, component_id, *prop.id()).await?;
+            let domain = Self::prop_value(ctx, component_id, domain_prop_id).await?;
+
+            let drifted = match prop.diff_func_id() {
+                Some(func_id) => {
+                    let func = Func::get_by_id(ctx, func_id)
+                        .await?
+                        .ok_or(DriftError::FuncNotFound(*func_id))?;
+                    let func_binding = FuncBinding::new(
+                        ctx,
+                        serde_json::json!({ "first": domain, "second": resource }),
+                        *func.id(),
+                        *func.backend_kind(),
+                    )
+                    .await?;
+                    func_binding
+                        .execute(ctx)
+                        .await?
+                        .value()
+                        .and_then(|value| value.get("diff"))
+                        .and_then(Value::as_bool)
+                        .unwrap_or(false)
+                }
+                None => domain != resource,
+            };
+
+            if drifted {
+                drifted_props.push(DriftedProp {
+                    path: prop.path().with_replaced_sep("/"),
+                    domain,
+                    resource,
+                });
+            }
+        }
+
+        Ok(drifted_props)
+    }
+
+    async fn prop_value(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        prop_id: PropId,
+    ) -> DriftResult<Value> {
+        let context = AttributeReadContext {
+            prop_id: Some(prop_id),
+            internal_provider_id: Some(InternalProviderId::NONE),
+            external_provider_id: Some(ExternalProviderId::NONE),
+            component_id: Some(component_id),
+        };
+        let attribute_value = AttributeValue::find_for_context(ctx, context)
+            .await?
+            .ok_or(DriftError::AttributeValueNotFound(prop_id, component_id))?;
+
+        let view_context = AttributeReadContext {
+            prop_id: None,
+            ..context
+        };
+        let view = AttributeView::new(ctx, view_context, Some(*attribute_value.id())).await?;
+        Ok(view.value().clone())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftSummaryForComponent {
+    component_id: ComponentId,
+    component_name: String,
+    drifted_props: Vec<DriftedProp>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DriftSummary {
+    total: i64,
+    drifted: i64,
+    in_sync: i64,
+    components: Vec<DriftSummaryForComponent>,
+}
+
+impl DriftSummary {
+    /// Summarizes the drift last recorded for every [`Component`] in the workspace. Components
+    /// which have never been checked are counted as in sync.
+    #[instrument(skip_all)]
+    pub async fn get_summary(ctx: &DalContext) -> DriftResult<DriftSummary> {
+        let mut component_summaries = Vec::new();
+        let mut drifted = 0;
+        let mut in_sync = 0;
+
+        for component in Component::list(ctx).await? {
+            let component_id = *component.id();
+            let drifted_props = ComponentDrift::find_for_component(ctx, component_id)
+                .await?
+                .map(|drift| drift.drifted_props)
+                .unwrap_or_default();
+
+            if drifted_props.is_empty() {
+                in_sync += 1;
+            } else {
+                drifted += 1;
+            }
+
+            component_summaries.push(DriftSummaryForComponent {
+                component_id,
+                component_name: component.name(ctx).await?,
+                drifted_props,
+            });
+        }
+
+        Ok(DriftSummary {
+            total: drifted + in_sync,
+            drifted,
+            in_sync,
+            components: component_summaries,
+        })
+    }
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentDriftUpdatedPayload {
+    component_id: ComponentId,
+    drifted: bool,
+}
+
+impl WsEvent {
+    pub async fn component_drift_updated(
+        ctx: &DalContext,
+        component_id: ComponentId,
+        drifted: bool,
+    ) -> WsEventResult<Self> {
+        WsEvent::new(
+            ctx,
+            WsPayload::ComponentDriftUpdated(ComponentDriftUpdatedPayload {
+                component_id,
+                drifted,
+            }),
+        )
+        .await
+    }
+}
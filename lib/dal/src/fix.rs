@@ -15,13 +15,14 @@ use crate::{
     func::backend::js_action::ActionRunResult, impl_standard_model, pk, standard_model,
     standard_model_accessor, standard_model_accessor_ro, standard_model_belongs_to, ActionKind,
     ActionPrototype, ActionPrototypeError, ActionPrototypeId, AttributeValueId, Component,
-    ComponentError, ComponentId, DalContext, FixBatch, FixResolverError, FuncError,
+    ComponentError, ComponentId, DalContext, EdgeError, FixBatch, FixResolverError, FuncError,
     HistoryEventError, ResourceView, SchemaError, StandardModel, StandardModelError, Tenancy,
     Timestamp, TransactionsError, Visibility, WsEvent, WsEventError, WsEventResult, WsPayload,
 };
 use veritech_client::ResourceStatus;
 
 pub mod batch;
+pub mod plan;
 pub mod resolver;
 
 /// The completion status of a [`Fix`] or [`FixBatch`](crate::FixBatch).
@@ -75,6 +76,8 @@ pub enum FixError {
     BatchAlreadyStarted(FixId, FixBatchId),
     #[error(transparent)]
     Component(#[from] ComponentError),
+    #[error(transparent)]
+    Edge(#[from] EdgeError),
     #[error("completion status is empty")]
     EmptyCompletionStatus,
     #[error(transparent)]
@@ -95,9 +95,11 @@ impl FixBatch {
             // TODO(nick): getting what the batch completion status should be can be a query.
             let mut batch_completion_status = FixCompletionStatus::Success;
             for fix in self.fixes(ctx).await? {
+                // A fix which never finished, such as when the batch was cut short by an error,
+                // has no completion status
                 match fix
                     .completion_status()
-                    .ok_or(FixError::EmptyCompletionStatus)?
+                    .unwrap_or(&FixCompletionStatus::Unstarted)
                 {
                     FixCompletionStatus::Success => {}
                     FixCompletionStatus::Failure => {
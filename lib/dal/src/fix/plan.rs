@@ -0,0 +1,252 @@
+//! This module contains [`FixPlan`], which decides the order that the [`Fixes`](crate::Fix) in
+//! a [`FixBatch`](crate::FixBatch) run in so that independent fixes can run at the same time.
+//!
+//! Two fixes depend on one another when they fix the same [`Component`](crate::Component) or
+//! when one [`Component`](crate::Component) is an ancestor of the other in the configuration
+//! [`Edge`](crate::Edge) graph. Dependent fixes run in the order they were given in, one after
+//! another, while every other fix is free to run alongside them. When a fix doesn't succeed, the
+//! fixes depending on it are [skipped](FixPlan::fail()).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{fix::FixResult, ComponentId, DalContext, Edge, FixId};
+
+/// A dependency-ordered plan for running a [`FixBatch`](crate::FixBatch).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixPlan {
+    /// The fixes in the order they were given in.
+    order: Vec<FixId>,
+    /// The fixes that have not been started, along with the fixes each one is still waiting on.
+    waiting: HashMap<FixId, HashSet<FixId>>,
+    /// The fixes that wait on each fix.
+    dependents: HashMap<FixId, Vec<FixId>>,
+}
+
+impl FixPlan {
+    /// Plans the given fixes, each paired with the [`Component`](crate::Component) it fixes,
+    /// using the configuration [`Edges`](crate::Edge) between their components.
+    pub async fn new(ctx: &DalContext, fixes: &[(FixId, ComponentId)]) -> FixResult<Self> {
+        // Deletion fixes are for deleted components, whose edges are deleted along with them
+        let ctx = &ctx.clone_with_delete_visibility();
+        let mut parents: HashMap<ComponentId, Vec<ComponentId>> = HashMap::new();
+        let mut ancestors: HashMap<ComponentId, HashSet<ComponentId>> = HashMap::new();
+
+        for (_, component_id) in fixes {
+            if ancestors.contains_key(component_id) {
+                continue;
+            }
+
+            let mut component_ancestors = HashSet::new();
+            let mut queue = VecDeque::from([*component_id]);
+            while let Some(id) = queue.pop_front() {
+                if !parents.contains_key(&id) {
+                    parents.insert(id, Edge::list_parents_for_component(ctx, id).await?);
+                }
+                for parent_id in parents.get(&id).into_iter().flatten() {
+                    if component_ancestors.insert(*parent_id) {
+                        queue.push_back(*parent_id);
+                    }
+                }
+            }
+            ancestors.insert(*component_id, component_ancestors);
+        }
+
+        Ok(Self::from_ancestors(fixes, &ancestors))
+    }
+
+    /// Plans the given fixes from the ancestors of each of their
+    /// [`Components`](crate::Component).
+    pub fn from_ancestors(
+        fixes: &[(FixId, ComponentId)],
+        ancestors: &HashMap<ComponentId, HashSet<ComponentId>>,
+    ) -> Self {
+        let is_ancestor = |ancestor: &ComponentId, of: &ComponentId| {
+            ancestors
+                .get(of)
+                .map(|of_ancestors| of_ancestors.contains(ancestor))
+                .unwrap_or(false)
+        };
+
+        let mut waiting: HashMap<FixId, HashSet<FixId>> = HashMap::new();
+        let mut dependents: HashMap<FixId, Vec<FixId>> = HashMap::new();
+        for (index, (fix_id, component_id)) in fixes.iter().enumerate() {
+            let upstream = waiting.entry(*fix_id).or_default();
+            for (earlier_fix_id, earlier_component_id) in &fixes[..index] {
+                // Only ever waiting on earlier fixes means there can't be a cycle
+                if earlier_component_id == component_id
+                    || is_ancestor(earlier_component_id, component_id)
+                    || is_ancestor(component_id, earlier_component_id)
+                {
+                    upstream.insert(*earlier_fix_id);
+                    dependents.entry(*earlier_fix_id).or_default().push(*fix_id);
+                }
+            }
+        }
+
+        Self {
+            order: fixes.iter().map(|(fix_id, _)| *fix_id).collect(),
+            waiting,
+            dependents,
+        }
+    }
+
+    /// The fixes the given fix is still waiting on before it can start.
+    pub fn waiting_on(&self, fix_id: FixId) -> Option<&HashSet<FixId>> {
+        self.waiting.get(&fix_id)
+    }
+
+    /// Takes the earliest fix which isn't waiting on any other, if there is one. A fix is only
+    /// ever returned once.
+    pub fn next_ready(&mut self) -> Option<FixId> {
+        let fix_id = *self.order.iter().find(|fix_id| {
+            self.waiting
+                .get(fix_id)
+                .map(|upstream| upstream.is_empty())
+                .unwrap_or(false)
+        })?;
+        self.waiting.remove(&fix_id);
+        Some(fix_id)
+    }
+
+    /// Records that a fix has finished running, which lets the fixes waiting on it start.
+    pub fn complete(&mut self, fix_id: FixId) {
+        self.waiting.remove(&fix_id);
+        for dependent_id in self.dependents.remove(&fix_id).into_iter().flatten() {
+            if let Some(upstream) = self.waiting.get_mut(&dependent_id) {
+                upstream.remove(&fix_id);
+            }
+        }
+    }
+
+    /// Records that a fix finished without succeeding. Every fix waiting on it, directly or
+    /// through other fixes, will never start, and is returned in the order given to the plan so
+    /// that it can be marked as skipped.
+    pub fn fail(&mut self, fix_id: FixId) -> Vec<FixId> {
+        self.waiting.remove(&fix_id);
+        let mut skipped = HashSet::new();
+        let mut queue = VecDeque::from([fix_id]);
+        while let Some(id) = queue.pop_front() {
+            for dependent_id in self.dependents.remove(&id).into_iter().flatten() {
+                if self.waiting.remove(&dependent_id).is_some() {
+                    skipped.insert(dependent_id);
+                    queue.push_back(dependent_id);
+                }
+            }
+        }
+
+        self.order
+            .iter()
+            .filter(|id| skipped.contains(id))
+            .copied()
+            .collect()
+    }
+
+    /// Whether every fix has been started.
+    pub fn is_empty(&self) -> bool {
+        self.waiting.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ready(plan: &mut FixPlan) -> Vec<FixId> {
+        std::iter::from_fn(|| plan.next_ready()).collect()
+    }
+
+    #[test]
+    fn independent_fixes_are_all_ready() {
+        let fixes: Vec<(FixId, ComponentId)> = (0..3)
+            .map(|_| (FixId::generate(), ComponentId::generate()))
+            .collect();
+        let mut plan = FixPlan::from_ancestors(&fixes, &HashMap::new());
+
+        assert_eq!(
+            fixes.iter().map(|(fix_id, _)| *fix_id).collect::<Vec<_>>(),
+            ready(&mut plan)
+        );
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn dependents_wait_on_their_ancestors() {
+        let vpc = ComponentId::generate();
+        let subnet = ComponentId::generate();
+        let bucket = ComponentId::generate();
+        let ancestors = HashMap::from([
+            (vpc, HashSet::new()),
+            (subnet, HashSet::from([vpc])),
+            (bucket, HashSet::new()),
+        ]);
+
+        let (vpc_fix, subnet_fix, bucket_fix, vpc_refix) = (
+            FixId::generate(),
+            FixId::generate(),
+            FixId::generate(),
+            FixId::generate(),
+        );
+        let mut plan = FixPlan::from_ancestors(
+            &[
+                (vpc_fix, vpc),
+                (subnet_fix, subnet),
+                (bucket_fix, bucket),
+                (vpc_refix, vpc),
+            ],
+            &ancestors,
+        );
+        assert_eq!(
+            Some(&HashSet::from([vpc_fix, subnet_fix])),
+            plan.waiting_on(vpc_refix)
+        );
+
+        assert_eq!(vec![vpc_fix, bucket_fix], ready(&mut plan));
+
+        plan.complete(bucket_fix);
+        assert!(ready(&mut plan).is_empty());
+
+        plan.complete(vpc_fix);
+        assert_eq!(vec![subnet_fix], ready(&mut plan));
+
+        plan.complete(subnet_fix);
+        assert_eq!(vec![vpc_refix], ready(&mut plan));
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn failures_skip_everything_downstream() {
+        let vpc = ComponentId::generate();
+        let subnet = ComponentId::generate();
+        let instance = ComponentId::generate();
+        let bucket = ComponentId::generate();
+        let ancestors = HashMap::from([
+            (vpc, HashSet::new()),
+            (subnet, HashSet::from([vpc])),
+            (instance, HashSet::from([subnet, vpc])),
+            (bucket, HashSet::new()),
+        ]);
+
+        let (vpc_fix, subnet_fix, instance_fix, bucket_fix) = (
+            FixId::generate(),
+            FixId::generate(),
+            FixId::generate(),
+            FixId::generate(),
+        );
+        let mut plan = FixPlan::from_ancestors(
+            &[
+                (vpc_fix, vpc),
+                (subnet_fix, subnet),
+                (instance_fix, instance),
+                (bucket_fix, bucket),
+            ],
+            &ancestors,
+        );
+        assert_eq!(vec![vpc_fix, bucket_fix], ready(&mut plan));
+
+        assert_eq!(vec![subnet_fix, instance_fix], plan.fail(vpc_fix));
+        assert!(ready(&mut plan).is_empty());
+        assert!(plan.is_empty());
+
+        assert!(plan.fail(bucket_fix).is_empty());
+    }
+}
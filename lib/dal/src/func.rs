@@ -91,6 +91,9 @@ pk!(FuncId);
 /// For example, if we had a code block of
 /// `function myValidator(actual, expected) { return true; }` in `code_base64`,
 /// the `handler` value should be `myValidator`.
+///
+/// Action funcs may also have a `plan_handler`, a second entry point into the same code that
+/// describes what the action would change for a component without changing anything.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct Func {
     pk: FuncPk,
@@ -104,6 +107,7 @@ pub struct Func {
     backend_kind: FuncBackendKind,
     backend_response_type: FuncBackendResponseType,
     handler: Option<String>,
+    plan_handler: Option<String>,
     code_base64: Option<String>,
     code_sha256: String,
     #[serde(flatten)]
@@ -223,6 +227,7 @@ impl Func {
         FuncResult
     );
     standard_model_accessor!(handler, Option<String>, FuncResult);
+    standard_model_accessor!(plan_handler, Option<String>, FuncResult);
     standard_model_accessor!(code_base64, Option<String>, FuncResult);
     standard_model_accessor_ro!(code_sha256, String);
 }
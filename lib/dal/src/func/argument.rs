@@ -58,6 +58,7 @@ pub enum FuncArgumentKind {
     Any,
     Array,
     Boolean,
+    Float,
     Integer,
     Map,
     Object,
@@ -69,6 +70,7 @@ impl From<PropKind> for FuncArgumentKind {
         match prop_kind {
             PropKind::Array => FuncArgumentKind::Array,
             PropKind::Boolean => FuncArgumentKind::Boolean,
+            PropKind::Float => FuncArgumentKind::Float,
             PropKind::Integer => FuncArgumentKind::Integer,
             PropKind::Object => FuncArgumentKind::Object,
             PropKind::String => FuncArgumentKind::String,
@@ -83,6 +85,7 @@ impl From<PkgFuncArgumentKind> for FuncArgumentKind {
             PkgFuncArgumentKind::Any => FuncArgumentKind::Any,
             PkgFuncArgumentKind::Array => FuncArgumentKind::Array,
             PkgFuncArgumentKind::Boolean => FuncArgumentKind::Boolean,
+            PkgFuncArgumentKind::Float => FuncArgumentKind::Float,
             PkgFuncArgumentKind::Integer => FuncArgumentKind::Integer,
             PkgFuncArgumentKind::Map => FuncArgumentKind::Map,
             PkgFuncArgumentKind::Object => FuncArgumentKind::Object,
@@ -97,6 +100,7 @@ impl From<FuncArgumentKind> for PkgFuncArgumentKind {
             FuncArgumentKind::Any => PkgFuncArgumentKind::Any,
             FuncArgumentKind::Array => PkgFuncArgumentKind::Array,
             FuncArgumentKind::Boolean => PkgFuncArgumentKind::Boolean,
+            FuncArgumentKind::Float => PkgFuncArgumentKind::Float,
             FuncArgumentKind::Integer => PkgFuncArgumentKind::Integer,
             FuncArgumentKind::Map => PkgFuncArgumentKind::Map,
             FuncArgumentKind::Object => PkgFuncArgumentKind::Object,
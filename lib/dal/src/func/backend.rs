@@ -9,11 +9,15 @@ use veritech_client::{
     ResolverFunctionResponseType,
 };
 
-use crate::{label_list::ToLabelList, DalContext, Func, FuncId, PropKind, StandardModel};
+use crate::{
+    func::execution::FuncExecutionPk, label_list::ToLabelList, DalContext, Func, FuncId, PropKind,
+    StandardModel,
+};
 
 pub mod array;
 pub mod boolean;
 pub mod diff;
+pub mod float;
 pub mod identity;
 pub mod integer;
 pub mod js_action;
@@ -76,6 +80,7 @@ pub enum FuncBackendKind {
     Boolean,
     /// Comparison between two JSON values
     Diff,
+    Float,
     /// Mathematical identity of the [`Func`](crate::Func)'s arguments.
     Identity,
     Integer,
@@ -111,6 +116,7 @@ pub enum FuncBackendResponseType {
     Boolean,
     CodeGeneration,
     Confirmation,
+    Float,
     /// Mathematical identity of the [`Func`](crate::Func)'s arguments.
     Identity,
     Integer,
@@ -131,6 +137,7 @@ impl From<ResolverFunctionResponseType> for FuncBackendResponseType {
             ResolverFunctionResponseType::Action => FuncBackendResponseType::Action,
             ResolverFunctionResponseType::Array => FuncBackendResponseType::Array,
             ResolverFunctionResponseType::Boolean => FuncBackendResponseType::Boolean,
+            ResolverFunctionResponseType::Float => FuncBackendResponseType::Float,
             ResolverFunctionResponseType::Identity => FuncBackendResponseType::Identity,
             ResolverFunctionResponseType::Integer => FuncBackendResponseType::Integer,
             ResolverFunctionResponseType::Map => FuncBackendResponseType::Map,
@@ -156,6 +163,7 @@ impl From<FuncBackendResponseType> for ResolverFunctionResponseType {
             FuncBackendResponseType::Action => ResolverFunctionResponseType::Action,
             FuncBackendResponseType::Array => ResolverFunctionResponseType::Array,
             FuncBackendResponseType::Boolean => ResolverFunctionResponseType::Boolean,
+            FuncBackendResponseType::Float => ResolverFunctionResponseType::Float,
             FuncBackendResponseType::Integer => ResolverFunctionResponseType::Integer,
             FuncBackendResponseType::Identity => ResolverFunctionResponseType::Identity,
             FuncBackendResponseType::Map => ResolverFunctionResponseType::Map,
@@ -181,15 +189,21 @@ impl ToLabelList for FuncBackendKind {}
 pub struct FuncDispatchContext {
     pub veritech: VeritechClient,
     pub output_tx: mpsc::Sender<OutputStream>,
+    /// The id veritech knows the execution by, which is the [`FuncExecutionPk`] as a string.
+    pub execution_id: String,
 }
 
 impl FuncDispatchContext {
-    pub fn new(ctx: &DalContext) -> (Self, mpsc::Receiver<OutputStream>) {
+    pub fn new(
+        ctx: &DalContext,
+        execution_pk: FuncExecutionPk,
+    ) -> (Self, mpsc::Receiver<OutputStream>) {
         let (output_tx, rx) = mpsc::channel(64);
         (
             Self {
                 veritech: ctx.veritech().clone(),
                 output_tx,
+                execution_id: execution_pk.to_string(),
             },
             rx,
         )
@@ -235,13 +249,6 @@ pub trait FuncDispatch: std::fmt::Debug {
         Ok(value)
     }
 
-    // TODO: re-enable encryption
-    //{
-    //    for view in value.extract()? {
-    //        ComponentView::reencrypt_secrets(ctx, view).await?;
-    //    }
-    //}
-
     #[instrument(
     name = "funcdispatch.execute",
     skip_all,
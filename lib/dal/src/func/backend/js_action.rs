@@ -3,13 +3,21 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use telemetry::tracing::trace;
 use veritech_client::{
-    ActionRunRequest, ActionRunResultSuccess, FunctionResult, OutputStream, ResourceStatus,
+    ActionRunRequest, ActionRunResultSuccess, ExecutionLimits, FunctionResult, OutputStream,
+    ResourceStatus,
 };
 
 use crate::func::backend::{
     ExtractPayload, FuncBackendError, FuncBackendResult, FuncDispatch, FuncDispatchContext,
 };
 
+/// Actions create, refresh and delete real resources, which can take a provider a while.
+const EXECUTION_LIMITS: ExecutionLimits = ExecutionLimits {
+    timeout_secs: Some(900),
+    memory_bytes: None,
+    cpu_secs: None,
+};
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct FuncBackendJsActionArgs(serde_json::Value);
 
@@ -31,12 +39,11 @@ impl FuncDispatch for FuncBackendJsAction {
         args: Self::Args,
     ) -> Box<Self> {
         let request = ActionRunRequest {
-            // Once we start tracking the state of these executions, then this id will be useful,
-            // but for now it's passed along and back, and is opaue
-            execution_id: "ayrtonsennajscommand".to_string(),
+            execution_id: context.execution_id.clone(),
             handler: handler.into(),
             code_base64: code_base64.into(),
             args: serde_json::to_value(args).unwrap(),
+            limits: Some(EXECUTION_LIMITS),
         };
 
         Box::new(Self { context, request })
@@ -1,12 +1,20 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use veritech_client::{
-    FunctionResult, ResolverFunctionComponent, ResolverFunctionRequest,
+    ExecutionLimits, FunctionResult, ResolverFunctionComponent, ResolverFunctionRequest,
     ResolverFunctionResponseType, ResolverFunctionResultSuccess,
 };
 
 use crate::func::backend::{ExtractPayload, FuncBackendResult, FuncDispatch, FuncDispatchContext};
 
+/// Attribute functions run as part of value propagation, so one which hangs holds up every value
+/// that depends on it.
+const EXECUTION_LIMITS: ExecutionLimits = ExecutionLimits {
+    timeout_secs: Some(60),
+    memory_bytes: None,
+    cpu_secs: None,
+};
+
 #[derive(Deserialize, Serialize, Debug, Clone, Default)]
 pub struct FuncBackendJsAttributeArgs {
     pub component: ResolverFunctionComponent,
@@ -31,13 +39,12 @@ impl FuncDispatch for FuncBackendJsAttribute {
         args: Self::Args,
     ) -> Box<Self> {
         let request = ResolverFunctionRequest {
-            // Once we start tracking the state of these executions, then this id will be useful,
-            // but for now it's passed along and back, and is opaue
-            execution_id: "tomcruise".to_string(),
+            execution_id: context.execution_id.clone(),
             handler: handler.into(),
             component: args.component,
             response_type: args.response_type,
             code_base64: code_base64.into(),
+            limits: Some(EXECUTION_LIMITS),
         };
 
         Box::new(Self { context, request })
@@ -2,11 +2,20 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
-use veritech_client::{FunctionResult, ReconciliationRequest, ReconciliationResultSuccess};
+use veritech_client::{
+    ExecutionLimits, FunctionResult, ReconciliationRequest, ReconciliationResultSuccess,
+};
 
 use crate::func::backend::{ExtractPayload, FuncBackendResult, FuncDispatch, FuncDispatchContext};
 use crate::AttributeValueId;
 
+/// Reconciliations compare a resource with its component and may call out to the provider.
+const EXECUTION_LIMITS: ExecutionLimits = ExecutionLimits {
+    timeout_secs: Some(300),
+    memory_bytes: None,
+    cpu_secs: None,
+};
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ReconciliationDiffDomain {
@@ -43,12 +52,11 @@ impl FuncDispatch for FuncBackendJsReconciliation {
         args: Self::Args,
     ) -> Box<Self> {
         let request = ReconciliationRequest {
-            // Once we start tracking the state of these executions, then this id will be useful,
-            // but for now it's passed along and back, and is opaue
-            execution_id: "freeronaldinhogauchojsreconciliation".to_string(),
+            execution_id: context.execution_id.clone(),
             handler: handler.into(),
             code_base64: code_base64.into(),
             args: serde_json::to_value(args).unwrap(),
+            limits: Some(EXECUTION_LIMITS),
         };
 
         Box::new(Self { context, request })
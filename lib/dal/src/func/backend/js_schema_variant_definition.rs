@@ -22,7 +22,7 @@ impl FuncDispatch for FuncBackendJsSchemaVariantDefinition {
         _args: Self::Args,
     ) -> Box<Self> {
         let request = SchemaVariantDefinitionRequest {
-            execution_id: "villanelle".to_string(),
+            execution_id: context.execution_id.clone(),
             handler: handler.into(),
             code_base64: code_base64.to_owned(),
         };
@@ -6,7 +6,16 @@ use async_trait::async_trait;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use veritech_client::{FunctionResult, OutputStream, ValidationRequest, ValidationResultSuccess};
+use veritech_client::{
+    ExecutionLimits, FunctionResult, OutputStream, ValidationRequest, ValidationResultSuccess,
+};
+
+/// Validations only inspect a value, so they should never need long.
+const EXECUTION_LIMITS: ExecutionLimits = ExecutionLimits {
+    timeout_secs: Some(60),
+    memory_bytes: None,
+    cpu_secs: None,
+};
 
 #[derive(Debug, Clone)]
 pub struct FuncBackendJsValidation {
@@ -37,10 +46,11 @@ impl FuncDispatch for FuncBackendJsValidation {
         args: Self::Args,
     ) -> Box<Self> {
         let request = ValidationRequest {
-            execution_id: "johnwick".to_string(),
+            execution_id: context.execution_id.clone(),
             handler: handler.into(),
             code_base64: code_base64.to_owned(),
             value: args.value,
+            limits: Some(EXECUTION_LIMITS),
         };
 
         Box::new(Self { context, request })
@@ -3,6 +3,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
 use std::str::FromStr;
+use url::Url;
 
 use crate::func::backend::{FuncBackend, FuncBackendResult};
 use crate::validation::{Validation, ValidationError, ValidationErrorKind};
@@ -43,6 +44,42 @@ impl FuncBackend for FuncBackendValidation {
         };
 
         let maybe_validation_error = match self.args.validation {
+            Validation::ArrayItemsAreUnique { value } => match value {
+                Some(value) => match value.iter().enumerate().find(|(index, item)| value[..*index].contains(*item)) {
+                    None => None,
+                    Some((_, duplicate)) => Some(ValidationError {
+                        message: format!("value contains duplicate item ({duplicate})"),
+                        kind: ValidationErrorKind::ArrayItemsNotUnique,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
+            Validation::ArrayLengthIsBetween { value, lower_bound, upper_bound } => match value {
+                Some(value) => match length_is_between(value.len(), lower_bound, upper_bound) {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value has {} items, which is not in between lower ({lower_bound}) and upper ({upper_bound}) bounds", value.len()),
+                        kind: ValidationErrorKind::ArrayLengthNotInBetween,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
+            Validation::FloatIsBetweenTwoFloats { value, lower_bound, upper_bound } => match value {
+                Some(value) => match value >= lower_bound && value <= upper_bound {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value ({value}) is not in between lower ({lower_bound}) and upper ({upper_bound}) bounds"),
+                        kind: ValidationErrorKind::FloatNotInBetweenTwoFloats,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
             Validation::IntegerIsBetweenTwoIntegers { value, lower_bound, upper_bound } => match value {
                 Some(value) => match value > lower_bound && value < upper_bound {
                     true => None,
@@ -55,6 +92,10 @@ impl FuncBackend for FuncBackendValidation {
                 },
                 None => Some(value_must_be_present_error),
             },
+            Validation::FloatIsNotEmpty { value } => match value {
+                Some(_value) => None,
+                None => Some(value_must_be_present_error),
+            },
             Validation::IntegerIsNotEmpty { value} => match value {
                 Some(_value) => None,
                 None => Some(value_must_be_present_error),
@@ -118,6 +159,86 @@ impl FuncBackend for FuncBackendValidation {
                 },
                 None => Some(value_must_be_present_error),
             },
+            Validation::StringIsArn { value } => match value {
+                Some(value) => match is_arn(&value) {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value ({value}) is not a valid ARN"),
+                        kind: ValidationErrorKind::InvalidArn,
+                        link: Some("https://docs.aws.amazon.com/IAM/latest/UserGuide/reference-arns.html".to_owned()),
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
+            Validation::StringIsHostname { value } => match value {
+                Some(value) => match is_hostname(&value) {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value ({value}) is not a valid hostname"),
+                        kind: ValidationErrorKind::InvalidHostname,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
+            Validation::StringIsValidCidr { value } => match value {
+                Some(value) => match parse_cidr(&value) {
+                    Ok(()) => None,
+                    Err(e) => Some(ValidationError {
+                        message: format!("value ({value}) is an invalid cidr block: {e}"),
+                        kind: ValidationErrorKind::InvalidCidr,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
+            Validation::StringIsValidUrl { value } => match value {
+                Some(value) => match Url::parse(&value) {
+                    Ok(_) => None,
+                    Err(e) => Some(ValidationError {
+                        message: format!("value ({value}) is an invalid url: {e}"),
+                        kind: ValidationErrorKind::InvalidUrl,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
+            Validation::StringLengthIsBetween { value, lower_bound, upper_bound } => match value {
+                Some(value) => match length_is_between(value.chars().count(), lower_bound, upper_bound) {
+                    true => None,
+                    false => Some(ValidationError {
+                        message: format!("value ({value}) has a length that is not in between lower ({lower_bound}) and upper ({upper_bound}) bounds"),
+                        kind: ValidationErrorKind::StringLengthNotInBetween,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
+            Validation::StringMatchesRegex { value, pattern } => match value {
+                Some(value) => match Regex::new(&pattern) {
+                    Ok(re) => match re.is_match(&value) {
+                        true => None,
+                        false => Some(ValidationError {
+                            message: format!("value ({value}) does not match pattern ({pattern})"),
+                            kind: ValidationErrorKind::StringDoesNotMatchRegex,
+                            link: None,
+                            level: None,
+                        }),
+                    },
+                    Err(e) => Some(ValidationError {
+                        message: format!("pattern ({pattern}) is not a valid regular expression: {e}"),
+                        kind: ValidationErrorKind::InvalidRegex,
+                        link: None,
+                        level: None,
+                    }),
+                },
+                None => Some(value_must_be_present_error),
+            },
             Validation::StringInStringArray {
                 value,
                 expected,
@@ -150,3 +271,197 @@ impl FuncBackend for FuncBackendValidation {
         Ok((Some(value.clone()), Some(value)))
     }
 }
+
+fn length_is_between(length: usize, lower_bound: i64, upper_bound: i64) -> bool {
+    let length = length as i64;
+    length >= lower_bound && length <= upper_bound
+}
+
+/// An ARN looks like `arn:partition:service:region:account-id:resource`, where the region and
+/// account id may be empty and the resource may itself contain colons and slashes.
+fn is_arn(value: &str) -> bool {
+    let re = Regex::new(r"^arn:[a-z0-9-]+:[a-zA-Z0-9-]+:[a-z0-9-]*:[a-z0-9-]*:.+$").unwrap();
+    re.is_match(value)
+}
+
+/// Per RFC 1123, a hostname is at most 253 characters of dot separated labels, each of which is
+/// 1 to 63 letters, digits or hyphens and neither starts nor ends with a hyphen.
+fn is_hostname(value: &str) -> bool {
+    let value = value.strip_suffix('.').unwrap_or(value);
+    !value.is_empty()
+        && value.len() <= 253
+        && value.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+fn parse_cidr(value: &str) -> Result<(), String> {
+    let (address, prefix) = value
+        .split_once('/')
+        .ok_or_else(|| "missing prefix length".to_string())?;
+    let address = IpAddr::from_str(address).map_err(|e| e.to_string())?;
+    let prefix = u8::from_str(prefix).map_err(|e| format!("invalid prefix length: {e}"))?;
+    let max_prefix = match address {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+    if prefix > max_prefix {
+        return Err(format!("prefix length must be at most {max_prefix}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    async fn errors(validation: Validation) -> Vec<ValidationErrorKind> {
+        let (value, _) = FuncBackendValidation::new(FuncBackendValidationArgs::new(validation))
+            .inline()
+            .await
+            .expect("validation failed to run");
+        let errors: Vec<ValidationError> =
+            serde_json::from_value(value.expect("no value returned"))
+                .expect("failed to deserialize errors");
+        errors.into_iter().map(|error| error.kind).collect()
+    }
+
+    #[tokio::test]
+    async fn array_validations() {
+        let value = Some(vec![Value::from("a"), Value::from("b"), Value::from("a")]);
+        assert_eq!(
+            vec![ValidationErrorKind::ArrayItemsNotUnique],
+            errors(Validation::ArrayItemsAreUnique {
+                value: value.clone()
+            })
+            .await
+        );
+        assert!(errors(Validation::ArrayLengthIsBetween {
+            value: value.clone(),
+            lower_bound: 1,
+            upper_bound: 3,
+        })
+        .await
+        .is_empty());
+        assert_eq!(
+            vec![ValidationErrorKind::ArrayLengthNotInBetween],
+            errors(Validation::ArrayLengthIsBetween {
+                value,
+                lower_bound: 4,
+                upper_bound: 10,
+            })
+            .await
+        );
+    }
+
+    #[tokio::test]
+    async fn float_is_between_two_floats() {
+        for (value, valid) in [(0.5, true), (1.0, true), (1.5, false)] {
+            assert_eq!(
+                valid,
+                errors(Validation::FloatIsBetweenTwoFloats {
+                    value: Some(value),
+                    lower_bound: 0.0,
+                    upper_bound: 1.0,
+                })
+                .await
+                .is_empty(),
+                "{value}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn float_is_not_empty() {
+        assert!(errors(Validation::FloatIsNotEmpty { value: Some(0.0) })
+            .await
+            .is_empty());
+        assert_eq!(
+            vec![ValidationErrorKind::ValueMustBePresent],
+            errors(Validation::FloatIsNotEmpty { value: None }).await
+        );
+    }
+
+    #[tokio::test]
+    async fn string_formats() {
+        for (value, valid) in [
+            ("arn:aws:iam::123456789012:user/johndoe", true),
+            ("arn:aws:iam::aws:policy/AdministratorAccess", true),
+            ("arn:aws:s3:::my-bucket/*", true),
+            ("aws:iam::123456789012:user/johndoe", false),
+        ] {
+            let result = errors(Validation::StringIsArn {
+                value: Some(value.to_string()),
+            })
+            .await;
+            assert_eq!(valid, result.is_empty(), "{value}");
+        }
+
+        for (value, valid) in [
+            ("example.com", true),
+            ("my-host-1", true),
+            ("-bad.example.com", false),
+            ("under_score.example.com", false),
+            ("a..b", false),
+        ] {
+            let result = errors(Validation::StringIsHostname {
+                value: Some(value.to_string()),
+            })
+            .await;
+            assert_eq!(valid, result.is_empty(), "{value}");
+        }
+
+        for (value, valid) in [
+            ("10.0.0.0/16", true),
+            ("fd00::/8", true),
+            ("10.0.0.0/33", false),
+            ("10.0.0.0", false),
+        ] {
+            let result = errors(Validation::StringIsValidCidr {
+                value: Some(value.to_string()),
+            })
+            .await;
+            assert_eq!(valid, result.is_empty(), "{value}");
+        }
+
+        for (value, valid) in [("https://systeminit.com/docs", true), ("systeminit", false)] {
+            let result = errors(Validation::StringIsValidUrl {
+                value: Some(value.to_string()),
+            })
+            .await;
+            assert_eq!(valid, result.is_empty(), "{value}");
+        }
+    }
+
+    #[tokio::test]
+    async fn string_length_and_regex() {
+        assert_eq!(
+            vec![ValidationErrorKind::StringLengthNotInBetween],
+            errors(Validation::StringLengthIsBetween {
+                value: Some("toolong".to_string()),
+                lower_bound: 1,
+                upper_bound: 3,
+            })
+            .await
+        );
+        assert!(errors(Validation::StringMatchesRegex {
+            value: Some("ami-0123abcd".to_string()),
+            pattern: "^ami-[0-9a-f]+$".to_string(),
+        })
+        .await
+        .is_empty());
+        assert_eq!(
+            vec![ValidationErrorKind::InvalidRegex],
+            errors(Validation::StringMatchesRegex {
+                value: Some("ami-0123abcd".to_string()),
+                pattern: "(".to_string(),
+            })
+            .await
+        );
+    }
+}
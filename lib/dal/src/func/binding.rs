@@ -5,7 +5,7 @@ use si_data_pg::PgError;
 use telemetry::prelude::*;
 use thiserror::Error;
 use tokio::sync::mpsc;
-use veritech_client::{OutputStream, ResolverFunctionComponent};
+use veritech_client::{FunctionResultFailureError, OutputStream, ResolverFunctionComponent};
 
 use crate::func::execution::FuncExecutionPk;
 use crate::FuncError;
@@ -14,6 +14,7 @@ use crate::{
         array::FuncBackendArray,
         boolean::FuncBackendBoolean,
         diff::FuncBackendDiff,
+        float::FuncBackendFloat,
         identity::FuncBackendIdentity,
         integer::FuncBackendInteger,
         js_action::FuncBackendJsAction,
@@ -204,10 +205,31 @@ impl FuncBinding {
         result: FuncBindingResult,
     );
 
-    // For a given [`FuncBinding`](Self), execute using veritech.
+    /// For a given [`FuncBinding`](Self), execute using veritech.
+    ///
+    /// If the execution is cancelled, its [`Cancelled`](super::execution::FuncExecutionState)
+    /// state is committed before the error is returned, so that it survives callers rolling back
+    /// on error.
     pub async fn execute(&self, ctx: &DalContext) -> FuncBindingResult<FuncBindingReturnValue> {
-        let (func, execution, context, mut rx) = self.prepare_execution(ctx).await?;
-        let value = self.execute_critical_section(func.clone(), context).await?;
+        let (func, mut execution, context, mut rx) = self.prepare_execution(ctx).await?;
+        let value = match self.execute_critical_section(func.clone(), context).await {
+            Err(FuncBindingError::FuncBackendResultFailure {
+                kind,
+                message,
+                backend,
+            }) if kind == FunctionResultFailureError::KIND_CANCELLED => {
+                execution
+                    .set_state(ctx, super::execution::FuncExecutionState::Cancelled)
+                    .await?;
+                ctx.commit().await?;
+                return Err(FuncBindingError::FuncBackendResultFailure {
+                    kind,
+                    message,
+                    backend,
+                });
+            }
+            result => result?,
+        };
 
         let mut output = Vec::new();
         while let Some(output_stream) = rx.recv().await {
@@ -266,6 +288,7 @@ impl FuncBinding {
             FuncBackendKind::Boolean => FuncBackendBoolean::create_and_execute(&self.args).await,
             FuncBackendKind::Identity => FuncBackendIdentity::create_and_execute(&self.args).await,
             FuncBackendKind::Diff => FuncBackendDiff::create_and_execute(&self.args).await,
+            FuncBackendKind::Float => FuncBackendFloat::create_and_execute(&self.args).await,
             FuncBackendKind::Integer => FuncBackendInteger::create_and_execute(&self.args).await,
             FuncBackendKind::Map => FuncBackendMap::create_and_execute(&self.args).await,
             FuncBackendKind::Object => FuncBackendObject::create_and_execute(&self.args).await,
@@ -345,6 +368,7 @@ impl FuncBinding {
             | FuncBackendKind::Boolean
             | FuncBackendKind::Identity
             | FuncBackendKind::Diff
+            | FuncBackendKind::Float
             | FuncBackendKind::Integer
             | FuncBackendKind::Map
             | FuncBackendKind::Object
@@ -369,7 +393,7 @@ impl FuncBinding {
             .set_state(ctx, super::execution::FuncExecutionState::Run)
             .await?;
 
-        let (context, rx) = FuncDispatchContext::new(ctx);
+        let (context, rx) = FuncDispatchContext::new(ctx, execution.pk());
         Ok((func, execution, context, rx))
     }
 }
@@ -13,6 +13,9 @@ use crate::{
     StandardModel, StandardModelError, Timestamp,
 };
 
+const FUNC_EXECUTION_GET_BY_PK_IN_TENANCY: &str =
+    include_str!("../queries/func_execution_get_by_pk_in_tenancy.sql");
+
 use super::{
     binding::{FuncBinding, FuncBindingId},
     binding_return_value::{FuncBindingReturnValue, FuncBindingReturnValueId},
@@ -26,6 +29,8 @@ pub enum FuncExecutionError {
     HistoryEvent(#[from] HistoryEventError),
     #[error("nats txn error: {0}")]
     Nats(#[from] NatsError),
+    #[error("func execution not found: {0}")]
+    NotFound(FuncExecutionPk),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error("error serializing/deserializing json: {0}")]
@@ -34,6 +39,8 @@ pub enum FuncExecutionError {
     StandardModelError(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("veritech client error: {0}")]
+    Veritech(#[from] veritech_client::ClientError),
 }
 
 pub type FuncExecutionResult<T> = Result<T, FuncExecutionError>;
@@ -46,6 +53,7 @@ pk!(FuncExecutionPk);
     Deserialize, Serialize, Debug, Clone, PartialEq, Eq, strum::EnumString, strum::Display, Copy,
 )]
 pub enum FuncExecutionState {
+    Cancelled,
     Create,
     Dispatch,
     Failure,
@@ -54,6 +62,13 @@ pub enum FuncExecutionState {
     Success,
 }
 
+impl FuncExecutionState {
+    /// Whether the execution has stopped for good, one way or another.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Cancelled | Self::Failure | Self::Success)
+    }
+}
+
 /// [`FuncExecutions`](Self) record that a [`function`](crate::Func) has executed alongside all the
 /// context required to understand the execution as well as the log of the output stream for the
 /// [`function`](crate::Func).
@@ -121,6 +136,30 @@ impl FuncExecution {
         Ok(object)
     }
 
+    /// Asks veritech to stop the execution with the given [`pk`](FuncExecutionPk) if it is still
+    /// running. The execution is marked [`Cancelled`](FuncExecutionState::Cancelled) by whoever
+    /// is running it once veritech reports that it stopped.
+    ///
+    /// Only executions within the [`DalContext`]'s tenancy can be cancelled, and executions that
+    /// have already finished are left alone.
+    pub async fn cancel(ctx: &DalContext, pk: FuncExecutionPk) -> FuncExecutionResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(FUNC_EXECUTION_GET_BY_PK_IN_TENANCY, &[ctx.tenancy(), &pk])
+            .await?
+            .ok_or(FuncExecutionError::NotFound(pk))?;
+        let execution: Self = object_from_row(row)?;
+        if execution.state.is_finished() {
+            debug!(%pk, state = %execution.state, "func execution already finished, not cancelling");
+            return Ok(());
+        }
+
+        ctx.veritech().cancel_execution(&pk.to_string()).await?;
+        Ok(())
+    }
+
     pub fn state(&self) -> FuncExecutionState {
         self.state
     }
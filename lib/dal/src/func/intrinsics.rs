@@ -13,6 +13,7 @@ pub enum IntrinsicFunc {
     Identity,
     SetArray,
     SetBoolean,
+    SetFloat,
     SetInteger,
     SetMap,
     SetObject,
@@ -66,6 +67,10 @@ impl IntrinsicFunc {
                 builder.backend_kind(FuncSpecBackendKind::Boolean);
                 builder.response_type(FuncSpecBackendResponseType::Boolean);
             }
+            Self::SetFloat => {
+                builder.backend_kind(FuncSpecBackendKind::Float);
+                builder.response_type(FuncSpecBackendResponseType::Float);
+            }
             Self::SetInteger => {
                 builder.backend_kind(FuncSpecBackendKind::Integer);
                 builder.response_type(FuncSpecBackendResponseType::Integer);
@@ -102,6 +107,7 @@ impl IntrinsicFunc {
             Self::Identity => "si:identity",
             Self::SetArray => "si:setArray",
             Self::SetBoolean => "si:setBoolean",
+            Self::SetFloat => "si:setFloat",
             Self::SetInteger => "si:setInteger",
             Self::SetMap => "si:setMap",
             Self::SetObject => "si:setObject",
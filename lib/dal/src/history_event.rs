@@ -1,4 +1,5 @@
 use crate::{Tenancy, TransactionsError};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use strum::Display as StrumDisplay;
 use thiserror::Error;
@@ -7,7 +8,10 @@ use si_data_nats::NatsError;
 use si_data_pg::PgError;
 use telemetry::prelude::*;
 
-use crate::{pk, DalContext, Timestamp, UserPk};
+use crate::standard_model::objects_from_rows;
+use crate::{pk, DalContext, StandardModelError, Timestamp, UserPk};
+
+const HISTORY_EVENT_LIST: &str = include_str!("queries/history_event/list.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -18,6 +22,8 @@ pub enum HistoryEventError {
     Pg(#[from] PgError),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
 }
@@ -48,6 +54,39 @@ impl From<UserPk> for HistoryActor {
 
 pk!(HistoryEventPk);
 
+/// Narrows down the [`HistoryEvents`](HistoryEvent) returned by [`HistoryEvent::list()`]. Every
+/// field that is set must match; the events come back newest first.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryEventFilter {
+    pub actor: Option<HistoryActor>,
+    /// Matches labels starting with this prefix, such as `"change_set."`.
+    pub label_prefix: Option<String>,
+    /// Matches events whose data refers to this object by its `pk` or `id`.
+    pub object_id: Option<String>,
+    /// Matches events created at or after this time.
+    pub since: Option<DateTime<Utc>>,
+    /// Matches events created before this time.
+    pub until: Option<DateTime<Utc>>,
+    /// Matches events older than this one, for fetching the next page.
+    pub before: Option<HistoryEventPk>,
+    /// How many events to return, at most [`HistoryEventFilter::MAX_LIMIT`].
+    pub limit: Option<i64>,
+}
+
+impl HistoryEventFilter {
+    pub const DEFAULT_LIMIT: i64 = 100;
+    pub const MAX_LIMIT: i64 = 1000;
+
+    /// How many events [`HistoryEvent::list()`] returns at most. A full page means there may be
+    /// more to fetch.
+    pub fn page_size(&self) -> i64 {
+        self.limit
+            .unwrap_or(Self::DEFAULT_LIMIT)
+            .clamp(1, Self::MAX_LIMIT)
+    }
+}
+
 /// HistoryEvents are the audit trail for things in SI. They track
 /// that a specific actor did something, and optionally store data
 /// associated with the activity for posterity.
@@ -89,4 +128,33 @@ impl HistoryEvent {
         let object: HistoryEvent = serde_json::from_value(json)?;
         Ok(object)
     }
+
+    /// List the workspace's events matching the filter, newest first. Pass the `pk` of the last
+    /// event as [`before`](HistoryEventFilter::before) to fetch the next page.
+    #[instrument(skip(ctx))]
+    pub async fn list(
+        ctx: &DalContext,
+        filter: &HistoryEventFilter,
+    ) -> HistoryEventResult<Vec<HistoryEvent>> {
+        let actor = filter.actor.map(serde_json::to_value).transpose()?;
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                HISTORY_EVENT_LIST,
+                &[
+                    ctx.tenancy(),
+                    &actor,
+                    &filter.label_prefix,
+                    &filter.object_id,
+                    &filter.since,
+                    &filter.until,
+                    &filter.before,
+                    &filter.page_size(),
+                ],
+            )
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
 }
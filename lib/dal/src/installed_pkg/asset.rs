@@ -5,7 +5,7 @@ use telemetry::prelude::*;
 use crate::schema::variant::definition::SchemaVariantDefinitionId;
 use crate::{
     impl_standard_model, pk, standard_model, standard_model_accessor, DalContext, FuncId, SchemaId,
-    SchemaVariantId, StandardModel, Tenancy, Timestamp, Visibility,
+    SchemaVariantId, SecretDefinitionId, StandardModel, Tenancy, Timestamp, Visibility,
 };
 use strum::{AsRefStr, Display, EnumIter, EnumString};
 
@@ -40,6 +40,7 @@ pub enum InstalledPkgAssetKind {
     Schema,
     SchemaVariant,
     SchemaVariantDefinition,
+    SecretDefinition,
 }
 
 /// An `InstalledPkgAsset` is a record of the installation of a package asset. It tracks the
@@ -90,6 +91,12 @@ pub enum InstalledPkgAssetTyped {
         id: SchemaVariantDefinitionId,
         hash: String,
     },
+    SecretDefinition {
+        installed_pkg_asset_id: InstalledPkgAssetId,
+        installed_pkg_id: InstalledPkgId,
+        id: SecretDefinitionId,
+        hash: String,
+    },
 }
 
 impl InstalledPkgAssetTyped {
@@ -132,6 +139,19 @@ impl InstalledPkgAssetTyped {
         }
     }
 
+    pub fn new_for_secret_definition(
+        secret_definition_id: SecretDefinitionId,
+        installed_pkg_id: InstalledPkgId,
+        hash: String,
+    ) -> Self {
+        Self::SecretDefinition {
+            installed_pkg_asset_id: InstalledPkgAssetId::NONE,
+            installed_pkg_id,
+            id: secret_definition_id,
+            hash,
+        }
+    }
+
     pub fn new_for_func(func_id: FuncId, installed_pkg_id: InstalledPkgId, hash: String) -> Self {
         Self::Func {
             installed_pkg_asset_id: InstalledPkgAssetId::NONE,
@@ -173,6 +193,12 @@ impl From<&InstalledPkgAsset> for InstalledPkgAssetTyped {
                 id: Into::<ulid::Ulid>::into(value.asset_id()).into(),
                 hash,
             },
+            InstalledPkgAssetKind::SecretDefinition => Self::SecretDefinition {
+                installed_pkg_asset_id,
+                installed_pkg_id,
+                id: Into::<ulid::Ulid>::into(value.asset_id()).into(),
+                hash,
+            },
         }
     }
 }
@@ -231,7 +257,17 @@ impl InstalledPkgAsset {
                 hash,
                 InstalledPkgAssetKind::SchemaVariantDefinition,
             ),
-
+            InstalledPkgAssetTyped::SecretDefinition {
+                installed_pkg_id,
+                id,
+                hash,
+                ..
+            } => (
+                installed_pkg_id,
+                Into::<ulid::Ulid>::into(id).into(),
+                hash,
+                InstalledPkgAssetKind::SecretDefinition,
+            ),
             InstalledPkgAssetTyped::Func {
                 installed_pkg_id,
                 id,
@@ -295,6 +331,14 @@ impl InstalledPkgAsset {
                 InstalledPkgAssetKind::Schema,
                 InstalledPkgAssetKind::Func,
             )),
+            InstalledPkgAssetTyped::SecretDefinition {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::Schema,
+                InstalledPkgAssetKind::SecretDefinition,
+            )),
         }
     }
 
@@ -329,6 +373,14 @@ impl InstalledPkgAsset {
                 InstalledPkgAssetKind::SchemaVariantDefinition,
                 InstalledPkgAssetKind::Func,
             )),
+            InstalledPkgAssetTyped::SecretDefinition {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::SchemaVariantDefinition,
+                InstalledPkgAssetKind::SecretDefinition,
+            )),
         }
     }
 
@@ -361,6 +413,14 @@ impl InstalledPkgAsset {
                 InstalledPkgAssetKind::SchemaVariant,
                 InstalledPkgAssetKind::Func,
             )),
+            InstalledPkgAssetTyped::SecretDefinition {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::SchemaVariant,
+                InstalledPkgAssetKind::SecretDefinition,
+            )),
         }
     }
 
@@ -393,6 +453,54 @@ impl InstalledPkgAsset {
                 InstalledPkgAssetKind::Func,
                 InstalledPkgAssetKind::SchemaVariant,
             )),
+            InstalledPkgAssetTyped::SecretDefinition {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::Func,
+                InstalledPkgAssetKind::SecretDefinition,
+            )),
+        }
+    }
+
+    pub fn as_installed_secret_definition(&self) -> InstalledPkgResult<InstalledPkgAssetTyped> {
+        let typed: InstalledPkgAssetTyped = self.into();
+
+        match typed {
+            InstalledPkgAssetTyped::SecretDefinition { .. } => Ok(typed),
+            InstalledPkgAssetTyped::Schema {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::SecretDefinition,
+                InstalledPkgAssetKind::Schema,
+            )),
+            InstalledPkgAssetTyped::SchemaVariant {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::SecretDefinition,
+                InstalledPkgAssetKind::SchemaVariant,
+            )),
+            InstalledPkgAssetTyped::SchemaVariantDefinition {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::SecretDefinition,
+                InstalledPkgAssetKind::SchemaVariantDefinition,
+            )),
+            InstalledPkgAssetTyped::Func {
+                installed_pkg_asset_id,
+                ..
+            } => Err(super::InstalledPkgError::InstalledPkgKindMismatch(
+                installed_pkg_asset_id,
+                InstalledPkgAssetKind::SecretDefinition,
+                InstalledPkgAssetKind::Func,
+            )),
         }
     }
 
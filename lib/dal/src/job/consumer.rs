@@ -11,8 +11,8 @@ use crate::{
     fix::FixError, func::binding_return_value::FuncBindingReturnValueError,
     job::producer::BlockingJobError, job::producer::JobProducerError, status::StatusUpdaterError,
     AccessBuilder, ActionPrototypeError, ActionPrototypeId, AttributeValueError, ComponentError,
-    ComponentId, DalContext, DalContextBuilder, FixBatchId, FixResolverError, StandardModelError,
-    TransactionsError, Visibility, WsEventError,
+    ComponentId, DalContext, DalContextBuilder, DriftError, FixBatchId, FixResolverError,
+    StandardModelError, TransactionsError, Visibility, WsEventError,
 };
 
 #[remain::sorted]
@@ -39,6 +39,8 @@ pub enum JobConsumerError {
     #[error("Protocol error with council: {0}")]
     CouncilProtocol(String),
     #[error(transparent)]
+    Drift(#[from] DriftError),
+    #[error(transparent)]
     Fix(#[from] FixError),
     #[error(transparent)]
     FixResolver(#[from] FixResolverError),
@@ -125,4 +127,28 @@ pub trait JobConsumer: std::fmt::Debug + Sync + JobConsumerMetadata {
 
         Ok(())
     }
+
+    /// Called instead of [`run`](Self::run) when the job is given up on, such as when it runs
+    /// out of attempts, so that it doesn't leave anything looking like it's still in progress.
+    /// Does nothing by default.
+    async fn abandon(&self, _ctx: &mut DalContext, _reason: &str) -> JobConsumerResult<()> {
+        Ok(())
+    }
+
+    /// Sets up the data necessary to abandon the job and in-turn calls the `abandon` method.
+    async fn abandon_job(
+        &self,
+        ctx_builder: DalContextBuilder,
+        reason: &str,
+    ) -> JobConsumerResult<()> {
+        let mut ctx = ctx_builder
+            .build(self.access_builder().build(self.visibility()))
+            .await?;
+
+        self.abandon(&mut ctx, reason).await?;
+
+        ctx.commit().await?;
+
+        Ok(())
+    }
 }
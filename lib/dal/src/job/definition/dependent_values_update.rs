@@ -1,4 +1,4 @@
-use std::{collections::HashMap, collections::HashSet, convert::TryFrom};
+use std::{collections::HashMap, collections::HashSet, collections::VecDeque, convert::TryFrom};
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -209,14 +209,24 @@ impl DependentValuesUpdate {
         ctx.rollback().await?;
 
         let mut update_tasks = JoinSet::new();
+        // Nodes council told us to process, so a status report can reveal any it told us about
+        // in a response that never arrived.
+        let mut started_node_ids = HashSet::new();
+        // Responses recovered from a status report, handled before waiting on council again.
+        let mut recovered_responses = VecDeque::new();
 
         while !dependency_graph.is_empty() {
-            match council.fetch_response().await? {
+            let response = match recovered_responses.pop_front() {
+                Some(response) => Some(response),
+                None => council.fetch_response().await?,
+            };
+            match response {
                 Some(response) => match response {
                     council_server::Response::OkToProcess { node_ids } => {
                         debug!(?node_ids, job_id = ?self.job_id(), "Ok to start processing nodes");
                         for node_id in node_ids {
                             let id = AttributeValueId::from(node_id);
+                            started_node_ids.insert(id);
 
                             status_updater.values_running(ctx, vec![id]).await;
                             // Status updater reads from the database and uses its own connection
@@ -270,6 +280,33 @@ impl DependentValuesUpdate {
                     // as it breaks the protocol contract we have with council.
                     council_server::Response::OkToCreate => return Err(JobConsumerError::CouncilProtocol("Told to create values again after we've finished creating values. Multiple instances of council running?".to_string())),
                     council_server::Response::Shutdown => break,
+                    council_server::Response::Status { nodes, .. } => {
+                        debug!(?nodes, job_id = ?self.job_id(), "Council reported our status");
+                        let lost_node_ids: Vec<council_server::Id> = nodes
+                            .iter()
+                            .filter(|(node_id, status)| {
+                                **status == council_server::NodeStatus::Processing
+                                    && !started_node_ids.contains(&AttributeValueId::from(**node_id))
+                            })
+                            .map(|(node_id, _)| *node_id)
+                            .collect();
+                        if !lost_node_ids.is_empty() {
+                            recovered_responses.push_back(council_server::Response::OkToProcess {
+                                node_ids: lost_node_ids,
+                            });
+                        }
+
+                        // Council restarted without the part of our graph it had yet to persist
+                        let forgotten_graph: council_server::Graph = dependency_graph
+                            .iter()
+                            .filter(|(id, _)| !nodes.contains_key(&council_server::Id::from(**id)))
+                            .map(|(key, value)| (key.into(), value.iter().map(Into::into).collect()))
+                            .collect();
+                        if !forgotten_graph.is_empty() {
+                            warn!(?forgotten_graph, job_id = ?self.job_id(), "Council lost part of our graph, registering it again");
+                            council.register_dependency_graph(forgotten_graph).await?;
+                        }
+                    }
                 },
                 // FIXME: reconnect
                 None => break, // Happens if subscription has been unsubscribed or if connection is closed
@@ -1,7 +1,9 @@
-use std::convert::TryFrom;
+use std::{collections::HashMap, convert::TryFrom};
 
 use async_trait::async_trait;
+use futures::{stream::FuturesUnordered, StreamExt};
 use serde::{Deserialize, Serialize};
+use telemetry::prelude::*;
 
 use crate::{
     fix::FixError,
@@ -13,7 +15,7 @@ use crate::{
     },
     AccessBuilder, ActionKind, ActionPrototype, ActionPrototypeId, AttributeValueId, Component,
     ComponentId, DalContext, DependentValuesUpdate, Fix, FixBatch, FixBatchId, FixCompletionStatus,
-    FixId, FixResolver, RootPropChild, StandardModel, Visibility, WsEvent,
+    FixId, FixPlan, FixResolver, RootPropChild, StandardModel, Visibility, WsEvent,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,7 +30,6 @@ pub struct FixItem {
 struct FixesJobArgs {
     fixes: Vec<FixItem>,
     batch_id: FixBatchId,
-    started: bool,
 }
 
 impl From<FixesJob> for FixesJobArgs {
@@ -36,15 +37,16 @@ impl From<FixesJob> for FixesJobArgs {
         Self {
             fixes: value.fixes,
             batch_id: value.batch_id,
-            started: value.started,
         }
     }
 }
 
+/// Runs every [`Fix`] in a [`FixBatch`]. Fixes run concurrently, up to the context's
+/// [fix concurrency limit](DalContext::fix_concurrency_limit()), in the order decided by a
+/// [`FixPlan`].
 #[derive(Clone, Debug, Serialize)]
 pub struct FixesJob {
     fixes: Vec<FixItem>,
-    started: bool,
     batch_id: FixBatchId,
     access_builder: AccessBuilder,
     visibility: Visibility,
@@ -53,26 +55,11 @@ pub struct FixesJob {
 
 impl FixesJob {
     pub fn new(ctx: &DalContext, fixes: Vec<FixItem>, batch_id: FixBatchId) -> Box<Self> {
-        Self::new_raw(ctx, fixes, batch_id, false)
-    }
-
-    /// Used for creating another fix job in a "fixes" sequence.
-    fn new_iteration(ctx: &DalContext, fixes: Vec<FixItem>, batch_id: FixBatchId) -> Box<Self> {
-        Self::new_raw(ctx, fixes, batch_id, true)
-    }
-
-    fn new_raw(
-        ctx: &DalContext,
-        fixes: Vec<FixItem>,
-        batch_id: FixBatchId,
-        started: bool,
-    ) -> Box<Self> {
         let access_builder = AccessBuilder::from(ctx.clone());
         let visibility = *ctx.visibility();
 
         Box::new(Self {
             fixes,
-            started,
             batch_id,
             access_builder,
             visibility,
@@ -104,113 +91,249 @@ impl JobConsumerMetadata for FixesJob {
 #[async_trait]
 impl JobConsumer for FixesJob {
     async fn run(&self, ctx: &mut DalContext) -> JobConsumerResult<()> {
-        // Mark the batch as started if it has not been yet.
-        if !self.started {
-            let mut batch = FixBatch::get_by_id(ctx, &self.batch_id)
-                .await?
-                .ok_or(JobConsumerError::MissingFixBatch(self.batch_id))?;
+        // Mark the batch as started if it has not been yet. It will have been if this job is
+        // being retried.
+        let mut batch = FixBatch::get_by_id(ctx, &self.batch_id)
+            .await?
+            .ok_or(JobConsumerError::MissingFixBatch(self.batch_id))?;
+        if batch.started_at().is_none() {
             batch.stamp_started(ctx).await?;
+            ctx.commit().await?;
         }
 
-        if self.fixes.is_empty() {
-            return finish_batch(ctx, self.batch_id).await;
+        let fix_components: Vec<(FixId, ComponentId)> = self
+            .fixes
+            .iter()
+            .map(|fix_item| (fix_item.id, fix_item.component_id))
+            .collect();
+        let mut plan = FixPlan::new(ctx, &fix_components).await?;
+
+        // Fixes which already finished were run by a previous attempt at this job.
+        let mut fix_items = HashMap::new();
+        let mut unsuccessful = Vec::new();
+        for fix_item in &self.fixes {
+            let fix = Fix::get_by_id(ctx, &fix_item.id)
+                .await?
+                .ok_or(FixError::MissingFix(fix_item.id))?;
+            match (fix.finished_at(), fix.completion_status()) {
+                (Some(_), Some(FixCompletionStatus::Success)) => plan.complete(fix_item.id),
+                (Some(_), _) => unsuccessful.push(fix_item.id),
+                (None, _) => {
+                    fix_items.insert(fix_item.id, fix_item.clone());
+                }
+            }
+        }
+        for failed_fix_id in unsuccessful {
+            for skipped_id in plan.fail(failed_fix_id) {
+                if fix_items.remove(&skipped_id).is_some() {
+                    skip_fix(ctx, skipped_id, failed_fix_id).await?;
+                }
+            }
         }
-        let fix_item = &self.fixes[0];
-
-        let deleted_ctx = &ctx.clone_with_delete_visibility();
-        // Get the workflow for the action we need to run.
-        let component = Component::get_by_id(deleted_ctx, &fix_item.component_id)
-            .await?
-            .ok_or(JobConsumerError::ComponentNotFound(fix_item.component_id))?;
-
-        let action = ActionPrototype::get_by_id(ctx, &fix_item.action_prototype_id)
-            .await?
-            .ok_or_else(|| {
-                JobConsumerError::ActionPrototypeNotFound(fix_item.action_prototype_id)
-            })?;
 
-        // Run the fix (via the action prototype).
-        let mut fix = Fix::get_by_id(ctx, &fix_item.id)
-            .await?
-            .ok_or(FixError::MissingFix(fix_item.id))?;
-        let resource = fix.run(ctx, &action).await?;
-        let completion_status: FixCompletionStatus = *fix
-            .completion_status()
-            .ok_or(FixError::EmptyCompletionStatus)?;
-
-        // Upsert the fix resolver.
-        FixResolver::upsert(
-            ctx,
-            *action.id(),
-            fix_item.attribute_value_id,
-            Some(matches!(completion_status, FixCompletionStatus::Success)),
-            *fix.id(),
-        )
-        .await?;
+        let concurrency_limit = ctx.fix_concurrency_limit().max(1);
+        let mut running = FuturesUnordered::new();
+        let mut first_error = None;
+        loop {
+            // Stop starting fixes after an error, but let the ones already running finish.
+            while first_error.is_none() && running.len() < concurrency_limit {
+                let fix_item = match plan.next_ready().and_then(|id| fix_items.remove(&id)) {
+                    Some(fix_item) => fix_item,
+                    None => break,
+                };
+
+                // Each fix commits its own progress, so each needs its own transactions.
+                let fix_ctx = ctx
+                    .services_context()
+                    .into_builder(ctx.blocking())
+                    .build(ctx.access_builder().build(*ctx.visibility()))
+                    .await?;
+                let batch_id = self.batch_id;
+                running.push(async move {
+                    let fix_id = fix_item.id;
+                    (fix_id, run_fix(fix_ctx, fix_item, batch_id).await)
+                });
+            }
+
+            match running.next().await {
+                Some((fix_id, Ok(FixCompletionStatus::Success))) => plan.complete(fix_id),
+                Some((fix_id, Ok(_))) => {
+                    // Fixes downstream of one which didn't succeed can't rely on what it did
+                    for skipped_id in plan.fail(fix_id) {
+                        if fix_items.remove(&skipped_id).is_none() {
+                            continue;
+                        }
+                        if let Err(err) = skip_fix(ctx, skipped_id, fix_id).await {
+                            error!(fix_id = %skipped_id, error = ?err, "unable to skip fix");
+                            first_error.get_or_insert(err);
+                        }
+                    }
+                }
+                Some((fix_id, Err(err))) => {
+                    error!(%fix_id, error = ?err, "unable to run fix");
+                    first_error.get_or_insert(err);
+                }
+                None => break,
+            }
+        }
 
-        let logs: Vec<_> = match resource {
-            Some(r) => r
-                .logs
-                .iter()
-                .flat_map(|l| l.split('\n'))
-                .map(|l| l.to_owned())
-                .collect(),
-            None => vec![],
-        };
-
-        let attribute_value = Component::root_prop_child_attribute_value_for_component(
-            ctx,
-            *component.id(),
-            RootPropChild::Resource,
-        )
-        .await?;
+        if let Some(err) = first_error {
+            // The batch is finished either way, so that it doesn't look like it's still running.
+            // The error is returned afterwards, so the context has to be committed here.
+            match finish_batch(ctx, self.batch_id).await {
+                Ok(()) => ctx.commit().await?,
+                Err(finish_err) => {
+                    error!(error = ?finish_err, "unable to finish fix batch after an error");
+                }
+            }
+            return Err(err);
+        }
 
-        // Always retriggers confirmations, and propagates resource if it changed.
-        ctx.enqueue_job(DependentValuesUpdate::new(
-            ctx.access_builder(),
-            *ctx.visibility(),
-            vec![*attribute_value.id()],
-        ))
-        .await?;
+        finish_batch(ctx, self.batch_id).await
+    }
 
-        // Commit progress so far, and wait for dependent values propagation so we can run
-        // consecutive fixes that depend on the /root/resource from the previous fix.
-        // `blocking_commit()` will wait for any jobs that have ben created through
-        // `enqueue_job(...)` to finish before moving on.
-        ctx.blocking_commit().await?;
-
-        component.act(ctx, ActionKind::Refresh).await?;
-
-        ctx.blocking_commit().await?;
-
-        WsEvent::fix_return(
-            ctx,
-            *fix.id(),
-            self.batch_id,
-            fix_item.attribute_value_id,
-            *action.kind(),
-            completion_status,
-            logs,
-        )
-        .await?
-        .publish_on_commit(ctx)
-        .await?;
+    /// Finishes the batch, marking every fix which never finished as errored, so that a batch
+    /// whose job won't be run again doesn't look like it's still running.
+    async fn abandon(&self, ctx: &mut DalContext, reason: &str) -> JobConsumerResult<()> {
+        let mut batch = FixBatch::get_by_id(ctx, &self.batch_id)
+            .await?
+            .ok_or(JobConsumerError::MissingFixBatch(self.batch_id))?;
+        if batch.finished_at().is_some() {
+            return Ok(());
+        }
+        if batch.started_at().is_none() {
+            batch.stamp_started(ctx).await?;
+        }
 
-        if self.fixes.len() == 1 {
-            finish_batch(ctx, self.batch_id).await?;
-        } else {
-            ctx.enqueue_job(FixesJob::new_iteration(
+        for fix_item in &self.fixes {
+            let mut fix = Fix::get_by_id(ctx, &fix_item.id)
+                .await?
+                .ok_or(FixError::MissingFix(fix_item.id))?;
+            if fix.finished_at().is_some() {
+                continue;
+            }
+            if fix.started_at().is_none() {
+                fix.stamp_started(ctx).await?;
+            }
+            fix.stamp_finished(
                 ctx,
-                self.fixes.iter().skip(1).cloned().collect(),
-                self.batch_id,
-            ))
+                FixCompletionStatus::Error,
+                Some(format!("Fix was abandoned before it finished: {reason}")),
+                None,
+            )
             .await?;
         }
 
-        Ok(())
+        finish_batch(ctx, self.batch_id).await
     }
 }
 
+/// Marks a fix which will never run, because a fix it depends on didn't succeed, as finished.
+async fn skip_fix(ctx: &DalContext, fix_id: FixId, failed_fix_id: FixId) -> JobConsumerResult<()> {
+    let mut fix = Fix::get_by_id(ctx, &fix_id)
+        .await?
+        .ok_or(FixError::MissingFix(fix_id))?;
+    fix.stamp_started(ctx).await?;
+    fix.stamp_finished(
+        ctx,
+        FixCompletionStatus::Failure,
+        Some(format!(
+            "Skipped because fix {failed_fix_id}, which it depends on, did not succeed"
+        )),
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Runs a single fix, committing the [`DalContext`] when it's done, and returns how it went.
+async fn run_fix(
+    ctx: DalContext,
+    fix_item: FixItem,
+    batch_id: FixBatchId,
+) -> JobConsumerResult<FixCompletionStatus> {
+    let deleted_ctx = &ctx.clone_with_delete_visibility();
+    // Get the workflow for the action we need to run.
+    let component = Component::get_by_id(deleted_ctx, &fix_item.component_id)
+        .await?
+        .ok_or(JobConsumerError::ComponentNotFound(fix_item.component_id))?;
+
+    let action = ActionPrototype::get_by_id(&ctx, &fix_item.action_prototype_id)
+        .await?
+        .ok_or_else(|| JobConsumerError::ActionPrototypeNotFound(fix_item.action_prototype_id))?;
+
+    // Run the fix (via the action prototype).
+    let mut fix = Fix::get_by_id(&ctx, &fix_item.id)
+        .await?
+        .ok_or(FixError::MissingFix(fix_item.id))?;
+    let resource = fix.run(&ctx, &action).await?;
+    let completion_status: FixCompletionStatus = *fix
+        .completion_status()
+        .ok_or(FixError::EmptyCompletionStatus)?;
+
+    // Upsert the fix resolver.
+    FixResolver::upsert(
+        &ctx,
+        *action.id(),
+        fix_item.attribute_value_id,
+        Some(matches!(completion_status, FixCompletionStatus::Success)),
+        *fix.id(),
+    )
+    .await?;
+
+    let logs: Vec<_> = match resource {
+        Some(r) => r
+            .logs
+            .iter()
+            .flat_map(|l| l.split('\n'))
+            .map(|l| l.to_owned())
+            .collect(),
+        None => vec![],
+    };
+
+    let attribute_value = Component::root_prop_child_attribute_value_for_component(
+        &ctx,
+        *component.id(),
+        RootPropChild::Resource,
+    )
+    .await?;
+
+    // Always retriggers confirmations, and propagates resource if it changed.
+    ctx.enqueue_job(DependentValuesUpdate::new(
+        ctx.access_builder(),
+        *ctx.visibility(),
+        vec![*attribute_value.id()],
+    ))
+    .await?;
+
+    // Commit progress so far, and wait for dependent values propagation so we can run
+    // dependent fixes that depend on the /root/resource from this fix.
+    // `blocking_commit()` will wait for any jobs that have ben created through
+    // `enqueue_job(...)` to finish before moving on.
+    ctx.blocking_commit().await?;
+
+    component.act(&ctx, ActionKind::Refresh).await?;
+
+    ctx.blocking_commit().await?;
+
+    WsEvent::fix_return(
+        &ctx,
+        *fix.id(),
+        batch_id,
+        fix_item.attribute_value_id,
+        *action.kind(),
+        completion_status,
+        logs,
+    )
+    .await?
+    .publish_on_commit(&ctx)
+    .await?;
+
+    ctx.commit().await?;
+
+    Ok(completion_status)
+}
+
 impl TryFrom<JobInfo> for FixesJob {
     type Error = JobConsumerError;
 
@@ -220,7 +343,6 @@ impl TryFrom<JobInfo> for FixesJob {
         Ok(Self {
             fixes: args.fixes,
             batch_id: args.batch_id,
-            started: args.started,
             access_builder: job.access_builder,
             visibility: job.visibility,
             job: Some(job),
@@ -11,8 +11,8 @@ use crate::{
         },
         producer::{JobProducer, JobProducerResult},
     },
-    AccessBuilder, ActionKind, Component, ComponentId, DalContext, StandardModel, Visibility,
-    WsEvent,
+    AccessBuilder, ActionKind, Component, ComponentDrift, ComponentId, DalContext, StandardModel,
+    Visibility, WsEvent,
 };
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -90,6 +90,7 @@ impl JobConsumer for RefreshJob {
                 .await?
                 .ok_or(JobConsumerError::ComponentNotFound(*component_id))?;
             component.act(ctx, ActionKind::Refresh).await?;
+            ComponentDrift::update_for_component(ctx, *component.id()).await?;
 
             WsEvent::resource_refreshed(ctx, *component.id())
                 .await?
@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use dyn_clone::DynClone;
+use si_data_nats::NatsError;
 use thiserror::Error;
 
 use crate::{
@@ -15,6 +16,10 @@ pub use nats_processor::NatsProcessor;
 pub enum JobQueueProcessorError {
     #[error("Error processing blocking job: {0}")]
     BlockingJob(#[from] BlockingJobError),
+    #[error(
+        "could not publish {0} job to the durable job queue, has pinga set up its stream? {1}"
+    )]
+    JobNotQueued(String, #[source] NatsError),
     #[error(transparent)]
     JobProducer(#[from] JobProducerError),
     #[error(transparent)]
@@ -16,26 +16,35 @@ use crate::{
 use super::{JobQueueProcessor, JobQueueProcessorError, JobQueueProcessorResult};
 
 const NATS_JOB_QUEUE: &str = "pinga-jobs";
+/// Non-blocking jobs are published to a subject captured by pinga's durable job queue, so they
+/// survive pinga restarts. Blocking jobs need a reply and so stay on [`NATS_JOB_QUEUE`].
+const NATS_DURABLE_JOB_QUEUE: &str = "pinga-jobs.durable";
 
 #[derive(Clone, Debug)]
 pub struct NatsProcessor {
     client: NatsClient,
     queue: JobQueue,
     pinga_subject: String,
+    pinga_durable_subject: String,
 }
 
 impl NatsProcessor {
     pub fn new(client: NatsClient) -> Self {
-        let pinga_subject = if let Some(prefix) = client.metadata().subject_prefix() {
-            format!("{prefix}.{NATS_JOB_QUEUE}")
-        } else {
-            NATS_JOB_QUEUE.to_owned()
-        };
+        let (pinga_subject, pinga_durable_subject) =
+            if let Some(prefix) = client.metadata().subject_prefix() {
+                (
+                    format!("{prefix}.{NATS_JOB_QUEUE}"),
+                    format!("{prefix}.{NATS_DURABLE_JOB_QUEUE}"),
+                )
+            } else {
+                (NATS_JOB_QUEUE.to_owned(), NATS_DURABLE_JOB_QUEUE.to_owned())
+            };
 
         Self {
             client,
             queue: JobQueue::new(),
             pinga_subject,
+            pinga_durable_subject,
         }
     }
 
@@ -45,11 +54,19 @@ impl NatsProcessor {
 
             if let Err(err) = self
                 .client
-                .publish(&self.pinga_subject, serde_json::to_vec(&job_info)?)
+                .jetstream()
+                .publish(&self.pinga_durable_subject, serde_json::to_vec(&job_info)?)
                 .await
             {
-                error!("Nats job push failed, some jobs will be dropped");
-                return Err(JobQueueProcessorError::Transport(Box::new(err)));
+                let dropped = self.queue.drain().await.len() + 1;
+                error!(
+                    error = ?err,
+                    job.id = %job_info.id,
+                    job.kind = %job_info.kind,
+                    dropped,
+                    "Nats job push failed, dropping the remaining jobs",
+                );
+                return Err(JobQueueProcessorError::JobNotQueued(job_info.kind, err));
             }
         }
         Ok(())
@@ -138,15 +155,11 @@ impl JobQueueProcessor for NatsProcessor {
         }
     }
 
+    /// Publishes the queued jobs, failing if any can't be. Publishing to the durable job queue
+    /// only succeeds once pinga has created the stream backing it, so rather than losing jobs
+    /// quietly in the background, the failure is handed back to whoever is committing.
     async fn process_queue(&self) -> JobQueueProcessorResult<()> {
-        let processor = self.clone();
-        tokio::spawn(async move {
-            if let Err(err) = processor.push_all_jobs().await {
-                error!("Unable to push jobs to nats: {err}");
-            }
-        });
-
-        Ok(())
+        self.push_all_jobs().await
     }
 
     async fn blocking_process_queue(&self) -> JobQueueProcessorResult<()> {
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use base64::{engine::general_purpose, Engine};
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
@@ -7,8 +9,8 @@ use telemetry::prelude::*;
 use thiserror::Error;
 
 use crate::{
-    pk, standard_model_accessor_ro, DalContext, HistoryEvent, HistoryEventError, Timestamp,
-    TransactionsError, Workspace, WorkspaceError, WorkspacePk,
+    pk, standard_model_accessor_ro, DalContext, EncryptedSecret, HistoryEvent, HistoryEventError,
+    SecretError, Timestamp, TransactionsError, Workspace, WorkspaceError, WorkspacePk,
 };
 
 mod key_pair_box_public_key_serde;
@@ -16,6 +18,7 @@ mod key_pair_box_secret_key_serde;
 
 const PUBLIC_KEY_GET_CURRENT: &str = include_str!("./queries/public_key_get_current.sql");
 const KEY_PAIR_GET_BY_PK: &str = include_str!("queries/key_pair_get_by_pk.sql");
+const KEY_PAIR_RETIRE_UNUSED: &str = include_str!("queries/key_pair_retire_unused.sql");
 
 #[remain::sorted]
 #[derive(Error, Debug)]
@@ -30,6 +33,8 @@ pub enum KeyPairError {
     NoCurrentKeyPair,
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("secret error: {0}")]
+    Secret(#[from] Box<SecretError>),
     #[error("error serializing/deserializing json: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error("transactions error: {0}")]
@@ -42,6 +47,18 @@ pub type KeyPairResult<T> = Result<T, KeyPairError>;
 
 pk!(KeyPairPk);
 
+/// The outcome of [`KeyPair::rotate()`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPairRotation {
+    /// The new current key pair for the workspace.
+    pub key_pair_pk: KeyPairPk,
+    /// The key pairs that no longer have any secrets sealed to them and have been retired.
+    pub retired_key_pair_pks: Vec<KeyPairPk>,
+    /// How many secret rows were re-encrypted.
+    pub reencrypted_secret_count: usize,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub struct KeyPair {
     pk: KeyPairPk,
@@ -120,6 +137,62 @@ impl KeyPair {
         Ok(serde_json::from_value(json)?)
     }
 
+    /// Rotates the workspace's key pair: generates a new current [`KeyPair`], re-encrypts every
+    /// [`EncryptedSecret`] in the workspace under it (moving them to
+    /// [`SecretVersion::V2`](crate::SecretVersion::V2)) and then retires the old key pairs.
+    ///
+    /// Old key pairs are only retired once no secret is sealed to them, so a failure part way
+    /// through (which rolls back the transaction anyway) can never orphan a secret.
+    pub async fn rotate(ctx: &DalContext) -> KeyPairResult<KeyPairRotation> {
+        let current = Self::get_current(ctx).await?;
+        let new = Self::new(ctx, &current.name).await?;
+
+        let mut key_pairs: HashMap<KeyPairPk, Self> = HashMap::new();
+        let secrets = EncryptedSecret::list_for_reencryption(ctx, new.pk)
+            .await
+            .map_err(Box::new)?;
+        let reencrypted_secret_count = secrets.len();
+        for mut secret in secrets {
+            let sealed_to_pk = secret.key_pair_pk();
+            if !key_pairs.contains_key(&sealed_to_pk) {
+                key_pairs.insert(sealed_to_pk, Self::get_by_pk(ctx, sealed_to_pk).await?);
+            }
+            secret
+                .reencrypt(ctx, &key_pairs[&sealed_to_pk], &new)
+                .await
+                .map_err(Box::new)?;
+        }
+
+        let retired_key_pair_pks = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                KEY_PAIR_RETIRE_UNUSED,
+                &[&ctx.tenancy().workspace_pk(), &new.pk],
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.try_get("pk"))
+            .collect::<Result<Vec<KeyPairPk>, _>>()?;
+
+        let rotation = KeyPairRotation {
+            key_pair_pk: new.pk,
+            retired_key_pair_pks,
+            reencrypted_secret_count,
+        };
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "key_pair.rotate".to_owned(),
+            "Key Pair rotated".to_owned(),
+            &serde_json::to_value(&rotation)?,
+        )
+        .await?;
+
+        Ok(rotation)
+    }
+
     standard_model_accessor_ro!(name, String);
     standard_model_accessor_ro!(workspace_pk, WorkspacePk);
     standard_model_accessor_ro!(public_key, BoxPublicKey);
@@ -19,6 +19,7 @@ use crate::builtins::SelectedTestBuiltinSchemas;
 
 pub mod action_prototype;
 pub mod actor_view;
+pub mod api_token;
 pub mod attribute;
 pub mod builtins;
 pub mod change_set;
@@ -28,6 +29,7 @@ pub mod component;
 pub mod context;
 pub mod cyclone_key_pair;
 pub mod diagram;
+pub mod drift;
 pub mod edge;
 pub mod fix;
 pub mod func;
@@ -63,13 +65,16 @@ pub mod timestamp;
 pub mod user;
 pub mod validation;
 pub mod visibility;
+pub mod webhook;
 pub mod workspace;
 pub mod ws_event;
 
 pub use action_prototype::{
-    ActionKind, ActionPrototype, ActionPrototypeContext, ActionPrototypeError, ActionPrototypeId,
+    ActionKind, ActionPlan, ActionPrototype, ActionPrototypeContext, ActionPrototypeError,
+    ActionPrototypeId,
 };
 pub use actor_view::ActorView;
+pub use api_token::{ApiToken, ApiTokenError, ApiTokenPk, ApiTokenResult};
 pub use attribute::value::view::AttributeView;
 pub use attribute::{
     context::{
@@ -89,7 +94,11 @@ pub use attribute::{
     },
 };
 pub use builtins::{BuiltinsError, BuiltinsResult};
-pub use change_set::{ChangeSet, ChangeSetError, ChangeSetPk, ChangeSetStatus};
+pub use change_set::{
+    ChangeSet, ChangeSetApproval, ChangeSetApprovalDecision, ChangeSetApprovalPk,
+    ChangeSetConflict, ChangeSetConflictChoice, ChangeSetConflictKind, ChangeSetConflictObject,
+    ChangeSetConflictResolution, ChangeSetDiff, ChangeSetError, ChangeSetPk, ChangeSetStatus,
+};
 pub use code_view::{CodeLanguage, CodeView};
 pub use component::{
     resource::ResourceView, status::ComponentStatus, status::HistoryActorTimestamp, Component,
@@ -97,14 +106,16 @@ pub use component::{
 };
 pub use context::{
     AccessBuilder, Connections, DalContext, DalContextBuilder, RequestContext, ServicesContext,
-    Transactions, TransactionsError,
+    Transactions, TransactionsError, DEFAULT_FIX_CONCURRENCY_LIMIT,
 };
 pub use cyclone_key_pair::CycloneKeyPair;
 pub use diagram::{
     connection::Connection, connection::DiagramEdgeView, Diagram, DiagramError, DiagramKind,
 };
+pub use drift::{ComponentDrift, ComponentDriftId, DriftError, DriftSummary, DriftedProp};
 pub use edge::{Edge, EdgeError, EdgeResult};
 pub use fix::batch::{FixBatch, FixBatchId};
+pub use fix::plan::FixPlan;
 pub use fix::resolver::{FixResolver, FixResolverError, FixResolverId};
 pub use fix::{Fix, FixCompletionStatus, FixError, FixId};
 pub use func::argument::FuncArgument;
@@ -116,13 +127,15 @@ pub use func::{
     binding::{FuncBinding, FuncBindingError, FuncBindingId},
     Func, FuncError, FuncId, FuncResult,
 };
-pub use history_event::{HistoryActor, HistoryEvent, HistoryEventError};
+pub use history_event::{
+    HistoryActor, HistoryEvent, HistoryEventError, HistoryEventFilter, HistoryEventPk,
+};
 pub use index_map::IndexMap;
 pub use job::definition::DependentValuesUpdate;
 pub use job::processor::{JobQueueProcessor, NatsProcessor};
 pub use job_failure::{JobFailure, JobFailureError, JobFailureResult};
 pub use jwt_key::JwtPublicSigningKey;
-pub use key_pair::{KeyPair, KeyPairError, KeyPairResult, PublicKey};
+pub use key_pair::{KeyPair, KeyPairError, KeyPairResult, KeyPairRotation, PublicKey};
 pub use label_list::{LabelEntry, LabelList, LabelListError};
 pub use node::NodeId;
 pub use node::{Node, NodeError, NodeKind};
@@ -148,8 +161,9 @@ pub use schema::variant::root_prop::RootPropChild;
 pub use schema::variant::SchemaVariantError;
 pub use schema::{Schema, SchemaError, SchemaId, SchemaPk, SchemaVariant, SchemaVariantId};
 pub use secret::{
-    DecryptedSecret, EncryptedSecret, Secret, SecretAlgorithm, SecretError, SecretId, SecretKind,
-    SecretObjectType, SecretPk, SecretResult, SecretVersion,
+    DecryptedSecret, EncryptedSecret, Secret, SecretAlgorithm, SecretBackendKind, SecretDefinition,
+    SecretDefinitionId, SecretError, SecretId, SecretKind, SecretObjectType, SecretPk,
+    SecretReference, SecretResult, SecretVersion,
 };
 pub use socket::{Socket, SocketArity, SocketId};
 pub use standard_model::{StandardModel, StandardModelError, StandardModelResult};
@@ -158,7 +172,10 @@ pub use status::{
 };
 pub use tenancy::{Tenancy, TenancyError};
 pub use timestamp::{Timestamp, TimestampError};
-pub use user::{User, UserClaim, UserError, UserPk, UserResult};
+pub use user::{
+    AccessLevel, Permission, PermissionScope, User, UserClaim, UserError, UserPk, UserResult,
+    WorkspaceRole,
+};
 pub use validation::prototype::{
     context::ValidationPrototypeContext, ValidationPrototype, ValidationPrototypeError,
     ValidationPrototypeId,
@@ -167,6 +184,10 @@ pub use validation::resolver::{
     ValidationResolver, ValidationResolverError, ValidationResolverId, ValidationStatus,
 };
 pub use visibility::{Visibility, VisibilityError};
+pub use webhook::{
+    Webhook, WebhookDelivery, WebhookDeliveryPk, WebhookError, WebhookPendingDelivery,
+    WebhookPendingDeliveryPk, WebhookPk, WebhookResult, WebhookSendOutcome,
+};
 pub use workspace::{Workspace, WorkspaceError, WorkspacePk, WorkspaceResult, WorkspaceSignup};
 pub use ws_event::{WsEvent, WsEventError, WsEventResult, WsPayload};
 
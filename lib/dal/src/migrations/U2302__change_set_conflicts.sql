@@ -0,0 +1,16 @@
+-- Throws away the change set's copy of a row so that, on apply, the row on head is left as-is.
+-- This is how a conflict is resolved in favor of head.
+CREATE OR REPLACE FUNCTION change_set_discard_object_v1(this_table_name text,
+                                                        this_change_set_pk ident,
+                                                        this_id ident,
+                                                        this_tenancy jsonb)
+    RETURNS void AS
+$$
+BEGIN
+    EXECUTE format('DELETE FROM %1$I ' ||
+                   'WHERE id = %2$L ' ||
+                   '  AND visibility_change_set_pk = %3$L ' ||
+                   '  AND in_tenancy_v1(%4$L, tenancy_workspace_pk)',
+                   this_table_name, this_id, this_change_set_pk, this_tenancy);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,51 @@
+-- Moves a change set's "base" forward to the current state of head.
+--
+-- Every row a change set touches is a copy of the head row, and the copy's created_at marks when
+-- the change set forked it. For each row whose head counterpart has moved on since then:
+--
+-- * if the change set's copy is now identical to head, the copy is dropped so head shows through
+-- * otherwise the copy is kept, and re-forked from the current head by bumping its created_at
+--
+-- Conflicts are expected to be resolved (or refused) before this is called.
+CREATE OR REPLACE FUNCTION change_set_rebase_v1(this_change_set_pk ident,
+                                                this_tenancy jsonb,
+                                                OUT timestamp_updated_at timestamp with time zone) AS
+$$
+DECLARE
+    standard_model  standard_models%ROWTYPE;
+    this_table_name regclass;
+BEGIN
+    UPDATE change_sets
+    SET updated_at = clock_timestamp()
+    WHERE pk = this_change_set_pk
+    RETURNING updated_at INTO timestamp_updated_at;
+
+    FOR standard_model IN SELECT * FROM standard_models
+        LOOP
+            this_table_name := standard_model.table_name::regclass;
+
+            EXECUTE format('DELETE FROM %1$I AS change_set_rows ' ||
+                           'USING %1$I AS head_rows ' ||
+                           'WHERE change_set_rows.visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, change_set_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.id = change_set_rows.id ' ||
+                           '  AND head_rows.visibility_change_set_pk = ident_nil_v1() ' ||
+                           '  AND in_tenancy_v1(%3$L, head_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.updated_at > change_set_rows.created_at ' ||
+                           '  AND (to_jsonb(change_set_rows) - ARRAY[''pk'', ''visibility_change_set_pk'', ''created_at'', ''updated_at'']) ' ||
+                           '    = (to_jsonb(head_rows) - ARRAY[''pk'', ''visibility_change_set_pk'', ''created_at'', ''updated_at''])',
+                           this_table_name, this_change_set_pk, this_tenancy);
+
+            EXECUTE format('UPDATE %1$I AS change_set_rows ' ||
+                           'SET created_at = clock_timestamp() ' ||
+                           'FROM %1$I AS head_rows ' ||
+                           'WHERE change_set_rows.visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, change_set_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.id = change_set_rows.id ' ||
+                           '  AND head_rows.visibility_change_set_pk = ident_nil_v1() ' ||
+                           '  AND in_tenancy_v1(%3$L, head_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.updated_at > change_set_rows.created_at',
+                           this_table_name, this_change_set_pk, this_tenancy);
+        END LOOP;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,73 @@
+-- Secrets can now hold a sealed reference to a value in an external store (Vault, a file, an
+-- environment variable) rather than the value itself. Existing secrets are all database-backed.
+ALTER TABLE encrypted_secrets
+    ADD COLUMN backend text NOT NULL DEFAULT 'database';
+
+CREATE OR REPLACE VIEW secrets AS
+SELECT pk,
+       id,
+       tenancy_workspace_pk,
+       visibility_change_set_pk,
+       visibility_deleted_at,
+       key_pair_pk,
+       created_at,
+       updated_at,
+       name,
+       object_type,
+       kind,
+       backend
+FROM encrypted_secrets;
+
+DROP FUNCTION IF EXISTS encrypted_secret_create_v1(jsonb, jsonb, text, text, text, text, text, text, ident);
+CREATE OR REPLACE FUNCTION encrypted_secret_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_name text,
+    this_object_type text,
+    this_kind text,
+    this_crypted text,
+    this_version text,
+    this_algorithm text,
+    this_key_pair_pk ident,
+    this_backend text,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           encrypted_secrets%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO encrypted_secrets (tenancy_workspace_pk,
+                                   visibility_change_set_pk,
+                                   name,
+                                   object_type,
+                                   kind,
+                                   crypted,
+                                   version,
+                                   algorithm,
+                                   key_pair_pk,
+                                   backend)
+    VALUES (this_tenancy_record.tenancy_workspace_pk,
+            this_visibility_record.visibility_change_set_pk,
+            this_name,
+            this_object_type,
+            this_kind,
+            this_crypted,
+            this_version,
+            this_algorithm,
+            this_key_pair_pk,
+            this_backend)
+    RETURNING * INTO this_new_row;
+
+    -- Purge the returning record of sensitive data to avoid accidentally
+    -- deserializing these fields in application code
+    this_new_row.crypted = null;
+    this_new_row.version = null;
+    this_new_row.algorithm = null;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,52 @@
+CREATE TABLE secret_definitions
+(
+    pk                          ident                    PRIMARY KEY DEFAULT ident_create_v1(),
+    id                          ident                    NOT NULL DEFAULT ident_create_v1(),
+    tenancy_workspace_pk        ident,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    name                        text                     NOT NULL,
+    description                 text,
+    json_schema                 jsonb                    NOT NULL
+);
+CREATE UNIQUE INDEX unique_secret_definition_name ON secret_definitions (
+    name,
+    tenancy_workspace_pk,
+    visibility_change_set_pk)
+    WHERE visibility_deleted_at IS NULL;
+SELECT standard_model_table_constraints_v1('secret_definitions');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('secret_definitions', 'model', 'secret_definition', 'Secret Definition');
+
+CREATE OR REPLACE FUNCTION secret_definition_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_name text,
+    this_description text,
+    this_json_schema jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           secret_definitions%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO secret_definitions (
+        tenancy_workspace_pk, visibility_change_set_pk,
+        name, description, json_schema
+    ) VALUES (
+        this_tenancy_record.tenancy_workspace_pk,
+        this_visibility_record.visibility_change_set_pk,
+        this_name, this_description, this_json_schema
+    )
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,50 @@
+CREATE TABLE component_drifts
+(
+    pk                          ident                    PRIMARY KEY DEFAULT ident_create_v1(),
+    id                          ident                    NOT NULL DEFAULT ident_create_v1(),
+    tenancy_workspace_pk        ident,
+    visibility_change_set_pk    ident                    NOT NULL DEFAULT ident_nil_v1(),
+    visibility_deleted_at       timestamp with time zone,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    component_id                ident                    NOT NULL,
+    drifted_props               jsonb                    NOT NULL DEFAULT '[]'::jsonb
+);
+CREATE UNIQUE INDEX unique_component_drift_component ON component_drifts (
+    component_id,
+    tenancy_workspace_pk,
+    visibility_change_set_pk)
+    WHERE visibility_deleted_at IS NULL;
+SELECT standard_model_table_constraints_v1('component_drifts');
+
+INSERT INTO standard_models (table_name, table_type, history_event_label_base, history_event_message_name)
+VALUES ('component_drifts', 'model', 'component_drift', 'Component Drift');
+
+CREATE OR REPLACE FUNCTION component_drift_create_v1(
+    this_tenancy jsonb,
+    this_visibility jsonb,
+    this_component_id ident,
+    this_drifted_props jsonb,
+    OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record    tenancy_record_v1;
+    this_visibility_record visibility_record_v1;
+    this_new_row           component_drifts%ROWTYPE;
+BEGIN
+    this_tenancy_record := tenancy_json_to_columns_v1(this_tenancy);
+    this_visibility_record := visibility_json_to_columns_v1(this_visibility);
+
+    INSERT INTO component_drifts (
+        tenancy_workspace_pk, visibility_change_set_pk,
+        component_id, drifted_props
+    ) VALUES (
+        this_tenancy_record.tenancy_workspace_pk,
+        this_visibility_record.visibility_change_set_pk,
+        this_component_id, this_drifted_props
+    )
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,95 @@
+-- How many approvals a change set needs before it can be applied. Zero turns approvals off.
+ALTER TABLE workspaces
+    ADD COLUMN required_change_set_approvals integer NOT NULL DEFAULT 0;
+
+-- The user who asked for the change set to be reviewed. They can't approve it themselves.
+ALTER TABLE change_sets
+    ADD COLUMN approval_requested_by ident;
+
+CREATE TABLE change_set_reviewers
+(
+    change_set_pk               ident                    NOT NULL,
+    user_pk                     ident                    NOT NULL,
+    tenancy_workspace_pk        ident,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (change_set_pk, user_pk)
+);
+
+CREATE TABLE change_set_approvals
+(
+    pk                          ident primary key default ident_create_v1(),
+    change_set_pk               ident                    NOT NULL,
+    user_pk                     ident                    NOT NULL,
+    decision                    text                     NOT NULL,
+    comment                     text,
+    tenancy_workspace_pk        ident,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE UNIQUE INDEX unique_change_set_approval_user ON change_set_approvals (change_set_pk, user_pk);
+
+-- Moves the change set into review, replacing its reviewers and clearing any earlier decisions.
+CREATE OR REPLACE FUNCTION change_set_request_approval_v1(this_change_set_pk ident,
+                                                          this_requested_by ident,
+                                                          this_reviewers ident[],
+                                                          this_tenancy jsonb,
+                                                          OUT timestamp_updated_at timestamp with time zone) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    UPDATE change_sets
+    SET status                = 'NeedsApproval',
+        approval_requested_by = this_requested_by,
+        updated_at            = clock_timestamp()
+    WHERE pk = this_change_set_pk
+      AND in_tenancy_v1(this_tenancy, tenancy_workspace_pk)
+    RETURNING updated_at INTO timestamp_updated_at;
+
+    DELETE FROM change_set_reviewers WHERE change_set_pk = this_change_set_pk;
+    DELETE FROM change_set_approvals WHERE change_set_pk = this_change_set_pk;
+
+    INSERT INTO change_set_reviewers (change_set_pk, user_pk, tenancy_workspace_pk)
+    SELECT this_change_set_pk, reviewer, this_tenancy_record.tenancy_workspace_pk
+    FROM unnest(this_reviewers) AS reviewer
+    ON CONFLICT DO NOTHING;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Records a reviewer's decision, replacing any earlier decision of theirs. A rejection sends the
+-- change set back to being open.
+CREATE OR REPLACE FUNCTION change_set_decide_approval_v1(this_change_set_pk ident,
+                                                         this_user_pk ident,
+                                                         this_decision text,
+                                                         this_comment text,
+                                                         this_tenancy jsonb,
+                                                         OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_set_approvals%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    INSERT INTO change_set_approvals (change_set_pk, user_pk, decision, comment, tenancy_workspace_pk)
+    VALUES (this_change_set_pk, this_user_pk, this_decision, this_comment,
+            this_tenancy_record.tenancy_workspace_pk)
+    ON CONFLICT (change_set_pk, user_pk) DO UPDATE
+        SET decision   = EXCLUDED.decision,
+            comment    = EXCLUDED.comment,
+            updated_at = clock_timestamp()
+    RETURNING * INTO this_new_row;
+
+    IF this_decision = 'Rejected' THEN
+        UPDATE change_sets
+        SET status     = 'Open',
+            updated_at = clock_timestamp()
+        WHERE pk = this_change_set_pk
+          AND in_tenancy_v1(this_tenancy, tenancy_workspace_pk);
+    END IF;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
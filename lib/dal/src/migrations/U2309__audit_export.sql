@@ -0,0 +1,14 @@
+CREATE INDEX IF NOT EXISTS history_events_created_at_pk_idx
+    ON history_events (created_at, pk);
+CREATE INDEX IF NOT EXISTS history_events_tenancy_created_at_idx
+    ON history_events (tenancy_workspace_pk, created_at);
+
+-- How far each audit export sink has got through the history events, so that a restarted exporter
+-- carries on where it stopped instead of sending everything again.
+CREATE TABLE audit_export_cursors
+(
+    sink                  text PRIMARY KEY,
+    last_created_at       timestamp with time zone NOT NULL,
+    last_pk               ident                    NOT NULL,
+    updated_at            timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
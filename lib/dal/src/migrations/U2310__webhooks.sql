@@ -0,0 +1,79 @@
+CREATE TABLE webhooks
+(
+    pk                          ident primary key default ident_create_v1(),
+    url                         text                     NOT NULL,
+    -- Shared with the receiver, which uses it to check the signature of each delivery.
+    secret                      text                     NOT NULL,
+    event_kinds                 text[]                   NOT NULL,
+    description                 text,
+    enabled                     bool                     NOT NULL DEFAULT TRUE,
+    tenancy_workspace_pk        ident,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE INDEX webhooks_tenancy_workspace_pk_idx ON webhooks (tenancy_workspace_pk);
+
+-- One row per attempt at delivering an event to a webhook.
+CREATE TABLE webhook_deliveries
+(
+    pk                          ident primary key default ident_create_v1(),
+    webhook_pk                  ident                    NOT NULL REFERENCES webhooks (pk) ON DELETE CASCADE,
+    event_kind                  text                     NOT NULL,
+    payload                     jsonb                    NOT NULL,
+    attempt                     integer                  NOT NULL,
+    succeeded                   bool                     NOT NULL,
+    status_code                 integer,
+    error                       text,
+    tenancy_workspace_pk        ident,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE INDEX webhook_deliveries_webhook_pk_created_at_idx ON webhook_deliveries (webhook_pk, created_at);
+
+CREATE OR REPLACE FUNCTION webhook_create_v1(this_url text,
+                                             this_secret text,
+                                             this_event_kinds text[],
+                                             this_description text,
+                                             this_tenancy jsonb,
+                                             OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        webhooks%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    INSERT INTO webhooks (url, secret, event_kinds, description, tenancy_workspace_pk)
+    VALUES (this_url, this_secret, this_event_kinds, this_description,
+            this_tenancy_record.tenancy_workspace_pk)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+CREATE OR REPLACE FUNCTION webhook_delivery_create_v1(this_webhook_pk ident,
+                                                      this_event_kind text,
+                                                      this_payload jsonb,
+                                                      this_attempt integer,
+                                                      this_succeeded bool,
+                                                      this_status_code integer,
+                                                      this_error text,
+                                                      this_tenancy jsonb,
+                                                      OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        webhook_deliveries%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    INSERT INTO webhook_deliveries (webhook_pk, event_kind, payload, attempt, succeeded, status_code,
+                                    error, tenancy_workspace_pk)
+    VALUES (this_webhook_pk, this_event_kind, this_payload, this_attempt, this_succeeded,
+            this_status_code, this_error, this_tenancy_record.tenancy_workspace_pk)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
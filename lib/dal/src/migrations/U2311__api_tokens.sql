@@ -0,0 +1,40 @@
+-- Tokens for calling the headless API. Only a hash of each token is kept; the token itself is
+-- handed out once, when it is created.
+CREATE TABLE api_tokens
+(
+    pk                          ident primary key default ident_create_v1(),
+    name                        text                     NOT NULL,
+    user_pk                     ident                    NOT NULL,
+    token_hash                  text                     NOT NULL,
+    -- The start of the token, so that people can tell their tokens apart.
+    token_prefix                text                     NOT NULL,
+    last_used_at                timestamp with time zone,
+    revoked_at                  timestamp with time zone,
+    tenancy_workspace_pk        ident,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE UNIQUE INDEX api_tokens_token_hash_idx ON api_tokens (token_hash);
+CREATE INDEX api_tokens_tenancy_workspace_pk_idx ON api_tokens (tenancy_workspace_pk);
+
+CREATE OR REPLACE FUNCTION api_token_create_v1(this_name text,
+                                               this_user_pk ident,
+                                               this_token_hash text,
+                                               this_token_prefix text,
+                                               this_tenancy jsonb,
+                                               OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        api_tokens%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    INSERT INTO api_tokens (name, user_pk, token_hash, token_prefix, tenancy_workspace_pk)
+    VALUES (this_name, this_user_pk, this_token_hash, this_token_prefix,
+            this_tenancy_record.tenancy_workspace_pk)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,441 @@
+ALTER TABLE props
+    DROP CONSTRAINT valid_kind_check;
+ALTER TABLE props
+    ADD CONSTRAINT valid_kind_check CHECK (kind IN ('array', 'boolean', 'float', 'map', 'integer', 'object', 'string'));
+
+CREATE OR REPLACE FUNCTION attribute_value_update_for_context_raw_v1(this_tenancy                         jsonb,
+                                                                     this_visibility                      jsonb,
+                                                                     this_attribute_value_id              ident,
+                                                                     this_maybe_parent_attribute_value_id ident,
+                                                                     this_attribute_context               jsonb,
+                                                                     this_new_value                       jsonb,
+                                                                     this_key                             text,
+                                                                     this_create_child_proxies            bool,
+                                                                     OUT new_attribute_value_id           ident
+)
+AS
+$$
+DECLARE
+    attribute_prototype_id          ident;
+    attribute_value_id              ident;
+    func                            funcs%ROWTYPE;
+    func_args                       jsonb;
+    func_binding                    func_bindings%ROWTYPE;
+    func_binding_created            bool;
+    func_binding_id                 ident;
+    func_binding_return_value       func_binding_return_values;
+    func_binding_return_value_id    ident;
+    func_name                       text;
+    given_attribute_value           attribute_values%ROWTYPE;
+    maybe_attribute_value           attribute_values%ROWTYPE;
+    maybe_parent_attribute_value_id ident;
+    original_attribute_prototype    attribute_prototypes%ROWTYPE;
+    parent_attribute_context        jsonb;
+    parent_attribute_value          attribute_values%ROWTYPE;
+    prop                            props%ROWTYPE;
+    typeof_value                    text;
+BEGIN
+    RAISE DEBUG 'attribute_value_update_for_context_raw_v1: Tenancy(%), Visibility(%) AttributeValue(%) ParentAttributeValue(%) AttributeContext(%) Value(%) Key(%) CreateChild(%)',
+        this_tenancy,
+        this_visibility,
+        this_attribute_value_id,
+        this_maybe_parent_attribute_value_id,
+        this_attribute_context,
+        this_new_value,
+        this_key,
+        this_create_child_proxies;
+    maybe_parent_attribute_value_id := this_maybe_parent_attribute_value_id;
+
+    SELECT *
+    INTO given_attribute_value
+    FROM attribute_values_v1(this_tenancy, this_visibility) AS av
+    WHERE id = this_attribute_value_id;
+    IF NOT FOUND THEN
+        RAISE 'Unable to find AttributeValue(%) in Tenancy(%), Visibility(%)', this_attribute_value_id,
+                                                                               this_tenancy,
+                                                                               this_visibility;
+    END IF;
+
+    SELECT ap.*
+    INTO original_attribute_prototype
+    FROM attribute_prototypes_v1(this_tenancy, this_visibility) AS ap
+    INNER JOIN attribute_value_belongs_to_attribute_prototype_v1(this_tenancy, this_visibility) AS avbtap
+        ON avbtap.belongs_to_id = ap.id
+            AND avbtap.object_id = given_attribute_value.id;
+    IF original_attribute_prototype IS NULL THEN
+        SELECT INTO func_binding_id FROM attribute_value_belongs_to_attribute_prototype as avbtap where avbtap.object_id = given_attribute_value.id;
+        RAISE WARNING '%', func_binding_id;
+        RAISE 'Unable to find AttributePrototype for AttributeValue(%), Tenancy(%), Visibility(%)', given_attribute_value.id,
+                                                                                                    this_tenancy,
+                                                                                                    this_visibility;
+    END IF;
+
+    -- We need to make sure that all of the parents "exist" (are not the "unset" value).  We can't rely on the
+    -- client having created/set all of the parents already, as the parent might be an Object, or an Array/Map
+    -- (instead of an element in an Array/Map).  The client will only be creating new elements in Arrays/Maps,
+    -- and not Objects/Arrays/Maps themselves (unless the Object/Array/Map itself is the element of an
+    -- Array/Map).
+    IF maybe_parent_attribute_value_id IS NOT NULL THEN
+        SELECT *
+        INTO parent_attribute_value
+        FROM attribute_values_v1(this_tenancy, this_visibility) AS av
+        WHERE id = maybe_parent_attribute_value_id;
+        IF NOT FOUND THEN
+            RAISE 'Unable to find parent AttributeValue(%) in Tenancy(%), Visibility(%)',
+                  maybe_parent_attribute_value_id,
+                  this_tenancy,
+                  this_visibility;
+        END IF;
+
+        parent_attribute_context := this_attribute_context || jsonb_build_object('attribute_context_prop_id', parent_attribute_value.attribute_context_prop_id);
+
+        maybe_parent_attribute_value_id := attribute_value_vivify_value_and_parent_values_raw_v1(this_tenancy,
+                                                                                                 this_visibility,
+                                                                                                 parent_attribute_context,
+                                                                                                 parent_attribute_value.id,
+                                                                                                 this_create_child_proxies);
+    END IF;
+
+    -- If the AttributeValue we were given isn't for the _specific_ context that we're trying to update, make a
+    -- new one. This is necessary, since the one that we were given might be the "default" one that is directly
+    -- attached to a Prop, or the one from a SchemaVariant, and the AttributeContext might be requesting that
+    -- we set the value in a more specific context.
+    IF attribute_contexts_match_v1(this_attribute_context, given_attribute_value) THEN
+        attribute_value_id := given_attribute_value.id;
+    ELSE
+        -- Check if we created an appropriate AttributeValue in the process of vivifying the parent
+        -- `AttributeValue`s, and populating proxy `AttributeValue`s for their child `AttributeValue`s.
+        maybe_attribute_value := jsonb_populate_record(NULL::attribute_values,
+                                                       attribute_value_find_with_parent_and_key_for_context_v1(this_tenancy,
+                                                                                                               this_visibility,
+                                                                                                               maybe_parent_attribute_value_id,
+                                                                                                               given_attribute_value.key,
+                                                                                                               this_attribute_context));
+        IF maybe_attribute_value.id IS NOT NULL
+           AND attribute_contexts_match_v1(this_attribute_context, maybe_attribute_value)
+        THEN
+            attribute_value_id := maybe_attribute_value.id;
+        ELSE
+            -- We haven't found an appropriate AttributeValue to use, so we need to make one.
+            SELECT (av.object ->> 'id')::ident
+            INTO attribute_value_id
+            FROM attribute_value_create_v1(this_tenancy,
+                                           this_visibility,
+                                           this_attribute_context,
+                                           given_attribute_value.func_binding_id,
+                                           given_attribute_value.func_binding_return_value_id,
+                                           given_attribute_value.key) AS av;
+            IF NOT FOUND THEN
+                RAISE 'Unable to create AttributeValue: attribute_value_create_v1(%, %, %, %, %, %)',
+                      this_tenancy,
+                      this_visibility,
+                      this_attribute_context,
+                      given_attribute_value.func_binding_id,
+                      given_attribute_value.func_binding_return_value_id,
+                      given_attribute_value.key;
+            END IF;
+
+            IF maybe_parent_attribute_value_id IS NOT NULL THEN
+                PERFORM set_belongs_to_v1(
+                    'attribute_value_belongs_to_attribute_value',
+                    this_tenancy,
+                    this_visibility,
+                    attribute_value_id,
+                    maybe_parent_attribute_value_id
+                );
+            END IF;
+
+            IF this_create_child_proxies THEN
+                PERFORM attribute_value_populate_child_proxies_for_value_v1(this_tenancy,
+                                                                            this_visibility,
+                                                                            given_attribute_value.id,
+                                                                            this_attribute_context,
+                                                                            attribute_value_id);
+            END IF;
+        END IF;
+    END IF;
+
+    RAISE DEBUG 'attribute_value_update_for_context_raw_v1: this_attribute_context - %', this_attribute_context;
+    IF (this_attribute_context ->> 'attribute_context_prop_id')::ident = ident_nil_v1() THEN
+        typeof_value := jsonb_typeof(this_new_value);
+
+        -- jsonb_typeof returns: 'object', 'array', 'string', 'number', 'boolean', 'null' and SQL NULL
+        --
+        -- json_typeof('null'::json) → null
+        -- json_typeof(NULL::json) IS NULL → t
+        CASE
+            WHEN typeof_value = 'object' THEN
+                -- It's an array/map, but since we're setting the value for a Provider, then it's an Object.
+                func_name := 'si:setObject';
+            WHEN typeof_value = 'array' THEN
+                func_name := 'si:setArray';
+            WHEN typeof_value = 'string' THEN
+                func_name := 'si:setString';
+            WHEN typeof_value = 'number' THEN
+                -- jsonb_typeof doesn't differentiate between integer & float, so look at how the
+                -- number was written: jsonb keeps its scale, so a whole float like 2.0 still has
+                -- a fractional part in its text form and stays a float.
+                IF (this_new_value #>> '{}') ~ '^-?[0-9]+$' THEN
+                    func_name := 'si:setInteger';
+                ELSE
+                    func_name := 'si:setFloat';
+                END IF;
+            WHEN typeof_value = 'boolean' THEN
+                func_name := 'si:setBoolean';
+            WHEN typeof_value = 'null' THEN
+                -- This should probably be different from 'si:unset' so we can differentiate between
+                -- "this doesn't have a value/shouldn't exist" and "this should exist with the literal
+                -- value 'nothing'".
+                func_name := 'si:unset';
+                func_args := 'null'::jsonb;
+            WHEN typeof_value IS NULL THEN
+                func_name := 'si:unset';
+                func_args := 'null'::jsonb;
+            ELSE
+                RAISE 'attribute_value_update_for_context_raw_v1: Unknown jsonb_typeof(%) - %',
+                    this_value,
+                    typeof_value;
+        END CASE;
+    ELSE
+        SELECT *
+        INTO prop
+        FROM props_v1(this_tenancy, this_visibility)
+        WHERE id = (this_attribute_context ->> 'attribute_context_prop_id')::ident;
+        IF NOT FOUND THEN
+            RAISE 'Unable to find Prop(%) in Tenancy(%), Visibility(%)', (this_attribute_context ->> 'attribute_context_prop_id')::ident,
+                                                                         this_tenancy,
+                                                                         this_visibility;
+        END IF;
+
+        IF this_new_value IS NULL THEN
+            func_name := 'si:unset';
+            func_args := 'null'::jsonb;
+        ELSIF prop.kind = 'array' THEN
+            func_name := 'si:setArray';
+        ELSIF prop.kind = 'boolean' THEN
+            func_name := 'si:setBoolean';
+        ELSIF prop.kind = 'float' THEN
+            func_name := 'si:setFloat';
+        ELSIF prop.kind = 'integer' THEN
+            func_name := 'si:setInteger';
+        ELSIF prop.kind = 'map' THEN
+            func_name := 'si:setMap';
+        ELSIF prop.kind = 'object' THEN
+            func_name := 'si:setObject';
+        ELSIF prop.kind = 'string' THEN
+            func_name := 'si:setString';
+        ELSE
+            RAISE 'Unknown Prop(%).kind(%) in Tenancy(%), Visibility(%)', prop.id, prop.kind, this_tenancy, this_visibility;
+        END IF;
+    END IF;
+
+    IF func_args IS NULL THEN
+        func_args := jsonb_build_object('value', this_new_value);
+    END IF;
+
+    SELECT *
+    INTO func
+    FROM funcs_v1(this_tenancy, this_visibility)
+    WHERE name = func_name;
+    IF NOT FOUND THEN
+        RAISE 'Unable to find Func(%) in Tenancy(%), Visibility(%)', func_name,
+                                                                     this_tenancy,
+                                                                     this_visibility;
+    END IF;
+
+    SELECT new_func_binding_id, new_func_binding_return_value_id
+    INTO func_binding_id, func_binding_return_value_id
+    FROM func_binding_create_and_execute_v1(
+        this_tenancy,
+        this_visibility,
+        func_args,
+        func.id
+    );
+
+    PERFORM update_by_id_v1('attribute_values',
+                            'func_binding_id',
+                            this_tenancy,
+                            this_visibility,
+                            attribute_value_id,
+                            func_binding_id);
+
+    attribute_prototype_id := attribute_prototype_update_for_context_v1(this_tenancy,
+                                                                        this_visibility,
+                                                                        original_attribute_prototype.id,
+                                                                        this_attribute_context,
+                                                                        func.id,
+                                                                        func_binding_id,
+                                                                        func_binding_return_value_id,
+                                                                        maybe_parent_attribute_value_id,
+                                                                        attribute_value_id);
+    IF attribute_prototype_id IS NULL THEN
+        RAISE 'Unable create AttributePrototype: attribute_prototype_update_for_context_v1(%, %, %, %, %, %, %, %, %)',
+              this_tenancy,
+              this_visibility,
+              original_attribute_prototype.id,
+              this_attribute_context,
+              func.id,
+              func_binding_id,
+              func_binding_return_value_id,
+              maybe_parent_attribute_value_id,
+              attribute_value_id;
+    END IF;
+
+    PERFORM set_belongs_to_v1(
+        'attribute_value_belongs_to_attribute_prototype',
+        this_tenancy,
+        this_visibility,
+        attribute_value_id,
+        attribute_prototype_id
+    );
+
+    PERFORM update_by_id_v1('attribute_values',
+                            'func_binding_return_value_id',
+                            this_tenancy,
+                            this_visibility,
+                            attribute_value_id,
+                            func_binding_return_value_id);
+
+    -- If the value we just updated is a proxy, we need to seal it to prevent it from automatically updated
+    -- by the AttributeValue it is proxying, since we overrode that value.
+    IF av.proxy_for_attribute_value_id IS NOT NULL
+        FROM attribute_values_v1(this_tenancy, this_visibility) AS av
+        WHERE id = attribute_value_id
+    THEN
+        PERFORM update_by_id_v1('attribute_values',
+                                'sealed_proxy',
+                                this_tenancy,
+                                this_visibility,
+                                attribute_value_id,
+                                true);
+    END IF;
+
+    PERFORM attribute_value_update_parent_index_map_v1(this_tenancy,
+                                                       this_visibility,
+                                                       attribute_value_id);
+
+    -- Do we need to process the unprocessed value and populate nested values?  If the unprocessed value
+    -- doesn't equal the value then we have a populated "container" (i.e. object, map, array) that contains
+    -- values which need to be made into AttributeValues of their own.
+    SELECT *
+    INTO func_binding_return_value
+    FROM func_binding_return_values_v1(this_tenancy, this_visibility)
+    WHERE id = func_binding_return_value_id;
+    IF func_binding_return_value.unprocessed_value IS NOT NULL
+        AND func_binding_return_value.unprocessed_value != func_binding_return_value.value
+    THEN
+        PERFORM attribute_value_populate_nested_values_v1(this_tenancy,
+                                                          this_visibility,
+                                                          attribute_value_id,
+                                                          this_attribute_context,
+                                                          func_binding_return_value.unprocessed_value);
+    END IF;
+
+    new_attribute_value_id := attribute_value_id;
+END;
+$$ LANGUAGE PLPGSQL;
+
+CREATE OR REPLACE FUNCTION func_binding_execute_v1(
+    this_tenancy                     jsonb,
+    this_visibility                  jsonb,
+    this_func_binding_id             ident,
+    OUT func_binding_return_value_id ident
+)
+AS
+$$
+DECLARE
+    func                    funcs%ROWTYPE;
+    func_binding            func_bindings%ROWTYPE;
+    func_execution_pk       ident;
+    fbrv_id                 ident;
+    tenancy                 jsonb;
+    result_value            jsonb;
+    result_value_processed  jsonb;
+BEGIN
+    -- binding.prepare_execution
+    SELECT *
+    INTO STRICT func_binding
+    FROM func_bindings_v1(this_tenancy, this_visibility)
+    WHERE id = this_func_binding_id;
+    RAISE DEBUG 'func_binding_execute_v1: Found FuncBinding(%)', func_binding;
+
+    SELECT funcs.*
+    INTO STRICT func
+    FROM funcs_v1(this_tenancy, this_visibility) AS funcs
+    INNER JOIN func_binding_belongs_to_func_v1(this_tenancy, this_visibility)
+        AS func_binding_belongs_to_func
+        ON funcs.id = func_binding_belongs_to_func.belongs_to_id
+            AND func_binding_belongs_to_func.object_id = func_binding.id;
+    RAISE DEBUG 'func_binding_execute_v1: Found Func(%)', func;
+
+    SELECT (fe.object ->> 'pk')::ident
+    INTO STRICT func_execution_pk
+    FROM func_execution_create_v1(
+        this_tenancy,
+        'Start'::text,
+        func.id,
+        func_binding.id,
+        func_binding.args::jsonb,
+        func_binding.backend_kind,
+        func.backend_response_type,
+        func.handler,
+        func.code_base64
+    ) AS fe;
+    RAISE DEBUG 'func_binding_execute_v1: Found FuncExecution(%)', func_execution_pk;
+    PERFORM func_execution_set_state_v1(func_execution_pk, 'Run');
+
+    -- FuncDispatchContext::new(read_context)
+    --   Don't need. Copies the veritech handle and set up an mpsc::channel (for streaming output)
+
+    -- binding.critical_section
+    result_value := func_binding.args -> 'value';
+    CASE
+        WHEN func_binding.backend_kind = 'Array' THEN
+            result_value_processed := '[]'::json;
+        WHEN func_binding.backend_kind = 'Boolean' THEN
+            result_value_processed := result_value;
+        WHEN func_binding.backend_kind = 'Float' THEN
+            result_value_processed := result_value;
+        WHEN func_binding.backend_kind = 'Identity' THEN
+            result_value := func_binding.args -> 'identity';
+            result_value_processed := result_value;
+        WHEN func_binding.backend_kind = 'Integer' THEN
+            result_value_processed := result_value;
+        WHEN func_binding.backend_kind = 'Map' THEN
+            result_value_processed := '{}'::json;
+        WHEN func_binding.backend_kind = 'Object' THEN
+            result_value_processed := '{}'::json;
+        WHEN func_binding.backend_kind = 'String' THEN
+            result_value_processed := result_value;
+        WHEN func_binding.backend_kind = 'Unset' THEN
+            result_value := NULL;
+            result_value_processed := result_value;
+        ELSE
+            RAISE 'BackendKind(%) cannot be executed directly in PG', func_binding.backend_kind;
+    END CASE;
+
+    -- binding.postprocess_execution
+    fbrv_id := (func_binding_return_value_create_v1(
+        this_tenancy,
+        this_visibility,
+        result_value,
+        result_value_processed,
+        func.id,
+        func_binding.id,
+        func_execution_pk
+    ) ->> 'id')::ident;
+    RAISE DEBUG 'func_binding_execute_v1: Created FuncBindingReturnValue(%)', fbrv_id;
+    -- execution.process_return_value
+    PERFORM func_execution_set_return_value_v1(
+        func_execution_pk,
+        fbrv_id,
+        result_value_processed,
+        result_value
+    );
+    RAISE DEBUG 'func_binding_execute_v1: Set FBRV on execution';
+    PERFORM func_execution_set_state_v1(func_execution_pk, 'Success');
+
+    RAISE DEBUG 'func_binding_execute_v1: DONE';
+    func_binding_return_value_id := fbrv_id;
+END;
+$$ LANGUAGE PLPGSQL;
@@ -0,0 +1 @@
+ALTER TABLE funcs ADD COLUMN plan_handler text;
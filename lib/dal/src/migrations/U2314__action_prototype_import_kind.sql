@@ -0,0 +1,5 @@
+ALTER TABLE action_prototypes
+    DROP CONSTRAINT valid_kind_check;
+
+ALTER TABLE action_prototypes
+    ADD CONSTRAINT valid_kind_check CHECK (kind IN ('create', 'delete', 'import', 'other', 'refresh'));
@@ -0,0 +1,74 @@
+-- The state of the change set when a reviewer approved it. Approvals only count while the change
+-- set is still in that state, so that nothing written after an approval can be applied unreviewed.
+ALTER TABLE change_set_approvals
+    ADD COLUMN change_set_fingerprint text;
+
+-- A digest of every row the change set holds, across every standard model table. Any write to
+-- the change set (including deletions, which are either soft and bump updated_at, or hard and
+-- drop a row) changes it.
+CREATE OR REPLACE FUNCTION change_set_fingerprint_v1(this_change_set_pk ident,
+                                                     this_tenancy jsonb,
+                                                     OUT fingerprint text) AS
+$$
+DECLARE
+    standard_model  standard_models%ROWTYPE;
+    table_digest    text;
+    table_digests   text[] := ARRAY[]::text[];
+BEGIN
+    FOR standard_model IN SELECT * FROM standard_models ORDER BY table_name
+        LOOP
+            EXECUTE format('SELECT md5(string_agg(pk::text || '':'' || created_at::text || '':'' || updated_at::text, '','' ' ||
+                           '                      ORDER BY pk)) ' ||
+                           'FROM %1$I ' ||
+                           'WHERE visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, tenancy_workspace_pk)',
+                           standard_model.table_name, this_change_set_pk, this_tenancy)
+                INTO table_digest;
+            IF table_digest IS NOT NULL THEN
+                table_digests := table_digests || (standard_model.table_name || '=' || table_digest);
+            END IF;
+        END LOOP;
+
+    fingerprint := md5(array_to_string(table_digests, ','));
+END;
+$$ LANGUAGE PLPGSQL STABLE;
+
+-- Records a reviewer's decision, replacing any earlier decision of theirs, along with the state
+-- of the change set it was made against. A rejection sends the change set back to being open.
+CREATE OR REPLACE FUNCTION change_set_decide_approval_v1(this_change_set_pk ident,
+                                                         this_user_pk ident,
+                                                         this_decision text,
+                                                         this_comment text,
+                                                         this_tenancy jsonb,
+                                                         OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        change_set_approvals%ROWTYPE;
+    this_fingerprint    text;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+    SELECT fingerprint FROM change_set_fingerprint_v1(this_change_set_pk, this_tenancy) INTO this_fingerprint;
+
+    INSERT INTO change_set_approvals (change_set_pk, user_pk, decision, comment, tenancy_workspace_pk,
+                                      change_set_fingerprint)
+    VALUES (this_change_set_pk, this_user_pk, this_decision, this_comment,
+            this_tenancy_record.tenancy_workspace_pk, this_fingerprint)
+    ON CONFLICT (change_set_pk, user_pk) DO UPDATE
+        SET decision               = EXCLUDED.decision,
+            comment                = EXCLUDED.comment,
+            change_set_fingerprint = EXCLUDED.change_set_fingerprint,
+            updated_at             = clock_timestamp()
+    RETURNING * INTO this_new_row;
+
+    IF this_decision = 'Rejected' THEN
+        UPDATE change_sets
+        SET status     = 'Open',
+            updated_at = clock_timestamp()
+        WHERE pk = this_change_set_pk
+          AND in_tenancy_v1(this_tenancy, tenancy_workspace_pk);
+    END IF;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,36 @@
+-- Deliveries which haven't succeeded or run out of attempts yet, so that retries survive a
+-- restart of the dispatcher. A row is deleted once its delivery is done with.
+CREATE TABLE webhook_pending_deliveries
+(
+    pk                          ident primary key default ident_create_v1(),
+    webhook_pk                  ident                    NOT NULL REFERENCES webhooks (pk) ON DELETE CASCADE,
+    payload                     jsonb                    NOT NULL,
+    -- How many attempts have been made so far.
+    attempt                     integer                  NOT NULL DEFAULT 0,
+    -- Pushed into the future while a dispatcher is attempting the delivery, so that only one
+    -- dispatcher attempts it at a time.
+    next_attempt_at             timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    tenancy_workspace_pk        ident,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    updated_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+CREATE INDEX webhook_pending_deliveries_next_attempt_at_idx ON webhook_pending_deliveries (next_attempt_at);
+
+CREATE OR REPLACE FUNCTION webhook_pending_delivery_create_v1(this_webhook_pk ident,
+                                                              this_payload jsonb,
+                                                              this_tenancy jsonb,
+                                                              OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        webhook_pending_deliveries%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    INSERT INTO webhook_pending_deliveries (webhook_pk, payload, tenancy_workspace_pk)
+    VALUES (this_webhook_pk, this_payload, this_tenancy_record.tenancy_workspace_pk)
+    RETURNING * INTO this_new_row;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
@@ -0,0 +1,77 @@
+-- The sinks history events are exported to. Events are only queued for registered sinks, so that
+-- nothing piles up when exporting isn't configured.
+CREATE TABLE audit_export_sinks
+(
+    sink                  text PRIMARY KEY,
+    created_at            timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP()
+);
+
+-- The history events each sink has yet to be sent. Rows are queued in the same transaction as
+-- their event, so the exporter sees an event as soon as it is committed, however long that takes,
+-- and deletes its row once the event is sent.
+CREATE TABLE audit_export_outbox
+(
+    sink                  text                     NOT NULL REFERENCES audit_export_sinks (sink) ON DELETE CASCADE,
+    history_event_pk      ident                    NOT NULL,
+    -- The event's, so that events are sent oldest first.
+    created_at            timestamp with time zone NOT NULL,
+    PRIMARY KEY (sink, history_event_pk)
+);
+CREATE INDEX audit_export_outbox_sink_created_at_idx
+    ON audit_export_outbox (sink, created_at, history_event_pk);
+
+-- Carry on from where the old cursors got to.
+INSERT INTO audit_export_sinks (sink)
+SELECT sink
+FROM audit_export_cursors;
+INSERT INTO audit_export_outbox (sink, history_event_pk, created_at)
+SELECT audit_export_cursors.sink, history_events.pk, history_events.created_at
+FROM audit_export_cursors
+         INNER JOIN history_events
+                    ON (history_events.created_at, history_events.pk) >
+                       (audit_export_cursors.last_created_at, audit_export_cursors.last_pk);
+DROP TABLE audit_export_cursors;
+
+CREATE OR REPLACE FUNCTION history_event_create_v1(this_label text,
+                                                   this_actor jsonb,
+                                                   this_message text,
+                                                   this_data jsonb,
+                                                   this_tenancy jsonb,
+                                                   OUT object json) AS
+$$
+DECLARE
+    this_tenancy_record tenancy_record_v1;
+    this_new_row        history_events%ROWTYPE;
+BEGIN
+    SELECT * FROM tenancy_json_to_columns_v1(this_tenancy) INTO this_tenancy_record;
+
+    INSERT INTO history_events (label, actor, message, data, tenancy_workspace_pk)
+    VALUES (this_label, this_actor, this_message, this_data, this_tenancy_record.tenancy_workspace_pk)
+    RETURNING * INTO this_new_row;
+
+    INSERT INTO audit_export_outbox (sink, history_event_pk, created_at)
+    SELECT audit_export_sinks.sink, this_new_row.pk, this_new_row.created_at
+    FROM audit_export_sinks;
+
+    object := row_to_json(this_new_row);
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
+
+-- Registers a sink, queueing every existing event for it. Creating history events is blocked while
+-- the first registration happens, so that no event is left out of both the backfill and the queue.
+CREATE OR REPLACE FUNCTION audit_export_sink_register_v1(this_sink text) RETURNS void AS
+$$
+BEGIN
+    IF EXISTS(SELECT 1 FROM audit_export_sinks WHERE sink = this_sink) THEN
+        RETURN;
+    END IF;
+
+    LOCK TABLE history_events IN SHARE MODE;
+    INSERT INTO audit_export_sinks (sink) VALUES (this_sink) ON CONFLICT (sink) DO NOTHING;
+    IF FOUND THEN
+        INSERT INTO audit_export_outbox (sink, history_event_pk, created_at)
+        SELECT this_sink, history_events.pk, history_events.created_at
+        FROM history_events;
+    END IF;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
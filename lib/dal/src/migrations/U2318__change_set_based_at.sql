@@ -0,0 +1,59 @@
+-- The point in head's history a change set is based on. Unlike updated_at, which anything that
+-- touches the change set row bumps (sending it for review, approving it, ...), this only moves
+-- when the change set is created or rebased. Existing change sets are based on head as of when they
+-- were opened.
+ALTER TABLE change_sets
+    ADD COLUMN based_at timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP();
+UPDATE change_sets
+SET based_at = created_at;
+
+-- Same as before, but moves based_at rather than updated_at, and only for a change set in the
+-- given tenancy.
+DROP FUNCTION change_set_rebase_v1(ident, jsonb);
+CREATE OR REPLACE FUNCTION change_set_rebase_v1(this_change_set_pk ident,
+                                                this_tenancy jsonb,
+                                                OUT timestamp_based_at timestamp with time zone) AS
+$$
+DECLARE
+    standard_model  standard_models%ROWTYPE;
+    this_table_name regclass;
+BEGIN
+    UPDATE change_sets
+    SET based_at = clock_timestamp()
+    WHERE pk = this_change_set_pk
+      AND in_tenancy_v1(this_tenancy, tenancy_workspace_pk)
+    RETURNING based_at INTO timestamp_based_at;
+
+    IF NOT FOUND THEN
+        RAISE EXCEPTION 'change set % not found in tenancy %', this_change_set_pk, this_tenancy;
+    END IF;
+
+    FOR standard_model IN SELECT * FROM standard_models
+        LOOP
+            this_table_name := standard_model.table_name::regclass;
+
+            EXECUTE format('DELETE FROM %1$I AS change_set_rows ' ||
+                           'USING %1$I AS head_rows ' ||
+                           'WHERE change_set_rows.visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, change_set_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.id = change_set_rows.id ' ||
+                           '  AND head_rows.visibility_change_set_pk = ident_nil_v1() ' ||
+                           '  AND in_tenancy_v1(%3$L, head_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.updated_at > change_set_rows.created_at ' ||
+                           '  AND (to_jsonb(change_set_rows) - ARRAY[''pk'', ''visibility_change_set_pk'', ''created_at'', ''updated_at'']) ' ||
+                           '    = (to_jsonb(head_rows) - ARRAY[''pk'', ''visibility_change_set_pk'', ''created_at'', ''updated_at''])',
+                           this_table_name, this_change_set_pk, this_tenancy);
+
+            EXECUTE format('UPDATE %1$I AS change_set_rows ' ||
+                           'SET created_at = clock_timestamp() ' ||
+                           'FROM %1$I AS head_rows ' ||
+                           'WHERE change_set_rows.visibility_change_set_pk = %2$L ' ||
+                           '  AND in_tenancy_v1(%3$L, change_set_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.id = change_set_rows.id ' ||
+                           '  AND head_rows.visibility_change_set_pk = ident_nil_v1() ' ||
+                           '  AND in_tenancy_v1(%3$L, head_rows.tenancy_workspace_pk) ' ||
+                           '  AND head_rows.updated_at > change_set_rows.created_at',
+                           this_table_name, this_change_set_pk, this_tenancy);
+        END LOOP;
+END;
+$$ LANGUAGE PLPGSQL VOLATILE;
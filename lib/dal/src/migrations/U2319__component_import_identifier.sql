@@ -0,0 +1,4 @@
+-- The identifier of the existing resource a component was imported from, if any, so that the same
+-- resource isn't imported twice.
+ALTER TABLE components
+    ADD COLUMN import_identifier text;
@@ -0,0 +1,8 @@
+-- Unregisters a sink, dropping the events still queued for it, so that a sink which is no longer
+-- exported to doesn't keep collecting every new event.
+CREATE OR REPLACE FUNCTION audit_export_sink_unregister_v1(this_sink text) RETURNS void AS
+$$
+DELETE
+FROM audit_export_sinks
+WHERE sink = this_sink;
+$$ LANGUAGE SQL VOLATILE;
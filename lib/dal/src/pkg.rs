@@ -6,10 +6,12 @@ mod import;
 
 pub use export::export_pkg_as_bytes;
 pub use export::get_component_type;
-pub use import::{import_pkg, import_pkg_from_pkg, ImportOptions};
+pub(crate) use import::set_component_domain;
+pub use import::{import_pkg, import_pkg_from_pkg, instantiate_template, ImportOptions};
 
 use si_pkg::{FuncSpecBackendKind, FuncSpecBackendResponseType, SiPkgError, SpecError};
 
+use crate::component::view::ComponentViewError;
 use crate::schema::variant::definition::SchemaVariantDefinitionId;
 use crate::{
     func::{
@@ -22,10 +24,11 @@ use crate::{
     socket::SocketError,
     ActionPrototypeError, AttributeContextBuilderError, AttributePrototypeArgumentError,
     AttributePrototypeArgumentId, AttributePrototypeError, AttributePrototypeId,
-    AttributeReadContext, AttributeValueError, ExternalProviderError, ExternalProviderId,
-    FuncBackendKind, FuncBackendResponseType, FuncError, FuncId, InternalProviderError,
-    InternalProviderId, PropError, PropId, PropKind, SchemaError, SchemaId, SchemaVariantError,
-    SchemaVariantId, StandardModelError, ValidationPrototypeError,
+    AttributeReadContext, AttributeValueError, ComponentError, ComponentId, DiagramError,
+    EdgeError, ExternalProviderError, ExternalProviderId, FuncBackendKind, FuncBackendResponseType,
+    FuncError, FuncId, InternalProviderError, InternalProviderId, NodeError, PropError, PropId,
+    PropKind, SchemaError, SchemaId, SchemaVariantError, SchemaVariantId, SecretDefinitionId,
+    SecretError, SocketId, StandardModelError, ValidationPrototypeError,
 };
 
 #[remain::sorted]
@@ -57,8 +60,26 @@ pub enum PkgError {
     ),
     #[error(transparent)]
     AttributeValue(#[from] AttributeValueError),
+    #[error("Components can only be imported into a change set")]
+    ChangeSetRequiredForComponents,
+    #[error(transparent)]
+    Component(#[from] ComponentError),
+    #[error("Component {0} has no node")]
+    ComponentMissingNode(ComponentId),
+    #[error("Component {0} has no schema")]
+    ComponentMissingSchema(ComponentId),
+    #[error("Component {0} not found")]
+    ComponentNotFound(ComponentId),
+    #[error(transparent)]
+    ComponentView(#[from] ComponentViewError),
     #[error("map item prop {0} has both custom key prototypes and custom prop only prototype")]
     ConflictingMapKeyPrototypes(PropId),
+    #[error(transparent)]
+    Diagram(#[from] DiagramError),
+    #[error("More than one component is named {0}, but names must be unique within a package")]
+    DuplicateComponentName(String),
+    #[error(transparent)]
+    Edge(#[from] EdgeError),
     #[error("Cannot find Socket for explicit InternalProvider {0}")]
     ExplicitInternalProviderMissingSocket(InternalProviderId),
     #[error(transparent)]
@@ -81,20 +102,30 @@ pub enum PkgError {
     InstalledSchemaVariantDefinitionMissing(SchemaVariantDefinitionId),
     #[error("Installed schema variant {0} does not exist")]
     InstalledSchemaVariantMissing(SchemaVariantId),
+    #[error("Installed secret definition {0} does not exist")]
+    InstalledSecretDefinitionMissing(SecretDefinitionId),
     #[error(transparent)]
     InternalProvider(#[from] InternalProviderError),
     #[error("Missing Prop {1} for InternalProvider {1}")]
     InternalProviderMissingProp(InternalProviderId, PropId),
     #[error("Leaf Function {0} has invalid argument {1}")]
     InvalidLeafArgument(FuncId, String),
+    #[error("Validation bound {0} is not a finite float")]
+    InvalidValidationFloatBound(String),
     #[error("Missing AttributePrototype {0} for explicit InternalProvider {1}")]
     MissingAttributePrototypeForInputSocket(AttributePrototypeId, InternalProviderId),
     #[error("Missing AttributePrototype {0} for ExternalProvider {1}")]
     MissingAttributePrototypeForOutputSocket(AttributePrototypeId, ExternalProviderId),
     #[error("Missing Func {1} for AttributePrototype {0}")]
     MissingAttributePrototypeFunc(AttributePrototypeId, FuncId),
+    #[error("Package refers to a component named {0} but none could be found")]
+    MissingComponent(String),
+    #[error("Cannot find Socket {1} for component {0}")]
+    MissingComponentSocket(String, String),
     #[error("Func {0} missing from exported funcs")]
     MissingExportedFunc(FuncId),
+    #[error("Cannot find ExternalProvider for Socket {0}")]
+    MissingExternalProviderForSocket(SocketId),
     #[error("Cannot find FuncArgument {0} for Func {1}")]
     MissingFuncArgument(String, FuncId),
     #[error("Package asked for a function with the unique id {0} but none could be found")]
@@ -107,12 +138,18 @@ pub enum PkgError {
     MissingIntrinsicFunc(String),
     #[error("Intrinsic function (0) argument {1} not found")]
     MissingIntrinsicFuncArgument(String, String),
+    #[error("Cannot find item prop for installed array prop {0}")]
+    MissingItemPropForArrayProp(PropId),
     #[error("Cannot find item prop for installed map prop {0}")]
     MissingItemPropForMapProp(PropId),
     #[error("Cannot find installed prop {0}")]
     MissingProp(PropId),
     #[error("Cannot find schema_variant_definition {0}")]
     MissingSchemaVariantDefinition(SchemaVariantId),
+    #[error("Cannot find Socket {0}")]
+    MissingSocket(SocketId),
+    #[error(transparent)]
+    Node(#[from] NodeError),
     #[error("Package with that hash already installed: {0}")]
     PackageAlreadyInstalled(String),
     #[error(transparent)]
@@ -135,6 +172,8 @@ pub enum PkgError {
     SchemaVariantDefinition(#[from] SchemaVariantDefinitionError),
     #[error("schema variant not found: {0}")]
     SchemaVariantNotFound(SchemaVariantId),
+    #[error(transparent)]
+    Secret(#[from] SecretError),
     #[error("json serialization error: {0}")]
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
@@ -170,6 +209,7 @@ impl From<FuncBackendKind> for FuncSpecBackendKind {
             FuncBackendKind::Boolean => Self::Boolean,
             FuncBackendKind::Diff => Self::Diff,
             FuncBackendKind::Identity => Self::Identity,
+            FuncBackendKind::Float => Self::Float,
             FuncBackendKind::Integer => Self::Integer,
             FuncBackendKind::JsAction => Self::JsAction,
             FuncBackendKind::JsAttribute => Self::JsAttribute,
@@ -192,6 +232,7 @@ impl From<FuncSpecBackendKind> for FuncBackendKind {
             FuncSpecBackendKind::Boolean => Self::Boolean,
             FuncSpecBackendKind::Diff => Self::Diff,
             FuncSpecBackendKind::Identity => Self::Identity,
+            FuncSpecBackendKind::Float => Self::Float,
             FuncSpecBackendKind::Integer => Self::Integer,
             FuncSpecBackendKind::JsAction => Self::JsAction,
             FuncSpecBackendKind::JsAttribute => Self::JsAttribute,
@@ -216,6 +257,7 @@ impl From<FuncBackendResponseType> for FuncSpecBackendResponseType {
             FuncBackendResponseType::CodeGeneration => Self::CodeGeneration,
             FuncBackendResponseType::Confirmation => Self::Confirmation,
             FuncBackendResponseType::Identity => Self::Identity,
+            FuncBackendResponseType::Float => Self::Float,
             FuncBackendResponseType::Integer => Self::Integer,
             FuncBackendResponseType::Json => Self::Json,
             FuncBackendResponseType::Map => Self::Map,
@@ -239,6 +281,7 @@ impl From<FuncSpecBackendResponseType> for FuncBackendResponseType {
             FuncSpecBackendResponseType::CodeGeneration => Self::CodeGeneration,
             FuncSpecBackendResponseType::Confirmation => Self::Confirmation,
             FuncSpecBackendResponseType::Identity => Self::Identity,
+            FuncSpecBackendResponseType::Float => Self::Float,
             FuncSpecBackendResponseType::Integer => Self::Integer,
             FuncSpecBackendResponseType::Json => Self::Json,
             FuncSpecBackendResponseType::Map => Self::Map,
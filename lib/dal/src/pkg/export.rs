@@ -3,26 +3,27 @@ use strum::IntoEnumIterator;
 use telemetry::prelude::*;
 
 use si_pkg::{
-    ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, FuncArgumentSpec,
-    FuncDescriptionSpec, FuncSpec, FuncUniqueId, LeafFunctionSpec, MapKeyFuncSpec, PkgSpec,
-    PropSpec, PropSpecBuilder, PropSpecKind, SchemaSpec, SchemaVariantSpec,
-    SchemaVariantSpecBuilder, SchemaVariantSpecComponentType, SchemaVariantSpecPropRoot, SiPkg,
-    SiPropFuncSpec, SiPropFuncSpecKind, SocketSpec, SocketSpecKind, SpecError, ValidationSpec,
-    ValidationSpecKind,
+    ActionFuncSpec, AttrFuncInputSpec, AttrFuncInputSpecKind, ComponentSpec, ConnectionSpec,
+    FuncArgumentSpec, FuncDescriptionSpec, FuncSpec, FuncUniqueId, LeafFunctionSpec,
+    MapKeyFuncSpec, PkgSpec, PropSpec, PropSpecBuilder, PropSpecKind, SchemaSpec,
+    SchemaVariantSpec, SchemaVariantSpecBuilder, SchemaVariantSpecComponentType,
+    SchemaVariantSpecPropRoot, SiPkg, SiPropFuncSpec, SiPropFuncSpecKind, SocketSpec,
+    SocketSpecKind, SpecError, ValidationSpec, ValidationSpecKind,
 };
 
 use crate::schema::variant::definition::SchemaVariantDefinition;
 use crate::{
+    edge::EdgeKind,
     func::{argument::FuncArgument, backend::validation::FuncBackendValidationArgs},
     prop_tree::{PropTree, PropTreeNode},
     socket::SocketKind,
     validation::Validation,
     ActionPrototype, ActionPrototypeContext, AttributeContextBuilder, AttributePrototype,
-    AttributePrototypeArgument, AttributeReadContext, AttributeValue, ComponentType, DalContext,
-    ExternalProvider, ExternalProviderId, Func, FuncDescription, FuncId, InternalProvider,
-    InternalProviderId, LeafInputLocation, LeafKind, Prop, PropId, PropKind, Schema, SchemaVariant,
-    SchemaVariantError, SchemaVariantId, Socket, StandardModel, StandardModelError,
-    ValidationPrototype,
+    AttributePrototypeArgument, AttributeReadContext, AttributeValue, Component, ComponentId,
+    ComponentType, ComponentView, DalContext, Edge, ExternalProvider, ExternalProviderId, Func,
+    FuncDescription, FuncId, InternalProvider, InternalProviderId, LeafInputLocation, LeafKind,
+    Prop, PropId, PropKind, Schema, SchemaVariant, SchemaVariantError, SchemaVariantId, Socket,
+    StandardModel, StandardModelError, ValidationPrototype,
 };
 
 use super::{PkgError, PkgResult};
@@ -36,9 +37,19 @@ pub async fn export_pkg_as_bytes(
     description: Option<impl Into<String>>,
     created_by: impl Into<String>,
     variant_ids: Vec<SchemaVariantId>,
+    component_ids: Vec<ComponentId>,
 ) -> PkgResult<Vec<u8>> {
     info!("Building module package");
-    let pkg = build_pkg(ctx, name, version, description, created_by, variant_ids).await?;
+    let pkg = build_pkg(
+        ctx,
+        name,
+        version,
+        description,
+        created_by,
+        variant_ids,
+        component_ids,
+    )
+    .await?;
     info!("Exporting as bytes");
 
     Ok(pkg.write_to_bytes()?)
@@ -51,6 +62,7 @@ async fn build_pkg(
     description: Option<impl Into<String>>,
     created_by: impl Into<String>,
     variant_ids: Vec<SchemaVariantId>,
+    component_ids: Vec<ComponentId>,
 ) -> PkgResult<SiPkg> {
     let mut pkg_spec_builder = PkgSpec::builder();
     pkg_spec_builder
@@ -89,6 +101,10 @@ async fn build_pkg(
         pkg_spec_builder.schema(schema_spec);
     }
 
+    for component_spec in build_component_specs(ctx, &component_ids).await? {
+        pkg_spec_builder.component(component_spec);
+    }
+
     let spec = pkg_spec_builder.build()?;
 
     let pkg = SiPkg::load_from_spec(spec)?;
@@ -96,6 +112,101 @@ async fn build_pkg(
     Ok(pkg)
 }
 
+/// Builds a [`ComponentSpec`] for each of the components, so that they can be recreated together
+/// as a template. Only the connections between these components are kept.
+async fn build_component_specs(
+    ctx: &DalContext,
+    component_ids: &[ComponentId],
+) -> PkgResult<Vec<ComponentSpec>> {
+    let mut components = Vec::with_capacity(component_ids.len());
+    let mut names: HashMap<ComponentId, String> = HashMap::new();
+    for component_id in component_ids {
+        let component = Component::get_by_id(ctx, component_id)
+            .await?
+            .ok_or(PkgError::ComponentNotFound(*component_id))?;
+        // Components refer to each other by name within the package
+        let name = component.name(ctx).await?;
+        if names.values().any(|existing| existing == &name) {
+            return Err(PkgError::DuplicateComponentName(name));
+        }
+        names.insert(*component_id, name);
+        components.push(component);
+    }
+
+    let mut specs = Vec::with_capacity(components.len());
+    for component in components {
+        let schema = component
+            .schema(ctx)
+            .await?
+            .ok_or(PkgError::ComponentMissingSchema(*component.id()))?;
+        let node = component
+            .node(ctx)
+            .await?
+            .pop()
+            .ok_or(PkgError::ComponentMissingNode(*component.id()))?;
+        let component_type: SchemaVariantSpecComponentType = component.get_type(ctx).await?.into();
+        let domain = ComponentView::new(ctx, *component.id())
+            .await?
+            .properties
+            .get("domain")
+            .cloned()
+            .unwrap_or_default();
+
+        let mut builder = ComponentSpec::builder();
+        builder
+            .name(&names[component.id()])
+            .schema_name(schema.name())
+            .component_type(component_type)
+            .x(node.x())
+            .y(node.y())
+            .width(node.width().map(ToOwned::to_owned))
+            .height(node.height().map(ToOwned::to_owned))
+            .domain(domain);
+
+        for edge in Edge::list_for_component(ctx, *component.id()).await? {
+            let head_component_id: ComponentId = edge.head_object_id().into();
+            let tail_component_id: ComponentId = edge.tail_object_id().into();
+            match edge.kind() {
+                EdgeKind::Symbolic if tail_component_id == *component.id() => {
+                    if let Some(parent_name) = names.get(&head_component_id) {
+                        builder.parent(parent_name.to_owned());
+                    }
+                }
+                // Aggregation frames connect their children with edges between the frame's own
+                // socket, which are recreated along with the frame
+                EdgeKind::Configuration
+                    if head_component_id == *component.id()
+                        && edge.head_socket_id() != edge.tail_socket_id() =>
+                {
+                    let from_component_name = match names.get(&tail_component_id) {
+                        Some(name) => name,
+                        None => continue,
+                    };
+                    let from_socket = Socket::get_by_id(ctx, &edge.tail_socket_id())
+                        .await?
+                        .ok_or(PkgError::MissingSocket(edge.tail_socket_id()))?;
+                    let to_socket = Socket::get_by_id(ctx, &edge.head_socket_id())
+                        .await?
+                        .ok_or(PkgError::MissingSocket(edge.head_socket_id()))?;
+
+                    builder.connection(
+                        ConnectionSpec::builder()
+                            .from_component_name(from_component_name)
+                            .from_socket_name(from_socket.name())
+                            .to_socket_name(to_socket.name())
+                            .build()?,
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        specs.push(builder.build()?);
+    }
+
+    Ok(specs)
+}
+
 fn build_func_spec(func: &Func, args: &[FuncArgument]) -> PkgResult<FuncSpec> {
     let mut func_spec_builder = FuncSpec::builder();
 
@@ -114,6 +225,9 @@ fn build_func_spec(func: &Func, args: &[FuncArgument]) -> PkgResult<FuncSpec> {
     }
     // Should we package an empty func?
     func_spec_builder.handler(func.handler().unwrap_or(""));
+    if let Some(plan_handler) = func.plan_handler() {
+        func_spec_builder.plan_handler(plan_handler);
+    }
     func_spec_builder.code_base64(func.code_base64().unwrap_or(""));
 
     func_spec_builder.response_type(*func.backend_response_type());
@@ -679,6 +793,7 @@ async fn set_variant_spec_prop_data(
             .kind(match tree_node.kind {
                 PropKind::Array => PropSpecKind::Array,
                 PropKind::Boolean => PropSpecKind::Boolean,
+                PropKind::Float => PropSpecKind::Float,
                 PropKind::Integer => PropSpecKind::Number,
                 PropKind::Object => PropSpecKind::Object,
                 PropKind::String => PropSpecKind::String,
@@ -741,7 +856,10 @@ async fn set_variant_spec_prop_data(
                         entry.builder.type_prop(type_prop);
                         maybe_type_prop_id = Some(type_prop_id);
                     }
-                    PropSpecKind::String | PropSpecKind::Number | PropSpecKind::Boolean => {
+                    PropSpecKind::String
+                    | PropSpecKind::Number
+                    | PropSpecKind::Float
+                    | PropSpecKind::Boolean => {
                         return Err(PkgError::prop_spec_children_invalid(format!(
                             "primitve prop type should have no children for prop id {}",
                             entry.prop_id,
@@ -803,7 +921,10 @@ async fn set_variant_spec_prop_data(
         // key or index
         if matches!(
             entry.builder.get_kind(),
-            Some(PropSpecKind::String) | Some(PropSpecKind::Number) | Some(PropSpecKind::Boolean)
+            Some(PropSpecKind::String)
+                | Some(PropSpecKind::Number)
+                | Some(PropSpecKind::Float)
+                | Some(PropSpecKind::Boolean)
         ) && !entry.inside_map_or_array
         {
             if let Some(av) = AttributeValue::find_for_context(ctx, context.into()).await? {
@@ -853,6 +974,38 @@ async fn get_validations_for_prop(
 
         match args {
             Some(validation) => match validation.validation {
+                Validation::ArrayItemsAreUnique { .. } => {
+                    spec_builder.kind(ValidationSpecKind::ArrayItemsAreUnique);
+                }
+                Validation::ArrayLengthIsBetween {
+                    lower_bound,
+                    upper_bound,
+                    ..
+                } => {
+                    spec_builder.kind(ValidationSpecKind::ArrayLengthIsBetween);
+                    spec_builder.upper_bound(upper_bound);
+                    spec_builder.lower_bound(lower_bound);
+                }
+                Validation::FloatIsBetweenTwoFloats {
+                    lower_bound,
+                    upper_bound,
+                    ..
+                } => {
+                    spec_builder.kind(ValidationSpecKind::FloatIsBetweenTwoFloats);
+                    spec_builder.float_upper_bound(
+                        serde_json::Number::from_f64(upper_bound).ok_or(
+                            PkgError::InvalidValidationFloatBound(upper_bound.to_string()),
+                        )?,
+                    );
+                    spec_builder.float_lower_bound(
+                        serde_json::Number::from_f64(lower_bound).ok_or(
+                            PkgError::InvalidValidationFloatBound(lower_bound.to_string()),
+                        )?,
+                    );
+                }
+                Validation::FloatIsNotEmpty { .. } => {
+                    spec_builder.kind(ValidationSpecKind::FloatIsNotEmpty);
+                }
                 Validation::IntegerIsBetweenTwoIntegers {
                     lower_bound,
                     upper_bound,
@@ -891,6 +1044,31 @@ async fn get_validations_for_prop(
                 Validation::StringIsHexColor { .. } => {
                     spec_builder.kind(ValidationSpecKind::StringIsHexColor);
                 }
+                Validation::StringIsArn { .. } => {
+                    spec_builder.kind(ValidationSpecKind::StringIsArn);
+                }
+                Validation::StringIsHostname { .. } => {
+                    spec_builder.kind(ValidationSpecKind::StringIsHostname);
+                }
+                Validation::StringIsValidCidr { .. } => {
+                    spec_builder.kind(ValidationSpecKind::StringIsValidCidr);
+                }
+                Validation::StringIsValidUrl { .. } => {
+                    spec_builder.kind(ValidationSpecKind::StringIsValidUrl);
+                }
+                Validation::StringLengthIsBetween {
+                    lower_bound,
+                    upper_bound,
+                    ..
+                } => {
+                    spec_builder.kind(ValidationSpecKind::StringLengthIsBetween);
+                    spec_builder.upper_bound(upper_bound);
+                    spec_builder.lower_bound(lower_bound);
+                }
+                Validation::StringMatchesRegex { pattern, .. } => {
+                    spec_builder.kind(ValidationSpecKind::StringMatchesRegex);
+                    spec_builder.expected_string(pattern);
+                }
             },
             None => {
                 let func_spec = func_specs
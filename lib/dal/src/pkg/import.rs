@@ -1,20 +1,26 @@
+use async_recursion::async_recursion;
+use std::collections::HashMap;
 use std::path::Path;
 use telemetry::prelude::*;
 use tokio::sync::Mutex;
 
 use si_pkg::{
     FuncUniqueId, SchemaVariantSpecPropRoot, SiPkg, SiPkgActionFunc, SiPkgAttrFuncInputView,
-    SiPkgError, SiPkgFunc, SiPkgFuncDescription, SiPkgLeafFunction, SiPkgProp, SiPkgSchema,
-    SiPkgSchemaVariant, SiPkgSocket, SiPkgValidation, SocketSpecKind,
+    SiPkgComponent, SiPkgError, SiPkgFunc, SiPkgFuncDescription, SiPkgLeafFunction, SiPkgProp,
+    SiPkgSchema, SiPkgSchemaVariant, SiPkgSecretDefinition, SiPkgSocket, SiPkgValidation,
+    SocketSpecKind,
 };
 
 use crate::{
     component::ComponentKind,
+    edge::EdgeKind,
     func::{binding::FuncBinding, binding_return_value::FuncBindingReturnValue},
     installed_pkg::{
         InstalledPkg, InstalledPkgAsset, InstalledPkgAssetKind, InstalledPkgAssetTyped,
         InstalledPkgId,
     },
+    job::definition::DependentValuesUpdate,
+    prop::PropPath,
     schema::{
         variant::{
             definition::{SchemaVariantDefinition, SchemaVariantDefinitionJson},
@@ -22,12 +28,14 @@ use crate::{
         },
         SchemaUiMenu,
     },
+    socket::SocketEdgeKind,
     validation::{create_validation, Validation, ValidationKind},
     ActionPrototype, ActionPrototypeContext, AttributeContextBuilder, AttributePrototypeArgument,
-    AttributeReadContext, AttributeValue, AttributeValueError, DalContext, ExternalProvider,
-    ExternalProviderId, Func, FuncArgument, FuncDescription, FuncDescriptionContents, FuncError,
-    FuncId, InternalProvider, Prop, PropId, PropKind, Schema, SchemaId, SchemaVariant,
-    SchemaVariantError, SchemaVariantId, StandardModel,
+    AttributeReadContext, AttributeValue, AttributeValueError, AttributeValueId, Component,
+    ComponentId, Connection, DalContext, Edge, ExternalProvider, ExternalProviderId, Func,
+    FuncArgument, FuncBackendKind, FuncDescription, FuncDescriptionContents, FuncError, FuncId,
+    InternalProvider, Node, Prop, PropId, PropKind, Schema, SchemaId, SchemaVariant,
+    SchemaVariantError, SchemaVariantId, SecretDefinition, Socket, StandardModel,
 };
 
 use super::{PkgError, PkgResult};
@@ -106,6 +114,16 @@ pub async fn import_pkg_from_pkg(
         funcs_by_unique_id.insert(unique_id, func);
     }
 
+    for secret_definition_spec in pkg.secret_definitions()? {
+        info!(
+            "installing secret definition '{}' from {}",
+            secret_definition_spec.name(),
+            file_name
+        );
+
+        create_secret_definition(ctx, secret_definition_spec, installed_pkg_id).await?;
+    }
+
     let mut installed_schema_variant_ids = vec![];
 
     for schema_spec in pkg.schemas()? {
@@ -130,6 +148,16 @@ pub async fn import_pkg_from_pkg(
         installed_schema_variant_ids.extend(schema_variant_ids);
     }
 
+    let component_count = pkg.components()?.len();
+    if component_count > 0 {
+        // Installing records the package by hash, so it can only happen once, while a template
+        // can be instantiated any number of times
+        info!(
+            "skipping {} components from {}, use instantiate_template to create them",
+            component_count, file_name
+        );
+    }
+
     Ok((installed_pkg_id, installed_schema_variant_ids))
 }
 
@@ -143,6 +171,375 @@ pub async fn import_pkg(ctx: &DalContext, pkg_file_path: impl AsRef<Path>) -> Pk
     Ok(pkg)
 }
 
+/// Creates the components of a template package in the current change set, with fresh ids each
+/// time. Unlike [`import_pkg_from_pkg`], nothing is installed or recorded, so the schemas the
+/// template uses must already be installed and the same template can be instantiated repeatedly.
+pub async fn instantiate_template(ctx: &DalContext, pkg: &SiPkg) -> PkgResult<Vec<ComponentId>> {
+    // Components are never created directly on head
+    if ctx.visibility().is_head() {
+        return Err(PkgError::ChangeSetRequiredForComponents);
+    }
+
+    let component_specs = pkg.components()?;
+    info!(
+        "creating {} components from {}",
+        component_specs.len(),
+        pkg.metadata()?.name()
+    );
+
+    create_components(ctx, &component_specs).await
+}
+
+/// Creates the components of a template, placing them in their frames and connecting them to each
+/// other as they were when exported.
+async fn create_components(
+    ctx: &DalContext,
+    component_specs: &[SiPkgComponent<'_>],
+) -> PkgResult<Vec<ComponentId>> {
+    let mut created: HashMap<&str, (Component, Node)> = HashMap::new();
+    for component_spec in component_specs {
+        let schema = Schema::find_by_name(ctx, component_spec.schema_name()).await?;
+        let (component, mut node) = Component::new_for_default_variant_from_schema(
+            ctx,
+            component_spec.name(),
+            *schema.id(),
+        )
+        .await?;
+        node.set_geometry(
+            ctx,
+            component_spec.x(),
+            component_spec.y(),
+            component_spec.width(),
+            component_spec.height(),
+        )
+        .await?;
+        if let Some(component_type) = component_spec.component_type() {
+            component.set_type(ctx, component_type.into()).await?;
+        }
+        set_component_domain(ctx, &component, component_spec.domain()).await?;
+
+        created.insert(component_spec.name(), (component, node));
+    }
+
+    let find_node = |name: &str| {
+        created
+            .get(name)
+            .map(|(_, node)| *node.id())
+            .ok_or_else(|| PkgError::MissingComponent(name.to_owned()))
+    };
+
+    // Frames have to be in place before the explicit connections, since configuration frames
+    // connect themselves to their children
+    for component_spec in component_specs {
+        if let Some(parent_name) = component_spec.parent() {
+            let child_node_id = find_node(component_spec.name())?;
+            let parent_node_id = find_node(parent_name)?;
+
+            let from_socket = Socket::find_frame_socket_for_node(
+                ctx,
+                child_node_id,
+                SocketEdgeKind::ConfigurationOutput,
+            )
+            .await?;
+            let to_socket = Socket::find_frame_socket_for_node(
+                ctx,
+                parent_node_id,
+                SocketEdgeKind::ConfigurationInput,
+            )
+            .await?;
+            Connection::new(
+                ctx,
+                child_node_id,
+                *from_socket.id(),
+                parent_node_id,
+                *to_socket.id(),
+                EdgeKind::Symbolic,
+            )
+            .await?;
+            Connection::connect_component_sockets_to_frame(ctx, parent_node_id, child_node_id)
+                .await?;
+        }
+    }
+
+    for component_spec in component_specs {
+        let to_node_id = find_node(component_spec.name())?;
+        for connection_spec in component_spec.connections()? {
+            let from_node_id = find_node(connection_spec.from_component_name())?;
+            let from_socket = Socket::find_by_name_for_edge_kind_and_node(
+                ctx,
+                connection_spec.from_socket_name(),
+                SocketEdgeKind::ConfigurationOutput,
+                from_node_id,
+            )
+            .await?
+            .ok_or_else(|| {
+                PkgError::MissingComponentSocket(
+                    connection_spec.from_component_name().to_owned(),
+                    connection_spec.from_socket_name().to_owned(),
+                )
+            })?;
+            let to_socket = Socket::find_by_name_for_edge_kind_and_node(
+                ctx,
+                connection_spec.to_socket_name(),
+                SocketEdgeKind::ConfigurationInput,
+                to_node_id,
+            )
+            .await?
+            .ok_or_else(|| {
+                PkgError::MissingComponentSocket(
+                    component_spec.name().to_owned(),
+                    connection_spec.to_socket_name().to_owned(),
+                )
+            })?;
+
+            let (to_component, _) = &created[component_spec.name()];
+            let already_connected = Edge::list_for_component(ctx, *to_component.id())
+                .await?
+                .iter()
+                .any(|edge| {
+                    *edge.kind() == EdgeKind::Configuration
+                        && edge.tail_node_id() == from_node_id
+                        && edge.tail_socket_id() == *from_socket.id()
+                        && edge.head_node_id() == to_node_id
+                        && edge.head_socket_id() == *to_socket.id()
+                });
+            if already_connected {
+                continue;
+            }
+
+            Connection::new(
+                ctx,
+                from_node_id,
+                *from_socket.id(),
+                to_node_id,
+                *to_socket.id(),
+                EdgeKind::Configuration,
+            )
+            .await?;
+
+            let (from_component, _) = &created[connection_spec.from_component_name()];
+            let external_provider = ExternalProvider::find_for_socket(ctx, *from_socket.id())
+                .await?
+                .ok_or(PkgError::MissingExternalProviderForSocket(
+                    *from_socket.id(),
+                ))?;
+            let attribute_value_context = AttributeReadContext {
+                external_provider_id: Some(*external_provider.id()),
+                component_id: Some(*from_component.id()),
+                ..Default::default()
+            };
+            let attribute_value = AttributeValue::find_for_context(ctx, attribute_value_context)
+                .await?
+                .ok_or(AttributeValueError::Missing)?;
+
+            ctx.enqueue_job(DependentValuesUpdate::new(
+                ctx.access_builder(),
+                *ctx.visibility(),
+                vec![*attribute_value.id()],
+            ))
+            .await?;
+        }
+    }
+
+    Ok(component_specs
+        .iter()
+        .filter_map(|component_spec| created.get(component_spec.name()))
+        .map(|(component, _)| *component.id())
+        .collect())
+}
+
+/// Sets the domain of a newly created component to the values exported with it. Values which
+/// are computed by a function are left to that function.
+pub(crate) async fn set_component_domain(
+    ctx: &DalContext,
+    component: &Component,
+    domain: &serde_json::Value,
+) -> PkgResult<()> {
+    let schema_variant_id = Component::schema_variant_id(ctx, *component.id()).await?;
+    let domain_prop =
+        Prop::find_prop_by_path(ctx, schema_variant_id, &PropPath::new(["root", "domain"])).await?;
+    let read_context = AttributeReadContext {
+        prop_id: Some(*domain_prop.id()),
+        component_id: Some(*component.id()),
+        ..Default::default()
+    };
+    let domain_value = AttributeValue::find_for_context(ctx, read_context)
+        .await?
+        .ok_or(AttributeValueError::Missing)?;
+
+    set_component_children(
+        ctx,
+        *component.id(),
+        &domain_prop,
+        *domain_value.id(),
+        domain,
+    )
+    .await
+}
+
+async fn set_component_value(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    prop: &Prop,
+    parent_attribute_value_id: AttributeValueId,
+    value: &serde_json::Value,
+) -> PkgResult<()> {
+    if value.is_null() {
+        return Ok(());
+    }
+
+    let read_context = AttributeReadContext {
+        prop_id: Some(*prop.id()),
+        component_id: Some(component_id),
+        ..Default::default()
+    };
+    let attribute_value = AttributeValue::find_with_parent_and_key_for_context(
+        ctx,
+        Some(parent_attribute_value_id),
+        None,
+        read_context,
+    )
+    .await?
+    .ok_or(AttributeValueError::Missing)?;
+    if !is_set_directly(ctx, &attribute_value).await? {
+        return Ok(());
+    }
+
+    let (_, attribute_value_id) = AttributeValue::update_for_context(
+        ctx,
+        *attribute_value.id(),
+        Some(parent_attribute_value_id),
+        AttributeContextBuilder::from(read_context).to_context()?,
+        Some(initial_value(prop, value)),
+        None,
+    )
+    .await?;
+
+    set_component_children(ctx, component_id, prop, attribute_value_id, value).await
+}
+
+#[async_recursion]
+async fn set_component_children(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    prop: &Prop,
+    attribute_value_id: AttributeValueId,
+    value: &serde_json::Value,
+) -> PkgResult<()> {
+    match (prop.kind(), value) {
+        (PropKind::Object, serde_json::Value::Object(fields)) => {
+            for child_prop in prop.child_props(ctx).await? {
+                if let Some(child_value) = fields.get(child_prop.name()) {
+                    set_component_value(
+                        ctx,
+                        component_id,
+                        &child_prop,
+                        attribute_value_id,
+                        child_value,
+                    )
+                    .await?;
+                }
+            }
+        }
+        (PropKind::Array, serde_json::Value::Array(items)) => {
+            let item_prop = prop
+                .child_props(ctx)
+                .await?
+                .pop()
+                .ok_or(PkgError::MissingItemPropForArrayProp(*prop.id()))?;
+            for item in items {
+                insert_component_item(
+                    ctx,
+                    component_id,
+                    &item_prop,
+                    attribute_value_id,
+                    None,
+                    item,
+                )
+                .await?;
+            }
+        }
+        (PropKind::Map, serde_json::Value::Object(entries)) => {
+            let item_prop = prop
+                .child_props(ctx)
+                .await?
+                .pop()
+                .ok_or(PkgError::MissingItemPropForMapProp(*prop.id()))?;
+            for (key, item) in entries {
+                insert_component_item(
+                    ctx,
+                    component_id,
+                    &item_prop,
+                    attribute_value_id,
+                    Some(key.to_owned()),
+                    item,
+                )
+                .await?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+async fn insert_component_item(
+    ctx: &DalContext,
+    component_id: ComponentId,
+    item_prop: &Prop,
+    array_or_map_attribute_value_id: AttributeValueId,
+    key: Option<String>,
+    value: &serde_json::Value,
+) -> PkgResult<()> {
+    let item_context = AttributeContextBuilder::new()
+        .set_prop_id(*item_prop.id())
+        .set_component_id(component_id)
+        .to_context()?;
+    let item_id = AttributeValue::insert_for_context(
+        ctx,
+        item_context,
+        array_or_map_attribute_value_id,
+        Some(initial_value(item_prop, value)),
+        key,
+    )
+    .await?;
+
+    set_component_children(ctx, component_id, item_prop, item_id, value).await
+}
+
+/// Objects, arrays and maps are created empty and then filled in.
+fn initial_value(prop: &Prop, value: &serde_json::Value) -> serde_json::Value {
+    match prop.kind() {
+        PropKind::Object | PropKind::Map => serde_json::json!({}),
+        PropKind::Array => serde_json::json!([]),
+        _ => value.to_owned(),
+    }
+}
+
+/// Whether the attribute value is set by one of the intrinsic setter functions, rather than
+/// computed by a function of its own.
+async fn is_set_directly(ctx: &DalContext, attribute_value: &AttributeValue) -> PkgResult<bool> {
+    let prototype = attribute_value
+        .attribute_prototype(ctx)
+        .await?
+        .ok_or(AttributeValueError::MissingAttributePrototype)?;
+    let func = Func::get_by_id(ctx, &prototype.func_id()).await?.ok_or(
+        PkgError::MissingAttributePrototypeFunc(*prototype.id(), prototype.func_id()),
+    )?;
+
+    Ok(matches!(
+        func.backend_kind(),
+        FuncBackendKind::Array
+            | FuncBackendKind::Boolean
+            | FuncBackendKind::Float
+            | FuncBackendKind::Integer
+            | FuncBackendKind::Map
+            | FuncBackendKind::Object
+            | FuncBackendKind::String
+            | FuncBackendKind::Unset
+    ))
+}
+
 async fn create_func(
     ctx: &DalContext,
     func_spec: SiPkgFunc<'_>,
@@ -179,6 +576,7 @@ async fn create_func(
                 .await?;
             func.set_description(ctx, func_spec.description()).await?;
             func.set_handler(ctx, Some(func_spec.handler())).await?;
+            func.set_plan_handler(ctx, func_spec.plan_handler()).await?;
             func.set_hidden(ctx, func.hidden()).await?;
             func.set_link(ctx, func_spec.link().map(|l| l.to_string()))
                 .await?;
@@ -211,6 +609,63 @@ async fn create_func(
     Ok(func)
 }
 
+async fn create_secret_definition(
+    ctx: &DalContext,
+    secret_definition_spec: SiPkgSecretDefinition<'_>,
+    installed_pkg_id: Option<InstalledPkgId>,
+) -> PkgResult<SecretDefinition> {
+    let hash = secret_definition_spec.hash().to_string();
+    let existing_secret_definition = InstalledPkgAsset::list_for_kind_and_hash(
+        ctx,
+        InstalledPkgAssetKind::SecretDefinition,
+        &hash,
+    )
+    .await?
+    .pop();
+
+    let secret_definition = match existing_secret_definition {
+        Some(installed_secret_definition_record) => {
+            match installed_secret_definition_record.as_installed_secret_definition()? {
+                InstalledPkgAssetTyped::SecretDefinition { id, .. } => {
+                    match SecretDefinition::get_by_id(ctx, &id).await? {
+                        Some(secret_definition) => secret_definition,
+                        None => return Err(PkgError::InstalledSecretDefinitionMissing(id)),
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+        None => {
+            let description = match secret_definition_spec.description() {
+                "" => None,
+                description => Some(description.to_string()),
+            };
+
+            SecretDefinition::new(
+                ctx,
+                secret_definition_spec.name(),
+                description,
+                secret_definition_spec.schema().clone(),
+            )
+            .await?
+        }
+    };
+
+    if let Some(installed_pkg_id) = installed_pkg_id {
+        InstalledPkgAsset::new(
+            ctx,
+            InstalledPkgAssetTyped::new_for_secret_definition(
+                *secret_definition.id(),
+                installed_pkg_id,
+                hash,
+            ),
+        )
+        .await?;
+    }
+
+    Ok(secret_definition)
+}
+
 async fn create_schema(
     ctx: &DalContext,
     schema_spec: SiPkgSchema<'_>,
@@ -374,6 +829,10 @@ enum DefaultValueInfo {
         prop_id: PropId,
         default_value: bool,
     },
+    Float {
+        prop_id: PropId,
+        default_value: f64,
+    },
     Number {
         prop_id: PropId,
         default_value: i64,
@@ -790,6 +1249,7 @@ async fn set_default_value(
 ) -> PkgResult<()> {
     let prop = match &default_value_info {
         DefaultValueInfo::Number { prop_id, .. }
+        | DefaultValueInfo::Float { prop_id, .. }
         | DefaultValueInfo::String { prop_id, .. }
         | DefaultValueInfo::Boolean { prop_id, .. } => Prop::get_by_id(ctx, prop_id)
             .await?
@@ -803,6 +1263,9 @@ async fn set_default_value(
         DefaultValueInfo::Number { default_value, .. } => {
             prop.set_default_value(ctx, default_value).await?
         }
+        DefaultValueInfo::Float { default_value, .. } => {
+            prop.set_default_value(ctx, default_value).await?
+        }
         DefaultValueInfo::String { default_value, .. } => {
             prop.set_default_value(ctx, default_value).await?
         }
@@ -1011,56 +1474,114 @@ async fn create_prop_validation(
         .pop()
         .ok_or(FuncError::NotFoundByName("si:validation".to_string()))?;
 
-    let validation_kind = match spec {
-        SiPkgValidation::IntegerIsBetweenTwoIntegers {
-            lower_bound,
-            upper_bound,
-            ..
-        } => ValidationKind::Builtin(Validation::IntegerIsBetweenTwoIntegers {
-            value: None,
-            lower_bound,
-            upper_bound,
-        }),
-        SiPkgValidation::IntegerIsNotEmpty { .. } => {
-            ValidationKind::Builtin(Validation::IntegerIsNotEmpty { value: None })
-        }
-        SiPkgValidation::StringEquals { expected, .. } => {
-            ValidationKind::Builtin(Validation::StringEquals {
+    let validation_kind =
+        match spec {
+            SiPkgValidation::ArrayItemsAreUnique { .. } => {
+                ValidationKind::Builtin(Validation::ArrayItemsAreUnique { value: None })
+            }
+            SiPkgValidation::ArrayLengthIsBetween {
+                lower_bound,
+                upper_bound,
+                ..
+            } => ValidationKind::Builtin(Validation::ArrayLengthIsBetween {
+                value: None,
+                lower_bound,
+                upper_bound,
+            }),
+            SiPkgValidation::FloatIsBetweenTwoFloats {
+                lower_bound,
+                upper_bound,
+                ..
+            } => {
+                ValidationKind::Builtin(Validation::FloatIsBetweenTwoFloats {
+                    value: None,
+                    lower_bound: lower_bound.as_f64().ok_or(
+                        PkgError::InvalidValidationFloatBound(lower_bound.to_string()),
+                    )?,
+                    upper_bound: upper_bound.as_f64().ok_or(
+                        PkgError::InvalidValidationFloatBound(upper_bound.to_string()),
+                    )?,
+                })
+            }
+            SiPkgValidation::FloatIsNotEmpty { .. } => {
+                ValidationKind::Builtin(Validation::FloatIsNotEmpty { value: None })
+            }
+            SiPkgValidation::IntegerIsBetweenTwoIntegers {
+                lower_bound,
+                upper_bound,
+                ..
+            } => ValidationKind::Builtin(Validation::IntegerIsBetweenTwoIntegers {
                 value: None,
+                lower_bound,
+                upper_bound,
+            }),
+            SiPkgValidation::IntegerIsNotEmpty { .. } => {
+                ValidationKind::Builtin(Validation::IntegerIsNotEmpty { value: None })
+            }
+            SiPkgValidation::StringEquals { expected, .. } => {
+                ValidationKind::Builtin(Validation::StringEquals {
+                    value: None,
+                    expected,
+                })
+            }
+            SiPkgValidation::StringHasPrefix { expected, .. } => {
+                ValidationKind::Builtin(Validation::StringHasPrefix {
+                    value: None,
+                    expected,
+                })
+            }
+            SiPkgValidation::StringInStringArray {
                 expected,
-            })
-        }
-        SiPkgValidation::StringHasPrefix { expected, .. } => {
-            ValidationKind::Builtin(Validation::StringHasPrefix {
+                display_expected,
+                ..
+            } => ValidationKind::Builtin(Validation::StringInStringArray {
                 value: None,
                 expected,
-            })
-        }
-        SiPkgValidation::StringInStringArray {
-            expected,
-            display_expected,
-            ..
-        } => ValidationKind::Builtin(Validation::StringInStringArray {
-            value: None,
-            expected,
-            display_expected,
-        }),
-        SiPkgValidation::StringIsHexColor { .. } => {
-            ValidationKind::Builtin(Validation::StringIsHexColor { value: None })
-        }
-        SiPkgValidation::StringIsNotEmpty { .. } => {
-            ValidationKind::Builtin(Validation::StringIsNotEmpty { value: None })
-        }
-        SiPkgValidation::StringIsValidIpAddr { .. } => {
-            ValidationKind::Builtin(Validation::StringIsValidIpAddr { value: None })
-        }
-        SiPkgValidation::CustomValidation { func_unique_id, .. } => ValidationKind::Custom(
-            *ctx.func_map
-                .get(&func_unique_id)
-                .ok_or(PkgError::MissingFuncUniqueId(func_unique_id.to_string()))?
-                .id(),
-        ),
-    };
+                display_expected,
+            }),
+            SiPkgValidation::StringIsHexColor { .. } => {
+                ValidationKind::Builtin(Validation::StringIsHexColor { value: None })
+            }
+            SiPkgValidation::StringIsNotEmpty { .. } => {
+                ValidationKind::Builtin(Validation::StringIsNotEmpty { value: None })
+            }
+            SiPkgValidation::StringIsValidIpAddr { .. } => {
+                ValidationKind::Builtin(Validation::StringIsValidIpAddr { value: None })
+            }
+            SiPkgValidation::StringIsArn { .. } => {
+                ValidationKind::Builtin(Validation::StringIsArn { value: None })
+            }
+            SiPkgValidation::StringIsHostname { .. } => {
+                ValidationKind::Builtin(Validation::StringIsHostname { value: None })
+            }
+            SiPkgValidation::StringIsValidCidr { .. } => {
+                ValidationKind::Builtin(Validation::StringIsValidCidr { value: None })
+            }
+            SiPkgValidation::StringIsValidUrl { .. } => {
+                ValidationKind::Builtin(Validation::StringIsValidUrl { value: None })
+            }
+            SiPkgValidation::StringLengthIsBetween {
+                lower_bound,
+                upper_bound,
+                ..
+            } => ValidationKind::Builtin(Validation::StringLengthIsBetween {
+                value: None,
+                lower_bound,
+                upper_bound,
+            }),
+            SiPkgValidation::StringMatchesRegex { pattern, .. } => {
+                ValidationKind::Builtin(Validation::StringMatchesRegex {
+                    value: None,
+                    pattern,
+                })
+            }
+            SiPkgValidation::CustomValidation { func_unique_id, .. } => ValidationKind::Custom(
+                *ctx.func_map
+                    .get(&func_unique_id)
+                    .ok_or(PkgError::MissingFuncUniqueId(func_unique_id.to_string()))?
+                    .id(),
+            ),
+        };
 
     create_validation(
         ctx.ctx,
@@ -1086,6 +1607,7 @@ async fn create_prop(
         match &spec {
             SiPkgProp::String { .. } => PropKind::String,
             SiPkgProp::Number { .. } => PropKind::Integer,
+            SiPkgProp::Float { .. } => PropKind::Float,
             SiPkgProp::Boolean { .. } => PropKind::Boolean,
             SiPkgProp::Map { .. } => PropKind::Map,
             SiPkgProp::Array { .. } => PropKind::Array,
@@ -1102,6 +1624,11 @@ async fn create_prop(
                 widget_options,
                 ..
             }
+            | SiPkgProp::Float {
+                widget_kind,
+                widget_options,
+                ..
+            }
             | SiPkgProp::Boolean {
                 widget_kind,
                 widget_options,
@@ -1134,6 +1661,7 @@ async fn create_prop(
         match &spec {
             SiPkgProp::String { hidden, .. }
             | SiPkgProp::Number { hidden, .. }
+            | SiPkgProp::Float { hidden, .. }
             | SiPkgProp::Boolean { hidden, .. }
             | SiPkgProp::Map { hidden, .. }
             | SiPkgProp::Array { hidden, .. }
@@ -1147,6 +1675,7 @@ async fn create_prop(
         match &spec {
             SiPkgProp::String { doc_link, .. }
             | SiPkgProp::Number { doc_link, .. }
+            | SiPkgProp::Float { doc_link, .. }
             | SiPkgProp::Boolean { doc_link, .. }
             | SiPkgProp::Map { doc_link, .. }
             | SiPkgProp::Array { doc_link, .. }
@@ -1175,6 +1704,12 @@ async fn create_prop(
                 default_value,
             })
         }
+        SiPkgProp::Float { default_value, .. } => {
+            default_value.map(|default_value| DefaultValueInfo::Float {
+                prop_id,
+                default_value,
+            })
+        }
         SiPkgProp::Boolean { default_value, .. } => {
             default_value.map(|default_value| DefaultValueInfo::Boolean {
                 prop_id,
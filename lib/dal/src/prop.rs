@@ -24,8 +24,8 @@ use crate::{
     standard_model, standard_model_accessor, standard_model_belongs_to, standard_model_has_many,
     AttributeContext, AttributeContextBuilder, AttributeContextBuilderError,
     AttributePrototypeError, AttributeReadContext, DalContext, Func, FuncError, FuncId,
-    HistoryEventError, SchemaVariantId, StandardModel, StandardModelError, Tenancy, Timestamp,
-    Visibility,
+    HistoryEventError, SchemaVariantId, SecretKind, StandardModel, StandardModelError, Tenancy,
+    Timestamp, Visibility,
 };
 use crate::{AttributeValueError, AttributeValueId, FuncBackendResponseType, TransactionsError};
 
@@ -33,6 +33,10 @@ use crate::{AttributeValueError, AttributeValueId, FuncBackendResponseType, Tran
 /// not (we'll see) be able to be provided by our users in [`Prop`] names.
 pub const PROP_PATH_SEPARATOR: &str = "\x0B";
 
+/// The key in a [`WidgetKind::SecretSelect`] [`Prop`]'s widget options that holds the
+/// [`SecretKind`] its value must refer to.
+pub const SECRET_KIND_WIDGET_OPTION: &str = "secretKind";
+
 /// This type should be used to manage prop paths instead of a raw string
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PropPath(String);
@@ -173,6 +177,7 @@ pk!(PropId);
 pub enum PropKind {
     Array,
     Boolean,
+    Float,
     Integer,
     Map,
     Object,
@@ -186,6 +191,7 @@ impl From<PropKind> for PropSpecKind {
             PropKind::Boolean => Self::Boolean,
             PropKind::String => Self::String,
             PropKind::Integer => Self::Number,
+            PropKind::Float => Self::Float,
             PropKind::Object => Self::Object,
             PropKind::Map => Self::Map,
         }
@@ -199,7 +205,7 @@ impl From<PropKind> for WidgetKind {
         match prop {
             PropKind::Array => Self::Array,
             PropKind::Boolean => Self::Checkbox,
-            PropKind::String | PropKind::Integer => Self::Text,
+            PropKind::String | PropKind::Integer | PropKind::Float => Self::Text,
             PropKind::Object => Self::Header,
             PropKind::Map => Self::Map,
         }
@@ -212,6 +218,7 @@ impl From<PropKind> for FuncBackendResponseType {
             PropKind::Array => Self::Array,
             PropKind::Boolean => Self::Boolean,
             PropKind::Integer => Self::Integer,
+            PropKind::Float => Self::Float,
             PropKind::Object => Self::Object,
             PropKind::Map => Self::Map,
             PropKind::String => Self::String,
@@ -320,6 +327,19 @@ impl Prop {
         self.path.to_owned().into()
     }
 
+    /// Returns the [`SecretKind`] that values of this [`Prop`] must refer to, if it is a secret
+    /// reference (a [`WidgetKind::SecretSelect`] constrained by a `secretKind` widget option).
+    pub fn secret_kind(&self) -> Option<SecretKind> {
+        if self.widget_kind != WidgetKind::SecretSelect {
+            return None;
+        }
+        self.widget_options
+            .as_ref()
+            .and_then(|options| options.get(SECRET_KIND_WIDGET_OPTION))
+            .and_then(Value::as_str)
+            .map(SecretKind::from)
+    }
+
     // TODO(nick): replace this table with a foreign key relationship.
     standard_model_belongs_to!(
         lookup_fn: parent_prop,
@@ -394,7 +414,7 @@ impl Prop {
                     .await?
             ),
             PropKind::Boolean => "boolean".to_string(),
-            PropKind::Integer => "number".to_string(),
+            PropKind::Integer | PropKind::Float => "number".to_string(),
             PropKind::Map => format!(
                 "Record<string, {}>",
                 self.child_props(ctx)
@@ -559,7 +579,7 @@ impl Prop {
     ) -> PropResult<()> {
         let value = serde_json::to_value(value)?;
         match self.kind() {
-            PropKind::String | PropKind::Boolean | PropKind::Integer => {
+            PropKind::String | PropKind::Boolean | PropKind::Integer | PropKind::Float => {
                 let attribute_read_context = AttributeReadContext::default_with_prop(self.id);
                 let attribute_value = AttributeValue::find_for_context(ctx, attribute_read_context)
                     .await?
@@ -62,7 +62,7 @@ impl PropTreeNode {
                 format!("{}[] | null | undefined", array_element_type.ts_type()?)
             }
             PropKind::Boolean => "boolean | null | undefined".into(),
-            PropKind::Integer => "number | null | undefined".into(),
+            PropKind::Integer | PropKind::Float => "number | null | undefined".into(),
             PropKind::Object => {
                 let mut object_interface = "{\n".to_string();
                 for child in &self.children {
@@ -89,12 +89,7 @@ impl PropertyEditorProp {
             id: (*prop.id()).into(),
             name: prop.name().into(),
             kind: prop.kind().into(),
-            widget_kind: PropertyEditorPropWidgetKind::new(
-                ctx,
-                *prop.widget_kind(),
-                prop.widget_options().map(|v| v.to_owned()),
-            )
-            .await?,
+            widget_kind: PropertyEditorPropWidgetKind::new(ctx, &prop).await?,
             doc_link: prop.doc_link().map(Into::into),
         })
     }
@@ -106,6 +101,7 @@ impl PropertyEditorProp {
 pub enum PropertyEditorPropKind {
     Array,
     Boolean,
+    Float,
     Integer,
     Map,
     Object,
@@ -117,6 +113,7 @@ impl From<&PropKind> for PropertyEditorPropKind {
         match prop_kind {
             PropKind::Array => Self::Array,
             PropKind::Boolean => Self::Boolean,
+            PropKind::Float => Self::Float,
             PropKind::Integer => Self::Integer,
             PropKind::Object => Self::Object,
             PropKind::String => Self::String,
@@ -202,12 +199,9 @@ impl From<&PropSpecWidgetKind> for WidgetKind {
 }
 
 impl PropertyEditorPropWidgetKind {
-    pub async fn new(
-        ctx: &DalContext,
-        widget_kind: WidgetKind,
-        widget_options: Option<Value>,
-    ) -> PropertyEditorResult<Self> {
-        Ok(match widget_kind {
+    pub async fn new(ctx: &DalContext, prop: &Prop) -> PropertyEditorResult<Self> {
+        let widget_options = prop.widget_options().cloned();
+        Ok(match *prop.widget_kind() {
             WidgetKind::Array => Self::Array,
             WidgetKind::Checkbox => Self::Checkbox,
             WidgetKind::Header => Self::Header,
@@ -216,15 +210,20 @@ impl PropertyEditorPropWidgetKind {
                 options: widget_options,
             },
             WidgetKind::Color => Self::Color,
-            WidgetKind::SecretSelect => Self::SecretSelect {
-                options: LabelList::new(
-                    Secret::list(ctx)
-                        .await?
-                        .into_iter()
-                        .map(|s| LabelEntry::new(s.name(), *s.id()))
-                        .collect(),
-                ),
-            },
+            WidgetKind::SecretSelect => {
+                // Secret references constrained to a kind only offer secrets of that kind.
+                let secret_kind = prop.secret_kind();
+                Self::SecretSelect {
+                    options: LabelList::new(
+                        Secret::list(ctx)
+                            .await?
+                            .into_iter()
+                            .filter(|s| secret_kind.as_ref().map_or(true, |kind| s.kind() == kind))
+                            .map(|s| LabelEntry::new(s.name(), *s.id()))
+                            .collect(),
+                    ),
+                }
+            }
             WidgetKind::Text => Self::Text,
             WidgetKind::TextArea => Self::TextArea,
             WidgetKind::ComboBox => Self::ComboBox {
@@ -0,0 +1,5 @@
+UPDATE api_tokens
+SET last_used_at = clock_timestamp()
+WHERE api_tokens.token_hash = $1
+  AND api_tokens.revoked_at IS NULL
+RETURNING row_to_json(api_tokens.*) AS object
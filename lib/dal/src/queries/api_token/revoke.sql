@@ -0,0 +1,7 @@
+UPDATE api_tokens
+SET revoked_at = clock_timestamp(),
+    updated_at = clock_timestamp()
+WHERE api_tokens.pk = $2
+  AND api_tokens.revoked_at IS NULL
+  AND in_tenancy_v1($1, api_tokens.tenancy_workspace_pk)
+RETURNING row_to_json(api_tokens.*) AS object
@@ -0,0 +1,55 @@
+SELECT change_set_attribute_values.id                             AS object_id,
+       change_set_attribute_values.attribute_context_component_id AS component_id,
+       change_set_attribute_values.attribute_context_prop_id      AS prop_id,
+       CASE
+           WHEN change_set_attribute_values.visibility_deleted_at IS NOT NULL
+               THEN 'DeletedInChangeSetModifiedOnHead'
+           WHEN head_attribute_values.visibility_deleted_at IS NOT NULL
+               THEN 'ModifiedInChangeSetDeletedOnHead'
+           ELSE 'ModifiedInBoth'
+           END                                                    AS conflict_kind,
+       change_set_value.value                                     AS change_set_value,
+       head_value.value                                           AS head_value,
+       head_attribute_values.updated_at                           AS head_updated_at
+FROM attribute_values AS change_set_attribute_values
+
+         -- The row on head that the change set row was copied from
+         INNER JOIN attribute_values AS head_attribute_values
+                    ON head_attribute_values.id = change_set_attribute_values.id
+                        AND head_attribute_values.visibility_change_set_pk = ident_nil_v1()
+                        AND in_tenancy_v1($1, head_attribute_values.tenancy_workspace_pk)
+
+         -- Prefer the change set copy of the return value, falling back to head
+         LEFT JOIN LATERAL (SELECT func_binding_return_values.value
+                            FROM func_binding_return_values
+                            WHERE func_binding_return_values.id =
+                                  change_set_attribute_values.func_binding_return_value_id
+                              AND func_binding_return_values.visibility_change_set_pk IN (ident_nil_v1(), $2)
+                              AND in_tenancy_v1($1, func_binding_return_values.tenancy_workspace_pk)
+                            ORDER BY func_binding_return_values.visibility_change_set_pk = ident_nil_v1()
+                            LIMIT 1) AS change_set_value ON TRUE
+
+         LEFT JOIN LATERAL (SELECT func_binding_return_values.value
+                            FROM func_binding_return_values
+                            WHERE func_binding_return_values.id = head_attribute_values.func_binding_return_value_id
+                              AND func_binding_return_values.visibility_change_set_pk = ident_nil_v1()
+                              AND in_tenancy_v1($1, func_binding_return_values.tenancy_workspace_pk)
+                            LIMIT 1) AS head_value ON TRUE
+
+WHERE change_set_attribute_values.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, change_set_attribute_values.tenancy_workspace_pk)
+
+  -- Head has moved since the change set copied the row (our "base")
+  AND head_attribute_values.updated_at > change_set_attribute_values.created_at
+
+  -- Deleted on both sides is not a conflict
+  AND NOT (change_set_attribute_values.visibility_deleted_at IS NOT NULL
+    AND head_attribute_values.visibility_deleted_at IS NOT NULL)
+
+  -- Both sides made the same change, so there is nothing to choose between
+  AND (change_set_attribute_values.visibility_deleted_at IS NOT NULL
+    OR head_attribute_values.visibility_deleted_at IS NOT NULL
+    OR change_set_value.value IS DISTINCT FROM head_value.value)
+
+ORDER BY change_set_attribute_values.attribute_context_component_id,
+         change_set_attribute_values.id
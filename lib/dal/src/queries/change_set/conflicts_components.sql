@@ -0,0 +1,41 @@
+SELECT change_set_components.id                 AS object_id,
+       CASE
+           WHEN change_set_components.visibility_deleted_at IS NOT NULL
+               THEN 'DeletedInChangeSetModifiedOnHead'
+           WHEN head_components.visibility_deleted_at IS NOT NULL
+               THEN 'ModifiedInChangeSetDeletedOnHead'
+           ELSE 'ModifiedInBoth'
+           END                                AS conflict_kind,
+       to_jsonb(change_set_components) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                              'created_at', 'updated_at'] AS change_set_value,
+       to_jsonb(head_components) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                        'created_at', 'updated_at']       AS head_value,
+       head_components.updated_at               AS head_updated_at
+FROM components AS change_set_components
+
+         -- The row on head that the change set row was copied from
+         INNER JOIN components AS head_components
+                    ON head_components.id = change_set_components.id
+                        AND head_components.visibility_change_set_pk = ident_nil_v1()
+                        AND in_tenancy_v1($1, head_components.tenancy_workspace_pk)
+
+WHERE change_set_components.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, change_set_components.tenancy_workspace_pk)
+
+  -- Head has moved since the change set copied the row (our "base")
+  AND head_components.updated_at > change_set_components.created_at
+
+  -- Deleted on both sides is not a conflict
+  AND NOT (change_set_components.visibility_deleted_at IS NOT NULL
+    AND head_components.visibility_deleted_at IS NOT NULL)
+
+  -- Both sides made the same change, so there is nothing to choose between
+  AND (change_set_components.visibility_deleted_at IS NOT NULL
+    OR head_components.visibility_deleted_at IS NOT NULL
+    OR (to_jsonb(change_set_components) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                               'created_at', 'updated_at'])
+        IS DISTINCT FROM
+       (to_jsonb(head_components) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                         'created_at', 'updated_at']))
+
+ORDER BY change_set_components.id
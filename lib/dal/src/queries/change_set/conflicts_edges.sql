@@ -0,0 +1,41 @@
+SELECT change_set_edges.id                 AS object_id,
+       CASE
+           WHEN change_set_edges.visibility_deleted_at IS NOT NULL
+               THEN 'DeletedInChangeSetModifiedOnHead'
+           WHEN head_edges.visibility_deleted_at IS NOT NULL
+               THEN 'ModifiedInChangeSetDeletedOnHead'
+           ELSE 'ModifiedInBoth'
+           END                                AS conflict_kind,
+       to_jsonb(change_set_edges) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                              'created_at', 'updated_at'] AS change_set_value,
+       to_jsonb(head_edges) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                        'created_at', 'updated_at']       AS head_value,
+       head_edges.updated_at               AS head_updated_at
+FROM edges AS change_set_edges
+
+         -- The row on head that the change set row was copied from
+         INNER JOIN edges AS head_edges
+                    ON head_edges.id = change_set_edges.id
+                        AND head_edges.visibility_change_set_pk = ident_nil_v1()
+                        AND in_tenancy_v1($1, head_edges.tenancy_workspace_pk)
+
+WHERE change_set_edges.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, change_set_edges.tenancy_workspace_pk)
+
+  -- Head has moved since the change set copied the row (our "base")
+  AND head_edges.updated_at > change_set_edges.created_at
+
+  -- Deleted on both sides is not a conflict
+  AND NOT (change_set_edges.visibility_deleted_at IS NOT NULL
+    AND head_edges.visibility_deleted_at IS NOT NULL)
+
+  -- Both sides made the same change, so there is nothing to choose between
+  AND (change_set_edges.visibility_deleted_at IS NOT NULL
+    OR head_edges.visibility_deleted_at IS NOT NULL
+    OR (to_jsonb(change_set_edges) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                               'created_at', 'updated_at'])
+        IS DISTINCT FROM
+       (to_jsonb(head_edges) - ARRAY ['pk', 'visibility_change_set_pk', 'visibility_deleted_at',
+                                         'created_at', 'updated_at']))
+
+ORDER BY change_set_edges.id
@@ -0,0 +1,24 @@
+SELECT change_set_edges.id             AS id,
+       change_set_edges.head_object_id AS head_component_id,
+       change_set_edges.head_socket_id AS head_socket_id,
+       change_set_edges.tail_object_id AS tail_component_id,
+       change_set_edges.tail_socket_id AS tail_socket_id,
+       CASE
+           WHEN change_set_edges.visibility_deleted_at IS NOT NULL THEN 'deleted'
+           ELSE 'added'
+           END                         AS change_status
+FROM edges AS change_set_edges
+         LEFT JOIN edges AS head_edges
+                   ON head_edges.id = change_set_edges.id
+                       AND head_edges.visibility_change_set_pk = ident_nil_v1()
+                       AND head_edges.visibility_deleted_at IS NULL
+                       AND in_tenancy_v1($1, head_edges.tenancy_workspace_pk)
+
+WHERE change_set_edges.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, change_set_edges.tenancy_workspace_pk)
+
+  -- Edges are only ever created or deleted; a change set copy of a live head edge is not a change
+  AND ((change_set_edges.visibility_deleted_at IS NULL AND head_edges.id IS NULL)
+    OR (change_set_edges.visibility_deleted_at IS NOT NULL AND head_edges.id IS NOT NULL))
+
+ORDER BY change_set_edges.id
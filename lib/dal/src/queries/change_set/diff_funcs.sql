@@ -0,0 +1,21 @@
+SELECT change_set_funcs.id   AS id,
+       change_set_funcs.name AS name,
+       CASE
+           WHEN change_set_funcs.visibility_deleted_at IS NOT NULL THEN 'deleted'
+           WHEN head_funcs.id IS NULL THEN 'added'
+           ELSE 'modified'
+           END               AS change_status
+FROM funcs AS change_set_funcs
+         LEFT JOIN funcs AS head_funcs
+                   ON head_funcs.id = change_set_funcs.id
+                       AND head_funcs.visibility_change_set_pk = ident_nil_v1()
+                       AND head_funcs.visibility_deleted_at IS NULL
+                       AND in_tenancy_v1($1, head_funcs.tenancy_workspace_pk)
+
+WHERE change_set_funcs.visibility_change_set_pk = $2
+  AND in_tenancy_v1($1, change_set_funcs.tenancy_workspace_pk)
+
+  -- Something both created and deleted in the change set never existed as far as head is concerned
+  AND NOT (change_set_funcs.visibility_deleted_at IS NOT NULL AND head_funcs.id IS NULL)
+
+ORDER BY change_set_funcs.name
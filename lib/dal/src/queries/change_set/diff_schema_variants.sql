@@ -0,0 +1,45 @@
+SELECT schema_variants.id   AS id,
+       schema_variants.name AS name,
+       CASE
+           WHEN change_set_schema_variants.visibility_deleted_at IS NOT NULL THEN 'deleted'
+           WHEN head_schema_variants.id IS NULL THEN 'added'
+           ELSE 'modified'
+           END              AS change_status
+FROM (
+         -- Schema variants that were changed directly...
+         SELECT id
+         FROM schema_variants
+         WHERE visibility_change_set_pk = $2
+           AND in_tenancy_v1($1, tenancy_workspace_pk)
+         UNION
+         -- ...or through their props
+         SELECT schema_variant_id AS id
+         FROM props
+         WHERE visibility_change_set_pk = $2
+           AND schema_variant_id IS NOT NULL
+           AND in_tenancy_v1($1, tenancy_workspace_pk)) AS changed
+
+         -- The current version of the schema variant, as seen by the change set
+         INNER JOIN LATERAL (SELECT *
+                             FROM schema_variants
+                             WHERE schema_variants.id = changed.id
+                               AND schema_variants.visibility_change_set_pk IN (ident_nil_v1(), $2)
+                               AND in_tenancy_v1($1, schema_variants.tenancy_workspace_pk)
+                             ORDER BY schema_variants.visibility_change_set_pk = ident_nil_v1()
+                             LIMIT 1) AS schema_variants ON TRUE
+
+         LEFT JOIN schema_variants AS change_set_schema_variants
+                   ON change_set_schema_variants.id = changed.id
+                       AND change_set_schema_variants.visibility_change_set_pk = $2
+                       AND in_tenancy_v1($1, change_set_schema_variants.tenancy_workspace_pk)
+
+         LEFT JOIN schema_variants AS head_schema_variants
+                   ON head_schema_variants.id = changed.id
+                       AND head_schema_variants.visibility_change_set_pk = ident_nil_v1()
+                       AND head_schema_variants.visibility_deleted_at IS NULL
+                       AND in_tenancy_v1($1, head_schema_variants.tenancy_workspace_pk)
+
+-- Something both created and deleted in the change set never existed as far as head is concerned
+WHERE NOT (change_set_schema_variants.visibility_deleted_at IS NOT NULL AND head_schema_variants.id IS NULL)
+
+ORDER BY schema_variants.name
@@ -0,0 +1,6 @@
+SELECT change_set_reviewers.user_pk AS user_pk
+FROM change_set_reviewers
+WHERE
+    change_set_reviewers.change_set_pk = $2
+    AND in_tenancy_v1($1, change_set_reviewers.tenancy_workspace_pk)
+ORDER BY change_set_reviewers.created_at
@@ -3,5 +3,5 @@ SELECT
     change_sets.pk AS value
 FROM change_sets
 WHERE
-    status = 'Open'
+    status IN ('Open', 'NeedsApproval')
     AND in_tenancy_v1($1, change_sets.tenancy_workspace_pk)
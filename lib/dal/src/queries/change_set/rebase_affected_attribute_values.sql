@@ -0,0 +1,18 @@
+SELECT DISTINCT attribute_values.id AS attribute_value_id
+FROM attribute_values
+WHERE in_tenancy_v1($1, attribute_values.tenancy_workspace_pk)
+  AND attribute_values.visibility_deleted_at IS NULL
+  AND (
+    -- Everything the change set has touched
+            attribute_values.visibility_change_set_pk = $2
+
+        -- Everything head has touched since the change set was last based on it, unless the
+        -- change set has deleted it
+        OR (attribute_values.visibility_change_set_pk = ident_nil_v1()
+        AND attribute_values.updated_at > $3
+        AND attribute_values.id NOT IN (SELECT id
+                                        FROM attribute_values
+                                        WHERE visibility_change_set_pk = $2
+                                          AND visibility_deleted_at IS NOT NULL
+                                          AND in_tenancy_v1($1, tenancy_workspace_pk)))
+    )
@@ -0,0 +1,9 @@
+-- The components of the given schema variant which were imported from the given resource
+-- identifier.
+SELECT c.id AS component_id
+FROM components_v1($1, $2) AS c
+INNER JOIN component_belongs_to_schema_variant_v1($1, $2) AS cbtsv
+    ON cbtsv.object_id = c.id
+WHERE cbtsv.belongs_to_id = $3
+  AND c.import_identifier = $4
+ORDER BY c.id
@@ -0,0 +1,7 @@
+UPDATE encrypted_secrets
+SET crypted     = $2,
+    version     = $3,
+    key_pair_pk = $4,
+    updated_at  = clock_timestamp()
+WHERE pk = $1
+RETURNING updated_at
@@ -0,0 +1,4 @@
+SELECT row_to_json(func_executions.*) AS object
+FROM func_executions
+WHERE func_executions.pk = $2
+  AND in_tenancy_v1($1, func_executions.tenancy_workspace_pk)
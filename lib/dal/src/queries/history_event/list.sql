@@ -0,0 +1,19 @@
+SELECT row_to_json(history_events.*) AS object
+FROM history_events
+WHERE
+    in_tenancy_v1($1, history_events.tenancy_workspace_pk)
+    AND ($2::jsonb IS NULL OR history_events.actor = $2::jsonb)
+    AND ($3::text IS NULL OR starts_with(history_events.label, $3::text))
+    AND ($4::text IS NULL
+        OR history_events.data ->> 'pk' = $4::text
+        OR history_events.data ->> 'id' = $4::text)
+    AND ($5::timestamptz IS NULL OR history_events.created_at >= $5::timestamptz)
+    AND ($6::timestamptz IS NULL OR history_events.created_at < $6::timestamptz)
+    AND ($7::ident IS NULL
+        OR (history_events.created_at, history_events.pk) < (
+            SELECT before.created_at, before.pk
+            FROM history_events AS before
+            WHERE before.pk = $7::ident
+        ))
+ORDER BY history_events.created_at DESC, history_events.pk DESC
+LIMIT $8
@@ -0,0 +1,8 @@
+SELECT audit_export_outbox.history_event_pk AS pk,
+       row_to_json(history_events.*)        AS object
+FROM audit_export_outbox
+         INNER JOIN history_events
+                    ON history_events.pk = audit_export_outbox.history_event_pk
+WHERE audit_export_outbox.sink = $1
+ORDER BY audit_export_outbox.created_at, audit_export_outbox.history_event_pk
+LIMIT $2
@@ -0,0 +1,11 @@
+-- Retires every other key pair in the workspace that no secret is sealed to anymore.
+UPDATE key_pairs
+SET visibility_deleted_at = clock_timestamp(),
+    updated_at            = clock_timestamp()
+WHERE key_pairs.workspace_pk = $1
+  AND key_pairs.pk != $2
+  AND key_pairs.visibility_deleted_at IS NULL
+  AND NOT EXISTS (SELECT 1
+                  FROM encrypted_secrets
+                  WHERE encrypted_secrets.key_pair_pk = key_pairs.pk)
+RETURNING key_pairs.pk
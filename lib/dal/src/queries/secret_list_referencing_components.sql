@@ -0,0 +1,13 @@
+-- The components whose secret reference props hold the given secret's id.
+SELECT DISTINCT attribute_values.attribute_context_component_id AS component_id
+FROM attribute_values_v1($1, $2) AS attribute_values
+INNER JOIN props_v1($1, $2) AS props
+    ON props.id = attribute_values.attribute_context_prop_id
+       AND props.widget_kind = 'secretSelect'
+INNER JOIN func_binding_return_values_v1($1, $2) AS func_binding_return_values
+    ON func_binding_return_values.id = attribute_values.func_binding_return_value_id
+INNER JOIN components_v1($1, $2) AS components
+    ON components.id = attribute_values.attribute_context_component_id
+       AND components.visibility_deleted_at IS NULL
+WHERE func_binding_return_values.value = to_jsonb($3::text)
+ORDER BY component_id
@@ -0,0 +1,12 @@
+UPDATE webhook_pending_deliveries
+SET next_attempt_at = clock_timestamp() + make_interval(secs => $2),
+    updated_at      = clock_timestamp()
+WHERE webhook_pending_deliveries.pk IN (
+    SELECT pk
+    FROM webhook_pending_deliveries
+    WHERE next_attempt_at <= clock_timestamp()
+    ORDER BY next_attempt_at
+    LIMIT $1
+    FOR UPDATE SKIP LOCKED
+)
+RETURNING row_to_json(webhook_pending_deliveries.*) AS object
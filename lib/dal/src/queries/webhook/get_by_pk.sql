@@ -0,0 +1,5 @@
+SELECT row_to_json(webhooks.*) AS object
+FROM webhooks
+WHERE
+    webhooks.pk = $2
+    AND in_tenancy_v1($1, webhooks.tenancy_workspace_pk)
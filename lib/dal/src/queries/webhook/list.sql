@@ -0,0 +1,4 @@
+SELECT row_to_json(webhooks.*) AS object
+FROM webhooks
+WHERE in_tenancy_v1($1, webhooks.tenancy_workspace_pk)
+ORDER BY webhooks.created_at
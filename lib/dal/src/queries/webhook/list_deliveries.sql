@@ -0,0 +1,7 @@
+SELECT row_to_json(webhook_deliveries.*) AS object
+FROM webhook_deliveries
+WHERE
+    webhook_deliveries.webhook_pk = $2
+    AND in_tenancy_v1($1, webhook_deliveries.tenancy_workspace_pk)
+ORDER BY webhook_deliveries.created_at DESC
+LIMIT $3
@@ -0,0 +1,7 @@
+SELECT row_to_json(webhooks.*) AS object
+FROM webhooks
+WHERE
+    in_tenancy_v1($1, webhooks.tenancy_workspace_pk)
+    AND webhooks.enabled
+    AND $2::text = ANY(webhooks.event_kinds)
+ORDER BY webhooks.created_at
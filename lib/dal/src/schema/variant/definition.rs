@@ -699,6 +699,37 @@ impl PropDefinition {
                 },
                 map_key_funcs: None,
             },
+            PropSpec::Float {
+                name,
+                default_value,
+                validations,
+                func_unique_id,
+                inputs,
+                widget_kind,
+                widget_options,
+                hidden,
+                doc_link,
+            } => PropDefinition {
+                name,
+                kind: PropKind::Float,
+                doc_link_ref: None,
+                doc_link: doc_link.map(|l| l.to_string()),
+                children: vec![],
+                entry: None,
+                widget: PropWidgetDefinition::from_spec(widget_kind, widget_options),
+                value_from: ValueFrom::maybe_from_spec(
+                    inputs,
+                    func_unique_id,
+                    identity_func_unique_id,
+                ),
+                hidden,
+                validations,
+                default_value: match default_value {
+                    Some(dv) => Some(serde_json::to_value(dv)?),
+                    None => None,
+                },
+                map_key_funcs: None,
+            },
             PropSpec::Map {
                 name,
                 validations,
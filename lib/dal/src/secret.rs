@@ -19,36 +19,73 @@ use crate::{
     key_pair::KeyPairPk,
     pk,
     standard_model::{self, TypeHint},
-    standard_model_accessor, standard_model_accessor_ro, DalContext, HistoryEvent,
+    standard_model_accessor, standard_model_accessor_ro, ComponentId, DalContext, HistoryEvent,
     HistoryEventError, KeyPair, KeyPairError, StandardModel, StandardModelError, Timestamp,
-    Visibility,
+    Visibility, VisibilityError,
 };
 
+pub mod backend;
+pub mod definition;
+
+pub use backend::{
+    SecretBackend, SecretBackendError, SecretBackendKind, SecretBackends, SecretBackendsConfig,
+    SecretReference,
+};
+pub use definition::{SecretDefinition, SecretDefinitionId, SecretDefinitionPk};
+
 /// Error type for Secrets.
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum SecretError {
+    #[error("secret backend error: {0}")]
+    Backend(#[from] SecretBackendError),
     #[error("error when decrypting crypted secret")]
     DecryptionFailed,
+    #[error("secret definition {0} uses JSON Schema features that can't be checked: {1:?}")]
+    DefinitionSchemaUnsupported(String, Vec<String>),
     #[error("error deserializing message: {0}")]
     DeserializeMessage(#[source] serde_json::Error),
+    #[error("error deserializing secret reference: {0}")]
+    DeserializeReference(#[source] serde_json::Error),
     #[error("history event error: {0}")]
     HistoryEvent(#[from] HistoryEventError),
+    #[error("secret {0} is referenced by components: {1:?}")]
+    InUse(SecretId, Vec<ComponentId>),
     #[error("key pair error: {0}")]
     KeyPair(#[from] KeyPairError),
+    #[error("secret was sealed to key pair {0}, but is recorded against key pair {1}")]
+    KeyPairMismatch(KeyPairPk, KeyPairPk),
     #[error("key pair not found for secret")]
     KeyPairNotFound,
+    #[error("secret does not match the schema for {0}: {1:?}")]
+    MessageFailsSchema(SecretKind, Vec<String>),
+    #[error("secret not found: {0}")]
+    NotFound(SecretId),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
+    #[error("secret {0} references an external backend but belongs to no workspace")]
+    ReferenceWithoutWorkspace(SecretId),
+    #[error("no secret definition found for kind {0}")]
+    SecretDefinitionNotFound(SecretKind),
+    #[error("error serializing message: {0}")]
+    SerializeMessage(#[source] serde_json::Error),
     #[error("standard model error: {0}")]
     StandardModelError(#[from] StandardModelError),
     #[error("transactions error: {0}")]
     Transactions(#[from] TransactionsError),
+    #[error("visibility error: {0}")]
+    Visibility(#[from] VisibilityError),
 }
 
 /// Result type for Secrets.
 pub type SecretResult<T> = Result<T, SecretError>;
 
+const ENCRYPTED_SECRET_LIST_FOR_REENCRYPTION: &str =
+    include_str!("queries/encrypted_secret_list_for_reencryption.sql");
+const ENCRYPTED_SECRET_REENCRYPT: &str = include_str!("queries/encrypted_secret_reencrypt.sql");
+const SECRET_LIST_REFERENCING_COMPONENTS: &str =
+    include_str!("queries/secret_list_referencing_components.sql");
+
 pk!(SecretPk);
 pk!(SecretId);
 
@@ -64,6 +101,8 @@ pub struct Secret {
     object_type: SecretObjectType,
     key_pair_pk: KeyPairPk,
     kind: SecretKind,
+    #[serde(default)]
+    backend: SecretBackendKind,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -117,10 +156,71 @@ impl Secret {
     // Once created, these object fields are to be considered immutable
     standard_model_accessor_ro!(object_type, SecretObjectType);
     standard_model_accessor_ro!(kind, SecretKind);
+    standard_model_accessor_ro!(backend, SecretBackendKind);
 
     pub async fn key_pair(&self, ctx: &DalContext) -> SecretResult<KeyPair> {
         Ok(KeyPair::get_by_pk(ctx, self.key_pair_pk).await?)
     }
+
+    /// Lists the [`Components`](crate::Component) that refer to this secret through a secret
+    /// reference [`Prop`](crate::Prop).
+    pub async fn referencing_components(&self, ctx: &DalContext) -> SecretResult<Vec<ComponentId>> {
+        self.referencing_components_in(ctx, ctx.visibility()).await
+    }
+
+    async fn referencing_components_in(
+        &self,
+        ctx: &DalContext,
+        visibility: &Visibility,
+    ) -> SecretResult<Vec<ComponentId>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                SECRET_LIST_REFERENCING_COMPONENTS,
+                &[ctx.tenancy(), visibility, &self.id.to_string()],
+            )
+            .await?;
+
+        let mut component_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            let component_id: ComponentId = row.try_get("component_id")?;
+            component_ids.push(component_id);
+        }
+        Ok(component_ids)
+    }
+
+    /// Lists the [`Components`](crate::Component) that refer to this secret on _head_ or in any
+    /// open change set of the workspace.
+    pub async fn referencing_components_in_workspace(
+        &self,
+        ctx: &DalContext,
+    ) -> SecretResult<Vec<ComponentId>> {
+        let mut component_ids = Vec::new();
+        for visibility in Visibility::list_open_in_workspace(ctx).await? {
+            component_ids.extend(self.referencing_components_in(ctx, &visibility).await?);
+        }
+        component_ids.sort();
+        component_ids.dedup();
+        Ok(component_ids)
+    }
+
+    /// Deletes the secret. Secrets still referenced by a [`Component`](crate::Component), whether
+    /// on _head_ or in any open change set, cannot be deleted.
+    pub async fn delete(self, ctx: &DalContext) -> SecretResult<()> {
+        let component_ids = self.referencing_components_in_workspace(ctx).await?;
+        if !component_ids.is_empty() {
+            return Err(SecretError::InUse(self.id, component_ids));
+        }
+
+        let mut encrypted_secret = EncryptedSecret::get_by_id(ctx, &self.id)
+            .await?
+            .ok_or(SecretError::NotFound(self.id))?;
+        encrypted_secret.delete_by_id(ctx).await?;
+
+        Ok(())
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -130,6 +230,7 @@ pub struct SecretView {
     pub name: String,
     pub object_type: SecretObjectType,
     pub kind: SecretKind,
+    pub backend: SecretBackendKind,
 }
 
 impl From<Secret> for SecretView {
@@ -138,7 +239,8 @@ impl From<Secret> for SecretView {
             id: *secret.id(),
             name: secret.name().to_owned(),
             object_type: *secret.object_type(),
-            kind: *secret.kind(),
+            kind: secret.kind().clone(),
+            backend: *secret.backend(),
         }
     }
 }
@@ -162,6 +264,8 @@ pub struct EncryptedSecret {
     crypted: Vec<u8>,
     version: SecretVersion,
     algorithm: SecretAlgorithm,
+    #[serde(default)]
+    backend: SecretBackendKind,
     #[serde(flatten)]
     tenancy: Tenancy,
     #[serde(flatten)]
@@ -180,6 +284,7 @@ impl fmt::Debug for EncryptedSecret {
             .field("kind", &self.kind)
             .field("version", &self.version)
             .field("algorithm", &self.algorithm)
+            .field("backend", &self.backend)
             .field("tenancy", &self.tenancy)
             .field("timestamp", &self.timestamp)
             .field("visibility", &self.visibility)
@@ -208,6 +313,37 @@ impl EncryptedSecret {
         key_pair_pk: KeyPairPk,
         version: SecretVersion,
         algorithm: SecretAlgorithm,
+    ) -> SecretResult<Secret> {
+        Self::new_with_backend(
+            ctx,
+            name,
+            object_type,
+            kind,
+            crypted,
+            key_pair_pk,
+            version,
+            algorithm,
+            SecretBackendKind::Database,
+        )
+        .await
+    }
+
+    /// Creates a new encypted secret whose value lives in the given [`SecretBackendKind`] and
+    /// returns a corresponding [`Secret`] representation.
+    ///
+    /// For any backend other than [`SecretBackendKind::Database`], the crypted payload must be a
+    /// sealed [`SecretReference`] rather than the secret's value.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new_with_backend(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        object_type: SecretObjectType,
+        kind: SecretKind,
+        crypted: &[u8],
+        key_pair_pk: KeyPairPk,
+        version: SecretVersion,
+        algorithm: SecretAlgorithm,
+        backend: SecretBackendKind,
     ) -> SecretResult<Secret> {
         let name = name.as_ref();
 
@@ -216,7 +352,7 @@ impl EncryptedSecret {
             .await?
             .pg()
             .query_one(
-                "SELECT object FROM encrypted_secret_create_v1($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                "SELECT object FROM encrypted_secret_create_v1($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
                 &[
                     ctx.tenancy(),
                     ctx.visibility(),
@@ -227,6 +363,7 @@ impl EncryptedSecret {
                     &version.as_ref(),
                     &algorithm.as_ref(),
                     &key_pair_pk,
+                    &backend.as_ref(),
                 ],
             )
             .await?;
@@ -242,36 +379,203 @@ impl EncryptedSecret {
     standard_model_accessor_ro!(kind, SecretKind);
     standard_model_accessor_ro!(version, SecretVersion);
     standard_model_accessor_ro!(algorithm, SecretAlgorithm);
+    standard_model_accessor_ro!(backend, SecretBackendKind);
+
+    pub fn key_pair_pk(&self) -> KeyPairPk {
+        self.key_pair_pk
+    }
 
     /// Decrypts the encrypted secret with its associated [`KeyPair`] and returns a
     /// [`DecryptedSecret`].
+    ///
+    /// If the secret's value is held outside of the database, the decrypted [`SecretReference`]
+    /// is resolved through the [`DalContext`]'s [`SecretBackends`], within the scope of the
+    /// workspace that owns the secret.
     pub async fn decrypt(self, ctx: &DalContext) -> SecretResult<DecryptedSecret> {
         let key_pair = self.key_pair(ctx).await?;
-        self.into_decrypted(key_pair.public_key(), key_pair.secret_key())
+        let backend = self.backend;
+        let workspace_pk = self.tenancy.workspace_pk();
+        let id = self.id;
+        let mut decrypted = self.into_decrypted(key_pair.public_key(), key_pair.secret_key())?;
+
+        if backend != SecretBackendKind::Database {
+            let workspace_pk = workspace_pk.ok_or(SecretError::ReferenceWithoutWorkspace(id))?;
+            let reference: SecretReference = serde_json::from_value(decrypted.message)
+                .map_err(SecretError::DeserializeReference)?;
+            decrypted.message = ctx
+                .secret_backends()
+                .resolve(backend, workspace_pk, &reference)
+                .await?;
+        }
+
+        Ok(decrypted)
+    }
+
+    /// Checks that the [`SecretReference`] of a secret held in an external [`SecretBackend`] stays
+    /// within the part of the backend that belongs to the secret's workspace. Secrets held in the
+    /// database are always fine.
+    ///
+    /// The same check is made again whenever the secret is resolved.
+    pub async fn validate_reference(&self, ctx: &DalContext) -> SecretResult<()> {
+        if self.backend == SecretBackendKind::Database {
+            return Ok(());
+        }
+        let workspace_pk = self
+            .tenancy
+            .workspace_pk()
+            .ok_or(SecretError::ReferenceWithoutWorkspace(self.id))?;
+
+        let key_pair = self.key_pair(ctx).await?;
+        let reference: SecretReference =
+            serde_json::from_value(self.open(key_pair.public_key(), key_pair.secret_key())?)
+                .map_err(SecretError::DeserializeReference)?;
+        ctx.secret_backends()
+            .check(self.backend, workspace_pk, &reference)?;
+        Ok(())
+    }
+
+    /// Checks the secret's message against the JSON schema of the [`SecretDefinition`] for its
+    /// [`SecretKind`].
+    ///
+    /// Built in kinds are only checked if a definition with the same name has been installed,
+    /// while [`SecretKind::Defined`] kinds must have one. Secrets held in an external
+    /// [`SecretBackend`] are not checked, since their value is not available until they are used.
+    pub async fn validate_against_definition(&self, ctx: &DalContext) -> SecretResult<()> {
+        if self.backend != SecretBackendKind::Database {
+            return Ok(());
+        }
+
+        let definition = match SecretDefinition::find_by_name(ctx, self.kind.as_ref()).await? {
+            Some(definition) => definition,
+            None if self.kind.is_builtin() => return Ok(()),
+            None => return Err(SecretError::SecretDefinitionNotFound(self.kind.clone())),
+        };
+
+        let key_pair = self.key_pair(ctx).await?;
+        let message = self.open(key_pair.public_key(), key_pair.secret_key())?;
+        let errors = definition.validate(&message);
+        if !errors.is_empty() {
+            return Err(SecretError::MessageFailsSchema(self.kind.clone(), errors));
+        }
+
+        Ok(())
     }
 
     fn into_decrypted(self, pkey: &PublicKey, skey: &SecretKey) -> SecretResult<DecryptedSecret> {
+        let message = self.open(pkey, skey)?;
+        Ok(DecryptedSecret {
+            name: self.name,
+            object_type: self.object_type,
+            secret_kind: self.kind,
+            message,
+        })
+    }
+
+    /// Opens the crypted payload, returning the sealed message.
+    fn open(&self, pkey: &PublicKey, skey: &SecretKey) -> SecretResult<Value> {
         // Explicitly match on (version, algorithm) tuple to ensure that any new
         // versions/algorithms will trigger a compilation failure
         match (self.version, self.algorithm) {
-            (SecretVersion::V1, SecretAlgorithm::Sealedbox) => Ok(DecryptedSecret {
-                name: self.name,
-                object_type: self.object_type,
-                secret_kind: self.kind,
-                message: serde_json::from_slice(
+            (SecretVersion::V1, SecretAlgorithm::Sealedbox) => serde_json::from_slice(
+                &sealedbox::open(&self.crypted, pkey, skey)
+                    .map_err(|_| SecretError::DecryptionFailed)?,
+            )
+            .map_err(SecretError::DeserializeMessage),
+            (SecretVersion::V2, SecretAlgorithm::Sealedbox) => {
+                let envelope: SealedMessageV2 = serde_json::from_slice(
                     &sealedbox::open(&self.crypted, pkey, skey)
                         .map_err(|_| SecretError::DecryptionFailed)?,
                 )
-                .map_err(SecretError::DeserializeMessage)?,
-            }),
+                .map_err(SecretError::DeserializeMessage)?;
+                if envelope.key_pair_pk != self.key_pair_pk {
+                    return Err(SecretError::KeyPairMismatch(
+                        envelope.key_pair_pk,
+                        self.key_pair_pk,
+                    ));
+                }
+                Ok(envelope.message)
+            }
         }
     }
 
+    /// Re-seals the secret to the given [`KeyPair`] as a [`SecretVersion::V2`] message. Every row
+    /// of the secret is updated in place, regardless of the [`Visibility`] it belongs to, since
+    /// they all have to move off of the old key pair before it can be retired.
+    ///
+    /// For secrets held in an external [`SecretBackend`], only the sealed [`SecretReference`] is
+    /// re-encrypted; the value itself never passes through here.
+    pub(crate) async fn reencrypt(
+        &mut self,
+        ctx: &DalContext,
+        current: &KeyPair,
+        new: &KeyPair,
+    ) -> SecretResult<()> {
+        let message = self.open(current.public_key(), current.secret_key())?;
+        let envelope = SealedMessageV2 {
+            key_pair_pk: new.pk(),
+            message,
+        };
+        let crypted = sealedbox::seal(
+            &serde_json::to_vec(&envelope).map_err(SecretError::SerializeMessage)?,
+            new.public_key(),
+        );
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                ENCRYPTED_SECRET_REENCRYPT,
+                &[
+                    &self.pk,
+                    &encode_crypted(&crypted),
+                    &SecretVersion::V2.as_ref(),
+                    &new.pk(),
+                ],
+            )
+            .await?;
+
+        self.crypted = crypted;
+        self.version = SecretVersion::V2;
+        self.key_pair_pk = new.pk();
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+
+        Ok(())
+    }
+
+    /// Lists every row of every secret in the workspace that is not sealed to the given
+    /// [`KeyPair`], across all change sets and including deleted rows.
+    pub(crate) async fn list_for_reencryption(
+        ctx: &DalContext,
+        key_pair_pk: KeyPairPk,
+    ) -> SecretResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                ENCRYPTED_SECRET_LIST_FOR_REENCRYPTION,
+                &[&ctx.tenancy().workspace_pk(), &key_pair_pk],
+            )
+            .await?;
+        Ok(standard_model::objects_from_rows(rows)?)
+    }
+
     pub async fn key_pair(&self, ctx: &DalContext) -> SecretResult<KeyPair> {
         Ok(KeyPair::get_by_pk(ctx, self.key_pair_pk).await?)
     }
 }
 
+/// The payload sealed in a [`SecretVersion::V2`] secret. The key pair is sealed alongside the
+/// message so that a secret recorded against the wrong key pair is caught rather than silently
+/// failing to open.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SealedMessageV2 {
+    key_pair_pk: KeyPairPk,
+    message: Value,
+}
+
 /// A secret that has been decrypted.
 ///
 /// This type is returned by calling `EncryptedSecret.decrypt(&txn).await?` which contains the raw
@@ -305,7 +609,7 @@ impl DecryptedSecret {
 
     /// Gets the decrypted secret's kind.
     pub fn kind(&self) -> SecretKind {
-        self.secret_kind
+        self.secret_kind.clone()
     }
 }
 
@@ -329,6 +633,10 @@ impl fmt::Debug for DecryptedSecret {
 pub enum SecretVersion {
     /// Version 1 of the encryption
     V1,
+    /// Version 2 of the encryption, where the message is sealed together with the pk of the
+    /// [`KeyPair`] it was sealed to. Secrets are moved to this version when the key pair is
+    /// rotated.
+    V2,
 }
 
 impl Default for SecretVersion {
@@ -368,23 +676,83 @@ pub enum SecretObjectType {
 }
 
 /// The kind of a secret.
+///
+/// Besides the built in kinds, a kind can name any [`SecretDefinition`] (usually installed from a
+/// package), whose JSON schema describes the fields a secret of that kind has.
 #[remain::sorted]
-#[derive(
-    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, PartialEq, Serialize,
-)]
-#[serde(rename_all = "camelCase")]
-#[strum(serialize_all = "camelCase")]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(from = "String", into = "String")]
 pub enum SecretKind {
     /// An AWS access key
     AwsAccessKey,
     /// An Azure service principal
     AzureServicePrincipal,
+    /// A kind described by the [`SecretDefinition`] with this name
+    Defined(String),
     /// A Docker Hub credential
     DockerHub,
     /// A Helm repository credential
     HelmRepo,
 }
 
+impl SecretKind {
+    /// Returns `true` if the kind is one of the kinds built into SI rather than a
+    /// [`SecretDefinition`].
+    pub fn is_builtin(&self) -> bool {
+        !matches!(self, Self::Defined(_))
+    }
+}
+
+impl AsRef<str> for SecretKind {
+    fn as_ref(&self) -> &str {
+        match self {
+            Self::AwsAccessKey => "awsAccessKey",
+            Self::AzureServicePrincipal => "azureServicePrincipal",
+            Self::Defined(name) => name.as_str(),
+            Self::DockerHub => "dockerHub",
+            Self::HelmRepo => "helmRepo",
+        }
+    }
+}
+
+impl fmt::Display for SecretKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl From<String> for SecretKind {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "awsAccessKey" => Self::AwsAccessKey,
+            "azureServicePrincipal" => Self::AzureServicePrincipal,
+            "dockerHub" => Self::DockerHub,
+            "helmRepo" => Self::HelmRepo,
+            _ => Self::Defined(value),
+        }
+    }
+}
+
+impl From<&str> for SecretKind {
+    fn from(value: &str) -> Self {
+        value.to_string().into()
+    }
+}
+
+impl From<SecretKind> for String {
+    fn from(value: SecretKind) -> Self {
+        value.as_ref().to_string()
+    }
+}
+
+impl std::str::FromStr for SecretKind {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
 fn encode_crypted(crypted: &[u8]) -> String {
     general_purpose::STANDARD_NO_PAD.encode(crypted)
 }
@@ -445,6 +813,7 @@ mod tests {
                 crypted,
                 version: Default::default(),
                 algorithm: Default::default(),
+                backend: Default::default(),
                 tenancy: Tenancy::new(wid),
                 timestamp: Timestamp::now(),
                 visibility: Visibility::new_head(false),
@@ -486,6 +855,50 @@ mod tests {
             assert_eq!(SecretKind::DockerHub, decrypted.secret_kind);
             assert_eq!(message, decrypted.message);
         }
+
+        #[test]
+        fn into_decrypted_v2() {
+            sodiumoxide::init().expect("crypto failed to init");
+            let (pkey, skey) = box_::gen_keypair();
+            let key_pair_pk = KeyPairPk::generate();
+
+            let message =
+                serde_json::json!({"username": "Joan Jett", "password": "Bad Reputation"});
+            let crypted = crypt(
+                &SealedMessageV2 {
+                    key_pair_pk,
+                    message: message.clone(),
+                },
+                &pkey,
+            );
+
+            let mut encrypted = encrypted_secret(
+                "joan-jett",
+                SecretObjectType::Credential,
+                SecretKind::DockerHub,
+                crypted,
+                WorkspacePk::NONE,
+            );
+            encrypted.version = SecretVersion::V2;
+            encrypted.key_pair_pk = key_pair_pk;
+
+            let decrypted = encrypted
+                .clone()
+                .into_decrypted(&pkey, &skey)
+                .expect("could not decrypt secret");
+            assert_eq!(message, decrypted.message);
+
+            // A V2 secret recorded against a different key pair than it was sealed to is refused
+            encrypted.key_pair_pk = KeyPairPk::generate();
+            match encrypted.into_decrypted(&pkey, &skey) {
+                Err(SecretError::KeyPairMismatch(sealed_to, recorded)) => {
+                    assert_eq!(key_pair_pk, sealed_to);
+                    assert_ne!(key_pair_pk, recorded);
+                }
+                Err(err) => panic!("unexpected error: {err}"),
+                Ok(_) => panic!("decrypt should not succeed"),
+            }
+        }
     }
 
     mod secret_object_type {
@@ -548,8 +961,8 @@ mod tests {
             r#"{"kind":"dockerHub"}"#
         }
 
-        fn invalid() -> &'static str {
-            r#"{"kind":"nope"}"#
+        fn defined() -> &'static str {
+            r#"{"kind":"githubToken"}"#
         }
 
         fn object() -> Object {
@@ -575,10 +988,14 @@ mod tests {
         }
 
         #[test]
-        fn deserialize_invalid() {
-            if serde_json::from_str::<Object>(invalid()).is_ok() {
-                panic!("deserialize should not succeed")
-            }
+        fn deserialize_defined() {
+            let object: Object = serde_json::from_str(defined()).expect("failed to deserialize");
+            assert_eq!(SecretKind::Defined("githubToken".to_string()), object.kind);
+            assert!(!object.kind.is_builtin());
+            assert_eq!(
+                defined(),
+                serde_json::to_string(&object).expect("failed to serialize")
+            );
         }
     }
 
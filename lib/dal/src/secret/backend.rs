@@ -0,0 +1,655 @@
+//! This module contains [`SecretBackend`], the abstraction over _where_ the value of a
+//! [`Secret`](crate::Secret) lives.
+//!
+//! By default, the value of a secret is sealed and stored in the database alongside the secret
+//! itself ([`SecretBackendKind::Database`]). For every other backend, the database only holds a
+//! sealed [`SecretReference`] and the value is fetched from the external store when the secret is
+//! decrypted for function execution.
+//!
+//! References are scoped to the workspace that owns the secret: a Vault path or file path is
+//! relative to the workspace's own directory (`workspaces/<workspace pk>/`) and an environment
+//! variable must start with the configured prefix followed by the workspace pk. The scope is
+//! checked when a secret is created and again whenever it is resolved.
+//!
+//! References are resolved in the dal, when the secret is decrypted, rather than in veritech:
+//! only the dal knows which workspace a secret belongs to, and veritech forwards requests from
+//! every workspace to the same cyclone instances. The resolved value is sealed for cyclone
+//! exactly like a database-backed secret, so it is never sent over NATS in the clear.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Component as PathComponent, Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum::{AsRefStr, Display, EnumString};
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::WorkspacePk;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum SecretBackendError {
+    #[error("secret backend not configured: {0}")]
+    BackendNotConfigured(SecretBackendKind),
+    #[error("database-backed secrets are not resolved through a secret backend")]
+    DatabaseBackendHasNoResolver,
+    #[error("environment variable {0} is outside of the allowed prefix {1}")]
+    EnvironmentVariableNotAllowed(String, String),
+    #[error("environment variable not found: {0}")]
+    EnvironmentVariableNotFound(String),
+    #[error("invalid secret reference path: {0}")]
+    InvalidReferencePath(String),
+    #[error("invalid vault address: {0}")]
+    InvalidVaultAddress(String),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("key {0} not found in secret at {1}")]
+    KeyNotFound(String, String),
+    #[error("error deserializing secret: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("url parse error: {0}")]
+    UrlParse(#[from] url::ParseError),
+    #[error("vault request failed: {0}")]
+    Vault(#[from] reqwest::Error),
+    #[error("vault response missing data for {0}")]
+    VaultMissingData(String),
+}
+
+pub type SecretBackendResult<T> = Result<T, SecretBackendError>;
+
+/// The kind of store that holds the value of a [`Secret`](crate::Secret).
+#[remain::sorted]
+#[derive(
+    AsRefStr, Clone, Copy, Debug, Deserialize, Display, EnumString, Eq, Hash, PartialEq, Serialize,
+)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum SecretBackendKind {
+    /// The value is sealed and stored in the database (the default)
+    Database,
+    /// The value is read from an environment variable of the executing process
+    EnvironmentVariable,
+    /// The value is read from a JSON file beneath a configured root directory
+    File,
+    /// The value is read from a HashiCorp Vault KV (version 2) secrets engine
+    VaultKv,
+}
+
+impl Default for SecretBackendKind {
+    fn default() -> Self {
+        Self::Database
+    }
+}
+
+/// A pointer to a secret value held outside of SI. This is what gets sealed and stored for
+/// secrets whose [`SecretBackendKind`] is not [`SecretBackendKind::Database`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SecretReference {
+    /// Where the secret lives in the backend: a Vault KV path, a file path relative to the root
+    /// or an environment variable name.
+    pub path: String,
+    /// If set, only this key of the resolved JSON object is used as the secret's value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+impl SecretReference {
+    fn select_key(&self, value: Value) -> SecretBackendResult<Value> {
+        match &self.key {
+            None => Ok(value),
+            Some(key) => value
+                .get(key)
+                .cloned()
+                .ok_or_else(|| SecretBackendError::KeyNotFound(key.clone(), self.path.clone())),
+        }
+    }
+}
+
+/// Returns the reference's path if it is a non-empty relative path made only of plain segments,
+/// so that it can't climb out of the directory it is joined to.
+fn plain_relative_path(reference: &SecretReference) -> SecretBackendResult<&Path> {
+    let relative = Path::new(&reference.path);
+    if reference.path.is_empty()
+        || !relative
+            .components()
+            .all(|component| matches!(component, PathComponent::Normal(_)))
+    {
+        return Err(SecretBackendError::InvalidReferencePath(
+            reference.path.clone(),
+        ));
+    }
+    Ok(relative)
+}
+
+/// A store that can resolve a [`SecretReference`] into the secret's value.
+#[async_trait]
+pub trait SecretBackend: fmt::Debug + Send + Sync {
+    fn kind(&self) -> SecretBackendKind;
+
+    /// Checks that the reference stays within the part of the backend that belongs to the
+    /// workspace.
+    fn check(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<()>;
+
+    /// Resolves the reference on behalf of the workspace. Implementations must only ever read
+    /// from the workspace's part of the backend.
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<Value>;
+}
+
+/// Resolves references from a HashiCorp Vault KV (version 2) secrets engine.
+#[derive(Clone)]
+pub struct VaultKvSecretBackend {
+    address: url::Url,
+    mount: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl VaultKvSecretBackend {
+    pub fn new(
+        address: impl AsRef<str>,
+        mount: impl Into<String>,
+        token: impl Into<String>,
+    ) -> SecretBackendResult<Self> {
+        let address = url::Url::parse(address.as_ref())?;
+        if address.cannot_be_a_base() {
+            return Err(SecretBackendError::InvalidVaultAddress(address.to_string()));
+        }
+        Ok(Self {
+            address,
+            mount: mount.into(),
+            token: token.into(),
+            client: reqwest::Client::new(),
+        })
+    }
+}
+
+impl fmt::Debug for VaultKvSecretBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VaultKvSecretBackend")
+            .field("address", &self.address.as_str())
+            .field("mount", &self.mount)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl SecretBackend for VaultKvSecretBackend {
+    fn kind(&self) -> SecretBackendKind {
+        SecretBackendKind::VaultKv
+    }
+
+    fn check(
+        &self,
+        _workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<()> {
+        plain_relative_path(reference).map(|_| ())
+    }
+
+    #[instrument(skip_all, fields(path = %reference.path))]
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<Value> {
+        let relative = plain_relative_path(reference)?;
+
+        // Each segment is percent-encoded on its own, so nothing in the reference can be read as
+        // a dot segment once it is part of the url.
+        let workspace = workspace_pk.to_string();
+        let mut url = self.address.clone();
+        url.path_segments_mut()
+            .map_err(|_| SecretBackendError::InvalidVaultAddress(self.address.to_string()))?
+            .pop_if_empty()
+            .extend(["v1", &self.mount, "data", "workspaces", &workspace])
+            .extend(relative.iter().map(|segment| segment.to_string_lossy()));
+
+        let mut response: Value = self
+            .client
+            .get(url)
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        // KV version 2 nests the secret's data under "data.data".
+        let data = response
+            .pointer_mut("/data/data")
+            .map(Value::take)
+            .ok_or_else(|| SecretBackendError::VaultMissingData(reference.path.clone()))?;
+        reference.select_key(data)
+    }
+}
+
+/// Resolves references to JSON files beneath a root directory.
+#[derive(Clone, Debug)]
+pub struct FileSecretBackend {
+    root: PathBuf,
+}
+
+impl FileSecretBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl SecretBackend for FileSecretBackend {
+    fn kind(&self) -> SecretBackendKind {
+        SecretBackendKind::File
+    }
+
+    fn check(
+        &self,
+        _workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<()> {
+        plain_relative_path(reference).map(|_| ())
+    }
+
+    #[instrument(skip_all, fields(path = %reference.path))]
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<Value> {
+        let relative = plain_relative_path(reference)?;
+
+        // A symlink beneath the workspace's directory could still point anywhere, so the
+        // resolved path has to land inside of it too.
+        let workspace_root =
+            tokio::fs::canonicalize(self.root.join("workspaces").join(workspace_pk.to_string()))
+                .await?;
+        let path = tokio::fs::canonicalize(workspace_root.join(relative)).await?;
+        if !path.starts_with(&workspace_root) {
+            return Err(SecretBackendError::InvalidReferencePath(
+                reference.path.clone(),
+            ));
+        }
+
+        let contents = tokio::fs::read(path).await?;
+        reference.select_key(serde_json::from_slice(&contents)?)
+    }
+}
+
+/// Resolves references to environment variables of the executing process. Only variables
+/// starting with the configured prefix followed by the workspace pk (and an underscore) can be
+/// read, so that a secret can't point at, say, the database password or another workspace's
+/// variables.
+#[derive(Clone, Debug)]
+pub struct EnvironmentVariableSecretBackend {
+    prefix: String,
+}
+
+impl EnvironmentVariableSecretBackend {
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    fn workspace_prefix(&self, workspace_pk: WorkspacePk) -> String {
+        format!("{}{workspace_pk}_", self.prefix)
+    }
+}
+
+#[async_trait]
+impl SecretBackend for EnvironmentVariableSecretBackend {
+    fn kind(&self) -> SecretBackendKind {
+        SecretBackendKind::EnvironmentVariable
+    }
+
+    fn check(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<()> {
+        let prefix = self.workspace_prefix(workspace_pk);
+        if !reference.path.starts_with(&prefix) {
+            return Err(SecretBackendError::EnvironmentVariableNotAllowed(
+                reference.path.clone(),
+                prefix,
+            ));
+        }
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(path = %reference.path))]
+    async fn resolve(
+        &self,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<Value> {
+        self.check(workspace_pk, reference)?;
+
+        let raw = std::env::var(&reference.path)
+            .map_err(|_| SecretBackendError::EnvironmentVariableNotFound(reference.path.clone()))?;
+        // Structured secrets are stored as JSON, but a bare string is a perfectly fine secret too.
+        let value = serde_json::from_str(&raw).unwrap_or(Value::String(raw));
+        reference.select_key(value)
+    }
+}
+
+/// Configuration for the available [`SecretBackends`]. Backends that are not configured are not
+/// available.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct SecretBackendsConfig {
+    #[serde(default)]
+    pub vault_kv: Option<VaultKvSecretBackendConfig>,
+    #[serde(default)]
+    pub file: Option<FileSecretBackendConfig>,
+    #[serde(default)]
+    pub environment_variable: Option<EnvironmentVariableSecretBackendConfig>,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+pub struct VaultKvSecretBackendConfig {
+    pub address: String,
+    #[serde(default = "default_vault_kv_mount")]
+    pub mount: String,
+    pub token: String,
+}
+
+impl fmt::Debug for VaultKvSecretBackendConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VaultKvSecretBackendConfig")
+            .field("address", &self.address)
+            .field("mount", &self.mount)
+            .finish_non_exhaustive()
+    }
+}
+
+fn default_vault_kv_mount() -> String {
+    "secret".to_string()
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FileSecretBackendConfig {
+    pub root: PathBuf,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct EnvironmentVariableSecretBackendConfig {
+    #[serde(default = "default_environment_variable_prefix")]
+    pub prefix: String,
+}
+
+fn default_environment_variable_prefix() -> String {
+    "SI_SECRET_".to_string()
+}
+
+impl SecretBackendsConfig {
+    pub fn build(&self) -> SecretBackendResult<SecretBackends> {
+        let mut backends = SecretBackends::default();
+        if let Some(config) = &self.vault_kv {
+            backends.insert(Arc::new(VaultKvSecretBackend::new(
+                &config.address,
+                &config.mount,
+                &config.token,
+            )?));
+        }
+        if let Some(config) = &self.file {
+            backends.insert(Arc::new(FileSecretBackend::new(&config.root)));
+        }
+        if let Some(config) = &self.environment_variable {
+            backends.insert(Arc::new(EnvironmentVariableSecretBackend::new(
+                &config.prefix,
+            )));
+        }
+        Ok(backends)
+    }
+}
+
+/// The set of [`SecretBackends`](SecretBackend) available to a process, keyed by kind.
+#[derive(Clone, Debug, Default)]
+pub struct SecretBackends {
+    backends: HashMap<SecretBackendKind, Arc<dyn SecretBackend>>,
+}
+
+impl SecretBackends {
+    /// Adds a backend, replacing any existing backend of the same kind.
+    pub fn insert(&mut self, backend: Arc<dyn SecretBackend>) {
+        self.backends.insert(backend.kind(), backend);
+    }
+
+    fn get(&self, kind: SecretBackendKind) -> SecretBackendResult<&Arc<dyn SecretBackend>> {
+        if kind == SecretBackendKind::Database {
+            return Err(SecretBackendError::DatabaseBackendHasNoResolver);
+        }
+        self.backends
+            .get(&kind)
+            .ok_or(SecretBackendError::BackendNotConfigured(kind))
+    }
+
+    /// Check that a [`SecretReference`] stays within the workspace's part of the backend of the
+    /// given kind.
+    pub fn check(
+        &self,
+        kind: SecretBackendKind,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<()> {
+        self.get(kind)?.check(workspace_pk, reference)
+    }
+
+    /// Resolve a [`SecretReference`] on behalf of a workspace with the backend of the given kind.
+    pub async fn resolve(
+        &self,
+        kind: SecretBackendKind,
+        workspace_pk: WorkspacePk,
+        reference: &SecretReference,
+    ) -> SecretBackendResult<Value> {
+        let backend = self.get(kind)?;
+        backend.check(workspace_pk, reference)?;
+        backend.resolve(workspace_pk, reference).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn reference(path: &str, key: Option<&str>) -> SecretReference {
+        SecretReference {
+            path: path.to_string(),
+            key: key.map(ToString::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn file_backend_resolves_within_workspace() {
+        let workspace_pk = WorkspacePk::generate();
+        let other_workspace_pk = WorkspacePk::generate();
+        let root = tempfile::tempdir().expect("could not create tempdir");
+        let workspace_root = root
+            .path()
+            .join("workspaces")
+            .join(workspace_pk.to_string());
+        let other_workspace_root = root
+            .path()
+            .join("workspaces")
+            .join(other_workspace_pk.to_string());
+        std::fs::create_dir_all(workspace_root.join("aws")).expect("could not create dir");
+        std::fs::create_dir_all(&other_workspace_root).expect("could not create dir");
+        std::fs::write(
+            workspace_root.join("aws/prod.json"),
+            r#"{"accessKeyId":"AKIA","secretAccessKey":"shh"}"#,
+        )
+        .expect("could not write secret");
+        std::fs::write(
+            other_workspace_root.join("theirs.json"),
+            r#"{"secretAccessKey":"not yours"}"#,
+        )
+        .expect("could not write secret");
+
+        let backend = FileSecretBackend::new(root.path());
+        let value = backend
+            .resolve(
+                workspace_pk,
+                &reference("aws/prod.json", Some("secretAccessKey")),
+            )
+            .await
+            .expect("could not resolve");
+        assert_eq!(Value::String("shh".to_string()), value);
+
+        let err = backend
+            .resolve(workspace_pk, &reference("../etc/passwd", None))
+            .await
+            .expect_err("should refuse to leave the workspace");
+        assert!(matches!(err, SecretBackendError::InvalidReferencePath(_)));
+
+        // Another workspace's secrets are out of reach, even through a symlink
+        std::os::unix::fs::symlink(&other_workspace_root, workspace_root.join("escape"))
+            .expect("could not create symlink");
+        let err = backend
+            .resolve(workspace_pk, &reference("escape/theirs.json", None))
+            .await
+            .expect_err("should refuse to follow a symlink out of the workspace");
+        assert!(matches!(err, SecretBackendError::InvalidReferencePath(_)));
+        backend
+            .resolve(other_workspace_pk, &reference("aws/prod.json", None))
+            .await
+            .expect_err("should not find another workspace's secret");
+    }
+
+    #[tokio::test]
+    async fn environment_variable_backend_respects_workspace_prefix() {
+        let workspace_pk = WorkspacePk::generate();
+        let other_workspace_pk = WorkspacePk::generate();
+        let datadog = format!("SI_SECRET_{workspace_pk}_DATADOG");
+        let plain = format!("SI_SECRET_{workspace_pk}_PLAIN");
+        let theirs = format!("SI_SECRET_{other_workspace_pk}_DATADOG");
+        std::env::set_var(&datadog, r#"{"apiKey":"dd"}"#);
+        std::env::set_var(&plain, "just-a-string");
+        std::env::set_var(&theirs, "not yours");
+
+        let backend = EnvironmentVariableSecretBackend::new("SI_SECRET_");
+        assert_eq!(
+            Value::String("dd".to_string()),
+            backend
+                .resolve(workspace_pk, &reference(&datadog, Some("apiKey")))
+                .await
+                .expect("could not resolve")
+        );
+        assert_eq!(
+            Value::String("just-a-string".to_string()),
+            backend
+                .resolve(workspace_pk, &reference(&plain, None))
+                .await
+                .expect("could not resolve")
+        );
+
+        for path in ["PATH", theirs.as_str()] {
+            let err = backend
+                .resolve(workspace_pk, &reference(path, None))
+                .await
+                .expect_err("should refuse variables outside of the workspace prefix");
+            assert!(matches!(
+                err,
+                SecretBackendError::EnvironmentVariableNotAllowed(_, _)
+            ));
+        }
+    }
+
+    /// Serves a single canned Vault KV v2 response, returning the raw request it received.
+    async fn vault_stand_in(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("could not bind");
+        let address = format!("http://{}/", listener.local_addr().expect("no local addr"));
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("could not accept");
+            let mut buf = vec![0; 4096];
+            let read = socket.read(&mut buf).await.expect("could not read");
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket
+                .write_all(response.as_bytes())
+                .await
+                .expect("could not write");
+            String::from_utf8_lossy(&buf[..read]).to_string()
+        });
+        (address, handle)
+    }
+
+    #[tokio::test]
+    async fn vault_kv_backend_resolves_from_stand_in() {
+        let (address, handle) = vault_stand_in(
+            r#"{"data":{"data":{"accessKeyId":"AKIA","secretAccessKey":"shh"},"metadata":{"version":3}}}"#,
+        )
+        .await;
+
+        let workspace_pk = WorkspacePk::generate();
+        let mut backends = SecretBackends::default();
+        backends.insert(Arc::new(
+            VaultKvSecretBackend::new(address, "secret", "s.token").expect("invalid address"),
+        ));
+        let value = backends
+            .resolve(
+                SecretBackendKind::VaultKv,
+                workspace_pk,
+                &reference("aws/prod", Some("accessKeyId")),
+            )
+            .await
+            .expect("could not resolve");
+        assert_eq!(Value::String("AKIA".to_string()), value);
+
+        let request = handle.await.expect("stand-in panicked").to_lowercase();
+        assert!(request.starts_with(&format!(
+            "get /v1/secret/data/workspaces/{}/aws/prod ",
+            workspace_pk.to_string().to_lowercase()
+        )));
+        assert!(request.contains("x-vault-token: s.token"));
+    }
+
+    #[test]
+    fn vault_kv_backend_refuses_to_leave_workspace() {
+        let backend = VaultKvSecretBackend::new("http://127.0.0.1:8200/", "secret", "s.token")
+            .expect("invalid address");
+        for path in ["../other/aws/prod", "/aws/prod", "aws/../../prod", ""] {
+            let err = backend
+                .check(WorkspacePk::generate(), &reference(path, None))
+                .expect_err("should refuse to leave the workspace");
+            assert!(matches!(err, SecretBackendError::InvalidReferencePath(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn unconfigured_backend_is_an_error() {
+        let backends = SecretBackendsConfig::default()
+            .build()
+            .expect("could not build backends");
+        let err = backends
+            .resolve(
+                SecretBackendKind::File,
+                WorkspacePk::generate(),
+                &reference("aws/prod.json", None),
+            )
+            .await
+            .expect_err("file backend is not configured");
+        assert!(matches!(
+            err,
+            SecretBackendError::BackendNotConfigured(SecretBackendKind::File)
+        ));
+    }
+}
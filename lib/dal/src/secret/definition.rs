@@ -0,0 +1,540 @@
+//! This module contains [`SecretDefinition`], which describes a [`SecretKind`](crate::SecretKind)
+//! as data: a name and a [JSON Schema](https://json-schema.org) that the decrypted message of
+//! every secret of that kind must satisfy.
+//!
+//! Only the subset of JSON Schema that is useful for describing credentials is understood (see
+//! [`SecretDefinition::validate()`]). Definitions whose schema uses anything else are refused when
+//! they are created, so a schema never silently checks less than it appears to.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use telemetry::prelude::*;
+
+use crate::{
+    impl_standard_model, pk,
+    secret::{SecretError, SecretResult},
+    standard_model, standard_model_accessor, standard_model_accessor_ro, DalContext, StandardModel,
+    Tenancy, Timestamp, Visibility,
+};
+
+/// The types a schema may name in `type`.
+const TYPES: &[&str] = &[
+    "array", "boolean", "integer", "null", "number", "object", "string",
+];
+
+/// Keywords that only annotate a schema. They are accepted anywhere, as are `x-` extensions.
+const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$comment",
+    "$schema",
+    "default",
+    "description",
+    "examples",
+    "title",
+];
+
+pk!(SecretDefinitionPk);
+pk!(SecretDefinitionId);
+
+/// The description of a user-definable kind of secret. The [`name`](Self::name()) is what a
+/// [`SecretKind::Defined`](crate::SecretKind::Defined) refers to.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct SecretDefinition {
+    pk: SecretDefinitionPk,
+    id: SecretDefinitionId,
+    name: String,
+    description: Option<String>,
+    json_schema: Value,
+    #[serde(flatten)]
+    tenancy: Tenancy,
+    #[serde(flatten)]
+    timestamp: Timestamp,
+    #[serde(flatten)]
+    visibility: Visibility,
+}
+
+impl_standard_model! {
+    model: SecretDefinition,
+    pk: SecretDefinitionPk,
+    id: SecretDefinitionId,
+    table_name: "secret_definitions",
+    history_event_label_base: "secret_definition",
+    history_event_message_name: "Secret Definition"
+}
+
+impl SecretDefinition {
+    #[instrument(skip_all)]
+    pub async fn new(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+        description: Option<String>,
+        json_schema: Value,
+    ) -> SecretResult<Self> {
+        let name = name.as_ref();
+        let mut problems = Vec::new();
+        check_schema("", &json_schema, &mut problems);
+        if !problems.is_empty() {
+            return Err(SecretError::DefinitionSchemaUnsupported(
+                name.to_owned(),
+                problems,
+            ));
+        }
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM secret_definition_create_v1($1, $2, $3, $4, $5)",
+                &[
+                    ctx.tenancy(),
+                    ctx.visibility(),
+                    &name,
+                    &description,
+                    &json_schema,
+                ],
+            )
+            .await?;
+        let object = standard_model::finish_create_from_row(ctx, row).await?;
+        Ok(object)
+    }
+
+    standard_model_accessor_ro!(name, str);
+    standard_model_accessor!(description, Option<String>, SecretResult);
+    // The schema is checked when the definition is created, so it can't be changed afterwards
+    standard_model_accessor_ro!(json_schema, Value);
+
+    pub async fn find_by_name(
+        ctx: &DalContext,
+        name: impl AsRef<str>,
+    ) -> SecretResult<Option<Self>> {
+        let name = name.as_ref();
+        Ok(Self::find_by_attr(ctx, "name", &name).await?.pop())
+    }
+
+    /// Checks a decrypted secret message against the definition's JSON schema, returning a
+    /// description of every violation found. An empty list means the message is valid.
+    pub fn validate(&self, message: &Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        validate_at("", &self.json_schema, message, &mut errors);
+        errors
+    }
+}
+
+/// Check that `schema` only uses the keywords and types [`validate_at()`] understands, appending a
+/// message for each problem to `problems`. `pointer` is the JSON pointer to `schema` within the
+/// definition's schema.
+fn check_schema(pointer: &str, schema: &Value, problems: &mut Vec<String>) {
+    let schema = match schema {
+        Value::Bool(_) => return,
+        Value::Object(schema) => schema,
+        other => {
+            problems.push(format!(
+                "{}: a schema must be an object or a boolean, found {}",
+                display(pointer),
+                type_name(other)
+            ));
+            return;
+        }
+    };
+
+    for (keyword, value) in schema {
+        let keyword_pointer = format!("{pointer}/{}", escape(keyword));
+        let expected = |what: &str| format!("{}: must be {what}", display(&keyword_pointer));
+        match keyword.as_str() {
+            "type" => {
+                let kinds: Vec<&Value> = match value {
+                    Value::Array(kinds) => kinds.iter().collect(),
+                    kind => vec![kind],
+                };
+                for kind in kinds {
+                    match kind.as_str() {
+                        Some(name) if TYPES.contains(&name) => {}
+                        _ => problems.push(format!(
+                            "{}: unknown type {kind}",
+                            display(&keyword_pointer)
+                        )),
+                    }
+                }
+            }
+            "enum" => {
+                if !value.is_array() {
+                    problems.push(expected("an array"));
+                }
+            }
+            "const" => {}
+            "minLength" | "maxLength" | "minItems" | "maxItems" => {
+                if !value.is_u64() {
+                    problems.push(expected("a non-negative integer"));
+                }
+            }
+            "minimum" | "maximum" => {
+                if !value.is_number() {
+                    problems.push(expected("a number"));
+                }
+            }
+            "pattern" => match value.as_str() {
+                Some(pattern) => {
+                    if let Err(err) = Regex::new(pattern) {
+                        problems.push(format!(
+                            "{}: invalid pattern {pattern}: {err}",
+                            display(&keyword_pointer)
+                        ));
+                    }
+                }
+                None => problems.push(expected("a string")),
+            },
+            "required" => match value.as_array() {
+                Some(keys) if keys.iter().all(Value::is_string) => {}
+                _ => problems.push(expected("an array of strings")),
+            },
+            "properties" => match value.as_object() {
+                Some(properties) => {
+                    for (key, property_schema) in properties {
+                        check_schema(
+                            &format!("{keyword_pointer}/{}", escape(key)),
+                            property_schema,
+                            problems,
+                        );
+                    }
+                }
+                None => problems.push(expected("an object")),
+            },
+            "items" | "additionalProperties" => check_schema(&keyword_pointer, value, problems),
+            annotation
+                if ANNOTATION_KEYWORDS.contains(&annotation) || annotation.starts_with("x-") => {}
+            unsupported => problems.push(format!(
+                "{}: unsupported keyword {unsupported}",
+                display(pointer)
+            )),
+        }
+    }
+}
+
+/// Validate `value` against `schema`, appending a message for each violation to `errors`.
+/// `pointer` is the [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901) to `value`
+/// within the message being validated.
+fn validate_at(pointer: &str, schema: &Value, value: &Value, errors: &mut Vec<String>) {
+    let schema = match schema {
+        // `true` accepts anything, `false` accepts nothing.
+        Value::Bool(true) => return,
+        Value::Bool(false) => {
+            errors.push(format!("{}: no value is allowed here", display(pointer)));
+            return;
+        }
+        Value::Object(schema) => schema,
+        _ => return,
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(kind) => vec![kind.as_str()],
+            Value::Array(kinds) => kinds.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|kind| is_type(kind, value)) {
+            errors.push(format!(
+                "{}: expected {}, found {}",
+                display(pointer),
+                types.join(" or "),
+                type_name(value)
+            ));
+            // Nothing else can meaningfully be checked once the type is wrong.
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            errors.push(format!(
+                "{}: must be one of {}",
+                display(pointer),
+                Value::Array(allowed.clone())
+            ));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            errors.push(format!("{}: must be {constant}", display(pointer)));
+        }
+    }
+
+    match value {
+        Value::String(string) => validate_string(pointer, schema, string, errors),
+        Value::Number(number) => {
+            if let Some(number) = number.as_f64() {
+                validate_number(pointer, schema, number, errors)
+            }
+        }
+        Value::Array(items) => validate_array(pointer, schema, items, errors),
+        Value::Object(object) => validate_object(pointer, schema, object, errors),
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn validate_string(
+    pointer: &str,
+    schema: &Map<String, Value>,
+    string: &str,
+    errors: &mut Vec<String>,
+) {
+    let length = string.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if length < min {
+            errors.push(format!(
+                "{}: must be at least {min} characters long",
+                display(pointer)
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if length > max {
+            errors.push(format!(
+                "{}: must be at most {max} characters long",
+                display(pointer)
+            ));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        match Regex::new(pattern) {
+            Ok(regex) if !regex.is_match(string) => errors.push(format!(
+                "{}: must match the pattern {pattern}",
+                display(pointer)
+            )),
+            Ok(_) => {}
+            Err(err) => errors.push(format!(
+                "{}: schema pattern {pattern} is invalid: {err}",
+                display(pointer)
+            )),
+        }
+    }
+}
+
+fn validate_number(
+    pointer: &str,
+    schema: &Map<String, Value>,
+    number: f64,
+    errors: &mut Vec<String>,
+) {
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+        if number < min {
+            errors.push(format!("{}: must be at least {min}", display(pointer)));
+        }
+    }
+    if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+        if number > max {
+            errors.push(format!("{}: must be at most {max}", display(pointer)));
+        }
+    }
+}
+
+fn validate_array(
+    pointer: &str,
+    schema: &Map<String, Value>,
+    items: &[Value],
+    errors: &mut Vec<String>,
+) {
+    let length = items.len() as u64;
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if length < min {
+            errors.push(format!(
+                "{}: must have at least {min} items",
+                display(pointer)
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if length > max {
+            errors.push(format!(
+                "{}: must have at most {max} items",
+                display(pointer)
+            ));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            validate_at(&format!("{pointer}/{index}"), item_schema, item, errors);
+        }
+    }
+}
+
+fn validate_object(
+    pointer: &str,
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    errors: &mut Vec<String>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(key) {
+                errors.push(format!(
+                    "{}: missing required field {key}",
+                    display(pointer)
+                ));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (key, value) in object {
+        let child_pointer = format!("{pointer}/{}", escape(key));
+        match properties.and_then(|properties| properties.get(key)) {
+            Some(property_schema) => validate_at(&child_pointer, property_schema, value, errors),
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    errors.push(format!("{}: unexpected field {key}", display(pointer)))
+                }
+                Some(additional @ Value::Object(_)) => {
+                    validate_at(&child_pointer, additional, value, errors)
+                }
+                _ => {}
+            },
+        }
+    }
+}
+
+fn is_type(kind: &str, value: &Value) -> bool {
+    match kind {
+        "array" => value.is_array(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "object" => value.is_object(),
+        "string" => value.is_string(),
+        // Definitions naming any other type are refused when they are created.
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Array(_) => "array",
+        Value::Bool(_) => "boolean",
+        Value::Null => "null",
+        Value::Number(_) => "number",
+        Value::Object(_) => "object",
+        Value::String(_) => "string",
+    }
+}
+
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn display(pointer: &str) -> &str {
+    if pointer.is_empty() {
+        "/"
+    } else {
+        pointer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn errors(schema: Value, value: Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        validate_at("", &schema, &value, &mut errors);
+        errors
+    }
+
+    fn credential_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "username": { "type": "string", "minLength": 1 },
+                "token": { "type": "string", "pattern": "^ghp_" },
+                "scopes": { "type": "array", "items": { "enum": ["repo", "read:org"] } },
+                "expiresInDays": { "type": "integer", "minimum": 1, "maximum": 365 },
+            },
+            "required": ["username", "token"],
+            "additionalProperties": false,
+        })
+    }
+
+    #[test]
+    fn valid_message() {
+        assert!(errors(
+            credential_schema(),
+            json!({
+                "username": "octocat",
+                "token": "ghp_abc123",
+                "scopes": ["repo"],
+                "expiresInDays": 30,
+            }),
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn invalid_message_reports_every_violation() {
+        assert_eq!(
+            vec![
+                "/: missing required field token".to_string(),
+                "/username: must be at least 1 characters long".to_string(),
+                "/: unexpected field password".to_string(),
+                "/scopes/1: must be one of [\"repo\",\"read:org\"]".to_string(),
+                "/expiresInDays: must be at most 365".to_string(),
+            ],
+            errors(
+                credential_schema(),
+                json!({
+                    "username": "",
+                    "password": "hunter2",
+                    "scopes": ["repo", "admin"],
+                    "expiresInDays": 400,
+                }),
+            )
+        );
+    }
+
+    #[test]
+    fn wrong_type_stops_validation() {
+        assert_eq!(
+            vec!["/: expected object, found string".to_string()],
+            errors(credential_schema(), json!("ghp_abc123"))
+        );
+    }
+
+    fn problems(schema: Value) -> Vec<String> {
+        let mut problems = Vec::new();
+        check_schema("", &schema, &mut problems);
+        problems
+    }
+
+    #[test]
+    fn supported_schema_has_no_problems() {
+        assert!(problems(credential_schema()).is_empty());
+        assert!(problems(json!({
+            "title": "GitHub token",
+            "type": ["string", "null"],
+            "x-si-widget": "password",
+        }))
+        .is_empty());
+    }
+
+    #[test]
+    fn unsupported_keywords_and_types_are_refused() {
+        assert_eq!(
+            vec![
+                "/: unsupported keyword format".to_string(),
+                "/properties/token/type: unknown type \"secret\"".to_string(),
+                "/properties/username: unsupported keyword oneOf".to_string(),
+                "/: unsupported keyword $ref".to_string(),
+                "/items: a schema must be an object or a boolean, found array".to_string(),
+            ],
+            problems(json!({
+                "format": "email",
+                "properties": {
+                    "token": { "type": "secret" },
+                    "username": { "oneOf": [{ "type": "string" }] },
+                },
+                "$ref": "#/definitions/credential",
+                "items": [{ "type": "string" }],
+            }))
+        );
+    }
+}
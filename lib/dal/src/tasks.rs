@@ -2,9 +2,13 @@
 //! SI binaries that are dependent on the [`dal`](crate).
 
 // This modules should remain private! Add "pub use" statements to use their contents.
+mod audit_exporter;
 mod resource_scheduler;
 mod status_receiver;
+mod webhook_dispatcher;
 
-pub use resource_scheduler::{ResourceScheduler, ResourceSchedulerError};
+pub use audit_exporter::{AuditExporter, AuditExporterConfig, AuditExporterError, AuditSink};
+pub use resource_scheduler::{ResourceScheduler, ResourceSchedulerConfig, ResourceSchedulerError};
 pub use status_receiver::client::StatusReceiverClient;
 pub use status_receiver::{StatusReceiver, StatusReceiverError, StatusReceiverRequest};
+pub use webhook_dispatcher::{WebhookDispatcher, WebhookDispatcherError};
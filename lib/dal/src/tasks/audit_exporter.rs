@@ -0,0 +1,322 @@
+//! This module contains [`AuditExporter`], which is a "long-running" task that continuously
+//! exports [`HistoryEvents`](crate::HistoryEvent) from every workspace to an [`AuditSink`], for
+//! feeding them into a SIEM.
+//!
+//! Each sink is registered in the `audit_export_sinks` table, and every event is queued for each
+//! registered sink in the `audit_export_outbox` table, in the same transaction that creates it.
+//! Every [poll](AuditExporterConfig::poll_interval_secs), the exporter sends the queued events,
+//! oldest first, and then removes them from the queue. An event is queued as soon as its
+//! transaction commits, so none are missed however long that transaction is open for.
+//!
+//! When the configured sink changes, the exporter unregisters the sinks it no longer sends to as it
+//! starts, so that events stop being queued for them.
+//!
+//! Only one exporter sends to a sink at a time, guarded by an advisory lock, so that several
+//! running side by side don't send the same events. Events are delivered at least once: if the
+//! exporter stops between sending a batch and removing it from the queue, that batch is sent
+//! again. Events are written to the sink one by one as they are read, rather than collecting the
+//! batch in memory first.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use futures::StreamExt;
+use postgres_types::ToSql;
+use serde::{Deserialize, Serialize};
+use si_data_nats::{NatsClient, NatsError};
+use si_data_pg::{PgError, PgPoolError};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{
+    fs::File,
+    io::{AsyncWriteExt, BufWriter},
+    sync::broadcast,
+    time,
+};
+
+use crate::{HistoryEventPk, ServicesContext, TransactionsError};
+
+const HISTORY_EVENT_LIST_FOR_EXPORT: &str =
+    include_str!("../queries/history_event/list_for_export.sql");
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum AuditExporterError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Nats(#[from] NatsError),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    PgPool(#[from] PgPoolError),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+}
+
+pub type AuditExporterResult<T> = Result<T, AuditExporterError>;
+
+/// Where the [`AuditExporter`] sends [`HistoryEvents`](crate::HistoryEvent). Each event is sent
+/// as a single JSON object.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AuditSink {
+    /// Appends events to a file in [JSON Lines](https://jsonlines.org) format.
+    File { path: PathBuf },
+    /// Publishes each event as a message on a NATS subject.
+    Nats { subject: String },
+}
+
+impl AuditSink {
+    /// Identifies the sink in the `audit_export_sinks` table, so that each sink has its own queue
+    /// of events.
+    pub fn key(&self) -> String {
+        match self {
+            Self::File { path } => format!("file:{}", path.display()),
+            Self::Nats { subject } => format!("nats:{subject}"),
+        }
+    }
+}
+
+/// Where, and how often, the [`AuditExporter`] exports events. All durations are in seconds.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AuditExporterConfig {
+    /// Where to send events. Nothing is exported when unset.
+    pub sink: Option<AuditSink>,
+    /// How often the exporter looks for new events.
+    pub poll_interval_secs: u64,
+    /// The most events sent in one go.
+    pub batch_size: i64,
+}
+
+impl Default for AuditExporterConfig {
+    fn default() -> Self {
+        Self {
+            sink: None,
+            poll_interval_secs: 5,
+            batch_size: 500,
+        }
+    }
+}
+
+/// The audit exporter handles sending every [`HistoryEvent`](crate::HistoryEvent) across all
+/// workspaces to its configured [`AuditSink`].
+#[derive(Debug, Clone)]
+pub struct AuditExporter {
+    services_context: ServicesContext,
+    sink: AuditSink,
+    config: AuditExporterConfig,
+}
+
+impl AuditExporter {
+    /// Creates an exporter for the config's [`AuditSink`], or returns `None` if it has none.
+    pub fn new(
+        services_context: ServicesContext,
+        config: AuditExporterConfig,
+    ) -> Option<AuditExporter> {
+        let sink = config.sink.clone()?;
+        Some(AuditExporter {
+            services_context,
+            sink,
+            config,
+        })
+    }
+
+    /// Starts the exporter, consuming itself.
+    pub fn start(self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    info!("Audit Exporter received shutdown request, bailing out");
+                },
+                _ = self.start_task() => {}
+            }
+            info!("Audit Exporter stopped");
+        });
+    }
+
+    /// The internal task spawned by `start`. Every
+    /// [poll](AuditExporterConfig::poll_interval_secs), it exports batches of events until it
+    /// has caught up.
+    #[instrument(name = "audit_exporter.start_task", skip_all, level = "debug")]
+    async fn start_task(&self) {
+        if let Err(err) = self.unregister_stale_sinks().await {
+            error!("Unable to unregister stale audit sinks: {err}");
+        }
+
+        let mut interval =
+            time::interval(Duration::from_secs(self.config.poll_interval_secs.max(1)));
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            loop {
+                match self.run().await {
+                    Ok(exported) if exported as i64 >= self.config.batch_size => continue,
+                    Ok(_) => break,
+                    Err(err) => {
+                        error!("Unable to export audit events: {err}");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Unregisters every sink other than the configured one, dropping the events queued for them.
+    /// Returns the keys of the sinks which were unregistered.
+    #[instrument(
+        name = "audit_exporter.unregister_stale_sinks",
+        skip_all,
+        level = "debug"
+    )]
+    pub async fn unregister_stale_sinks(&self) -> AuditExporterResult<Vec<String>> {
+        let builder = self.services_context.clone().into_builder(false);
+        let ctx = builder.build_default().await?;
+        let sink_key = self.sink.key();
+
+        let mut stale = Vec::new();
+        {
+            let txns = ctx.txns().await?;
+            let rows = txns
+                .pg()
+                .query(
+                    "SELECT sink FROM audit_export_sinks WHERE sink <> $1",
+                    &[&sink_key],
+                )
+                .await?;
+            for row in rows {
+                let stale_key: String = row.try_get("sink")?;
+                txns.pg()
+                    .execute("SELECT audit_export_sink_unregister_v1($1)", &[&stale_key])
+                    .await?;
+                info!(sink = %stale_key, "Unregistered stale audit sink");
+                stale.push(stale_key);
+            }
+        }
+        ctx.commit().await?;
+
+        Ok(stale)
+    }
+
+    /// Exports the next batch of events, returning how many were exported. Nothing is exported
+    /// while another exporter is sending to the same sink.
+    #[instrument(name = "audit_exporter.run", skip_all, level = "debug")]
+    pub async fn run(&self) -> AuditExporterResult<usize> {
+        let builder = self.services_context.clone().into_builder(false);
+        let ctx = builder.build_default().await?;
+        let sink_key = self.sink.key();
+
+        // Registering a sink for the first time holds up the creation of events, so it is
+        // committed straight away
+        ctx.txns()
+            .await?
+            .pg()
+            .execute("SELECT audit_export_sink_register_v1($1)", &[&sink_key])
+            .await?;
+        ctx.commit().await?;
+
+        let txns = ctx.txns().await?;
+        let locked: bool = txns
+            .pg()
+            .query_one(
+                "SELECT pg_try_advisory_xact_lock(hashtext('audit_export:' || $1)) AS locked",
+                &[&sink_key],
+            )
+            .await?
+            .try_get("locked")?;
+        if !locked {
+            drop(txns);
+            ctx.commit().await?;
+            return Ok(0);
+        }
+
+        // We need to bypass tenancy checks, as events from every workspace are exported
+        let mut exported: Vec<HistoryEventPk> = Vec::new();
+        {
+            let batch_size = self.config.batch_size.max(1);
+            let params: [&(dyn ToSql + Sync); 2] = [&sink_key, &batch_size];
+            let rows = txns
+                .pg()
+                .query_raw(HISTORY_EVENT_LIST_FOR_EXPORT, params)
+                .await?;
+            futures::pin_mut!(rows);
+
+            let mut writer =
+                AuditSinkWriter::open(&self.sink, self.services_context.nats_conn()).await?;
+            while let Some(row) = rows.next().await {
+                let row = row?;
+                let event: serde_json::Value = row.try_get("object")?;
+                writer.write(&event).await?;
+                exported.push(row.try_get("pk")?);
+            }
+            writer.finish().await?;
+        }
+
+        if !exported.is_empty() {
+            txns.pg()
+                .execute(
+                    "DELETE FROM audit_export_outbox
+                     WHERE sink = $1 AND history_event_pk = ANY($2)",
+                    &[&sink_key, &exported],
+                )
+                .await?;
+        }
+        drop(txns);
+        ctx.commit().await?;
+
+        debug!("Exported {} audit events", exported.len());
+        Ok(exported.len())
+    }
+}
+
+/// Sends events to an [`AuditSink`] one at a time.
+enum AuditSinkWriter {
+    File(BufWriter<File>),
+    Nats { nats: NatsClient, subject: String },
+}
+
+impl AuditSinkWriter {
+    async fn open(sink: &AuditSink, nats: &NatsClient) -> AuditExporterResult<Self> {
+        Ok(match sink {
+            AuditSink::File { path } => {
+                let file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await?;
+                Self::File(BufWriter::new(file))
+            }
+            AuditSink::Nats { subject } => Self::Nats {
+                nats: nats.clone(),
+                subject: subject.clone(),
+            },
+        })
+    }
+
+    async fn write(&mut self, event: &serde_json::Value) -> AuditExporterResult<()> {
+        let mut bytes = serde_json::to_vec(event)?;
+        match self {
+            Self::File(file) => {
+                bytes.push(b'\n');
+                file.write_all(&bytes).await?;
+            }
+            Self::Nats { nats, subject } => nats.publish(subject.clone(), bytes).await?,
+        }
+        Ok(())
+    }
+
+    /// Makes sure everything written has reached the sink.
+    async fn finish(self) -> AuditExporterResult<()> {
+        match self {
+            Self::File(mut file) => {
+                file.flush().await?;
+                file.get_ref().sync_data().await?;
+            }
+            Self::Nats { nats, .. } => nats.flush().await?,
+        }
+        Ok(())
+    }
+}
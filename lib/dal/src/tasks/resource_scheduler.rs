@@ -1,19 +1,37 @@
 //! This module contains [`ResourceScheduler`], which is a "long-running" tasks that performs
 //! [`resource`](crate::component::resource) syncing on a cadence.
+//!
+//! Every [tick](ResourceSchedulerConfig::tick_interval_secs), the scheduler lists the components on
+//! head across all workspaces and refreshes the ones that are due, running their
+//! [`ActionKind::Refresh`] prototype. How often a component is due is decided by its
+//! [`SchemaVariant`](crate::SchemaVariant), then its workspace, then the default cadence (see
+//! [`ResourceSchedulerConfig`]), plus some jitter so that refreshes don't all land at once.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use futures::StreamExt;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::{PgError, PgPoolError};
 use telemetry::prelude::*;
 use thiserror::Error;
-use tokio::{sync::broadcast, time};
+use tokio::{sync::broadcast, time, time::Instant};
 
-use crate::{standard_model, Component, ServicesContext, StandardModelError, TransactionsError};
+use crate::{
+    standard_model, ActionKind, Component, ComponentDrift, ComponentError, ComponentId, DriftError,
+    SchemaVariantId, ServicesContext, StandardModel, StandardModelError, TransactionsError,
+    WorkspacePk, WsEvent, WsEventError,
+};
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum ResourceSchedulerError {
+    #[error(transparent)]
+    Component(#[from] ComponentError),
+    #[error(transparent)]
+    Drift(#[from] DriftError),
     #[error(transparent)]
     Nats(#[from] NatsError),
     #[error(transparent)]
@@ -26,23 +44,113 @@ pub enum ResourceSchedulerError {
     StandardModelError(#[from] StandardModelError),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    WsEvent(#[from] WsEventError),
 }
 
 pub type ResourceSchedulerResult<T> = Result<T, ResourceSchedulerError>;
 
-/// The resource scheduler handles looking up all the components, and scheduling
-/// their resources to refresh. Eventually, it should become smart enough to parallelize,
-/// it might be extracted to a fully separate service, etc etc. For now,
-/// it is the dumbest thing that could possibly work - no more often than every 30
-/// seconds, it will ask a resource to refresh
+/// How often, and how many at once, the [`ResourceScheduler`] refreshes resources. All durations
+/// are in seconds.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ResourceSchedulerConfig {
+    /// How often the scheduler looks for components that are due for a refresh.
+    pub tick_interval_secs: u64,
+    /// How long after a refresh a component is due again, unless overridden below.
+    pub default_cadence_secs: u64,
+    /// Per-[`SchemaVariant`](crate::SchemaVariant) cadences, which take precedence over the
+    /// workspace and default cadences.
+    pub schema_variant_cadence_secs: HashMap<SchemaVariantId, u64>,
+    /// Per-workspace cadences, which take precedence over the default cadence.
+    pub workspace_cadence_secs: HashMap<WorkspacePk, u64>,
+    /// The most random delay added on top of a cadence.
+    pub max_jitter_secs: u64,
+    /// The most refreshes which are allowed to run at the same time.
+    pub max_concurrency: usize,
+}
+
+impl Default for ResourceSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            tick_interval_secs: 30,
+            default_cadence_secs: 300,
+            schema_variant_cadence_secs: HashMap::new(),
+            workspace_cadence_secs: HashMap::new(),
+            max_jitter_secs: 30,
+            max_concurrency: 8,
+        }
+    }
+}
+
+impl ResourceSchedulerConfig {
+    fn cadence(
+        &self,
+        schema_variant_id: SchemaVariantId,
+        workspace_pk: Option<WorkspacePk>,
+    ) -> Duration {
+        let secs = self
+            .schema_variant_cadence_secs
+            .get(&schema_variant_id)
+            .or_else(|| {
+                workspace_pk.and_then(|workspace_pk| self.workspace_cadence_secs.get(&workspace_pk))
+            })
+            .copied()
+            .unwrap_or(self.default_cadence_secs);
+        Duration::from_secs(secs)
+    }
+
+    fn jitter(&self) -> Duration {
+        if self.max_jitter_secs == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(rand::thread_rng().gen_range(0..=self.max_jitter_secs * 1000))
+    }
+
+    /// Picks out the components whose entry in `next_refresh` has passed, adding entries for the
+    /// ones seen for the first time and dropping those of components which are gone.
+    fn due(
+        &self,
+        component_ids: impl IntoIterator<Item = ComponentId>,
+        next_refresh: &mut HashMap<ComponentId, Instant>,
+        now: Instant,
+    ) -> Vec<ComponentId> {
+        let component_ids: Vec<ComponentId> = component_ids.into_iter().collect();
+        next_refresh.retain(|id, _| component_ids.contains(id));
+
+        component_ids
+            .into_iter()
+            .filter(|component_id| {
+                let due_at = *next_refresh
+                    .entry(*component_id)
+                    .or_insert_with(|| now + self.jitter());
+                due_at <= now
+            })
+            .collect()
+    }
+}
+
+/// The resource scheduler handles looking up all the components on head, and refreshing their
+/// resources when they are due. Refreshes run concurrently, up to
+/// [`max_concurrency`](ResourceSchedulerConfig::max_concurrency) at a time.
 #[derive(Debug, Clone)]
 pub struct ResourceScheduler {
     services_context: ServicesContext,
+    config: ResourceSchedulerConfig,
 }
 
 impl ResourceScheduler {
     pub fn new(services_context: ServicesContext) -> ResourceScheduler {
-        ResourceScheduler { services_context }
+        ResourceScheduler {
+            services_context,
+            config: ResourceSchedulerConfig::default(),
+        }
+    }
+
+    /// Replaces the default [`ResourceSchedulerConfig`].
+    pub fn with_config(mut self, config: ResourceSchedulerConfig) -> Self {
+        self.config = config;
+        self
     }
 
     /// Starts the scheduler. It returns the join handle to the spawned scheduler, and
@@ -60,39 +168,108 @@ impl ResourceScheduler {
         });
     }
 
+    /// Refreshes every component whose entry in `next_refresh` has passed. Components seen
+    /// for the first time are due right away, after some jitter.
     #[instrument(name = "resource_scheduler.run", skip_all, level = "debug")]
-    async fn run(&self) -> Result<(), Box<dyn std::error::Error>> {
-        /*
+    async fn run(
+        &self,
+        next_refresh: &mut HashMap<ComponentId, Instant>,
+    ) -> ResourceSchedulerResult<()> {
         let components = self.components().await?;
-        info!("Refresh {} resources", components.len());
+        let due_ids = self.config.due(
+            components.iter().map(|component| *component.id()),
+            next_refresh,
+            Instant::now(),
+        );
+        let due: Vec<Component> = components
+            .into_iter()
+            .filter(|component| due_ids.contains(component.id()))
+            .collect();
+        if due.is_empty() {
+            return Ok(());
+        }
+        debug!("Refresh {} resources", due.len());
+
+        let results: Vec<(ComponentId, ResourceSchedulerResult<Duration>)> =
+            futures::stream::iter(due)
+                .map(|component| async move { (*component.id(), self.refresh(component).await) })
+                .buffer_unordered(self.config.max_concurrency.max(1))
+                .collect()
+                .await;
+
+        for (component_id, result) in results {
+            let cadence = match result {
+                Ok(cadence) => cadence,
+                Err(err) => {
+                    error!(%component_id, "Unable to refresh resource: {err}");
+                    Duration::from_secs(self.config.default_cadence_secs)
+                }
+            };
+            next_refresh.insert(
+                component_id,
+                Instant::now() + cadence + self.config.jitter(),
+            );
+        }
 
-        for component in components {
-            // First we're building a ctx with no tenancy at head, then updating it with a
-            // workspace head request context
+        Ok(())
+    }
 
-            let builder = self.services_context.clone().into_builder(false);
-            let mut ctx = builder.build_default().await?;
+    /// Runs the [`ActionKind::Refresh`] prototype of a single component in its own workspace on
+    /// head, returning how long to wait until it should be refreshed again. The refreshed
+    /// resource is then compared with the model, recording any [`ComponentDrift`].
+    #[instrument(name = "resource_scheduler.refresh", skip_all, level = "debug", fields(component_id = %component.id()))]
+    async fn refresh(&self, component: Component) -> ResourceSchedulerResult<Duration> {
+        // First we're building a ctx with no tenancy at head, then updating it with the
+        // component's workspace
+        let builder = self.services_context.clone().into_builder(false);
+        let mut ctx = builder.build_default().await?;
+        ctx.update_tenancy(*component.tenancy());
+        ctx.update_with_deleted_visibility();
 
-            ctx.update_tenancy(*component.tenancy());
+        let schema_variant_id = Component::schema_variant_id(&ctx, *component.id()).await?;
+        let cadence = self
+            .config
+            .cadence(schema_variant_id, component.tenancy().workspace_pk());
 
-            component.act(&ctx, "refresh").await?;
+        // Components without a resource have nothing to refresh
+        if component.resource(&ctx).await?.payload.is_none() {
             ctx.commit().await?;
+            return Ok(cadence);
         }
-        */
-        Ok(())
+
+        component.act(&ctx, ActionKind::Refresh).await?;
+
+        let drift = ComponentDrift::update_for_component(&ctx, *component.id()).await?;
+        if drift.is_drifted() {
+            info!(
+                component_id = %component.id(),
+                drifted_props = drift.drifted_props().len(),
+                "Resource has drifted from the model"
+            );
+        }
+
+        WsEvent::resource_refreshed(&ctx, *component.id())
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+        ctx.commit().await?;
+
+        Ok(cadence)
     }
 
-    /// The internal task spawned by `start`. No more frequently than every 30
-    /// seconds, it will iterate over all the components on head in the database and
-    /// schedule them to refresh.
+    /// The internal task spawned by `start`. Every
+    /// [tick](ResourceSchedulerConfig::tick_interval_secs), it will look at all the
+    /// components on head in the database and refresh the ones which are due.
     #[instrument(name = "resource_scheduler.start_task", skip_all, level = "debug")]
     async fn start_task(&self) {
-        let mut interval = time::interval(Duration::from_secs(300));
+        let mut next_refresh = HashMap::new();
+        let mut interval =
+            time::interval(Duration::from_secs(self.config.tick_interval_secs.max(1)));
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
         loop {
             interval.tick().await;
-            match self.run().await {
-                Ok(()) => {}
-                Err(err) => error!("{err}"),
+            if let Err(err) = self.run(&mut next_refresh).await {
+                error!("{err}");
             }
         }
     }
@@ -124,3 +301,114 @@ impl ResourceScheduler {
         Ok(components)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cadence_precedence() {
+        let schema_variant_id = SchemaVariantId::generate();
+        let workspace_pk = WorkspacePk::generate();
+        let mut config = ResourceSchedulerConfig {
+            default_cadence_secs: 300,
+            ..Default::default()
+        };
+        assert_eq!(
+            Duration::from_secs(300),
+            config.cadence(schema_variant_id, Some(workspace_pk))
+        );
+
+        config.workspace_cadence_secs.insert(workspace_pk, 60);
+        assert_eq!(
+            Duration::from_secs(60),
+            config.cadence(schema_variant_id, Some(workspace_pk))
+        );
+        assert_eq!(
+            Duration::from_secs(300),
+            config.cadence(schema_variant_id, Some(WorkspacePk::generate()))
+        );
+        assert_eq!(
+            Duration::from_secs(300),
+            config.cadence(schema_variant_id, None)
+        );
+
+        config
+            .schema_variant_cadence_secs
+            .insert(schema_variant_id, 10);
+        assert_eq!(
+            Duration::from_secs(10),
+            config.cadence(schema_variant_id, Some(workspace_pk))
+        );
+        assert_eq!(
+            Duration::from_secs(10),
+            config.cadence(schema_variant_id, None)
+        );
+        assert_eq!(
+            Duration::from_secs(60),
+            config.cadence(SchemaVariantId::generate(), Some(workspace_pk))
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let config = ResourceSchedulerConfig {
+            max_jitter_secs: 0,
+            ..Default::default()
+        };
+        assert_eq!(Duration::ZERO, config.jitter());
+
+        let config = ResourceSchedulerConfig {
+            max_jitter_secs: 2,
+            ..Default::default()
+        };
+        for _ in 0..100 {
+            assert!(config.jitter() <= Duration::from_secs(2));
+        }
+    }
+
+    #[tokio::test]
+    async fn due_components() {
+        let config = ResourceSchedulerConfig {
+            max_jitter_secs: 0,
+            ..Default::default()
+        };
+        let now = Instant::now();
+        let (new, overdue, waiting, gone) = (
+            ComponentId::generate(),
+            ComponentId::generate(),
+            ComponentId::generate(),
+            ComponentId::generate(),
+        );
+        let mut next_refresh = HashMap::from([
+            (overdue, now - Duration::from_secs(1)),
+            (waiting, now + Duration::from_secs(60)),
+            (gone, now - Duration::from_secs(1)),
+        ]);
+
+        let mut due = config.due([new, overdue, waiting], &mut next_refresh, now);
+        due.sort();
+        let mut expected = vec![new, overdue];
+        expected.sort();
+        assert_eq!(expected, due);
+
+        // Components seen for the first time are remembered, and those which are gone forgotten
+        assert_eq!(Some(&now), next_refresh.get(&new));
+        assert_eq!(
+            Some(&(now + Duration::from_secs(60))),
+            next_refresh.get(&waiting)
+        );
+        assert!(!next_refresh.contains_key(&gone));
+
+        // With jitter, new components wait a little before their first refresh
+        let config = ResourceSchedulerConfig {
+            max_jitter_secs: 30,
+            ..Default::default()
+        };
+        let jittered = ComponentId::generate();
+        let mut next_refresh = HashMap::new();
+        config.due([jittered], &mut next_refresh, now);
+        let due_at = next_refresh[&jittered];
+        assert!(due_at >= now && due_at <= now + Duration::from_secs(30));
+    }
+}
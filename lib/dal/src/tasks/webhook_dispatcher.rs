@@ -0,0 +1,249 @@
+//! The [`WebhookDispatcher`] is a spawned, "long-running" [tokio](https://tokio.rs/) task that
+//! listens for every [`WsEvent`] published over [NATS](https://nats.io) and delivers it to the
+//! [`Webhooks`](Webhook) of its workspace which subscribed to its kind.
+//!
+//! Each delivery is stored as a [`WebhookPendingDelivery`] before it is attempted, so that it
+//! survives the dispatcher stopping. A failed delivery is retried up to
+//! [`MAX_ATTEMPTS`](WebhookDispatcher::MAX_ATTEMPTS) times, waiting twice as long before each
+//! retry, starting from [`INITIAL_BACKOFF`]. Every [`POLL_INTERVAL`], the dispatcher attempts the
+//! deliveries which have come due. Every attempt is logged as a [`WebhookDelivery`].
+
+use std::time::Duration;
+
+use futures::{FutureExt, StreamExt};
+use nats_subscriber::{SubscriberError, Subscription};
+use si_data_nats::NatsError;
+use si_data_pg::{PgError, PgPoolError};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{sync::broadcast, time};
+
+use crate::{
+    ServicesContext, Tenancy, TransactionsError, Webhook, WebhookDelivery, WebhookError,
+    WebhookPendingDelivery, WsEvent,
+};
+
+/// The [NATS](https://nats.io) subject [`WsEvents`](WsEvent) are published on, for every
+/// workspace.
+const WEBHOOK_DISPATCHER_SUBJECT: &str = "si.workspace_pk.*.event";
+/// The queue name for [NATS](https://nats.io), so that each event is delivered once no matter how
+/// many dispatchers are running.
+const WEBHOOK_DISPATCHER_QUEUE_NAME: &str = "webhookDispatcher";
+/// How long to wait before the first retry.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// How often to look for deliveries whose retry has come due.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// The most deliveries claimed in one go.
+const CLAIM_BATCH_SIZE: i64 = 100;
+/// How long a claimed delivery is held back from other dispatchers. It has to outlast an attempt.
+const CLAIM_LEASE: Duration = Duration::from_secs(60);
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WebhookDispatcherError {
+    #[error(transparent)]
+    Nats(#[from] NatsError),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error(transparent)]
+    PgPool(#[from] PgPoolError),
+    #[error(transparent)]
+    Subscriber(#[from] SubscriberError),
+    #[error(transparent)]
+    Transactions(#[from] TransactionsError),
+    #[error(transparent)]
+    Webhook(#[from] WebhookError),
+}
+
+pub type WebhookDispatcherResult<T> = Result<T, WebhookDispatcherError>;
+
+/// The [`WebhookDispatcher`] posts [`WsEvents`](WsEvent) to the [`Webhooks`](Webhook) which
+/// subscribed to them.
+#[derive(Debug)]
+pub struct WebhookDispatcher {
+    /// The [`ServicesContext`](crate::ServicesContext) needed to assemble a
+    /// [`DalContext`](crate::DalContext) for looking up webhooks and logging deliveries.
+    services_context: ServicesContext,
+    /// A [NATS](https://nats.io) subscription to every workspace's [`WsEvents`](WsEvent).
+    events: Subscription<WsEvent>,
+    client: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    /// How many times a delivery is attempted before giving up.
+    pub const MAX_ATTEMPTS: i32 = 5;
+
+    /// Create a new [`WebhookDispatcher`].
+    pub async fn new(services_context: ServicesContext) -> WebhookDispatcherResult<Self> {
+        let nats = services_context.nats_conn();
+        let events: Subscription<WsEvent> = Subscription::create(WEBHOOK_DISPATCHER_SUBJECT)
+            .queue_name(WEBHOOK_DISPATCHER_QUEUE_NAME)
+            .start(nats)
+            .await?;
+        Ok(Self {
+            services_context,
+            events,
+            client: Webhook::http_client()?,
+        })
+    }
+
+    /// A _synchronous_ function that starts the [`dispatcher`](Self) in a new asynchronous task.
+    pub fn start(self, shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        info!("starting webhook dispatcher");
+        tokio::spawn(self.start_task(shutdown_broadcast_rx));
+    }
+
+    /// The "inner" portion of [`Self::start()`] that contains the core listener loop.
+    #[instrument(name = "webhook_dispatcher.start_task", skip_all, level = "debug")]
+    async fn start_task(mut self, mut shutdown_broadcast_rx: broadcast::Receiver<()>) {
+        let mut interval = time::interval(POLL_INTERVAL);
+        interval.set_missed_tick_behavior(time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = shutdown_broadcast_rx.recv() => {
+                    trace!("the webhook dispatcher task received shutdown");
+                    break;
+                }
+                _ = interval.tick() => {
+                    let services_context = self.services_context.clone();
+                    let client = self.client.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = Self::deliver_due(services_context, client).await {
+                            error!("Unable to deliver due webhook events: {err}");
+                        }
+                    });
+                }
+                event = self.events.next() => {
+                    match event {
+                        Some(Ok(event)) => {
+                            let services_context = self.services_context.clone();
+                            let client = self.client.clone();
+                            tokio::spawn(async move {
+                                if let Err(err) = Self::dispatch(services_context, client, event.payload).await {
+                                    error!("Unable to dispatch event to webhooks: {err}");
+                                }
+                            });
+                        }
+                        Some(Err(err)) => {
+                            warn!(error = ?err, "next webhook dispatcher event errored");
+                        }
+                        None => {
+                            trace!("webhook dispatcher events subscriber stream has closed");
+                            break;
+                        }
+                    }
+                }
+                else => {
+                    trace!("returning with all select arms closed");
+                    break
+                }
+            }
+        }
+
+        // Unsubscribe from subscription.
+        if let Err(e) = self.events.unsubscribe().await {
+            error!("could not unsubscribe from nats: {:?}", e);
+        }
+    }
+
+    /// Queue the event for each webhook subscribed to it and then attempt the deliveries.
+    async fn dispatch(
+        services_context: ServicesContext,
+        client: reqwest::Client,
+        event: WsEvent,
+    ) -> WebhookDispatcherResult<()> {
+        let mut ctx = services_context
+            .clone()
+            .into_builder(false)
+            .build_default()
+            .await?;
+        ctx.update_tenancy(Tenancy::new(event.workspace_pk()));
+        for webhook in Webhook::list_for_event_kind(&ctx, event.payload().as_ref()).await? {
+            WebhookPendingDelivery::new(&ctx, webhook.pk, &event).await?;
+        }
+        ctx.commit().await?;
+
+        Self::deliver_due(services_context, client).await
+    }
+
+    /// Claim the pending deliveries which are due, from every workspace, and attempt each of them
+    /// concurrently.
+    async fn deliver_due(
+        services_context: ServicesContext,
+        client: reqwest::Client,
+    ) -> WebhookDispatcherResult<()> {
+        let ctx = services_context
+            .clone()
+            .into_builder(false)
+            .build_default()
+            .await?;
+        let pending_deliveries =
+            WebhookPendingDelivery::claim_due(&ctx, CLAIM_BATCH_SIZE, CLAIM_LEASE).await?;
+        ctx.commit().await?;
+
+        futures::future::join_all(pending_deliveries.into_iter().map(|pending_delivery| {
+            let pending_delivery_pk = pending_delivery.pk;
+            Self::deliver(services_context.clone(), client.clone(), pending_delivery).map(
+                move |result| {
+                    if let Err(err) = result {
+                        error!(%pending_delivery_pk, "Unable to deliver webhook event: {err}");
+                    }
+                },
+            )
+        }))
+        .await;
+
+        Ok(())
+    }
+
+    /// Make the next attempt at a delivery and log it. The delivery is kept for a retry if it
+    /// failed and [`MAX_ATTEMPTS`](Self::MAX_ATTEMPTS) hasn't been reached yet.
+    ///
+    /// The delivery is expected to have been claimed already, see
+    /// [`WebhookPendingDelivery::claim_due`].
+    #[instrument(name = "webhook_dispatcher.deliver", skip_all, level = "debug", fields(webhook_pk = %pending_delivery.webhook_pk))]
+    pub async fn deliver(
+        services_context: ServicesContext,
+        client: reqwest::Client,
+        mut pending_delivery: WebhookPendingDelivery,
+    ) -> WebhookDispatcherResult<()> {
+        let mut ctx = services_context
+            .clone()
+            .into_builder(false)
+            .build_default()
+            .await?;
+        ctx.update_tenancy(pending_delivery.tenancy);
+
+        let webhook = match Webhook::get_by_pk(&ctx, pending_delivery.webhook_pk).await? {
+            Some(webhook) if webhook.enabled => webhook,
+            _ => {
+                pending_delivery.finish(&ctx).await?;
+                ctx.commit().await?;
+                return Ok(());
+            }
+        };
+        // Don't hold a transaction open while waiting on the receiver
+        ctx.commit().await?;
+
+        let attempt = pending_delivery.attempt + 1;
+        let outcome = webhook
+            .send(&client, pending_delivery.pk, &pending_delivery.event)
+            .await;
+        WebhookDelivery::new(&ctx, webhook.pk, &pending_delivery.event, attempt, &outcome).await?;
+        match outcome.error {
+            None => pending_delivery.finish(&ctx).await?,
+            Some(err) if attempt < Self::MAX_ATTEMPTS => {
+                debug!(attempt, "Webhook delivery failed, retrying: {err}");
+                let backoff = INITIAL_BACKOFF * 2u32.pow(attempt as u32 - 1);
+                pending_delivery.reschedule(&ctx, attempt, backoff).await?;
+            }
+            Some(err) => {
+                warn!(url = %webhook.url, "Giving up on webhook delivery: {err}");
+                pending_delivery.finish(&ctx).await?;
+            }
+        }
+        ctx.commit().await?;
+
+        Ok(())
+    }
+}
@@ -10,6 +10,10 @@ use crate::{
     HistoryEventError, JwtPublicSigningKey, Tenancy, Timestamp, TransactionsError, WorkspacePk,
 };
 
+pub mod role;
+
+pub use role::{AccessLevel, Permission, PermissionScope, WorkspaceRole};
+
 const USER_GET_BY_PK: &str = include_str!("queries/user/get_by_pk.sql");
 
 #[remain::sorted]
@@ -23,6 +27,8 @@ pub enum UserError {
     JwtKey(#[from] JwtKeyError),
     #[error("nats txn error: {0}")]
     Nats(#[from] NatsError),
+    #[error("user {0} is not a member of workspace {1}")]
+    NotAWorkspaceMember(UserPk, WorkspacePk),
     #[error("user not found in tenancy: {0} {1:?}")]
     NotFoundInTenancy(UserPk, Tenancy),
     #[error("no workspace in tenancy")]
@@ -33,6 +39,8 @@ pub enum UserError {
     SerdeJson(#[from] serde_json::Error),
     #[error(transparent)]
     Transactions(#[from] TransactionsError),
+    #[error("unknown workspace role: {0}")]
+    UnknownWorkspaceRole(String),
 }
 
 pub type UserResult<T> = Result<T, UserError>;
@@ -122,17 +130,31 @@ impl User {
         Ok(true)
     }
 
+    /// Makes the user a member of the workspace as an [`Editor`](WorkspaceRole::Editor). An
+    /// existing member keeps their role.
     pub async fn associate_workspace(
         &self,
         ctx: &DalContext,
         workspace_pk: WorkspacePk,
+    ) -> UserResult<()> {
+        self.associate_workspace_with_role(ctx, workspace_pk, WorkspaceRole::Editor)
+            .await
+    }
+
+    /// Makes the user a member of the workspace with the given [`WorkspaceRole`]. An existing
+    /// member keeps their role.
+    pub async fn associate_workspace_with_role(
+        &self,
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        role: WorkspaceRole,
     ) -> UserResult<()> {
         ctx.txns()
             .await?
             .pg()
             .execute(
-                "SELECT user_associate_workspace_v1($1, $2)",
-                &[&self.pk, &workspace_pk],
+                "SELECT user_associate_workspace_v2($1, $2, $3)",
+                &[&self.pk, &workspace_pk, &role.as_ref()],
             )
             .await?;
         Ok(())
@@ -0,0 +1,232 @@
+//! This module contains [`WorkspaceRole`], which decides what a [`User`] may do within a
+//! [`Workspace`](crate::Workspace) they are a member of.
+//!
+//! [`Tenancy`](crate::Tenancy) keeps workspaces apart; roles limit what each member can do inside
+//! one. Every role can read everything a viewer can, and each role adds to the one before it:
+//!
+//! | role       | adds                                                              |
+//! |------------|-------------------------------------------------------------------|
+//! | `Viewer`   | reading everything but secrets                                    |
+//! | `Editor`   | writing funcs, change sets, secrets and the model; running fixes  |
+//! | `Approver` | approving and applying change sets                                |
+//! | `Admin`    | installing packages and managing the workspace and its members    |
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use strum::{AsRefStr, Display, EnumIter, EnumString};
+use telemetry::prelude::*;
+
+use crate::user::{User, UserError, UserPk, UserResult};
+use crate::{DalContext, HistoryEvent, WorkspacePk};
+
+const USER_WORKSPACE_ROLE: &str = include_str!("../queries/user/workspace_role.sql");
+const USER_LIST_WORKSPACE_MEMBERS: &str =
+    include_str!("../queries/user/list_workspace_members.sql");
+
+/// A member's role within a [`Workspace`](crate::Workspace).
+#[remain::sorted]
+#[derive(
+    Deserialize,
+    Serialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Display,
+    EnumString,
+    EnumIter,
+    AsRefStr,
+)]
+pub enum WorkspaceRole {
+    Admin,
+    Approver,
+    Editor,
+    Viewer,
+}
+
+/// The area of a workspace that a [`Permission`] is about.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum PermissionScope {
+    ChangeSet,
+    Fix,
+    Func,
+    /// Components, schemas, the diagram and everything else that makes up the model.
+    Model,
+    Pkg,
+    Secret,
+    Workspace,
+}
+
+/// What kind of access a [`Permission`] asks for.
+#[remain::sorted]
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Display)]
+pub enum AccessLevel {
+    /// Approving, rejecting or applying a change set.
+    Approve,
+    Read,
+    Write,
+}
+
+/// Something a member may or may not do, depending on their [`WorkspaceRole`].
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Permission {
+    pub scope: PermissionScope,
+    pub level: AccessLevel,
+}
+
+impl Permission {
+    pub fn new(scope: PermissionScope, level: AccessLevel) -> Self {
+        Self { scope, level }
+    }
+}
+
+impl WorkspaceRole {
+    /// Whether the role grants the [`Permission`].
+    pub fn allows(&self, permission: Permission) -> bool {
+        use AccessLevel::*;
+        use PermissionScope::*;
+
+        match (self, permission.scope, permission.level) {
+            (Self::Admin, _, _) => true,
+
+            (_, Pkg | Workspace, Write | Approve) => false,
+            (Self::Viewer, Secret, _) => false,
+            (_, _, Read) => true,
+
+            (Self::Viewer, _, _) => false,
+            (Self::Editor, _, Approve) => false,
+            (Self::Editor | Self::Approver, _, Write) => true,
+            (Self::Approver, ChangeSet, Approve) => true,
+            (Self::Approver, _, Approve) => false,
+        }
+    }
+}
+
+impl User {
+    /// The user's [`WorkspaceRole`] in the workspace, or `None` if they aren't a member.
+    #[instrument(skip(ctx))]
+    pub async fn workspace_role(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+    ) -> UserResult<Option<WorkspaceRole>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(USER_WORKSPACE_ROLE, &[&user_pk, &workspace_pk])
+            .await?;
+        match row {
+            Some(row) => {
+                let role: String = row.try_get("role")?;
+                Ok(Some(WorkspaceRole::from_str(&role).map_err(|_| {
+                    UserError::UnknownWorkspaceRole(role.clone())
+                })?))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Whether the user is a member of the workspace with a role that grants the [`Permission`].
+    pub async fn is_permitted(
+        ctx: &DalContext,
+        user_pk: UserPk,
+        workspace_pk: WorkspacePk,
+        permission: Permission,
+    ) -> UserResult<bool> {
+        Ok(Self::workspace_role(ctx, user_pk, workspace_pk)
+            .await?
+            .map(|role| role.allows(permission))
+            .unwrap_or(false))
+    }
+
+    /// Change the user's role in a workspace they are already a member of.
+    #[instrument(skip(ctx))]
+    pub async fn set_workspace_role(
+        &self,
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+        role: WorkspaceRole,
+    ) -> UserResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT found FROM user_set_workspace_role_v1($1, $2, $3)",
+                &[&self.pk, &workspace_pk, &role.as_ref()],
+            )
+            .await?;
+        let found: bool = row.try_get("found")?;
+        if !found {
+            return Err(UserError::NotAWorkspaceMember(self.pk, workspace_pk));
+        }
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "user.set_workspace_role",
+            "User workspace role changed",
+            &serde_json::json![{ "userPk": self.pk, "workspacePk": workspace_pk, "role": role }],
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// List the members of a workspace along with their roles.
+    #[instrument(skip(ctx))]
+    pub async fn list_workspace_members(
+        ctx: &DalContext,
+        workspace_pk: WorkspacePk,
+    ) -> UserResult<Vec<(User, WorkspaceRole)>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(USER_LIST_WORKSPACE_MEMBERS, &[&workspace_pk])
+            .await?;
+        let mut members = Vec::with_capacity(rows.len());
+        for row in rows {
+            let json: serde_json::Value = row.try_get("object")?;
+            let role: String = row.try_get("role")?;
+            let role = WorkspaceRole::from_str(&role)
+                .map_err(|_| UserError::UnknownWorkspaceRole(role.clone()))?;
+            members.push((serde_json::from_value(json)?, role));
+        }
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_build_on_each_other() {
+        let read_secrets = Permission::new(PermissionScope::Secret, AccessLevel::Read);
+        let write_funcs = Permission::new(PermissionScope::Func, AccessLevel::Write);
+        let apply_change_sets = Permission::new(PermissionScope::ChangeSet, AccessLevel::Approve);
+        let install_pkgs = Permission::new(PermissionScope::Pkg, AccessLevel::Write);
+
+        assert!(
+            WorkspaceRole::Viewer.allows(Permission::new(PermissionScope::Func, AccessLevel::Read))
+        );
+        assert!(!WorkspaceRole::Viewer.allows(read_secrets));
+        assert!(!WorkspaceRole::Viewer.allows(write_funcs));
+
+        assert!(WorkspaceRole::Editor.allows(read_secrets));
+        assert!(WorkspaceRole::Editor.allows(write_funcs));
+        assert!(!WorkspaceRole::Editor.allows(apply_change_sets));
+
+        assert!(WorkspaceRole::Approver.allows(apply_change_sets));
+        assert!(!WorkspaceRole::Approver.allows(install_pkgs));
+
+        assert!(WorkspaceRole::Admin.allows(install_pkgs));
+        assert!(WorkspaceRole::Admin.allows(Permission::new(
+            PermissionScope::Workspace,
+            AccessLevel::Write
+        )));
+    }
+}
@@ -49,6 +49,23 @@ pub type ValidationConstructorResult<T> = Result<T, ValidationConstructorError>;
 #[remain::sorted]
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub enum Validation {
+    /// Validate that every item in the "value" array is unique.
+    ArrayItemsAreUnique { value: Option<Vec<Value>> },
+    /// Validate that the length of the "value" array is between the lower and upper bounds
+    /// (inclusive).
+    ArrayLengthIsBetween {
+        value: Option<Vec<Value>>,
+        lower_bound: i64,
+        upper_bound: i64,
+    },
+    /// Validate that the "value" float is between the lower and upper bound floats (inclusive).
+    FloatIsBetweenTwoFloats {
+        value: Option<f64>,
+        lower_bound: f64,
+        upper_bound: f64,
+    },
+    /// Validate that the "value" float is not empty
+    FloatIsNotEmpty { value: Option<f64> },
     /// Validate that the "value" integer is between the lower and upper bound integers.
     IntegerIsBetweenTwoIntegers {
         value: Option<i64>,
@@ -79,12 +96,35 @@ pub enum Validation {
         /// set this field to `true`.
         display_expected: bool,
     },
+    /// Validate that the "value" string is an
+    /// [ARN](https://docs.aws.amazon.com/IAM/latest/UserGuide/reference-arns.html).
+    StringIsArn { value: Option<String> },
     /// Validate that the "value" string is a Hex Color.
     StringIsHexColor { value: Option<String> },
+    /// Validate that the "value" string is a hostname, per
+    /// [RFC 1123](https://datatracker.ietf.org/doc/html/rfc1123#page-13).
+    StringIsHostname { value: Option<String> },
     /// Validate that the "value" string is not empty
     StringIsNotEmpty { value: Option<String> },
+    /// Validate that the "value" string is a valid IPv4 or IPv6 CIDR block (e.g. "10.0.0.0/16").
+    StringIsValidCidr { value: Option<String> },
     /// Validate that the "value" string is a valid [IpAddr](std::net::IpAddr).
     StringIsValidIpAddr { value: Option<String> },
+    /// Validate that the "value" string is a valid absolute URL.
+    StringIsValidUrl { value: Option<String> },
+    /// Validate that the number of characters in the "value" string is between the lower and
+    /// upper bounds (inclusive).
+    StringLengthIsBetween {
+        value: Option<String>,
+        lower_bound: i64,
+        upper_bound: i64,
+    },
+    /// Validate that the "value" string matches the regular expression "pattern". The pattern is
+    /// not anchored, so use `^` and `$` to match the whole string.
+    StringMatchesRegex {
+        value: Option<String>,
+        pattern: String,
+    },
 }
 
 impl Validation {
@@ -92,6 +132,27 @@ impl Validation {
     /// remaining fields' values will be identical.
     pub fn update_value(self, value: &Option<Value>) -> ValidationConstructorResult<Self> {
         let validation = match self {
+            Validation::ArrayItemsAreUnique { value: _ } => Validation::ArrayItemsAreUnique {
+                value: Self::value_as_array(value)?,
+            },
+            Validation::ArrayLengthIsBetween {
+                value: _,
+                lower_bound,
+                upper_bound,
+            } => Validation::ArrayLengthIsBetween {
+                value: Self::value_as_array(value)?,
+                lower_bound,
+                upper_bound,
+            },
+            Validation::FloatIsBetweenTwoFloats {
+                value: _,
+                lower_bound,
+                upper_bound,
+            } => Validation::FloatIsBetweenTwoFloats {
+                value: Self::value_as_f64(value)?,
+                lower_bound,
+                upper_bound,
+            },
             Validation::IntegerIsBetweenTwoIntegers {
                 value: _,
                 lower_bound,
@@ -101,6 +162,9 @@ impl Validation {
                 lower_bound,
                 upper_bound,
             },
+            Validation::FloatIsNotEmpty { value: _ } => Validation::FloatIsNotEmpty {
+                value: Self::value_as_f64(value)?,
+            },
             Validation::IntegerIsNotEmpty { value: _ } => Validation::IntegerIsNotEmpty {
                 value: Self::value_as_i64(value)?,
             },
@@ -130,6 +194,33 @@ impl Validation {
             Validation::StringIsNotEmpty { value: _ } => Validation::StringIsNotEmpty {
                 value: Self::value_as_string(value)?,
             },
+            Validation::StringIsArn { value: _ } => Validation::StringIsArn {
+                value: Self::value_as_string(value)?,
+            },
+            Validation::StringIsHostname { value: _ } => Validation::StringIsHostname {
+                value: Self::value_as_string(value)?,
+            },
+            Validation::StringIsValidCidr { value: _ } => Validation::StringIsValidCidr {
+                value: Self::value_as_string(value)?,
+            },
+            Validation::StringIsValidUrl { value: _ } => Validation::StringIsValidUrl {
+                value: Self::value_as_string(value)?,
+            },
+            Validation::StringLengthIsBetween {
+                value: _,
+                lower_bound,
+                upper_bound,
+            } => Validation::StringLengthIsBetween {
+                value: Self::value_as_string(value)?,
+                lower_bound,
+                upper_bound,
+            },
+            Validation::StringMatchesRegex { value: _, pattern } => {
+                Validation::StringMatchesRegex {
+                    value: Self::value_as_string(value)?,
+                    pattern,
+                }
+            }
         };
         Ok(validation)
     }
@@ -147,6 +238,34 @@ impl Validation {
         }
     }
 
+    fn value_as_f64(maybe_value: &Option<Value>) -> ValidationConstructorResult<Option<f64>> {
+        match maybe_value {
+            Some(value) => match value.as_f64() {
+                Some(success_value) => Ok(Some(success_value)),
+                None => Err(ValidationConstructorError::InvalidValueKind(
+                    "f64",
+                    value.clone(),
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn value_as_array(
+        maybe_value: &Option<Value>,
+    ) -> ValidationConstructorResult<Option<Vec<Value>>> {
+        match maybe_value {
+            Some(value) => match value.as_array() {
+                Some(success_value) => Ok(Some(success_value.clone())),
+                None => Err(ValidationConstructorError::InvalidValueKind(
+                    "Array",
+                    value.clone(),
+                )),
+            },
+            None => Ok(None),
+        }
+    }
+
     fn value_as_i64(maybe_value: &Option<Value>) -> ValidationConstructorResult<Option<i64>> {
         match maybe_value {
             Some(value) => match value.as_i64() {
@@ -164,12 +283,22 @@ impl Validation {
 #[remain::sorted]
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
 pub enum ValidationErrorKind {
+    ArrayItemsNotUnique,
+    ArrayLengthNotInBetween,
+    FloatNotInBetweenTwoFloats,
     IntegerNotInBetweenTwoIntegers,
+    InvalidArn,
+    InvalidCidr,
     InvalidHexString,
+    InvalidHostname,
     InvalidIpAddr,
+    InvalidRegex,
+    InvalidUrl,
     JsValidation,
     StringDoesNotEqual,
     StringDoesNotHavePrefix,
+    StringDoesNotMatchRegex,
+    StringLengthNotInBetween,
     StringNotInStringArray,
     ValueMustBePresent,
 }
@@ -177,11 +306,21 @@ pub enum ValidationErrorKind {
 impl ValidationErrorKind {
     pub fn as_str(&self) -> &'static str {
         match self {
+            Self::ArrayItemsNotUnique => "ArrayItemsNotUnique",
+            Self::ArrayLengthNotInBetween => "ArrayLengthNotInBetween",
+            Self::FloatNotInBetweenTwoFloats => "FloatNotInBetweenTwoFloats",
             Self::IntegerNotInBetweenTwoIntegers => "IntegerNotInBetweenTwoIntegers",
+            Self::InvalidArn => "InvalidArn",
+            Self::InvalidCidr => "InvalidCidr",
             Self::InvalidHexString => "InvalidHexString",
+            Self::InvalidHostname => "InvalidHostname",
             Self::InvalidIpAddr => "InvalidIpAddr",
+            Self::InvalidRegex => "InvalidRegex",
+            Self::InvalidUrl => "InvalidUrl",
             Self::StringDoesNotEqual => "StringDoesNotEqual",
             Self::StringDoesNotHavePrefix => "StringDoesNotHavePrefix",
+            Self::StringDoesNotMatchRegex => "StringDoesNotMatchRegex",
+            Self::StringLengthNotInBetween => "StringLengthNotInBetween",
             Self::StringNotInStringArray => "StringNotInStringArray",
             Self::ValueMustBePresent => "ValueMustBePresent",
             Self::JsValidation => "JsValidation",
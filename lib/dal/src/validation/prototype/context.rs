@@ -83,6 +83,7 @@ impl ValidationPrototypeContextBuilder {
         let prop_kind = prop.kind();
         if prop_kind != &PropKind::String
             && prop_kind != &PropKind::Integer
+            && prop_kind != &PropKind::Float
             && prop_kind != &PropKind::Boolean
         {
             return Err(ValidationPrototypeError::ContextPropKindIsNotPrimitive(
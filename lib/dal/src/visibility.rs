@@ -1,4 +1,4 @@
-use crate::{DalContext, TransactionsError};
+use crate::{DalContext, LabelList, LabelListError, TransactionsError};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use si_data_pg::PgError;
@@ -11,6 +11,8 @@ use serde_aux::field_attributes::deserialize_number_from_string;
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum VisibilityError {
+    #[error("label list error: {0}")]
+    LabelList(#[from] LabelListError),
     #[error("pg error: {0}")]
     Pg(#[from] PgError),
     #[error("transactions error: {0}")]
@@ -19,6 +21,8 @@ pub enum VisibilityError {
 
 pub type VisibilityResult<T> = Result<T, VisibilityError>;
 
+const CHANGE_SET_OPEN_LIST: &str = include_str!("queries/change_set/open_list.sql");
+
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Visibility {
     #[serde(
@@ -86,6 +90,27 @@ impl Visibility {
         Self::new_change_set(self.change_set_pk, self.deleted_at.is_some())
     }
 
+    /// Lists the non-deleted [`Visibilities`](Self) of _head_ and of every open change set in the
+    /// workspace, for looking something up wherever it may exist.
+    #[instrument(skip_all)]
+    pub async fn list_open_in_workspace(ctx: &DalContext) -> VisibilityResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(CHANGE_SET_OPEN_LIST, &[ctx.tenancy()])
+            .await?;
+        let open_change_sets: LabelList<ChangeSetPk> = LabelList::from_rows(rows)?;
+
+        let mut visibilities = vec![Self::new_head(false)];
+        visibilities.extend(
+            open_change_sets
+                .iter()
+                .map(|entry| Self::new_change_set(entry.value, false)),
+        );
+        Ok(visibilities)
+    }
+
     /// Returns true if this [`Visibility`] is in a working changeset (and not in head)
     #[instrument]
     pub fn in_change_set(&self) -> bool {
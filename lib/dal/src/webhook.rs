@@ -0,0 +1,636 @@
+//! This module contains [`Webhook`], which posts a workspace's [`WsEvents`](WsEvent) to an
+//! outside URL, and [`WebhookDelivery`], which logs each attempt at doing so.
+//!
+//! A webhook only receives events whose [`WsPayload`] kind it subscribed to, such as
+//! `"ChangeSetApplied"` or `"FixBatchReturn"`. Each event is posted as the JSON of the
+//! [`WsEvent`], signed with the webhook's secret using HMAC-SHA256. The signature is sent in the
+//! [`WEBHOOK_SIGNATURE_HEADER`] header as `sha256=<hex digest>`, so that the receiver can check
+//! that the event came from us.
+//!
+//! Deliveries are made by the [`WebhookDispatcher`](crate::tasks::WebhookDispatcher), which keeps
+//! each one as a [`WebhookPendingDelivery`] until it succeeds or runs out of attempts.
+//!
+//! Webhook URLs are chosen by users, so they must not be able to reach services inside our own
+//! network. The [client](Webhook::http_client()) deliveries are sent with only connects to public
+//! addresses, checked after the receiver's host is resolved rather than only when the URL is
+//! parsed, and never follows redirects.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use serde::{Deserialize, Serialize};
+use si_data_pg::PgError;
+use sodiumoxide::crypto::auth::hmacsha256;
+use strum::VariantNames;
+use telemetry::prelude::*;
+use thiserror::Error;
+
+use crate::standard_model::{object_option_from_row_option, objects_from_rows};
+use crate::{
+    pk, DalContext, HistoryEvent, HistoryEventError, StandardModelError, Tenancy, Timestamp,
+    TransactionsError, WsEvent, WsPayload,
+};
+
+const WEBHOOK_GET_BY_PK: &str = include_str!("queries/webhook/get_by_pk.sql");
+const WEBHOOK_LIST: &str = include_str!("queries/webhook/list.sql");
+const WEBHOOK_LIST_DELIVERIES: &str = include_str!("queries/webhook/list_deliveries.sql");
+const WEBHOOK_LIST_FOR_EVENT_KIND: &str = include_str!("queries/webhook/list_for_event_kind.sql");
+const WEBHOOK_CLAIM_DUE_PENDING_DELIVERIES: &str =
+    include_str!("queries/webhook/claim_due_pending_deliveries.sql");
+
+/// The header holding the signature of a delivery's body.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-SI-Signature-256";
+/// The header holding the [`WsPayload`] kind of a delivery.
+pub const WEBHOOK_EVENT_HEADER: &str = "X-SI-Event";
+/// The header identifying the event being delivered, which stays the same when a delivery is
+/// retried so that receivers can ignore duplicates.
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-SI-Delivery";
+
+/// How long to wait for the receiver to respond to a delivery.
+const WEBHOOK_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error("history event error: {0}")]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error("invalid webhook url {0}: only http and https urls are allowed")]
+    InvalidUrl(String),
+    #[error("a webhook needs at least one event kind")]
+    NoEventKinds,
+    #[error("invalid webhook url {0}: the host is not a public address")]
+    NonPublicAddress(String),
+    #[error("webhook not found: {0}")]
+    NotFound(WebhookPk),
+    #[error(transparent)]
+    Pg(#[from] PgError),
+    #[error("http client error: {0}")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("error serializing/deserializing json: {0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("standard model error: {0}")]
+    StandardModel(#[from] StandardModelError),
+    #[error("transactions error: {0}")]
+    Transactions(#[from] TransactionsError),
+    #[error("unknown event kind: {0}")]
+    UnknownEventKind(String),
+}
+
+pub type WebhookResult<T> = Result<T, WebhookError>;
+
+pk!(WebhookPk);
+pk!(WebhookDeliveryPk);
+pk!(WebhookPendingDeliveryPk);
+
+/// An outside URL which is sent the workspace's events of the kinds it subscribed to.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    pub pk: WebhookPk,
+    pub url: String,
+    /// Only handed out when the webhook is created.
+    #[serde(default, skip_serializing)]
+    secret: String,
+    pub event_kinds: Vec<String>,
+    pub description: Option<String>,
+    pub enabled: bool,
+    #[serde(flatten)]
+    pub tenancy: Tenancy,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+impl Webhook {
+    /// Create a webhook for the workspace with a freshly generated secret, which is returned by
+    /// [`Self::secret()`].
+    #[instrument(skip(ctx))]
+    pub async fn new(
+        ctx: &DalContext,
+        url: impl AsRef<str> + std::fmt::Debug,
+        event_kinds: Vec<String>,
+        description: Option<String>,
+    ) -> WebhookResult<Self> {
+        let url = url.as_ref();
+        match url::Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {
+                if !has_public_host(&parsed) {
+                    return Err(WebhookError::NonPublicAddress(url.to_owned()));
+                }
+            }
+            _ => return Err(WebhookError::InvalidUrl(url.to_owned())),
+        }
+        if event_kinds.is_empty() {
+            return Err(WebhookError::NoEventKinds);
+        }
+        if let Some(unknown) = event_kinds
+            .iter()
+            .find(|kind| !WsPayload::VARIANTS.contains(&kind.as_str()))
+        {
+            return Err(WebhookError::UnknownEventKind(unknown.clone()));
+        }
+
+        let mut secret = [0; 32];
+        sodiumoxide::randombytes::randombytes_into(&mut secret);
+        let secret = hex::encode(secret);
+
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM webhook_create_v1($1, $2, $3, $4, $5)",
+                &[&url, &secret, &event_kinds, &description, ctx.tenancy()],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        let webhook: Self = serde_json::from_value(json)?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "webhook.create",
+            "Webhook created",
+            &serde_json::json![{ "pk": &webhook.pk, "url": &webhook.url, "eventKinds": &webhook.event_kinds }],
+        )
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// The secret deliveries are signed with.
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    #[instrument(skip(ctx))]
+    pub async fn get_by_pk(ctx: &DalContext, pk: WebhookPk) -> WebhookResult<Option<Self>> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_opt(WEBHOOK_GET_BY_PK, &[ctx.tenancy(), &pk])
+            .await?;
+        Ok(object_option_from_row_option(row)?)
+    }
+
+    #[instrument(skip(ctx))]
+    pub async fn list(ctx: &DalContext) -> WebhookResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(WEBHOOK_LIST, &[ctx.tenancy()])
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+
+    /// List the workspace's enabled webhooks which subscribed to the [`WsPayload`] kind.
+    #[instrument(skip(ctx))]
+    pub async fn list_for_event_kind(
+        ctx: &DalContext,
+        event_kind: &str,
+    ) -> WebhookResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(WEBHOOK_LIST_FOR_EVENT_KIND, &[ctx.tenancy(), &event_kind])
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+
+    /// Stop or resume deliveries to the webhook.
+    // The webhook's secret stays out of traces
+    #[instrument(skip_all, fields(webhook_pk = %self.pk))]
+    pub async fn set_enabled(&mut self, ctx: &DalContext, enabled: bool) -> WebhookResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "UPDATE webhooks SET enabled = $3, updated_at = clock_timestamp()
+                 WHERE pk = $2 AND in_tenancy_v1($1, tenancy_workspace_pk)
+                 RETURNING updated_at",
+                &[ctx.tenancy(), &self.pk, &enabled],
+            )
+            .await?;
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+        self.enabled = enabled;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "webhook.set_enabled",
+            "Webhook enabled changed",
+            &serde_json::json![{ "pk": &self.pk, "enabled": enabled }],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Delete the webhook along with its delivery log.
+    #[instrument(skip_all, fields(webhook_pk = %self.pk))]
+    pub async fn delete(self, ctx: &DalContext) -> WebhookResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "DELETE FROM webhooks WHERE pk = $2 AND in_tenancy_v1($1, tenancy_workspace_pk)",
+                &[ctx.tenancy(), &self.pk],
+            )
+            .await?;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "webhook.delete",
+            "Webhook deleted",
+            &serde_json::json![{ "pk": &self.pk, "url": &self.url }],
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// List the most recent delivery attempts for the webhook, newest first.
+    pub async fn deliveries(
+        &self,
+        ctx: &DalContext,
+        limit: i64,
+    ) -> WebhookResult<Vec<WebhookDelivery>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(WEBHOOK_LIST_DELIVERIES, &[ctx.tenancy(), &self.pk, &limit])
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+
+    /// Sign a body with the secret, in the form sent in the [`WEBHOOK_SIGNATURE_HEADER`] header.
+    pub fn sign(secret: &str, body: &[u8]) -> String {
+        let mut state = hmacsha256::State::init(secret.as_bytes());
+        state.update(body);
+        format!("sha256={}", hex::encode(state.finalize().as_ref()))
+    }
+
+    /// The client deliveries are sent with. It refuses to connect to addresses which aren't
+    /// public and doesn't follow redirects, which could otherwise lead it somewhere it mustn't go.
+    pub fn http_client() -> WebhookResult<reqwest::Client> {
+        Ok(reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(PublicAddressResolver))
+            // A proxy would resolve the host itself, out of reach of our resolver
+            .no_proxy()
+            .build()?)
+    }
+
+    /// Post the event to the webhook once, with the [client](Self::http_client()). Only a
+    /// success response counts as delivered.
+    pub async fn send(
+        &self,
+        client: &reqwest::Client,
+        delivery_id: WebhookPendingDeliveryPk,
+        event: &WsEvent,
+    ) -> WebhookSendOutcome {
+        // Hosts given as addresses are never resolved, so the resolver can't check them
+        match url::Url::parse(&self.url) {
+            Ok(url) if has_public_host(&url) => {}
+            _ => {
+                return WebhookSendOutcome::failed(
+                    None,
+                    WebhookError::NonPublicAddress(self.url.clone()),
+                )
+            }
+        }
+        let body = match serde_json::to_vec(event) {
+            Ok(body) => body,
+            Err(err) => return WebhookSendOutcome::failed(None, err),
+        };
+        let response = client
+            .post(&self.url)
+            .timeout(WEBHOOK_REQUEST_TIMEOUT)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_EVENT_HEADER, event.payload().as_ref())
+            .header(WEBHOOK_DELIVERY_HEADER, delivery_id.to_string())
+            .header(WEBHOOK_SIGNATURE_HEADER, Self::sign(&self.secret, &body))
+            .body(body)
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => WebhookSendOutcome {
+                status_code: Some(response.status().as_u16() as i32),
+                error: None,
+            },
+            Ok(response) => WebhookSendOutcome::failed(
+                Some(response.status().as_u16() as i32),
+                format!("receiver responded with {}", response.status()),
+            ),
+            Err(err) => WebhookSendOutcome::failed(None, err),
+        }
+    }
+}
+
+/// What happened when [`Webhook::send()`] posted an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookSendOutcome {
+    /// The status the receiver responded with, if it responded at all.
+    pub status_code: Option<i32>,
+    /// What went wrong, if the event wasn't delivered.
+    pub error: Option<String>,
+}
+
+impl WebhookSendOutcome {
+    fn failed(status_code: Option<i32>, error: impl ToString) -> Self {
+        Self {
+            status_code,
+            error: Some(error.to_string()),
+        }
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// One attempt at delivering an event to a [`Webhook`].
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub pk: WebhookDeliveryPk,
+    pub webhook_pk: WebhookPk,
+    pub event_kind: String,
+    pub payload: serde_json::Value,
+    /// Counts up from 1 as the delivery is retried.
+    pub attempt: i32,
+    pub succeeded: bool,
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub tenancy: Tenancy,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+impl WebhookDelivery {
+    /// Log an attempt at delivering the event to the webhook.
+    #[instrument(skip(ctx, event))]
+    pub async fn new(
+        ctx: &DalContext,
+        webhook_pk: WebhookPk,
+        event: &WsEvent,
+        attempt: i32,
+        outcome: &WebhookSendOutcome,
+    ) -> WebhookResult<Self> {
+        let payload = serde_json::to_value(event)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM webhook_delivery_create_v1($1, $2, $3, $4, $5, $6, $7, $8)",
+                &[
+                    &webhook_pk,
+                    &event.payload().as_ref(),
+                    &payload,
+                    &attempt,
+                    &outcome.succeeded(),
+                    &outcome.status_code,
+                    &outcome.error,
+                    ctx.tenancy(),
+                ],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        Ok(serde_json::from_value(json)?)
+    }
+}
+
+/// Something to deliver to a [`Webhook`], which is kept until it is delivered or runs out of
+/// attempts so that retries aren't lost when the dispatcher stops.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct WebhookPendingDelivery {
+    /// Sent in the [`WEBHOOK_DELIVERY_HEADER`] header of every attempt.
+    pub pk: WebhookPendingDeliveryPk,
+    pub webhook_pk: WebhookPk,
+    #[serde(rename = "payload")]
+    pub event: WsEvent,
+    /// How many attempts have been made so far.
+    pub attempt: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub tenancy: Tenancy,
+    #[serde(flatten)]
+    pub timestamp: Timestamp,
+}
+
+impl WebhookPendingDelivery {
+    /// Queue the event for delivery to the webhook, due straight away.
+    #[instrument(skip(ctx, event))]
+    pub async fn new(
+        ctx: &DalContext,
+        webhook_pk: WebhookPk,
+        event: &WsEvent,
+    ) -> WebhookResult<Self> {
+        let payload = serde_json::to_value(event)?;
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "SELECT object FROM webhook_pending_delivery_create_v1($1, $2, $3)",
+                &[&webhook_pk, &payload, ctx.tenancy()],
+            )
+            .await?;
+        let json: serde_json::Value = row.try_get("object")?;
+        Ok(serde_json::from_value(json)?)
+    }
+
+    /// Claim up to `limit` deliveries which are due, across every workspace. Each one is held
+    /// back from other claims for `lease`, which must be long enough to make an attempt, so that
+    /// a delivery whose dispatcher stops mid-attempt is picked up again afterwards.
+    #[instrument(skip(ctx))]
+    pub async fn claim_due(
+        ctx: &DalContext,
+        limit: i64,
+        lease: Duration,
+    ) -> WebhookResult<Vec<Self>> {
+        let rows = ctx
+            .txns()
+            .await?
+            .pg()
+            .query(
+                WEBHOOK_CLAIM_DUE_PENDING_DELIVERIES,
+                &[&limit, &lease.as_secs_f64()],
+            )
+            .await?;
+        Ok(objects_from_rows(rows)?)
+    }
+
+    /// Record a failed attempt, making the delivery due again after `delay`.
+    #[instrument(skip_all, fields(pending_delivery_pk = %self.pk))]
+    pub async fn reschedule(
+        &mut self,
+        ctx: &DalContext,
+        attempt: i32,
+        delay: Duration,
+    ) -> WebhookResult<()> {
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "UPDATE webhook_pending_deliveries
+                 SET attempt = $3,
+                     next_attempt_at = clock_timestamp() + make_interval(secs => $4),
+                     updated_at = clock_timestamp()
+                 WHERE pk = $2 AND in_tenancy_v1($1, tenancy_workspace_pk)
+                 RETURNING next_attempt_at, updated_at",
+                &[ctx.tenancy(), &self.pk, &attempt, &delay.as_secs_f64()],
+            )
+            .await?;
+        self.attempt = attempt;
+        self.next_attempt_at = row.try_get("next_attempt_at")?;
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+        Ok(())
+    }
+
+    /// Stop attempting the delivery, whether or not it was delivered.
+    #[instrument(skip_all, fields(pending_delivery_pk = %self.pk))]
+    pub async fn finish(self, ctx: &DalContext) -> WebhookResult<()> {
+        ctx.txns()
+            .await?
+            .pg()
+            .execute(
+                "DELETE FROM webhook_pending_deliveries
+                 WHERE pk = $2 AND in_tenancy_v1($1, tenancy_workspace_pk)",
+                &[ctx.tenancy(), &self.pk],
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+/// Resolves receivers' hosts, leaving out any address which isn't public. A host with no public
+/// addresses fails to resolve.
+#[derive(Debug, Clone, Copy)]
+struct PublicAddressResolver;
+
+impl reqwest::dns::Resolve for PublicAddressResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        Box::pin(async move {
+            let host = name.as_str();
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host, 0))
+                .await?
+                .filter(|addr| is_public_address(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{host} does not resolve to a public address").into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(addrs)
+        })
+    }
+}
+
+/// Whether the URL's host is a name, which [`PublicAddressResolver`] checks when connecting, or a
+/// public address.
+fn has_public_host(url: &url::Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(_)) => true,
+        Some(url::Host::Ipv4(ip)) => is_public_address(ip.into()),
+        Some(url::Host::Ipv6(ip)) => is_public_address(ip.into()),
+        None => false,
+    }
+}
+
+/// Whether the address is reachable on the public internet, as opposed to being loopback,
+/// private, link-local (which includes cloud metadata services) or otherwise reserved.
+fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [first, second, ..] = ip.octets();
+            !(ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // "This network", 0.0.0.0/8
+                || first == 0
+                // Carrier-grade NAT, 100.64.0.0/10
+                || (first == 100 && second & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_address(ip.into()),
+            None => {
+                let first = ip.segments()[0];
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    // Unique local, fc00::/7
+                    || first & 0xfe00 == 0xfc00
+                    // Link-local, fe80::/10
+                    || first & 0xffc0 == 0xfe80)
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_matches_hmac_sha256() {
+        // RFC 4231, test case 2
+        let body = b"what do ya want for nothing?";
+        assert_eq!(
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            Webhook::sign("Jefe", body)
+        );
+    }
+
+    #[test]
+    fn only_public_addresses_are_allowed() {
+        for ip in ["93.184.216.34", "2606:2800:220:1:248:1893:25c8:1946"] {
+            assert!(
+                is_public_address(ip.parse().expect("valid address")),
+                "{ip}"
+            );
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(
+                !is_public_address(ip.parse().expect("valid address")),
+                "{ip}"
+            );
+        }
+    }
+
+    #[test]
+    fn hosts_given_as_addresses_must_be_public() {
+        for (url, public) in [
+            ("https://example.com/hook", true),
+            ("https://93.184.216.34/hook", true),
+            ("http://127.0.0.1:8080/hook", false),
+            ("http://[::1]/hook", false),
+            ("http://169.254.169.254/latest/meta-data", false),
+        ] {
+            let url = url::Url::parse(url).expect("valid url");
+            assert_eq!(public, has_public_host(&url), "{url}");
+        }
+    }
+}
@@ -7,7 +7,7 @@ use thiserror::Error;
 use crate::{
     pk, standard_model, standard_model_accessor_ro, DalContext, HistoryActor, HistoryEvent,
     HistoryEventError, KeyPair, KeyPairError, StandardModelError, Tenancy, Timestamp,
-    TransactionsError, User, UserError, UserPk,
+    TransactionsError, User, UserError, UserPk, WorkspaceRole,
 };
 
 const WORKSPACE_GET_BY_PK: &str = include_str!("queries/workspace/get_by_pk.sql");
@@ -49,6 +49,9 @@ pub struct WorkspaceSignup {
 pub struct Workspace {
     pk: WorkspacePk,
     name: String,
+    /// How many approvals a [`ChangeSet`](crate::ChangeSet) needs before it can be applied.
+    #[serde(default)]
+    required_change_set_approvals: i32,
     #[serde(flatten)]
     timestamp: Timestamp,
 }
@@ -125,6 +128,8 @@ impl Workspace {
             None::<&str>,
         )
         .await?;
+        user.associate_workspace_with_role(ctx, workspace.pk, WorkspaceRole::Admin)
+            .await?;
         ctx.update_history_actor(HistoryActor::User(user.pk()));
 
         ctx.import_builtins().await?;
@@ -166,4 +171,42 @@ impl Workspace {
     }
 
     standard_model_accessor_ro!(name, String);
+
+    pub fn required_change_set_approvals(&self) -> i32 {
+        self.required_change_set_approvals
+    }
+
+    /// Sets how many approvals a [`ChangeSet`](crate::ChangeSet) needs before it can be applied.
+    /// Zero lets any change set be applied without review.
+    #[instrument(skip(ctx))]
+    pub async fn set_required_change_set_approvals(
+        &mut self,
+        ctx: &DalContext,
+        required: i32,
+    ) -> WorkspaceResult<()> {
+        let required = required.max(0);
+        let row = ctx
+            .txns()
+            .await?
+            .pg()
+            .query_one(
+                "UPDATE workspaces
+                 SET required_change_set_approvals = $2, updated_at = clock_timestamp()
+                 WHERE pk = $1
+                 RETURNING updated_at",
+                &[&self.pk, &required],
+            )
+            .await?;
+        self.timestamp.updated_at = row.try_get("updated_at")?;
+        self.required_change_set_approvals = required;
+
+        let _history_event = HistoryEvent::new(
+            ctx,
+            "workspace.update_approval_policy",
+            "Workspace approval policy updated",
+            &serde_json::json![{ "pk": self.pk, "requiredChangeSetApprovals": required }],
+        )
+        .await?;
+        Ok(())
+    }
 }
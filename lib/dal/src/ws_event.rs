@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsError;
 use si_data_pg::PgError;
+use strum::{AsRefStr, EnumVariantNames};
 use thiserror::Error;
 
+use crate::change_set::approval::ChangeSetApprovalDecidedPayload;
 use crate::component::confirmation::ConfirmationsUpdatedPayload;
 use crate::component::ComponentCreatedPayload;
+use crate::drift::ComponentDriftUpdatedPayload;
 use crate::{
     component::{code::CodeGeneratedPayload, resource::ResourceRefreshedPayload},
     fix::{batch::FixBatchReturn, FixReturn},
@@ -34,17 +37,21 @@ pub enum WsEventError {
 pub type WsEventResult<T> = Result<T, WsEventError>;
 
 #[remain::sorted]
-#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq)]
+#[derive(Deserialize, Serialize, Debug, Clone, Eq, PartialEq, AsRefStr, EnumVariantNames)]
 #[serde(tag = "kind", content = "data")]
 #[allow(clippy::large_enum_variant)]
 pub enum WsPayload {
     ChangeSetApplied(ChangeSetPk),
+    ChangeSetApprovalDecided(ChangeSetApprovalDecidedPayload),
+    ChangeSetApprovalRequested(ChangeSetPk),
     ChangeSetCanceled(ChangeSetPk),
     ChangeSetCreated(ChangeSetPk),
+    ChangeSetRebased(ChangeSetPk),
     ChangeSetWritten(ChangeSetPk),
     CheckedQualifications(QualificationCheckPayload),
     CodeGenerated(CodeGeneratedPayload),
     ComponentCreated(ComponentCreatedPayload),
+    ComponentDriftUpdated(ComponentDriftUpdatedPayload),
     ConfirmationsUpdated(ConfirmationsUpdatedPayload),
     FixBatchReturn(FixBatchReturn),
     FixReturn(FixReturn),
@@ -117,6 +124,14 @@ impl WsEvent {
         self.workspace_pk
     }
 
+    pub fn change_set_pk(&self) -> ChangeSetPk {
+        self.change_set_pk
+    }
+
+    pub fn payload(&self) -> &WsPayload {
+        &self.payload
+    }
+
     /// Publishes the [`event`](Self) to the [`NatsTxn`](si_data_nats::NatsTxn). When the
     /// transaction is committed, the [`event`](Self) will be published for external use.
     pub async fn publish_on_commit(&self, ctx: &DalContext) -> WsEventResult<()> {
@@ -1,8 +1,16 @@
 use pretty_assertions_sorted::assert_eq;
 
 use dal::action_prototype::ActionKind;
-use dal::{ActionPrototype, ActionPrototypeContext, DalContext, FuncId};
-use dal_test::test;
+use dal::{
+    ActionPrototype, ActionPrototypeContext, ActionPrototypeError, Component, ComponentView,
+    DalContext, Func, FuncBackendKind, FuncBackendResponseType, FuncId, Prop, PropKind,
+    StandardModel,
+};
+use dal_test::{
+    test,
+    test_harness::{create_schema, create_schema_variant_with_root},
+};
+use veritech_client::ResourceStatus;
 
 #[test]
 async fn new(ctx: &DalContext) {
@@ -13,3 +21,192 @@ async fn new(ctx: &DalContext) {
     assert_eq!(*prototype.kind(), ActionKind::Create);
     assert_eq!(prototype.func_id(), FuncId::NONE);
 }
+
+#[test]
+async fn plan(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, _root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let mut func = Func::new(
+        ctx,
+        "test:createAndPlan",
+        FuncBackendKind::JsAction,
+        FuncBackendResponseType::Action,
+    )
+    .await
+    .expect("could not create func");
+    let code = "async function create(component) {
+        throw new Error('the plan should not create anything');
+    }
+    async function plan(component) {
+        return { status: 'ok', payload: { create: component.properties.si.name } };
+    }";
+    func.set_code_plaintext(ctx, Some(code))
+        .await
+        .expect("set code");
+    func.set_handler(ctx, Some("create"))
+        .await
+        .expect("set handler");
+
+    let mut context = ActionPrototypeContext::new();
+    context.set_schema_variant_id(*schema_variant.id());
+    let prototype = ActionPrototype::new(ctx, *func.id(), ActionKind::Create, context)
+        .await
+        .expect("unable to create action prototype");
+
+    let (component, _) = Component::new(ctx, "ivete", *schema_variant.id())
+        .await
+        .expect("unable to create component");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Without a plan handler, there is nothing to plan
+    assert_eq!(
+        None,
+        prototype
+            .plan(ctx, *component.id())
+            .await
+            .expect("unable to plan")
+    );
+
+    func.set_plan_handler(ctx, Some("plan"))
+        .await
+        .expect("set plan handler");
+    let plan = prototype
+        .plan(ctx, *component.id())
+        .await
+        .expect("unable to plan")
+        .expect("no plan returned");
+    assert_eq!(ResourceStatus::Ok, plan.status);
+    assert_eq!(
+        Some(serde_json::json!({ "create": "ivete" })),
+        plan.proposed_changes
+    );
+
+    let component = Component::get_by_id(ctx, component.id())
+        .await
+        .expect("unable to get component")
+        .expect("component not found");
+    assert_eq!(
+        None,
+        component
+            .resource(ctx)
+            .await
+            .expect("unable to get resource")
+            .payload
+    );
+}
+
+#[test]
+async fn import(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    Prop::new(
+        ctx,
+        "instance_type",
+        PropKind::String,
+        None,
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let mut func = Func::new(
+        ctx,
+        "test:discover",
+        FuncBackendKind::JsAction,
+        FuncBackendResponseType::Action,
+    )
+    .await
+    .expect("could not create func");
+    let code = "async function discover(component, identifier) {
+        if (identifier === 'i-0000') {
+            return { status: 'error', message: `not allowed to describe ${identifier}` };
+        }
+        if (identifier !== 'i-1234') {
+            return { status: 'ok' };
+        }
+        return {
+            status: 'ok',
+            payload: { id: identifier, name: component.properties.si.name },
+            domain: { instance_type: 't3.micro' },
+        };
+    }";
+    func.set_code_plaintext(ctx, Some(code))
+        .await
+        .expect("set code");
+    func.set_handler(ctx, Some("discover"))
+        .await
+        .expect("set handler");
+
+    let mut context = ActionPrototypeContext::new();
+    context.set_schema_variant_id(*schema_variant.id());
+    let prototype = ActionPrototype::new(ctx, *func.id(), ActionKind::Import, context)
+        .await
+        .expect("unable to create action prototype");
+
+    let (component, _) = prototype
+        .import(ctx, "existing", "i-1234")
+        .await
+        .expect("unable to import");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let resource = component
+        .resource(ctx)
+        .await
+        .expect("unable to get resource");
+    assert_eq!(ResourceStatus::Ok, resource.status);
+    assert_eq!(
+        Some(serde_json::json!({ "id": "i-1234", "name": "existing" })),
+        resource.payload
+    );
+
+    let component_view = ComponentView::new(ctx, *component.id())
+        .await
+        .expect("unable to generate component view");
+    assert_eq!(
+        Some(&serde_json::json!({ "instance_type": "t3.micro" })),
+        component_view.properties.pointer("/domain")
+    );
+
+    match prototype.import(ctx, "missing", "i-5678").await {
+        Err(ActionPrototypeError::ImportResourceNotFound(identifier)) => {
+            assert_eq!("i-5678", identifier)
+        }
+        other => panic!("a missing resource should not be imported: {other:?}"),
+    }
+    match prototype.import(ctx, "forbidden", "i-0000").await {
+        Err(ActionPrototypeError::ImportResourceError(identifier, message)) => {
+            assert_eq!("i-0000", identifier);
+            assert_eq!("not allowed to describe i-0000", message);
+        }
+        other => panic!("a resource the function refused should not be imported: {other:?}"),
+    }
+    match prototype.import(ctx, "again", "i-1234").await {
+        Err(ActionPrototypeError::AlreadyImported(identifier, component_ids)) => {
+            assert_eq!("i-1234", identifier);
+            assert_eq!(vec![*component.id()], component_ids);
+        }
+        other => panic!("a resource should only be imported once: {other:?}"),
+    }
+}
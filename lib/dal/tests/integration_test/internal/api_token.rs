@@ -0,0 +1,59 @@
+use dal::{ApiToken, ApiTokenError, DalContext, HistoryActor, User, UserPk, WorkspaceSignup};
+use dal_test::test;
+
+#[test]
+async fn create_authenticate_and_revoke(ctx: &mut DalContext, nw: &WorkspaceSignup) {
+    let error = ApiToken::new(ctx, "ci")
+        .await
+        .expect_err("tokens need a user to act as");
+    assert!(matches!(error, ApiTokenError::NoUserActor));
+
+    let user = User::new(
+        ctx,
+        UserPk::generate(),
+        "deployer",
+        "deployer@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    ctx.update_history_actor(HistoryActor::User(user.pk()));
+
+    let (api_token, token) = ApiToken::new(ctx, "ci")
+        .await
+        .expect("could not create api token");
+    assert!(token.starts_with(&api_token.token_prefix));
+    assert_eq!(user.pk(), api_token.user_pk);
+    assert_eq!(None, api_token.last_used_at);
+
+    let authenticated = ApiToken::authenticate(ctx, &token)
+        .await
+        .expect("could not authenticate")
+        .expect("token should authenticate");
+    assert_eq!(api_token.pk, authenticated.pk);
+    assert_eq!(Some(*nw.workspace.pk()), authenticated.workspace_pk());
+    assert!(authenticated.last_used_at.is_some());
+    assert!(ApiToken::authenticate(ctx, "si_not-a-token")
+        .await
+        .expect("could not authenticate")
+        .is_none());
+
+    let tokens = ApiToken::list(ctx).await.expect("could not list tokens");
+    assert_eq!(
+        vec![api_token.pk],
+        tokens.iter().map(|t| t.pk).collect::<Vec<_>>()
+    );
+
+    let revoked = ApiToken::revoke(ctx, api_token.pk)
+        .await
+        .expect("could not revoke token");
+    assert!(revoked.revoked_at.is_some());
+    assert!(ApiToken::authenticate(ctx, &token)
+        .await
+        .expect("could not authenticate")
+        .is_none());
+    let error = ApiToken::revoke(ctx, api_token.pk)
+        .await
+        .expect_err("tokens can only be revoked once");
+    assert!(matches!(error, ApiTokenError::NotFound(_)));
+}
@@ -0,0 +1,141 @@
+use dal::{
+    tasks::{AuditExporter, AuditExporterConfig, AuditSink},
+    DalContext, HistoryEvent, HistoryEventPk,
+};
+use dal_test::test;
+
+/// Whether the event is still queued for the sink.
+async fn queued(ctx: &DalContext, sink_key: &str, pk: HistoryEventPk) -> bool {
+    ctx.txns()
+        .await
+        .expect("cannot get transactions")
+        .pg()
+        .query_opt(
+            "SELECT 1 FROM audit_export_outbox WHERE sink = $1 AND history_event_pk = $2",
+            &[&sink_key, &pk],
+        )
+        .await
+        .expect("cannot query outbox")
+        .is_some()
+}
+
+async fn registered(ctx: &DalContext, sink_key: &str) -> bool {
+    ctx.txns()
+        .await
+        .expect("cannot get transactions")
+        .pg()
+        .query_opt(
+            "SELECT 1 FROM audit_export_sinks WHERE sink = $1",
+            &[&sink_key],
+        )
+        .await
+        .expect("cannot query sinks")
+        .is_some()
+}
+
+async fn new_event(ctx: &DalContext) -> HistoryEventPk {
+    let event = HistoryEvent::new(ctx, "audit_test.export", "exported", &serde_json::json!({}))
+        .await
+        .expect("cannot create history event");
+    ctx.commit().await.expect("cannot commit");
+    event.pk
+}
+
+/// How many times the event was written to the sink's file.
+fn times_exported(path: &std::path::Path, pk: HistoryEventPk) -> usize {
+    let pk = serde_json::to_value(pk).expect("cannot serialize pk");
+    std::fs::read_to_string(path)
+        .expect("cannot read sink file")
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).expect("invalid json line"))
+        .filter(|event| event["pk"] == pk)
+        .count()
+}
+
+#[test]
+async fn export_through_outbox(ctx: &DalContext) {
+    let dir = tempfile::tempdir().expect("cannot create temp dir");
+    let path = dir.path().join("audit.jsonl");
+    let sink = AuditSink::File { path: path.clone() };
+    let sink_key = sink.key();
+    let exporter = AuditExporter::new(
+        ctx.services_context(),
+        AuditExporterConfig {
+            sink: Some(sink),
+            batch_size: 100_000,
+            ..Default::default()
+        },
+    )
+    .expect("exporter has a sink");
+
+    // A sink left over from an earlier configuration is unregistered
+    let stale_key = AuditSink::File {
+        path: dir.path().join("stale.jsonl"),
+    }
+    .key();
+    ctx.txns()
+        .await
+        .expect("cannot get transactions")
+        .pg()
+        .execute("SELECT audit_export_sink_register_v1($1)", &[&stale_key])
+        .await
+        .expect("cannot register sink");
+    ctx.commit().await.expect("cannot commit");
+    let unregistered = exporter
+        .unregister_stale_sinks()
+        .await
+        .expect("cannot unregister stale sinks");
+    assert!(unregistered.contains(&stale_key));
+    assert!(!registered(ctx, &stale_key).await);
+
+    // Registering the sink queues every existing event, so catch up on those first
+    while exporter.run().await.expect("cannot export") > 0 {}
+    assert!(registered(ctx, &sink_key).await);
+
+    let pk = new_event(ctx).await;
+    assert!(queued(ctx, &sink_key, pk).await);
+
+    // Nothing is sent while another exporter holds the sink's lock
+    let locker = ctx
+        .services_context()
+        .into_builder(false)
+        .build_default()
+        .await
+        .expect("cannot build dal context");
+    locker
+        .txns()
+        .await
+        .expect("cannot get transactions")
+        .pg()
+        .execute(
+            "SELECT pg_advisory_xact_lock(hashtext('audit_export:' || $1))",
+            &[&sink_key],
+        )
+        .await
+        .expect("cannot take lock");
+    assert_eq!(0, exporter.run().await.expect("cannot export"));
+    assert!(queued(ctx, &sink_key, pk).await);
+    locker.commit().await.expect("cannot release lock");
+
+    // Once sent, the event leaves the queue and isn't sent again
+    assert!(exporter.run().await.expect("cannot export") >= 1);
+    assert_eq!(1, times_exported(&path, pk));
+    assert!(!queued(ctx, &sink_key, pk).await);
+    while exporter.run().await.expect("cannot export") > 0 {}
+    assert_eq!(1, times_exported(&path, pk));
+
+    // An event stays queued for as long as it can't be sent
+    std::fs::remove_file(&path).expect("cannot remove sink file");
+    std::fs::create_dir(&path).expect("cannot put a directory in the sink's place");
+    let pk = new_event(ctx).await;
+    exporter
+        .run()
+        .await
+        .expect_err("exporting to a directory should fail");
+    assert!(queued(ctx, &sink_key, pk).await);
+
+    std::fs::remove_dir(&path).expect("cannot remove directory");
+    while exporter.run().await.expect("cannot export") > 0 {}
+    assert_eq!(1, times_exported(&path, pk));
+    assert!(!queued(ctx, &sink_key, pk).await);
+}
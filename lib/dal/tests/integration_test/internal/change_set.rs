@@ -1,4 +1,12 @@
-use dal::{ChangeSet, ChangeSetStatus, DalContext, Visibility};
+use dal::change_set::diff::AttributeChange;
+use dal::change_status::ChangeStatus;
+use dal::{
+    ChangeSet, ChangeSetApprovalDecision, ChangeSetConflictChoice, ChangeSetConflictKind,
+    ChangeSetConflictObject, ChangeSetConflictResolution, ChangeSetDiff, ChangeSetError,
+    ChangeSetStatus, DalContext, Func, FuncBackendKind, FuncBackendResponseType, HistoryActor,
+    Tenancy, User, UserPk, Visibility, WorkspacePk, WorkspaceSignup,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
 use dal_test::{helpers::create_change_set, test, DalContextHeadMutRef, DalContextHeadRef};
 
 #[test]
@@ -84,3 +92,361 @@ async fn get_by_pk(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
         .expect("change set pk should exist");
     assert_eq!(&change_set, &result);
 }
+
+#[test]
+async fn conflicts_with_head(ctx: &mut DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "source", "fallout").await;
+    let rads_prop = fallout_bag
+        .find_prop(ctx, &["root", "domain", "rads"])
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Get the component onto head.
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Open a change set and modify the component in it.
+    let mut change_set = create_change_set(ctx).await;
+    ctx.update_visibility(Visibility::new(change_set.pk, None));
+    fallout_bag
+        .update_attribute_value_for_prop(ctx, *rads_prop.id(), Some(serde_json::json![2]))
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Head moves on underneath it.
+    ctx.update_visibility(Visibility::new_head(false));
+    fallout_bag
+        .update_attribute_value_for_prop(ctx, *rads_prop.id(), Some(serde_json::json![3]))
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let conflicts = change_set
+        .conflicts(ctx)
+        .await
+        .expect("could not list conflicts");
+    let rads_conflict = conflicts
+        .iter()
+        .find(|conflict| {
+            matches!(
+                conflict.object,
+                ChangeSetConflictObject::AttributeValue { prop_id, .. } if prop_id == *rads_prop.id()
+            )
+        })
+        .expect("rads should conflict");
+    assert_eq!(ChangeSetConflictKind::ModifiedInBoth, rads_conflict.kind);
+    assert_eq!(Some(serde_json::json![2]), rads_conflict.change_set_value);
+    assert_eq!(Some(serde_json::json![3]), rads_conflict.head_value);
+
+    // Applying without resolutions is refused.
+    let result = change_set.apply_raw(ctx, false).await;
+    assert!(matches!(result, Err(ChangeSetError::Conflicts(_))));
+    ctx.update_visibility(Visibility::new_head(false));
+
+    // Keeping head for every conflict leaves head's value in place.
+    let resolutions: Vec<ChangeSetConflictResolution> = conflicts
+        .iter()
+        .map(|conflict| ChangeSetConflictResolution {
+            object: conflict.object,
+            choice: ChangeSetConflictChoice::KeepHead,
+        })
+        .collect();
+    change_set
+        .apply_with_resolutions(ctx, false, &resolutions)
+        .await
+        .expect("cannot apply change set with resolutions");
+    assert_eq!(&change_set.status, &ChangeSetStatus::Applied);
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let properties = fallout_bag
+        .component_view_properties(ctx)
+        .await
+        .to_value()
+        .expect("could not convert to value");
+    assert_eq!(serde_json::json![3], properties["domain"]["rads"]);
+}
+
+#[test]
+async fn rebase_onto_head(ctx: &mut DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "source", "fallout").await;
+    let name_prop = fallout_bag.find_prop(ctx, &["root", "si", "name"]).await;
+    let rads_prop = fallout_bag
+        .find_prop(ctx, &["root", "domain", "rads"])
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Get the component onto head.
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Open a change set and modify the component in it.
+    let mut change_set = create_change_set(ctx).await;
+    let change_set_visibility = Visibility::new(change_set.pk, None);
+    ctx.update_visibility(change_set_visibility);
+    fallout_bag
+        .update_attribute_value_for_prop(ctx, *rads_prop.id(), Some(serde_json::json![2]))
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Head moves on underneath it, without touching the same values.
+    ctx.update_visibility(Visibility::new_head(false));
+    fallout_bag
+        .update_attribute_value_for_prop(
+            ctx,
+            *name_prop.id(),
+            Some(serde_json::json!["source-renamed"]),
+        )
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let previously_based_at = change_set.based_at;
+    change_set
+        .rebase(ctx, &[])
+        .await
+        .expect("cannot rebase change set");
+    assert_eq!(&change_set.status, &ChangeSetStatus::Open);
+    assert!(change_set.based_at > previously_based_at);
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // The change set now sees head's rename, including the values derived from it, alongside
+    // its own edit.
+    ctx.update_visibility(change_set_visibility);
+    let properties = fallout_bag
+        .component_view_properties(ctx)
+        .await
+        .to_value()
+        .expect("could not convert to value");
+    assert_eq!(
+        serde_json::json!["source-renamed"],
+        properties["si"]["name"]
+    );
+    assert_eq!(
+        serde_json::json!["source-renamed"],
+        properties["domain"]["name"]
+    );
+    assert_eq!(serde_json::json![2], properties["domain"]["rads"]);
+}
+
+#[test]
+async fn rebase_is_scoped_to_tenancy(DalContextHeadRef(ctx): DalContextHeadRef<'_>) {
+    let mut change_set = create_change_set(ctx).await;
+
+    let other_workspace_ctx = ctx.clone_with_new_tenancy(Tenancy::new(WorkspacePk::generate()));
+    let result = change_set.rebase(&other_workspace_ctx, &[]).await;
+    assert!(
+        matches!(result, Err(ChangeSetError::Pg(_))),
+        "rebase from another workspace should be refused: {result:?}"
+    );
+}
+
+#[test]
+async fn diff(ctx: &mut DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "source", "fallout").await;
+    let rads_prop = fallout_bag
+        .find_prop(ctx, &["root", "domain", "rads"])
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let diff = ChangeSetDiff::new(ctx)
+        .await
+        .expect("could not generate change set diff");
+    let component_diff = diff
+        .components
+        .iter()
+        .find(|component| component.component_id == fallout_bag.component_id)
+        .expect("new component should be in the diff");
+    assert_eq!(ChangeStatus::Added, component_diff.change_status);
+
+    // Get the component onto head, then modify it in a new change set.
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let change_set = create_change_set(ctx).await;
+    ctx.update_visibility(Visibility::new(change_set.pk, None));
+    fallout_bag
+        .update_attribute_value_for_prop(ctx, *rads_prop.id(), Some(serde_json::json![9]))
+        .await;
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let diff = ChangeSetDiff::new(ctx)
+        .await
+        .expect("could not generate change set diff");
+    assert_eq!(1, diff.components.len());
+    let component_diff = &diff.components[0];
+    assert_eq!(fallout_bag.component_id, component_diff.component_id);
+    assert_eq!(ChangeStatus::Modified, component_diff.change_status);
+    assert_eq!(
+        vec![AttributeChange {
+            pointer: "/root/domain/rads".to_string(),
+            // The fallout schema does not set a default for rads.
+            change_status: ChangeStatus::Added,
+            head: None,
+            change_set: Some(serde_json::json![9]),
+        }],
+        component_diff.attribute_changes
+    );
+    assert!(diff.edges.is_empty());
+}
+
+#[test]
+async fn apply_requires_approval(ctx: &mut DalContext, nw: &WorkspaceSignup) {
+    let mut workspace = nw.workspace.clone();
+    workspace
+        .set_required_change_set_approvals(ctx, 1)
+        .await
+        .expect("could not set approval policy");
+
+    let author = User::new(
+        ctx,
+        UserPk::generate(),
+        "author",
+        "author@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    let reviewer = User::new(
+        ctx,
+        UserPk::generate(),
+        "reviewer",
+        "reviewer@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not perform get by pk")
+        .expect("could not get change set");
+
+    // Nobody has reviewed the change set yet.
+    let result = change_set.apply(ctx).await;
+    assert!(matches!(
+        result,
+        Err(ChangeSetError::NotApproved {
+            required: 1,
+            approved: 0
+        })
+    ));
+
+    ctx.update_history_actor(HistoryActor::User(author.pk()));
+    change_set
+        .request_approval(ctx, vec![reviewer.pk()])
+        .await
+        .expect("could not request approval");
+    assert_eq!(ChangeSetStatus::NeedsApproval, change_set.status);
+
+    // The author can't approve their own change set.
+    let result = change_set.approve(ctx, None).await;
+    assert!(matches!(result, Err(ChangeSetError::SelfReview(_))));
+
+    // A rejection sends the change set back to being open.
+    ctx.update_history_actor(HistoryActor::User(reviewer.pk()));
+    change_set
+        .reject(ctx, Some("not yet".to_string()))
+        .await
+        .expect("could not reject change set");
+    assert_eq!(ChangeSetStatus::Open, change_set.status);
+
+    ctx.update_history_actor(HistoryActor::User(author.pk()));
+    change_set
+        .request_approval(ctx, vec![reviewer.pk()])
+        .await
+        .expect("could not request approval");
+    assert!(change_set
+        .approvals(ctx)
+        .await
+        .expect("could not list approvals")
+        .is_empty());
+
+    ctx.update_history_actor(HistoryActor::User(reviewer.pk()));
+    let approval = change_set
+        .approve(ctx, Some("ship it".to_string()))
+        .await
+        .expect("could not approve change set");
+    assert_eq!(ChangeSetApprovalDecision::Approved, approval.decision);
+    assert_eq!(reviewer.pk(), approval.user_pk);
+
+    // Anything written after the approval hasn't been reviewed, so the approval no longer counts.
+    ctx.update_history_actor(HistoryActor::User(author.pk()));
+    Func::new(
+        ctx,
+        "test:writtenAfterApproval",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::Json,
+    )
+    .await
+    .expect("could not create func");
+    let result = change_set.apply(ctx).await;
+    assert!(matches!(
+        result,
+        Err(ChangeSetError::NotApproved {
+            required: 1,
+            approved: 0
+        })
+    ));
+
+    ctx.update_history_actor(HistoryActor::User(reviewer.pk()));
+    change_set
+        .approve(ctx, Some("ship it, again".to_string()))
+        .await
+        .expect("could not approve change set");
+
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    assert_eq!(ChangeSetStatus::Applied, change_set.status);
+
+    ctx.update_visibility(Visibility::new_head(false));
+}
@@ -2,10 +2,11 @@ use dal::edge::EdgeKind;
 use dal::schema::variant::root_prop::SiPropChild;
 use dal::socket::SocketEdgeKind;
 use dal::{
-    func::backend::js_action::ActionRunResult, generate_name, AttributePrototypeArgument,
-    AttributeReadContext, AttributeValue, ChangeSet, ChangeSetStatus, Component, ComponentType,
-    ComponentView, Connection, DalContext, Edge, ExternalProvider, InternalProvider, Prop, PropId,
-    PropKind, SchemaVariant, Socket, SocketArity, StandardModel, Visibility,
+    func::backend::js_action::ActionRunResult, generate_name, prop::PropPath,
+    AttributePrototypeArgument, AttributeReadContext, AttributeValue, ChangeSet, ChangeSetStatus,
+    Component, ComponentError, ComponentType, ComponentView, Connection, DalContext, Edge,
+    ExternalProvider, InternalProvider, Prop, PropId, PropKind, SchemaVariant, Socket, SocketArity,
+    StandardModel, Visibility,
 };
 use dal_test::helpers::component_bag::ComponentBagger;
 use dal_test::{
@@ -747,3 +748,85 @@ async fn create_delete_and_restore_components(ctx: &mut DalContext) {
             .expect("could not convert to value") // actual
     );
 }
+
+#[test]
+async fn set_value_by_prop_path(ctx: &mut DalContext) {
+    ctx.update_to_head();
+
+    let schema = create_schema(ctx).await;
+    let (mut schema_variant, root_prop) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    Prop::new(
+        ctx,
+        "region",
+        PropKind::String,
+        None,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    let tags_prop = Prop::new(
+        ctx,
+        "tags",
+        PropKind::Map,
+        None,
+        *schema_variant.id(),
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    Prop::new(
+        ctx,
+        "tag",
+        PropKind::String,
+        None,
+        *schema_variant.id(),
+        Some(*tags_prop.id()),
+    )
+    .await
+    .expect("could not create prop");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize schema variant");
+
+    let new_change_set = ChangeSet::new(ctx, generate_name(), None)
+        .await
+        .expect("could not create new change set");
+    ctx.update_visibility(Visibility::new(new_change_set.pk, None));
+    let (component, _) = Component::new(ctx, "bastion", *schema_variant.id())
+        .await
+        .expect("could not create component");
+
+    component
+        .set_value_by_prop_path(
+            ctx,
+            &PropPath::new(["root", "domain", "region"]),
+            Some(serde_json::json!["us-east-2"]),
+        )
+        .await
+        .expect("could not set value by prop path");
+
+    // Map entries have to be inserted, rather than set.
+    let error = component
+        .set_value_by_prop_path(
+            ctx,
+            &PropPath::new(["root", "domain", "tags", "tag"]),
+            Some(serde_json::json!["prod"]),
+        )
+        .await
+        .expect_err("props under a map can't be set by path");
+    assert!(matches!(error, ComponentError::PropNotSettableByPath(_)));
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let component_view = ComponentView::new(ctx, *component.id())
+        .await
+        .expect("could not generate component view");
+    assert_eq!(
+        serde_json::json!["us-east-2"],
+        component_view.properties["domain"]["region"]
+    );
+}
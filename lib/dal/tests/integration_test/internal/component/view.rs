@@ -1,10 +1,12 @@
 use dal::{
-    schema::RootProp, AttributeContext, AttributeValue, Component, ComponentView, DalContext, Prop,
-    PropKind, Schema, SchemaVariant, StandardModel,
+    component::ComponentViewSecretReference, property_editor::schema::WidgetKind, schema::RootProp,
+    AttributeContext, AttributeValue, AttributeValueError, Component, ComponentView, DalContext,
+    Func, Prop, PropKind, Schema, SchemaVariant, SecretError, SecretId, SecretKind, StandardModel,
+    WorkspaceSignup,
 };
 use dal_test::{
     test,
-    test_harness::{create_schema, create_schema_variant_with_root},
+    test_harness::{create_schema, create_schema_variant_with_root, create_secret},
 };
 use pretty_assertions_sorted::assert_eq;
 
@@ -1520,3 +1522,334 @@ async fn complex_nested_array_of_objects_with_a_map(ctx: &DalContext) {
         component_view.properties, // actual
     );
 }
+
+#[test]
+async fn secret_references(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let octx = ctx.clone_with_head();
+    let head_ctx = &octx;
+    let mut schema = create_schema(head_ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(head_ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(head_ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let token_prop = Prop::new(
+        head_ctx,
+        "token",
+        PropKind::String,
+        Some((
+            WidgetKind::SecretSelect,
+            Some(serde_json::json!({ "secretKind": "dockerHub" })),
+        )),
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    assert_eq!(Some(SecretKind::DockerHub), token_prop.secret_kind());
+    schema_variant
+        .finalize(head_ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+    head_ctx
+        .blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (component, _) = Component::new(ctx, "vault", *schema_variant.id())
+        .await
+        .expect("Unable to create component");
+    let secret = create_secret(ctx, nw.key_pair.pk()).await;
+
+    let mut base_attribute_context = AttributeContext::builder();
+    base_attribute_context.set_component_id(*component.id());
+    let domain_context = base_attribute_context
+        .clone()
+        .set_prop_id(root.domain_prop_id)
+        .to_context()
+        .expect("cannot create domain AttributeContext");
+    let domain_value = AttributeValue::find_for_context(ctx, domain_context.into())
+        .await
+        .expect("could not fetch domain AttributeValue")
+        .expect("could not find domain AttributeValue");
+    let token_context = base_attribute_context
+        .clone()
+        .set_prop_id(*token_prop.id())
+        .to_context()
+        .expect("cannot create token AttributeContext");
+    let token_value = AttributeValue::find_for_context(ctx, token_context.into())
+        .await
+        .expect("could not retrieve token AttributeValue")
+        .expect("could not find token AttributeValue");
+    let (_, _) = AttributeValue::update_for_context(
+        ctx,
+        *token_value.id(),
+        Some(*domain_value.id()),
+        token_context,
+        Some(serde_json::json![secret.id().to_string()]),
+        None,
+    )
+    .await
+    .expect("could not update token prop value");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let component_view = ComponentView::new(ctx, *component.id())
+        .await
+        .expect("cannot get component view");
+    assert_eq!(
+        vec![ComponentViewSecretReference {
+            pointer: "/domain/token".to_owned(),
+            secret_id: *secret.id(),
+        }], // expected
+        component_view.secret_references, // actual
+    );
+
+    let reencrypted = component_view
+        .reencrypt_secrets(ctx)
+        .await
+        .expect("cannot reencrypt secrets");
+    assert_eq!(veritech_client::ComponentKind::Standard, reencrypted.kind);
+    assert_eq!(
+        Some(&serde_json::json!(true)),
+        reencrypted
+            .properties
+            .pointer("/domain/token/message/cycloneEncryptedDataMarker"),
+    );
+
+    match secret.clone().delete(ctx).await {
+        Err(SecretError::InUse(_, component_ids)) => {
+            assert_eq!(vec![*component.id()], component_ids)
+        }
+        other => panic!("referenced secret should not be deleted: {other:?}"),
+    }
+
+    // The component only exists in the change set, but the secret is shared with _head_
+    match secret.delete(head_ctx).await {
+        Err(SecretError::InUse(_, component_ids)) => {
+            assert_eq!(vec![*component.id()], component_ids)
+        }
+        other => panic!("secret referenced in a change set should not be deleted: {other:?}"),
+    }
+}
+
+#[test]
+async fn secret_reference_writes_are_checked(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let octx = ctx.clone_with_head();
+    let head_ctx = &octx;
+    let mut schema = create_schema(head_ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(head_ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(head_ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let token_prop = Prop::new(
+        head_ctx,
+        "token",
+        PropKind::String,
+        Some((
+            WidgetKind::SecretSelect,
+            Some(serde_json::json!({ "secretKind": "helmRepo" })),
+        )),
+        *schema_variant.id(),
+        Some(root.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    schema_variant
+        .finalize(head_ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+    head_ctx
+        .blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let (component, _) = Component::new(ctx, "vault", *schema_variant.id())
+        .await
+        .expect("Unable to create component");
+    // The harness only creates Docker Hub secrets
+    let secret = create_secret(ctx, nw.key_pair.pk()).await;
+
+    let mut base_attribute_context = AttributeContext::builder();
+    base_attribute_context.set_component_id(*component.id());
+    let domain_context = base_attribute_context
+        .clone()
+        .set_prop_id(root.domain_prop_id)
+        .to_context()
+        .expect("cannot create domain AttributeContext");
+    let domain_value = AttributeValue::find_for_context(ctx, domain_context.into())
+        .await
+        .expect("could not fetch domain AttributeValue")
+        .expect("could not find domain AttributeValue");
+    let token_context = base_attribute_context
+        .clone()
+        .set_prop_id(*token_prop.id())
+        .to_context()
+        .expect("cannot create token AttributeContext");
+    let token_value = AttributeValue::find_for_context(ctx, token_context.into())
+        .await
+        .expect("could not retrieve token AttributeValue")
+        .expect("could not find token AttributeValue");
+
+    let update = |value: serde_json::Value| {
+        AttributeValue::update_for_context(
+            ctx,
+            *token_value.id(),
+            Some(*domain_value.id()),
+            token_context,
+            Some(value),
+            None,
+        )
+    };
+
+    match update(serde_json::json!["hunter2"]).await {
+        Err(AttributeValueError::InvalidSecretReference(prop_id, raw)) => {
+            assert_eq!(*token_prop.id(), prop_id);
+            assert_eq!("hunter2", raw);
+        }
+        other => panic!("a non-id value should be refused: {other:?}"),
+    }
+
+    let missing_secret_id = SecretId::generate();
+    match update(serde_json::json![missing_secret_id.to_string()]).await {
+        Err(AttributeValueError::SecretNotFound(prop_id, secret_id)) => {
+            assert_eq!(*token_prop.id(), prop_id);
+            assert_eq!(missing_secret_id, secret_id);
+        }
+        other => panic!("a missing secret should be refused: {other:?}"),
+    }
+
+    match update(serde_json::json![secret.id().to_string()]).await {
+        Err(AttributeValueError::SecretKindMismatch {
+            secret_id,
+            expected,
+            found,
+            ..
+        }) => {
+            assert_eq!(*secret.id(), secret_id);
+            assert_eq!(SecretKind::HelmRepo, expected);
+            assert_eq!(SecretKind::DockerHub, found);
+        }
+        other => panic!("a secret of another kind should be refused: {other:?}"),
+    }
+}
+
+#[test]
+async fn float_props(ctx: &DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    schema
+        .set_default_schema_variant_id(ctx, Some(*schema_variant.id()))
+        .await
+        .expect("cannot set default schema variant");
+    let schema_variant_id = *schema_variant.id();
+
+    let cpu_shares_prop = Prop::new(
+        ctx,
+        "cpu_shares",
+        PropKind::Float,
+        None,
+        schema_variant_id,
+        Some(root.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    let weight_prop = Prop::new(
+        ctx,
+        "weight",
+        PropKind::Float,
+        None,
+        schema_variant_id,
+        Some(root.domain_prop_id),
+    )
+    .await
+    .expect("could not create prop");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("cannot finalize SchemaVariant");
+
+    let (component, _) = Component::new(ctx, "cgroup", schema_variant_id)
+        .await
+        .expect("Unable to create component");
+
+    let mut base_attribute_context = AttributeContext::builder();
+    base_attribute_context.set_component_id(*component.id());
+
+    let domain_context = base_attribute_context
+        .clone()
+        .set_prop_id(root.domain_prop_id)
+        .to_context()
+        .expect("cannot create domain AttributeContext");
+    let domain_value = AttributeValue::find_for_context(ctx, domain_context.into())
+        .await
+        .expect("could not fetch domain AttributeValue")
+        .expect("could not find domain AttributeValue");
+
+    // A whole number is still a float for a float prop
+    for (prop, value) in [
+        (&cpu_shares_prop, serde_json::json![0.5]),
+        (&weight_prop, serde_json::json![2.0]),
+    ] {
+        let context = base_attribute_context
+            .clone()
+            .set_prop_id(*prop.id())
+            .to_context()
+            .expect("cannot create AttributeContext");
+        let attribute_value = AttributeValue::find_for_context(ctx, context.into())
+            .await
+            .expect("could not retrieve AttributeValue")
+            .expect("could not find AttributeValue");
+        let (_, updated_attribute_value_id) = AttributeValue::update_for_context(
+            ctx,
+            *attribute_value.id(),
+            Some(*domain_value.id()),
+            context,
+            Some(value),
+            None,
+        )
+        .await
+        .expect("could not update float prop value");
+
+        let prototype = AttributeValue::get_by_id(ctx, &updated_attribute_value_id)
+            .await
+            .expect("could not retrieve AttributeValue")
+            .expect("could not find AttributeValue")
+            .attribute_prototype(ctx)
+            .await
+            .expect("could not retrieve AttributePrototype")
+            .expect("could not find AttributePrototype");
+        let func = Func::get_by_id(ctx, &prototype.func_id())
+            .await
+            .expect("could not retrieve Func")
+            .expect("could not find Func");
+        assert_eq!("si:setFloat", func.name());
+    }
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let component_view = ComponentView::new(ctx, *component.id())
+        .await
+        .expect("cannot get component view");
+    assert_eq!(
+        serde_json::json![
+            {
+                "si": {
+                    "name": "cgroup",
+                    "type": "component",
+                    "protected": false
+                },
+                "domain": {
+                    "cpu_shares": 0.5,
+                    "weight": 2.0
+                }
+            }
+        ], // expected
+        component_view.properties, // actual
+    );
+}
@@ -0,0 +1,198 @@
+use dal::func::backend::js_action::ActionRunResult;
+use dal::{
+    attribute::context::AttributeContextBuilder, AttributeReadContext, AttributeValue, ChangeSet,
+    Component, ComponentDrift, DalContext, DriftSummary, DriftedProp, Prop, PropKind,
+    StandardModel,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::{
+    test,
+    test_harness::{create_schema, create_schema_variant_with_root},
+};
+use pretty_assertions_sorted::assert_eq;
+use veritech_client::ResourceStatus;
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn component_without_resource_has_not_drifted(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let bag = bagger.create_component(ctx, "fallout", "fallout").await;
+
+    let drifted_props = ComponentDrift::detect(ctx, bag.component_id)
+        .await
+        .expect("could not detect drift");
+    assert!(drifted_props.is_empty());
+
+    let drift = ComponentDrift::update_for_component(ctx, bag.component_id)
+        .await
+        .expect("could not update drift");
+    assert!(!drift.is_drifted());
+    assert_eq!(bag.component_id, *drift.component_id());
+
+    // Updating again without any change must not create a second record.
+    let again = ComponentDrift::update_for_component(ctx, bag.component_id)
+        .await
+        .expect("could not update drift");
+    assert_eq!(drift.id(), again.id());
+}
+
+/// Recommendation: run this test with the following environment variable:
+/// ```shell
+/// SI_TEST_BUILTIN_SCHEMAS=test
+/// ```
+#[test]
+async fn summary(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "fallout", "fallout").await;
+    let _starfield_bag = bagger.create_component(ctx, "starfield", "starfield").await;
+
+    let drifted_props = vec![DriftedProp {
+        path: "root/resource_value/rads".to_string(),
+        domain: serde_json::json!(1),
+        resource: serde_json::json!(2),
+    }];
+    let drift = ComponentDrift::new(ctx, fallout_bag.component_id, drifted_props.clone())
+        .await
+        .expect("could not create component drift");
+    assert!(drift.is_drifted());
+    assert_eq!(drifted_props.as_slice(), drift.drifted_props());
+
+    let found = ComponentDrift::find_for_component(ctx, fallout_bag.component_id)
+        .await
+        .expect("could not find component drift")
+        .expect("component drift not found");
+    assert_eq!(drift, found);
+
+    let summary = serde_json::to_value(
+        DriftSummary::get_summary(ctx)
+            .await
+            .expect("could not get drift summary"),
+    )
+    .expect("could not serialize drift summary");
+    assert_eq!(serde_json::json!(2), summary["total"]);
+    assert_eq!(serde_json::json!(1), summary["drifted"]);
+    assert_eq!(serde_json::json!(1), summary["inSync"]);
+}
+
+#[test]
+async fn component_whose_resource_differs_from_its_domain_has_drifted(ctx: &mut DalContext) {
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root_prop) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    let schema_variant_id = *schema_variant.id();
+    schema
+        .set_default_schema_variant_id(ctx, Some(schema_variant_id))
+        .await
+        .expect("cannot set default schema variant");
+
+    // "rads" is tracked in both the domain and the resource, which refers back to the domain
+    let domain_rads_prop = Prop::new(
+        ctx,
+        "rads",
+        PropKind::Integer,
+        None,
+        schema_variant_id,
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create domain prop");
+    let mut resource_rads_prop = Prop::new(
+        ctx,
+        "rads",
+        PropKind::Integer,
+        None,
+        schema_variant_id,
+        Some(root_prop.resource_value_prop_id),
+    )
+    .await
+    .expect("could not create resource value prop");
+    resource_rads_prop
+        .set_refers_to_prop_id(ctx, Some(*domain_rads_prop.id()))
+        .await
+        .expect("could not set referred to prop");
+
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("unable to finalize schema variant");
+    let (component, _) = Component::new(ctx, "ghoul", schema_variant_id)
+        .await
+        .expect("cannot create component");
+
+    let rads_read_context = AttributeReadContext {
+        prop_id: Some(*domain_rads_prop.id()),
+        component_id: Some(*component.id()),
+        ..AttributeReadContext::default()
+    };
+    let rads_value = AttributeValue::find_for_context(ctx, rads_read_context)
+        .await
+        .expect("could not perform find for context")
+        .expect("attribute value not found");
+    let domain_value = rads_value
+        .parent_attribute_value(ctx)
+        .await
+        .expect("could not perform parent attribute value")
+        .expect("parent attribute value not found");
+    AttributeValue::update_for_context(
+        ctx,
+        *rads_value.id(),
+        Some(*domain_value.id()),
+        AttributeContextBuilder::from(rads_read_context)
+            .to_context()
+            .expect("could not convert builder to context"),
+        Some(serde_json::json![1]),
+        None,
+    )
+    .await
+    .expect("could not update for context");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    // Resources only exist on head
+    let mut change_set = ChangeSet::get_by_pk(ctx, &ctx.visibility().change_set_pk)
+        .await
+        .expect("could not fetch change set by pk")
+        .expect("no change set found for pk");
+    change_set
+        .apply(ctx)
+        .await
+        .expect("cannot apply change set");
+    component
+        .set_resource(
+            ctx,
+            ActionRunResult {
+                status: ResourceStatus::Ok,
+                payload: Some(serde_json::json![{ "rads": 2 }]),
+                message: None,
+                logs: vec![],
+                last_synced: Default::default(),
+            },
+            true,
+        )
+        .await
+        .expect("could not set resource");
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let drifted_props = ComponentDrift::detect(ctx, *component.id())
+        .await
+        .expect("could not detect drift");
+    assert_eq!(
+        vec![DriftedProp {
+            path: "root/resource_value/rads".to_string(),
+            domain: serde_json::json!(1),
+            resource: serde_json::json!(2),
+        }],
+        drifted_props
+    );
+
+    let drift = ComponentDrift::update_for_component(ctx, *component.id())
+        .await
+        .expect("could not update drift");
+    assert!(drift.is_drifted());
+    assert_eq!(drifted_props.as_slice(), drift.drifted_props());
+}
@@ -0,0 +1,106 @@
+use dal::{
+    action_prototype::ActionKind,
+    job::{
+        consumer::JobConsumer,
+        definition::{FixItem, FixesJob},
+    },
+    ActionPrototype, ActionPrototypeContext, AttributeValueId, ComponentId, DalContext, Fix,
+    FixBatch, FixCompletionStatus, FuncId, StandardModel,
+};
+use dal_test::test;
+use pretty_assertions_sorted::assert_eq;
+
+#[test]
+async fn abandoned_fixes_job_finishes_its_batch(ctx: &mut DalContext) {
+    let prototype = ActionPrototype::new(
+        ctx,
+        FuncId::NONE,
+        ActionKind::Create,
+        ActionPrototypeContext::default(),
+    )
+    .await
+    .expect("unable to create action prototype");
+    let mut batch = FixBatch::new(ctx, "paulatreides@systeminit.com")
+        .await
+        .expect("could not create fix batch");
+
+    let mut fixes = Vec::new();
+    let mut fix_items = Vec::new();
+    for _ in 0..2 {
+        let fix = Fix::new(
+            ctx,
+            *batch.id(),
+            AttributeValueId::NONE,
+            ComponentId::NONE,
+            *prototype.id(),
+        )
+        .await
+        .expect("could not create fix");
+        fix_items.push(FixItem {
+            id: *fix.id(),
+            action_prototype_id: *prototype.id(),
+            component_id: ComponentId::NONE,
+            attribute_value_id: AttributeValueId::NONE,
+        });
+        fixes.push(fix);
+    }
+
+    // The first fix finished before the job was cut short
+    batch
+        .stamp_started(ctx)
+        .await
+        .expect("could not start batch");
+    fixes[0]
+        .stamp_started(ctx)
+        .await
+        .expect("could not start fix");
+    fixes[0]
+        .stamp_finished(ctx, FixCompletionStatus::Success, None, None)
+        .await
+        .expect("could not finish fix");
+
+    let job = FixesJob::new(ctx, fix_items, *batch.id());
+    job.abandon(ctx, "never completed")
+        .await
+        .expect("could not abandon job");
+
+    let batch = FixBatch::get_by_id(ctx, batch.id())
+        .await
+        .expect("could not get fix batch")
+        .expect("fix batch not found");
+    assert!(batch.finished_at().is_some());
+    assert_eq!(Some(&FixCompletionStatus::Error), batch.completion_status());
+
+    let finished = Fix::get_by_id(ctx, fixes[0].id())
+        .await
+        .expect("could not get fix")
+        .expect("fix not found");
+    assert_eq!(
+        Some(&FixCompletionStatus::Success),
+        finished.completion_status()
+    );
+
+    let abandoned = Fix::get_by_id(ctx, fixes[1].id())
+        .await
+        .expect("could not get fix")
+        .expect("fix not found");
+    assert!(abandoned.finished_at().is_some());
+    assert_eq!(
+        Some(&FixCompletionStatus::Error),
+        abandoned.completion_status()
+    );
+    assert!(abandoned
+        .completion_message()
+        .expect("abandoned fix has no completion message")
+        .contains("never completed"));
+
+    // A batch which already finished is left alone
+    job.abandon(ctx, "abandoned again")
+        .await
+        .expect("could not abandon job again");
+    let batch_again = FixBatch::get_by_id(ctx, batch.id())
+        .await
+        .expect("could not get fix batch")
+        .expect("fix batch not found");
+    assert_eq!(batch.finished_at(), batch_again.finished_at());
+}
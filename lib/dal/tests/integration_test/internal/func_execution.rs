@@ -1,9 +1,13 @@
+use std::time::Duration;
+
 use dal::{
     func::{
         backend::string::FuncBackendStringArgs,
-        execution::{FuncExecution, FuncExecutionState},
+        binding::FuncBinding,
+        execution::{FuncExecution, FuncExecutionError, FuncExecutionPk, FuncExecutionState},
     },
-    DalContext, StandardModel,
+    DalContext, Func, FuncBackendKind, FuncBackendResponseType, StandardModel, Tenancy,
+    WorkspacePk,
 };
 use dal_test::{
     test,
@@ -70,6 +74,115 @@ async fn set_output_stream(ctx: &DalContext) {
     assert_eq!(output_stream.len(), 1);
 }
 
+#[test]
+async fn cancel(ctx: &DalContext) {
+    let func = create_func(ctx).await;
+    let args = FuncBackendStringArgs::new("slayer".to_string());
+    let args_json = serde_json::to_value(args).expect("cannot serialize args to json");
+    let func_binding = create_func_binding(ctx, args_json, *func.id(), *func.backend_kind()).await;
+    let mut execution = FuncExecution::new(ctx, &func, &func_binding)
+        .await
+        .expect("cannot create a new func execution");
+    execution
+        .set_state(ctx, FuncExecutionState::Success)
+        .await
+        .expect("cannot set state");
+
+    // Finished executions are left alone
+    FuncExecution::cancel(ctx, execution.pk())
+        .await
+        .expect("cannot cancel a finished execution");
+
+    // Executions of other workspaces can't be seen, let alone cancelled
+    let other_ctx = ctx.clone_with_new_tenancy(Tenancy::new(WorkspacePk::generate()));
+    let result = FuncExecution::cancel(&other_ctx, execution.pk()).await;
+    assert!(matches!(result, Err(FuncExecutionError::NotFound(pk)) if pk == execution.pk()));
+
+    let result = FuncExecution::cancel(ctx, FuncExecutionPk::generate()).await;
+    assert!(matches!(result, Err(FuncExecutionError::NotFound(_))));
+}
+
+#[test]
+async fn cancel_running_execution(ctx: &DalContext) {
+    let mut func = Func::new(
+        ctx,
+        "test:napping",
+        FuncBackendKind::JsAttribute,
+        FuncBackendResponseType::String,
+    )
+    .await
+    .expect("could not create func");
+    func.set_code_plaintext(
+        ctx,
+        Some("async function nap() { await new Promise((r) => setTimeout(r, 60000)); return 'awake'; }"),
+    )
+    .await
+    .expect("set code");
+    func.set_handler(ctx, Some("nap"))
+        .await
+        .expect("set handler");
+    let func_binding =
+        FuncBinding::new(ctx, serde_json::json!({}), *func.id(), *func.backend_kind())
+            .await
+            .expect("cannot create func binding");
+    ctx.commit().await.expect("cannot commit");
+
+    let execute_ctx = ctx.clone();
+    let execute_binding = func_binding.clone();
+    let execution_task = tokio::spawn(async move { execute_binding.execute(&execute_ctx).await });
+
+    // Keep asking veritech to cancel until the execution gives up: the request is only heard once
+    // veritech has picked the function up.
+    let result = loop {
+        if execution_task.is_finished() {
+            break execution_task.await.expect("execution task panicked");
+        }
+        let row = ctx
+            .txns()
+            .await
+            .expect("cannot get transactions")
+            .pg()
+            .query_opt(
+                "SELECT pk FROM func_executions WHERE func_binding_id = $1",
+                &[func_binding.id()],
+            )
+            .await
+            .expect("cannot query func executions");
+        if let Some(row) = row {
+            let pk: FuncExecutionPk = row.try_get("pk").expect("cannot get pk");
+            FuncExecution::cancel(ctx, pk)
+                .await
+                .expect("cannot cancel execution");
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+    assert!(result.is_err(), "cancelled execution returned a value");
+
+    // The cancelled state was committed, so it is visible from a fresh context
+    let fresh_ctx = ctx
+        .services_context()
+        .into_builder(ctx.blocking())
+        .build(ctx.access_builder().build(*ctx.visibility()))
+        .await
+        .expect("cannot build dal context");
+    let row = fresh_ctx
+        .txns()
+        .await
+        .expect("cannot get transactions")
+        .pg()
+        .query_one(
+            "SELECT pk FROM func_executions WHERE func_binding_id = $1",
+            &[func_binding.id()],
+        )
+        .await
+        .expect("cannot find func execution");
+    let pk: FuncExecutionPk = row.try_get("pk").expect("cannot get pk");
+    let execution = FuncExecution::get_by_pk(&fresh_ctx, &pk)
+        .await
+        .expect("cannot get func execution");
+    assert_eq!(execution.state(), FuncExecutionState::Cancelled);
+}
+
 #[test]
 async fn process_return_value(ctx: &DalContext) {
     let func = create_func(ctx).await;
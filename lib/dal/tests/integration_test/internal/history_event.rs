@@ -1,4 +1,4 @@
-use dal::{DalContext, HistoryActor, HistoryEvent};
+use dal::{DalContext, HistoryActor, HistoryEvent, HistoryEventFilter};
 use dal_test::test;
 
 #[test]
@@ -17,3 +17,66 @@ async fn new(ctx: &DalContext) {
     assert_eq!(&history_event.data, &serde_json::json!({}));
     assert_eq!(&history_event.tenancy, ctx.tenancy());
 }
+
+#[test]
+async fn list(ctx: &DalContext) {
+    for n in 0..3 {
+        HistoryEvent::new(
+            ctx,
+            "audit_test.poke",
+            "poked",
+            &serde_json::json!({ "pk": format!("poked-{n}") }),
+        )
+        .await
+        .expect("cannot create a new history event");
+    }
+    HistoryEvent::new(
+        ctx,
+        "other_test.prod",
+        "prodded",
+        &serde_json::json!({ "id": "poked-0" }),
+    )
+    .await
+    .expect("cannot create a new history event");
+
+    let mut filter = HistoryEventFilter {
+        label_prefix: Some("audit_test.".to_owned()),
+        limit: Some(2),
+        ..Default::default()
+    };
+    let first_page = HistoryEvent::list(ctx, &filter)
+        .await
+        .expect("cannot list history events");
+    assert_eq!(
+        vec![serde_json::json!("poked-2"), serde_json::json!("poked-1")],
+        first_page
+            .iter()
+            .map(|event| event.data["pk"].clone())
+            .collect::<Vec<_>>()
+    );
+
+    filter.before = first_page.last().map(|event| event.pk);
+    let second_page = HistoryEvent::list(ctx, &filter)
+        .await
+        .expect("cannot list history events");
+    assert_eq!(1, second_page.len());
+    assert_eq!(serde_json::json!("poked-0"), second_page[0].data["pk"]);
+
+    let about_object = HistoryEvent::list(
+        ctx,
+        &HistoryEventFilter {
+            object_id: Some("poked-0".to_owned()),
+            actor: Some(HistoryActor::SystemInit),
+            ..Default::default()
+        },
+    )
+    .await
+    .expect("cannot list history events");
+    assert_eq!(
+        vec!["other_test.prod", "audit_test.poke"],
+        about_object
+            .iter()
+            .map(|event| event.label.as_str())
+            .collect::<Vec<_>>()
+    );
+}
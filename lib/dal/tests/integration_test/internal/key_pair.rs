@@ -1,7 +1,10 @@
-use dal::{key_pair::PublicKey, DalContext, KeyPair, Tenancy};
+use dal::{
+    key_pair::PublicKey, DalContext, EncryptedSecret, KeyPair, SecretVersion, StandardModel,
+    Tenancy,
+};
 use dal_test::{
     test,
-    test_harness::{create_key_pair, create_workspace},
+    test_harness::{create_key_pair, create_secret_with_message, create_workspace},
 };
 
 #[test]
@@ -44,3 +47,39 @@ async fn public_key_get_current(ctx: &mut DalContext) {
     assert_eq!(second_key_pair.pk(), *pk.pk());
     assert_eq!(second_key_pair.public_key(), pk.public_key());
 }
+
+#[test]
+async fn rotate(ctx: &mut DalContext) {
+    let workspace = create_workspace(ctx).await;
+    ctx.update_tenancy(Tenancy::new(*workspace.pk()));
+
+    let old_key_pair = create_key_pair(ctx).await;
+    let message = serde_json::json!({"song": "Holiday in Cambodia"});
+    let secret = create_secret_with_message(ctx, old_key_pair.pk(), &message).await;
+
+    let rotation = KeyPair::rotate(ctx).await.expect("cannot rotate key pair");
+    assert_ne!(old_key_pair.pk(), rotation.key_pair_pk);
+    assert_eq!(vec![old_key_pair.pk()], rotation.retired_key_pair_pks);
+    assert_eq!(1, rotation.reencrypted_secret_count);
+
+    let current = PublicKey::get_current(ctx)
+        .await
+        .expect("cannot get public key");
+    assert_eq!(rotation.key_pair_pk, *current.pk());
+    assert!(KeyPair::get_by_pk(ctx, old_key_pair.pk()).await.is_err());
+
+    let encrypted_secret = EncryptedSecret::get_by_id(ctx, secret.id())
+        .await
+        .expect("failed to fetch encrypted secret")
+        .expect("failed to find encrypted secret");
+    assert_eq!(rotation.key_pair_pk, encrypted_secret.key_pair_pk());
+    assert_eq!(&SecretVersion::V2, encrypted_secret.version());
+
+    let decrypted = encrypted_secret
+        .decrypt(ctx)
+        .await
+        .expect("failed to decrypt re-encrypted secret");
+    let decrypted_value =
+        serde_json::to_value(&decrypted).expect("failed to serialize decrypted secret");
+    assert_eq!(decrypted_value["message"], message);
+}
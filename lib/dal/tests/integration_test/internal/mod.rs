@@ -1,9 +1,13 @@
 mod action_prototype;
+mod api_token;
 mod attribute;
+mod audit_exporter;
 mod change_set;
 mod component;
 mod diagram;
+mod drift;
 mod edge;
+mod fix;
 mod func;
 mod func_execution;
 mod graph;
@@ -26,4 +30,5 @@ mod user;
 mod validation_prototype;
 mod validation_resolver;
 mod visibility;
+mod webhook;
 mod workspace;
@@ -1,11 +1,18 @@
 use base64::{engine::general_purpose, Engine};
 use dal::func::intrinsics::IntrinsicFunc;
 use dal::{
-    func::backend::validation::FuncBackendValidationArgs, installed_pkg::*, pkg::*,
-    schema::variant::leaves::LeafKind, validation::Validation, DalContext, ExternalProvider, Func,
-    InternalProvider, Schema, SchemaVariant, StandardModel, ValidationPrototype,
+    attribute::context::AttributeContextBuilder, edge::EdgeKind,
+    func::backend::validation::FuncBackendValidationArgs, installed_pkg::*, pkg::*, prop::PropPath,
+    schema::variant::leaves::LeafKind, socket::SocketEdgeKind, validation::Validation,
+    AttributeReadContext, AttributeValue, Component, ComponentId, ComponentType, ComponentView,
+    Connection, DalContext, Edge, ExternalProvider, Func, InternalProvider, Prop, PropKind, Schema,
+    SchemaVariant, Socket, StandardModel, ValidationPrototype,
+};
+use dal_test::helpers::component_bag::ComponentBagger;
+use dal_test::{
+    test,
+    test_harness::{create_schema, create_schema_variant_with_root},
 };
-use dal_test::test;
 use si_pkg::{
     FuncSpec, FuncSpecBackendKind, FuncSpecBackendResponseType, LeafFunctionSpec,
     LeafInputLocation as PkgLeafInputLocation, LeafKind as PkgLeafKind, PkgSpec, PropSpec,
@@ -314,6 +321,7 @@ async fn test_install_pkg(ctx: &DalContext) {
                 }
             }
             InstalledPkgAssetKind::SchemaVariantDefinition => {}
+            InstalledPkgAssetKind::SecretDefinition => {}
             InstalledPkgAssetKind::Func => {
                 let typed: InstalledPkgAssetTyped =
                     ipa.as_installed_func().expect("get func ipa typed");
@@ -416,3 +424,273 @@ async fn test_install_pkg(ctx: &DalContext) {
         .expect("func is there");
     assert_eq!(func.name(), "groucho");
 }
+
+#[test]
+async fn export_and_import_component_template(ctx: &DalContext) {
+    let mut bagger = ComponentBagger::new();
+    let fallout_bag = bagger.create_component(ctx, "vault", "fallout").await;
+    let starfield_bag = bagger
+        .create_component(ctx, "constellation", "starfield")
+        .await;
+
+    let fallout = Component::get_by_id(ctx, &fallout_bag.component_id)
+        .await
+        .expect("could not get component")
+        .expect("component not found");
+    fallout
+        .set_value_by_prop_path(
+            ctx,
+            &PropPath::new(["root", "domain", "special"]),
+            Some(serde_json::json!["charisma"]),
+        )
+        .await
+        .expect("could not set special");
+
+    // A frame around the constellation, with a map in its domain
+    let mut schema = create_schema(ctx).await;
+    let (mut schema_variant, root_prop) = create_schema_variant_with_root(ctx, *schema.id()).await;
+    let schema_variant_id = *schema_variant.id();
+    schema
+        .set_default_schema_variant_id(ctx, Some(schema_variant_id))
+        .await
+        .expect("cannot set default schema variant");
+    let perks_prop = Prop::new(
+        ctx,
+        "perks",
+        PropKind::Map,
+        None,
+        schema_variant_id,
+        Some(root_prop.domain_prop_id),
+    )
+    .await
+    .expect("could not create map prop");
+    let perk_prop = Prop::new(
+        ctx,
+        "perk",
+        PropKind::String,
+        None,
+        schema_variant_id,
+        Some(*perks_prop.id()),
+    )
+    .await
+    .expect("could not create map item prop");
+    schema_variant
+        .finalize(ctx, None)
+        .await
+        .expect("unable to finalize schema variant");
+
+    let (shelter, shelter_node) = Component::new(ctx, "shelter", schema_variant_id)
+        .await
+        .expect("cannot create component");
+    shelter
+        .set_type(ctx, ComponentType::ConfigurationFrame)
+        .await
+        .expect("could not set component type");
+    let perks_value = AttributeValue::find_for_context(
+        ctx,
+        AttributeReadContext {
+            prop_id: Some(*perks_prop.id()),
+            component_id: Some(*shelter.id()),
+            ..AttributeReadContext::default()
+        },
+    )
+    .await
+    .expect("could not perform find for context")
+    .expect("attribute value not found");
+    AttributeValue::insert_for_context(
+        ctx,
+        AttributeContextBuilder::new()
+            .set_prop_id(*perk_prop.id())
+            .set_component_id(*shelter.id())
+            .to_context()
+            .expect("could not convert builder to context"),
+        *perks_value.id(),
+        Some(serde_json::json!["iron fist"]),
+        Some("strength".to_owned()),
+    )
+    .await
+    .expect("could not insert map item");
+
+    let child_frame_socket = Socket::find_frame_socket_for_node(
+        ctx,
+        starfield_bag.node_id,
+        SocketEdgeKind::ConfigurationOutput,
+    )
+    .await
+    .expect("could not find frame socket");
+    let parent_frame_socket = Socket::find_frame_socket_for_node(
+        ctx,
+        *shelter_node.id(),
+        SocketEdgeKind::ConfigurationInput,
+    )
+    .await
+    .expect("could not find frame socket");
+    Connection::new(
+        ctx,
+        starfield_bag.node_id,
+        *child_frame_socket.id(),
+        *shelter_node.id(),
+        *parent_frame_socket.id(),
+        EdgeKind::Symbolic,
+    )
+    .await
+    .expect("could not connect to frame");
+    Connection::connect_component_sockets_to_frame(ctx, *shelter_node.id(), starfield_bag.node_id)
+        .await
+        .expect("could not connect sockets to frame");
+
+    let output_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationOutput,
+        fallout_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    let input_socket = Socket::find_by_name_for_edge_kind_and_node(
+        ctx,
+        "bethesda",
+        SocketEdgeKind::ConfigurationInput,
+        starfield_bag.node_id,
+    )
+    .await
+    .expect("could not perform socket find")
+    .expect("could not find socket");
+    Connection::new(
+        ctx,
+        fallout_bag.node_id,
+        *output_socket.id(),
+        starfield_bag.node_id,
+        *input_socket.id(),
+        EdgeKind::Configuration,
+    )
+    .await
+    .expect("could not create connection");
+
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let bytes = export_pkg_as_bytes(
+        ctx,
+        "vault template",
+        "0.1",
+        None::<String>,
+        "Vault-Tec",
+        vec![],
+        vec![
+            fallout_bag.component_id,
+            starfield_bag.component_id,
+            *shelter.id(),
+        ],
+    )
+    .await
+    .expect("could not export template");
+    let pkg = SiPkg::load_from_bytes(bytes).expect("could not load template");
+
+    let components = pkg.components().expect("could not get components");
+    assert_eq!(3, components.len());
+    let constellation = components
+        .iter()
+        .find(|component| component.name() == "constellation")
+        .expect("constellation not in template");
+    let connections = constellation
+        .connections()
+        .expect("could not get connections");
+    assert_eq!(1, connections.len());
+    assert_eq!("vault", connections[0].from_component_name());
+    assert_eq!("bethesda", connections[0].to_socket_name());
+    assert_eq!(Some("shelter"), constellation.parent());
+
+    let imported = instantiate_template(ctx, &pkg)
+        .await
+        .expect("could not instantiate template");
+    assert_eq!(3, imported.len());
+    // A template is not installed, so it can be instantiated again with fresh ids
+    let instantiated_again = instantiate_template(ctx, &pkg)
+        .await
+        .expect("could not instantiate template a second time");
+    assert_eq!(3, instantiated_again.len());
+    assert!(instantiated_again
+        .iter()
+        .all(|component_id| !imported.contains(component_id)));
+    ctx.blocking_commit()
+        .await
+        .expect("could not commit & run jobs");
+
+    let mut imported_vault = None;
+    let mut imported_constellation = None;
+    let mut imported_shelter = None;
+    for component_id in &imported {
+        assert!(![
+            fallout_bag.component_id,
+            starfield_bag.component_id,
+            *shelter.id()
+        ]
+        .contains(component_id));
+        let component = Component::get_by_id(ctx, component_id)
+            .await
+            .expect("could not get component")
+            .expect("component not found");
+        match component
+            .name(ctx)
+            .await
+            .expect("could not get name")
+            .as_str()
+        {
+            "vault" => imported_vault = Some(component),
+            "constellation" => imported_constellation = Some(component),
+            "shelter" => imported_shelter = Some(component),
+            _ => {}
+        }
+    }
+    let imported_vault = imported_vault.expect("vault not imported");
+    let imported_constellation = imported_constellation.expect("constellation not imported");
+    let imported_shelter = imported_shelter.expect("shelter not imported");
+
+    let view = ComponentView::new(ctx, *imported_vault.id())
+        .await
+        .expect("could not get component view");
+    assert_eq!(
+        serde_json::json!["charisma"],
+        view.properties["domain"]["special"]
+    );
+
+    let view = ComponentView::new(ctx, *imported_shelter.id())
+        .await
+        .expect("could not get component view");
+    assert_eq!(
+        serde_json::json![{ "strength": "iron fist" }],
+        view.properties["domain"]["perks"]
+    );
+    assert_eq!(
+        ComponentType::ConfigurationFrame,
+        imported_shelter
+            .get_type(ctx)
+            .await
+            .expect("could not get type")
+    );
+
+    let edges = Edge::list_for_component(ctx, *imported_constellation.id())
+        .await
+        .expect("could not list edges");
+    let configuration_edges: Vec<_> = edges
+        .iter()
+        .filter(|edge| *edge.kind() == EdgeKind::Configuration)
+        .collect();
+    assert_eq!(1, configuration_edges.len());
+    let tail_component_id: ComponentId = configuration_edges[0].tail_object_id().into();
+    assert_eq!(*imported_vault.id(), tail_component_id);
+
+    // The constellation is back inside the shelter
+    let frame_edges: Vec<_> = edges
+        .iter()
+        .filter(|edge| *edge.kind() == EdgeKind::Symbolic)
+        .collect();
+    assert_eq!(1, frame_edges.len());
+    let head_component_id: ComponentId = frame_edges[0].head_object_id().into();
+    let tail_component_id: ComponentId = frame_edges[0].tail_object_id().into();
+    assert_eq!(*imported_shelter.id(), head_component_id);
+    assert_eq!(*imported_constellation.id(), tail_component_id);
+}
@@ -1,10 +1,11 @@
 use dal::{
-    DalContext, EncryptedSecret, Secret, SecretAlgorithm, SecretKind, SecretObjectType,
-    SecretVersion, StandardModel, WorkspaceSignup,
+    secret::SecretBackendError, DalContext, EncryptedSecret, Secret, SecretAlgorithm,
+    SecretBackendKind, SecretDefinition, SecretError, SecretKind, SecretObjectType,
+    SecretReference, SecretVersion, StandardModel, WorkspaceSignup,
 };
 use dal_test::{
     test,
-    test_harness::{create_secret, generate_fake_name},
+    test_harness::{create_secret, encrypt_message, generate_fake_name},
 };
 
 #[test]
@@ -117,3 +118,175 @@ async fn encrypt_decrypt_round_trip(ctx: &DalContext, nw: &WorkspaceSignup) {
         serde_json::to_value(&decrypted).expect("failed to serial decrypted into Value");
     assert_eq!(decrypted_value["message"], message);
 }
+
+#[test]
+async fn decrypt_reference_without_backend(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let pkey = nw.key_pair.public_key();
+    let name = generate_fake_name();
+
+    let reference = SecretReference {
+        path: "SI_SECRET_DOCKER_HUB".to_string(),
+        key: Some("password".to_string()),
+    };
+    let crypted = sodiumoxide::crypto::sealedbox::seal(
+        &serde_json::to_vec(&reference).expect("failed to serialize reference"),
+        pkey,
+    );
+
+    let secret = EncryptedSecret::new_with_backend(
+        ctx,
+        &name,
+        SecretObjectType::Credential,
+        SecretKind::DockerHub,
+        &crypted,
+        nw.key_pair.pk(),
+        Default::default(),
+        Default::default(),
+        SecretBackendKind::EnvironmentVariable,
+    )
+    .await
+    .expect("failed to create encrypted secret");
+    assert_eq!(secret.backend(), &SecretBackendKind::EnvironmentVariable);
+
+    // The test services context has no external backends configured, so the reference can't be
+    // resolved--and the sealed reference must never be handed out as the secret's value.
+    let result = EncryptedSecret::get_by_id(ctx, secret.id())
+        .await
+        .expect("failed to fetch encrypted secret")
+        .expect("failed to find encrypted secret for tenancy and/or visibility")
+        .decrypt(ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(SecretError::Backend(
+            SecretBackendError::BackendNotConfigured(SecretBackendKind::EnvironmentVariable)
+        ))
+    ));
+}
+
+#[test]
+async fn definition_with_unsupported_schema_is_refused(ctx: &DalContext) {
+    let result = SecretDefinition::new(
+        ctx,
+        "githubToken",
+        None,
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "token": { "type": "string", "format": "password" },
+            },
+        }),
+    )
+    .await;
+    match result {
+        Err(SecretError::DefinitionSchemaUnsupported(name, problems)) => {
+            assert_eq!("githubToken", name);
+            assert_eq!(
+                vec!["/properties/token: unsupported keyword format".to_string()],
+                problems
+            );
+        }
+        other => panic!("definition should have been refused: {other:?}"),
+    }
+}
+
+#[test]
+async fn validate_against_definition(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let definition = SecretDefinition::new(
+        ctx,
+        "githubToken",
+        Some("A GitHub personal access token".to_string()),
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "token": { "type": "string", "pattern": "^ghp_" },
+            },
+            "required": ["token"],
+            "additionalProperties": false,
+        }),
+    )
+    .await
+    .expect("failed to create secret definition");
+    let found = SecretDefinition::find_by_name(ctx, "githubToken")
+        .await
+        .expect("failed to find secret definition")
+        .expect("secret definition not found");
+    assert_eq!(definition, found);
+
+    let kind = SecretKind::Defined("githubToken".to_string());
+    for (message, valid) in [
+        (serde_json::json!({ "token": "ghp_abc123" }), true),
+        (serde_json::json!({ "token": "nope" }), false),
+        (serde_json::json!({ "username": "octocat" }), false),
+    ] {
+        let secret = EncryptedSecret::new(
+            ctx,
+            generate_fake_name(),
+            SecretObjectType::Credential,
+            kind.clone(),
+            &encrypt_message(ctx, nw.key_pair.pk(), &message).await,
+            nw.key_pair.pk(),
+            Default::default(),
+            Default::default(),
+        )
+        .await
+        .expect("failed to create secret");
+        assert_eq!(secret.kind(), &kind);
+
+        let result = EncryptedSecret::get_by_id(ctx, secret.id())
+            .await
+            .expect("failed to get encrypted secret")
+            .expect("encrypted secret not found")
+            .validate_against_definition(ctx)
+            .await;
+        match (valid, result) {
+            (true, Ok(())) => {}
+            (false, Err(SecretError::MessageFailsSchema(failed_kind, errors))) => {
+                assert_eq!(kind, failed_kind);
+                assert_eq!(1, errors.len());
+            }
+            (_, result) => panic!("unexpected validation result for {message}: {result:?}"),
+        }
+    }
+}
+
+#[test]
+async fn validate_against_missing_definition(ctx: &DalContext, nw: &WorkspaceSignup) {
+    // Built in kinds don't need a definition
+    let secret = create_secret(ctx, nw.key_pair.pk()).await;
+    EncryptedSecret::get_by_id(ctx, secret.id())
+        .await
+        .expect("failed to get encrypted secret")
+        .expect("encrypted secret not found")
+        .validate_against_definition(ctx)
+        .await
+        .expect("built in kind should not need a definition");
+
+    let secret = EncryptedSecret::new(
+        ctx,
+        generate_fake_name(),
+        SecretObjectType::Credential,
+        SecretKind::Defined("datadogApiKey".to_string()),
+        &encrypt_message(
+            ctx,
+            nw.key_pair.pk(),
+            &serde_json::json!({ "apiKey": "abc" }),
+        )
+        .await,
+        nw.key_pair.pk(),
+        Default::default(),
+        Default::default(),
+    )
+    .await
+    .expect("failed to create secret");
+    let result = EncryptedSecret::get_by_id(ctx, secret.id())
+        .await
+        .expect("failed to get encrypted secret")
+        .expect("encrypted secret not found")
+        .validate_against_definition(ctx)
+        .await;
+    assert!(matches!(
+        result,
+        Err(SecretError::SecretDefinitionNotFound(SecretKind::Defined(name))) if name == "datadogApiKey"
+    ));
+}
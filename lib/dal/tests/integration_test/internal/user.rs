@@ -1,4 +1,7 @@
-use dal::{DalContext, User, UserPk, WorkspaceSignup};
+use dal::{
+    AccessLevel, DalContext, Permission, PermissionScope, User, UserPk, WorkspaceRole,
+    WorkspaceSignup,
+};
 use dal_test::test;
 
 #[test]
@@ -39,3 +42,57 @@ async fn authorize(ctx: &DalContext, nw: &WorkspaceSignup) {
     );
     */
 }
+
+#[test]
+async fn workspace_roles(ctx: &DalContext, nw: &WorkspaceSignup) {
+    let workspace_pk = *nw.workspace.pk();
+
+    // Whoever signs up administers the workspace.
+    let role = User::workspace_role(ctx, nw.user.pk(), workspace_pk)
+        .await
+        .expect("could not get workspace role");
+    assert_eq!(Some(WorkspaceRole::Admin), role);
+
+    let auditor = User::new(
+        ctx,
+        UserPk::generate(),
+        "auditor",
+        "auditor@systeminit.com",
+        None::<String>,
+    )
+    .await
+    .expect("cannot create user");
+    let read_funcs = Permission::new(PermissionScope::Func, AccessLevel::Read);
+    assert!(
+        !User::is_permitted(ctx, auditor.pk(), workspace_pk, read_funcs)
+            .await
+            .expect("could not check permission")
+    );
+
+    auditor
+        .associate_workspace(ctx, workspace_pk)
+        .await
+        .expect("could not associate workspace");
+    auditor
+        .set_workspace_role(ctx, workspace_pk, WorkspaceRole::Viewer)
+        .await
+        .expect("could not set workspace role");
+    assert!(
+        User::is_permitted(ctx, auditor.pk(), workspace_pk, read_funcs)
+            .await
+            .expect("could not check permission")
+    );
+    assert!(!User::is_permitted(
+        ctx,
+        auditor.pk(),
+        workspace_pk,
+        Permission::new(PermissionScope::Func, AccessLevel::Write)
+    )
+    .await
+    .expect("could not check permission"));
+
+    let members = User::list_workspace_members(ctx, workspace_pk)
+        .await
+        .expect("could not list workspace members");
+    assert_eq!(2, members.len());
+}
@@ -0,0 +1,371 @@
+use std::{collections::HashMap, time::Duration};
+
+use dal::{
+    tasks::WebhookDispatcher,
+    webhook::{WEBHOOK_DELIVERY_HEADER, WEBHOOK_EVENT_HEADER, WEBHOOK_SIGNATURE_HEADER},
+    ChangeSetPk, DalContext, Webhook, WebhookDelivery, WebhookError, WebhookPendingDelivery,
+    WebhookPendingDeliveryPk, WebhookSendOutcome, WsEvent,
+};
+use dal_test::test;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::mpsc,
+};
+
+/// A request the [`receiver`] was sent, with its header names lowercased.
+struct ReceivedRequest {
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Listen on a local port, answering every request with `status` and passing it on. Returns the
+/// url to reach the receiver at.
+async fn receiver(status: u16) -> (String, mpsc::UnboundedReceiver<ReceivedRequest>) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("cannot bind receiver");
+    let port = listener
+        .local_addr()
+        .expect("receiver has no local address")
+        .port();
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut buf = Vec::new();
+            let mut chunk = [0; 4096];
+            let header_end = loop {
+                if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+                let read = stream.read(&mut chunk).await.expect("cannot read request");
+                assert!(read > 0, "request ended before its headers");
+                buf.extend_from_slice(&chunk[..read]);
+            };
+            let headers: HashMap<String, String> = String::from_utf8_lossy(&buf[..header_end])
+                .lines()
+                .skip(1)
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_owned()))
+                .collect();
+            let length: usize = headers
+                .get("content-length")
+                .map(|length| length.parse().expect("invalid content length"))
+                .unwrap_or_default();
+            while buf.len() < header_end + length {
+                let read = stream.read(&mut chunk).await.expect("cannot read request");
+                assert!(read > 0, "request ended before its body");
+                buf.extend_from_slice(&chunk[..read]);
+            }
+            stream
+                .write_all(
+                    format!(
+                        "HTTP/1.1 {status} Test\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                    )
+                    .as_bytes(),
+                )
+                .await
+                .expect("cannot write response");
+            let body = buf[header_end..header_end + length].to_vec();
+            let _ = tx.send(ReceivedRequest { headers, body });
+        }
+    });
+    (format!("http://localhost:{port}/hook"), rx)
+}
+
+/// The attempts made so far at a pending delivery and whether its next attempt was put off, if it
+/// is still pending.
+async fn pending_state(ctx: &DalContext, pk: WebhookPendingDeliveryPk) -> Option<(i32, bool)> {
+    ctx.txns()
+        .await
+        .expect("cannot get transactions")
+        .pg()
+        .query_opt(
+            "SELECT attempt, next_attempt_at > updated_at AS backed_off
+             FROM webhook_pending_deliveries WHERE pk = $1",
+            &[&pk],
+        )
+        .await
+        .expect("cannot query pending deliveries")
+        .map(|row| (row.get("attempt"), row.get("backed_off")))
+}
+
+/// Queue an event for a new webhook posting to the receiver, committing both so the dispatcher
+/// can see them.
+async fn queue_delivery(ctx: &DalContext, url: &str) -> (Webhook, WebhookPendingDelivery) {
+    let webhook = Webhook::new(ctx, url, vec!["ChangeSetApplied".to_owned()], None)
+        .await
+        .expect("could not create webhook");
+    let event = WsEvent::change_set_applied(ctx, ChangeSetPk::generate())
+        .await
+        .expect("could not create event");
+    let pending = WebhookPendingDelivery::new(ctx, webhook.pk, &event)
+        .await
+        .expect("could not queue delivery");
+    ctx.commit().await.expect("cannot commit");
+    (webhook, pending)
+}
+
+#[test]
+async fn create_and_filter(ctx: &DalContext) {
+    let error = Webhook::new(
+        ctx,
+        "ftp://example.com/hook",
+        vec!["ChangeSetApplied".to_owned()],
+        None,
+    )
+    .await
+    .expect_err("only http and https urls are allowed");
+    assert!(matches!(error, WebhookError::InvalidUrl(_)));
+
+    let error = Webhook::new(
+        ctx,
+        "https://example.com/hook",
+        vec!["SomethingHappened".to_owned()],
+        None,
+    )
+    .await
+    .expect_err("unknown event kinds are refused");
+    assert!(matches!(error, WebhookError::UnknownEventKind(_)));
+
+    let error = Webhook::new(
+        ctx,
+        "http://169.254.169.254/latest/meta-data",
+        vec!["ChangeSetApplied".to_owned()],
+        None,
+    )
+    .await
+    .expect_err("addresses which aren't public are refused");
+    assert!(matches!(error, WebhookError::NonPublicAddress(_)));
+
+    let mut webhook = Webhook::new(
+        ctx,
+        "https://example.com/hook",
+        vec!["ChangeSetApplied".to_owned(), "FixBatchReturn".to_owned()],
+        Some("chat".to_owned()),
+    )
+    .await
+    .expect("could not create webhook");
+    assert_eq!(64, webhook.secret().len());
+
+    let subscribed = Webhook::list_for_event_kind(ctx, "ChangeSetApplied")
+        .await
+        .expect("could not list webhooks");
+    assert_eq!(
+        vec![webhook.pk],
+        subscribed.iter().map(|w| w.pk).collect::<Vec<_>>()
+    );
+    assert!(Webhook::list_for_event_kind(ctx, "ResourceRefreshed")
+        .await
+        .expect("could not list webhooks")
+        .is_empty());
+
+    webhook
+        .set_enabled(ctx, false)
+        .await
+        .expect("could not disable webhook");
+    assert!(Webhook::list_for_event_kind(ctx, "ChangeSetApplied")
+        .await
+        .expect("could not list webhooks")
+        .is_empty());
+}
+
+#[test]
+async fn delivery_log(ctx: &DalContext) {
+    let webhook = Webhook::new(
+        ctx,
+        "https://example.com/hook",
+        vec!["ChangeSetApplied".to_owned()],
+        None,
+    )
+    .await
+    .expect("could not create webhook");
+    let event = WsEvent::change_set_applied(ctx, ChangeSetPk::generate())
+        .await
+        .expect("could not create event");
+
+    let failure = WebhookSendOutcome {
+        status_code: Some(502),
+        error: Some("receiver responded with 502 Bad Gateway".to_owned()),
+    };
+    let success = WebhookSendOutcome {
+        status_code: Some(200),
+        error: None,
+    };
+    WebhookDelivery::new(ctx, webhook.pk, &event, 1, &failure)
+        .await
+        .expect("could not log delivery");
+    WebhookDelivery::new(ctx, webhook.pk, &event, 2, &success)
+        .await
+        .expect("could not log delivery");
+
+    let deliveries = webhook
+        .deliveries(ctx, 10)
+        .await
+        .expect("could not list deliveries");
+    assert_eq!(
+        vec![(2, true), (1, false)],
+        deliveries
+            .iter()
+            .map(|delivery| (delivery.attempt, delivery.succeeded))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!("ChangeSetApplied", deliveries[0].event_kind);
+
+    webhook.delete(ctx).await.expect("could not delete webhook");
+    assert!(Webhook::list(ctx)
+        .await
+        .expect("could not list webhooks")
+        .is_empty());
+}
+
+#[test]
+async fn pending_deliveries(ctx: &DalContext) {
+    let webhook = Webhook::new(
+        ctx,
+        "https://example.com/hook",
+        vec!["ChangeSetApplied".to_owned()],
+        None,
+    )
+    .await
+    .expect("could not create webhook");
+    let event = WsEvent::change_set_applied(ctx, ChangeSetPk::generate())
+        .await
+        .expect("could not create event");
+    let pending = WebhookPendingDelivery::new(ctx, webhook.pk, &event)
+        .await
+        .expect("could not queue delivery");
+    assert_eq!(0, pending.attempt);
+    assert_eq!(event, pending.event);
+
+    let lease = Duration::from_secs(60);
+    let claimed = WebhookPendingDelivery::claim_due(ctx, 10, lease)
+        .await
+        .expect("could not claim deliveries");
+    assert_eq!(
+        vec![pending.pk],
+        claimed.iter().map(|p| p.pk).collect::<Vec<_>>()
+    );
+    assert!(WebhookPendingDelivery::claim_due(ctx, 10, lease)
+        .await
+        .expect("could not claim deliveries")
+        .is_empty());
+
+    let mut pending = claimed.into_iter().next().expect("claimed one delivery");
+    pending
+        .reschedule(ctx, 1, Duration::ZERO)
+        .await
+        .expect("could not reschedule delivery");
+    let claimed = WebhookPendingDelivery::claim_due(ctx, 10, lease)
+        .await
+        .expect("could not claim deliveries");
+    assert_eq!(
+        vec![1],
+        claimed.iter().map(|p| p.attempt).collect::<Vec<_>>()
+    );
+
+    pending
+        .finish(ctx)
+        .await
+        .expect("could not finish delivery");
+    assert!(WebhookPendingDelivery::claim_due(ctx, 10, Duration::ZERO)
+        .await
+        .expect("could not claim deliveries")
+        .is_empty());
+}
+
+#[test]
+async fn dispatcher_delivers_signed_event(ctx: &DalContext) {
+    let (url, mut requests) = receiver(200).await;
+    let (webhook, pending) = queue_delivery(ctx, &url).await;
+    let pending_pk = pending.pk;
+
+    // The dispatcher's own client refuses to connect to the local receiver
+    WebhookDispatcher::deliver(ctx.services_context(), reqwest::Client::new(), pending)
+        .await
+        .expect("could not deliver");
+
+    let request = requests.recv().await.expect("receiver got no request");
+    assert_eq!(
+        Some(&Webhook::sign(webhook.secret(), &request.body)),
+        request
+            .headers
+            .get(&WEBHOOK_SIGNATURE_HEADER.to_lowercase())
+    );
+    assert_eq!(
+        Some(&"ChangeSetApplied".to_owned()),
+        request.headers.get(&WEBHOOK_EVENT_HEADER.to_lowercase())
+    );
+    assert_eq!(
+        Some(&pending_pk.to_string()),
+        request.headers.get(&WEBHOOK_DELIVERY_HEADER.to_lowercase())
+    );
+
+    let deliveries = webhook
+        .deliveries(ctx, 10)
+        .await
+        .expect("could not list deliveries");
+    assert_eq!(
+        vec![(1, true, Some(200))],
+        deliveries
+            .iter()
+            .map(|delivery| (delivery.attempt, delivery.succeeded, delivery.status_code))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(None, pending_state(ctx, pending_pk).await);
+}
+
+#[test]
+async fn dispatcher_retries_then_gives_up(ctx: &DalContext) {
+    let (url, mut requests) = receiver(500).await;
+    let (webhook, pending) = queue_delivery(ctx, &url).await;
+    let pending_pk = pending.pk;
+
+    WebhookDispatcher::deliver(ctx.services_context(), reqwest::Client::new(), pending)
+        .await
+        .expect("could not deliver");
+    requests.recv().await.expect("receiver got no request");
+    assert_eq!(Some((1, true)), pending_state(ctx, pending_pk).await);
+
+    // Skip ahead to the last attempt, due straight away
+    ctx.txns()
+        .await
+        .expect("cannot get transactions")
+        .pg()
+        .execute(
+            "UPDATE webhook_pending_deliveries
+             SET attempt = $2, next_attempt_at = clock_timestamp()
+             WHERE pk = $1",
+            &[&pending_pk, &(WebhookDispatcher::MAX_ATTEMPTS - 1)],
+        )
+        .await
+        .expect("cannot update pending delivery");
+    let pending = WebhookPendingDelivery::claim_due(ctx, 10, Duration::from_secs(60))
+        .await
+        .expect("could not claim deliveries")
+        .into_iter()
+        .find(|pending| pending.pk == pending_pk)
+        .expect("delivery is due");
+    ctx.commit().await.expect("cannot commit");
+
+    WebhookDispatcher::deliver(ctx.services_context(), reqwest::Client::new(), pending)
+        .await
+        .expect("could not deliver");
+    requests.recv().await.expect("receiver got no request");
+    assert_eq!(None, pending_state(ctx, pending_pk).await);
+
+    let deliveries = webhook
+        .deliveries(ctx, 10)
+        .await
+        .expect("could not list deliveries");
+    assert_eq!(
+        vec![
+            (WebhookDispatcher::MAX_ATTEMPTS, false, Some(500)),
+            (1, false, Some(500))
+        ],
+        deliveries
+            .iter()
+            .map(|delivery| (delivery.attempt, delivery.succeeded, delivery.status_code))
+            .collect::<Vec<_>>()
+    );
+}
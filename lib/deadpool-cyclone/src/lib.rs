@@ -23,6 +23,7 @@ pub use self::instance::{Instance, Spec};
 
 pub use cyclone_client::{
     ClientError, CycloneClient, EncryptionKey, EncryptionKeyError, ExecutionError,
+    ExecutionStarted, UnixStream,
 };
 pub use cyclone_core::{
     ActionRunRequest, ActionRunResultSuccess, ComponentView, FunctionResult, FunctionResultFailure,
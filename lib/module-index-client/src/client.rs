@@ -1,7 +1,9 @@
 use ulid::Ulid;
 use url::Url;
 
-use crate::{IndexClientResult, ModuleDetailsResponse};
+use crate::{
+    IndexClientResult, ListModuleVersionsResponse, ListModulesResponse, ModuleDetailsResponse,
+};
 
 #[derive(Debug, Clone)]
 pub struct IndexClient {
@@ -56,4 +58,73 @@ impl IndexClient {
 
         Ok(bytes.to_vec())
     }
+
+    /// Lists the modules in the index, optionally only those whose name contains `name` and
+    /// which have at least one version in the semver range `version_range`.
+    pub async fn list_modules(
+        &self,
+        name: Option<&str>,
+        version_range: Option<&str>,
+    ) -> IndexClientResult<ListModulesResponse> {
+        let mut query = vec![];
+        if let Some(name) = name {
+            query.push(("name", name));
+        }
+        if let Some(version_range) = version_range {
+            query.push(("versionRange", version_range));
+        }
+
+        let list_url = self.base_url.join("modules")?;
+        let response = reqwest::Client::new()
+            .get(list_url)
+            .query(&query)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.json::<ListModulesResponse>().await?)
+    }
+
+    /// Lists every version of a module, newest first.
+    pub async fn list_module_versions(
+        &self,
+        module_id: Ulid,
+    ) -> IndexClientResult<ListModuleVersionsResponse> {
+        let versions_url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{module_id}/"))?
+            .join("versions")?;
+        let response = reqwest::Client::new()
+            .get(versions_url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.json::<ListModuleVersionsResponse>().await?)
+    }
+
+    pub async fn download_module_version(
+        &self,
+        module_id: Ulid,
+        version: &str,
+    ) -> IndexClientResult<Vec<u8>> {
+        let download_url = self
+            .base_url
+            .join("modules/")?
+            .join(&format!("{module_id}/"))?
+            .join("versions/")?
+            .join(&format!("{version}/"))?
+            .join("download")?;
+        let response = reqwest::Client::new()
+            .get(download_url)
+            .bearer_auth(&self.auth_token)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(response.bytes().await?.to_vec())
+    }
 }
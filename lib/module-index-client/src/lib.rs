@@ -2,6 +2,9 @@ pub mod client;
 pub mod types;
 
 pub use client::IndexClient;
-pub use types::{FuncMetadata, IndexClientError, IndexClientResult, ModuleDetailsResponse};
+pub use types::{
+    parse_version, FuncMetadata, IndexClientError, IndexClientResult, ListModuleVersionsResponse,
+    ListModulesResponse, ModuleDetailsResponse, ModuleVersionResponse,
+};
 
 pub const DEFAULT_URL: &str = "http://localhost:5157";
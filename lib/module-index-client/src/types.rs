@@ -31,6 +31,34 @@ pub struct ModuleDetailsResponse {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListModulesResponse {
+    pub modules: Vec<ModuleDetailsResponse>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleVersionResponse {
+    pub module_id: String,
+    pub version: String,
+    pub hash: String,
+    pub metadata: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Parses a module version as a semantic version, tolerating a leading `v`.
+pub fn parse_version(version: &str) -> Option<semver::Version> {
+    let version = version.trim();
+    semver::Version::parse(version.strip_prefix('v').unwrap_or(version)).ok()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListModuleVersionsResponse {
+    pub versions: Vec<ModuleVersionResponse>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FuncMetadata {
@@ -38,3 +66,30 @@ pub struct FuncMetadata {
     pub display_name: Option<String>,
     pub description: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_semantic_versions() {
+        assert_eq!(Some(semver::Version::new(1, 2, 3)), parse_version("1.2.3"));
+        assert_eq!(Some(semver::Version::new(1, 2, 3)), parse_version("v1.2.3"));
+        assert_eq!(
+            Some(semver::Version::new(1, 2, 3)),
+            parse_version(" v1.2.3 ")
+        );
+        assert_eq!(
+            Some(semver::Version::parse("2.0.0-beta.1").expect("valid version")),
+            parse_version("2.0.0-beta.1")
+        );
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        assert_eq!(None, parse_version("2023-06-01"));
+        assert_eq!(None, parse_version("1.2"));
+        assert_eq!(None, parse_version("vv1.2.3"));
+        assert_eq!(None, parse_version(""));
+    }
+}
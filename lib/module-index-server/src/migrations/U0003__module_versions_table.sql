@@ -0,0 +1,18 @@
+CREATE TABLE module_versions
+(
+    module_id                   ident                    NOT NULL REFERENCES modules (id),
+    version                     text                     NOT NULL,
+    hash                        char(64)                 NOT NULL,
+    metadata                    json,
+    created_at                  timestamp with time zone NOT NULL DEFAULT CLOCK_TIMESTAMP(),
+    PRIMARY KEY (module_id, version)
+);
+
+-- Every module uploaded before versions were tracked has exactly one version: its latest one
+INSERT INTO module_versions (module_id, version, hash, metadata, created_at)
+SELECT id,
+       COALESCE(metadata ->> 'version', ''),
+       latest_hash,
+       metadata,
+       COALESCE(latest_hash_created_at, created_at)
+FROM modules;
@@ -1 +1,2 @@
 pub mod si_module;
+pub mod si_module_version;
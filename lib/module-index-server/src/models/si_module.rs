@@ -25,7 +25,16 @@ pub struct Model {
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::si_module_version::Entity")]
+    Versions,
+}
+
+impl Related<super::si_module_version::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Versions.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
 
@@ -0,0 +1,119 @@
+use std::cmp::Ordering;
+
+use module_index_client::parse_version;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::si_module::ModuleId;
+
+/// Every version of a module ever uploaded, keyed by the version from the module's `PkgSpec`.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[sea_orm(table_name = "module_versions")]
+pub struct Model {
+    #[sea_orm(
+        primary_key,
+        auto_increment = false,
+        column_type = r##"custom("ident")"##
+    )]
+    pub module_id: ModuleId,
+    #[sea_orm(primary_key, auto_increment = false, column_type = "Text")]
+    pub version: String,
+    pub hash: String,
+    pub metadata: Json,
+    pub created_at: DateTimeWithTimeZone,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::si_module::Entity",
+        from = "Column::ModuleId",
+        to = "super::si_module::Column::Id"
+    )]
+    Module,
+}
+
+impl Related<super::si_module::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Module.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    /// The version parsed as a semantic version, if it is one.
+    pub fn semver(&self) -> Option<semver::Version> {
+        parse_version(&self.version)
+    }
+
+    /// Whether the version is a semantic version which satisfies `range`.
+    pub fn matches(&self, range: &semver::VersionReq) -> bool {
+        self.semver()
+            .map(|version| range.matches(&version))
+            .unwrap_or(false)
+    }
+
+    /// Orders versions newest first. Semantic versions come before any which aren't, and
+    /// otherwise the most recently uploaded version wins.
+    pub fn cmp_newest_first(&self, other: &Self) -> Ordering {
+        other
+            .semver()
+            .cmp(&self.semver())
+            .then_with(|| other.created_at.cmp(&self.created_at))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, FixedOffset, Offset, Utc};
+    use ulid::Ulid;
+
+    use super::*;
+
+    fn version(version: &str, created_at: DateTime<FixedOffset>) -> Model {
+        Model {
+            module_id: ModuleId(Ulid::new()),
+            version: version.to_owned(),
+            hash: "hash".to_owned(),
+            metadata: serde_json::json!({}),
+            created_at,
+        }
+    }
+
+    #[test]
+    fn matches_only_semantic_versions_in_range() {
+        let now = DateTime::<FixedOffset>::from_utc(Utc::now().naive_utc(), Utc.fix());
+        let range = semver::VersionReq::parse("^1.2").expect("valid range");
+
+        assert!(version("1.2.0", now).matches(&range));
+        assert!(version("v1.9.3", now).matches(&range));
+        assert!(!version("2.0.0", now).matches(&range));
+        assert!(!version("1.1.9", now).matches(&range));
+        assert!(!version("2023-06-01", now).matches(&range));
+    }
+
+    #[test]
+    fn orders_newest_first() {
+        let now = DateTime::<FixedOffset>::from_utc(Utc::now().naive_utc(), Utc.fix());
+        let earlier = now - Duration::hours(1);
+        let mut versions = vec![
+            version("1.0.0", now),
+            version("nightly", earlier),
+            version("v2.0.0", earlier),
+            version("nightly-2", now),
+            version("1.10.0", earlier),
+        ];
+
+        versions.sort_by(Model::cmp_newest_first);
+
+        assert_eq!(
+            vec!["v2.0.0", "1.10.0", "1.0.0", "nightly-2", "nightly"],
+            versions
+                .iter()
+                .map(|version| version.version.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+}
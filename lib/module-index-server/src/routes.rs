@@ -11,7 +11,9 @@ use thiserror::Error;
 use tower_http::cors::CorsLayer;
 
 mod download_module_route;
+mod download_module_version_route;
 mod get_module_details_route;
+mod list_module_versions_route;
 mod list_modules_route;
 pub(crate) mod upsert_module_route;
 
@@ -32,6 +34,14 @@ pub fn routes(state: AppState) -> Router {
             "/modules/:module_id/download",
             get(download_module_route::download_module_route),
         )
+        .route(
+            "/modules/:module_id/versions",
+            get(list_module_versions_route::list_module_versions_route),
+        )
+        .route(
+            "/modules/:module_id/versions/:version/download",
+            get(download_module_version_route::download_module_version_route),
+        )
         .layer(CorsLayer::permissive());
 
     router.with_state(state)
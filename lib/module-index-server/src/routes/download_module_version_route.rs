@@ -0,0 +1,61 @@
+use axum::{
+    extract::Path,
+    response::{IntoResponse, Redirect, Response},
+    Json,
+};
+use hyper::StatusCode;
+use s3::error::S3Error;
+use sea_orm::{DbErr, EntityTrait};
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection, ExtractedS3Bucket},
+    models::{si_module::ModuleId, si_module_version},
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum DownloadModuleVersionError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error(r#"Version "{1}" of module "{0}" not found"#)]
+    NotFound(ModuleId, String),
+    #[error("s3 error: {0}")]
+    S3Error(#[from] S3Error),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for DownloadModuleVersionError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::NotFound(_, _) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub async fn download_module_version_route(
+    Path((module_id, version)): Path<(ModuleId, String)>,
+    Authorization { .. }: Authorization,
+    ExtractedS3Bucket(s3_bucket): ExtractedS3Bucket,
+    DbConnection(txn): DbConnection,
+) -> Result<Redirect, DownloadModuleVersionError> {
+    let module_version = match si_module_version::Entity::find_by_id((module_id, version.clone()))
+        .one(&txn)
+        .await?
+    {
+        Some(module_version) => module_version,
+        _ => return Err(DownloadModuleVersionError::NotFound(module_id, version)),
+    };
+
+    let download_url =
+        s3_bucket.presign_get(format!("{}.sipkg", module_version.hash), 60 * 5, None)?;
+
+    Ok(Redirect::temporary(&download_url))
+}
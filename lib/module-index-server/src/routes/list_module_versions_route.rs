@@ -0,0 +1,85 @@
+use axum::{
+    extract::{Path, Query},
+    response::{IntoResponse, Response},
+    Json,
+};
+use hyper::StatusCode;
+use sea_orm::{DbErr, EntityTrait, ModelTrait};
+use semver::VersionReq;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    extract::{Authorization, DbConnection},
+    models::{
+        si_module::{self, ModuleId},
+        si_module_version,
+    },
+};
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum ListModuleVersionsError {
+    #[error("db error: {0}")]
+    DbErr(#[from] DbErr),
+    #[error("invalid version range: {0}")]
+    InvalidVersionRange(#[from] semver::Error),
+    #[error(r#"Module "{0}" not found"#)]
+    NotFound(ModuleId),
+}
+
+// TODO: figure out how to not keep this serialization logic here
+impl IntoResponse for ListModuleVersionsError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            Self::InvalidVersionRange(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            Self::NotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListModuleVersionsRequest {
+    /// Only list versions in this semver range, e.g. `>1.2.0`
+    pub version_range: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListModuleVersionsResponse {
+    versions: Vec<si_module_version::Model>,
+}
+
+pub async fn list_module_versions_route(
+    Path(module_id): Path<ModuleId>,
+    Authorization { .. }: Authorization,
+    DbConnection(txn): DbConnection,
+    Query(request): Query<ListModuleVersionsRequest>,
+) -> Result<Json<ListModuleVersionsResponse>, ListModuleVersionsError> {
+    let module = match si_module::Entity::find_by_id(module_id).one(&txn).await? {
+        Some(module) => module,
+        _ => return Err(ListModuleVersionsError::NotFound(module_id)),
+    };
+
+    let mut versions = module
+        .find_related(si_module_version::Entity)
+        .all(&txn)
+        .await?;
+
+    if let Some(version_range) = request.version_range {
+        let version_range = VersionReq::parse(&version_range)?;
+        versions.retain(|version| version.matches(&version_range));
+    }
+
+    versions.sort_by(si_module_version::Model::cmp_newest_first);
+
+    Ok(Json(ListModuleVersionsResponse { versions }))
+}
@@ -5,13 +5,14 @@ use axum::{
 };
 use hyper::StatusCode;
 use sea_orm::{ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder};
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::{
     app_state::AppState,
     extract::{Authorization, DbConnection},
-    models::si_module,
+    models::{si_module, si_module_version},
     whoami::{is_systeminit_auth_token, WhoamiError},
 };
 
@@ -20,6 +21,8 @@ use crate::{
 pub enum ListModulesError {
     #[error("db error: {0}")]
     DbErr(#[from] DbErr),
+    #[error("invalid version range: {0}")]
+    InvalidVersionRange(#[from] semver::Error),
     #[error("whoami error: {0}")]
     Whoami(#[from] WhoamiError),
 }
@@ -27,7 +30,10 @@ pub enum ListModulesError {
 // TODO: figure out how to not keep this serialization logic here
 impl IntoResponse for ListModulesError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+        let (status, error_message) = match self {
+            Self::InvalidVersionRange(_) => (StatusCode::BAD_REQUEST, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
 
         let body = Json(
             serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
@@ -41,6 +47,8 @@ impl IntoResponse for ListModulesError {
 #[serde(rename_all = "camelCase")]
 pub struct ListModulesRequest {
     pub name: Option<String>,
+    /// Only list modules with at least one version in this semver range, e.g. `^1.2`
+    pub version_range: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -76,7 +84,24 @@ pub async fn list_module_route(
     // ordering
     let query = query.order_by_asc(si_module::Column::Name);
 
-    let modules: Vec<si_module::Model> = query.all(&txn).await?;
+    let modules: Vec<si_module::Model> = match request.version_range {
+        Some(version_range) => {
+            let version_range = VersionReq::parse(&version_range)?;
+            query
+                .find_with_related(si_module_version::Entity)
+                .all(&txn)
+                .await?
+                .into_iter()
+                .filter(|(_, versions)| {
+                    versions
+                        .iter()
+                        .any(|version| version.matches(&version_range))
+                })
+                .map(|(module, _)| module)
+                .collect()
+        }
+        None => query.all(&txn).await?,
+    };
 
     Ok(Json(ListModulesResponse { modules }))
 }
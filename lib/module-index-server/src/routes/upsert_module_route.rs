@@ -5,9 +5,12 @@ use axum::{
 };
 use chrono::{DateTime, FixedOffset, Offset, Utc};
 use hyper::StatusCode;
-use module_index_client::{FuncMetadata, ModuleDetailsResponse};
+use module_index_client::{parse_version, FuncMetadata, ModuleDetailsResponse};
 use s3::error::S3Error;
-use sea_orm::{ActiveModelTrait, DbErr, Set};
+use sea_orm::{
+    sqlx, ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, RuntimeErr,
+    Set,
+};
 use serde::{Deserialize, Serialize};
 use si_pkg::{SiPkg, SiPkgError};
 use telemetry::prelude::*;
@@ -16,7 +19,7 @@ use ulid::Ulid;
 
 use crate::{
     extract::{Authorization, DbConnection, ExtractedS3Bucket},
-    models::si_module,
+    models::{si_module, si_module_version},
 };
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -40,12 +43,30 @@ pub enum UpsertModuleError {
     SiPkgError(#[from] SiPkgError),
     #[error("upload is required")]
     UploadRequiredError,
+    #[error(r#"Version "{1}" of module "{0}" has already been uploaded"#)]
+    VersionAlreadyExists(String, String),
+}
+
+/// Postgres' SQLSTATE for a row that duplicates a unique key.
+const UNIQUE_VIOLATION: &str = "23505";
+
+fn is_unique_violation(err: &DbErr) -> bool {
+    match err {
+        DbErr::Exec(RuntimeErr::SqlxError(sqlx::Error::Database(err)))
+        | DbErr::Query(RuntimeErr::SqlxError(sqlx::Error::Database(err))) => {
+            err.code().as_deref() == Some(UNIQUE_VIOLATION)
+        }
+        _ => false,
+    }
 }
 
 // TODO: figure out how to not keep this serialization logic here
 impl IntoResponse for UpsertModuleError {
     fn into_response(self) -> Response {
-        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+        let (status, error_message) = match self {
+            Self::VersionAlreadyExists(_, _) => (StatusCode::CONFLICT, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
 
         let body = Json(
             serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
@@ -91,37 +112,102 @@ pub async fn upsert_module_route(
         })
         .collect();
 
-    let new_module = si_module::ActiveModel {
-        name: Set(module_metadata.name().to_owned()),
-        description: Set(Some(module_metadata.description().to_owned())),
-        // owner_user_id: Set(claim.user_pk.to_string()),
-        owner_user_id: Set(Ulid::new().to_string()),
-        owner_display_name: Set(Some(module_metadata.created_by().to_owned())),
-        latest_hash: Set(module_metadata.hash().to_string()),
-        // maybe use db's `CLOCK_TIMESTAMP()`?
-        latest_hash_created_at: Set(DateTime::<FixedOffset>::from_utc(
-            Utc::now().naive_utc(),
-            Utc.fix(),
-        )),
-        metadata: Set(serde_json::to_value(ExtraMetadata {
-            version,
-            schemas,
-            funcs,
-        })?),
-        ..Default::default() // all other attributes are `NotSet`
-    };
+    let module_name = module_metadata.name().to_owned();
+    let hash = module_metadata.hash().to_string();
+    // maybe use db's `CLOCK_TIMESTAMP()`?
+    let now = DateTime::<FixedOffset>::from_utc(Utc::now().naive_utc(), Utc.fix());
+    let metadata = serde_json::to_value(ExtraMetadata {
+        version: version.clone(),
+        schemas,
+        funcs,
+    })?;
+
+    // Uploads of a module with a name we've seen before are new versions of that module
+    let existing_module = si_module::Entity::find()
+        .filter(si_module::Column::Name.eq(module_name.as_str()))
+        .order_by_desc(si_module::Column::CreatedAt)
+        .one(&txn)
+        .await?;
+
+    if let Some(existing_module) = &existing_module {
+        if si_module_version::Entity::find_by_id((existing_module.id, version.clone()))
+            .one(&txn)
+            .await?
+            .is_some()
+        {
+            return Err(UpsertModuleError::VersionAlreadyExists(
+                module_name,
+                version,
+            ));
+        }
+    }
 
     // TODO: put below
     // upload to s3
-    s3_bucket
-        .put_object(format!("{}.sipkg", module_metadata.hash()), &data)
-        .await?;
+    s3_bucket.put_object(format!("{hash}.sipkg"), &data).await?;
+
+    let module: si_module::Model = match existing_module {
+        Some(existing_module) => {
+            // An older version uploaded after a newer one doesn't become the latest
+            let latest_version =
+                serde_json::from_value::<ExtraMetadata>(existing_module.metadata.clone())
+                    .ok()
+                    .and_then(|metadata| parse_version(&metadata.version));
+            let is_older = match (parse_version(&version), latest_version) {
+                (Some(version), Some(latest_version)) => version < latest_version,
+                _ => false,
+            };
 
-    let new_module: si_module::Model = new_module.insert(&txn).await?;
+            if is_older {
+                existing_module
+            } else {
+                let mut module: si_module::ActiveModel = existing_module.into();
+                module.description = Set(Some(module_metadata.description().to_owned()));
+                module.owner_display_name = Set(Some(module_metadata.created_by().to_owned()));
+                module.latest_hash = Set(hash.clone());
+                module.latest_hash_created_at = Set(now);
+                module.metadata = Set(metadata.clone());
+                module.update(&txn).await?
+            }
+        }
+        None => {
+            let new_module = si_module::ActiveModel {
+                name: Set(module_name),
+                description: Set(Some(module_metadata.description().to_owned())),
+                // owner_user_id: Set(claim.user_pk.to_string()),
+                owner_user_id: Set(Ulid::new().to_string()),
+                owner_display_name: Set(Some(module_metadata.created_by().to_owned())),
+                latest_hash: Set(hash.clone()),
+                latest_hash_created_at: Set(now),
+                metadata: Set(metadata.clone()),
+                ..Default::default() // all other attributes are `NotSet`
+            };
+            new_module.insert(&txn).await?
+        }
+    };
+
+    // The lookup above races with concurrent uploads of the same version, so
+    // the primary key has the final say
+    si_module_version::ActiveModel {
+        module_id: Set(module.id),
+        version: Set(version.clone()),
+        hash: Set(hash),
+        metadata: Set(metadata),
+        created_at: Set(now),
+    }
+    .insert(&txn)
+    .await
+    .map_err(|err| {
+        if is_unique_violation(&err) {
+            UpsertModuleError::VersionAlreadyExists(module.name.clone(), version)
+        } else {
+            err.into()
+        }
+    })?;
 
     txn.commit().await?;
 
-    Ok(dbg!(Json(new_module.try_into()?)))
+    Ok(dbg!(Json(module.try_into()?)))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -130,3 +216,39 @@ pub struct ExtraMetadata {
     pub schemas: Vec<String>,
     pub funcs: Vec<FuncMetadata>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn duplicate_versions_are_conflicts() {
+        let response =
+            UpsertModuleError::VersionAlreadyExists("docker".to_owned(), "1.0.0".to_owned())
+                .into_response();
+        assert_eq!(StatusCode::CONFLICT, response.status());
+
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("cannot read body");
+        let body: serde_json::Value = serde_json::from_slice(&body).expect("body is not json");
+        assert_eq!(409, body["error"]["statusCode"]);
+
+        assert_eq!(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            UpsertModuleError::UploadRequiredError
+                .into_response()
+                .status()
+        );
+    }
+
+    #[test]
+    fn only_unique_violations_are_duplicates() {
+        assert!(!is_unique_violation(&DbErr::RecordNotFound(
+            "module".to_owned()
+        )));
+        assert!(!is_unique_violation(&DbErr::Exec(RuntimeErr::Internal(
+            "connection reset".to_owned()
+        ))));
+    }
+}
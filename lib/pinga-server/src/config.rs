@@ -1,6 +1,7 @@
 use std::{env, path::Path};
 
 use buck2_resources::Buck2Resources;
+use dal::{secret::SecretBackendsConfig, DEFAULT_FIX_CONCURRENCY_LIMIT};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 use si_data_nats::NatsConfig;
@@ -13,6 +14,8 @@ pub use dal::CycloneKeyPair;
 pub use si_settings::{StandardConfig, StandardConfigFile};
 use ulid::Ulid;
 
+use crate::JobQueueConfig;
+
 const DEFAULT_CONCURRENCY_LIMIT: usize = 5;
 
 #[remain::sorted]
@@ -49,8 +52,17 @@ pub struct Config {
     #[builder(default = "default_concurrency_limit()")]
     concurrency: usize,
 
+    #[builder(default = "default_fix_concurrency_limit()")]
+    fix_concurrency_limit: usize,
+
     #[builder(default = "random_instance_id()")]
     instance_id: String,
+
+    #[builder(default)]
+    secret_backends: SecretBackendsConfig,
+
+    #[builder(default)]
+    job_queue: JobQueueConfig,
 }
 
 impl StandardConfig for Config {
@@ -86,10 +98,25 @@ impl Config {
         self.concurrency
     }
 
+    /// Gets the number of fixes in a batch which may run at once.
+    pub fn fix_concurrency_limit(&self) -> usize {
+        self.fix_concurrency_limit
+    }
+
     /// Gets the config's instance ID.
     pub fn instance_id(&self) -> &str {
         self.instance_id.as_ref()
     }
+
+    /// Gets a reference to the config's secret backends.
+    pub fn secret_backends(&self) -> &SecretBackendsConfig {
+        &self.secret_backends
+    }
+
+    /// Gets a reference to the config's durable job queue.
+    pub fn job_queue(&self) -> &JobQueueConfig {
+        &self.job_queue
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -102,8 +129,14 @@ pub struct ConfigFile {
     cyclone_encryption_key_path: String,
     #[serde(default = "default_concurrency_limit")]
     concurrency_limit: usize,
+    #[serde(default = "default_fix_concurrency_limit")]
+    fix_concurrency_limit: usize,
     #[serde(default = "random_instance_id")]
     instance_id: String,
+    #[serde(default)]
+    secret_backends: SecretBackendsConfig,
+    #[serde(default)]
+    job_queue: JobQueueConfig,
 }
 
 impl Default for ConfigFile {
@@ -113,7 +146,10 @@ impl Default for ConfigFile {
             nats: Default::default(),
             cyclone_encryption_key_path: default_cyclone_encryption_key_path(),
             concurrency_limit: default_concurrency_limit(),
+            fix_concurrency_limit: default_fix_concurrency_limit(),
             instance_id: random_instance_id(),
+            secret_backends: Default::default(),
+            job_queue: Default::default(),
         }
     }
 }
@@ -133,7 +169,10 @@ impl TryFrom<ConfigFile> for Config {
         config.nats(value.nats);
         config.cyclone_encryption_key_path(value.cyclone_encryption_key_path.try_into()?);
         config.concurrency(value.concurrency_limit);
+        config.fix_concurrency_limit(value.fix_concurrency_limit);
         config.instance_id(value.instance_id);
+        config.secret_backends(value.secret_backends);
+        config.job_queue(value.job_queue);
         config.build().map_err(Into::into)
     }
 }
@@ -150,6 +189,10 @@ fn default_concurrency_limit() -> usize {
     DEFAULT_CONCURRENCY_LIMIT
 }
 
+fn default_fix_concurrency_limit() -> usize {
+    DEFAULT_FIX_CONCURRENCY_LIMIT
+}
+
 #[allow(clippy::disallowed_methods)] // Used to determine if running in development
 pub fn detect_and_configure_development(config: &mut ConfigFile) -> Result<()> {
     if env::var("BUCK_RUN_BUILD_ID").is_ok() || env::var("BUCK_BUILD_ID").is_ok() {
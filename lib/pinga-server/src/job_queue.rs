@@ -0,0 +1,601 @@
+//! The durable job queue which pinga consumes non-blocking jobs from.
+//!
+//! Jobs are published to a [JetStream](https://docs.nats.io/nats-concepts/jetstream) stream with
+//! work queue retention and delivered to every pinga instance through a single durable push
+//! consumer, so a job which is in flight when an instance stops is redelivered rather than lost.
+//! Each delivery must be explicitly acknowledged: a job which fails is retried with a backoff
+//! taken from the [`JobRetryPolicy`] for its kind and, once it runs out of attempts, is moved to a
+//! dead-letter stream where it can be inspected and replayed.
+//!
+//! Blocking jobs are still sent over a core NATS request/reply subject as the spawning job waits
+//! on the reply, which JetStream does not carry through to the consumer.
+
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use dal::job::consumer::JobInfo;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use si_data_nats::{
+    jetstream::{
+        AckKind, AckPolicy, ConsumerConfig, DeliverPolicy, JetStream, RetentionPolicy, StorageType,
+        StreamConfig,
+    },
+    Message, NatsClient, NatsConfig, NatsError,
+};
+use telemetry::prelude::*;
+use thiserror::Error;
+use tokio::{task::JoinHandle, time};
+
+use crate::{
+    nats_subject, NATS_JOBS_DEAD_LETTER_SUBJECT, NATS_JOBS_DEFAULT_QUEUE,
+    NATS_JOBS_DELIVER_SUBJECT, NATS_JOBS_DURABLE_SUBJECT,
+};
+
+/// How long to wait for another dead-lettered job before assuming the stream has been read.
+const DEAD_LETTER_READ_IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The retry policy for jobs which run actions against real resources. Running such a job again
+/// could run an action twice, such as creating a resource twice, so unless the config says
+/// otherwise a failed attempt is dead-lettered straight away for someone to look at.
+const RUNS_ACTIONS_RETRY_POLICY: JobRetryPolicy = JobRetryPolicy {
+    max_attempts: 1,
+    initial_backoff_ms: 1_000,
+    max_backoff_ms: 60_000,
+    multiplier: 2,
+};
+/// The kinds of job which are given [`RUNS_ACTIONS_RETRY_POLICY`] by default.
+const RUNS_ACTIONS_JOB_KINDS: &[&str] = &["FixesJob"];
+
+#[remain::sorted]
+#[derive(Debug, Error)]
+pub enum JobQueueError {
+    #[error(transparent)]
+    Nats(#[from] NatsError),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+pub type JobQueueResult<T> = Result<T, JobQueueError>;
+
+/// How often, and how far apart, a failed job of a given kind is attempted before it is
+/// dead-lettered.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct JobRetryPolicy {
+    /// The total number of times a job is attempted, including the first attempt.
+    pub max_attempts: u32,
+    /// How long to wait before the second attempt.
+    pub initial_backoff_ms: u64,
+    /// The longest to ever wait between two attempts.
+    pub max_backoff_ms: u64,
+    /// What the backoff is multiplied by after each failed attempt.
+    pub multiplier: u32,
+}
+
+impl Default for JobRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff_ms: 1_000,
+            max_backoff_ms: 60_000,
+            multiplier: 2,
+        }
+    }
+}
+
+impl JobRetryPolicy {
+    /// Returns how long to wait before retrying a job whose `attempt`th attempt (counting from
+    /// 1) has just failed.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = u64::from(self.multiplier).saturating_pow(attempt.saturating_sub(1));
+        Duration::from_millis(
+            self.initial_backoff_ms
+                .saturating_mul(factor)
+                .min(self.max_backoff_ms),
+        )
+    }
+
+    /// Returns true if a job may be attempted again after its `attempt`th attempt failed.
+    pub fn should_retry(&self, attempt: u32) -> bool {
+        attempt < self.max_attempts
+    }
+}
+
+/// Configures the streams and consumer backing the durable job queue.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct JobQueueConfig {
+    /// The name of the stream holding queued jobs.
+    pub stream_name: String,
+    /// The name of the stream holding dead-lettered jobs.
+    pub dead_letter_stream_name: String,
+    /// How long a delivered job may go without an acknowledgement, or a sign of progress, before
+    /// it is redelivered.
+    pub ack_wait_secs: u64,
+    /// The maximum number of jobs which may be delivered and not yet acknowledged, across all
+    /// pinga instances.
+    pub max_in_flight: i64,
+    /// How long dead-lettered jobs are kept before they are discarded.
+    pub dead_letter_max_age_secs: u64,
+    /// Whether the streams are stored in memory rather than on disk.
+    pub in_memory: bool,
+    /// The retry policy for jobs of any kind without a policy in `retry_policies`, except for
+    /// jobs which run actions, such as `FixesJob`, which are only attempted once.
+    pub default_retry_policy: JobRetryPolicy,
+    /// Retry policies keyed by [`JobInfo`] kind, such as `FixesJob`.
+    pub retry_policies: HashMap<String, JobRetryPolicy>,
+}
+
+impl Default for JobQueueConfig {
+    fn default() -> Self {
+        Self {
+            stream_name: "PINGA_JOBS".to_string(),
+            dead_letter_stream_name: "PINGA_JOBS_DEAD_LETTER".to_string(),
+            ack_wait_secs: 30,
+            max_in_flight: 1024,
+            dead_letter_max_age_secs: 14 * 24 * 60 * 60,
+            in_memory: false,
+            default_retry_policy: JobRetryPolicy::default(),
+            retry_policies: HashMap::new(),
+        }
+    }
+}
+
+impl JobQueueConfig {
+    /// Gets the retry policy for jobs of the given kind.
+    pub fn retry_policy(&self, kind: &str) -> &JobRetryPolicy {
+        match self.retry_policies.get(kind) {
+            Some(policy) => policy,
+            None if RUNS_ACTIONS_JOB_KINDS.contains(&kind) => &RUNS_ACTIONS_RETRY_POLICY,
+            None => &self.default_retry_policy,
+        }
+    }
+
+    fn ack_wait(&self) -> Duration {
+        Duration::from_secs(self.ack_wait_secs)
+    }
+
+    /// How often a job which is still being worked on, or waiting to be retried, tells the server
+    /// it's alive so that it isn't redelivered.
+    fn progress_interval(&self) -> Duration {
+        (self.ack_wait() / 3).max(Duration::from_millis(100))
+    }
+
+    fn storage(&self) -> StorageType {
+        if self.in_memory {
+            StorageType::Memory
+        } else {
+            StorageType::File
+        }
+    }
+}
+
+/// A job which was moved to the dead-letter stream after it ran out of attempts.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetter {
+    pub job: JobInfo,
+    pub error: String,
+    pub attempts: u32,
+    pub dead_lettered_at: DateTime<Utc>,
+}
+
+/// A dead-lettered job together with its position on the dead-letter stream.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeadLetteredJob {
+    pub sequence: u64,
+    #[serde(flatten)]
+    pub dead_letter: DeadLetter,
+}
+
+/// The durable job queue for a NATS connection and its subject prefix.
+#[derive(Clone, Debug)]
+pub struct JobQueue {
+    nats: NatsClient,
+    jetstream: JetStream,
+    config: JobQueueConfig,
+}
+
+impl JobQueue {
+    pub fn new(nats: NatsClient, config: JobQueueConfig) -> Self {
+        Self {
+            jetstream: nats.jetstream(),
+            nats,
+            config,
+        }
+    }
+
+    /// Connects to NATS to work with the job queue outside of a running [`Server`].
+    ///
+    /// [`Server`]: crate::Server
+    pub async fn connect(nats_config: &NatsConfig, config: JobQueueConfig) -> JobQueueResult<Self> {
+        Ok(Self::new(NatsClient::new(nats_config).await?, config))
+    }
+
+    /// Creates the job and dead-letter streams and the durable consumer shared by all pinga
+    /// instances, updating their configuration if they already exist.
+    #[instrument(name = "pinga.job_queue.setup", skip_all)]
+    pub async fn setup(&self) -> JobQueueResult<()> {
+        for stream in [self.jobs_stream_config(), self.dead_letter_stream_config()] {
+            if let Err(err) = self.jetstream.add_stream(stream.clone()).await {
+                debug!(error = ?err, stream = %stream.name, "could not add stream, updating it");
+                self.jetstream.update_stream(stream).await?;
+            }
+        }
+
+        self.jetstream
+            .add_consumer(self.stream_name(), self.consumer_config())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Subscribes to the jobs delivered by the durable consumer.
+    pub async fn subscribe(&self) -> JobQueueResult<impl Stream<Item = QueuedJob>> {
+        let queue = self.clone();
+        Ok(self
+            .nats
+            .queue_subscribe(self.deliver_subject(), NATS_JOBS_DEFAULT_QUEUE)
+            .await?
+            .filter_map(move |message| {
+                let queue = queue.clone();
+                async move {
+                    match message {
+                        Ok(message) => Some(QueuedJob { message, queue }),
+                        Err(err) => {
+                            warn!(error = ?err, "next queued job had an error, skipping");
+                            None
+                        }
+                    }
+                }
+            }))
+    }
+
+    /// Gets the jobs currently on the dead-letter stream, optionally only those of one kind.
+    pub async fn dead_letters(&self, kind: Option<&str>) -> JobQueueResult<Vec<DeadLetteredJob>> {
+        let inbox = self.nats.new_inbox();
+        let mut subscription = self.nats.subscribe(&inbox).await?;
+        let consumer = self
+            .jetstream
+            .add_consumer(
+                self.dead_letter_stream_name(),
+                ConsumerConfig {
+                    deliver_subject: Some(inbox),
+                    deliver_policy: DeliverPolicy::All,
+                    ack_policy: AckPolicy::None,
+                    filter_subject: self.dead_letter_subject(kind.unwrap_or("*")),
+                    ..Default::default()
+                },
+            )
+            .await?;
+
+        let mut dead_letters = Vec::new();
+        while let Ok(Some(message)) =
+            time::timeout(DEAD_LETTER_READ_IDLE_TIMEOUT, subscription.next()).await
+        {
+            let message = message?;
+            let (sequence, pending) = match message.jetstream_message_info() {
+                Some(info) => (info.stream_seq, info.pending),
+                None => continue,
+            };
+            dead_letters.push(DeadLetteredJob {
+                sequence,
+                dead_letter: serde_json::from_slice(message.data())?,
+            });
+            if pending == 0 {
+                break;
+            }
+        }
+
+        self.jetstream
+            .delete_consumer(self.dead_letter_stream_name(), consumer.name)
+            .await?;
+        subscription.unsubscribe().await?;
+
+        Ok(dead_letters)
+    }
+
+    /// Puts the jobs on the dead-letter stream, optionally only those of one kind, back on the
+    /// job queue with a fresh set of attempts. Returns the number of jobs replayed.
+    #[instrument(name = "pinga.job_queue.replay_dead_letters", skip_all)]
+    pub async fn replay_dead_letters(&self, kind: Option<&str>) -> JobQueueResult<usize> {
+        let dead_letters = self.dead_letters(kind).await?;
+        for dead_lettered in &dead_letters {
+            self.publish(&dead_lettered.dead_letter.job).await?;
+            self.jetstream
+                .delete_message(self.dead_letter_stream_name(), dead_lettered.sequence)
+                .await?;
+            info!(
+                job.id = %dead_lettered.dead_letter.job.id,
+                job.kind = %dead_lettered.dead_letter.job.kind,
+                "replayed dead-lettered job"
+            );
+        }
+
+        Ok(dead_letters.len())
+    }
+
+    /// Puts a job on the queue.
+    pub async fn publish(&self, job: &JobInfo) -> JobQueueResult<()> {
+        self.jetstream
+            .publish(self.durable_subject(), serde_json::to_vec(job)?)
+            .await?;
+        Ok(())
+    }
+
+    async fn dead_letter(&self, dead_letter: &DeadLetter) -> JobQueueResult<()> {
+        self.jetstream
+            .publish(
+                self.dead_letter_subject(&dead_letter.job.kind),
+                serde_json::to_vec(dead_letter)?,
+            )
+            .await?;
+        Ok(())
+    }
+
+    fn jobs_stream_config(&self) -> StreamConfig {
+        StreamConfig {
+            name: self.stream_name(),
+            subjects: vec![self.durable_subject()],
+            retention: RetentionPolicy::WorkQueue,
+            storage: self.config.storage(),
+            ..Default::default()
+        }
+    }
+
+    fn dead_letter_stream_config(&self) -> StreamConfig {
+        StreamConfig {
+            name: self.dead_letter_stream_name(),
+            subjects: vec![self.dead_letter_subject(">")],
+            retention: RetentionPolicy::Limits,
+            max_age: Duration::from_secs(self.config.dead_letter_max_age_secs),
+            storage: self.config.storage(),
+            ..Default::default()
+        }
+    }
+
+    fn consumer_config(&self) -> ConsumerConfig {
+        ConsumerConfig {
+            durable_name: Some(NATS_JOBS_DEFAULT_QUEUE.to_string()),
+            deliver_subject: Some(self.deliver_subject()),
+            deliver_group: Some(NATS_JOBS_DEFAULT_QUEUE.to_string()),
+            deliver_policy: DeliverPolicy::All,
+            ack_policy: AckPolicy::Explicit,
+            ack_wait: self.config.ack_wait(),
+            // Attempts are counted against the retry policy for the job's kind instead
+            max_deliver: -1,
+            max_ack_pending: self.config.max_in_flight,
+            ..Default::default()
+        }
+    }
+
+    fn subject_prefix(&self) -> Option<&str> {
+        self.nats.metadata().subject_prefix()
+    }
+
+    /// Stream names are shared by the whole NATS server, so they're prefixed like subjects are to
+    /// keep queues with different subject prefixes apart.
+    fn prefixed_stream_name(&self, name: &str) -> String {
+        match self.subject_prefix() {
+            Some(prefix) => format!("{}_{name}", prefix.replace('.', "_")),
+            None => name.to_string(),
+        }
+    }
+
+    fn stream_name(&self) -> String {
+        self.prefixed_stream_name(&self.config.stream_name)
+    }
+
+    fn dead_letter_stream_name(&self) -> String {
+        self.prefixed_stream_name(&self.config.dead_letter_stream_name)
+    }
+
+    fn durable_subject(&self) -> String {
+        nats_subject(self.subject_prefix(), NATS_JOBS_DURABLE_SUBJECT)
+    }
+
+    fn deliver_subject(&self) -> String {
+        nats_subject(self.subject_prefix(), NATS_JOBS_DELIVER_SUBJECT)
+    }
+
+    fn dead_letter_subject(&self, kind: &str) -> String {
+        nats_subject(
+            self.subject_prefix(),
+            format!("{NATS_JOBS_DEAD_LETTER_SUBJECT}.{kind}"),
+        )
+    }
+}
+
+/// A job delivered from the durable job queue which has yet to be acknowledged.
+#[derive(Debug)]
+pub struct QueuedJob {
+    message: Message,
+    queue: JobQueue,
+}
+
+impl QueuedJob {
+    /// Deserializes the job carried by this delivery.
+    pub fn job_info(&self) -> JobQueueResult<JobInfo> {
+        Ok(serde_json::from_slice(self.message.data())?)
+    }
+
+    /// Which attempt at running the job this delivery is, counting from 1.
+    pub fn attempt(&self) -> u32 {
+        self.message
+            .jetstream_message_info()
+            .map(|info| u32::try_from(info.delivered).unwrap_or(u32::MAX))
+            .unwrap_or(1)
+            .max(1)
+    }
+
+    /// Returns true if the job has already been attempted as often as its retry policy allows,
+    /// which happens when pinga stops while running it, and shouldn't be run again.
+    pub fn is_exhausted(&self, job_info: &JobInfo) -> bool {
+        self.attempt() > self.queue.config.retry_policy(&job_info.kind).max_attempts
+    }
+
+    /// Returns true if the job will be attempted again should this delivery fail.
+    pub fn will_retry(&self, job_info: &JobInfo) -> bool {
+        self.queue
+            .config
+            .retry_policy(&job_info.kind)
+            .should_retry(self.attempt())
+    }
+
+    /// Spawns a task which keeps telling the server the job is being worked on so that it isn't
+    /// redelivered to another instance. The task must be aborted once the job has finished.
+    pub fn keep_alive(&self) -> JoinHandle<()> {
+        let message = self.message.clone();
+        let interval = self.queue.config.progress_interval();
+        tokio::spawn(async move {
+            let mut ticks = time::interval(interval);
+            // The first tick completes immediately and the job was only just delivered
+            ticks.tick().await;
+            loop {
+                ticks.tick().await;
+                if let Err(err) = message.ack_kind(AckKind::Progress).await {
+                    warn!(error = ?err, "failed to report progress on queued job");
+                }
+            }
+        })
+    }
+
+    /// Acknowledges the job, removing it from the queue.
+    pub async fn succeeded(self) {
+        if let Err(err) = self.message.ack_kind(AckKind::Ack).await {
+            warn!(error = ?err, "failed to acknowledge queued job, it may be run again");
+        }
+    }
+
+    /// Handles a failed attempt at running the job: the job is redelivered after a backoff if its
+    /// retry policy allows another attempt, and dead-lettered otherwise.
+    pub async fn failed(self, job_info: JobInfo, error: String) {
+        let attempt = self.attempt();
+        let policy = self.queue.config.retry_policy(&job_info.kind).clone();
+
+        if policy.should_retry(attempt) {
+            let backoff = policy.backoff(attempt);
+            info!(
+                job.id = %job_info.id,
+                job.kind = %job_info.kind,
+                attempt,
+                backoff_ms = backoff.as_millis() as u64,
+                "queued job failed, retrying after backoff"
+            );
+            // Hold on to the job while backing off rather than occupying a concurrency slot
+            drop(tokio::spawn(self.retry_after(backoff)));
+        } else {
+            self.dead_letter(job_info, error, attempt).await;
+        }
+    }
+
+    /// Moves the job to the dead-letter stream without running it again.
+    pub async fn dead_letter(self, job_info: JobInfo, error: String, attempts: u32) {
+        warn!(
+            job.id = %job_info.id,
+            job.kind = %job_info.kind,
+            attempts,
+            error = %error,
+            "queued job ran out of attempts, moving it to the dead-letter stream"
+        );
+        let dead_letter = DeadLetter {
+            job: job_info,
+            error,
+            attempts,
+            dead_lettered_at: Utc::now(),
+        };
+
+        match self.queue.dead_letter(&dead_letter).await {
+            Ok(()) => self.succeeded().await,
+            Err(err) => {
+                // Leave the job unacknowledged so it's redelivered and dead-lettered again
+                error!(error = ?err, "failed to dead-letter queued job");
+            }
+        }
+    }
+
+    /// Rejects a delivery which can't be turned into a job, so that it's never redelivered.
+    pub async fn reject(self) {
+        if let Err(err) = self.message.ack_kind(AckKind::Term).await {
+            warn!(error = ?err, "failed to reject queued job");
+        }
+    }
+
+    async fn retry_after(self, backoff: Duration) {
+        let keep_alive = self.keep_alive();
+        time::sleep(backoff).await;
+        keep_alive.abort();
+
+        if let Err(err) = self.message.ack_kind(AckKind::Nak).await {
+            // The job is redelivered anyway once the ack wait has passed
+            warn!(error = ?err, "failed to request redelivery of queued job");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_until_capped() {
+        let policy = JobRetryPolicy {
+            max_attempts: 10,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 3_000,
+            multiplier: 2,
+        };
+
+        assert_eq!(
+            vec![500, 1_000, 2_000, 3_000, 3_000],
+            (1..=5)
+                .map(|attempt| policy.backoff(attempt).as_millis())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Duration::from_millis(3_000), policy.backoff(u32::MAX));
+    }
+
+    #[test]
+    fn retries_until_max_attempts() {
+        let policy = JobRetryPolicy {
+            max_attempts: 2,
+            ..Default::default()
+        };
+
+        assert!(policy.should_retry(1));
+        assert!(!policy.should_retry(2));
+    }
+
+    #[test]
+    fn retry_policy_by_kind() {
+        let fixes = JobRetryPolicy {
+            max_attempts: 4,
+            ..Default::default()
+        };
+        let config = JobQueueConfig {
+            retry_policies: HashMap::from([("FixesJob".to_string(), fixes.clone())]),
+            ..Default::default()
+        };
+
+        assert_eq!(&fixes, config.retry_policy("FixesJob"));
+        assert_eq!(
+            &JobRetryPolicy::default(),
+            config.retry_policy("DependentValuesUpdate")
+        );
+    }
+
+    #[test]
+    fn jobs_which_run_actions_are_attempted_once_by_default() {
+        let config = JobQueueConfig::default();
+
+        let policy = config.retry_policy("FixesJob");
+        assert_eq!(1, policy.max_attempts);
+        assert!(!policy.should_retry(1));
+        assert_eq!(
+            &JobRetryPolicy::default(),
+            config.retry_policy("DependentValuesUpdate")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 mod config;
+pub mod job_queue;
 pub mod server;
 
 pub use crate::{
@@ -6,11 +7,15 @@ pub use crate::{
         detect_and_configure_development, Config, ConfigBuilder, ConfigError, ConfigFile,
         StandardConfig, StandardConfigFile,
     },
+    job_queue::{JobQueue, JobQueueConfig, JobQueueError, JobRetryPolicy},
     server::{Server, ServerError},
 };
 
 const NATS_JOBS_DEFAULT_SUBJECT: &str = "pinga-jobs";
 const NATS_JOBS_DEFAULT_QUEUE: &str = "pinga";
+const NATS_JOBS_DURABLE_SUBJECT: &str = "pinga-jobs.durable";
+const NATS_JOBS_DELIVER_SUBJECT: &str = "pinga-jobs.deliver";
+const NATS_JOBS_DEAD_LETTER_SUBJECT: &str = "pinga-jobs.dead-letter";
 
 pub fn nats_jobs_subject(prefix: Option<&str>) -> String {
     nats_subject(prefix, NATS_JOBS_DEFAULT_SUBJECT)
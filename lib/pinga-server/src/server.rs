@@ -6,10 +6,12 @@ use dal::{
         definition::{FixesJob, RefreshJob},
         producer::BlockingJobError,
     },
+    secret::{SecretBackendError, SecretBackends},
     DalContext, DalContextBuilder, DependentValuesUpdate, InitializationError, JobFailure,
     JobFailureError, JobQueueProcessor, NatsProcessor, ServicesContext, TransactionsError,
+    DEFAULT_FIX_CONCURRENCY_LIMIT,
 };
-use futures::{FutureExt, Stream, StreamExt};
+use futures::{stream, FutureExt, Stream, StreamExt};
 use nats_subscriber::{Request, SubscriberError, Subscription};
 use si_data_nats::{NatsClient, NatsConfig, NatsError};
 use si_data_pg::{PgPool, PgPoolConfig, PgPoolError};
@@ -27,7 +29,10 @@ use tokio::{
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use veritech_client::{Client as VeritechClient, EncryptionKey, EncryptionKeyError};
 
-use crate::{nats_jobs_subject, Config, NATS_JOBS_DEFAULT_QUEUE};
+use crate::{
+    job_queue::{JobQueue, JobQueueConfig, JobQueueError, QueuedJob},
+    nats_jobs_subject, Config, NATS_JOBS_DEFAULT_QUEUE,
+};
 
 #[remain::sorted]
 #[derive(Debug, Error)]
@@ -40,10 +45,14 @@ pub enum ServerError {
     JobConsumer(#[from] JobConsumerError),
     #[error(transparent)]
     JobFailure(#[from] Box<JobFailureError>),
+    #[error("job queue error: {0}")]
+    JobQueue(#[from] JobQueueError),
     #[error(transparent)]
     Nats(#[from] NatsError),
     #[error(transparent)]
     PgPool(#[from] Box<PgPoolError>),
+    #[error("error configuring secret backends: {0}")]
+    SecretBackend(#[from] SecretBackendError),
     #[error(transparent)]
     SerdeJson(#[from] serde_json::Error),
     #[error("failed to setup signal handler")]
@@ -83,6 +92,9 @@ pub struct Server {
     pg_pool: PgPool,
     veritech: VeritechClient,
     job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
+    secret_backends: SecretBackends,
+    job_queue_config: JobQueueConfig,
+    fix_concurrency_limit: usize,
     /// An internal shutdown watch receiver handle which can be provided to internal tasks which
     /// want to be notified when a shutdown event is in progress.
     shutdown_watch_rx: watch::Receiver<()>,
@@ -106,8 +118,9 @@ impl Server {
         let pg_pool = Self::create_pg_pool(config.pg_pool()).await?;
         let veritech = Self::create_veritech_client(nats.clone());
         let job_processor = Self::create_job_processor(nats.clone());
+        let secret_backends = config.secret_backends().build()?;
 
-        Self::from_services(
+        Ok(Self::from_services(
             config.instance_id().to_string(),
             config.concurrency(),
             encryption_key,
@@ -115,7 +128,10 @@ impl Server {
             pg_pool,
             veritech,
             job_processor,
-        )
+        )?
+        .with_secret_backends(secret_backends)
+        .with_job_queue_config(config.job_queue().clone())
+        .with_fix_concurrency_limit(config.fix_concurrency_limit()))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -153,6 +169,9 @@ impl Server {
             veritech,
             encryption_key,
             job_processor,
+            secret_backends: SecretBackends::default(),
+            job_queue_config: JobQueueConfig::default(),
+            fix_concurrency_limit: DEFAULT_FIX_CONCURRENCY_LIMIT,
             shutdown_watch_rx,
             external_shutdown_tx,
             graceful_shutdown_rx,
@@ -160,7 +179,36 @@ impl Server {
         })
     }
 
+    /// Sets the [`SecretBackends`] that jobs can resolve secret values from.
+    pub fn with_secret_backends(mut self, secret_backends: SecretBackends) -> Self {
+        self.secret_backends = secret_backends;
+        self
+    }
+
+    /// Sets the [`JobQueueConfig`] for the durable job queue non-blocking jobs are consumed from.
+    pub fn with_job_queue_config(mut self, job_queue_config: JobQueueConfig) -> Self {
+        self.job_queue_config = job_queue_config;
+        self
+    }
+
+    /// Sets the number of fixes in a batch which jobs may run at once.
+    pub fn with_fix_concurrency_limit(mut self, fix_concurrency_limit: usize) -> Self {
+        self.fix_concurrency_limit = fix_concurrency_limit;
+        self
+    }
+
+    /// Creates the streams and consumer backing the durable job queue if they don't already
+    /// exist. This is also done when the server is [run](Self::run()), but jobs can only be
+    /// enqueued once it has happened.
+    pub async fn setup_job_queue(&self) -> Result<()> {
+        self.job_queue().setup().await?;
+        Ok(())
+    }
+
     pub async fn run(self) -> Result<()> {
+        let job_queue = self.job_queue();
+        job_queue.setup().await?;
+
         let (tx, rx) = mpsc::unbounded_channel();
 
         // Span a task to receive and process jobs from the unbounded channel
@@ -179,6 +227,9 @@ impl Server {
             self.veritech,
             self.job_processor,
             self.encryption_key,
+            self.secret_backends,
+            job_queue,
+            self.fix_concurrency_limit,
             self.shutdown_watch_rx,
         )
         .await;
@@ -197,6 +248,10 @@ impl Server {
         }
     }
 
+    fn job_queue(&self) -> JobQueue {
+        JobQueue::new(self.nats.clone(), self.job_queue_config.clone())
+    }
+
     #[instrument(name = "pinga.init.load_encryption_key", skip_all)]
     async fn load_encryption_key(path: impl AsRef<Path>) -> Result<Arc<EncryptionKey>> {
         Ok(Arc::new(EncryptionKey::load(path).await?))
@@ -262,11 +317,14 @@ pub struct JobItem {
     messaging_destination: Arc<String>,
     ctx_builder: DalContextBuilder,
     request: Result<Request<JobInfo>>,
+    /// The delivery from the durable job queue, if the job didn't come from a core subscription.
+    queued: Option<QueuedJob>,
 }
 
 pub struct Subscriber;
 
 impl Subscriber {
+    #[allow(clippy::too_many_arguments)]
     pub async fn jobs(
         metadata: Arc<ServerMetadata>,
         pg_pool: PgPool,
@@ -274,6 +332,9 @@ impl Subscriber {
         veritech: veritech_client::Client,
         job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
         encryption_key: Arc<veritech_client::EncryptionKey>,
+        secret_backends: SecretBackends,
+        job_queue: JobQueue,
+        fix_concurrency_limit: usize,
     ) -> Result<impl Stream<Item = JobItem>> {
         let subject = nats_jobs_subject(nats.metadata().subject_prefix());
         debug!(
@@ -289,7 +350,9 @@ impl Subscriber {
             encryption_key,
             None,
             None,
-        );
+        )
+        .with_secret_backends(secret_backends)
+        .with_fix_concurrency_limit(fix_concurrency_limit);
 
         // Make non blocking context here, and update it for each job
         // Since the any blocking job should block on its child jobs
@@ -297,16 +360,39 @@ impl Subscriber {
 
         let messaging_destination = Arc::new(subject.clone());
 
-        Ok(Subscription::create(subject)
-            .queue_name(NATS_JOBS_DEFAULT_QUEUE)
-            .start(&nats)
-            .await?
-            .map(move |request| JobItem {
-                metadata: metadata.clone(),
-                messaging_destination: messaging_destination.clone(),
-                ctx_builder: ctx_builder.clone(),
-                request: request.map_err(Into::into),
-            }))
+        // Blocking jobs arrive on the core subject as their reply mailbox must be preserved
+        let blocking_jobs = {
+            let metadata = metadata.clone();
+            let messaging_destination = messaging_destination.clone();
+            let ctx_builder = ctx_builder.clone();
+            Subscription::create(subject)
+                .queue_name(NATS_JOBS_DEFAULT_QUEUE)
+                .start(&nats)
+                .await?
+                .map(move |request| JobItem {
+                    metadata: metadata.clone(),
+                    messaging_destination: messaging_destination.clone(),
+                    ctx_builder: ctx_builder.clone(),
+                    request: request.map_err(Into::into),
+                    queued: None,
+                })
+        };
+
+        let queued_jobs = job_queue.subscribe().await?.map(move |queued| JobItem {
+            metadata: metadata.clone(),
+            messaging_destination: messaging_destination.clone(),
+            ctx_builder: ctx_builder.clone(),
+            request: queued
+                .job_info()
+                .map(|payload| Request {
+                    payload,
+                    reply_mailbox: None,
+                })
+                .map_err(Into::into),
+            queued: Some(queued),
+        });
+
+        Ok(stream::select(blocking_jobs, queued_jobs))
     }
 }
 
@@ -319,6 +405,9 @@ async fn receive_job_requests_task(
     veritech: veritech_client::Client,
     job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
     encryption_key: Arc<veritech_client::EncryptionKey>,
+    secret_backends: SecretBackends,
+    job_queue: JobQueue,
+    fix_concurrency_limit: usize,
     shutdown_watch_rx: watch::Receiver<()>,
 ) {
     if let Err(err) = receive_job_requests(
@@ -329,6 +418,9 @@ async fn receive_job_requests_task(
         veritech,
         job_processor,
         encryption_key,
+        secret_backends,
+        job_queue,
+        fix_concurrency_limit,
         shutdown_watch_rx,
     )
     .await
@@ -346,6 +438,9 @@ async fn receive_job_requests(
     veritech: veritech_client::Client,
     job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
     encryption_key: Arc<veritech_client::EncryptionKey>,
+    secret_backends: SecretBackends,
+    job_queue: JobQueue,
+    fix_concurrency_limit: usize,
     mut shutdown_watch_rx: watch::Receiver<()>,
 ) -> Result<()> {
     let mut requests = Subscriber::jobs(
@@ -355,6 +450,9 @@ async fn receive_job_requests(
         veritech,
         job_processor,
         encryption_key,
+        secret_backends,
+        job_queue,
+        fix_concurrency_limit,
     )
     .await?
     .take_until_if(Box::pin(shutdown_watch_rx.changed().map(|_| true)));
@@ -378,12 +476,26 @@ async fn process_job_requests_task(rx: UnboundedReceiver<JobItem>, concurrency_l
 
             match job.request {
                 Ok(request) => {
+                    // A job which was being run when pinga stopped may have used up its attempts
+                    let queued = match job.queued {
+                        Some(queued) if queued.is_exhausted(&request.payload) => {
+                            let attempts = queued.attempt() - 1;
+                            let error =
+                                "job was not completed by any of its delivery attempts".to_string();
+                            abandon_job(job.ctx_builder, request.payload.clone(), &error).await;
+                            queued.dead_letter(request.payload, error, attempts).await;
+                            return;
+                        }
+                        queued => queued,
+                    };
+
                     // Spawn a task and process the request
                     let join_handle = task::spawn(execute_job_task(
                         job.metadata,
                         job.messaging_destination,
                         job.ctx_builder,
                         request,
+                        queued,
                     ));
                     if let Err(err) = join_handle.await {
                         // NOTE(fnichol): This likely happens when there is contention or
@@ -399,6 +511,9 @@ async fn process_job_requests_task(rx: UnboundedReceiver<JobItem>, concurrency_l
                 }
                 Err(err) => {
                     warn!(error = ?err, "next job request had an error, job will not be executed");
+                    if let Some(queued) = job.queued {
+                        queued.reject().await;
+                    }
                 }
             }
         })
@@ -429,9 +544,11 @@ async fn execute_job_task(
     messaging_destination: Arc<String>,
     ctx_builder: DalContextBuilder,
     request: Request<JobInfo>,
+    queued: Option<QueuedJob>,
 ) {
     let span = Span::current();
     let id = request.payload.id.clone();
+    let job_info = request.payload.clone();
 
     let arg_str = serde_json::to_string(&request.payload.arg)
         .unwrap_or_else(|_| "arg failed to serialize".to_string());
@@ -444,6 +561,7 @@ async fn execute_job_task(
     );
 
     let maybe_reply_channel = request.reply_mailbox.clone();
+    let keep_alive = queued.as_ref().map(QueuedJob::keep_alive);
     let reply_message = match execute_job(
         &metadata,
         messaging_destination,
@@ -470,6 +588,21 @@ async fn execute_job_task(
         }
     };
 
+    if let Some(keep_alive) = keep_alive {
+        keep_alive.abort();
+    }
+    if let Some(queued) = queued {
+        match &reply_message {
+            Ok(()) => queued.succeeded().await,
+            Err(err) => {
+                if !queued.will_retry(&job_info) {
+                    abandon_job(ctx_builder.clone(), job_info.clone(), &err.to_string()).await;
+                }
+                queued.failed(job_info, err.to_string()).await
+            }
+        }
+    }
+
     if let Some(reply_channel) = maybe_reply_channel {
         if let Ok(message) = serde_json::to_vec(&reply_message) {
             if let Err(err) = ctx_builder
@@ -511,18 +644,7 @@ async fn execute_job(
         tracing::Span::current().record("job_info.blocking", job_info.blocking);
     }
 
-    let job =
-        match job_info.kind.as_str() {
-            stringify!(DependentValuesUpdate) => {
-                Box::new(DependentValuesUpdate::try_from(job_info.clone())?)
-                    as Box<dyn JobConsumer + Send + Sync>
-            }
-            stringify!(FixesJob) => Box::new(FixesJob::try_from(job_info.clone())?)
-                as Box<dyn JobConsumer + Send + Sync>,
-            stringify!(RefreshJob) => Box::new(RefreshJob::try_from(job_info.clone())?)
-                as Box<dyn JobConsumer + Send + Sync>,
-            kind => return Err(ServerError::UnknownJobKind(kind.to_owned())),
-        };
+    let job = job_consumer(job_info)?;
 
     info!("Processing job");
 
@@ -536,6 +658,37 @@ async fn execute_job(
     Ok(())
 }
 
+fn job_consumer(job_info: JobInfo) -> Result<Box<dyn JobConsumer + Send + Sync>> {
+    let job = match job_info.kind.as_str() {
+        stringify!(DependentValuesUpdate) => Box::new(DependentValuesUpdate::try_from(job_info)?)
+            as Box<dyn JobConsumer + Send + Sync>,
+        stringify!(FixesJob) => {
+            Box::new(FixesJob::try_from(job_info)?) as Box<dyn JobConsumer + Send + Sync>
+        }
+        stringify!(RefreshJob) => {
+            Box::new(RefreshJob::try_from(job_info)?) as Box<dyn JobConsumer + Send + Sync>
+        }
+        kind => return Err(ServerError::UnknownJobKind(kind.to_owned())),
+    };
+    Ok(job)
+}
+
+/// Gives a job which won't be attempted again the chance to clean up after itself, such as a
+/// [`FixesJob`] finishing its batch, before it's dead-lettered.
+async fn abandon_job(ctx_builder: DalContextBuilder, job_info: JobInfo, reason: &str) {
+    let job_id = job_info.id.clone();
+    let result = match job_consumer(job_info) {
+        Ok(job) => job
+            .abandon_job(ctx_builder, reason)
+            .await
+            .map_err(Into::into),
+        Err(err) => Err(err),
+    };
+    if let Err(err) = result {
+        error!(error = ?err, job.id = %job_id, "failed to abandon job");
+    }
+}
+
 async fn record_job_failure(
     ctx_builder: DalContextBuilder,
     job: Box<dyn JobConsumer + Send + Sync>,
@@ -0,0 +1,184 @@
+//! Runs the durable job queue against a NATS server with JetStream enabled.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use dal::{job::consumer::JobInfo, AccessBuilder, HistoryActor, Tenancy, Visibility};
+use futures::{Stream, StreamExt};
+use pinga_server::{job_queue::QueuedJob, JobQueue, JobQueueConfig, JobRetryPolicy};
+use si_data_nats::NatsConfig;
+use tokio::time;
+use ulid::Ulid;
+
+const ENV_VAR_NATS_URL: &str = "SI_TEST_NATS_URL";
+
+/// How long to wait for a delivery which is expected to arrive.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long to wait to be sure a delivery isn't going to arrive, comfortably past the ack wait.
+const NO_DELIVERY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Sets up a job queue with its own subject prefix, and so its own streams, which redelivers
+/// unacknowledged jobs after a second.
+#[allow(clippy::disallowed_methods)] // Environment variables are used exclusively in test and
+                                     // all are prefixed with `SI_TEST_`
+async fn job_queue(config: JobQueueConfig) -> JobQueue {
+    let nats_config = NatsConfig {
+        url: std::env::var(ENV_VAR_NATS_URL).unwrap_or_else(|_| "localhost".to_string()),
+        subject_prefix: Some(format!("pinga_test_{}", Ulid::new())),
+    };
+    let queue = JobQueue::connect(
+        &nats_config,
+        JobQueueConfig {
+            ack_wait_secs: 1,
+            in_memory: true,
+            ..config
+        },
+    )
+    .await
+    .expect("failed to connect to nats");
+    queue.setup().await.expect("failed to set up job queue");
+    queue
+}
+
+fn job(kind: &str) -> JobInfo {
+    JobInfo {
+        id: Ulid::new().to_string(),
+        kind: kind.to_string(),
+        created_at: Utc::now(),
+        arg: serde_json::json!([]),
+        access_builder: AccessBuilder::new(Tenancy::new_empty(), HistoryActor::SystemInit),
+        visibility: Visibility::new_head(false),
+        blocking: false,
+    }
+}
+
+async fn next_job(jobs: &mut (impl Stream<Item = QueuedJob> + Unpin)) -> QueuedJob {
+    time::timeout(DELIVERY_TIMEOUT, jobs.next())
+        .await
+        .expect("timed out waiting for a job")
+        .expect("job stream ended")
+}
+
+async fn assert_no_job(jobs: &mut (impl Stream<Item = QueuedJob> + Unpin)) {
+    if let Ok(Some(queued)) = time::timeout(NO_DELIVERY_TIMEOUT, jobs.next()).await {
+        panic!(
+            "unexpected delivery of job {:?}, attempt {}",
+            queued.job_info().map(|job| job.id),
+            queued.attempt()
+        );
+    }
+}
+
+#[tokio::test]
+async fn acknowledged_job_is_not_redelivered() {
+    let queue = job_queue(JobQueueConfig::default()).await;
+    let mut jobs = Box::pin(queue.subscribe().await.expect("failed to subscribe"));
+
+    let job = job("DependentValuesUpdate");
+    queue.publish(&job).await.expect("failed to publish job");
+
+    let queued = next_job(&mut jobs).await;
+    assert_eq!(job.id, queued.job_info().expect("invalid job").id);
+    assert_eq!(1, queued.attempt());
+    queued.succeeded().await;
+
+    assert_no_job(&mut jobs).await;
+    assert!(queue
+        .dead_letters(None)
+        .await
+        .expect("failed to read dead letters")
+        .is_empty());
+}
+
+#[tokio::test]
+async fn unacknowledged_job_is_redelivered_and_dead_lettered_once_exhausted() {
+    let queue = job_queue(JobQueueConfig::default()).await;
+    let mut jobs = Box::pin(queue.subscribe().await.expect("failed to subscribe"));
+
+    // Jobs which run actions are only attempted once
+    let job = job("FixesJob");
+    queue.publish(&job).await.expect("failed to publish job");
+
+    // An instance which stops while running the job never acknowledges it
+    let queued = next_job(&mut jobs).await;
+    assert_eq!(1, queued.attempt());
+    assert!(!queued.is_exhausted(&job));
+    drop(queued);
+
+    let queued = next_job(&mut jobs).await;
+    assert_eq!(job.id, queued.job_info().expect("invalid job").id);
+    assert_eq!(2, queued.attempt());
+    assert!(queued.is_exhausted(&job));
+    queued
+        .dead_letter(job.clone(), "never completed".to_string(), 1)
+        .await;
+
+    assert_no_job(&mut jobs).await;
+    let dead_letters = queue
+        .dead_letters(Some("FixesJob"))
+        .await
+        .expect("failed to read dead letters");
+    assert_eq!(1, dead_letters.len());
+    assert_eq!(job.id, dead_letters[0].dead_letter.job.id);
+    assert_eq!(1, dead_letters[0].dead_letter.attempts);
+    assert_eq!("never completed", dead_letters[0].dead_letter.error);
+}
+
+#[tokio::test]
+async fn failed_job_is_retried_then_dead_lettered_and_replayed() {
+    let queue = job_queue(JobQueueConfig {
+        default_retry_policy: JobRetryPolicy {
+            max_attempts: 2,
+            initial_backoff_ms: 10,
+            max_backoff_ms: 10,
+            multiplier: 1,
+        },
+        ..Default::default()
+    })
+    .await;
+    let mut jobs = Box::pin(queue.subscribe().await.expect("failed to subscribe"));
+
+    let job = job("DependentValuesUpdate");
+    queue.publish(&job).await.expect("failed to publish job");
+
+    let queued = next_job(&mut jobs).await;
+    assert_eq!(1, queued.attempt());
+    assert!(queued.will_retry(&job));
+    queued
+        .failed(job.clone(), "first failure".to_string())
+        .await;
+
+    let queued = next_job(&mut jobs).await;
+    assert_eq!(2, queued.attempt());
+    assert!(!queued.will_retry(&job));
+    queued
+        .failed(job.clone(), "second failure".to_string())
+        .await;
+
+    assert_no_job(&mut jobs).await;
+    let dead_letters = queue
+        .dead_letters(None)
+        .await
+        .expect("failed to read dead letters");
+    assert_eq!(1, dead_letters.len());
+    assert_eq!(2, dead_letters[0].dead_letter.attempts);
+    assert_eq!("second failure", dead_letters[0].dead_letter.error);
+
+    // A replayed job gets a fresh set of attempts
+    assert_eq!(
+        1,
+        queue
+            .replay_dead_letters(None)
+            .await
+            .expect("failed to replay dead letters")
+    );
+    let queued = next_job(&mut jobs).await;
+    assert_eq!(job.id, queued.job_info().expect("invalid job").id);
+    assert_eq!(1, queued.attempt());
+    queued.succeeded().await;
+    assert!(queue
+        .dead_letters(None)
+        .await
+        .expect("failed to read dead letters")
+        .is_empty());
+}
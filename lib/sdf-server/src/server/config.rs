@@ -15,7 +15,11 @@ use si_std::SensitiveString;
 use telemetry::prelude::*;
 use thiserror::Error;
 
-pub use dal::{CycloneKeyPair, MigrationMode};
+pub use dal::{
+    secret::SecretBackendsConfig,
+    tasks::{AuditExporterConfig, ResourceSchedulerConfig},
+    CycloneKeyPair, MigrationMode,
+};
 pub use si_settings::{StandardConfig, StandardConfigFile};
 
 const DEFAULT_SIGNUP_SECRET: &str = "cool-steam";
@@ -65,6 +69,15 @@ pub struct Config {
     #[builder(default = "MigrationMode::default()")]
     migration_mode: MigrationMode,
 
+    #[builder(default = "ResourceSchedulerConfig::default()")]
+    resource_scheduler: ResourceSchedulerConfig,
+
+    #[builder(default = "AuditExporterConfig::default()")]
+    audit_export: AuditExporterConfig,
+
+    #[builder(default = "SecretBackendsConfig::default()")]
+    secret_backends: SecretBackendsConfig,
+
     jwt_signing_public_key_path: CanonicalFile,
 
     cyclone_encryption_key_path: CanonicalFile,
@@ -135,6 +148,24 @@ impl Config {
         &self.posthog
     }
 
+    /// Gets a reference to the config's resource scheduler config.
+    #[must_use]
+    pub fn resource_scheduler(&self) -> &ResourceSchedulerConfig {
+        &self.resource_scheduler
+    }
+
+    /// Gets a reference to the config's audit export config.
+    #[must_use]
+    pub fn audit_export(&self) -> &AuditExporterConfig {
+        &self.audit_export
+    }
+
+    /// Gets a reference to the config's secret backends.
+    #[must_use]
+    pub fn secret_backends(&self) -> &SecretBackendsConfig {
+        &self.secret_backends
+    }
+
     /// URL to the module index service
     #[must_use]
     pub fn module_index_url(&self) -> &str {
@@ -172,6 +203,12 @@ pub struct ConfigFile {
     pub posthog: PosthogConfig,
     #[serde(default)]
     pub module_index_url: String,
+    #[serde(default)]
+    pub resource_scheduler: ResourceSchedulerConfig,
+    #[serde(default)]
+    pub audit_export: AuditExporterConfig,
+    #[serde(default)]
+    pub secret_backends: SecretBackendsConfig,
 }
 
 impl Default for ConfigFile {
@@ -186,6 +223,9 @@ impl Default for ConfigFile {
             pkgs_path: default_pkgs_path(),
             posthog: Default::default(),
             module_index_url: default_module_index_url(),
+            resource_scheduler: Default::default(),
+            audit_export: Default::default(),
+            secret_backends: Default::default(),
         }
     }
 }
@@ -210,6 +250,9 @@ impl TryFrom<ConfigFile> for Config {
         config.pkgs_path(value.pkgs_path.try_into()?);
         config.posthog(value.posthog);
         config.module_index_url(value.module_index_url);
+        config.resource_scheduler(value.resource_scheduler);
+        config.audit_export(value.audit_export);
+        config.secret_backends(value.secret_backends);
         config.build().map_err(Into::into)
     }
 }
@@ -2,13 +2,14 @@ use std::{collections::HashMap, fmt};
 
 use axum::{
     async_trait,
-    extract::{FromRequestParts, Query},
-    http::request::Parts,
+    extract::{FromRequestParts, OriginalUri, Query},
+    http::{request::Parts, Method},
     Json,
 };
 use dal::{
     context::{self, DalContextBuilder},
-    User, UserClaim,
+    AccessLevel, ApiToken, DalContext, Permission, PermissionScope, User, UserClaim, UserPk,
+    WorkspacePk,
 };
 use hyper::StatusCode;
 
@@ -27,6 +28,11 @@ impl FromRequestParts<AppState> for AccessBuilder {
         let Authorization(claim) = Authorization::from_request_parts(parts, state).await?;
         let Tenancy(tenancy) = tenancy_from_claim(&claim).await?;
 
+        // Every route group requires the member's role to grant a permission for it
+        let HandlerContext(builder) = HandlerContext::from_request_parts(parts, state).await?;
+        let ctx = builder.build_default().await.map_err(internal_error)?;
+        ensure_permitted(parts, &ctx, claim.user_pk, claim.workspace_pk).await?;
+
         Ok(Self(context::AccessBuilder::new(
             tenancy,
             dal::HistoryActor::from(claim.user_pk),
@@ -34,6 +40,43 @@ impl FromRequestParts<AppState> for AccessBuilder {
     }
 }
 
+/// The [`AccessBuilder`] for the headless API, which is authenticated by an [`ApiToken`] instead
+/// of a session. The token acts as the user who created it, in the token's workspace.
+pub struct ApiTokenAccessBuilder(pub context::AccessBuilder);
+
+#[async_trait]
+impl FromRequestParts<AppState> for ApiTokenAccessBuilder {
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let RawAccessToken(raw_token) = RawAccessToken::from_request_parts(parts, state).await?;
+        let HandlerContext(builder) = HandlerContext::from_request_parts(parts, state).await?;
+        let mut ctx = builder.build_default().await.map_err(internal_error)?;
+
+        let api_token = ApiToken::authenticate(&ctx, &raw_token)
+            .await
+            .map_err(internal_error)?
+            .ok_or_else(unauthorized_error)?;
+        let workspace_pk = api_token.workspace_pk().ok_or_else(unauthorized_error)?;
+        ctx.update_tenancy(dal::Tenancy::new(workspace_pk));
+        User::authorize(&ctx, &api_token.user_pk)
+            .await
+            .map_err(|_| unauthorized_error())?;
+        // Record that the token was used, even if its user is not allowed to use this route
+        ctx.commit().await.map_err(internal_error)?;
+
+        ensure_permitted(parts, &ctx, api_token.user_pk, workspace_pk).await?;
+
+        Ok(Self(context::AccessBuilder::new(
+            dal::Tenancy::new(workspace_pk),
+            dal::HistoryActor::from(api_token.user_pk),
+        )))
+    }
+}
+
 pub struct RawAccessToken(pub String);
 
 #[async_trait]
@@ -198,6 +241,73 @@ async fn tenancy_from_claim(
     Ok(Tenancy(dal::Tenancy::new(claim.workspace_pk)))
 }
 
+/// Checks that the user's role in the workspace grants the [`Permission`] needed for the
+/// requested route.
+async fn ensure_permitted(
+    parts: &Parts,
+    ctx: &DalContext,
+    user_pk: UserPk,
+    workspace_pk: WorkspacePk,
+) -> Result<(), (StatusCode, Json<serde_json::Value>)> {
+    let path = match parts.extensions.get::<OriginalUri>() {
+        Some(OriginalUri(uri)) => uri.path(),
+        None => parts.uri.path(),
+    };
+    let permission = required_permission(&parts.method, path);
+    let permitted = User::is_permitted(ctx, user_pk, workspace_pk, permission)
+        .await
+        .map_err(internal_error)?;
+    if !permitted {
+        return Err(forbidden_error(permission));
+    }
+    Ok(())
+}
+
+/// The [`Permission`] needed to call the route at `path` with `method`. Reads need
+/// [`AccessLevel::Read`] and everything else needs [`AccessLevel::Write`], except for the change
+/// set routes which approve or apply change sets.
+fn required_permission(method: &Method, path: &str) -> Permission {
+    // The headless API mirrors the route groups of the rest of the API
+    let mut segments = path
+        .trim_start_matches("/api/")
+        .trim_start_matches("v1/")
+        .split('/');
+    let group = segments.next().unwrap_or_default();
+    let route = segments.last().unwrap_or_default();
+
+    let scope = match group {
+        // The audit log covers the whole workspace
+        "audit" => PermissionScope::Workspace,
+        "change_set" => PermissionScope::ChangeSet,
+        "fix" => PermissionScope::Fix,
+        "func" => PermissionScope::Func,
+        "pkg" => PermissionScope::Pkg,
+        "secret" => PermissionScope::Secret,
+        "webhook" | "workspace" => PermissionScope::Workspace,
+        _ => PermissionScope::Model,
+    };
+    let level = match (scope, route) {
+        (
+            PermissionScope::ChangeSet,
+            "apply_change_set" | "apply_change_set2" | "review_change_set",
+        ) => AccessLevel::Approve,
+        // The approval policy belongs to the workspace, not to any one change set
+        (PermissionScope::ChangeSet, "update_approval_policy") => {
+            return Permission::new(PermissionScope::Workspace, AccessLevel::Write)
+        }
+        // Instantiating a template creates components, so it is editing the model
+        (PermissionScope::Pkg, "instantiate_template") => {
+            return Permission::new(PermissionScope::Model, AccessLevel::Write)
+        }
+        // Planning fixes only reads, even though the batch is posted
+        (PermissionScope::Fix, "plan") => AccessLevel::Read,
+        _ if method == Method::GET => AccessLevel::Read,
+        _ => AccessLevel::Write,
+    };
+
+    Permission::new(scope, level)
+}
+
 fn internal_error(message: impl fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
     let status_code = StatusCode::INTERNAL_SERVER_ERROR;
     (
@@ -212,6 +322,23 @@ fn internal_error(message: impl fmt::Display) -> (StatusCode, Json<serde_json::V
     )
 }
 
+fn forbidden_error(permission: Permission) -> (StatusCode, Json<serde_json::Value>) {
+    let status_code = StatusCode::FORBIDDEN;
+    (
+        status_code,
+        Json(serde_json::json!({
+            "error": {
+                "message": format!(
+                    "forbidden: your workspace role does not allow {} access to {}",
+                    permission.level, permission.scope
+                ),
+                "statusCode": status_code.as_u16(),
+                "code": 42,
+            },
+        })),
+    )
+}
+
 fn unauthorized_error() -> (StatusCode, Json<serde_json::Value>) {
     let status_code = StatusCode::UNAUTHORIZED;
     (
@@ -225,3 +352,119 @@ fn unauthorized_error() -> (StatusCode, Json<serde_json::Value>) {
         })),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_permissions() {
+        let cases = [
+            (
+                Method::GET,
+                "/api/component/list",
+                PermissionScope::Model,
+                AccessLevel::Read,
+            ),
+            (
+                Method::POST,
+                "/api/component/update_property_editor_value",
+                PermissionScope::Model,
+                AccessLevel::Write,
+            ),
+            (
+                Method::GET,
+                "/api/audit/list_events",
+                PermissionScope::Workspace,
+                AccessLevel::Read,
+            ),
+            (
+                Method::POST,
+                "/api/func/save_func",
+                PermissionScope::Func,
+                AccessLevel::Write,
+            ),
+            (
+                Method::POST,
+                "/api/change_set/create_change_set",
+                PermissionScope::ChangeSet,
+                AccessLevel::Write,
+            ),
+            (
+                Method::POST,
+                "/api/change_set/apply_change_set",
+                PermissionScope::ChangeSet,
+                AccessLevel::Approve,
+            ),
+            (
+                Method::POST,
+                "/api/change_set/apply_change_set2",
+                PermissionScope::ChangeSet,
+                AccessLevel::Approve,
+            ),
+            (
+                Method::POST,
+                "/api/change_set/review_change_set",
+                PermissionScope::ChangeSet,
+                AccessLevel::Approve,
+            ),
+            (
+                Method::POST,
+                "/api/change_set/update_approval_policy",
+                PermissionScope::Workspace,
+                AccessLevel::Write,
+            ),
+            (
+                Method::POST,
+                "/api/pkg/install_pkg",
+                PermissionScope::Pkg,
+                AccessLevel::Write,
+            ),
+            (
+                Method::POST,
+                "/api/pkg/instantiate_template",
+                PermissionScope::Model,
+                AccessLevel::Write,
+            ),
+            (
+                Method::POST,
+                "/api/fix/plan",
+                PermissionScope::Fix,
+                AccessLevel::Read,
+            ),
+            (
+                Method::POST,
+                "/api/fix/run",
+                PermissionScope::Fix,
+                AccessLevel::Write,
+            ),
+            // The headless API is checked like the routes it mirrors
+            (
+                Method::GET,
+                "/api/v1/change_set/get_change_set",
+                PermissionScope::ChangeSet,
+                AccessLevel::Read,
+            ),
+            (
+                Method::POST,
+                "/api/v1/change_set/apply_change_set",
+                PermissionScope::ChangeSet,
+                AccessLevel::Approve,
+            ),
+            (
+                Method::POST,
+                "/api/v1/component/create_component",
+                PermissionScope::Model,
+                AccessLevel::Write,
+            ),
+        ];
+
+        for (method, path, scope, level) in cases {
+            assert_eq!(
+                Permission::new(scope, level),
+                required_permission(&method, path),
+                "{method} {path}"
+            );
+        }
+    }
+}
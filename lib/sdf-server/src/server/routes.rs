@@ -22,6 +22,7 @@ pub fn routes(state: AppState) -> Router {
             "/api/",
             Router::new().route("/", get(system_status_route).layer(CorsLayer::permissive())),
         )
+        .nest("/api/audit", crate::server::service::audit::routes())
         .nest(
             "/api/change_set",
             crate::server::service::change_set::routes(),
@@ -43,10 +44,16 @@ pub fn routes(state: AppState) -> Router {
         .nest("/api/secret", crate::server::service::secret::routes())
         .nest("/api/session", crate::server::service::session::routes())
         .nest("/api/status", crate::server::service::status::routes())
+        .nest("/api/v1", crate::server::service::v1::routes())
         .nest(
             "/api/variant_def",
             crate::server::service::variant_definition::routes(),
         )
+        .nest("/api/webhook", crate::server::service::webhook::routes())
+        .nest(
+            "/api/workspace",
+            crate::server::service::workspace::routes(),
+        )
         .nest("/api/ws", crate::server::service::ws::routes());
 
     // Load dev routes if we are in dev mode (decided by "opt-level" at the moment).
@@ -3,11 +3,14 @@ use std::{io, net::SocketAddr, path::Path, path::PathBuf, sync::Arc};
 use crate::server::config::CycloneKeyPair;
 use axum::routing::IntoMakeService;
 use axum::Router;
-use dal::tasks::{StatusReceiver, StatusReceiverError};
+use dal::tasks::{StatusReceiver, StatusReceiverError, WebhookDispatcher, WebhookDispatcherError};
 use dal::JwtPublicSigningKey;
 use dal::{
-    cyclone_key_pair::CycloneKeyPairError, job::processor::JobQueueProcessor,
-    tasks::ResourceScheduler, ServicesContext,
+    cyclone_key_pair::CycloneKeyPairError,
+    job::processor::JobQueueProcessor,
+    secret::SecretBackendError,
+    tasks::{AuditExporter, AuditExporterConfig, ResourceScheduler, ResourceSchedulerConfig},
+    ServicesContext,
 };
 use hyper::server::{accept::Accept, conn::AddrIncoming};
 use si_data_nats::{NatsClient, NatsConfig, NatsError};
@@ -54,12 +57,16 @@ pub enum ServerError {
     PgPool(#[from] Box<PgPoolError>),
     #[error(transparent)]
     Posthog(#[from] si_posthog::PosthogError),
+    #[error(transparent)]
+    SecretBackend(#[from] SecretBackendError),
     #[error("failed to setup signal handler")]
     Signal(#[source] io::Error),
     #[error(transparent)]
     StatusReceiver(#[from] StatusReceiverError),
     #[error(transparent)]
     Uds(#[from] UdsIncomingStreamError),
+    #[error(transparent)]
+    WebhookDispatcher(#[from] WebhookDispatcherError),
     #[error("wrong incoming stream for {0} server: {1:?}")]
     WrongIncomingStream(&'static str, IncomingStream),
 }
@@ -95,6 +102,7 @@ impl Server<(), ()> {
     ) -> Result<(Server<AddrIncoming, SocketAddr>, broadcast::Receiver<()>)> {
         match config.incoming_stream() {
             IncomingStream::HTTPSocket(socket_addr) => {
+                let secret_backends = config.secret_backends().build()?;
                 let services_context = ServicesContext::new(
                     pg_pool,
                     nats,
@@ -103,7 +111,8 @@ impl Server<(), ()> {
                     Arc::new(encryption_key),
                     Some(pkgs_path),
                     Some(module_index_url),
-                );
+                )
+                .with_secret_backends(secret_backends);
 
                 let (service, shutdown_rx, shutdown_broadcast_rx) = build_service(
                     services_context,
@@ -147,6 +156,7 @@ impl Server<(), ()> {
     ) -> Result<(Server<UdsIncomingStream, PathBuf>, broadcast::Receiver<()>)> {
         match config.incoming_stream() {
             IncomingStream::UnixDomainSocket(path) => {
+                let secret_backends = config.secret_backends().build()?;
                 let services_context = ServicesContext::new(
                     pg_pool,
                     nats,
@@ -155,7 +165,8 @@ impl Server<(), ()> {
                     Arc::new(encryption_key),
                     Some(pkgs_path),
                     Some(module_index_url),
-                );
+                )
+                .with_secret_backends(secret_backends);
 
                 let (service, shutdown_rx, shutdown_broadcast_rx) = build_service(
                     services_context,
@@ -249,6 +260,7 @@ impl Server<(), ()> {
         job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
         veritech: VeritechClient,
         encryption_key: EncryptionKey,
+        config: ResourceSchedulerConfig,
         shutdown_broadcast_rx: broadcast::Receiver<()>,
     ) {
         let services_context = ServicesContext::new(
@@ -260,7 +272,34 @@ impl Server<(), ()> {
             None,
             None,
         );
-        ResourceScheduler::new(services_context).start(shutdown_broadcast_rx);
+        ResourceScheduler::new(services_context)
+            .with_config(config)
+            .start(shutdown_broadcast_rx);
+    }
+
+    /// Start exporting audit events, if an audit sink is configured
+    pub async fn start_audit_exporter(
+        pg: PgPool,
+        nats: NatsClient,
+        job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
+        veritech: VeritechClient,
+        encryption_key: EncryptionKey,
+        config: AuditExporterConfig,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) {
+        let services_context = ServicesContext::new(
+            pg,
+            nats,
+            job_processor,
+            veritech,
+            Arc::new(encryption_key),
+            None,
+            None,
+        );
+        match AuditExporter::new(services_context, config) {
+            Some(exporter) => exporter.start(shutdown_broadcast_rx),
+            None => debug!("no audit sink configured, skipping audit export"),
+        }
     }
 
     pub async fn start_status_updater(
@@ -286,6 +325,29 @@ impl Server<(), ()> {
         Ok(())
     }
 
+    pub async fn start_webhook_dispatcher(
+        pg: PgPool,
+        nats: NatsClient,
+        job_processor: Box<dyn JobQueueProcessor + Send + Sync>,
+        veritech: VeritechClient,
+        encryption_key: EncryptionKey,
+        shutdown_broadcast_rx: broadcast::Receiver<()>,
+    ) -> Result<()> {
+        let services_context = ServicesContext::new(
+            pg,
+            nats,
+            job_processor,
+            veritech,
+            Arc::new(encryption_key),
+            None,
+            None,
+        );
+        WebhookDispatcher::new(services_context)
+            .await?
+            .start(shutdown_broadcast_rx);
+        Ok(())
+    }
+
     #[instrument(name = "sdf.init.create_pg_pool", skip_all)]
     pub async fn create_pg_pool(pg_pool_config: &PgPoolConfig) -> Result<PgPool> {
         let pool = PgPool::new(pg_pool_config).await?;
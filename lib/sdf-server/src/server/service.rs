@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod change_set;
 pub mod component;
 pub mod diagram;
@@ -10,7 +11,10 @@ pub mod schema;
 pub mod secret;
 pub mod session;
 pub mod status;
+pub mod v1;
 pub mod variant_definition;
+pub mod webhook;
+pub mod workspace;
 pub mod ws;
 
 /// A module containing dev routes for local development only.
@@ -0,0 +1,85 @@
+use axum::{
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use dal::{
+    HistoryActor, HistoryEventError, HistoryEventFilter, HistoryEventPk, TransactionsError, UserPk,
+};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod export_events;
+pub mod list_events;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum AuditError {
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+    #[error(transparent)]
+    HistoryEvent(#[from] HistoryEventError),
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+pub type AuditResult<T> = std::result::Result<T, AuditError>;
+
+impl IntoResponse for AuditError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = (StatusCode::INTERNAL_SERVER_ERROR, self.to_string());
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+/// The query string accepted by the audit routes, narrowing down which events are returned. See
+/// [`HistoryEventFilter`].
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFilterRequest {
+    /// Only events caused by this user.
+    pub user_pk: Option<UserPk>,
+    /// Only events caused by the system rather than a user.
+    #[serde(default)]
+    pub system_init: bool,
+    pub label_prefix: Option<String>,
+    pub object_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub before: Option<HistoryEventPk>,
+    pub limit: Option<i64>,
+}
+
+impl From<AuditFilterRequest> for HistoryEventFilter {
+    fn from(request: AuditFilterRequest) -> Self {
+        let actor = match (request.user_pk, request.system_init) {
+            (Some(user_pk), _) => Some(HistoryActor::User(user_pk)),
+            (None, true) => Some(HistoryActor::SystemInit),
+            (None, false) => None,
+        };
+        Self {
+            actor,
+            label_prefix: request.label_prefix,
+            object_id: request.object_id,
+            since: request.since,
+            until: request.until,
+            before: request.before,
+            limit: request.limit,
+        }
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/export_events", get(export_events::export_events))
+        .route("/list_events", get(list_events::list_events))
+}
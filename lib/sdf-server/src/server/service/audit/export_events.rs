@@ -0,0 +1,50 @@
+use axum::body::StreamBody;
+use axum::extract::Query;
+use axum::http::header;
+use axum::response::IntoResponse;
+use dal::{HistoryEvent, HistoryEventFilter};
+
+use super::{AuditError, AuditFilterRequest, AuditResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+/// Export every one of the workspace's history events matching the filter as
+/// [JSON Lines](https://jsonlines.org), newest first. Any `limit` is ignored.
+///
+/// Events are fetched a page at a time, and each page is sent as soon as it's read.
+pub async fn export_events(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<AuditFilterRequest>,
+) -> AuditResult<impl IntoResponse> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut filter = HistoryEventFilter::from(request);
+    filter.limit = Some(HistoryEventFilter::MAX_LIMIT);
+    let pages = futures::stream::try_unfold((ctx, Some(filter)), |(ctx, filter)| async move {
+        let mut filter = match filter {
+            Some(filter) => filter,
+            None => return Ok(None),
+        };
+
+        let events = HistoryEvent::list(&ctx, &filter).await?;
+        let mut page = Vec::new();
+        for event in &events {
+            serde_json::to_writer(&mut page, event)?;
+            page.push(b'\n');
+        }
+        let next_filter = match events.last() {
+            Some(last) if events.len() as i64 == filter.page_size() => {
+                filter.before = Some(last.pk);
+                Some(filter)
+            }
+            _ => None,
+        };
+
+        Ok::<_, AuditError>(Some((page, (ctx, next_filter))))
+    });
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        StreamBody::new(pages),
+    ))
+}
@@ -0,0 +1,37 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{HistoryEvent, HistoryEventFilter, HistoryEventPk};
+use serde::{Deserialize, Serialize};
+
+use super::{AuditFilterRequest, AuditResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEventsResponse {
+    pub events: Vec<HistoryEvent>,
+    /// Pass as `before` to fetch the next page. Unset on the last page.
+    pub next_cursor: Option<HistoryEventPk>,
+}
+
+/// List the workspace's history events matching the filter, newest first, one page at a time.
+pub async fn list_events(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<AuditFilterRequest>,
+) -> AuditResult<Json<ListEventsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let filter = HistoryEventFilter::from(request);
+    let events = HistoryEvent::list(&ctx, &filter).await?;
+    let next_cursor = if events.len() as i64 == filter.page_size() {
+        events.last().map(|event| event.pk)
+    } else {
+        None
+    };
+
+    Ok(Json(ListEventsResponse {
+        events,
+        next_cursor,
+    }))
+}
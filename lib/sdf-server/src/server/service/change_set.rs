@@ -7,7 +7,7 @@ use axum::{
 use dal::{
     change_status::ChangeStatusError, ChangeSetError as DalChangeSetError,
     ComponentError as DalComponentError, FixError, StandardModelError, TransactionsError,
-    UserError, UserPk,
+    UserError, UserPk, WorkspaceError, WorkspacePk,
 };
 use module_index_client::IndexClientError;
 use telemetry::prelude::*;
@@ -19,8 +19,15 @@ pub mod apply_change_set;
 pub mod apply_change_set2;
 pub mod create_change_set;
 pub mod get_change_set;
+pub mod get_diff;
 pub mod get_stats;
+pub mod list_approvals;
+pub mod list_conflicts;
 pub mod list_open_change_sets;
+pub mod rebase_change_set;
+pub mod request_change_set_approval;
+pub mod review_change_set;
+pub mod update_approval_policy;
 pub mod update_selected_change_set;
 
 #[remain::sorted]
@@ -48,6 +55,8 @@ pub enum ChangeSetError {
     InvalidUserSystemInit,
     #[error(transparent)]
     Nats(#[from] si_data_nats::NatsError),
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
     #[error(transparent)]
     Pg(#[from] si_data_pg::PgError),
     #[error(transparent)]
@@ -58,6 +67,10 @@ pub enum ChangeSetError {
     UrlParse(#[from] url::ParseError),
     #[error(transparent)]
     User(#[from] UserError),
+    #[error(transparent)]
+    Workspace(#[from] WorkspaceError),
+    #[error("workspace not found: {0}")]
+    WorkspaceNotFound(WorkspacePk),
 }
 
 pub type ChangeSetResult<T> = std::result::Result<T, ChangeSetError>;
@@ -66,6 +79,17 @@ impl IntoResponse for ChangeSetError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
             ChangeSetError::ChangeSetNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            ChangeSetError::ChangeSet(DalChangeSetError::Conflicts(_))
+            | ChangeSetError::ChangeSet(DalChangeSetError::NotApproved { .. })
+            | ChangeSetError::ChangeSet(DalChangeSetError::NotAwaitingApproval(_))
+            | ChangeSetError::ChangeSet(DalChangeSetError::NotOpen(_)) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
+            ChangeSetError::ChangeSet(DalChangeSetError::NoUserActor)
+            | ChangeSetError::ChangeSet(DalChangeSetError::NotAReviewer(_))
+            | ChangeSetError::ChangeSet(DalChangeSetError::SelfReview(_)) => {
+                (StatusCode::FORBIDDEN, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -88,7 +112,22 @@ pub fn routes() -> Router<AppState> {
             post(create_change_set::create_change_set),
         )
         .route("/get_change_set", get(get_change_set::get_change_set))
+        .route("/get_diff", get(get_diff::get_diff))
         .route("/get_stats", get(get_stats::get_stats))
+        .route("/list_conflicts", get(list_conflicts::list_conflicts))
+        .route("/list_approvals", get(list_approvals::list_approvals))
+        .route(
+            "/request_change_set_approval",
+            post(request_change_set_approval::request_change_set_approval),
+        )
+        .route(
+            "/review_change_set",
+            post(review_change_set::review_change_set),
+        )
+        .route(
+            "/update_approval_policy",
+            post(update_approval_policy::update_approval_policy),
+        )
         .route(
             "/apply_change_set",
             post(apply_change_set::apply_change_set),
@@ -97,6 +136,10 @@ pub fn routes() -> Router<AppState> {
             "/apply_change_set2",
             post(apply_change_set2::apply_change_set),
         )
+        .route(
+            "/rebase_change_set",
+            post(rebase_change_set::rebase_change_set),
+        )
         .route(
             "/update_selected_change_set",
             post(update_selected_change_set::update_selected_change_set),
@@ -128,6 +171,7 @@ async fn upload_workspace_backup_module(
         Some("Backup of all schema variants on HEAD."),
         "Sally Signup",
         schema_variant_ids,
+        vec![],
     )
     .instrument(debug_span!("Generating workspace backup module"))
     .await?;
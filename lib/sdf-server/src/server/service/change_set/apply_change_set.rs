@@ -4,13 +4,16 @@ use crate::server::service::change_set::ChangeSetError;
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
-use dal::{ChangeSet, ChangeSetPk};
+use dal::{ChangeSet, ChangeSetConflictResolution, ChangeSetPk};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ApplyChangeSetRequest {
     pub change_set_pk: ChangeSetPk,
+    /// Per-conflict choices for any objects that both the change set and head have modified.
+    #[serde(default)]
+    pub resolutions: Vec<ChangeSetConflictResolution>,
 }
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -31,7 +34,9 @@ pub async fn apply_change_set(
     let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
         .await?
         .ok_or(ChangeSetError::ChangeSetNotFound)?;
-    change_set.apply(&mut ctx).await?;
+    change_set
+        .apply_with_resolutions(&mut ctx, true, &request.resolutions)
+        .await?;
 
     track(
         &posthog_client,
@@ -6,8 +6,8 @@ use axum::extract::OriginalUri;
 use axum::Json;
 use dal::job::definition::{FixItem, FixesJob};
 use dal::{
-    ActionPrototypeId, AttributeValueId, ChangeSet, ChangeSetPk, ComponentId, Fix, FixBatch,
-    HistoryActor, StandardModel, User,
+    ActionPrototypeId, AttributeValueId, ChangeSet, ChangeSetConflictResolution, ChangeSetPk,
+    ComponentId, Fix, FixBatch, HistoryActor, StandardModel, User,
 };
 use serde::{Deserialize, Serialize};
 //use telemetry::tracing::{info_span, Instrument, log::warn};
@@ -24,6 +24,9 @@ pub struct FixRunRequest {
 #[serde(rename_all = "camelCase")]
 pub struct ApplyChangeSetRequest {
     pub change_set_pk: ChangeSetPk,
+    /// Per-conflict choices for any objects that both the change set and head have modified.
+    #[serde(default)]
+    pub resolutions: Vec<ChangeSetConflictResolution>,
     pub list: Vec<FixRunRequest>,
 }
 
@@ -45,7 +48,9 @@ pub async fn apply_change_set(
     let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
         .await?
         .ok_or(ChangeSetError::ChangeSetNotFound)?;
-    change_set.apply_raw(&mut ctx, false).await?;
+    change_set
+        .apply_with_resolutions(&mut ctx, false, &request.resolutions)
+        .await?;
 
     track(
         &posthog_client,
@@ -0,0 +1,33 @@
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+use axum::extract::Query;
+use axum::Json;
+use dal::{ChangeSetDiff, Visibility};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDiffRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDiffResponse {
+    pub diff: ChangeSetDiff,
+}
+
+/// Describe everything the _current_ change set would do to head if it were applied.
+pub async fn get_diff(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetDiffRequest>,
+) -> ChangeSetResult<Json<GetDiffResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let diff = ChangeSetDiff::new(&ctx).await?;
+
+    Ok(Json(GetDiffResponse { diff }))
+}
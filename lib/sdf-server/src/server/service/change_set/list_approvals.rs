@@ -0,0 +1,51 @@
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use axum::extract::Query;
+use axum::Json;
+use dal::{ChangeSet, ChangeSetApproval, ChangeSetPk, UserPk, Workspace};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListApprovalsRequest {
+    pub change_set_pk: ChangeSetPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListApprovalsResponse {
+    pub required_approvals: i32,
+    pub reviewers: Vec<UserPk>,
+    pub approvals: Vec<ChangeSetApproval>,
+}
+
+/// List who has been asked to review the change set, what they decided, and how many approvals
+/// the workspace requires before the change set can be applied.
+pub async fn list_approvals(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ListApprovalsRequest>,
+) -> ChangeSetResult<Json<ListApprovalsResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(ChangeSetError::ChangeSetNotFound)?;
+    let required_approvals = match ctx.tenancy().workspace_pk() {
+        Some(workspace_pk) => Workspace::get_by_pk(&ctx, &workspace_pk)
+            .await?
+            .map(|workspace| workspace.required_change_set_approvals())
+            .unwrap_or_default(),
+        None => 0,
+    };
+    let reviewers = change_set.reviewers(&ctx).await?;
+    let approvals = change_set.approvals(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(ListApprovalsResponse {
+        required_approvals,
+        reviewers,
+        approvals,
+    }))
+}
@@ -0,0 +1,52 @@
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::service::change_set::ChangeSetError;
+use crate::server::tracking::track;
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{ChangeSet, ChangeSetConflictResolution, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+    /// Per-conflict choices for any objects that both the change set and head have modified.
+    #[serde(default)]
+    pub resolutions: Vec<ChangeSetConflictResolution>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RebaseChangeSetResponse {
+    pub change_set: ChangeSet,
+}
+
+pub async fn rebase_change_set(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RebaseChangeSetRequest>,
+) -> ChangeSetResult<Json<RebaseChangeSetResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(ChangeSetError::ChangeSetNotFound)?;
+    change_set.rebase(&ctx, &request.resolutions).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "rebase_change_set",
+        serde_json::json!({
+            "rebased_change_set": request.change_set_pk,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RebaseChangeSetResponse { change_set }))
+}
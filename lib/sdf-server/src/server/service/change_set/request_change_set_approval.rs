@@ -0,0 +1,55 @@
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::service::change_set::ChangeSetError;
+use crate::server::tracking::track;
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{ChangeSet, ChangeSetPk, UserPk};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestChangeSetApprovalRequest {
+    pub change_set_pk: ChangeSetPk,
+    /// The users asked to review the change set. When empty, any other user may review it.
+    #[serde(default)]
+    pub reviewers: Vec<UserPk>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestChangeSetApprovalResponse {
+    pub change_set: ChangeSet,
+}
+
+pub async fn request_change_set_approval(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<RequestChangeSetApprovalRequest>,
+) -> ChangeSetResult<Json<RequestChangeSetApprovalResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(ChangeSetError::ChangeSetNotFound)?;
+    change_set
+        .request_approval(&ctx, request.reviewers.clone())
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "request_change_set_approval",
+        serde_json::json!({
+            "change_set": request.change_set_pk,
+            "reviewer_count": request.reviewers.len(),
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(RequestChangeSetApprovalResponse { change_set }))
+}
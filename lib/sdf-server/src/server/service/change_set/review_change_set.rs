@@ -0,0 +1,60 @@
+use super::ChangeSetResult;
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::service::change_set::ChangeSetError;
+use crate::server::tracking::track;
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{ChangeSet, ChangeSetApproval, ChangeSetApprovalDecision, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+    pub decision: ChangeSetApprovalDecision,
+    pub comment: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewChangeSetResponse {
+    pub change_set: ChangeSet,
+    pub approval: ChangeSetApproval,
+}
+
+/// Approve or reject a change set that is awaiting approval, as the current user.
+pub async fn review_change_set(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ReviewChangeSetRequest>,
+) -> ChangeSetResult<Json<ReviewChangeSetResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(ChangeSetError::ChangeSetNotFound)?;
+    let approval = match request.decision {
+        ChangeSetApprovalDecision::Approved => change_set.approve(&ctx, request.comment).await?,
+        ChangeSetApprovalDecision::Rejected => change_set.reject(&ctx, request.comment).await?,
+    };
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "review_change_set",
+        serde_json::json!({
+            "change_set": request.change_set_pk,
+            "decision": request.decision,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    Ok(Json(ReviewChangeSetResponse {
+        change_set,
+        approval,
+    }))
+}
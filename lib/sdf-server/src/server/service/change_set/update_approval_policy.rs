@@ -0,0 +1,43 @@
+use super::{ChangeSetError, ChangeSetResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use axum::Json;
+use dal::Workspace;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateApprovalPolicyRequest {
+    /// How many approvals a change set needs before it can be applied. Zero turns approvals off.
+    pub required_approvals: i32,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateApprovalPolicyResponse {
+    pub required_approvals: i32,
+}
+
+pub async fn update_approval_policy(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<UpdateApprovalPolicyRequest>,
+) -> ChangeSetResult<Json<UpdateApprovalPolicyResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .ok_or(ChangeSetError::NoWorkspaceInTenancy)?;
+    let mut workspace = Workspace::get_by_pk(&ctx, &workspace_pk)
+        .await?
+        .ok_or(ChangeSetError::WorkspaceNotFound(workspace_pk))?;
+    workspace
+        .set_required_change_set_approvals(&ctx, request.required_approvals)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(UpdateApprovalPolicyResponse {
+        required_approvals: workspace.required_change_set_approvals(),
+    }))
+}
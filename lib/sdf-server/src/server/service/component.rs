@@ -113,6 +113,11 @@ impl IntoResponse for ComponentError {
         let (status, error_message) = match self {
             ComponentError::SchemaNotFound => (StatusCode::NOT_FOUND, self.to_string()),
             ComponentError::InvalidVisibility => (StatusCode::NOT_FOUND, self.to_string()),
+            ComponentError::AttributeValue(
+                AttributeValueError::InvalidSecretReference(..)
+                | AttributeValueError::SecretKindMismatch { .. }
+                | AttributeValueError::SecretNotFound(..),
+            ) => (StatusCode::UNPROCESSABLE_ENTITY, self.to_string()),
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -6,8 +6,8 @@ use axum::Router;
 use dal::provider::external::ExternalProviderError as DalExternalProviderError;
 use dal::socket::{SocketError, SocketId};
 use dal::{
-    node::NodeId, schema::variant::SchemaVariantError, AttributeValueError, ChangeSetError,
-    ComponentError, ComponentType, DiagramError as DalDiagramError, EdgeError,
+    node::NodeId, schema::variant::SchemaVariantError, ActionPrototypeError, AttributeValueError,
+    ChangeSetError, ComponentError, DiagramError as DalDiagramError, EdgeError,
     InternalProviderError, NodeError, NodeKind, NodeMenuError, SchemaError as DalSchemaError,
     SchemaVariantId, StandardModelError, TransactionsError,
 };
@@ -24,6 +24,7 @@ pub mod delete_component;
 pub mod delete_connection;
 pub mod get_diagram;
 pub mod get_node_add_menu;
+pub mod import_component;
 pub mod list_schema_variants;
 mod restore_component;
 pub mod restore_connection;
@@ -32,6 +33,8 @@ pub mod set_node_position;
 #[remain::sorted]
 #[derive(Debug, Error)]
 pub enum DiagramError {
+    #[error("action prototype error: {0}")]
+    ActionPrototype(#[from] ActionPrototypeError),
     #[error("attribute value error: {0}")]
     AttributeValue(#[from] AttributeValueError),
     #[error("attribute value not found for context: {0:?}")]
@@ -62,12 +65,12 @@ pub enum DiagramError {
     FrameSocketNotFound(SchemaVariantId),
     #[error("invalid header name {0}")]
     Hyper(#[from] hyper::http::Error),
+    #[error("import action not found for schema variant id: {0}")]
+    ImportActionNotFound(SchemaVariantId),
     #[error(transparent)]
     InternalProvider(#[from] InternalProviderError),
     #[error("internal provider not found for socket id: {0}")]
     InternalProviderNotFoundForSocket(SocketId),
-    #[error("invalid component type ({0:?}) for frame")]
-    InvalidComponentTypeForFrame(ComponentType),
     #[error("invalid parent node kind {0:?}")]
     InvalidParentNode(NodeKind),
     #[error("invalid request")]
@@ -115,7 +118,18 @@ pub type DiagramResult<T> = Result<T, DiagramError>;
 impl IntoResponse for DiagramError {
     fn into_response(self) -> Response {
         let (status, error_message) = match self {
-            DiagramError::SchemaNotFound => (StatusCode::NOT_FOUND, self.to_string()),
+            DiagramError::SchemaNotFound | DiagramError::ImportActionNotFound(_) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            DiagramError::ActionPrototype(ActionPrototypeError::ImportResourceNotFound(_)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            DiagramError::ActionPrototype(ActionPrototypeError::AlreadyImported(..)) => {
+                (StatusCode::CONFLICT, self.to_string())
+            }
+            DiagramError::ActionPrototype(ActionPrototypeError::ImportResourceError(..)) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, self.to_string())
+            }
             _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
         };
 
@@ -135,6 +149,10 @@ pub fn routes() -> Router<AppState> {
             post(get_node_add_menu::get_node_add_menu),
         )
         .route("/create_node", post(create_node::create_node))
+        .route(
+            "/import_component",
+            post(import_component::import_component),
+        )
         .route(
             "/set_node_position",
             post(set_node_position::set_node_position),
@@ -12,7 +12,6 @@ use dal::{
 
 use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
 use crate::server::tracking::track;
-use crate::service::diagram::connect_component_to_frame::connect_component_sockets_to_frame;
 use crate::service::diagram::{DiagramError, DiagramResult};
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -99,7 +98,7 @@ pub async fn create_node(
         )
         .await?;
 
-        connect_component_sockets_to_frame(&ctx, frame_id, *node.id()).await?;
+        Connection::connect_component_sockets_to_frame(&ctx, frame_id, *node.id()).await?;
 
         let child_comp = Node::get_by_id(&ctx, node.id())
             .await?
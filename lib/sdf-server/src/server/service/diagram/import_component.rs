@@ -0,0 +1,124 @@
+use axum::extract::OriginalUri;
+use axum::{response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+
+use dal::node::NodeId;
+use dal::{
+    generate_name, ActionKind, ActionPrototype, ActionPrototypeContext, ChangeSet, ComponentId,
+    Schema, SchemaId, StandardModel, Visibility, WsEvent,
+};
+
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient};
+use crate::server::tracking::track;
+use crate::service::diagram::{DiagramError, DiagramResult};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportComponentRequest {
+    pub schema_id: SchemaId,
+    /// The identifier of the existing resource, such as an ARN.
+    pub identifier: String,
+    pub name: Option<String>,
+    pub x: String,
+    pub y: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportComponentResponse {
+    pub component_id: ComponentId,
+    pub node_id: NodeId,
+}
+
+/// Creates a [`Component`](dal::Component) for an existing resource by running the import action
+/// of the schema's default variant. If the import fails, nothing is committed.
+pub async fn import_component(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<ImportComponentRequest>,
+) -> DiagramResult<impl IntoResponse> {
+    let mut ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut force_changeset_pk = None;
+    if ctx.visibility().is_head() {
+        let change_set = ChangeSet::new(&ctx, ChangeSet::generate_name(), None).await?;
+
+        let new_visibility = Visibility::new(change_set.pk, request.visibility.deleted_at);
+
+        ctx.update_visibility(new_visibility);
+
+        force_changeset_pk = Some(change_set.pk);
+
+        WsEvent::change_set_created(&ctx, change_set.pk)
+            .await?
+            .publish_on_commit(&ctx)
+            .await?;
+    };
+
+    let name = request.name.clone().unwrap_or_else(generate_name);
+    let schema = Schema::get_by_id(&ctx, &request.schema_id)
+        .await?
+        .ok_or(DiagramError::SchemaNotFound)?;
+
+    let schema_variant_id = schema
+        .default_schema_variant_id()
+        .ok_or(DiagramError::SchemaVariantNotFound)?;
+
+    let prototype = ActionPrototype::find_for_context_and_kind(
+        &ctx,
+        ActionKind::Import,
+        ActionPrototypeContext {
+            schema_variant_id: *schema_variant_id,
+        },
+    )
+    .await?
+    .pop()
+    .ok_or(DiagramError::ImportActionNotFound(*schema_variant_id))?;
+
+    let (component, mut node) = prototype.import(&ctx, &name, &request.identifier).await?;
+
+    node.set_geometry(
+        &ctx,
+        request.x.clone(),
+        request.y.clone(),
+        Some("500"),
+        Some("500"),
+    )
+    .await?;
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "component_imported",
+        serde_json::json!({
+                    "schema_id": schema.id(),
+                    "schema_name": schema.name(),
+                    "schema_variant_id": &schema_variant_id,
+                    "component_id": component.id(),
+                    "component_name": &name,
+        }),
+    );
+
+    ctx.commit().await?;
+
+    let mut response = axum::response::Response::builder();
+    if let Some(force_changeset_pk) = force_changeset_pk {
+        response = response.header("force_changeset_pk", force_changeset_pk.to_string());
+    }
+    Ok(
+        response.body(serde_json::to_string(&ImportComponentResponse {
+            component_id: *component.id(),
+            node_id: *node.id(),
+        })?)?,
+    )
+}
@@ -9,19 +9,22 @@ use thiserror::Error;
 use dal::fix::FixError as DalFixError;
 use dal::schema::SchemaError as DalSchemaError;
 use dal::{
-    ComponentError, ComponentId, FixResolverError, FuncBindingReturnValueError, StandardModelError,
-    TransactionsError, UserError, UserPk,
+    ActionPrototypeId, ComponentError, ComponentId, FixResolverError, FuncBindingReturnValueError,
+    StandardModelError, TransactionsError, UserError, UserPk,
 };
 
 use crate::server::state::AppState;
 
 pub mod confirmations;
 pub mod list;
+pub mod plan;
 pub mod run;
 
 #[remain::sorted]
 #[derive(Error, Debug)]
 pub enum FixError {
+    #[error("action prototype {0} not found")]
+    ActionPrototypeNotFound(ActionPrototypeId),
     #[error(transparent)]
     Component(#[from] ComponentError),
     #[error("component {0} not found")]
@@ -68,5 +71,6 @@ pub fn routes() -> Router<AppState> {
     Router::new()
         .route("/confirmations", get(confirmations::confirmations))
         .route("/list", get(list::list))
+        .route("/plan", post(plan::plan))
         .route("/run", post(run::run))
 }
@@ -0,0 +1,102 @@
+use axum::Json;
+use futures::{StreamExt, TryFutureExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+
+use super::run::FixRunRequest;
+use super::{FixError, FixResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use dal::{
+    ActionKind, ActionPlan, ActionPrototype, ActionPrototypeId, AttributeValueId, Component,
+    ComponentId, DalContext, StandardModel, Visibility,
+};
+use veritech_client::ResourceStatus;
+
+/// How many plans are run at once. Each one is a function execution in veritech, so a large
+/// batch is kept from taking over the executors.
+const MAX_CONCURRENT_PLANS: usize = 4;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FixesPlanRequest {
+    pub list: Vec<FixRunRequest>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+/// The plan for one of the fixes in the batch.
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FixPlanView {
+    pub attribute_value_id: AttributeValueId,
+    pub component_id: ComponentId,
+    pub component_name: String,
+    pub action_prototype_id: ActionPrototypeId,
+    pub action_kind: ActionKind,
+    /// Empty when the action's func has no plan handler.
+    pub plan: Option<ActionPlan>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct FixesPlanResponse {
+    pub plans: Vec<FixPlanView>,
+}
+
+/// Plans every fix in what would be the batch, without running any of them, so the combined
+/// plan can be reviewed before the batch is confirmed with [`run`](super::run::run).
+pub async fn plan(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<FixesPlanRequest>,
+) -> FixResult<Json<FixesPlanResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let mut plans: Vec<(usize, FixPlanView)> =
+        futures::stream::iter(request.list.into_iter().enumerate())
+            .map(|(index, fix_run_request)| {
+                plan_fix(&ctx, fix_run_request).map_ok(move |plan| (index, plan))
+            })
+            .buffer_unordered(MAX_CONCURRENT_PLANS)
+            .try_collect()
+            .await?;
+    // Plans come back in the order they finish, but are listed in the order they were requested
+    plans.sort_by_key(|(index, _)| *index);
+    let plans = plans.into_iter().map(|(_, plan)| plan).collect();
+
+    Ok(Json(FixesPlanResponse { plans }))
+}
+
+/// Plans a single fix, as it would run in the batch.
+async fn plan_fix(ctx: &DalContext, fix_run_request: FixRunRequest) -> FixResult<FixPlanView> {
+    let action_prototype = ActionPrototype::get_by_id(ctx, &fix_run_request.action_prototype_id)
+        .await?
+        .ok_or(FixError::ActionPrototypeNotFound(
+            fix_run_request.action_prototype_id,
+        ))?;
+    let component = Component::get_by_id(ctx, &fix_run_request.component_id)
+        .await?
+        .ok_or(FixError::ComponentNotFound(fix_run_request.component_id))?;
+
+    // A plan that fails to run is reported alongside the others rather than failing them
+    let plan = match action_prototype
+        .plan(ctx, fix_run_request.component_id)
+        .await
+    {
+        Ok(plan) => plan,
+        Err(err) => Some(ActionPlan {
+            status: ResourceStatus::Error,
+            proposed_changes: None,
+            message: Some(err.to_string()),
+            logs: Vec::new(),
+        }),
+    };
+
+    Ok(FixPlanView {
+        attribute_value_id: fix_run_request.attribute_value_id,
+        component_id: fix_run_request.component_id,
+        component_name: component.name(ctx).await?,
+        action_prototype_id: fix_run_request.action_prototype_id,
+        action_kind: *action_prototype.kind(),
+        plan,
+    })
+}
@@ -29,6 +29,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use thiserror::Error;
 
+pub mod cancel_execution;
 pub mod create_func;
 pub mod get_func;
 pub mod list_funcs;
@@ -593,6 +594,7 @@ pub async fn get_func_view(ctx: &DalContext, func: &Func) -> FuncResult<GetFuncR
     Ok(GetFuncResponse {
         id: func.id().to_owned(),
         handler: func.handler().map(|h| h.to_owned()),
+        plan_handler: func.plan_handler().map(|h| h.to_owned()),
         variant: func.try_into()?,
         display_name: func.display_name().map(Into::into),
         name: func.name().to_owned(),
@@ -622,7 +624,9 @@ pub fn compile_return_types(ty: FuncBackendResponseType, kind: FuncBackendKind)
     match ty {
         FuncBackendResponseType::Boolean => "type Output = boolean | null;",
         FuncBackendResponseType::String => "type Output = string | null;",
-        FuncBackendResponseType::Integer => "type Output = number | null;",
+        FuncBackendResponseType::Integer | FuncBackendResponseType::Float => {
+            "type Output = number | null;"
+        }
         FuncBackendResponseType::Qualification => {
             "interface Output {
   result: 'success' | 'warning' | 'failure';
@@ -660,6 +664,7 @@ interface Output {
     status: 'ok' | 'warning' | 'error';
     payload?: { [key: string]: unknown } | null;
     message?: string | null;
+    domain?: { [key: string]: unknown };
 }"
         }
         FuncBackendResponseType::Json => "type Output = any;",
@@ -867,6 +872,10 @@ pub fn routes() -> Router<AppState> {
         .route("/save_func", post(save_func::save_func))
         .route("/save_and_exec", post(save_and_exec::save_and_exec))
         .route("/revert_func", post(revert_func::revert_func))
+        .route(
+            "/cancel_execution",
+            post(cancel_execution::cancel_execution),
+        )
         .route(
             "/list_input_sources",
             get(list_input_sources::list_input_sources),
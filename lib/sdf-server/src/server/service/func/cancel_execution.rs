@@ -0,0 +1,33 @@
+use axum::Json;
+use dal::func::execution::{FuncExecution, FuncExecutionPk};
+use dal::Visibility;
+use serde::{Deserialize, Serialize};
+
+use super::FuncResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelExecutionRequest {
+    pub execution_pk: FuncExecutionPk,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelExecutionResponse {
+    pub success: bool,
+}
+
+pub async fn cancel_execution(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Json(request): Json<CancelExecutionRequest>,
+) -> FuncResult<Json<CancelExecutionResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    FuncExecution::cancel(&ctx, request.execution_pk).await?;
+
+    Ok(Json(CancelExecutionResponse { success: true }))
+}
@@ -37,6 +37,7 @@ pub struct GetFuncRequest {
 pub struct GetFuncResponse {
     pub id: FuncId,
     pub handler: Option<String>,
+    pub plan_handler: Option<String>,
     pub variant: FuncVariant,
     pub name: String,
     pub display_name: Option<String>,
@@ -27,6 +27,9 @@ use dal::{FuncBackendResponseType, FuncDescription, PropKind, SchemaVariant, Val
 pub struct SaveFuncRequest {
     pub id: FuncId,
     pub handler: Option<String>,
+    /// The entry point that plans an action func's changes, see [`Func`](dal::Func).
+    #[serde(default)]
+    pub plan_handler: Option<String>,
     pub display_name: Option<String>,
     pub name: String,
     pub description: Option<String>,
@@ -621,6 +624,7 @@ pub async fn do_save_func(
     func.set_name(ctx, request.name).await?;
     func.set_description(ctx, request.description).await?;
     func.set_handler(ctx, request.handler).await?;
+    func.set_plan_handler(ctx, request.plan_handler).await?;
     func.set_code_plaintext(ctx, request.code.as_deref())
         .await?;
 
@@ -6,8 +6,10 @@ use axum::{
 };
 use convert_case::{Case, Casing};
 use dal::{
-    installed_pkg::InstalledPkgError, pkg::PkgError as DalPkgError, DalContextBuilder,
-    StandardModelError, TenancyError, TransactionsError, UserError, WsEventError,
+    installed_pkg::{InstalledPkgError, InstalledPkgId},
+    pkg::PkgError as DalPkgError,
+    DalContextBuilder, StandardModelError, TenancyError, TransactionsError, UserError,
+    WsEventError,
 };
 use serde::{Deserialize, Serialize};
 use si_pkg::{SiPkg, SiPkgError};
@@ -22,6 +24,8 @@ const MAX_NAME_SEARCH_ATTEMPTS: usize = 100;
 pub mod export_pkg;
 pub mod get_pkg;
 pub mod install_pkg;
+pub mod instantiate_template;
+pub mod list_pkg_upgrades;
 pub mod list_pkgs;
 pub mod remote_module_spec;
 
@@ -37,6 +41,8 @@ pub enum PkgError {
     // add error for matching hash
     #[error(transparent)]
     InstalledPkg(#[from] InstalledPkgError),
+    #[error("Installed package not found: {0}")]
+    InstalledPkgNotFound(InstalledPkgId),
     #[error("Invalid pacakge file name: {0}")]
     InvalidPackageFileName(String),
     #[error("IO Error: {0}")]
@@ -53,7 +59,7 @@ pub enum PkgError {
     PackageAlreadyInstalled(String),
     #[error("That package already exists: {0}")]
     PackageAlreadyOnDisk(String),
-    #[error("No schema variants added to package export")]
+    #[error("No schema variants or components added to package export")]
     PackageExportEmpty,
     #[error("Package name required")]
     PackageNameEmpty,
@@ -75,6 +81,8 @@ pub enum PkgError {
     StandardModel(#[from] StandardModelError),
     #[error("tenancy error: {0}")]
     Tenancy(#[from] TenancyError),
+    #[error("error decoding ulid: {0}")]
+    UlidDecode(#[from] ulid::DecodeError),
     #[error("Unable to parse URL: {0}")]
     Url(#[from] url::ParseError),
     #[error("transparent")]
@@ -180,6 +188,14 @@ pub fn routes() -> Router<AppState> {
         .route("/export_pkg", post(export_pkg::export_pkg))
         .route("/get_module_by_hash", get(get_pkg::get_module_by_hash))
         .route("/install_pkg", post(install_pkg::install_pkg))
+        .route(
+            "/instantiate_template",
+            post(instantiate_template::instantiate_template_pkg),
+        )
+        .route(
+            "/list_pkg_upgrades",
+            get(list_pkg_upgrades::list_pkg_upgrades),
+        )
         .route("/list_pkgs", get(list_pkgs::list_pkgs))
         .route(
             "/remote_module_spec",
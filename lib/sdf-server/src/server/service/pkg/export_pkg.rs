@@ -3,7 +3,7 @@ use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RawAc
 use crate::server::tracking::track;
 use axum::extract::OriginalUri;
 use axum::Json;
-use dal::{HistoryActor, SchemaVariantId, User, Visibility, WsEvent};
+use dal::{ComponentId, HistoryActor, SchemaVariantId, User, Visibility, WsEvent};
 use serde::{Deserialize, Serialize};
 use telemetry::prelude::*;
 
@@ -14,6 +14,9 @@ pub struct ExportPkgRequest {
     pub version: String,
     pub description: Option<String>,
     pub schema_variants: Vec<SchemaVariantId>,
+    /// Components to include as a template, along with the connections between them.
+    #[serde(default)]
+    pub components: Vec<ComponentId>,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
@@ -43,7 +46,7 @@ pub async fn export_pkg(
         return Err(PkgError::PackageVersionEmpty);
     }
 
-    if request.schema_variants.is_empty() {
+    if request.schema_variants.is_empty() && request.components.is_empty() {
         return Err(PkgError::PackageExportEmpty);
     }
 
@@ -72,6 +75,7 @@ pub async fn export_pkg(
         request.description.as_ref(),
         &created_by_email,
         request.schema_variants.clone(),
+        request.components.clone(),
     )
     .await?;
 
@@ -93,6 +97,7 @@ pub async fn export_pkg(
                     "pkg_created_by_name": created_by_name,
                     "pkg_created_by_email": created_by_email,
                     "pkg_schema_count": request.schema_variants.len(),
+                    "pkg_component_count": request.components.len(),
                     "pkg_hash": response.latest_hash,
         }),
     );
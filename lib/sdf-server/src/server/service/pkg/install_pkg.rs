@@ -17,6 +17,8 @@ use ulid::Ulid;
 #[serde(rename_all = "camelCase")]
 pub struct InstallPkgRequest {
     pub id: Ulid,
+    /// Install this version of the module rather than its latest one
+    pub version: Option<String>,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
@@ -43,7 +45,14 @@ pub async fn install_pkg(
     };
 
     let module_index_client = IndexClient::new(module_index_url.try_into()?, &raw_access_token);
-    let pkg_data = module_index_client.download_module(request.id).await?;
+    let pkg_data = match &request.version {
+        Some(version) => {
+            module_index_client
+                .download_module_version(request.id, version)
+                .await?
+        }
+        None => module_index_client.download_module(request.id).await?,
+    };
 
     let pkg = SiPkg::load_from_bytes(pkg_data)?;
     let pkg_name = pkg.metadata()?.name().to_owned();
@@ -56,6 +65,7 @@ pub async fn install_pkg(
         "install_pkg",
         serde_json::json!({
                     "pkg_name": pkg_name,
+                    "pkg_version": request.version,
         }),
     );
 
@@ -0,0 +1,80 @@
+use super::PkgResult;
+use crate::server::extract::RawAccessToken;
+use crate::server::tracking::track;
+use crate::{
+    server::extract::{AccessBuilder, HandlerContext, PosthogClient},
+    service::pkg::PkgError,
+};
+use axum::extract::OriginalUri;
+use axum::Json;
+use dal::{pkg::instantiate_template, ComponentId, Visibility, WsEvent};
+use module_index_client::IndexClient;
+use serde::{Deserialize, Serialize};
+use si_pkg::SiPkg;
+use ulid::Ulid;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateRequest {
+    pub id: Ulid,
+    /// Instantiate this version of the template rather than its latest one
+    pub version: Option<String>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstantiateTemplateResponse {
+    pub component_ids: Vec<ComponentId>,
+}
+
+pub async fn instantiate_template_pkg(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    RawAccessToken(raw_access_token): RawAccessToken,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Json(request): Json<InstantiateTemplateRequest>,
+) -> PkgResult<Json<InstantiateTemplateResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let module_index_url = match ctx.module_index_url() {
+        Some(url) => url,
+        None => return Err(PkgError::ModuleIndexNotConfigured),
+    };
+
+    let module_index_client = IndexClient::new(module_index_url.try_into()?, &raw_access_token);
+    let pkg_data = match &request.version {
+        Some(version) => {
+            module_index_client
+                .download_module_version(request.id, version)
+                .await?
+        }
+        None => module_index_client.download_module(request.id).await?,
+    };
+
+    let pkg = SiPkg::load_from_bytes(pkg_data)?;
+    let pkg_name = pkg.metadata()?.name().to_owned();
+    let component_ids = instantiate_template(&ctx, &pkg).await?;
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "instantiate_template",
+        serde_json::json!({
+                    "pkg_name": pkg_name,
+                    "pkg_version": request.version,
+                    "pkg_component_count": component_ids.len(),
+        }),
+    );
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+    ctx.commit().await?;
+
+    Ok(Json(InstantiateTemplateResponse { component_ids }))
+}
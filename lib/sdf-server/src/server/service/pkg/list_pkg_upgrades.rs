@@ -0,0 +1,123 @@
+use super::{PkgError, PkgResult};
+use crate::server::extract::{AccessBuilder, HandlerContext, PosthogClient, RawAccessToken};
+use crate::server::tracking::track;
+use axum::extract::OriginalUri;
+use axum::{extract::Query, Json};
+use chrono::{DateTime, Utc};
+use dal::{
+    installed_pkg::{InstalledPkg, InstalledPkgId},
+    StandardModel, Visibility,
+};
+use module_index_client::{parse_version, IndexClient, ModuleVersionResponse};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPkgUpgradesRequest {
+    pub id: InstalledPkgId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPkgUpgradesResponse {
+    /// The version of the installed package, if the module index knows about it
+    pub installed_version: Option<String>,
+    pub upgrades: Vec<PkgUpgradeView>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PkgUpgradeView {
+    module_id: String,
+    version: String,
+    hash: String,
+    created_at: DateTime<Utc>,
+}
+
+/// Lists the versions of the module an [`InstalledPkg`] was installed from which are newer than
+/// the installed one, newest first. Only semantic versions can be compared, so nothing is
+/// offered as an upgrade for a package whose version isn't one.
+pub async fn list_pkg_upgrades(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    RawAccessToken(raw_access_token): RawAccessToken,
+    PosthogClient(posthog_client): PosthogClient,
+    OriginalUri(original_uri): OriginalUri,
+    Query(request): Query<ListPkgUpgradesRequest>,
+) -> PkgResult<Json<ListPkgUpgradesResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let installed_pkg = InstalledPkg::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(PkgError::InstalledPkgNotFound(request.id))?;
+
+    let module_index_url = match ctx.module_index_url() {
+        Some(url) => url,
+        None => return Err(PkgError::ModuleIndexNotConfigured),
+    };
+    let module_index_client = IndexClient::new(module_index_url.try_into()?, &raw_access_token);
+
+    // The name filter matches on a substring, so only keep modules with exactly this name
+    let mut versions: Vec<ModuleVersionResponse> = vec![];
+    for module in module_index_client
+        .list_modules(Some(installed_pkg.name()), None)
+        .await?
+        .modules
+        .into_iter()
+        .filter(|module| module.name == installed_pkg.name())
+    {
+        let module_id = Ulid::from_string(&module.id)?;
+        versions.extend(
+            module_index_client
+                .list_module_versions(module_id)
+                .await?
+                .versions,
+        );
+    }
+
+    let installed_version = versions
+        .iter()
+        .find(|version| version.hash.trim() == installed_pkg.root_hash())
+        .map(|version| version.version.to_owned());
+
+    let mut upgrades: Vec<(semver::Version, ModuleVersionResponse)> =
+        match installed_version.as_deref().and_then(parse_version) {
+            Some(installed) => versions
+                .into_iter()
+                .filter_map(|version| {
+                    parse_version(&version.version)
+                        .filter(|parsed| parsed > &installed)
+                        .map(|parsed| (parsed, version))
+                })
+                .collect(),
+            None => vec![],
+        };
+    upgrades.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+    track(
+        &posthog_client,
+        &ctx,
+        &original_uri,
+        "list_pkg_upgrades",
+        serde_json::json!({
+                    "pkg_name": installed_pkg.name(),
+                    "pkg_version": installed_version,
+        }),
+    );
+
+    Ok(Json(ListPkgUpgradesResponse {
+        installed_version,
+        upgrades: upgrades
+            .into_iter()
+            .map(|(_, version)| PkgUpgradeView {
+                module_id: version.module_id,
+                version: version.version,
+                hash: version.hash,
+                created_at: version.created_at,
+            })
+            .collect(),
+    }))
+}
@@ -11,12 +11,13 @@ use thiserror::Error;
 
 use dal::{qualification::QualificationSummaryError, WsEventError};
 use dal::{
-    AttributeValueError, ComponentError, ComponentId, FuncError, FuncId, SchemaError, SchemaId,
-    StandardModelError, TenancyError, TransactionsError,
+    AttributeValueError, ComponentError, ComponentId, DriftError, FuncError, FuncId, SchemaError,
+    SchemaId, StandardModelError, TenancyError, TransactionsError,
 };
 
 use crate::server::state::AppState;
 
+pub mod get_drift_summary;
 pub mod get_summary;
 
 // code endpoints here are deprecated, removing them from the module tree
@@ -37,6 +38,8 @@ pub enum QualificationError {
     Component(#[from] ComponentError),
     #[error("component not found: {0}")]
     ComponentNotFound(ComponentId),
+    #[error("drift error: {0}")]
+    Drift(#[from] DriftError),
     #[error("func error: {0}")]
     Func(#[from] FuncError),
     #[error("func code not found: {0}")]
@@ -86,5 +89,10 @@ impl IntoResponse for QualificationError {
 }
 
 pub fn routes() -> Router<AppState> {
-    Router::new().route("/get_summary", get(get_summary::get_summary))
+    Router::new()
+        .route("/get_summary", get(get_summary::get_summary))
+        .route(
+            "/get_drift_summary",
+            get(get_drift_summary::get_drift_summary),
+        )
 }
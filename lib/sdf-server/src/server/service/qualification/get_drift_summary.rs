@@ -0,0 +1,29 @@
+use axum::extract::Query;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::{DriftSummary, Visibility};
+
+use crate::server::extract::{AccessBuilder, HandlerContext};
+use crate::service::qualification::QualificationResult;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDriftSummaryRequest {
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+pub type GetDriftSummaryResponse = DriftSummary;
+
+pub async fn get_drift_summary(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_ctx): AccessBuilder,
+    Query(request): Query<GetDriftSummaryRequest>,
+) -> QualificationResult<Json<GetDriftSummaryResponse>> {
+    let ctx = builder.build(request_ctx.build(request.visibility)).await?;
+
+    let drift_summary = DriftSummary::get_summary(&ctx).await?;
+
+    Ok(Json(drift_summary))
+}
@@ -1,13 +1,13 @@
 use axum::Json;
 use dal::{
-    key_pair::KeyPairPk, EncryptedSecret, Secret, SecretAlgorithm, SecretKind, SecretObjectType,
-    SecretVersion, Visibility, WsEvent,
+    key_pair::KeyPairPk, EncryptedSecret, Secret, SecretAlgorithm, SecretBackendKind, SecretKind,
+    SecretObjectType, SecretVersion, StandardModel, Visibility, WsEvent,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::server::extract::{AccessBuilder, HandlerContext};
 
-use super::SecretResult;
+use super::{SecretError, SecretResult};
 
 #[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -19,6 +19,10 @@ pub struct CreateSecretRequest {
     pub key_pair_pk: KeyPairPk,
     pub version: SecretVersion,
     pub algorithm: SecretAlgorithm,
+    /// Where the secret's value lives. For anything other than the database, `crypted` holds a
+    /// sealed [`SecretReference`](dal::SecretReference) instead of the value.
+    #[serde(default)]
+    pub backend: SecretBackendKind,
     #[serde(flatten)]
     pub visibility: Visibility,
 }
@@ -36,7 +40,7 @@ pub async fn create_secret(
 ) -> SecretResult<Json<CreateSecretResponse>> {
     let ctx = builder.build(request_tx.build(request.visibility)).await?;
 
-    let secret = EncryptedSecret::new(
+    let secret = EncryptedSecret::new_with_backend(
         &ctx,
         request.name,
         request.object_type,
@@ -45,9 +49,19 @@ pub async fn create_secret(
         request.key_pair_pk,
         request.version,
         request.algorithm,
+        request.backend,
     )
     .await?;
 
+    // Secrets whose kind has a definition must match its JSON schema, and references to an
+    // external backend must stay within the workspace. If they don't, the error drops the
+    // transaction along with the secret.
+    let encrypted_secret = EncryptedSecret::get_by_id(&ctx, secret.id())
+        .await?
+        .ok_or(SecretError::SecretNotFound(*secret.id()))?;
+    encrypted_secret.validate_reference(&ctx).await?;
+    encrypted_secret.validate_against_definition(&ctx).await?;
+
     WsEvent::change_set_written(&ctx)
         .await?
         .publish_on_commit(&ctx)
@@ -0,0 +1,44 @@
+use axum::Json;
+use dal::{Secret, SecretId, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::{SecretError, SecretResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSecretRequest {
+    pub id: SecretId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteSecretResponse {
+    pub success: bool,
+}
+
+pub async fn delete_secret(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(request_tx): AccessBuilder,
+    Json(request): Json<DeleteSecretRequest>,
+) -> SecretResult<Json<DeleteSecretResponse>> {
+    let ctx = builder.build(request_tx.build(request.visibility)).await?;
+
+    // Secrets still referenced by a component are not deleted, see `SecretError::InUse`.
+    Secret::get_by_id(&ctx, &request.id)
+        .await?
+        .ok_or(SecretError::SecretNotFound(request.id))?
+        .delete(&ctx)
+        .await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(DeleteSecretResponse { success: true }))
+}
@@ -0,0 +1,20 @@
+use axum::Json;
+use dal::{KeyPair, KeyPairRotation};
+
+use super::SecretResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+pub type RotateKeyPairResponse = KeyPairRotation;
+
+pub async fn rotate_key_pair(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> SecretResult<Json<RotateKeyPairResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let response: RotateKeyPairResponse = KeyPair::rotate(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(response))
+}
@@ -1,7 +1,9 @@
 use super::{SessionError, SessionResult};
 use crate::server::extract::HandlerContext;
 use axum::Json;
-use dal::{HistoryActor, KeyPair, Tenancy, User, UserPk, Workspace, WorkspacePk};
+use dal::{
+    DalContext, HistoryActor, KeyPair, Tenancy, User, UserPk, Workspace, WorkspacePk, WorkspaceRole,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -87,17 +89,36 @@ pub async fn auth_connect(
     let res_body = res.json::<AuthApiConnectResponse>().await?;
 
     let mut ctx = builder.build_default().await?;
+    let (user, workspace) = connect_user_and_workspace(&mut ctx, &res_body).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(AuthConnectResponse {
+        user,
+        workspace,
+        token: res_body.token,
+    }))
+}
+
+/// Finds or creates the user and workspace of a completed auth connect and makes sure the user is
+/// a member of the workspace. The workspace's creator joins as an [`Admin`](WorkspaceRole::Admin),
+/// so that someone can manage the workspace; everyone else joins as an
+/// [`Editor`](WorkspaceRole::Editor). Members keep whatever role they have been given since.
+pub async fn connect_user_and_workspace(
+    ctx: &mut DalContext,
+    res_body: &AuthApiConnectResponse,
+) -> SessionResult<(User, Workspace)> {
     // lookup user or create if we've never seen it before
-    let maybe_user = User::get_by_pk(&ctx, res_body.user.id).await?;
+    let maybe_user = User::get_by_pk(ctx, res_body.user.id).await?;
     let user = match maybe_user {
         Some(user) => user,
         None => {
             User::new(
-                &ctx,
+                ctx,
                 res_body.user.id,
-                res_body.user.nickname,
-                res_body.user.email,
-                res_body.user.picture_url,
+                &res_body.user.nickname,
+                &res_body.user.email,
+                res_body.user.picture_url.as_ref(),
             )
             .await?
         }
@@ -105,33 +126,35 @@ pub async fn auth_connect(
     ctx.update_history_actor(HistoryActor::User(user.pk()));
 
     // lookup workspace or create if we've never seen it before
-    let maybe_workspace = Workspace::get_by_pk(&ctx, &res_body.workspace.id).await?;
-    let workspace = match maybe_workspace {
+    let maybe_workspace = Workspace::get_by_pk(ctx, &res_body.workspace.id).await?;
+    let (workspace, created) = match maybe_workspace {
         Some(workspace) => {
             ctx.update_tenancy(Tenancy::new(*workspace.pk()));
-            workspace
+            (workspace, false)
         }
         None => {
-            let workspace = Workspace::new(
-                &mut ctx,
-                res_body.workspace.id,
-                res_body.workspace.display_name,
-            )
-            .await?;
-            let _key_pair = KeyPair::new(&ctx, "default").await?;
+            let workspace =
+                Workspace::new(ctx, res_body.workspace.id, &res_body.workspace.display_name)
+                    .await?;
+            let _key_pair = KeyPair::new(ctx, "default").await?;
             ctx.import_builtins().await?;
-            workspace
+            (workspace, true)
         }
     };
 
-    // ensure workspace is associated to user
-    user.associate_workspace(&ctx, *workspace.pk()).await?;
-
-    ctx.commit().await?;
+    // ensure workspace is associated to user, leaving the role of existing members alone
+    if User::workspace_role(ctx, user.pk(), *workspace.pk())
+        .await?
+        .is_none()
+    {
+        let role = if created || res_body.workspace.creator_user_id == user.pk() {
+            WorkspaceRole::Admin
+        } else {
+            WorkspaceRole::Editor
+        };
+        user.associate_workspace_with_role(ctx, *workspace.pk(), role)
+            .await?;
+    }
 
-    Ok(Json(AuthConnectResponse {
-        user,
-        workspace,
-        token: res_body.token,
-    }))
+    Ok((user, workspace))
 }
@@ -41,7 +41,7 @@ class SocketDefinitionBuilder implements ISocketDefinitionBuilder {
     setUiHidden(hidden: boolean): this;
     setValueFrom(valueFrom: ValueFrom): this;
 }
-type ValidationKind = "customValidation" | "integerIsBetweenTwoIntegers" | "integerIsNotEmpty" | "stringEquals" | "stringHasPrefix" | "stringInStringArray" | "stringIsHexColor" | "stringIsNotEmpty" | "stringIsValidIpAddr";
+type ValidationKind = "arrayItemsAreUnique" | "arrayLengthIsBetween" | "customValidation" | "floatIsBetweenTwoFloats" | "floatIsNotEmpty" | "integerIsBetweenTwoIntegers" | "integerIsNotEmpty" | "stringEquals" | "stringHasPrefix" | "stringInStringArray" | "stringIsArn" | "stringIsHexColor" | "stringIsHostname" | "stringIsNotEmpty" | "stringIsValidCidr" | "stringIsValidIpAddr" | "stringIsValidUrl" | "stringLengthIsBetween" | "stringMatchesRegex";
 interface Validation {
     type: ValidationKind;
     funcUniqueId?: Record<string, unknown>;
@@ -49,6 +49,7 @@ interface Validation {
     upperBound?: number;
     expected?: string[];
     displayExpected?: boolean;
+    pattern?: string;
 }
 interface IValidationBuilder {
     setKind(kind: ValidationKind): this;
@@ -57,6 +58,7 @@ interface IValidationBuilder {
     setUpperBound(value: number): this;
     addExpected(expected: string): this;
     setDisplayExpected(display: boolean): this;
+    setPattern(pattern: string): this;
     build(): Validation;
 }
 class ValidationBuilder implements IValidationBuilder {
@@ -68,6 +70,7 @@ class ValidationBuilder implements IValidationBuilder {
     addExpected(expected: string): this;
     setLowerBound(value: number): this;
     setKind(type: ValidationKind): this;
+    setPattern(pattern: string): this;
     setUpperBound(value: number): this;
 }
 type PropWidgetDefinitionKind = "array" | "checkbox" | "color" | "comboBox" | "header" | "map" | "secretSelect" | "select" | "text" | "textArea";
@@ -124,7 +127,7 @@ class SiPropValueFromDefinitionBuilder implements ISiPropValueFromDefinitionBuil
     setKind(kind: SiPropValueFromDefinitionKind): this;
     setValueFrom(valueFrom: ValueFrom): this;
 }
-type PropDefinitionKind = "array" | "boolean" | "integer" | "map" | "object" | "string";
+type PropDefinitionKind = "array" | "boolean" | "float" | "integer" | "map" | "object" | "string";
 interface PropDefinition {
     name: string;
     kind: PropDefinitionKind;
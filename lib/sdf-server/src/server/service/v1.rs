@@ -0,0 +1,174 @@
+//! The headless API, a stable and versioned set of routes for driving a workspace from scripts
+//! and pipelines. Routes are authenticated by an [`ApiToken`](dal::ApiToken) rather than a
+//! session, and are grouped the same way as the rest of the API so that workspace roles apply to
+//! them alike.
+//!
+//! Components are addressed by schema name and their properties by prop path (e.g.
+//! "/root/domain/region"), so callers don't need to know any ids up front. Everything but
+//! creating, reading and applying a change set must happen in one.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use dal::prop::PropPath;
+use dal::socket::{SocketError, SocketId};
+use dal::{
+    AttributeReadContext, AttributeValueError, ChangeSetError, ChangeSetPk, Component,
+    ComponentError, ComponentId, DalContext, DiagramError, ExternalProviderError, NodeError,
+    PropError, SchemaError, StandardModelError, TransactionsError, WsEventError,
+};
+use hyper::StatusCode;
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod apply_change_set;
+pub mod connect_sockets;
+pub mod create_change_set;
+pub mod create_component;
+pub mod get_change_set;
+pub mod list_qualifications;
+pub mod run_qualifications;
+pub mod update_component;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum V1Error {
+    #[error(transparent)]
+    AttributeValue(#[from] AttributeValueError),
+    #[error("attribute value not found for context: {0:?}")]
+    AttributeValueNotFoundForContext(AttributeReadContext),
+    #[error(transparent)]
+    ChangeSet(#[from] ChangeSetError),
+    #[error("change set not found: {0}")]
+    ChangeSetNotFound(ChangeSetPk),
+    #[error("changes must be made in a change set")]
+    ChangeSetRequired,
+    #[error(transparent)]
+    Component(#[from] ComponentError),
+    #[error("component not found: {0}")]
+    ComponentNotFound(ComponentId),
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+    #[error(transparent)]
+    Diagram(#[from] DiagramError),
+    #[error(transparent)]
+    ExternalProvider(#[from] ExternalProviderError),
+    #[error("external provider not found for socket: {0}")]
+    ExternalProviderNotFoundForSocket(SocketId),
+    #[error(transparent)]
+    Node(#[from] NodeError),
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+    #[error("schema has no default variant: {0}")]
+    SchemaVariantNotFound(String),
+    #[error(transparent)]
+    Socket(#[from] SocketError),
+    #[error("socket {0} not found on component {1}")]
+    SocketNotFound(String, ComponentId),
+    #[error(transparent)]
+    StandardModel(#[from] StandardModelError),
+    #[error(transparent)]
+    WsEvent(#[from] WsEventError),
+}
+
+pub type V1Result<T> = std::result::Result<T, V1Error>;
+
+impl IntoResponse for V1Error {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            V1Error::ChangeSetNotFound(_)
+            | V1Error::ComponentNotFound(_)
+            | V1Error::Schema(SchemaError::NotFoundByName(_))
+            | V1Error::SchemaVariantNotFound(_)
+            | V1Error::SocketNotFound(_, _) => (StatusCode::NOT_FOUND, self.to_string()),
+            V1Error::ChangeSetRequired
+            | V1Error::Component(
+                ComponentError::PropNotSettableByPath(_)
+                | ComponentError::Prop(PropError::NotFoundAtPath(_, _)),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            V1Error::ChangeSet(
+                ChangeSetError::Conflicts(_)
+                | ChangeSetError::NotApproved { .. }
+                | ChangeSetError::NotAwaitingApproval(_)
+                | ChangeSetError::NotOpen(_),
+            ) => (StatusCode::CONFLICT, self.to_string()),
+            V1Error::ChangeSet(
+                ChangeSetError::NoUserActor
+                | ChangeSetError::NotAReviewer(_)
+                | ChangeSetError::SelfReview(_),
+            ) => (StatusCode::FORBIDDEN, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/change_set/apply_change_set",
+            post(apply_change_set::apply_change_set),
+        )
+        .route(
+            "/change_set/create_change_set",
+            post(create_change_set::create_change_set),
+        )
+        .route(
+            "/change_set/get_change_set",
+            get(get_change_set::get_change_set),
+        )
+        .route(
+            "/component/connect_sockets",
+            post(connect_sockets::connect_sockets),
+        )
+        .route(
+            "/component/create_component",
+            post(create_component::create_component),
+        )
+        .route(
+            "/component/list_qualifications",
+            get(list_qualifications::list_qualifications),
+        )
+        .route(
+            "/component/run_qualifications",
+            post(run_qualifications::run_qualifications),
+        )
+        .route(
+            "/component/update_component",
+            post(update_component::update_component),
+        )
+}
+
+/// Errors unless the request is for a change set, rather than head.
+fn ensure_change_set(ctx: &DalContext) -> V1Result<()> {
+    if ctx.visibility().is_head() {
+        return Err(V1Error::ChangeSetRequired);
+    }
+    Ok(())
+}
+
+/// Sets each of the component's properties, keyed by prop path (e.g. "/root/domain/region").
+/// A `null` value unsets the property.
+async fn set_properties(
+    ctx: &DalContext,
+    component: &Component,
+    properties: &BTreeMap<String, Option<serde_json::Value>>,
+) -> V1Result<()> {
+    for (path, value) in properties {
+        let path = PropPath::new(path.trim_start_matches('/').split('/'));
+        component
+            .set_value_by_prop_path(ctx, &path, value.clone())
+            .await?;
+    }
+    Ok(())
+}
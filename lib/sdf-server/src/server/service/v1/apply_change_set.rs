@@ -0,0 +1,40 @@
+use axum::Json;
+use dal::{ChangeSet, ChangeSetConflictResolution, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+use super::{V1Error, V1Result};
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+    /// Per-conflict choices for any objects that both the change set and head have modified.
+    #[serde(default)]
+    pub resolutions: Vec<ChangeSetConflictResolution>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyChangeSetResponse {
+    pub change_set: ChangeSet,
+}
+
+pub async fn apply_change_set(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Json(request): Json<ApplyChangeSetRequest>,
+) -> V1Result<Json<ApplyChangeSetResponse>> {
+    let mut ctx = builder.build_head(access_builder).await?;
+
+    let mut change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(V1Error::ChangeSetNotFound(request.change_set_pk))?;
+    change_set
+        .apply_with_resolutions(&mut ctx, true, &request.resolutions)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(ApplyChangeSetResponse { change_set }))
+}
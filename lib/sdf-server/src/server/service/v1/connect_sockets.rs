@@ -0,0 +1,118 @@
+use axum::Json;
+use dal::edge::EdgeKind;
+use dal::socket::SocketEdgeKind;
+use dal::{
+    job::definition::DependentValuesUpdate, AttributeReadContext, AttributeValue, Component,
+    ComponentId, Connection, ExternalProvider, Node, Socket, StandardModel, Visibility, WsEvent,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_change_set, V1Error, V1Result};
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectSocketsRequest {
+    pub from_component_id: ComponentId,
+    /// The name of an output socket on the "from" component.
+    pub from_socket_name: String,
+    pub to_component_id: ComponentId,
+    /// The name of an input socket on the "to" component.
+    pub to_socket_name: String,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectSocketsResponse {
+    pub connection: Connection,
+}
+
+pub async fn connect_sockets(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Json(request): Json<ConnectSocketsRequest>,
+) -> V1Result<Json<ConnectSocketsResponse>> {
+    let ctx = builder
+        .build(access_builder.build(request.visibility))
+        .await?;
+    ensure_change_set(&ctx)?;
+
+    let (from_node, from_socket) = find_node_and_socket(
+        &ctx,
+        request.from_component_id,
+        &request.from_socket_name,
+        SocketEdgeKind::ConfigurationOutput,
+    )
+    .await?;
+    let (to_node, to_socket) = find_node_and_socket(
+        &ctx,
+        request.to_component_id,
+        &request.to_socket_name,
+        SocketEdgeKind::ConfigurationInput,
+    )
+    .await?;
+
+    let connection = Connection::new(
+        &ctx,
+        *from_node.id(),
+        *from_socket.id(),
+        *to_node.id(),
+        *to_socket.id(),
+        EdgeKind::Configuration,
+    )
+    .await?;
+
+    let from_socket_external_provider = ExternalProvider::find_for_socket(&ctx, *from_socket.id())
+        .await?
+        .ok_or(V1Error::ExternalProviderNotFoundForSocket(
+            *from_socket.id(),
+        ))?;
+    let attribute_value_context = AttributeReadContext {
+        external_provider_id: Some(*from_socket_external_provider.id()),
+        component_id: Some(request.from_component_id),
+        ..Default::default()
+    };
+    let attribute_value = AttributeValue::find_for_context(&ctx, attribute_value_context)
+        .await?
+        .ok_or(V1Error::AttributeValueNotFoundForContext(
+            attribute_value_context,
+        ))?;
+
+    ctx.enqueue_job(DependentValuesUpdate::new(
+        ctx.access_builder(),
+        *ctx.visibility(),
+        vec![*attribute_value.id()],
+    ))
+    .await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(ConnectSocketsResponse { connection }))
+}
+
+async fn find_node_and_socket(
+    ctx: &dal::DalContext,
+    component_id: ComponentId,
+    socket_name: &str,
+    socket_edge_kind: SocketEdgeKind,
+) -> V1Result<(Node, Socket)> {
+    let node = Component::get_by_id(ctx, &component_id)
+        .await?
+        .ok_or(V1Error::ComponentNotFound(component_id))?
+        .node(ctx)
+        .await?
+        .pop()
+        .ok_or(V1Error::ComponentNotFound(component_id))?;
+    let socket =
+        Socket::find_by_name_for_edge_kind_and_node(ctx, socket_name, socket_edge_kind, *node.id())
+            .await?
+            .ok_or_else(|| V1Error::SocketNotFound(socket_name.to_owned(), component_id))?;
+    Ok((node, socket))
+}
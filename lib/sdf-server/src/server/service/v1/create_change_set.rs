@@ -0,0 +1,37 @@
+use axum::Json;
+use dal::{ChangeSet, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::V1Result;
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateChangeSetRequest {
+    pub change_set_name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateChangeSetResponse {
+    pub change_set: ChangeSet,
+}
+
+pub async fn create_change_set(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Json(request): Json<CreateChangeSetRequest>,
+) -> V1Result<Json<CreateChangeSetResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = ChangeSet::new(&ctx, &request.change_set_name, None).await?;
+
+    WsEvent::change_set_created(&ctx, change_set.pk)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateChangeSetResponse { change_set }))
+}
@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+
+use axum::Json;
+use dal::node::NodeId;
+use dal::{Component, ComponentId, Schema, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_change_set, set_properties, V1Error, V1Result};
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateComponentRequest {
+    pub schema_name: String,
+    pub name: String,
+    /// Values to set, keyed by prop path (e.g. "/root/domain/region").
+    #[serde(default)]
+    pub properties: BTreeMap<String, Option<serde_json::Value>>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateComponentResponse {
+    pub component_id: ComponentId,
+    pub node_id: NodeId,
+}
+
+pub async fn create_component(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Json(request): Json<CreateComponentRequest>,
+) -> V1Result<Json<CreateComponentResponse>> {
+    let ctx = builder
+        .build(access_builder.build(request.visibility))
+        .await?;
+    ensure_change_set(&ctx)?;
+
+    let schema = Schema::find_by_name(&ctx, &request.schema_name).await?;
+    let schema_variant_id = schema
+        .default_schema_variant_id()
+        .ok_or_else(|| V1Error::SchemaVariantNotFound(request.schema_name.clone()))?;
+
+    let (component, mut node) = Component::new(&ctx, &request.name, *schema_variant_id).await?;
+    node.set_geometry(&ctx, "0", "0", Some("500"), Some("500"))
+        .await?;
+    set_properties(&ctx, &component, &request.properties).await?;
+
+    WsEvent::component_created(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateComponentResponse {
+        component_id: *component.id(),
+        node_id: *node.id(),
+    }))
+}
@@ -0,0 +1,33 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{ChangeSet, ChangeSetPk};
+use serde::{Deserialize, Serialize};
+
+use super::{V1Error, V1Result};
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChangeSetRequest {
+    pub change_set_pk: ChangeSetPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GetChangeSetResponse {
+    pub change_set: ChangeSet,
+}
+
+pub async fn get_change_set(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Query(request): Query<GetChangeSetRequest>,
+) -> V1Result<Json<GetChangeSetResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let change_set = ChangeSet::get_by_pk(&ctx, &request.change_set_pk)
+        .await?
+        .ok_or(V1Error::ChangeSetNotFound(request.change_set_pk))?;
+
+    Ok(Json(GetChangeSetResponse { change_set }))
+}
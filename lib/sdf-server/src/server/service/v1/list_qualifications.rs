@@ -0,0 +1,41 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{qualification::QualificationView, Component, ComponentId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{V1Error, V1Result};
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQualificationsRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQualificationsResponse {
+    pub qualifications: Vec<QualificationView>,
+}
+
+pub async fn list_qualifications(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Query(request): Query<ListQualificationsRequest>,
+) -> V1Result<Json<ListQualificationsResponse>> {
+    let ctx = builder
+        .build(access_builder.build(request.visibility))
+        .await?;
+
+    if Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .is_none()
+    {
+        return Err(V1Error::ComponentNotFound(request.component_id));
+    }
+    let qualifications = Component::list_qualifications(&ctx, request.component_id).await?;
+
+    Ok(Json(ListQualificationsResponse { qualifications }))
+}
@@ -0,0 +1,41 @@
+use axum::Json;
+use dal::{Component, ComponentId, StandardModel, Visibility};
+use serde::{Deserialize, Serialize};
+
+use super::{V1Error, V1Result};
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RunQualificationsRequest {
+    pub component_id: ComponentId,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RunQualificationsResponse {
+    pub success: bool,
+}
+
+/// Queues the component's qualifications to run again. Their results are available from
+/// [`list_qualifications`](super::list_qualifications::list_qualifications) once they finish.
+pub async fn run_qualifications(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Json(request): Json<RunQualificationsRequest>,
+) -> V1Result<Json<RunQualificationsResponse>> {
+    let ctx = builder
+        .build(access_builder.build(request.visibility))
+        .await?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(V1Error::ComponentNotFound(request.component_id))?;
+    component.run_qualifications(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(RunQualificationsResponse { success: true }))
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+use axum::Json;
+use dal::{Component, ComponentId, StandardModel, Visibility, WsEvent};
+use serde::{Deserialize, Serialize};
+
+use super::{ensure_change_set, set_properties, V1Error, V1Result};
+use crate::server::extract::{ApiTokenAccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateComponentRequest {
+    pub component_id: ComponentId,
+    /// Values to set, keyed by prop path (e.g. "/root/domain/region").
+    pub properties: BTreeMap<String, Option<serde_json::Value>>,
+    #[serde(flatten)]
+    pub visibility: Visibility,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateComponentResponse {
+    pub success: bool,
+}
+
+pub async fn update_component(
+    HandlerContext(builder): HandlerContext,
+    ApiTokenAccessBuilder(access_builder): ApiTokenAccessBuilder,
+    Json(request): Json<UpdateComponentRequest>,
+) -> V1Result<Json<UpdateComponentResponse>> {
+    let ctx = builder
+        .build(access_builder.build(request.visibility))
+        .await?;
+    ensure_change_set(&ctx)?;
+
+    let component = Component::get_by_id(&ctx, &request.component_id)
+        .await?
+        .ok_or(V1Error::ComponentNotFound(request.component_id))?;
+    set_properties(&ctx, &component, &request.properties).await?;
+
+    WsEvent::change_set_written(&ctx)
+        .await?
+        .publish_on_commit(&ctx)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(UpdateComponentResponse { success: true }))
+}
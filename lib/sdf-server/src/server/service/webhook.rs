@@ -0,0 +1,62 @@
+use axum::{
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use dal::{TransactionsError, WebhookError as DalWebhookError, WebhookPk};
+use hyper::StatusCode;
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod create_webhook;
+pub mod delete_webhook;
+pub mod list_deliveries;
+pub mod list_webhooks;
+pub mod set_webhook_enabled;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WebhookError {
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+    #[error(transparent)]
+    Webhook(#[from] DalWebhookError),
+    #[error("webhook not found: {0}")]
+    WebhookNotFound(WebhookPk),
+}
+
+pub type WebhookResult<T> = std::result::Result<T, WebhookError>;
+
+impl IntoResponse for WebhookError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            WebhookError::WebhookNotFound(_) => (StatusCode::NOT_FOUND, self.to_string()),
+            WebhookError::Webhook(
+                DalWebhookError::InvalidUrl(_)
+                | DalWebhookError::NonPublicAddress(_)
+                | DalWebhookError::NoEventKinds
+                | DalWebhookError::UnknownEventKind(_),
+            ) => (StatusCode::BAD_REQUEST, self.to_string()),
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route("/create_webhook", post(create_webhook::create_webhook))
+        .route("/delete_webhook", post(delete_webhook::delete_webhook))
+        .route("/list_deliveries", get(list_deliveries::list_deliveries))
+        .route("/list_webhooks", get(list_webhooks::list_webhooks))
+        .route(
+            "/set_webhook_enabled",
+            post(set_webhook_enabled::set_webhook_enabled),
+        )
+}
@@ -0,0 +1,39 @@
+use axum::Json;
+use dal::Webhook;
+use serde::{Deserialize, Serialize};
+
+use super::WebhookResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// The kinds of event to deliver, such as `"ChangeSetApplied"`.
+    pub event_kinds: Vec<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWebhookResponse {
+    pub webhook: Webhook,
+    /// The secret deliveries are signed with. This is the only time it is handed out.
+    pub secret: String,
+}
+
+pub async fn create_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<CreateWebhookRequest>,
+) -> WebhookResult<Json<CreateWebhookResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let webhook =
+        Webhook::new(&ctx, &request.url, request.event_kinds, request.description).await?;
+    let secret = webhook.secret().to_owned();
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateWebhookResponse { webhook, secret }))
+}
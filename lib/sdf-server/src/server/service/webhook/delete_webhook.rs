@@ -0,0 +1,35 @@
+use axum::Json;
+use dal::{Webhook, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WebhookError, WebhookResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWebhookRequest {
+    pub webhook_pk: WebhookPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteWebhookResponse {
+    pub success: bool,
+}
+
+pub async fn delete_webhook(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<DeleteWebhookRequest>,
+) -> WebhookResult<Json<DeleteWebhookResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let webhook = Webhook::get_by_pk(&ctx, request.webhook_pk)
+        .await?
+        .ok_or(WebhookError::WebhookNotFound(request.webhook_pk))?;
+    webhook.delete(&ctx).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(DeleteWebhookResponse { success: true }))
+}
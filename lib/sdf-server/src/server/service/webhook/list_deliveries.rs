@@ -0,0 +1,40 @@
+use axum::extract::Query;
+use axum::Json;
+use dal::{Webhook, WebhookDelivery, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WebhookError, WebhookResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+const DEFAULT_LIMIT: i64 = 50;
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeliveriesRequest {
+    pub webhook_pk: WebhookPk,
+    pub limit: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDeliveriesResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+}
+
+/// List the most recent delivery attempts for a webhook, newest first.
+pub async fn list_deliveries(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Query(request): Query<ListDeliveriesRequest>,
+) -> WebhookResult<Json<ListDeliveriesResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let webhook = Webhook::get_by_pk(&ctx, request.webhook_pk)
+        .await?
+        .ok_or(WebhookError::WebhookNotFound(request.webhook_pk))?;
+    let deliveries = webhook
+        .deliveries(&ctx, request.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, 500))
+        .await?;
+
+    Ok(Json(ListDeliveriesResponse { deliveries }))
+}
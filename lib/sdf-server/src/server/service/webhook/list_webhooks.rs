@@ -0,0 +1,23 @@
+use axum::Json;
+use dal::Webhook;
+use serde::{Deserialize, Serialize};
+
+use super::WebhookResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListWebhooksResponse {
+    pub webhooks: Vec<Webhook>,
+}
+
+pub async fn list_webhooks(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> WebhookResult<Json<ListWebhooksResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let webhooks = Webhook::list(&ctx).await?;
+
+    Ok(Json(ListWebhooksResponse { webhooks }))
+}
@@ -0,0 +1,36 @@
+use axum::Json;
+use dal::{Webhook, WebhookPk};
+use serde::{Deserialize, Serialize};
+
+use super::{WebhookError, WebhookResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWebhookEnabledRequest {
+    pub webhook_pk: WebhookPk,
+    pub enabled: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetWebhookEnabledResponse {
+    pub webhook: Webhook,
+}
+
+pub async fn set_webhook_enabled(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<SetWebhookEnabledRequest>,
+) -> WebhookResult<Json<SetWebhookEnabledResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let mut webhook = Webhook::get_by_pk(&ctx, request.webhook_pk)
+        .await?
+        .ok_or(WebhookError::WebhookNotFound(request.webhook_pk))?;
+    webhook.set_enabled(&ctx, request.enabled).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(SetWebhookEnabledResponse { webhook }))
+}
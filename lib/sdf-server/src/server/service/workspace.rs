@@ -0,0 +1,67 @@
+use axum::{
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use dal::{ApiTokenError, TransactionsError, UserError, UserPk};
+use hyper::StatusCode;
+use thiserror::Error;
+
+use crate::server::state::AppState;
+
+pub mod create_api_token;
+pub mod list_api_tokens;
+pub mod list_members;
+pub mod revoke_api_token;
+pub mod set_member_role;
+
+#[remain::sorted]
+#[derive(Error, Debug)]
+pub enum WorkspaceError {
+    #[error(transparent)]
+    ApiToken(#[from] ApiTokenError),
+    #[error(transparent)]
+    ContextTransaction(#[from] TransactionsError),
+    #[error("no workspace in tenancy")]
+    NoWorkspaceInTenancy,
+    #[error(transparent)]
+    User(#[from] UserError),
+    #[error("user not found: {0}")]
+    UserNotFound(UserPk),
+}
+
+pub type WorkspaceResult<T> = std::result::Result<T, WorkspaceError>;
+
+impl IntoResponse for WorkspaceError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = match self {
+            WorkspaceError::ApiToken(ApiTokenError::NotFound(_))
+            | WorkspaceError::UserNotFound(_)
+            | WorkspaceError::User(UserError::NotAWorkspaceMember(_, _)) => {
+                (StatusCode::NOT_FOUND, self.to_string())
+            }
+            _ => (StatusCode::INTERNAL_SERVER_ERROR, self.to_string()),
+        };
+
+        let body = Json(
+            serde_json::json!({ "error": { "message": error_message, "code": 42, "statusCode": status.as_u16() } }),
+        );
+
+        (status, body).into_response()
+    }
+}
+
+pub fn routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/create_api_token",
+            post(create_api_token::create_api_token),
+        )
+        .route("/list_api_tokens", get(list_api_tokens::list_api_tokens))
+        .route("/list_members", get(list_members::list_members))
+        .route(
+            "/revoke_api_token",
+            post(revoke_api_token::revoke_api_token),
+        )
+        .route("/set_member_role", post(set_member_role::set_member_role))
+}
@@ -0,0 +1,35 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::ApiToken;
+
+use super::WorkspaceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiTokenRequest {
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateApiTokenResponse {
+    pub api_token: ApiToken,
+    /// The token itself, which is only ever returned here.
+    pub token: String,
+}
+
+pub async fn create_api_token(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<CreateApiTokenRequest>,
+) -> WorkspaceResult<Json<CreateApiTokenResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let (api_token, token) = ApiToken::new(&ctx, &request.name).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(CreateApiTokenResponse { api_token, token }))
+}
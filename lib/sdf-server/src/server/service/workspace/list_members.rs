@@ -0,0 +1,46 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::{User, UserPk, WorkspaceRole};
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceMember {
+    pub user_pk: UserPk,
+    pub name: String,
+    pub email: String,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMembersResponse {
+    pub members: Vec<WorkspaceMember>,
+}
+
+pub async fn list_members(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+) -> WorkspaceResult<Json<ListMembersResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .ok_or(WorkspaceError::NoWorkspaceInTenancy)?;
+    let members = User::list_workspace_members(&ctx, workspace_pk)
+        .await?
+        .into_iter()
+        .map(|(user, role)| WorkspaceMember {
+            user_pk: user.pk(),
+            name: user.name().to_owned(),
+            email: user.email().to_owned(),
+            role,
+        })
+        .collect();
+
+    Ok(Json(ListMembersResponse { members }))
+}
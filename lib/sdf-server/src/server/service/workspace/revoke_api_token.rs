@@ -0,0 +1,33 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::{ApiToken, ApiTokenPk};
+
+use super::WorkspaceResult;
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeApiTokenRequest {
+    pub pk: ApiTokenPk,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevokeApiTokenResponse {
+    pub api_token: ApiToken,
+}
+
+pub async fn revoke_api_token(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<RevokeApiTokenRequest>,
+) -> WorkspaceResult<Json<RevokeApiTokenResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let api_token = ApiToken::revoke(&ctx, request.pk).await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(RevokeApiTokenResponse { api_token }))
+}
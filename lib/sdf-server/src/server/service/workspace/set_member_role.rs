@@ -0,0 +1,42 @@
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use dal::{User, UserPk, WorkspaceRole};
+
+use super::{WorkspaceError, WorkspaceResult};
+use crate::server::extract::{AccessBuilder, HandlerContext};
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMemberRoleRequest {
+    pub user_pk: UserPk,
+    pub role: WorkspaceRole,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMemberRoleResponse {
+    pub success: bool,
+}
+
+pub async fn set_member_role(
+    HandlerContext(builder): HandlerContext,
+    AccessBuilder(access_builder): AccessBuilder,
+    Json(request): Json<SetMemberRoleRequest>,
+) -> WorkspaceResult<Json<SetMemberRoleResponse>> {
+    let ctx = builder.build_head(access_builder).await?;
+
+    let workspace_pk = ctx
+        .tenancy()
+        .workspace_pk()
+        .ok_or(WorkspaceError::NoWorkspaceInTenancy)?;
+    let user = User::get_by_pk(&ctx, request.user_pk)
+        .await?
+        .ok_or(WorkspaceError::UserNotFound(request.user_pk))?;
+    user.set_workspace_role(&ctx, workspace_pk, request.role)
+        .await?;
+
+    ctx.commit().await?;
+
+    Ok(Json(SetMemberRoleResponse { success: true }))
+}
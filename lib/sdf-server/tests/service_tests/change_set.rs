@@ -1,7 +1,11 @@
-use axum::{http::Method, Router};
+use axum::{
+    http::{Method, StatusCode},
+    Router,
+};
+use dal::{User, UserClaim, UserPk, WorkspaceRole, WorkspaceSignup};
 use dal_test::{
-    sdf_test, test_harness::create_change_set as dal_create_change_set, AuthTokenRef,
-    DalContextHead,
+    helpers::create_auth_token, sdf_test, test_harness::create_change_set as dal_create_change_set,
+    AuthTokenRef, DalContextHead,
 };
 use sdf_server::service::change_set::{
     apply_change_set::{ApplyChangeSetRequest, ApplyChangeSetResponse},
@@ -11,7 +15,8 @@ use sdf_server::service::change_set::{
 };
 
 use crate::service_tests::{
-    api_request_auth_empty, api_request_auth_json_body, api_request_auth_query,
+    api_request_auth_empty, api_request_auth_json_body, api_request_auth_json_body_status,
+    api_request_auth_query,
 };
 
 #[sdf_test]
@@ -76,6 +81,7 @@ async fn apply_change_set(
     ctx.commit().await.expect("cannot commit txn");
     let request = ApplyChangeSetRequest {
         change_set_pk: change_set.pk,
+        resolutions: Vec::new(),
     };
 
     let _response: ApplyChangeSetResponse = api_request_auth_json_body(
@@ -87,3 +93,67 @@ async fn apply_change_set(
     )
     .await;
 }
+
+#[sdf_test]
+async fn roles_without_permission_are_forbidden(
+    DalContextHead(ctx): DalContextHead,
+    app: Router,
+    nw: WorkspaceSignup,
+) {
+    let change_set = dal_create_change_set(&ctx).await;
+    let mut tokens = Vec::new();
+    for role in [WorkspaceRole::Viewer, WorkspaceRole::Editor] {
+        let user = User::new(
+            &ctx,
+            UserPk::generate(),
+            format!("{role} user"),
+            format!("{role}@example.com"),
+            None::<&str>,
+        )
+        .await
+        .expect("cannot create user");
+        user.associate_workspace_with_role(&ctx, *nw.workspace.pk(), role)
+            .await
+            .expect("cannot add user to workspace");
+        tokens.push(create_auth_token(UserClaim::new(user.pk(), *nw.workspace.pk())).await);
+    }
+    ctx.commit().await.expect("cannot commit txn");
+    let (viewer_token, editor_token) = (&tokens[0], &tokens[1]);
+
+    // Viewers can't write
+    let (status, _) = api_request_auth_json_body_status(
+        app.clone(),
+        Method::POST,
+        "/api/change_set/create_change_set",
+        viewer_token,
+        &CreateChangeSetRequest {
+            change_set_name: "mastodon".to_string(),
+        },
+    )
+    .await;
+    assert_eq!(StatusCode::FORBIDDEN, status);
+
+    // Editors can write, but can't apply
+    let _response: CreateChangeSetResponse = api_request_auth_json_body(
+        app.clone(),
+        Method::POST,
+        "/api/change_set/create_change_set",
+        editor_token,
+        &CreateChangeSetRequest {
+            change_set_name: "mastodon".to_string(),
+        },
+    )
+    .await;
+    let (status, _) = api_request_auth_json_body_status(
+        app,
+        Method::POST,
+        "/api/change_set/apply_change_set",
+        editor_token,
+        &ApplyChangeSetRequest {
+            change_set_pk: change_set.pk,
+            resolutions: Vec::new(),
+        },
+    )
+    .await;
+    assert_eq!(StatusCode::FORBIDDEN, status);
+}
@@ -1,7 +1,8 @@
 use axum::Router;
 use dal::{
-    EncryptedSecret, SecretAlgorithm, SecretKind, SecretObjectType, SecretVersion, StandardModel,
-    Visibility, WorkspaceSignup,
+    secret::{backend::EnvironmentVariableSecretBackendConfig, SecretBackendsConfig},
+    EncryptedSecret, SecretAlgorithm, SecretBackendKind, SecretKind, SecretObjectType,
+    SecretVersion, StandardModel, Visibility, WorkspaceSignup,
 };
 use dal_test::{sdf_test, test_harness::encrypt_message, AuthTokenRef, DalContextHead};
 use hyper::Method;
@@ -29,6 +30,7 @@ async fn create_secret(
         key_pair_pk: nw.key_pair.pk(),
         version: SecretVersion::V1,
         algorithm: SecretAlgorithm::Sealedbox,
+        backend: SecretBackendKind::Database,
         visibility,
     };
 
@@ -62,3 +64,67 @@ async fn create_secret(
         serde_json::to_value(&decrypted_secret).expect("failed to serial decrypted into Value");
     assert_eq!(decrypted_value["message"], message);
 }
+
+#[sdf_test]
+async fn create_secret_in_external_backend(
+    DalContextHead(ctx): DalContextHead,
+    AuthTokenRef(auth_token): AuthTokenRef<'_>,
+    nw: WorkspaceSignup,
+) {
+    // The test harness has no external backends, so serve sdf the way it's configured with one
+    let secret_backends = SecretBackendsConfig {
+        environment_variable: Some(EnvironmentVariableSecretBackendConfig {
+            prefix: "SI_TEST_SECRET_".to_string(),
+        }),
+        ..Default::default()
+    }
+    .build()
+    .expect("failed to build secret backends");
+    let (posthog_client, posthog_sender) = si_posthog::new()
+        .api_endpoint("http://localhost:9999")
+        .api_key("not-a-key")
+        .enabled(false)
+        .build()
+        .expect("failed to create posthog client and sender");
+    drop(tokio::spawn(posthog_sender.run()));
+    let (app, _, _) = sdf_server::build_service_for_tests(
+        ctx.services_context().with_secret_backends(secret_backends),
+        dal_test::jwt_public_signing_key()
+            .await
+            .expect("failed to load jwt public signing key"),
+        "sign-me-up".into(),
+        posthog_client,
+    )
+    .expect("failed to build sdf router");
+
+    let reference = serde_json::json!({
+        "path": format!("SI_TEST_SECRET_{}_DOCKER_HUB", nw.workspace.pk()),
+    });
+    let crypted = encrypt_message(&ctx, nw.key_pair.pk(), &reference).await;
+
+    let request = CreateSecretRequest {
+        name: "electric-avenue".to_string(),
+        object_type: SecretObjectType::Credential,
+        kind: SecretKind::DockerHub,
+        crypted,
+        key_pair_pk: nw.key_pair.pk(),
+        version: SecretVersion::V1,
+        algorithm: SecretAlgorithm::Sealedbox,
+        backend: SecretBackendKind::EnvironmentVariable,
+        visibility: Visibility::new_head(false),
+    };
+
+    let response: CreateSecretResponse = api_request_auth_json_body(
+        app,
+        Method::POST,
+        "/api/secret/create_secret",
+        auth_token,
+        &request,
+    )
+    .await;
+    assert_eq!(response.secret.name(), "electric-avenue");
+    assert_eq!(
+        response.secret.backend(),
+        &SecretBackendKind::EnvironmentVariable
+    );
+}
@@ -1,8 +1,12 @@
 use axum::{http::Method, Router};
-use dal::WorkspaceSignup;
+use dal::{User, UserPk, WorkspacePk, WorkspaceRole, WorkspaceSignup};
 use dal_test::{sdf_test, AuthTokenRef, DalContextHead};
 use sdf_server::service::session::{
-    load_workspace::LoadWorkspaceResponse, restore_authentication::RestoreAuthenticationResponse,
+    auth_connect::{
+        connect_user_and_workspace, AuthApiConnectResponse, AuthApiUser, AuthApiWorkspace,
+    },
+    load_workspace::LoadWorkspaceResponse,
+    restore_authentication::RestoreAuthenticationResponse,
 };
 
 use crate::service_tests::api_request_auth_empty;
@@ -52,3 +56,77 @@ async fn load_workspace(
         api_request_auth_empty(app, Method::GET, "/api/session/load_workspace", auth_token).await;
     assert_eq!(nw.workspace, response.workspace);
 }
+
+#[sdf_test]
+async fn auth_connect_grants_admin_to_workspace_creator(DalContextHead(mut ctx): DalContextHead) {
+    let workspace_pk = WorkspacePk::generate();
+    let creator_pk = UserPk::generate();
+    let member_pk = UserPk::generate();
+    let connect_response = |user_pk: UserPk| AuthApiConnectResponse {
+        user: AuthApiUser {
+            id: user_pk,
+            nickname: format!("user-{user_pk}"),
+            first_name: None,
+            last_name: None,
+            picture_url: None,
+            email: format!("{user_pk}@example.com"),
+        },
+        workspace: AuthApiWorkspace {
+            id: workspace_pk,
+            display_name: "existing infrastructure".to_owned(),
+            creator_user_id: creator_pk,
+            instance_url: "http://localhost:8080".to_owned(),
+            instance_env_type: "LOCAL".to_owned(),
+        },
+        token: "token".to_owned(),
+    };
+
+    // The first connect creates the workspace, whose creator has to be able to manage it
+    let (creator, workspace) = connect_user_and_workspace(&mut ctx, &connect_response(creator_pk))
+        .await
+        .expect("could not connect creator");
+    assert_eq!(workspace_pk, *workspace.pk());
+    assert_eq!(
+        Some(WorkspaceRole::Admin),
+        User::workspace_role(&ctx, creator.pk(), workspace_pk)
+            .await
+            .expect("could not get role")
+    );
+
+    // Anyone else joining the workspace is an editor
+    let (member, _) = connect_user_and_workspace(&mut ctx, &connect_response(member_pk))
+        .await
+        .expect("could not connect member");
+    assert_eq!(
+        Some(WorkspaceRole::Editor),
+        User::workspace_role(&ctx, member.pk(), workspace_pk)
+            .await
+            .expect("could not get role")
+    );
+
+    // Connecting again doesn't change anyone's role
+    connect_user_and_workspace(&mut ctx, &connect_response(creator_pk))
+        .await
+        .expect("could not reconnect creator");
+    assert_eq!(
+        Some(WorkspaceRole::Admin),
+        User::workspace_role(&ctx, creator.pk(), workspace_pk)
+            .await
+            .expect("could not get role")
+    );
+
+    // Nor does it undo a role given since
+    creator
+        .set_workspace_role(&ctx, workspace_pk, WorkspaceRole::Editor)
+        .await
+        .expect("could not set role");
+    connect_user_and_workspace(&mut ctx, &connect_response(creator_pk))
+        .await
+        .expect("could not reconnect creator");
+    assert_eq!(
+        Some(WorkspaceRole::Editor),
+        User::workspace_role(&ctx, creator.pk(), workspace_pk)
+            .await
+            .expect("could not get role")
+    );
+}
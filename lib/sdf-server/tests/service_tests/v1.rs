@@ -0,0 +1,74 @@
+use axum::{
+    http::{Method, StatusCode},
+    Router,
+};
+use dal::{ApiToken, HistoryActor, WorkspaceSignup};
+use dal_test::{sdf_test, DalContextHead};
+use sdf_server::service::v1::{
+    apply_change_set::ApplyChangeSetRequest,
+    create_change_set::{CreateChangeSetRequest, CreateChangeSetResponse},
+};
+
+use crate::service_tests::{api_request_auth_json_body, api_request_auth_json_body_status};
+
+#[sdf_test]
+async fn api_token_access(
+    DalContextHead(mut ctx): DalContextHead,
+    app: Router,
+    nw: WorkspaceSignup,
+) {
+    ctx.update_history_actor(HistoryActor::User(nw.user.pk()));
+    let (api_token, token) = ApiToken::new(&ctx, "pipeline")
+        .await
+        .expect("cannot create api token");
+    let mut workspace = nw.workspace.clone();
+    workspace
+        .set_required_change_set_approvals(&ctx, 1)
+        .await
+        .expect("cannot set approval policy");
+    ctx.commit().await.expect("cannot commit txn");
+
+    // The token acts as the user who created it
+    let response: CreateChangeSetResponse = api_request_auth_json_body(
+        app.clone(),
+        Method::POST,
+        "/api/v1/change_set/create_change_set",
+        &token,
+        &CreateChangeSetRequest {
+            change_set_name: "pipeline".to_string(),
+        },
+    )
+    .await;
+    assert_eq!("pipeline", response.change_set.name);
+
+    // Applying is refused until the change set has the approvals the workspace requires
+    let (status, body) = api_request_auth_json_body_status(
+        app.clone(),
+        Method::POST,
+        "/api/v1/change_set/apply_change_set",
+        &token,
+        &ApplyChangeSetRequest {
+            change_set_pk: response.change_set.pk,
+            resolutions: Vec::new(),
+        },
+    )
+    .await;
+    assert_eq!(StatusCode::CONFLICT, status, "{body}");
+
+    // Revoked tokens are turned away
+    ApiToken::revoke(&ctx, api_token.pk)
+        .await
+        .expect("cannot revoke api token");
+    ctx.commit().await.expect("cannot commit txn");
+    let (status, _) = api_request_auth_json_body_status(
+        app,
+        Method::POST,
+        "/api/v1/change_set/create_change_set",
+        &token,
+        &CreateChangeSetRequest {
+            change_set_name: "too late".to_string(),
+        },
+    )
+    .await;
+    assert_eq!(StatusCode::UNAUTHORIZED, status);
+}
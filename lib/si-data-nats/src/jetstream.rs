@@ -1,10 +1,429 @@
+use std::{fmt, sync::Arc};
+
+use telemetry::prelude::*;
+use tokio::task::spawn_blocking;
+
 pub use super::{Client, Message};
+use super::{ConnectionMetadata, Error, Result};
 
 // Re-export JetStream types. Since this is a private module, we'll have to name them from
 // `nats::jetstream` :(
 pub use nats::jetstream::{
     AccountInfo, AccountLimits, AckKind, AckPolicy, ApiStats, ClusterInfo, ConsumerConfig,
-    ConsumerInfo, DateTime, DeliverPolicy, DiscardPolicy, JetStreamMessageInfo, PurgeResponse,
-    ReplayPolicy, RetentionPolicy, SequencePair, StorageType, StreamConfig, StreamInfo,
-    StreamState,
+    ConsumerInfo, DateTime, DeliverPolicy, DiscardPolicy, JetStreamMessageInfo, PublishAck,
+    PurgeResponse, ReplayPolicy, RetentionPolicy, SequencePair, StorageType, StreamConfig,
+    StreamInfo, StreamState,
 };
+pub use nats::kv::Config as KeyValueConfig;
+
+/// A `JetStream` context which manages streams and consumers and publishes messages that are
+/// acknowledged by the server once they have been persisted.
+///
+/// Messages delivered by a push consumer are received with a plain [`Subscription`] on the
+/// consumer's deliver subject and acknowledged with [`Message::ack_kind`].
+///
+/// [`Subscription`]: super::Subscription
+#[derive(Clone, Debug)]
+pub struct JetStream {
+    inner: nats::jetstream::JetStream,
+    metadata: Arc<ConnectionMetadata>,
+}
+
+impl JetStream {
+    pub(crate) fn new(connection: nats::Connection, metadata: Arc<ConnectionMetadata>) -> Self {
+        Self {
+            inner: nats::jetstream::new(connection),
+            metadata,
+        }
+    }
+
+    /// Creates a stream, succeeding if an identical stream already exists.
+    #[instrument(
+        name = "jetstream.add_stream",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = %config.name,
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn add_stream(&self, config: StreamConfig) -> Result<StreamInfo> {
+        let span = Span::current();
+
+        let inner = self.inner.clone();
+        let info = spawn_blocking(move || inner.add_stream(config))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(info)
+    }
+
+    /// Updates the configuration of an existing stream.
+    #[instrument(
+        name = "jetstream.update_stream",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = %config.name,
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn update_stream(&self, config: StreamConfig) -> Result<StreamInfo> {
+        let span = Span::current();
+
+        let inner = self.inner.clone();
+        let info = spawn_blocking(move || inner.update_stream(&config))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(info)
+    }
+
+    /// Gets information about a stream, including its current [`StreamState`].
+    #[instrument(
+        name = "jetstream.stream_info",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = Empty,
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn stream_info(&self, stream: impl Into<String>) -> Result<StreamInfo> {
+        let span = Span::current();
+
+        let stream = stream.into();
+        span.record("messaging.destination", stream.as_str());
+        let inner = self.inner.clone();
+        let info = spawn_blocking(move || inner.stream_info(&stream))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(info)
+    }
+
+    /// Deletes a single message from a stream by its sequence number.
+    #[instrument(
+        name = "jetstream.delete_message",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = Empty,
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn delete_message(&self, stream: impl Into<String>, sequence: u64) -> Result<bool> {
+        let span = Span::current();
+
+        let stream = stream.into();
+        span.record("messaging.destination", stream.as_str());
+        let inner = self.inner.clone();
+        let deleted = spawn_blocking(move || inner.delete_message(&stream, sequence))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(deleted)
+    }
+
+    /// Creates a consumer on a stream, succeeding if an identical durable consumer already
+    /// exists.
+    #[instrument(
+        name = "jetstream.add_consumer",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = Empty,
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn add_consumer(
+        &self,
+        stream: impl Into<String>,
+        config: ConsumerConfig,
+    ) -> Result<ConsumerInfo> {
+        let span = Span::current();
+
+        let stream = stream.into();
+        span.record("messaging.destination", stream.as_str());
+        let inner = self.inner.clone();
+        let info = spawn_blocking(move || inner.add_consumer(&stream, config))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(info)
+    }
+
+    /// Deletes a consumer from a stream.
+    #[instrument(
+        name = "jetstream.delete_consumer",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = Empty,
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn delete_consumer(
+        &self,
+        stream: impl Into<String>,
+        consumer: impl Into<String>,
+    ) -> Result<()> {
+        let span = Span::current();
+
+        let stream = stream.into();
+        let consumer = consumer.into();
+        span.record("messaging.destination", stream.as_str());
+        let inner = self.inner.clone();
+        spawn_blocking(move || inner.delete_consumer(&stream, &consumer))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(())
+    }
+
+    /// Publishes a message to a subject captured by a stream, returning once the server has
+    /// persisted it.
+    #[instrument(
+        name = "jetstream.publish",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = Empty,
+            messaging.destination_kind = "topic",
+            messaging.operation = "send",
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Producer),
+            otel.name = Empty,
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn publish(
+        &self,
+        subject: impl Into<String>,
+        msg: impl Into<Vec<u8>>,
+    ) -> Result<PublishAck> {
+        let span = Span::current();
+
+        let subject = subject.into();
+        let msg = msg.into();
+        span.record("messaging.destination", subject.as_str());
+        span.record("otel.name", format!("{} send", &subject).as_str());
+        let inner = self.inner.clone();
+        let ack = spawn_blocking(move || inner.publish(&subject, msg))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(ack)
+    }
+
+    /// Creates a key-value bucket, or binds to it if it already exists.
+    #[instrument(
+        name = "jetstream.create_key_value",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.destination = %config.bucket,
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            messaging.url = %self.metadata.messaging_url,
+            net.transport = %self.metadata.net_transport,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn create_key_value(&self, config: KeyValueConfig) -> Result<KeyValue> {
+        let span = Span::current();
+
+        let inner = self.inner.clone();
+        let store = spawn_blocking(move || inner.create_key_value(&config))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(KeyValue {
+            inner: store,
+            metadata: self.metadata.clone(),
+        })
+    }
+}
+
+/// A `JetStream` key-value bucket, holding the latest value written for each key.
+#[derive(Clone)]
+pub struct KeyValue {
+    inner: nats::kv::Store,
+    metadata: Arc<ConnectionMetadata>,
+}
+
+impl fmt::Debug for KeyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyValue")
+            .field("metadata", &self.metadata)
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyValue {
+    /// Gets the latest value for a key, or `None` if the key has no value or was deleted.
+    #[instrument(
+        name = "key_value.get",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn get(&self, key: impl Into<String>) -> Result<Option<Vec<u8>>> {
+        let span = Span::current();
+
+        let key = key.into();
+        let inner = self.inner.clone();
+        let value = spawn_blocking(move || inner.get(&key))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(value)
+    }
+
+    /// Writes the value for a key, returning the revision it was written at.
+    #[instrument(
+        name = "key_value.put",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn put(&self, key: impl Into<String>, value: impl Into<Vec<u8>>) -> Result<u64> {
+        let span = Span::current();
+
+        let key = key.into();
+        let value = value.into();
+        let inner = self.inner.clone();
+        let revision = spawn_blocking(move || inner.put(&key, value))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(revision)
+    }
+
+    /// Deletes the value for a key.
+    #[instrument(
+        name = "key_value.delete",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn delete(&self, key: impl Into<String>) -> Result<()> {
+        let span = Span::current();
+
+        let key = key.into();
+        let inner = self.inner.clone();
+        spawn_blocking(move || inner.delete(&key))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(())
+    }
+
+    /// Gets every key which currently has a value.
+    #[instrument(
+        name = "key_value.keys",
+        skip_all,
+        level = "debug",
+        fields(
+            messaging.protocol = %self.metadata.messaging_protocol,
+            messaging.system = %self.metadata.messaging_system,
+            otel.kind = %FormattedSpanKind(SpanKind::Client),
+            otel.status_code = Empty,
+            otel.status_message = Empty,
+        )
+    )]
+    pub async fn keys(&self) -> Result<Vec<String>> {
+        let span = Span::current();
+
+        let inner = self.inner.clone();
+        let keys = spawn_blocking(move || inner.keys().map(Iterator::collect))
+            .await
+            .map_err(|err| span.record_err(Error::Async(err)))?
+            .map_err(|err| span.record_err(Error::Nats(err)))?;
+
+        span.record_ok();
+        Ok(keys)
+    }
+}
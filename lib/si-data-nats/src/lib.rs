@@ -906,6 +906,11 @@ impl Client {
     pub fn metadata(&self) -> &ConnectionMetadata {
         self.metadata.as_ref()
     }
+
+    /// Gets a [`JetStream`](jetstream::JetStream) context which shares this client's connection.
+    pub fn jetstream(&self) -> jetstream::JetStream {
+        jetstream::JetStream::new(self.inner.clone(), self.metadata.clone())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -3,20 +3,23 @@ mod pkg;
 mod spec;
 
 pub use pkg::{
-    SiPkg, SiPkgActionFunc, SiPkgAttrFuncInput, SiPkgAttrFuncInputView, SiPkgError, SiPkgFunc,
-    SiPkgFuncDescription, SiPkgLeafFunction, SiPkgMapKeyFunc, SiPkgMetadata, SiPkgProp,
-    SiPkgSchema, SiPkgSchemaVariant, SiPkgSocket, SiPkgValidation,
+    SiPkg, SiPkgActionFunc, SiPkgAttrFuncInput, SiPkgAttrFuncInputView, SiPkgComponent,
+    SiPkgConnection, SiPkgError, SiPkgFunc, SiPkgFuncDescription, SiPkgLeafFunction,
+    SiPkgMapKeyFunc, SiPkgMetadata, SiPkgProp, SiPkgSchema, SiPkgSchemaVariant,
+    SiPkgSecretDefinition, SiPkgSocket, SiPkgValidation,
 };
 pub use spec::{
     ActionFuncSpec, ActionFuncSpecBuilder, ActionFuncSpecKind, AttrFuncInputSpec,
-    AttrFuncInputSpecKind, FuncArgumentKind, FuncArgumentSpec, FuncArgumentSpecBuilder,
+    AttrFuncInputSpecKind, ComponentSpec, ComponentSpecBuilder, ConnectionSpec,
+    ConnectionSpecBuilder, FuncArgumentKind, FuncArgumentSpec, FuncArgumentSpecBuilder,
     FuncDescriptionSpec, FuncDescriptionSpecBuilder, FuncSpec, FuncSpecBackendKind,
     FuncSpecBackendResponseType, FuncUniqueId, LeafFunctionSpec, LeafFunctionSpecBuilder,
     LeafInputLocation, LeafKind, MapKeyFuncSpec, MapKeyFuncSpecBuilder, PkgSpec, PkgSpecBuilder,
     PropSpec, PropSpecBuilder, PropSpecKind, PropSpecWidgetKind, SchemaSpec, SchemaSpecBuilder,
     SchemaVariantSpec, SchemaVariantSpecBuilder, SchemaVariantSpecComponentType,
-    SchemaVariantSpecPropRoot, SiPropFuncSpec, SiPropFuncSpecBuilder, SiPropFuncSpecKind,
-    SocketSpec, SocketSpecArity, SocketSpecKind, SpecError, ValidationSpec, ValidationSpecKind,
+    SchemaVariantSpecPropRoot, SecretDefinitionSpec, SecretDefinitionSpecBuilder, SiPropFuncSpec,
+    SiPropFuncSpecBuilder, SiPropFuncSpecKind, SocketSpec, SocketSpecArity, SocketSpecKind,
+    SpecError, ValidationSpec, ValidationSpecKind,
 };
 
 #[cfg(test)]
@@ -142,4 +145,358 @@ mod tests {
 
         let _ = dbg!(props.lock().await);
     }
+
+    pub async fn validation_visitor(
+        prop: SiPkgProp<'_>,
+        _parent_id: Option<()>,
+        context: &Mutex<Vec<ValidationSpec>>,
+    ) -> Result<Option<()>, SiPkgError> {
+        for validation in prop.validations()? {
+            context.lock().await.push(validation.try_into()?);
+        }
+
+        Ok(None)
+    }
+
+    #[tokio::test]
+    async fn validations_round_trip() {
+        let validations = vec![
+            ValidationSpec::ArrayItemsAreUnique,
+            ValidationSpec::ArrayLengthIsBetween {
+                lower_bound: 1,
+                upper_bound: 5,
+            },
+            ValidationSpec::FloatIsBetweenTwoFloats {
+                lower_bound: serde_json::Number::from_f64(0.5).expect("finite"),
+                upper_bound: serde_json::Number::from_f64(99.9).expect("finite"),
+            },
+            ValidationSpec::FloatIsNotEmpty,
+            ValidationSpec::StringIsArn,
+            ValidationSpec::StringIsHostname,
+            ValidationSpec::StringIsValidCidr,
+            ValidationSpec::StringIsValidUrl,
+            ValidationSpec::StringLengthIsBetween {
+                lower_bound: 3,
+                upper_bound: 63,
+            },
+            ValidationSpec::StringMatchesRegex {
+                pattern: "^ami-[0-9a-f]+$".to_string(),
+            },
+        ];
+
+        let mut spec_json: serde_json::Value = serde_json::from_str(PACKAGE_JSON).unwrap();
+        *spec_json
+            .pointer_mut("/schemas/0/variants/0/domain/entries/0/validations")
+            .expect("has validations") =
+            serde_json::to_value(&validations).expect("failed to serialize validations");
+        let spec: PkgSpec = serde_json::from_value(spec_json).unwrap();
+
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        let read_pkg = SiPkg::load_from_bytes(pkg.write_to_bytes().expect("failed to serialize"))
+            .expect("failed to load pkg from bytes");
+        let variant = read_pkg
+            .schemas()
+            .expect("get schema")
+            .pop()
+            .expect("has schema")
+            .variants()
+            .expect("get variants")
+            .pop()
+            .expect("has a variant");
+
+        let read_validations: Mutex<Vec<ValidationSpec>> = Mutex::new(Vec::new());
+        variant
+            .visit_prop_tree(
+                SchemaVariantSpecPropRoot::Domain,
+                validation_visitor,
+                None,
+                &read_validations,
+            )
+            .await
+            .expect("able to visit prop tree");
+
+        // Child order isn't preserved through the graph, so compare without it
+        let read_validations = read_validations.into_inner();
+        assert_eq!(validations.len(), read_validations.len());
+        for validation in &validations {
+            assert!(
+                read_validations.contains(validation),
+                "missing {validation:?}"
+            );
+        }
+    }
+
+    pub async fn float_prop_visitor(
+        prop: SiPkgProp<'_>,
+        _parent_id: Option<()>,
+        context: &Mutex<Vec<(String, Option<f64>)>>,
+    ) -> Result<Option<()>, SiPkgError> {
+        if let SiPkgProp::Float {
+            name,
+            default_value,
+            ..
+        } = prop
+        {
+            context.lock().await.push((name, default_value));
+        }
+
+        Ok(None)
+    }
+
+    #[tokio::test]
+    async fn float_props_round_trip() {
+        let mut spec_json: serde_json::Value = serde_json::from_str(PACKAGE_JSON).unwrap();
+        spec_json
+            .pointer_mut("/schemas/0/variants/0/domain/entries")
+            .expect("has domain entries")
+            .as_array_mut()
+            .expect("domain entries is an array")
+            .extend([
+                serde_json::json!({
+                    "kind": "float",
+                    "name": "ratio",
+                    "defaultValue": 0.75,
+                    "validations": [{ "kind": "floatIsNotEmpty" }],
+                }),
+                serde_json::json!({
+                    "kind": "float",
+                    "name": "weight",
+                    "defaultValue": 2.0,
+                }),
+            ]);
+        let spec: PkgSpec = serde_json::from_value(spec_json).unwrap();
+
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        let read_pkg = SiPkg::load_from_bytes(pkg.write_to_bytes().expect("failed to serialize"))
+            .expect("failed to load pkg from bytes");
+        let variant = read_pkg
+            .schemas()
+            .expect("get schema")
+            .pop()
+            .expect("has schema")
+            .variants()
+            .expect("get variants")
+            .pop()
+            .expect("has a variant");
+
+        let floats: Mutex<Vec<(String, Option<f64>)>> = Mutex::new(Vec::new());
+        variant
+            .visit_prop_tree(
+                SchemaVariantSpecPropRoot::Domain,
+                float_prop_visitor,
+                None,
+                &floats,
+            )
+            .await
+            .expect("able to visit prop tree");
+        assert_eq!(
+            vec![
+                ("ratio".to_string(), Some(0.75)),
+                ("weight".to_string(), Some(2.0))
+            ],
+            floats.into_inner()
+        );
+
+        let variant_spec = variant.to_spec().await.expect("failed to convert to spec");
+        let domain_json = serde_json::to_value(variant_spec.domain).expect("serialize domain");
+        let ratio = domain_json["entries"]
+            .as_array()
+            .expect("domain entries is an array")
+            .iter()
+            .find(|entry| entry["name"] == "ratio")
+            .expect("has ratio prop");
+        assert_eq!("float", ratio["kind"]);
+        assert_eq!(0.75, ratio["defaultValue"]);
+
+        // A whole default must come back as a float, not an integer
+        let weight = domain_json["entries"]
+            .as_array()
+            .expect("domain entries is an array")
+            .iter()
+            .find(|entry| entry["name"] == "weight")
+            .expect("has weight prop");
+        assert_eq!(Some(2.0), weight["defaultValue"].as_f64());
+        assert!(weight["defaultValue"].is_f64());
+    }
+
+    #[tokio::test]
+    async fn secret_reference_props_round_trip() {
+        let token = PropSpec::builder()
+            .kind(PropSpecKind::String)
+            .name("token")
+            .secret_kind("dockerHub")
+            .build()
+            .expect("failed to build prop spec");
+
+        let mut spec_json: serde_json::Value = serde_json::from_str(PACKAGE_JSON).unwrap();
+        spec_json
+            .pointer_mut("/schemas/0/variants/0/domain/entries")
+            .expect("has domain entries")
+            .as_array_mut()
+            .expect("domain entries is an array")
+            .push(serde_json::to_value(token).expect("serialize prop spec"));
+        let spec: PkgSpec = serde_json::from_value(spec_json).unwrap();
+
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        let read_pkg = SiPkg::load_from_bytes(pkg.write_to_bytes().expect("failed to serialize"))
+            .expect("failed to load pkg from bytes");
+        let variant_spec = read_pkg
+            .schemas()
+            .expect("get schema")
+            .pop()
+            .expect("has schema")
+            .variants()
+            .expect("get variants")
+            .pop()
+            .expect("has a variant")
+            .to_spec()
+            .await
+            .expect("failed to convert to spec");
+
+        let domain_json = serde_json::to_value(variant_spec.domain).expect("serialize domain");
+        let token = domain_json["entries"]
+            .as_array()
+            .expect("domain entries is an array")
+            .iter()
+            .find(|entry| entry["name"] == "token")
+            .expect("has token prop");
+        assert_eq!(
+            serde_json::to_value(PropSpecWidgetKind::SecretSelect).expect("serialize widget kind"),
+            token["widgetKind"]
+        );
+        assert_eq!(
+            serde_json::json!({ "secretKind": "dockerHub" }),
+            token["widgetOptions"]
+        );
+    }
+
+    #[tokio::test]
+    async fn secret_definitions_round_trip() {
+        let mut spec: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
+        let hash_without = SiPkg::load_from_spec(spec.clone())
+            .expect("failed to load spec")
+            .hash()
+            .expect("failed to hash pkg");
+
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "token": { "type": "string" } },
+            "required": ["token"],
+        });
+        spec.secret_definitions.push(
+            SecretDefinitionSpec::builder()
+                .name("githubToken")
+                .description("A GitHub\npersonal access token")
+                .schema(schema.clone())
+                .build()
+                .expect("failed to build secret definition spec"),
+        );
+
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        assert_ne!(hash_without, pkg.hash().expect("failed to hash pkg"));
+
+        let read_pkg = SiPkg::load_from_bytes(pkg.write_to_bytes().expect("failed to serialize"))
+            .expect("failed to load pkg from bytes");
+        let secret_definitions = read_pkg
+            .secret_definitions()
+            .expect("failed to get secret definitions");
+        assert_eq!(1, secret_definitions.len());
+        let secret_definition = secret_definitions.get(0).expect("has secret definition");
+        assert_eq!("githubToken", secret_definition.name());
+        assert_eq!(
+            "A GitHub\npersonal access token",
+            secret_definition.description()
+        );
+        assert_eq!(&schema, secret_definition.schema());
+
+        // Packages without secret definitions have no category for them at all
+        let spec: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        assert!(pkg
+            .secret_definitions()
+            .expect("failed to get secret definitions")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn components_round_trip() {
+        let mut spec: PkgSpec = serde_json::from_str(PACKAGE_JSON).unwrap();
+        let hash_without = SiPkg::load_from_spec(spec.clone())
+            .expect("failed to load spec")
+            .hash()
+            .expect("failed to hash pkg");
+
+        let domain = serde_json::json!({ "region": "us-east-2", "tags": { "env": "dev" } });
+        spec.components.push(
+            ComponentSpec::builder()
+                .name("frame")
+                .schema_name("Region")
+                .component_type(SchemaVariantSpecComponentType::ConfigurationFrame)
+                .x("0")
+                .y("0")
+                .width("500".to_string())
+                .height("500".to_string())
+                .domain(domain.clone())
+                .build()
+                .expect("failed to build component spec"),
+        );
+        let connection = ConnectionSpec::builder()
+            .from_component_name("frame")
+            .from_socket_name("Region")
+            .to_socket_name("Region")
+            .build()
+            .expect("failed to build connection spec");
+        spec.components.push(
+            ComponentSpec::builder()
+                .name("server")
+                .schema_name("EC2 Instance")
+                .x("100")
+                .y("200")
+                .parent("frame".to_string())
+                .connection(connection.clone())
+                .build()
+                .expect("failed to build component spec"),
+        );
+
+        let pkg = SiPkg::load_from_spec(spec).expect("failed to load spec");
+        assert_ne!(hash_without, pkg.hash().expect("failed to hash pkg"));
+
+        let read_pkg = SiPkg::load_from_bytes(pkg.write_to_bytes().expect("failed to serialize"))
+            .expect("failed to load pkg from bytes");
+        let components = read_pkg.components().expect("failed to get components");
+        assert_eq!(2, components.len());
+
+        let frame = components
+            .iter()
+            .find(|component| component.name() == "frame")
+            .expect("has frame");
+        assert_eq!("Region", frame.schema_name());
+        assert_eq!(
+            Some(SchemaVariantSpecComponentType::ConfigurationFrame),
+            frame.component_type()
+        );
+        assert_eq!(Some("500"), frame.width());
+        assert_eq!(None, frame.parent());
+        assert_eq!(&domain, frame.domain());
+        assert!(frame
+            .connections()
+            .expect("failed to get connections")
+            .is_empty());
+
+        let server = components
+            .iter()
+            .find(|component| component.name() == "server")
+            .expect("has server");
+        assert_eq!(None, server.component_type());
+        assert_eq!(("100", "200"), (server.x(), server.y()));
+        assert_eq!(None, server.width());
+        assert_eq!(Some("frame"), server.parent());
+        let connections: Vec<ConnectionSpec> = server
+            .connections()
+            .expect("failed to get connections")
+            .into_iter()
+            .map(|connection| connection.try_into().expect("failed to convert connection"))
+            .collect();
+        assert_eq!(vec![connection], connections);
+    }
 }
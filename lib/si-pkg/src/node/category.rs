@@ -6,12 +6,14 @@ use object_tree::{
 };
 use serde::{Deserialize, Serialize};
 
-use crate::{FuncSpec, SchemaSpec};
+use crate::{ComponentSpec, FuncSpec, SchemaSpec, SecretDefinitionSpec};
 
 use super::PkgNode;
 
 const CATEGORY_TYPE_SCHEMAS: &str = "schemas";
 const CATEGORY_TYPE_FUNCS: &str = "funcs";
+const CATEGORY_TYPE_SECRET_DEFINITIONS: &str = "secret_definitions";
+const CATEGORY_TYPE_COMPONENTS: &str = "components";
 
 const KEY_KIND_STR: &str = "kind";
 
@@ -19,15 +21,19 @@ const KEY_KIND_STR: &str = "kind";
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub enum PackageCategory {
+    Components(Vec<ComponentSpec>),
     Funcs(Vec<FuncSpec>),
     Schemas(Vec<SchemaSpec>),
+    SecretDefinitions(Vec<SecretDefinitionSpec>),
 }
 
 #[remain::sorted]
 #[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
 pub enum CategoryNode {
+    Components,
     Funcs,
     Schemas,
+    SecretDefinitions,
 }
 
 impl CategoryNode {
@@ -35,6 +41,8 @@ impl CategoryNode {
         match self {
             Self::Schemas => CATEGORY_TYPE_SCHEMAS,
             Self::Funcs => CATEGORY_TYPE_FUNCS,
+            Self::SecretDefinitions => CATEGORY_TYPE_SECRET_DEFINITIONS,
+            Self::Components => CATEGORY_TYPE_COMPONENTS,
         }
     }
 }
@@ -44,6 +52,8 @@ impl NameStr for CategoryNode {
         match self {
             Self::Schemas => CATEGORY_TYPE_SCHEMAS,
             Self::Funcs => CATEGORY_TYPE_FUNCS,
+            Self::SecretDefinitions => CATEGORY_TYPE_SECRET_DEFINITIONS,
+            Self::Components => CATEGORY_TYPE_COMPONENTS,
         }
     }
 }
@@ -65,6 +75,8 @@ impl ReadBytes for CategoryNode {
         let node = match kind_str.as_str() {
             CATEGORY_TYPE_SCHEMAS => Self::Schemas,
             CATEGORY_TYPE_FUNCS => Self::Funcs,
+            CATEGORY_TYPE_SECRET_DEFINITIONS => Self::SecretDefinitions,
+            CATEGORY_TYPE_COMPONENTS => Self::Components,
             invalid_kind => {
                 return Err(GraphError::parse_custom(format!(
                     "invalid package category node kind: {invalid_kind}"
@@ -109,6 +121,34 @@ impl NodeChild for PackageCategory {
                     children,
                 )
             }
+            Self::SecretDefinitions(entries) => {
+                let mut children = Vec::new();
+                for entry in entries {
+                    children
+                        .push(Box::new(entry.clone())
+                            as Box<dyn NodeChild<NodeType = Self::NodeType>>);
+                }
+
+                NodeWithChildren::new(
+                    NodeKind::Tree,
+                    Self::NodeType::Category(CategoryNode::SecretDefinitions),
+                    children,
+                )
+            }
+            Self::Components(entries) => {
+                let mut children = Vec::new();
+                for entry in entries {
+                    children
+                        .push(Box::new(entry.clone())
+                            as Box<dyn NodeChild<NodeType = Self::NodeType>>);
+                }
+
+                NodeWithChildren::new(
+                    NodeKind::Tree,
+                    Self::NodeType::Category(CategoryNode::Components),
+                    children,
+                )
+            }
         }
     }
 }
@@ -0,0 +1,137 @@
+use std::{
+    io::{BufRead, Write},
+    str::FromStr,
+};
+
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NameStr, NodeChild, NodeKind,
+    NodeWithChildren, ReadBytes, WriteBytes,
+};
+
+use crate::{ComponentSpec, SchemaVariantSpecComponentType};
+
+use super::PkgNode;
+
+const KEY_COMPONENT_TYPE_STR: &str = "component_type";
+const KEY_DOMAIN_STR: &str = "domain";
+const KEY_HEIGHT_STR: &str = "height";
+const KEY_NAME_STR: &str = "name";
+const KEY_PARENT_STR: &str = "parent";
+const KEY_SCHEMA_NAME_STR: &str = "schema_name";
+const KEY_WIDTH_STR: &str = "width";
+const KEY_X_STR: &str = "x";
+const KEY_Y_STR: &str = "y";
+
+#[derive(Clone, Debug)]
+pub struct ComponentNode {
+    pub name: String,
+    pub schema_name: String,
+    pub component_type: Option<SchemaVariantSpecComponentType>,
+    pub x: String,
+    pub y: String,
+    pub width: Option<String>,
+    pub height: Option<String>,
+    pub parent: Option<String>,
+    pub domain: serde_json::Value,
+}
+
+impl NameStr for ComponentNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl WriteBytes for ComponentNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_NAME_STR, self.name())?;
+        write_key_value_line(writer, KEY_SCHEMA_NAME_STR, &self.schema_name)?;
+        write_key_value_line(
+            writer,
+            KEY_COMPONENT_TYPE_STR,
+            self.component_type
+                .map(|component_type| component_type.to_string())
+                .unwrap_or_default(),
+        )?;
+        write_key_value_line(writer, KEY_X_STR, &self.x)?;
+        write_key_value_line(writer, KEY_Y_STR, &self.y)?;
+        write_key_value_line(writer, KEY_WIDTH_STR, self.width.as_deref().unwrap_or(""))?;
+        write_key_value_line(writer, KEY_HEIGHT_STR, self.height.as_deref().unwrap_or(""))?;
+        write_key_value_line(writer, KEY_PARENT_STR, self.parent.as_deref().unwrap_or(""))?;
+        write_key_value_line(
+            writer,
+            KEY_DOMAIN_STR,
+            serde_json::to_string(&self.domain).map_err(GraphError::parse)?,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for ComponentNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Self, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let name = read_key_value_line(reader, KEY_NAME_STR)?;
+        let schema_name = read_key_value_line(reader, KEY_SCHEMA_NAME_STR)?;
+        let component_type_str = read_key_value_line(reader, KEY_COMPONENT_TYPE_STR)?;
+        let component_type = if component_type_str.is_empty() {
+            None
+        } else {
+            Some(
+                SchemaVariantSpecComponentType::from_str(&component_type_str)
+                    .map_err(GraphError::parse)?,
+            )
+        };
+        let x = read_key_value_line(reader, KEY_X_STR)?;
+        let y = read_key_value_line(reader, KEY_Y_STR)?;
+        let width = Some(read_key_value_line(reader, KEY_WIDTH_STR)?).filter(|s| !s.is_empty());
+        let height = Some(read_key_value_line(reader, KEY_HEIGHT_STR)?).filter(|s| !s.is_empty());
+        let parent = Some(read_key_value_line(reader, KEY_PARENT_STR)?).filter(|s| !s.is_empty());
+        let domain_str = read_key_value_line(reader, KEY_DOMAIN_STR)?;
+        let domain: serde_json::Value =
+            serde_json::from_str(&domain_str).map_err(GraphError::parse)?;
+
+        Ok(Self {
+            name,
+            schema_name,
+            component_type,
+            x,
+            y,
+            width,
+            height,
+            parent,
+            domain,
+        })
+    }
+}
+
+impl NodeChild for ComponentSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        let children = self
+            .connections
+            .iter()
+            .map(|connection| {
+                Box::new(connection.clone()) as Box<dyn NodeChild<NodeType = Self::NodeType>>
+            })
+            .collect();
+
+        NodeWithChildren::new(
+            NodeKind::Tree,
+            Self::NodeType::Component(ComponentNode {
+                name: self.name.to_owned(),
+                schema_name: self.schema_name.to_owned(),
+                component_type: self.component_type,
+                x: self.x.to_owned(),
+                y: self.y.to_owned(),
+                width: self.width.to_owned(),
+                height: self.height.to_owned(),
+                parent: self.parent.to_owned(),
+                domain: self.domain.to_owned(),
+            }),
+            children,
+        )
+    }
+}
@@ -0,0 +1,68 @@
+use std::io::{BufRead, Write};
+
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NodeChild, NodeKind, NodeWithChildren,
+    ReadBytes, WriteBytes,
+};
+
+use crate::ConnectionSpec;
+
+use super::PkgNode;
+
+const KEY_FROM_COMPONENT_NAME_STR: &str = "from_component_name";
+const KEY_FROM_SOCKET_NAME_STR: &str = "from_socket_name";
+const KEY_TO_SOCKET_NAME_STR: &str = "to_socket_name";
+
+#[derive(Clone, Debug)]
+pub struct ConnectionNode {
+    pub from_component_name: String,
+    pub from_socket_name: String,
+    pub to_socket_name: String,
+}
+
+impl WriteBytes for ConnectionNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(
+            writer,
+            KEY_FROM_COMPONENT_NAME_STR,
+            &self.from_component_name,
+        )?;
+        write_key_value_line(writer, KEY_FROM_SOCKET_NAME_STR, &self.from_socket_name)?;
+        write_key_value_line(writer, KEY_TO_SOCKET_NAME_STR, &self.to_socket_name)?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for ConnectionNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Self, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let from_component_name = read_key_value_line(reader, KEY_FROM_COMPONENT_NAME_STR)?;
+        let from_socket_name = read_key_value_line(reader, KEY_FROM_SOCKET_NAME_STR)?;
+        let to_socket_name = read_key_value_line(reader, KEY_TO_SOCKET_NAME_STR)?;
+
+        Ok(Self {
+            from_component_name,
+            from_socket_name,
+            to_socket_name,
+        })
+    }
+}
+
+impl NodeChild for ConnectionSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Leaf,
+            Self::NodeType::Connection(ConnectionNode {
+                from_component_name: self.from_component_name.to_owned(),
+                from_socket_name: self.from_socket_name.to_owned(),
+                to_socket_name: self.to_socket_name.to_owned(),
+            }),
+            vec![],
+        )
+    }
+}
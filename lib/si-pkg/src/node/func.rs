@@ -17,6 +17,7 @@ const KEY_NAME_STR: &str = "name";
 const KEY_DISPLAY_NAME_STR: &str = "display_name";
 const KEY_DESCRIPTION_STR: &str = "description";
 const KEY_HANDLER_STR: &str = "handler";
+const KEY_PLAN_HANDLER_STR: &str = "plan_handler";
 const KEY_CODE_STR: &str = "code_base64";
 const KEY_BACKEND_KIND_STR: &str = "backend_kind";
 const KEY_RESPONSE_TYPE_STR: &str = "response_type";
@@ -30,6 +31,7 @@ pub struct FuncNode {
     pub display_name: Option<String>,
     pub description: Option<String>,
     pub handler: String,
+    pub plan_handler: Option<String>,
     pub code_base64: String,
     pub backend_kind: FuncSpecBackendKind,
     pub response_type: FuncSpecBackendResponseType,
@@ -58,6 +60,11 @@ impl WriteBytes for FuncNode {
             self.description.as_deref().unwrap_or(""),
         )?;
         write_key_value_line(writer, KEY_HANDLER_STR, &self.handler)?;
+        write_key_value_line(
+            writer,
+            KEY_PLAN_HANDLER_STR,
+            self.plan_handler.as_deref().unwrap_or(""),
+        )?;
         write_key_value_line(writer, KEY_CODE_STR, &self.code_base64)?;
         write_key_value_line(writer, KEY_BACKEND_KIND_STR, self.backend_kind)?;
         write_key_value_line(writer, KEY_RESPONSE_TYPE_STR, self.response_type)?;
@@ -92,6 +99,12 @@ impl ReadBytes for FuncNode {
             Some(description_str)
         };
         let handler = read_key_value_line(reader, KEY_HANDLER_STR)?;
+        let plan_handler_str = read_key_value_line(reader, KEY_PLAN_HANDLER_STR)?;
+        let plan_handler = if plan_handler_str.is_empty() {
+            None
+        } else {
+            Some(plan_handler_str)
+        };
         let code_base64 = read_key_value_line(reader, KEY_CODE_STR)?;
         let backend_kind_str = read_key_value_line(reader, KEY_BACKEND_KIND_STR)?;
         let backend_kind =
@@ -115,6 +128,7 @@ impl ReadBytes for FuncNode {
             display_name,
             description,
             handler,
+            plan_handler,
             code_base64,
             backend_kind,
             response_type,
@@ -142,6 +156,7 @@ impl NodeChild for FuncSpec {
                 display_name: self.display_name.as_ref().cloned(),
                 description: self.description.as_ref().cloned(),
                 handler: self.handler.to_string(),
+                plan_handler: self.plan_handler.as_ref().cloned(),
                 code_base64: self.code_base64.to_string(),
                 backend_kind: self.backend_kind,
                 response_type: self.response_type,
@@ -7,6 +7,8 @@ use object_tree::{
 mod action_func;
 mod attr_func_input;
 mod category;
+mod component;
+mod connection;
 mod func;
 mod func_argument;
 mod func_description;
@@ -18,6 +20,7 @@ mod prop_child;
 mod schema;
 mod schema_variant;
 mod schema_variant_child;
+mod secret_definition;
 mod si_prop_func;
 mod socket;
 mod validation;
@@ -26,6 +29,8 @@ pub(crate) use self::{
     action_func::ActionFuncNode,
     attr_func_input::AttrFuncInputNode,
     category::CategoryNode,
+    component::ComponentNode,
+    connection::ConnectionNode,
     func::FuncNode,
     func_argument::FuncArgumentNode,
     func_description::FuncDescriptionNode,
@@ -37,6 +42,7 @@ pub(crate) use self::{
     schema::SchemaNode,
     schema_variant::SchemaVariantNode,
     schema_variant_child::{SchemaVariantChild, SchemaVariantChildNode},
+    secret_definition::SecretDefinitionNode,
     si_prop_func::SiPropFuncNode,
     socket::SocketNode,
     validation::ValidationNode,
@@ -45,6 +51,8 @@ pub(crate) use self::{
 const NODE_KIND_ACTION_FUNC: &str = "action_func";
 const NODE_KIND_ATTR_FUNC_INPUT: &str = "attr_func_input";
 const NODE_KIND_CATEGORY: &str = "category";
+const NODE_KIND_COMPONENT: &str = "component";
+const NODE_KIND_CONNECTION: &str = "connection";
 const NODE_KIND_FUNC: &str = "func";
 const NODE_KIND_FUNC_ARGUMENT: &str = "func_argument";
 const NODE_KIND_FUNC_DESCRIPTION: &str = "func_description";
@@ -56,6 +64,7 @@ const NODE_KIND_PROP_CHILD: &str = "prop_child";
 const NODE_KIND_SCHEMA: &str = "schema";
 const NODE_KIND_SCHEMA_VARIANT: &str = "schema_variant";
 const NODE_KIND_SCHEMA_VARIANT_CHILD: &str = "schema_variant_child";
+const NODE_KIND_SECRET_DEFINITION: &str = "secret_definition";
 const NODE_KIND_SOCKET: &str = "socket";
 const NODE_KIND_SI_PROP_FUNC: &str = "si_prop_func";
 const NODE_KIND_VALIDATION: &str = "validation";
@@ -68,6 +77,8 @@ pub enum PkgNode {
     ActionFunc(ActionFuncNode),
     AttrFuncInput(AttrFuncInputNode),
     Category(CategoryNode),
+    Component(ComponentNode),
+    Connection(ConnectionNode),
     Func(FuncNode),
     FuncArgument(FuncArgumentNode),
     FuncDescription(FuncDescriptionNode),
@@ -79,6 +90,7 @@ pub enum PkgNode {
     Schema(SchemaNode),
     SchemaVariant(SchemaVariantNode),
     SchemaVariantChild(SchemaVariantChildNode),
+    SecretDefinition(SecretDefinitionNode),
     SiPropFunc(SiPropFuncNode),
     Socket(SocketNode),
     Validation(ValidationNode),
@@ -88,6 +100,8 @@ impl PkgNode {
     pub const ACTION_FUNC_KIND_STR: &str = NODE_KIND_ACTION_FUNC;
     pub const ATTR_FUNC_INPUT_KIND_STR: &str = NODE_KIND_ATTR_FUNC_INPUT;
     pub const CATEGORY_KIND_STR: &str = NODE_KIND_CATEGORY;
+    pub const COMPONENT_KIND_STR: &str = NODE_KIND_COMPONENT;
+    pub const CONNECTION_KIND_STR: &str = NODE_KIND_CONNECTION;
     pub const FUNC_KIND_STR: &str = NODE_KIND_FUNC;
     pub const FUNC_ARGUMENT_KIND_STR: &str = NODE_KIND_FUNC_ARGUMENT;
     pub const FUNC_DESCRIPTION_KIND_STR: &str = NODE_KIND_FUNC_DESCRIPTION;
@@ -99,6 +113,7 @@ impl PkgNode {
     pub const SCHEMA_KIND_STR: &str = NODE_KIND_SCHEMA;
     pub const SCHEMA_VARIANT_KIND_STR: &str = NODE_KIND_SCHEMA_VARIANT;
     pub const SCHEMA_VARIANT_KIND_CHILD_STR: &str = NODE_KIND_SCHEMA_VARIANT_CHILD;
+    pub const SECRET_DEFINITION_KIND_STR: &str = NODE_KIND_SECRET_DEFINITION;
     pub const SOCKET_KIND_STR: &str = NODE_KIND_SOCKET;
     pub const SI_PROP_FUNC_KIND_STR: &str = NODE_KIND_SI_PROP_FUNC;
     pub const VALIDATION_KIND_STR: &str = NODE_KIND_VALIDATION;
@@ -107,6 +122,8 @@ impl PkgNode {
         match self {
             Self::AttrFuncInput(_) => NODE_KIND_ATTR_FUNC_INPUT,
             Self::Category(_) => NODE_KIND_CATEGORY,
+            Self::Component(_) => NODE_KIND_COMPONENT,
+            Self::Connection(_) => NODE_KIND_CONNECTION,
             Self::ActionFunc(_) => NODE_KIND_ACTION_FUNC,
             Self::Func(_) => NODE_KIND_FUNC,
             Self::FuncArgument(_) => NODE_KIND_FUNC_ARGUMENT,
@@ -119,6 +136,7 @@ impl PkgNode {
             Self::Schema(_) => NODE_KIND_SCHEMA,
             Self::SchemaVariant(_) => NODE_KIND_SCHEMA_VARIANT,
             Self::SchemaVariantChild(_) => NODE_KIND_SCHEMA_VARIANT_CHILD,
+            Self::SecretDefinition(_) => NODE_KIND_SECRET_DEFINITION,
             Self::Socket(_) => NODE_KIND_SOCKET,
             Self::SiPropFunc(_) => NODE_KIND_SI_PROP_FUNC,
             Self::Validation(_) => NODE_KIND_VALIDATION,
@@ -131,6 +149,8 @@ impl NameStr for PkgNode {
         match self {
             Self::AttrFuncInput(node) => node.name(),
             Self::Category(node) => node.name(),
+            Self::Component(node) => node.name(),
+            Self::Connection(_) => NODE_KIND_CONNECTION,
             Self::ActionFunc(_) => NODE_KIND_ACTION_FUNC,
             Self::Func(node) => node.name(),
             Self::FuncArgument(node) => node.name(),
@@ -143,6 +163,7 @@ impl NameStr for PkgNode {
             Self::Schema(node) => node.name(),
             Self::SchemaVariant(node) => node.name(),
             Self::SchemaVariantChild(node) => node.name(),
+            Self::SecretDefinition(node) => node.name(),
             Self::Socket(node) => node.name(),
             Self::SiPropFunc(_) => NODE_KIND_SI_PROP_FUNC,
             Self::Validation(_) => NODE_KIND_VALIDATION,
@@ -157,6 +178,8 @@ impl WriteBytes for PkgNode {
         match self {
             Self::AttrFuncInput(node) => node.write_bytes(writer)?,
             Self::Category(node) => node.write_bytes(writer)?,
+            Self::Component(node) => node.write_bytes(writer)?,
+            Self::Connection(node) => node.write_bytes(writer)?,
             Self::ActionFunc(node) => node.write_bytes(writer)?,
             Self::Func(node) => node.write_bytes(writer)?,
             Self::FuncArgument(node) => node.write_bytes(writer)?,
@@ -169,6 +192,7 @@ impl WriteBytes for PkgNode {
             Self::Schema(node) => node.write_bytes(writer)?,
             Self::SchemaVariant(node) => node.write_bytes(writer)?,
             Self::SchemaVariantChild(node) => node.write_bytes(writer)?,
+            Self::SecretDefinition(node) => node.write_bytes(writer)?,
             Self::Socket(node) => node.write_bytes(writer)?,
             Self::SiPropFunc(node) => node.write_bytes(writer)?,
             Self::Validation(node) => node.write_bytes(writer)?,
@@ -191,6 +215,8 @@ impl ReadBytes for PkgNode {
                 Self::AttrFuncInput(AttrFuncInputNode::read_bytes(reader)?)
             }
             NODE_KIND_CATEGORY => Self::Category(CategoryNode::read_bytes(reader)?),
+            NODE_KIND_COMPONENT => Self::Component(ComponentNode::read_bytes(reader)?),
+            NODE_KIND_CONNECTION => Self::Connection(ConnectionNode::read_bytes(reader)?),
             NODE_KIND_FUNC => Self::Func(FuncNode::read_bytes(reader)?),
             NODE_KIND_FUNC_ARGUMENT => Self::FuncArgument(FuncArgumentNode::read_bytes(reader)?),
             NODE_KIND_FUNC_DESCRIPTION => {
@@ -206,6 +232,9 @@ impl ReadBytes for PkgNode {
             NODE_KIND_SCHEMA_VARIANT_CHILD => {
                 Self::SchemaVariantChild(SchemaVariantChildNode::read_bytes(reader)?)
             }
+            NODE_KIND_SECRET_DEFINITION => {
+                Self::SecretDefinition(SecretDefinitionNode::read_bytes(reader)?)
+            }
             NODE_KIND_SOCKET => Self::Socket(SocketNode::read_bytes(reader)?),
             NODE_KIND_SI_PROP_FUNC => Self::SiPropFunc(SiPropFuncNode::read_bytes(reader)?),
             NODE_KIND_VALIDATION => Self::Validation(ValidationNode::read_bytes(reader)?),
@@ -71,6 +71,27 @@ impl NodeChild for PkgSpec {
     type NodeType = PkgNode;
 
     fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        let mut children = vec![
+            Box::new(PackageCategory::Schemas(self.schemas.clone()))
+                as Box<dyn NodeChild<NodeType = Self::NodeType>>,
+            Box::new(PackageCategory::Funcs(self.funcs.clone()))
+                as Box<dyn NodeChild<NodeType = Self::NodeType>>,
+        ];
+        // Only packages that ship secret definitions get the category, which keeps the hashes of
+        // every other package unchanged.
+        if !self.secret_definitions.is_empty() {
+            children.push(Box::new(PackageCategory::SecretDefinitions(
+                self.secret_definitions.clone(),
+            ))
+                as Box<dyn NodeChild<NodeType = Self::NodeType>>);
+        }
+        if !self.components.is_empty() {
+            children.push(
+                Box::new(PackageCategory::Components(self.components.clone()))
+                    as Box<dyn NodeChild<NodeType = Self::NodeType>>,
+            );
+        }
+
         NodeWithChildren::new(
             NodeKind::Tree,
             Self::NodeType::Package(PackageNode {
@@ -80,12 +101,7 @@ impl NodeChild for PkgSpec {
                 created_at: self.created_at,
                 created_by: self.created_by.clone(),
             }),
-            vec![
-                Box::new(PackageCategory::Schemas(self.schemas.clone()))
-                    as Box<dyn NodeChild<NodeType = Self::NodeType>>,
-                Box::new(PackageCategory::Funcs(self.funcs.clone()))
-                    as Box<dyn NodeChild<NodeType = Self::NodeType>>,
-            ],
+            children,
         )
     }
 }
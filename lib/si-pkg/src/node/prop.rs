@@ -25,6 +25,7 @@ const KEY_DOC_LINK_STR: &str = "doc_link";
 
 const PROP_TY_STRING: &str = "string";
 const PROP_TY_INTEGER: &str = "integer";
+const PROP_TY_FLOAT: &str = "float";
 const PROP_TY_BOOLEAN: &str = "boolean";
 const PROP_TY_MAP: &str = "map";
 const PROP_TY_ARRAY: &str = "array";
@@ -51,6 +52,15 @@ pub enum PropNode {
         doc_link: Option<Url>,
         hidden: bool,
     },
+    Float {
+        name: String,
+        func_unique_id: Option<FuncUniqueId>,
+        default_value: Option<f64>,
+        widget_kind: PropSpecWidgetKind,
+        widget_options: Option<serde_json::Value>,
+        hidden: bool,
+        doc_link: Option<Url>,
+    },
     Integer {
         name: String,
         func_unique_id: Option<FuncUniqueId>,
@@ -94,6 +104,7 @@ impl PropNode {
         match self {
             Self::String { .. } => PROP_TY_STRING,
             Self::Integer { .. } => PROP_TY_INTEGER,
+            Self::Float { .. } => PROP_TY_FLOAT,
             Self::Boolean { .. } => PROP_TY_BOOLEAN,
             Self::Map { .. } => PROP_TY_MAP,
             Self::Array { .. } => PROP_TY_ARRAY,
@@ -107,6 +118,7 @@ impl NameStr for PropNode {
         match self {
             Self::String { name, .. }
             | Self::Integer { name, .. }
+            | Self::Float { name, .. }
             | Self::Boolean { name, .. }
             | Self::Map { name, .. }
             | Self::Array { name, .. }
@@ -123,6 +135,7 @@ impl WriteBytes for PropNode {
         let func_unique_id = match &self {
             Self::String { func_unique_id, .. }
             | Self::Integer { func_unique_id, .. }
+            | Self::Float { func_unique_id, .. }
             | Self::Boolean { func_unique_id, .. }
             | Self::Map { func_unique_id, .. }
             | Self::Array { func_unique_id, .. }
@@ -148,6 +161,10 @@ impl WriteBytes for PropNode {
                     Some(dv) => serde_json::to_string(dv).map_err(GraphError::parse)?,
                     None => "".to_string(),
                 },
+                Self::Float { default_value, .. } => match default_value {
+                    Some(dv) => serde_json::to_string(dv).map_err(GraphError::parse)?,
+                    None => "".to_string(),
+                },
                 Self::Boolean { default_value, .. } => match default_value {
                     Some(dv) => serde_json::to_string(dv).map_err(GraphError::parse)?,
                     None => "".to_string(),
@@ -167,6 +184,7 @@ impl WriteBytes for PropNode {
             match &self {
                 Self::String { widget_kind, .. }
                 | Self::Integer { widget_kind, .. }
+                | Self::Float { widget_kind, .. }
                 | Self::Boolean { widget_kind, .. }
                 | Self::Map { widget_kind, .. }
                 | Self::Array { widget_kind, .. }
@@ -180,6 +198,7 @@ impl WriteBytes for PropNode {
             match &self {
                 Self::String { widget_options, .. }
                 | Self::Integer { widget_options, .. }
+                | Self::Float { widget_options, .. }
                 | Self::Boolean { widget_options, .. }
                 | Self::Map { widget_options, .. }
                 | Self::Array { widget_options, .. }
@@ -196,6 +215,7 @@ impl WriteBytes for PropNode {
             match &self {
                 Self::String { hidden, .. }
                 | Self::Integer { hidden, .. }
+                | Self::Float { hidden, .. }
                 | Self::Boolean { hidden, .. }
                 | Self::Map { hidden, .. }
                 | Self::Array { hidden, .. }
@@ -209,6 +229,7 @@ impl WriteBytes for PropNode {
             match &self {
                 Self::String { doc_link, .. }
                 | Self::Integer { doc_link, .. }
+                | Self::Float { doc_link, .. }
                 | Self::Boolean { doc_link, .. }
                 | Self::Map { doc_link, .. }
                 | Self::Array { doc_link, .. }
@@ -304,6 +325,26 @@ impl ReadBytes for PropNode {
                 hidden,
                 doc_link,
             },
+            PROP_TY_FLOAT => Self::Float {
+                name,
+                default_value: match default_value_json {
+                    None => None,
+                    Some(value) => {
+                        if value.is_number() {
+                            value.as_f64()
+                        } else {
+                            return Err(GraphError::parse_custom(
+                                "Float prop must get a number as a default value",
+                            ));
+                        }
+                    }
+                },
+                func_unique_id,
+                widget_kind,
+                widget_options,
+                hidden,
+                doc_link,
+            },
             PROP_TY_BOOLEAN => Self::Boolean {
                 name,
                 default_value: match default_value_json {
@@ -427,6 +468,36 @@ impl NodeChild for PropSpec {
                     )) as Box<dyn NodeChild<NodeType = Self::NodeType>>,
                 ],
             ),
+            Self::Float {
+                name,
+                default_value,
+                validations,
+                func_unique_id,
+                inputs,
+                widget_kind,
+                widget_options,
+                hidden,
+                doc_link,
+            } => NodeWithChildren::new(
+                NodeKind::Tree,
+                Self::NodeType::Prop(PropNode::Float {
+                    name: name.to_string(),
+                    default_value: default_value.to_owned(),
+                    func_unique_id: *func_unique_id,
+                    widget_kind: widget_kind.unwrap_or(PropSpecWidgetKind::from(self)),
+                    widget_options: widget_options.to_owned(),
+                    hidden: hidden.unwrap_or(false),
+                    doc_link: doc_link.to_owned(),
+                }),
+                vec![
+                    Box::new(PropChild::Validations(
+                        validations.to_owned().unwrap_or(vec![]),
+                    )) as Box<dyn NodeChild<NodeType = Self::NodeType>>,
+                    Box::new(PropChild::AttrFuncInputs(
+                        inputs.to_owned().unwrap_or(vec![]),
+                    )) as Box<dyn NodeChild<NodeType = Self::NodeType>>,
+                ],
+            ),
             Self::Boolean {
                 name,
                 default_value,
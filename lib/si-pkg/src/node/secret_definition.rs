@@ -0,0 +1,77 @@
+use std::io::{BufRead, Write};
+
+use object_tree::{
+    read_key_value_line, write_key_value_line, GraphError, NameStr, NodeChild, NodeKind,
+    NodeWithChildren, ReadBytes, WriteBytes,
+};
+
+use crate::SecretDefinitionSpec;
+
+use super::PkgNode;
+
+const KEY_DESCRIPTION_STR: &str = "description";
+const KEY_NAME_STR: &str = "name";
+const KEY_SCHEMA_STR: &str = "schema";
+
+#[derive(Clone, Debug)]
+pub struct SecretDefinitionNode {
+    pub name: String,
+    pub description: String,
+    pub schema: serde_json::Value,
+}
+
+impl NameStr for SecretDefinitionNode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl WriteBytes for SecretDefinitionNode {
+    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<(), GraphError> {
+        write_key_value_line(writer, KEY_NAME_STR, self.name())?;
+        write_key_value_line(writer, KEY_DESCRIPTION_STR, &self.description)?;
+        write_key_value_line(
+            writer,
+            KEY_SCHEMA_STR,
+            serde_json::to_string(&self.schema).map_err(GraphError::parse)?,
+        )?;
+
+        Ok(())
+    }
+}
+
+impl ReadBytes for SecretDefinitionNode {
+    fn read_bytes<R: BufRead>(reader: &mut R) -> Result<Self, GraphError>
+    where
+        Self: std::marker::Sized,
+    {
+        let name = read_key_value_line(reader, KEY_NAME_STR)?;
+        let description = read_key_value_line(reader, KEY_DESCRIPTION_STR)?;
+
+        let schema_str = read_key_value_line(reader, KEY_SCHEMA_STR)?;
+        let schema: serde_json::Value =
+            serde_json::from_str(&schema_str).map_err(GraphError::parse)?;
+
+        Ok(Self {
+            name,
+            description,
+            schema,
+        })
+    }
+}
+
+impl NodeChild for SecretDefinitionSpec {
+    type NodeType = PkgNode;
+
+    fn as_node_with_children(&self) -> NodeWithChildren<Self::NodeType> {
+        NodeWithChildren::new(
+            NodeKind::Leaf,
+            Self::NodeType::SecretDefinition(SecretDefinitionNode {
+                name: self.name.to_owned(),
+                description: self.description.to_owned(),
+                schema: self.schema.to_owned(),
+            }),
+            vec![],
+        )
+    }
+}
@@ -8,6 +8,8 @@ use object_tree::{
     ReadBytes, WriteBytes,
 };
 
+use serde_json::Number;
+
 use crate::{FuncUniqueId, ValidationSpec, ValidationSpecKind};
 
 use super::PkgNode;
@@ -15,6 +17,8 @@ use super::PkgNode;
 const KEY_KIND_STR: &str = "kind";
 const KEY_UPPER_BOUND_STR: &str = "upper_bound";
 const KEY_LOWER_BOUND_STR: &str = "lower_bound";
+const KEY_FLOAT_UPPER_BOUND_STR: &str = "float_upper_bound";
+const KEY_FLOAT_LOWER_BOUND_STR: &str = "float_lower_bound";
 const KEY_EXPECTED_STRING_STR: &str = "expected_string";
 const KEY_EXPECTED_STRING_ARRAY_STR: &str = "expected_string_array";
 const KEY_DISPLAY_EXPECTED_STR: &str = "display_expected";
@@ -25,6 +29,8 @@ pub struct ValidationNode {
     pub kind: ValidationSpecKind,
     pub upper_bound: Option<i64>,
     pub lower_bound: Option<i64>,
+    pub float_upper_bound: Option<Number>,
+    pub float_lower_bound: Option<Number>,
     pub expected_string: Option<String>,
     pub expected_string_array: Option<Vec<String>>,
     pub display_expected: Option<bool>,
@@ -37,6 +43,8 @@ impl Default for ValidationNode {
             kind: ValidationSpecKind::CustomValidation,
             upper_bound: None,
             lower_bound: None,
+            float_upper_bound: None,
+            float_lower_bound: None,
             expected_string: None,
             expected_string_array: None,
             display_expected: None,
@@ -50,7 +58,9 @@ impl WriteBytes for ValidationNode {
         write_key_value_line(writer, KEY_KIND_STR, self.kind)?;
 
         match self.kind {
-            ValidationSpecKind::IntegerIsBetweenTwoIntegers => {
+            ValidationSpecKind::IntegerIsBetweenTwoIntegers
+            | ValidationSpecKind::ArrayLengthIsBetween
+            | ValidationSpecKind::StringLengthIsBetween => {
                 write_key_value_line(
                     writer,
                     KEY_UPPER_BOUND_STR,
@@ -66,13 +76,31 @@ impl WriteBytes for ValidationNode {
                         .unwrap_or("".to_string()),
                 )?;
             }
-            ValidationSpecKind::StringEquals | ValidationSpecKind::StringHasPrefix => {
+            ValidationSpecKind::FloatIsBetweenTwoFloats => {
                 write_key_value_line(
                     writer,
-                    KEY_EXPECTED_STRING_STR,
-                    self.expected_string.clone().unwrap_or("".to_string()),
-                )?
+                    KEY_FLOAT_UPPER_BOUND_STR,
+                    self.float_upper_bound
+                        .as_ref()
+                        .map(|n| n.to_string())
+                        .unwrap_or("".to_string()),
+                )?;
+                write_key_value_line(
+                    writer,
+                    KEY_FLOAT_LOWER_BOUND_STR,
+                    self.float_lower_bound
+                        .as_ref()
+                        .map(|n| n.to_string())
+                        .unwrap_or("".to_string()),
+                )?;
             }
+            ValidationSpecKind::StringEquals
+            | ValidationSpecKind::StringHasPrefix
+            | ValidationSpecKind::StringMatchesRegex => write_key_value_line(
+                writer,
+                KEY_EXPECTED_STRING_STR,
+                self.expected_string.clone().unwrap_or("".to_string()),
+            )?,
             ValidationSpecKind::StringInStringArray => {
                 write_key_value_line(
                     writer,
@@ -95,8 +123,14 @@ impl WriteBytes for ValidationNode {
                     .map(|id| id.to_string())
                     .unwrap_or("".to_string()),
             )?,
-            ValidationSpecKind::IntegerIsNotEmpty
+            ValidationSpecKind::ArrayItemsAreUnique
+            | ValidationSpecKind::FloatIsNotEmpty
+            | ValidationSpecKind::IntegerIsNotEmpty
+            | ValidationSpecKind::StringIsArn
+            | ValidationSpecKind::StringIsHostname
+            | ValidationSpecKind::StringIsValidCidr
             | ValidationSpecKind::StringIsValidIpAddr
+            | ValidationSpecKind::StringIsValidUrl
             | ValidationSpecKind::StringIsHexColor
             | ValidationSpecKind::StringIsNotEmpty => {}
         }
@@ -114,20 +148,35 @@ impl ReadBytes for ValidationNode {
         let kind = ValidationSpecKind::from_str(&kind_str).map_err(GraphError::parse)?;
         let mut upper_bound = None;
         let mut lower_bound = None;
+        let mut float_upper_bound = None;
+        let mut float_lower_bound = None;
         let mut expected_string = None;
         let mut expected_string_array = None;
         let mut display_expected = None;
         let mut func_unique_id = None;
 
         match kind {
-            ValidationSpecKind::IntegerIsBetweenTwoIntegers => {
+            ValidationSpecKind::IntegerIsBetweenTwoIntegers
+            | ValidationSpecKind::ArrayLengthIsBetween
+            | ValidationSpecKind::StringLengthIsBetween => {
                 let upper_bound_str = read_key_value_line(reader, KEY_UPPER_BOUND_STR)?;
                 upper_bound = Some(i64::from_str(&upper_bound_str).map_err(GraphError::parse)?);
 
                 let lower_bound_str = read_key_value_line(reader, KEY_LOWER_BOUND_STR)?;
                 lower_bound = Some(i64::from_str(&lower_bound_str).map_err(GraphError::parse)?);
             }
-            ValidationSpecKind::StringEquals | ValidationSpecKind::StringHasPrefix => {
+            ValidationSpecKind::FloatIsBetweenTwoFloats => {
+                let float_upper_bound_str = read_key_value_line(reader, KEY_FLOAT_UPPER_BOUND_STR)?;
+                float_upper_bound =
+                    Some(Number::from_str(&float_upper_bound_str).map_err(GraphError::parse)?);
+
+                let float_lower_bound_str = read_key_value_line(reader, KEY_FLOAT_LOWER_BOUND_STR)?;
+                float_lower_bound =
+                    Some(Number::from_str(&float_lower_bound_str).map_err(GraphError::parse)?);
+            }
+            ValidationSpecKind::StringEquals
+            | ValidationSpecKind::StringHasPrefix
+            | ValidationSpecKind::StringMatchesRegex => {
                 let expected_string_str = read_key_value_line(reader, KEY_EXPECTED_STRING_STR)?;
                 if !expected_string_str.is_empty() {
                     expected_string = Some(expected_string_str);
@@ -153,8 +202,14 @@ impl ReadBytes for ValidationNode {
                 func_unique_id =
                     Some(FuncUniqueId::from_str(&func_unique_id_str).map_err(GraphError::parse)?);
             }
-            ValidationSpecKind::IntegerIsNotEmpty
+            ValidationSpecKind::ArrayItemsAreUnique
+            | ValidationSpecKind::FloatIsNotEmpty
+            | ValidationSpecKind::IntegerIsNotEmpty
+            | ValidationSpecKind::StringIsArn
+            | ValidationSpecKind::StringIsHostname
+            | ValidationSpecKind::StringIsValidCidr
             | ValidationSpecKind::StringIsValidIpAddr
+            | ValidationSpecKind::StringIsValidUrl
             | ValidationSpecKind::StringIsHexColor
             | ValidationSpecKind::StringIsNotEmpty => {}
         }
@@ -163,6 +218,8 @@ impl ReadBytes for ValidationNode {
             kind,
             lower_bound,
             upper_bound,
+            float_upper_bound,
+            float_lower_bound,
             expected_string,
             expected_string_array,
             display_expected,
@@ -178,6 +235,28 @@ impl NodeChild for ValidationSpec {
         NodeWithChildren::new(
             NodeKind::Leaf,
             Self::NodeType::Validation(match self {
+                ValidationSpec::ArrayItemsAreUnique => ValidationNode {
+                    kind: ValidationSpecKind::ArrayItemsAreUnique,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::ArrayLengthIsBetween {
+                    lower_bound,
+                    upper_bound,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::ArrayLengthIsBetween,
+                    upper_bound: Some(*upper_bound),
+                    lower_bound: Some(*lower_bound),
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::FloatIsBetweenTwoFloats {
+                    lower_bound,
+                    upper_bound,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::FloatIsBetweenTwoFloats,
+                    float_upper_bound: Some(upper_bound.clone()),
+                    float_lower_bound: Some(lower_bound.clone()),
+                    ..ValidationNode::default()
+                },
                 ValidationSpec::IntegerIsBetweenTwoIntegers {
                     lower_bound,
                     upper_bound,
@@ -187,6 +266,10 @@ impl NodeChild for ValidationSpec {
                     lower_bound: Some(*lower_bound),
                     ..ValidationNode::default()
                 },
+                ValidationSpec::FloatIsNotEmpty => ValidationNode {
+                    kind: ValidationSpecKind::FloatIsNotEmpty,
+                    ..ValidationNode::default()
+                },
                 ValidationSpec::IntegerIsNotEmpty => ValidationNode {
                     kind: ValidationSpecKind::IntegerIsNotEmpty,
                     ..ValidationNode::default()
@@ -222,6 +305,36 @@ impl NodeChild for ValidationSpec {
                     kind: ValidationSpecKind::StringIsNotEmpty,
                     ..ValidationNode::default()
                 },
+                ValidationSpec::StringIsArn => ValidationNode {
+                    kind: ValidationSpecKind::StringIsArn,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringIsHostname => ValidationNode {
+                    kind: ValidationSpecKind::StringIsHostname,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringIsValidCidr => ValidationNode {
+                    kind: ValidationSpecKind::StringIsValidCidr,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringIsValidUrl => ValidationNode {
+                    kind: ValidationSpecKind::StringIsValidUrl,
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringLengthIsBetween {
+                    lower_bound,
+                    upper_bound,
+                } => ValidationNode {
+                    kind: ValidationSpecKind::StringLengthIsBetween,
+                    upper_bound: Some(*upper_bound),
+                    lower_bound: Some(*lower_bound),
+                    ..ValidationNode::default()
+                },
+                ValidationSpec::StringMatchesRegex { pattern } => ValidationNode {
+                    kind: ValidationSpecKind::StringMatchesRegex,
+                    expected_string: Some(pattern.clone()),
+                    ..ValidationNode::default()
+                },
                 ValidationSpec::CustomValidation { func_unique_id } => ValidationNode {
                     kind: ValidationSpecKind::CustomValidation,
                     func_unique_id: Some(*func_unique_id),
@@ -11,25 +11,31 @@ use thiserror::Error;
 
 mod action_func;
 mod attr_func_input;
+mod component;
 mod func;
 mod func_description;
 mod leaf_function;
 mod map_key_func;
 mod prop;
 mod schema;
+mod secret_definition;
 mod si_prop_func;
 mod socket;
 mod validation;
 mod variant;
 
 pub use {
-    action_func::*, attr_func_input::*, func::*, func_description::*, leaf_function::*,
-    map_key_func::*, prop::*, schema::*, si_prop_func::*, socket::*, validation::*, variant::*,
+    action_func::*, attr_func_input::*, component::*, func::*, func_description::*,
+    leaf_function::*, map_key_func::*, prop::*, schema::*, secret_definition::*, si_prop_func::*,
+    socket::*, validation::*, variant::*,
 };
 
 use crate::{
     node::{CategoryNode, PkgNode},
-    spec::{FuncSpec, PkgSpec, SchemaVariantSpecPropRoot, SpecError},
+    spec::{
+        ComponentSpec, FuncSpec, PkgSpec, SchemaVariantSpecPropRoot, SecretDefinitionSpec,
+        SpecError,
+    },
 };
 
 #[remain::sorted]
@@ -186,6 +192,30 @@ impl SiPkg {
         SiPkgSchema::from_graph(graph, node_idx)
     }
 
+    pub fn secret_definitions(&self) -> PkgResult<Vec<SiPkgSecretDefinition>> {
+        let (graph, root_idx) = self.as_petgraph();
+
+        let node_idxs = secret_definition_node_idxs(graph, root_idx)?;
+        let mut secret_definitions = Vec::with_capacity(node_idxs.len());
+        for node_idx in node_idxs {
+            secret_definitions.push(SiPkgSecretDefinition::from_graph(graph, node_idx)?);
+        }
+
+        Ok(secret_definitions)
+    }
+
+    pub fn components(&self) -> PkgResult<Vec<SiPkgComponent>> {
+        let (graph, root_idx) = self.as_petgraph();
+
+        let node_idxs = component_node_idxs(graph, root_idx)?;
+        let mut components = Vec::with_capacity(node_idxs.len());
+        for node_idx in node_idxs {
+            components.push(SiPkgComponent::from_graph(graph, node_idx)?);
+        }
+
+        Ok(components)
+    }
+
     pub fn as_petgraph(&self) -> (&Graph<HashedNode<PkgNode>, ()>, NodeIndex) {
         self.tree.as_petgraph()
     }
@@ -210,6 +240,14 @@ impl SiPkg {
             builder.schema(schema.to_spec().await?);
         }
 
+        for secret_definition in self.secret_definitions()? {
+            builder.secret_definition(SecretDefinitionSpec::try_from(secret_definition)?);
+        }
+
+        for component in self.components()? {
+            builder.component(ComponentSpec::try_from(component)?);
+        }
+
         Ok(builder.build()?)
     }
 }
@@ -269,6 +307,28 @@ fn func_node_idxs(
     category_node_idxs(CategoryNode::Funcs, graph, root_idx)
 }
 
+fn secret_definition_node_idxs(
+    graph: &Graph<HashedNode<PkgNode>, ()>,
+    root_idx: NodeIndex,
+) -> PkgResult<Vec<NodeIndex>> {
+    // The category is only written for packages that have secret definitions
+    match category_node_idxs(CategoryNode::SecretDefinitions, graph, root_idx) {
+        Err(SiPkgError::CategoryNotFound(_)) => Ok(vec![]),
+        result => result,
+    }
+}
+
+fn component_node_idxs(
+    graph: &Graph<HashedNode<PkgNode>, ()>,
+    root_idx: NodeIndex,
+) -> PkgResult<Vec<NodeIndex>> {
+    // The category is only written for packages that have components
+    match category_node_idxs(CategoryNode::Components, graph, root_idx) {
+        Err(SiPkgError::CategoryNotFound(_)) => Ok(vec![]),
+        result => result,
+    }
+}
+
 #[derive(Clone)]
 pub struct Source<'a> {
     graph: &'a Graph<HashedNode<PkgNode>, ()>,
@@ -0,0 +1,204 @@
+use object_tree::{Hash, HashedNode};
+use petgraph::prelude::*;
+
+use super::{PkgResult, SiPkgError, Source};
+
+use crate::{node::PkgNode, ComponentSpec, ConnectionSpec, SchemaVariantSpecComponentType};
+
+#[derive(Clone, Debug)]
+pub struct SiPkgConnection<'a> {
+    from_component_name: String,
+    from_socket_name: String,
+    to_socket_name: String,
+
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgConnection<'a> {
+    fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::Connection(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::CONNECTION_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            from_component_name: node.from_component_name,
+            from_socket_name: node.from_socket_name,
+            to_socket_name: node.to_socket_name,
+
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn from_component_name(&self) -> &str {
+        self.from_component_name.as_ref()
+    }
+
+    pub fn from_socket_name(&self) -> &str {
+        self.from_socket_name.as_ref()
+    }
+
+    pub fn to_socket_name(&self) -> &str {
+        self.to_socket_name.as_ref()
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgConnection<'a>> for ConnectionSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgConnection<'a>) -> Result<Self, Self::Error> {
+        Ok(ConnectionSpec::builder()
+            .from_component_name(value.from_component_name)
+            .from_socket_name(value.from_socket_name)
+            .to_socket_name(value.to_socket_name)
+            .build()?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SiPkgComponent<'a> {
+    name: String,
+    schema_name: String,
+    component_type: Option<SchemaVariantSpecComponentType>,
+    x: String,
+    y: String,
+    width: Option<String>,
+    height: Option<String>,
+    parent: Option<String>,
+    domain: serde_json::Value,
+
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgComponent<'a> {
+    pub fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::Component(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::COMPONENT_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: node.name,
+            schema_name: node.schema_name,
+            component_type: node.component_type,
+            x: node.x,
+            y: node.y,
+            width: node.width,
+            height: node.height,
+            parent: node.parent,
+            domain: node.domain,
+
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn connections(&self) -> PkgResult<Vec<SiPkgConnection>> {
+        let mut connections = vec![];
+        for idx in self
+            .source
+            .graph
+            .neighbors_directed(self.source.node_idx, Outgoing)
+        {
+            connections.push(SiPkgConnection::from_graph(self.source.graph, idx)?);
+        }
+
+        Ok(connections)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn schema_name(&self) -> &str {
+        self.schema_name.as_ref()
+    }
+
+    pub fn component_type(&self) -> Option<SchemaVariantSpecComponentType> {
+        self.component_type
+    }
+
+    pub fn x(&self) -> &str {
+        self.x.as_ref()
+    }
+
+    pub fn y(&self) -> &str {
+        self.y.as_ref()
+    }
+
+    pub fn width(&self) -> Option<&str> {
+        self.width.as_deref()
+    }
+
+    pub fn height(&self) -> Option<&str> {
+        self.height.as_deref()
+    }
+
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    pub fn domain(&self) -> &serde_json::Value {
+        &self.domain
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgComponent<'a>> for ComponentSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgComponent<'a>) -> Result<Self, Self::Error> {
+        let mut builder = ComponentSpec::builder();
+        for connection in value.connections()? {
+            builder.connection(ConnectionSpec::try_from(connection)?);
+        }
+
+        Ok(builder
+            .name(value.name)
+            .schema_name(value.schema_name)
+            .component_type(value.component_type)
+            .x(value.x)
+            .y(value.y)
+            .width(value.width)
+            .height(value.height)
+            .parent(value.parent)
+            .domain(value.domain)
+            .build()?)
+    }
+}
@@ -88,6 +88,7 @@ pub struct SiPkgFunc<'a> {
     display_name: Option<String>,
     description: Option<String>,
     handler: String,
+    plan_handler: Option<String>,
     code_base64: String,
     backend_kind: FuncSpecBackendKind,
     response_type: FuncSpecBackendResponseType,
@@ -120,6 +121,7 @@ impl<'a> SiPkgFunc<'a> {
             display_name: func_node.display_name,
             description: func_node.description,
             handler: func_node.handler,
+            plan_handler: func_node.plan_handler,
             code_base64: func_node.code_base64,
             backend_kind: func_node.backend_kind,
             response_type: func_node.response_type,
@@ -160,6 +162,10 @@ impl<'a> SiPkgFunc<'a> {
         self.handler.as_ref()
     }
 
+    pub fn plan_handler(&self) -> Option<&str> {
+        self.plan_handler.as_deref()
+    }
+
     pub fn code_base64(&self) -> &str {
         self.code_base64.as_ref()
     }
@@ -215,6 +221,10 @@ impl<'a> TryFrom<SiPkgFunc<'a>> for FuncSpec {
             builder.description(description);
         }
 
+        if let Some(plan_handler) = &value.plan_handler {
+            builder.plan_handler(plan_handler);
+        }
+
         for argument in value.arguments()? {
             builder.argument(argument.try_into()?);
         }
@@ -34,6 +34,17 @@ pub enum SiPkgProp<'a> {
         hash: Hash,
         source: Source<'a>,
     },
+    Float {
+        name: String,
+        default_value: Option<f64>,
+        func_unique_id: Option<FuncUniqueId>,
+        widget_kind: PropSpecWidgetKind,
+        widget_options: Option<serde_json::Value>,
+        doc_link: Option<Url>,
+        hidden: bool,
+        hash: Hash,
+        source: Source<'a>,
+    },
     Map {
         name: String,
         default_value: Option<serde_json::Value>,
@@ -88,6 +99,7 @@ macro_rules! impl_prop_child_from_graph {
                 | SiPkgProp::Array { source, .. }
                 | SiPkgProp::String { source, .. }
                 | SiPkgProp::Number { source, .. }
+                | SiPkgProp::Float { source, .. }
                 | SiPkgProp::Object { source, .. }
                 | SiPkgProp::Boolean { source, .. } => {
                     let mut entries = vec![];
@@ -184,6 +196,25 @@ impl<'a> SiPkgProp<'a> {
                 hash,
                 source,
             },
+            PropNode::Float {
+                name,
+                default_value,
+                func_unique_id,
+                widget_kind,
+                widget_options,
+                hidden,
+                doc_link,
+            } => Self::Float {
+                name,
+                default_value,
+                func_unique_id,
+                widget_kind,
+                widget_options,
+                hidden,
+                doc_link,
+                hash,
+                source,
+            },
             PropNode::Boolean {
                 name,
                 default_value,
@@ -274,6 +305,7 @@ impl<'a> SiPkgProp<'a> {
         match self {
             Self::String { func_unique_id, .. }
             | Self::Number { func_unique_id, .. }
+            | Self::Float { func_unique_id, .. }
             | Self::Boolean { func_unique_id, .. }
             | Self::Map { func_unique_id, .. }
             | Self::Array { func_unique_id, .. }
@@ -285,6 +317,7 @@ impl<'a> SiPkgProp<'a> {
         match self {
             Self::String { name, .. }
             | Self::Number { name, .. }
+            | Self::Float { name, .. }
             | Self::Boolean { name, .. }
             | Self::Map { name, .. }
             | Self::Array { name, .. }
@@ -296,6 +329,7 @@ impl<'a> SiPkgProp<'a> {
         match self {
             Self::String { hash, .. }
             | Self::Number { hash, .. }
+            | Self::Float { hash, .. }
             | Self::Boolean { hash, .. }
             | Self::Map { hash, .. }
             | Self::Array { hash, .. }
@@ -307,6 +341,7 @@ impl<'a> SiPkgProp<'a> {
         match self {
             Self::String { source, .. }
             | Self::Number { source, .. }
+            | Self::Float { source, .. }
             | Self::Boolean { source, .. }
             | Self::Map { source, .. }
             | Self::Array { source, .. }
@@ -0,0 +1,73 @@
+use object_tree::{Hash, HashedNode};
+use petgraph::prelude::*;
+
+use super::{PkgResult, SiPkgError, Source};
+
+use crate::{node::PkgNode, SecretDefinitionSpec};
+
+#[derive(Clone, Debug)]
+pub struct SiPkgSecretDefinition<'a> {
+    name: String,
+    description: String,
+    schema: serde_json::Value,
+    hash: Hash,
+    source: Source<'a>,
+}
+
+impl<'a> SiPkgSecretDefinition<'a> {
+    pub fn from_graph(
+        graph: &'a Graph<HashedNode<PkgNode>, ()>,
+        node_idx: NodeIndex,
+    ) -> PkgResult<Self> {
+        let hashed_node = &graph[node_idx];
+        let node = match hashed_node.inner() {
+            PkgNode::SecretDefinition(node) => node.clone(),
+            unexpected => {
+                return Err(SiPkgError::UnexpectedPkgNodeType(
+                    PkgNode::SECRET_DEFINITION_KIND_STR,
+                    unexpected.node_kind_str(),
+                ))
+            }
+        };
+
+        Ok(Self {
+            name: node.name,
+            description: node.description,
+            schema: node.schema,
+            hash: hashed_node.hash(),
+            source: Source::new(graph, node_idx),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_ref()
+    }
+
+    pub fn description(&self) -> &str {
+        self.description.as_ref()
+    }
+
+    pub fn schema(&self) -> &serde_json::Value {
+        &self.schema
+    }
+
+    pub fn hash(&self) -> Hash {
+        self.hash
+    }
+
+    pub fn source(&self) -> &Source<'a> {
+        &self.source
+    }
+}
+
+impl<'a> TryFrom<SiPkgSecretDefinition<'a>> for SecretDefinitionSpec {
+    type Error = SiPkgError;
+
+    fn try_from(value: SiPkgSecretDefinition<'a>) -> Result<Self, Self::Error> {
+        Ok(SecretDefinitionSpec::builder()
+            .name(value.name)
+            .description(value.description)
+            .schema(value.schema)
+            .build()?)
+    }
+}
@@ -1,5 +1,6 @@
 use object_tree::{Hash, HashedNode};
 use petgraph::prelude::*;
+use serde_json::Number;
 
 use super::{PkgResult, SiPkgError, Source};
 
@@ -8,11 +9,31 @@ use crate::{node::PkgNode, ValidationSpec, ValidationSpecKind};
 #[remain::sorted]
 #[derive(Clone, Debug)]
 pub enum SiPkgValidation<'a> {
+    ArrayItemsAreUnique {
+        hash: Hash,
+        source: Source<'a>,
+    },
+    ArrayLengthIsBetween {
+        lower_bound: i64,
+        upper_bound: i64,
+        hash: Hash,
+        source: Source<'a>,
+    },
     CustomValidation {
         func_unique_id: Hash,
         hash: Hash,
         source: Source<'a>,
     },
+    FloatIsBetweenTwoFloats {
+        lower_bound: Number,
+        upper_bound: Number,
+        hash: Hash,
+        source: Source<'a>,
+    },
+    FloatIsNotEmpty {
+        hash: Hash,
+        source: Source<'a>,
+    },
     IntegerIsBetweenTwoIntegers {
         lower_bound: i64,
         upper_bound: i64,
@@ -39,18 +60,45 @@ pub enum SiPkgValidation<'a> {
         hash: Hash,
         source: Source<'a>,
     },
+    StringIsArn {
+        hash: Hash,
+        source: Source<'a>,
+    },
     StringIsHexColor {
         hash: Hash,
         source: Source<'a>,
     },
+    StringIsHostname {
+        hash: Hash,
+        source: Source<'a>,
+    },
     StringIsNotEmpty {
         hash: Hash,
         source: Source<'a>,
     },
+    StringIsValidCidr {
+        hash: Hash,
+        source: Source<'a>,
+    },
     StringIsValidIpAddr {
         hash: Hash,
         source: Source<'a>,
     },
+    StringIsValidUrl {
+        hash: Hash,
+        source: Source<'a>,
+    },
+    StringLengthIsBetween {
+        lower_bound: i64,
+        upper_bound: i64,
+        hash: Hash,
+        source: Source<'a>,
+    },
+    StringMatchesRegex {
+        pattern: String,
+        hash: Hash,
+        source: Source<'a>,
+    },
 }
 
 impl<'a> SiPkgValidation<'a> {
@@ -73,6 +121,34 @@ impl<'a> SiPkgValidation<'a> {
         let source = Source::new(graph, node_idx);
 
         Ok(match node.kind {
+            ValidationSpecKind::ArrayItemsAreUnique => {
+                SiPkgValidation::ArrayItemsAreUnique { hash, source }
+            }
+            ValidationSpecKind::ArrayLengthIsBetween => SiPkgValidation::ArrayLengthIsBetween {
+                upper_bound: node.upper_bound.ok_or(SiPkgError::ValidationMissingField(
+                    "upper_bound".to_string(),
+                ))?,
+                lower_bound: node.lower_bound.ok_or(SiPkgError::ValidationMissingField(
+                    "lower_bound".to_string(),
+                ))?,
+                hash,
+                source,
+            },
+            ValidationSpecKind::FloatIsBetweenTwoFloats => {
+                SiPkgValidation::FloatIsBetweenTwoFloats {
+                    upper_bound: node.float_upper_bound.ok_or(
+                        SiPkgError::ValidationMissingField("float_upper_bound".to_string()),
+                    )?,
+                    lower_bound: node.float_lower_bound.ok_or(
+                        SiPkgError::ValidationMissingField("float_lower_bound".to_string()),
+                    )?,
+                    hash,
+                    source,
+                }
+            }
+            ValidationSpecKind::FloatIsNotEmpty => {
+                SiPkgValidation::FloatIsNotEmpty { hash, source }
+            }
             ValidationSpecKind::IntegerIsBetweenTwoIntegers => {
                 SiPkgValidation::IntegerIsBetweenTwoIntegers {
                     upper_bound: node.upper_bound.ok_or(SiPkgError::ValidationMissingField(
@@ -128,6 +204,35 @@ impl<'a> SiPkgValidation<'a> {
             ValidationSpecKind::StringIsNotEmpty => {
                 SiPkgValidation::StringIsNotEmpty { hash, source }
             }
+            ValidationSpecKind::StringIsArn => SiPkgValidation::StringIsArn { hash, source },
+            ValidationSpecKind::StringIsHostname => {
+                SiPkgValidation::StringIsHostname { hash, source }
+            }
+            ValidationSpecKind::StringIsValidCidr => {
+                SiPkgValidation::StringIsValidCidr { hash, source }
+            }
+            ValidationSpecKind::StringIsValidUrl => {
+                SiPkgValidation::StringIsValidUrl { hash, source }
+            }
+            ValidationSpecKind::StringLengthIsBetween => SiPkgValidation::StringLengthIsBetween {
+                upper_bound: node.upper_bound.ok_or(SiPkgError::ValidationMissingField(
+                    "upper_bound".to_string(),
+                ))?,
+                lower_bound: node.lower_bound.ok_or(SiPkgError::ValidationMissingField(
+                    "lower_bound".to_string(),
+                ))?,
+                hash,
+                source,
+            },
+            ValidationSpecKind::StringMatchesRegex => SiPkgValidation::StringMatchesRegex {
+                pattern: node
+                    .expected_string
+                    .ok_or(SiPkgError::ValidationMissingField(
+                        "expected_string".to_string(),
+                    ))?,
+                hash,
+                source,
+            },
             ValidationSpecKind::CustomValidation => {
                 SiPkgValidation::CustomValidation {
                     func_unique_id: node.func_unique_id.ok_or(
@@ -148,6 +253,30 @@ impl<'a> TryFrom<SiPkgValidation<'a>> for ValidationSpec {
         let mut builder = ValidationSpec::builder();
 
         match value {
+            SiPkgValidation::ArrayItemsAreUnique { .. } => {
+                builder.kind(ValidationSpecKind::ArrayItemsAreUnique);
+            }
+            SiPkgValidation::ArrayLengthIsBetween {
+                lower_bound,
+                upper_bound,
+                ..
+            } => {
+                builder.kind(ValidationSpecKind::ArrayLengthIsBetween);
+                builder.lower_bound(lower_bound);
+                builder.upper_bound(upper_bound);
+            }
+            SiPkgValidation::FloatIsBetweenTwoFloats {
+                lower_bound,
+                upper_bound,
+                ..
+            } => {
+                builder.kind(ValidationSpecKind::FloatIsBetweenTwoFloats);
+                builder.float_lower_bound(lower_bound);
+                builder.float_upper_bound(upper_bound);
+            }
+            SiPkgValidation::FloatIsNotEmpty { .. } => {
+                builder.kind(ValidationSpecKind::FloatIsNotEmpty);
+            }
             SiPkgValidation::IntegerIsBetweenTwoIntegers {
                 lower_bound,
                 upper_bound,
@@ -190,6 +319,31 @@ impl<'a> TryFrom<SiPkgValidation<'a>> for ValidationSpec {
             SiPkgValidation::StringIsNotEmpty { .. } => {
                 builder.kind(ValidationSpecKind::StringIsNotEmpty);
             }
+            SiPkgValidation::StringIsArn { .. } => {
+                builder.kind(ValidationSpecKind::StringIsArn);
+            }
+            SiPkgValidation::StringIsHostname { .. } => {
+                builder.kind(ValidationSpecKind::StringIsHostname);
+            }
+            SiPkgValidation::StringIsValidCidr { .. } => {
+                builder.kind(ValidationSpecKind::StringIsValidCidr);
+            }
+            SiPkgValidation::StringIsValidUrl { .. } => {
+                builder.kind(ValidationSpecKind::StringIsValidUrl);
+            }
+            SiPkgValidation::StringLengthIsBetween {
+                lower_bound,
+                upper_bound,
+                ..
+            } => {
+                builder.kind(ValidationSpecKind::StringLengthIsBetween);
+                builder.lower_bound(lower_bound);
+                builder.upper_bound(upper_bound);
+            }
+            SiPkgValidation::StringMatchesRegex { pattern, .. } => {
+                builder.kind(ValidationSpecKind::StringMatchesRegex);
+                builder.expected_string(pattern);
+            }
         }
 
         Ok(builder.build()?)
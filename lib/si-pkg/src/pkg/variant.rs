@@ -296,7 +296,7 @@ impl<'a> SiPkgSchemaVariant<'a> {
                     }
                     _ => {
                         return Err(SiPkgError::prop_tree_invalid(
-                            "Leaf prop (String, Number, Boolean) cannot have children",
+                            "Leaf prop (String, Number, Float, Boolean) cannot have children",
                         ));
                     }
                 }
@@ -407,6 +407,12 @@ async fn create_prop_stack(
                 builder.default_value(serde_json::to_value(dv)?);
             }
         }
+        SiPkgProp::Float { default_value, .. } => {
+            builder.kind(PropSpecKind::Float);
+            if let Some(dv) = default_value {
+                builder.default_value(serde_json::to_value(dv)?);
+            }
+        }
         SiPkgProp::Object { .. } => {
             builder.kind(PropSpecKind::Object);
         }
@@ -454,6 +460,14 @@ async fn create_prop_stack(
             hidden,
             ..
         }
+        | SiPkgProp::Float {
+            name,
+            func_unique_id,
+            widget_kind,
+            widget_options,
+            hidden,
+            ..
+        }
         | SiPkgProp::Object {
             name,
             func_unique_id,
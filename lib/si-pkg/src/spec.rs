@@ -5,20 +5,23 @@ use thiserror::Error;
 
 mod action_func;
 mod attr_func_input;
+mod component;
 mod func;
 mod func_description;
 mod leaf_function;
 mod map_key_func;
 mod prop;
 mod schema;
+mod secret_definition;
 mod si_prop_func;
 mod socket;
 mod validation;
 mod variant;
 
 pub use {
-    action_func::*, attr_func_input::*, func::*, func_description::*, leaf_function::*,
-    map_key_func::*, prop::*, schema::*, si_prop_func::*, socket::*, validation::*, variant::*,
+    action_func::*, attr_func_input::*, component::*, func::*, func_description::*,
+    leaf_function::*, map_key_func::*, prop::*, schema::*, secret_definition::*, si_prop_func::*,
+    socket::*, validation::*, variant::*,
 };
 
 #[derive(Builder, Clone, Debug, Deserialize, Serialize)]
@@ -42,6 +45,14 @@ pub struct PkgSpec {
 
     #[builder(setter(each(name = "func", into)), default)]
     pub funcs: Vec<FuncSpec>,
+
+    #[builder(setter(each(name = "secret_definition", into)), default)]
+    #[serde(default)]
+    pub secret_definitions: Vec<SecretDefinitionSpec>,
+
+    #[builder(setter(each(name = "component", into)), default)]
+    #[serde(default)]
+    pub components: Vec<ComponentSpec>,
 }
 
 impl PkgSpec {
@@ -82,6 +93,24 @@ impl PkgSpecBuilder {
         let converted: FuncSpec = item.try_into()?;
         Ok(self.func(converted))
     }
+
+    #[allow(unused_mut)]
+    pub fn try_secret_definition<I>(&mut self, item: I) -> Result<&mut Self, I::Error>
+    where
+        I: TryInto<SecretDefinitionSpec>,
+    {
+        let converted: SecretDefinitionSpec = item.try_into()?;
+        Ok(self.secret_definition(converted))
+    }
+
+    #[allow(unused_mut)]
+    pub fn try_component<I>(&mut self, item: I) -> Result<&mut Self, I::Error>
+    where
+        I: TryInto<ComponentSpec>,
+    {
+        let converted: ComponentSpec = item.try_into()?;
+        Ok(self.component(converted))
+    }
 }
 
 impl TryFrom<PkgSpecBuilder> for PkgSpec {
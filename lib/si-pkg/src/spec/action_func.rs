@@ -23,6 +23,7 @@ pub enum ActionFuncSpecKind {
     Refresh,
     Other,
     Delete,
+    Import,
 }
 
 #[derive(Builder, Clone, Debug, Deserialize, Serialize)]
@@ -0,0 +1,72 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::{SchemaVariantSpecComponentType, SpecError};
+
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct ComponentSpec {
+    /// Identifies the component within the package, so it has to be unique there.
+    #[builder(setter(into))]
+    pub name: String,
+
+    /// The name of the schema to create the component from. The schema must either be in the
+    /// package or already installed.
+    #[builder(setter(into))]
+    pub schema_name: String,
+
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub component_type: Option<SchemaVariantSpecComponentType>,
+
+    #[builder(setter(into))]
+    pub x: String,
+    #[builder(setter(into))]
+    pub y: String,
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub width: Option<String>,
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub height: Option<String>,
+
+    /// The name of the frame component this component sits in, if any.
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub parent: Option<String>,
+
+    /// The values of the component's domain tree.
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub domain: serde_json::Value,
+
+    /// The connections coming in to this component from other components in the package.
+    #[builder(setter(each(name = "connection"), into), default)]
+    #[serde(default)]
+    pub connections: Vec<ConnectionSpec>,
+}
+
+impl ComponentSpec {
+    pub fn builder() -> ComponentSpecBuilder {
+        ComponentSpecBuilder::default()
+    }
+}
+
+#[derive(Builder, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct ConnectionSpec {
+    #[builder(setter(into))]
+    pub from_component_name: String,
+    #[builder(setter(into))]
+    pub from_socket_name: String,
+    #[builder(setter(into))]
+    pub to_socket_name: String,
+}
+
+impl ConnectionSpec {
+    pub fn builder() -> ConnectionSpecBuilder {
+        ConnectionSpecBuilder::default()
+    }
+}
@@ -26,6 +26,7 @@ pub enum FuncArgumentKind {
     Any,
     Array,
     Boolean,
+    Float,
     Integer,
     Map,
     Object,
@@ -57,6 +58,7 @@ pub enum FuncSpecBackendKind {
     Array,
     Boolean,
     Diff,
+    Float,
     Identity,
     Integer,
     JsAction,
@@ -80,6 +82,7 @@ pub enum FuncSpecBackendResponseType {
     Boolean,
     CodeGeneration,
     Confirmation,
+    Float,
     Identity,
     Integer,
     Json,
@@ -107,6 +110,9 @@ pub struct FuncSpec {
     pub description: Option<String>,
     #[builder(setter(into))]
     pub handler: String,
+    /// An optional second entry point for action funcs that plans their changes.
+    #[builder(setter(into, strip_option), default)]
+    pub plan_handler: Option<String>,
     #[builder(setter(into))]
     pub code_base64: String,
     #[builder(setter(into))]
@@ -166,6 +172,13 @@ impl FuncSpecBuilder {
                 .as_bytes(),
         );
         bytes.extend(self.handler.clone().unwrap_or("".to_string()).as_bytes());
+        bytes.extend(
+            self.plan_handler
+                .clone()
+                .unwrap_or(Some("".to_string()))
+                .unwrap_or("".to_string())
+                .as_bytes(),
+        );
         bytes.extend(
             self.code_base64
                 .clone()
@@ -39,7 +39,9 @@ impl From<&PropSpec> for PropSpecWidgetKind {
         match node {
             PropSpec::Array { .. } => Self::Array,
             PropSpec::Boolean { .. } => Self::Checkbox,
-            PropSpec::String { .. } | PropSpec::Number { .. } => Self::Text,
+            PropSpec::String { .. } | PropSpec::Number { .. } | PropSpec::Float { .. } => {
+                Self::Text
+            }
             PropSpec::Object { .. } => Self::Header,
             PropSpec::Map { .. } => Self::Map,
         }
@@ -76,6 +78,18 @@ pub enum PropSpec {
         doc_link: Option<Url>,
     },
     #[serde(rename_all = "camelCase")]
+    Float {
+        name: String,
+        default_value: Option<f64>,
+        validations: Option<Vec<ValidationSpec>>,
+        func_unique_id: Option<FuncUniqueId>,
+        inputs: Option<Vec<AttrFuncInputSpec>>,
+        widget_kind: Option<PropSpecWidgetKind>,
+        widget_options: Option<serde_json::Value>,
+        hidden: Option<bool>,
+        doc_link: Option<Url>,
+    },
+    #[serde(rename_all = "camelCase")]
     Map {
         name: String,
         default_value: Option<serde_json::Value>,
@@ -139,6 +153,7 @@ impl PropSpec {
 pub enum PropSpecKind {
     Array,
     Boolean,
+    Float,
     Map,
     Number,
     Object,
@@ -231,6 +246,13 @@ impl PropSpecBuilder {
         self
     }
 
+    /// Makes this prop a reference to a secret of the given kind, shown as a secret select
+    /// widget that only offers secrets of that kind.
+    pub fn secret_kind(&mut self, value: impl Into<String>) -> &mut Self {
+        self.widget_kind(PropSpecWidgetKind::SecretSelect)
+            .widget_options(serde_json::json!({ "secretKind": value.into() }))
+    }
+
     pub fn hidden(&mut self, value: impl Into<bool>) -> &mut Self {
         self.hidden = value.into();
         self
@@ -319,6 +341,28 @@ impl PropSpecBuilder {
                     hidden: Some(hidden),
                     doc_link,
                 },
+                PropSpecKind::Float => PropSpec::Float {
+                    name,
+                    default_value: match &self.default_value {
+                        Some(value) => {
+                            if value.is_number() {
+                                value.as_f64()
+                            } else {
+                                return Err(SpecError::ValidationError(
+                                    "Float props must get a number as a default value".to_string(),
+                                ));
+                            }
+                        }
+                        None => None,
+                    },
+                    validations: Some(validations),
+                    func_unique_id,
+                    inputs: Some(inputs),
+                    widget_kind,
+                    widget_options,
+                    hidden: Some(hidden),
+                    doc_link,
+                },
                 PropSpecKind::Boolean => PropSpec::Boolean {
                     name,
                     default_value: match &self.default_value {
@@ -0,0 +1,27 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::SpecError;
+
+#[derive(Builder, Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[builder(build_fn(error = "SpecError"))]
+pub struct SecretDefinitionSpec {
+    /// The secret kind, which secrets refer to by name (e.g. "githubToken").
+    #[builder(setter(into))]
+    pub name: String,
+
+    #[builder(setter(into), default)]
+    #[serde(default)]
+    pub description: String,
+
+    /// A JSON schema describing the fields of a secret of this kind.
+    #[builder(setter(into))]
+    pub schema: serde_json::Value,
+}
+
+impl SecretDefinitionSpec {
+    pub fn builder() -> SecretDefinitionSpecBuilder {
+        SecretDefinitionSpecBuilder::default()
+    }
+}
@@ -1,5 +1,6 @@
 use derive_builder::UninitializedFieldError;
 use serde::{Deserialize, Serialize};
+use serde_json::Number;
 use strum::{Display, EnumIter, EnumString};
 
 use object_tree::Hash;
@@ -10,9 +11,19 @@ use super::SpecError;
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum ValidationSpec {
+    ArrayItemsAreUnique,
+    ArrayLengthIsBetween {
+        lower_bound: i64,
+        upper_bound: i64,
+    },
     CustomValidation {
         func_unique_id: Hash,
     },
+    FloatIsBetweenTwoFloats {
+        lower_bound: Number,
+        upper_bound: Number,
+    },
+    FloatIsNotEmpty,
     IntegerIsBetweenTwoIntegers {
         lower_bound: i64,
         upper_bound: i64,
@@ -28,9 +39,20 @@ pub enum ValidationSpec {
         expected: Vec<String>,
         display_expected: bool,
     },
+    StringIsArn,
     StringIsHexColor,
+    StringIsHostname,
     StringIsNotEmpty,
+    StringIsValidCidr,
     StringIsValidIpAddr,
+    StringIsValidUrl,
+    StringLengthIsBetween {
+        lower_bound: i64,
+        upper_bound: i64,
+    },
+    StringMatchesRegex {
+        pattern: String,
+    },
 }
 
 impl ValidationSpec {
@@ -44,15 +66,25 @@ impl ValidationSpec {
     Clone, Copy, Debug, Eq, Hash, PartialEq, EnumIter, EnumString, Display, Serialize, Deserialize,
 )]
 pub enum ValidationSpecKind {
+    ArrayItemsAreUnique,
+    ArrayLengthIsBetween,
     CustomValidation,
+    FloatIsBetweenTwoFloats,
+    FloatIsNotEmpty,
     IntegerIsBetweenTwoIntegers,
     IntegerIsNotEmpty,
     StringEquals,
     StringHasPrefix,
     StringInStringArray,
+    StringIsArn,
     StringIsHexColor,
+    StringIsHostname,
     StringIsNotEmpty,
+    StringIsValidCidr,
     StringIsValidIpAddr,
+    StringIsValidUrl,
+    StringLengthIsBetween,
+    StringMatchesRegex,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -60,6 +92,8 @@ pub struct ValidationSpecBuilder {
     kind: Option<ValidationSpecKind>,
     upper_bound: Option<i64>,
     lower_bound: Option<i64>,
+    float_upper_bound: Option<Number>,
+    float_lower_bound: Option<Number>,
     expected_string: Option<String>,
     expected_string_array: Option<Vec<String>>,
     display_expected: Option<bool>,
@@ -82,6 +116,16 @@ impl ValidationSpecBuilder {
         self
     }
 
+    pub fn float_upper_bound(&mut self, float_upper_bound: Number) -> &mut Self {
+        self.float_upper_bound = Some(float_upper_bound);
+        self
+    }
+
+    pub fn float_lower_bound(&mut self, float_lower_bound: Number) -> &mut Self {
+        self.float_lower_bound = Some(float_lower_bound);
+        self
+    }
+
     pub fn expected_string(&mut self, expected_string: String) -> &mut Self {
         self.expected_string = Some(expected_string);
         self
@@ -105,6 +149,27 @@ impl ValidationSpecBuilder {
     pub fn build(&self) -> Result<ValidationSpec, SpecError> {
         Ok(match self.kind {
             Some(kind) => match kind {
+                ValidationSpecKind::ArrayItemsAreUnique => ValidationSpec::ArrayItemsAreUnique,
+                ValidationSpecKind::ArrayLengthIsBetween => ValidationSpec::ArrayLengthIsBetween {
+                    lower_bound: self
+                        .lower_bound
+                        .ok_or(UninitializedFieldError::from("lower_bound"))?,
+                    upper_bound: self
+                        .upper_bound
+                        .ok_or(UninitializedFieldError::from("upper_bound"))?,
+                },
+                ValidationSpecKind::FloatIsBetweenTwoFloats => {
+                    ValidationSpec::FloatIsBetweenTwoFloats {
+                        lower_bound: self
+                            .float_lower_bound
+                            .clone()
+                            .ok_or(UninitializedFieldError::from("float_lower_bound"))?,
+                        upper_bound: self
+                            .float_upper_bound
+                            .clone()
+                            .ok_or(UninitializedFieldError::from("float_upper_bound"))?,
+                    }
+                }
                 ValidationSpecKind::IntegerIsBetweenTwoIntegers => {
                     ValidationSpec::IntegerIsBetweenTwoIntegers {
                         lower_bound: self
@@ -115,6 +180,7 @@ impl ValidationSpecBuilder {
                             .ok_or(UninitializedFieldError::from("lower_bound"))?,
                     }
                 }
+                ValidationSpecKind::FloatIsNotEmpty => ValidationSpec::FloatIsNotEmpty,
                 ValidationSpecKind::IntegerIsNotEmpty => ValidationSpec::IntegerIsNotEmpty,
                 ValidationSpecKind::StringEquals => ValidationSpec::StringEquals {
                     expected: self
@@ -142,6 +208,27 @@ impl ValidationSpecBuilder {
                 ValidationSpecKind::StringIsValidIpAddr => ValidationSpec::StringIsValidIpAddr,
                 ValidationSpecKind::StringIsHexColor => ValidationSpec::StringIsHexColor,
                 ValidationSpecKind::StringIsNotEmpty => ValidationSpec::StringIsNotEmpty,
+                ValidationSpecKind::StringIsArn => ValidationSpec::StringIsArn,
+                ValidationSpecKind::StringIsHostname => ValidationSpec::StringIsHostname,
+                ValidationSpecKind::StringIsValidCidr => ValidationSpec::StringIsValidCidr,
+                ValidationSpecKind::StringIsValidUrl => ValidationSpec::StringIsValidUrl,
+                ValidationSpecKind::StringLengthIsBetween => {
+                    ValidationSpec::StringLengthIsBetween {
+                        lower_bound: self
+                            .lower_bound
+                            .ok_or(UninitializedFieldError::from("lower_bound"))?,
+                        upper_bound: self
+                            .upper_bound
+                            .ok_or(UninitializedFieldError::from("upper_bound"))?,
+                    }
+                }
+                ValidationSpecKind::StringMatchesRegex => ValidationSpec::StringMatchesRegex {
+                    pattern: self
+                        .expected_string
+                        .as_ref()
+                        .ok_or(UninitializedFieldError::from("expected_string"))?
+                        .to_string(),
+                },
                 ValidationSpecKind::CustomValidation => ValidationSpec::CustomValidation {
                     func_unique_id: self
                         .func_unique_id
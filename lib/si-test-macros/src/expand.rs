@@ -372,6 +372,7 @@ pub(crate) trait FnSetupExpander {
         let pinga_server = pinga_server.as_ref();
 
         self.code_extend(quote! {
+            #pinga_server.setup_job_queue().await?;
             ::tokio::spawn(#pinga_server.run());
         });
         self.set_start_pinga_server(Some(()));
@@ -6,18 +6,20 @@ use thiserror::Error;
 use tokio::sync::mpsc;
 
 use veritech_core::{
-    nats_action_run_subject, nats_reconciliation_subject, nats_resolver_function_subject,
-    nats_schema_variant_definition_subject, nats_subject, nats_validation_subject,
-    reply_mailbox_for_output, reply_mailbox_for_result, FINAL_MESSAGE_HEADER_KEY,
+    nats_action_run_subject, nats_cancel_execution_subject, nats_reconciliation_subject,
+    nats_resolver_function_subject, nats_schema_variant_definition_subject, nats_subject,
+    nats_validation_subject, reply_mailbox_for_output, reply_mailbox_for_result,
+    FINAL_MESSAGE_HEADER_KEY,
 };
 
 pub use cyclone_core::{
     ActionRunRequest, ActionRunResultSuccess, ComponentKind, ComponentView, EncryptionKey,
-    EncryptionKeyError, FunctionResult, FunctionResultFailure, OutputStream, ReconciliationRequest,
-    ReconciliationResultSuccess, ResolverFunctionComponent, ResolverFunctionRequest,
-    ResolverFunctionResponseType, ResolverFunctionResultSuccess, ResourceStatus,
-    SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess, SensitiveContainer,
-    ValidationRequest, ValidationResultSuccess,
+    EncryptionKeyError, ExecutionLimits, FunctionResult, FunctionResultFailure,
+    FunctionResultFailureError, OutputStream, ReconciliationRequest, ReconciliationResultSuccess,
+    ResolverFunctionComponent, ResolverFunctionRequest, ResolverFunctionResponseType,
+    ResolverFunctionResultSuccess, ResourceStatus, SchemaVariantDefinitionRequest,
+    SchemaVariantDefinitionResultSuccess, SensitiveContainer, ValidationRequest,
+    ValidationResultSuccess,
 };
 use si_data_nats::NatsClient;
 
@@ -199,6 +201,20 @@ impl Client {
         .await
     }
 
+    /// Asks veritech to cancel the function execution with the given id, if it is still running.
+    ///
+    /// The execution's caller receives a failed result with a `"cancelled"` kind.
+    #[instrument(name = "client.cancel_execution", skip(self))]
+    pub async fn cancel_execution(&self, execution_id: &str) -> ClientResult<()> {
+        self.nats
+            .publish(
+                nats_cancel_execution_subject(self.nats_subject_prefix(), execution_id),
+                vec![],
+            )
+            .await?;
+        Ok(())
+    }
+
     async fn execute_request<R, S>(
         &self,
         subject: impl Into<String>,
@@ -1,14 +1,16 @@
 use chrono::Utc;
 use deadpool_cyclone::{
     instance::cyclone::LocalUdsInstanceSpec, ActionRunRequest, ActionRunResultSuccess,
-    CycloneClient, FunctionResult, FunctionResultFailure, FunctionResultFailureError, Manager,
-    Pool, ProgressMessage, ReconciliationRequest, ReconciliationResultSuccess,
-    ResolverFunctionRequest, ResolverFunctionResultSuccess, SchemaVariantDefinitionRequest,
-    SchemaVariantDefinitionResultSuccess, ValidationRequest, ValidationResultSuccess,
+    CycloneClient, ExecutionError, ExecutionStarted, FunctionResult, FunctionResultFailure,
+    FunctionResultFailureError, Manager, Pool, ProgressMessage, ReconciliationRequest,
+    ReconciliationResultSuccess, ResolverFunctionRequest, ResolverFunctionResultSuccess,
+    SchemaVariantDefinitionRequest, SchemaVariantDefinitionResultSuccess, UnixStream,
+    ValidationRequest, ValidationResultSuccess,
 };
 use futures::{channel::oneshot, join, StreamExt};
 use nats_subscriber::Request;
-use si_data_nats::NatsClient;
+use serde::de::DeserializeOwned;
+use si_data_nats::{NatsClient, Subscription};
 use std::io;
 use telemetry::prelude::*;
 use thiserror::Error;
@@ -16,6 +18,7 @@ use tokio::{
     signal::unix,
     sync::{broadcast, mpsc},
 };
+use veritech_core::nats_cancel_execution_subject;
 
 use crate::{config::CycloneSpec, Config, FunctionSubscriber, Publisher, PublisherError};
 
@@ -32,6 +35,8 @@ pub enum ServerError {
     CycloneProgress(#[source] Box<dyn std::error::Error + Sync + Send + 'static>),
     #[error("cyclone spec builder error: {0}")]
     CycloneSpec(#[source] Box<dyn std::error::Error + Sync + Send + 'static>),
+    #[error("nats error: {0}")]
+    Nats(#[from] si_data_nats::NatsError),
     #[error("error connecting to nats: {0}")]
     NatsConnect(#[source] si_data_nats::NatsError),
     #[error("no reply mailbox found")]
@@ -283,7 +288,7 @@ async fn resolver_function_request_task(
     let publisher = Publisher::new(&nats, &reply_mailbox);
 
     let function_result =
-        resolver_function_request(&publisher, cyclone_pool, cyclone_request).await;
+        resolver_function_request(&nats, &publisher, cyclone_pool, cyclone_request).await;
 
     if let Err(err) = publisher.finalize_output().await {
         error!(error = ?err, "failed to finalize output by sending final message");
@@ -326,36 +331,25 @@ async fn resolver_function_request_task(
 }
 
 async fn resolver_function_request(
+    nats: &NatsClient,
     publisher: &Publisher<'_>,
     cyclone_pool: Pool<LocalUdsInstanceSpec>,
     cyclone_request: ResolverFunctionRequest,
 ) -> ServerResult<FunctionResult<ResolverFunctionResultSuccess>> {
+    let execution_id = cyclone_request.execution_id.clone();
+    let cancel_subscription = subscribe_to_cancel(nats, &execution_id).await?;
     let mut client = cyclone_pool
         .get()
         .await
         .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
-    let mut progress = client
+    let progress = client
         .execute_resolver(cyclone_request)
         .await?
         .start()
         .await?;
 
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
-
-    let function_result = progress.finish().await?;
+    let function_result =
+        run_to_completion(publisher, &execution_id, cancel_subscription, progress).await?;
 
     Ok(function_result)
 }
@@ -441,33 +435,21 @@ async fn validation_request(
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
+    let execution_id = cyclone_request.execution_id.clone();
+    let cancel_subscription = subscribe_to_cancel(&nats, &execution_id).await?;
     let mut client = cyclone_pool
         .get()
         .await
         .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
-    let mut progress = client
+    let progress = client
         .execute_validation(cyclone_request)
         .await?
         .start()
         .await?;
 
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
+    let function_result =
+        run_to_completion(&publisher, &execution_id, cancel_subscription, progress).await?;
     publisher.finalize_output().await?;
-
-    let function_result = progress.finish().await?;
     publisher.publish_result(&function_result).await?;
 
     Ok(())
@@ -560,34 +542,22 @@ async fn schema_variant_definition_request(
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
+    let execution_id = cyclone_request.execution_id.clone();
+    let cancel_subscription = subscribe_to_cancel(&nats, &execution_id).await?;
     let mut client = cyclone_pool
         .get()
         .await
         .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
 
-    let mut progress = client
+    let progress = client
         .execute_schema_variant_definition(cyclone_request)
         .await?
         .start()
         .await?;
 
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
+    let function_result =
+        run_to_completion(&publisher, &execution_id, cancel_subscription, progress).await?;
     publisher.finalize_output().await?;
-
-    let function_result = progress.finish().await?;
     publisher.publish_result(&function_result).await?;
 
     Ok(())
@@ -674,34 +644,22 @@ async fn action_run_request(
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
+    let execution_id = cyclone_request.execution_id.clone();
+    let cancel_subscription = subscribe_to_cancel(&nats, &execution_id).await?;
     let mut client = cyclone_pool
         .get()
         .await
         .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
 
-    let mut progress = client
+    let progress = client
         .execute_action_run(cyclone_request)
         .await?
         .start()
         .await?;
 
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
-            }
-        }
-    }
+    let function_result =
+        run_to_completion(&publisher, &execution_id, cancel_subscription, progress).await?;
     publisher.finalize_output().await?;
-
-    let function_result = progress.finish().await?;
     publisher.publish_result(&function_result).await?;
 
     Ok(())
@@ -789,37 +747,72 @@ async fn reconciliation_request(
     let reply_mailbox = reply_mailbox.ok_or(ServerError::NoReplyMailboxFound)?;
 
     let publisher = Publisher::new(&nats, &reply_mailbox);
+    let execution_id = cyclone_request.execution_id.clone();
+    let cancel_subscription = subscribe_to_cancel(&nats, &execution_id).await?;
     let mut client = cyclone_pool
         .get()
         .await
         .map_err(|err| ServerError::CyclonePool(Box::new(err)))?;
 
-    let mut progress = client
+    let progress = client
         .execute_reconciliation(cyclone_request)
         .await?
         .start()
         .await?;
 
-    while let Some(msg) = progress.next().await {
-        match msg {
-            Ok(ProgressMessage::OutputStream(output)) => {
-                publisher.publish_output(&output).await?;
-            }
-            Ok(ProgressMessage::Heartbeat) => {
-                trace!("received heartbeat message");
-            }
-            Err(err) => {
-                warn!(error = ?err, "next progress message was an error, bailing out");
-                break;
+    let function_result =
+        run_to_completion(&publisher, &execution_id, cancel_subscription, progress).await?;
+    publisher.finalize_output().await?;
+    publisher.publish_result(&function_result).await?;
+
+    Ok(())
+}
+
+/// Streams a started execution's output to the publisher until cyclone reports a result, asking
+/// cyclone to cancel the execution if a cancel request arrives for it in the meantime.
+async fn run_to_completion<Success>(
+    publisher: &Publisher<'_>,
+    execution_id: &str,
+    mut cancel_subscription: Subscription,
+    mut progress: ExecutionStarted<UnixStream, Success>,
+) -> ServerResult<FunctionResult<Success>>
+where
+    Success: DeserializeOwned + Unpin + std::fmt::Debug,
+    ServerError: From<ExecutionError<Success>>,
+{
+    let mut cancelled = false;
+    loop {
+        tokio::select! {
+            msg = progress.next() => match msg {
+                Some(Ok(ProgressMessage::OutputStream(output))) => {
+                    publisher.publish_output(&output).await?;
+                }
+                Some(Ok(ProgressMessage::Heartbeat)) => {
+                    trace!("received heartbeat message");
+                }
+                Some(Err(err)) => {
+                    warn!(error = ?err, "next progress message was an error, bailing out");
+                    break;
+                }
+                None => break,
+            },
+            // Cyclone reports the cancellation as the function's result, so keep going until then
+            Some(_) = cancel_subscription.next(), if !cancelled => {
+                info!(%execution_id, "cancelling function execution");
+                progress.cancel().await?;
+                cancelled = true;
             }
         }
     }
-    publisher.finalize_output().await?;
+    cancel_subscription.unsubscribe().await?;
 
-    let function_result = progress.finish().await?;
-    publisher.publish_result(&function_result).await?;
+    Ok(progress.finish().await?)
+}
 
-    Ok(())
+/// Subscribes to requests to cancel the given function execution.
+async fn subscribe_to_cancel(nats: &NatsClient, execution_id: &str) -> ServerResult<Subscription> {
+    let subject = nats_cancel_execution_subject(nats.metadata().subject_prefix(), execution_id);
+    nats.subscribe(subject).await.map_err(Into::into)
 }
 
 async fn connect_to_nats(config: &Config) -> ServerResult<NatsClient> {